@@ -0,0 +1,249 @@
+#!/usr/bin/env python3
+# SPDX-License-Identifier: MIT
+# SPDX-FileCopyrightText: 2025 Alexander Minges
+
+"""Build the monochrome laboratory emoji font bundled with ELNPack.
+
+egui's emoji fonts end at Unicode 8, so the laboratory emoji added in Unicode 11 render as
+boxes. This draws them as simple outlines, in the style of the monochrome Noto Emoji egui
+already uses, and writes a TrueType font with only those glyphs.
+
+Usage: scripts/build-lab-emoji-font.py assets/fonts/ELNPack-LabEmoji.ttf
+
+Needs only the Python standard library.
+"""
+
+import math
+import struct
+import sys
+
+UNITS_PER_EM = 1000
+ASCENDER = 880
+DESCENDER = -120
+ADVANCE = 1000
+FAMILY = "ELNPack Lab Emoji"
+POSTSCRIPT_NAME = "ELNPackLabEmoji-Regular"
+VERSION = "Version 1.000"
+COPYRIGHT = "Copyright 2025 Alexander Minges. MIT License."
+
+
+def area(points):
+    """Twice the signed area; negative for clockwise contours with y pointing up."""
+    return sum(x0 * y1 - x1 * y0 for (x0, y0), (x1, y1) in zip(points, points[1:] + points[:1]))
+
+
+def solid(points):
+    """Contour that is filled: clockwise, as TrueType expects outer contours."""
+    return points if area(points) < 0 else points[::-1]
+
+
+def hole(points):
+    """Contour cut out of the solid contours around it."""
+    return points if area(points) > 0 else points[::-1]
+
+
+def ellipse(cx, cy, rx, ry, start=0.0, end=360.0, steps=48):
+    span = end - start
+    count = max(2, round(steps * abs(span) / 360))
+    closed = abs(span) == 360
+    return [
+        (cx + rx * math.cos(math.radians(start + span * i / count)),
+         cy + ry * math.sin(math.radians(start + span * i / count)))
+        for i in range(count if closed else count + 1)
+    ]
+
+
+def circle(cx, cy, r, steps=32):
+    return ellipse(cx, cy, r, r, steps=steps)
+
+
+def rect(x0, y0, x1, y1):
+    return [(x0, y0), (x0, y1), (x1, y1), (x1, y0)]
+
+
+def stroke(line, width):
+    """Outline of a polyline drawn with the given width and flat ends."""
+    left, right = [], []
+    for i, (x, y) in enumerate(line):
+        (ax, ay), (bx, by) = line[max(i - 1, 0)], line[min(i + 1, len(line) - 1)]
+        length = math.hypot(bx - ax, by - ay)
+        nx, ny = -(by - ay) / length * width / 2, (bx - ax) / length * width / 2
+        left.append((x + nx, y + ny))
+        right.append((x - nx, y - ny))
+    return left + right[::-1]
+
+
+def rotate(contours, degrees, cx, cy):
+    a = math.radians(degrees)
+    cos, sin = math.cos(a), math.sin(a)
+    return [
+        [(cx + (x - cx) * cos - (y - cy) * sin, cy + (x - cx) * sin + (y - cy) * cos) for x, y in c]
+        for c in contours
+    ]
+
+
+def test_tube():
+    """U+1F9EA TEST TUBE: a tilted tube, filled with liquid up to half its height."""
+    body = ellipse(500, 130, 110, 110, 180, 360) + [(610, 720), (390, 720)]
+    contours = [
+        solid(body),
+        solid(rect(350, 700, 650, 780)),
+        hole([(445, 430), (445, 700), (555, 700), (555, 430)]),
+        hole(circle(470, 230, 26)),
+        hole(circle(530, 330, 18)),
+    ]
+    return rotate(contours, -40, 500, 390)
+
+
+def petri_dish():
+    """U+1F9EB PETRI DISH: a shallow dish seen from above at an angle, with colonies."""
+    outline = ellipse(500, 340, 440, 230, 0, 180) + ellipse(500, 250, 440, 230, 180, 360)
+    return [
+        solid(outline),
+        hole(ellipse(500, 340, 380, 180)),
+        solid(circle(390, 320, 62)),
+        solid(circle(580, 410, 46)),
+        solid(circle(600, 270, 36)),
+        solid(circle(460, 440, 28)),
+    ]
+
+
+def dna():
+    """U+1F9EC DNA: two strands of a double helix joined by base pairs."""
+    amplitude, period, top, bottom = 230, 540, 800, -20
+    ys = [bottom + (top - bottom) * i / 64 for i in range(65)]
+    phase = lambda y: math.sin(2 * math.pi * (y - bottom) / period)
+    contours = [
+        solid(stroke([(500 + amplitude * phase(y), y) for y in ys], 80)),
+        solid(stroke([(500 - amplitude * phase(y), y) for y in ys], 80)),
+    ]
+    for i in range(1, 12):
+        y = bottom + (top - bottom) * i / 12
+        offset = amplitude * phase(y)
+        if abs(offset) > 0.45 * amplitude:
+            contours.append(solid(rect(500 - abs(offset), y - 20, 500 + abs(offset), y + 20)))
+    return contours
+
+
+# Code point and outline of every glyph after `.notdef`, in glyph id order.
+GLYPHS = [(0x1F9EA, test_tube()), (0x1F9EB, petri_dish()), (0x1F9EC, dna())]
+
+
+def encode_glyph(contours):
+    """`glyf` entry with only on-curve points; the bounding box follows."""
+    contours = [[(round(x), round(y)) for x, y in c] for c in contours]
+    points = [p for c in contours for p in c]
+    xs, ys = [x for x, _ in points], [y for _, y in points]
+    bbox = (min(xs), min(ys), max(xs), max(ys))
+    data = struct.pack(">h4h", len(contours), *bbox)
+    end = -1
+    for c in contours:
+        end += len(c)
+        data += struct.pack(">H", end)
+    data += struct.pack(">H", 0) + bytes([0x01] * len(points))
+    for axis in (0, 1):
+        previous = 0
+        for p in points:
+            data += struct.pack(">h", p[axis] - previous)
+            previous = p[axis]
+    return data + b"\0" * (-len(data) % 4), bbox, len(points), len(contours)
+
+
+def name_table():
+    records = {0: COPYRIGHT, 1: FAMILY, 2: "Regular", 3: f"{VERSION};{POSTSCRIPT_NAME}",
+               4: FAMILY, 5: VERSION, 6: POSTSCRIPT_NAME, 13: "MIT License"}
+    strings, entries = b"", b""
+    for name_id, text in records.items():
+        encoded = text.encode("utf-16-be")
+        entries += struct.pack(">6H", 3, 1, 0x409, name_id, len(encoded), len(strings))
+        strings += encoded
+    return struct.pack(">3H", 0, len(records), 6 + len(entries)) + entries + strings
+
+
+def cmap_table():
+    # An empty format 4 subtable for the BMP and a format 12 subtable with the emoji.
+    fmt4 = struct.pack(">7H", 4, 24, 0, 2, 2, 0, 0) + struct.pack(">4H", 0xFFFF, 0, 0xFFFF, 1)
+    fmt4 += struct.pack(">H", 0)
+    groups = b"".join(struct.pack(">3I", code, code, glyph + 1) for glyph, (code, _) in enumerate(GLYPHS))
+    fmt12 = struct.pack(">HHIII", 12, 0, 16 + len(groups), 0, len(GLYPHS)) + groups
+    header = struct.pack(">HH", 0, 2)
+    header += struct.pack(">HHI", 3, 1, 4 + 16) + struct.pack(">HHI", 3, 10, 4 + 16 + len(fmt4))
+    return header + fmt4 + fmt12
+
+
+def checksum(data):
+    data += b"\0" * (-len(data) % 4)
+    return sum(struct.unpack(f">{len(data) // 4}I", data)) & 0xFFFFFFFF
+
+
+def write_font(tables):
+    tags = sorted(tables)
+    count = len(tags)
+    search = 1
+    while search * 2 <= count:
+        search *= 2
+    header = struct.pack(">IHHHH", 0x00010000, count, 16 * search, search.bit_length() - 1, 16 * (count - search))
+    offset = 12 + 16 * count
+    directory, body = b"", b""
+    for tag in tags:
+        data = tables[tag]
+        if tag == b"head":
+            head_offset = offset + len(body)
+        directory += struct.pack(">4sIII", tag, checksum(data), offset + len(body), len(data))
+        body += data + b"\0" * (-len(data) % 4)
+    font = bytearray(header + directory + body)
+    adjustment = (0xB1B0AFBA - checksum(bytes(font))) & 0xFFFFFFFF
+    struct.pack_into(">I", font, head_offset + 8, adjustment)
+    return bytes(font)
+
+
+def main(target):
+    glyf, loca, hmtx = b"", [0], struct.pack(">Hh", ADVANCE, 0)
+    boxes, max_points, max_contours = [], 0, 0
+    for _, contours in GLYPHS:
+        data, bbox, points, count = encode_glyph(contours)
+        glyf += data
+        loca.append(len(glyf))
+        hmtx += struct.pack(">Hh", ADVANCE, bbox[0])
+        boxes.append(bbox)
+        max_points, max_contours = max(max_points, points), max(max_contours, count)
+    loca.insert(1, 0)  # `.notdef` is empty.
+    num_glyphs = len(GLYPHS) + 1
+    x_min, y_min = min(b[0] for b in boxes), min(b[1] for b in boxes)
+    x_max, y_max = max(b[2] for b in boxes), max(b[3] for b in boxes)
+
+    head = struct.pack(">IIIIHHQQ4hHhhhh", 0x00010000, 0x00010000, 0, 0x5F0F3CF5, 0x000B,
+                       UNITS_PER_EM, 0, 0, x_min, y_min, x_max, y_max, 0, 8, 2, 1, 0)
+    hhea = struct.pack(">I3hH3h3h4hhH", 0x00010000, ASCENDER, DESCENDER, 0, ADVANCE,
+                       x_min, min(ADVANCE - b[2] for b in boxes), x_max, 1, 0, 0, 0, 0, 0, 0, 0,
+                       num_glyphs)
+    maxp = struct.pack(">I14H", 0x00010000, num_glyphs, max_points, max_contours,
+                       0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0)
+    os2 = struct.pack(">HhHHH10hh", 4, ADVANCE, 400, 5, 0,
+                      650, 600, 0, 75, 650, 600, 0, 350, 50, 250, 0)
+    os2 += bytes(10) + struct.pack(">4I", 0, 1 << 25, 0, 0) + b"NONE"
+    os2 += struct.pack(">3H5h2I5H", 0x40, 0xFFFF, 0xFFFF, ASCENDER, DESCENDER, 0,
+                       ASCENDER, -DESCENDER, 1, 0, 0, 0, 0, 32, 0)
+    post = struct.pack(">IIhhIIIII", 0x00030000, 0, -100, 50, 0, 0, 0, 0, 0)
+
+    tables = {
+        b"cmap": cmap_table(),
+        b"glyf": glyf,
+        b"head": head,
+        b"hhea": hhea,
+        b"hmtx": hmtx,
+        b"loca": struct.pack(f">{len(loca)}I", *loca),
+        b"maxp": maxp,
+        b"name": name_table(),
+        b"OS/2": os2,
+        b"post": post,
+    }
+    with open(target, "wb") as f:
+        f.write(write_font(tables))
+    print(f"{target}: {len(GLYPHS)} emoji")
+
+
+if __name__ == "__main__":
+    if len(sys.argv) != 2:
+        sys.exit(f"Usage: {sys.argv[0]} assets/fonts/ELNPack-LabEmoji.ttf")
+    main(sys.argv[1])
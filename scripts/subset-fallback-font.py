@@ -0,0 +1,219 @@
+#!/usr/bin/env python3
+# SPDX-License-Identifier: MIT
+# SPDX-FileCopyrightText: 2025 Alexander Minges
+
+"""Subset DejaVu Sans to the symbols ELNPack needs from its fallback font.
+
+The default egui fonts already cover Latin text; the fallback font only has to supply the
+symbols they lack. This keeps the outlines of the characters in KEEP, maps only those in the
+character map, and drops the layout and name-list tables egui does not read. Glyph ids stay
+unchanged, so the remaining tables need no renumbering.
+
+Usage: scripts/subset-fallback-font.py DejaVuSans.ttf assets/fonts/DejaVuSans-Symbols.ttf
+
+Needs only the Python standard library; the input is DejaVuSans.ttf from DejaVu 2.37.
+"""
+
+import struct
+import sys
+
+# Unicode blocks kept in the subset.
+KEEP = [
+    (0x0020, 0x024F),  # Basic Latin to Latin Extended-B, e.g. µ ± ° ×
+    (0x0370, 0x03FF),  # Greek and Coptic
+    (0x2000, 0x23FF),  # Punctuation, super/subscripts, units, arrows, math, technical
+    (0x2460, 0x24FF),  # Enclosed Alphanumerics
+    (0x25A0, 0x27FF),  # Geometric Shapes, Miscellaneous Symbols, Dingbats, e.g. ✕ ✓
+    (0x2900, 0x2AFF),  # Supplemental Arrows-B, Supplemental Mathematical Operators
+]
+
+# Tables egui does not read; the glyph names in `post` are dropped separately.
+DROPPED_TABLES = {b"GSUB", b"GPOS", b"GDEF", b"kern", b"hdmx", b"LTSH", b"VDMX", b"FFTM"}
+
+# Composite glyph flags.
+ARG_1_AND_2_ARE_WORDS = 0x0001
+WE_HAVE_A_SCALE = 0x0008
+MORE_COMPONENTS = 0x0020
+WE_HAVE_AN_X_AND_Y_SCALE = 0x0040
+WE_HAVE_A_TWO_BY_TWO = 0x0080
+
+
+def read_tables(font):
+    (num_tables,) = struct.unpack_from(">H", font, 4)
+    tables = {}
+    for i in range(num_tables):
+        tag, _, offset, length = struct.unpack_from(">4sIII", font, 12 + 16 * i)
+        tables[tag] = font[offset : offset + length]
+    return tables
+
+
+def read_cmap(cmap):
+    """Code point to glyph id map of the format 4 and format 12 subtables."""
+    mapping = {}
+    (count,) = struct.unpack_from(">H", cmap, 2)
+    for i in range(count):
+        _, _, offset = struct.unpack_from(">HHI", cmap, 4 + 8 * i)
+        (fmt,) = struct.unpack_from(">H", cmap, offset)
+        if fmt == 4:
+            (seg_x2,) = struct.unpack_from(">H", cmap, offset + 6)
+            ends = offset + 14
+            starts = ends + seg_x2 + 2
+            deltas = starts + seg_x2
+            range_offsets = deltas + seg_x2
+            for s in range(seg_x2 // 2):
+                (end,) = struct.unpack_from(">H", cmap, ends + 2 * s)
+                (start,) = struct.unpack_from(">H", cmap, starts + 2 * s)
+                (delta,) = struct.unpack_from(">h", cmap, deltas + 2 * s)
+                at = range_offsets + 2 * s
+                (range_offset,) = struct.unpack_from(">H", cmap, at)
+                for code in range(start, min(end, 0xFFFE) + 1):
+                    if range_offset == 0:
+                        glyph = (code + delta) & 0xFFFF
+                    else:
+                        (glyph,) = struct.unpack_from(">H", cmap, at + range_offset + 2 * (code - start))
+                        glyph = (glyph + delta) & 0xFFFF if glyph else 0
+                    if glyph:
+                        mapping.setdefault(code, glyph)
+        elif fmt == 12:
+            (groups,) = struct.unpack_from(">I", cmap, offset + 12)
+            for g in range(groups):
+                start, end, glyph = struct.unpack_from(">III", cmap, offset + 16 + 12 * g)
+                for code in range(start, end + 1):
+                    mapping.setdefault(code, glyph + code - start)
+    return mapping
+
+
+def glyph_data(glyf, loca, long_loca, glyph):
+    if long_loca:
+        start, end = struct.unpack_from(">II", loca, 4 * glyph)
+    else:
+        start, end = (2 * v for v in struct.unpack_from(">HH", loca, 2 * glyph))
+    return glyf[start:end]
+
+
+def components(data):
+    """Glyph ids a composite glyph is built from."""
+    if len(data) < 10 or struct.unpack_from(">h", data, 0)[0] >= 0:
+        return []
+    found = []
+    at = 10
+    while True:
+        flags, glyph = struct.unpack_from(">HH", data, at)
+        found.append(glyph)
+        at += 4 + (4 if flags & ARG_1_AND_2_ARE_WORDS else 2)
+        if flags & WE_HAVE_A_SCALE:
+            at += 2
+        elif flags & WE_HAVE_AN_X_AND_Y_SCALE:
+            at += 4
+        elif flags & WE_HAVE_A_TWO_BY_TWO:
+            at += 8
+        if not flags & MORE_COMPONENTS:
+            return found
+
+
+def build_cmap(mapping):
+    """cmap with one format 4 subtable for the Unicode and Windows platforms."""
+    codes = sorted(c for c in mapping if c < 0xFFFF)
+    segments = []
+    for code in codes:
+        if segments and segments[-1][1] == code - 1:
+            segments[-1][1] = code
+        else:
+            segments.append([code, code])
+    segments.append([0xFFFF, 0xFFFF])
+    count = len(segments)
+    ends, starts, deltas, range_offsets, glyph_ids = [], [], [], [], []
+    for s, (start, end) in enumerate(segments):
+        ends.append(end)
+        starts.append(start)
+        if start == 0xFFFF:
+            deltas.append(1)
+            range_offsets.append(0)
+            continue
+        deltas.append(0)
+        range_offsets.append(2 * (count - s + len(glyph_ids)))
+        glyph_ids.extend(mapping[c] for c in range(start, end + 1))
+    search = 1
+    while search * 2 <= count:
+        search *= 2
+    body = struct.pack(">HHHH", 2 * count, 2 * search, search.bit_length() - 1, 2 * (count - search))
+    body += struct.pack(f">{count}H", *ends) + b"\0\0" + struct.pack(f">{count}H", *starts)
+    body += struct.pack(f">{count}h", *deltas) + struct.pack(f">{count}H", *range_offsets)
+    body += struct.pack(f">{len(glyph_ids)}H", *glyph_ids)
+    subtable = struct.pack(">HHH", 4, 6 + len(body), 0) + body
+    return struct.pack(">HHHHIHHI", 0, 2, 0, 3, 20, 3, 1, 20) + subtable
+
+
+def checksum(data):
+    data += b"\0" * (-len(data) % 4)
+    return sum(struct.unpack(f">{len(data) // 4}I", data)) & 0xFFFFFFFF
+
+
+def write_font(tables):
+    tags = sorted(tables)
+    count = len(tags)
+    search = 1
+    while search * 2 <= count:
+        search *= 2
+    header = struct.pack(">IHHHH", 0x00010000, count, 16 * search, search.bit_length() - 1, 16 * (count - search))
+    offset = 12 + 16 * count
+    directory, body = b"", b""
+    for tag in tags:
+        data = tables[tag]
+        if tag == b"head":
+            head_offset = offset + len(body)
+        directory += struct.pack(">4sIII", tag, checksum(data), offset + len(body), len(data))
+        body += data + b"\0" * (-len(data) % 4)
+    font = bytearray(header + directory + body)
+    adjustment = (0xB1B0AFBA - checksum(bytes(font))) & 0xFFFFFFFF
+    struct.pack_into(">I", font, head_offset + 8, adjustment)
+    return bytes(font)
+
+
+def main(source, target):
+    with open(source, "rb") as f:
+        tables = read_tables(f.read())
+    long_loca = struct.unpack_from(">h", tables[b"head"], 50)[0] == 1
+    (num_glyphs,) = struct.unpack_from(">H", tables[b"maxp"], 4)
+    mapping = {
+        code: glyph
+        for code, glyph in read_cmap(tables[b"cmap"]).items()
+        if any(start <= code <= end for start, end in KEEP)
+    }
+
+    kept = {0} | set(mapping.values())
+    pending = list(kept)
+    while pending:
+        data = glyph_data(tables[b"glyf"], tables[b"loca"], long_loca, pending.pop())
+        for glyph in components(data):
+            if glyph not in kept:
+                kept.add(glyph)
+                pending.append(glyph)
+
+    glyf, offsets = b"", []
+    for glyph in range(num_glyphs):
+        offsets.append(len(glyf))
+        if glyph in kept:
+            data = glyph_data(tables[b"glyf"], tables[b"loca"], long_loca, glyph)
+            glyf += data + b"\0" * (-len(data) % 4)
+    offsets.append(len(glyf))
+
+    tables = {tag: data for tag, data in tables.items() if tag not in DROPPED_TABLES}
+    tables[b"glyf"] = glyf
+    tables[b"loca"] = struct.pack(f">{len(offsets)}I", *offsets)
+    head = bytearray(tables[b"head"])
+    struct.pack_into(">h", head, 50, 1)
+    struct.pack_into(">I", head, 8, 0)
+    tables[b"head"] = bytes(head)
+    tables[b"cmap"] = build_cmap(mapping)
+    tables[b"post"] = struct.pack(">I", 0x00030000) + tables[b"post"][4:32]
+
+    with open(target, "wb") as f:
+        f.write(write_font(tables))
+    print(f"{target}: {len(mapping)} characters, {len(kept)} of {num_glyphs} glyphs")
+
+
+if __name__ == "__main__":
+    if len(sys.argv) != 3:
+        sys.exit(f"Usage: {sys.argv[0]} DejaVuSans.ttf assets/fonts/DejaVuSans-Symbols.ttf")
+    main(sys.argv[1], sys.argv[2])
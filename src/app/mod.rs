@@ -3,12 +3,15 @@
 
 //! Application entry point wiring egui/eframe to launch the ELNPack UI.
 
+use crate::logic::settings::WindowSettings;
 use crate::ui::ElnPackApp;
 use eframe::egui;
-use egui_phosphor::Variant;
 
 /// Bootstrap the desktop application and run the main egui event loop.
 ///
+/// With `debug_events`, recent messages are recorded for export from the Help menu. With
+/// `safe_mode`, no saved settings, draft, templates or lists are loaded.
+///
 /// # Errors
 ///
 /// Propagates any failure from `eframe::run_native`, such as window creation errors.
@@ -17,18 +20,28 @@ use egui_phosphor::Variant;
 ///
 /// ```rust,ignore
 /// fn main() -> eframe::Result<()> {
-///     elnpack::app::run()
+///     elnpack::app::run(false, false)
 /// }
 /// ```
-pub fn run() -> eframe::Result<()> {
-    // Register Phosphor icon font.
-    let mut fonts = egui::FontDefinitions::default();
-    egui_phosphor::add_to_fonts(&mut fonts, Variant::Regular);
+pub fn run(debug_events: bool, safe_mode: bool) -> eframe::Result<()> {
+    // Register the Phosphor icon font and the bundled fallback font.
+    let fonts = crate::ui::fonts::definitions();
+
+    // Restore the last window geometry; unreadable settings are reported once the UI runs.
+    let paths = crate::utils::storage::AppPaths::platform();
+    let window = paths
+        .settings
+        .as_deref()
+        .filter(|_| !safe_mode)
+        .and_then(|path| crate::logic::settings::load_settings(path).ok())
+        .map(|settings| settings.window)
+        .unwrap_or_default();
 
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            .with_inner_size([1024.0, 768.0])
-            .with_min_inner_size([600.0, 400.0]),
+            .with_inner_size(window.inner_size())
+            .with_min_inner_size(WindowSettings::MIN_SIZE.map(|v| v as f32))
+            .with_maximized(window.maximized),
         ..Default::default()
     };
 
@@ -37,7 +50,11 @@ pub fn run() -> eframe::Result<()> {
         options,
         Box::new(|cc| {
             cc.egui_ctx.set_fonts(fonts);
-            Ok(Box::new(ElnPackApp::default()))
+            let mut app = ElnPackApp::new(paths, safe_mode);
+            if debug_events {
+                app.enable_event_trace();
+            }
+            Ok(Box::new(app))
         }),
     )
 }
@@ -0,0 +1,329 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Headless command line interface for building archives from scripts.
+//!
+//! `elnpack pack` assembles one entry from files on disk and writes it with the same
+//! validation and archive writer the desktop app uses. `elnpack verify-signature` checks an
+//! archive against the `.sig` file written when signing is enabled. Without a subcommand the
+//! desktop app starts; `--debug-events` makes it record recent actions for bug reports and
+//! `--safe-mode` starts it without loading any saved state.
+//!
+//! # Exit codes
+//!
+//! - `0`: the archive was written, or its signature is valid and made with the key given
+//!   by `--key`; the archive path or the signer's fingerprint is printed to stdout.
+//! - `1`: reading an input or writing the archive failed, or the signature does not match.
+//! - `2`: invalid usage or input, e.g. an empty title or duplicate attachment names.
+//! - `3`: the signature is valid, but no `--key` was given to say whose key to trust.
+
+use std::ffi::OsString;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use anyhow::{Context, anyhow};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::logic::batch::hashed_attachment;
+use crate::logic::eln::{
+    ArchiveEntry, ArchiveGenre, BodyFormat, ExportOptions, build_and_write_archive,
+    ensure_extension,
+};
+use crate::logic::language::normalize_language_tag;
+use crate::logic::signing::{self, Signer, signature_path};
+use crate::models::attachment::Attachment;
+use crate::models::extra_fields::parse_elabftw_extra_fields;
+use crate::models::keywords::Keywords;
+use crate::models::license::License;
+use crate::utils::folder_scan::{MAX_FOLDER_FILES, scan_folder};
+use crate::utils::sanitize_component::sanitize_folder;
+
+/// Exit code for failed reads or writes.
+const EXIT_FAILED: u8 = 1;
+/// Exit code for invalid input; clap uses the same code for usage errors.
+const EXIT_INVALID: u8 = 2;
+/// Exit code for a valid signature by a key the caller did not vouch for.
+const EXIT_UNTRUSTED: u8 = 3;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "elnpack",
+    version,
+    about = "Package ELN entries as .eln archives"
+)]
+struct Cli {
+    /// Record recent actions in the desktop app for export via Help → Export event trace.
+    #[arg(long)]
+    debug_events: bool,
+    /// Start the desktop app with default settings, without loading saved settings, the
+    /// draft, templates or lists.
+    #[arg(long)]
+    safe_mode: bool,
+    #[command(subcommand)]
+    command: Option<CliCommand>,
+}
+
+#[derive(Debug, Subcommand)]
+enum CliCommand {
+    /// Build an .eln archive with a single entry without opening the window.
+    Pack(PackArgs),
+    /// Check an .eln archive against its detached SSH signature.
+    VerifySignature(VerifyArgs),
+}
+
+#[derive(Debug, Args)]
+struct PackArgs {
+    /// Entry title.
+    #[arg(long)]
+    title: String,
+    /// Markdown file with the main text; `-` reads standard input.
+    #[arg(long, value_name = "FILE")]
+    body: Option<PathBuf>,
+    /// File or folder to attach; folders keep their structure. Repeatable.
+    #[arg(long, value_name = "PATH")]
+    attach: Vec<PathBuf>,
+    /// Keyword to add. Repeatable.
+    #[arg(long)]
+    keyword: Vec<String>,
+    /// eLabFTW metadata JSON with extra fields.
+    #[arg(long, value_name = "FILE")]
+    metadata: Option<PathBuf>,
+    /// When the entry was performed, as RFC 3339 (e.g. 2025-03-14T09:30:00+01:00); defaults to now.
+    #[arg(long, value_name = "DATETIME", value_parser = parse_rfc3339)]
+    performed_at: Option<OffsetDateTime>,
+    /// Entry type.
+    #[arg(long, value_enum, default_value_t = GenreArg::Experiment)]
+    genre: GenreArg,
+    /// Language of the main text (e.g. de, en-GB); detected from the text when omitted.
+    #[arg(long, value_name = "TAG", value_parser = parse_language)]
+    language: Option<String>,
+    /// How the main text is stored; `both` adds the markdown as body.md to the HTML.
+    #[arg(long, value_enum, default_value_t = FormatArg::Html)]
+    format: FormatArg,
+    /// Archive to write; `.eln` is appended when missing.
+    #[arg(long, short, value_name = "FILE")]
+    output: PathBuf,
+    /// Replace the output archive if it already exists.
+    #[arg(long)]
+    force: bool,
+}
+
+#[derive(Debug, Args)]
+struct VerifyArgs {
+    /// Signed archive.
+    archive: PathBuf,
+    /// Signature file; defaults to the archive path with `.sig` appended.
+    #[arg(long, value_name = "FILE")]
+    signature: Option<PathBuf>,
+    /// OpenSSH public key the archive must be signed with, e.g. `elnpack_ed25519.pub`.
+    /// Without it, a valid signature is reported as untrusted with exit code 3, since
+    /// anyone could have signed an altered copy with their own key.
+    #[arg(long, value_name = "FILE")]
+    key: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum GenreArg {
+    Experiment,
+    Resource,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum FormatArg {
+    Html,
+    Markdown,
+    Both,
+}
+
+/// Why a command failed, deciding the exit code.
+enum CliError {
+    /// The input is unusable; fixing the command line fixes it.
+    Invalid(String),
+    /// An input could not be read or the archive could not be written.
+    Failed(anyhow::Error),
+    /// The signature is valid, but its key was not given as trusted.
+    Untrusted(String),
+}
+
+impl From<anyhow::Error> for CliError {
+    fn from(err: anyhow::Error) -> Self {
+        Self::Failed(err)
+    }
+}
+
+/// Parse `args` (including the program name) and run the requested command, or the
+/// desktop app when none is given.
+///
+/// Usage errors, `--help` and `--version` are printed by clap, which exits on its own.
+pub fn run(args: impl IntoIterator<Item = OsString>) -> ExitCode {
+    let cli = Cli::parse_from(args);
+    let result = match cli.command {
+        Some(CliCommand::Pack(args)) => pack(args).map(|output| output.display().to_string()),
+        Some(CliCommand::VerifySignature(args)) => verify_signature(args),
+        None => {
+            return match crate::app::run(cli.debug_events, cli.safe_mode) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(err) => {
+                    eprintln!("elnpack: {err}");
+                    ExitCode::from(EXIT_FAILED)
+                }
+            };
+        }
+    };
+    match result {
+        Ok(output) => {
+            println!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(CliError::Invalid(message)) => {
+            eprintln!("elnpack: {message}");
+            ExitCode::from(EXIT_INVALID)
+        }
+        Err(CliError::Failed(err)) => {
+            eprintln!("elnpack: {err:#}");
+            ExitCode::from(EXIT_FAILED)
+        }
+        Err(CliError::Untrusted(message)) => {
+            eprintln!("elnpack: {message}");
+            ExitCode::from(EXIT_UNTRUSTED)
+        }
+    }
+}
+
+/// Build and write the archive described by `args`, returning the written path.
+fn pack(args: PackArgs) -> Result<PathBuf, CliError> {
+    let output = ensure_extension(args.output, "eln");
+    if output.exists() && !args.force {
+        return Err(CliError::Invalid(format!(
+            "{} already exists; pass --force to replace it.",
+            output.display()
+        )));
+    }
+
+    let body = match &args.body {
+        Some(path) => read_body(path)?,
+        None => String::new(),
+    };
+
+    let (mut extra_fields, extra_groups) = match &args.metadata {
+        Some(path) => {
+            let json = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read metadata file {:?}", path))?;
+            let import = parse_elabftw_extra_fields(&json)
+                .map_err(|err| CliError::Invalid(format!("{}: {err:#}", path.display())))?;
+            (import.fields, import.groups)
+        }
+        None => (Vec::new(), Vec::new()),
+    };
+    extra_fields.sort_by(|a, b| a.cmp_key().cmp(&b.cmp_key()));
+
+    let mut attachments = Vec::new();
+    for path in &args.attach {
+        collect_attachments(path, &mut attachments)?;
+    }
+
+    let entry = ArchiveEntry {
+        title: args.title.trim().to_string(),
+        body: body.trim().to_string(),
+        attachments,
+        extra_fields,
+        extra_groups,
+        performed_at: args.performed_at.unwrap_or_else(OffsetDateTime::now_utc),
+        genre: match args.genre {
+            GenreArg::Experiment => ArchiveGenre::Experiment,
+            GenreArg::Resource => ArchiveGenre::Resource,
+        },
+        keywords: Keywords::new(args.keyword).into_vec(),
+        language: args.language,
+        related_links: Vec::new(),
+        imported_metadata: None,
+        license: License::None,
+        summary: String::new(),
+        hide_main_text: false,
+    };
+    entry
+        .validate(OffsetDateTime::now_utc())
+        .map_err(|err| CliError::Invalid(err.to_string()))?;
+
+    let body_format = match args.format {
+        FormatArg::Html => BodyFormat::Html,
+        FormatArg::Markdown => BodyFormat::Markdown,
+        FormatArg::Both => BodyFormat::Both,
+    };
+    build_and_write_archive(&output, &[entry], body_format, &ExportOptions::default())?;
+    Ok(output)
+}
+
+/// Check `args.archive` against its signature, returning the trusted signer's fingerprint.
+fn verify_signature(args: VerifyArgs) -> Result<String, CliError> {
+    let trusted = args
+        .key
+        .as_deref()
+        .map(|path| {
+            ssh_key::PublicKey::read_openssh_file(path)
+                .map_err(|err| anyhow!("Failed to read public key {:?}: {err}", path))
+        })
+        .transpose()?;
+    let signature = args
+        .signature
+        .unwrap_or_else(|| signature_path(&args.archive));
+    match signing::verify_archive(&args.archive, &signature, trusted.as_ref())? {
+        Signer::Trusted(fingerprint) => Ok(format!("Good signature by {fingerprint}")),
+        Signer::Untrusted(fingerprint) => Err(CliError::Untrusted(format!(
+            "Valid signature by untrusted key {fingerprint}; pass --key with the signer's public key to check who signed the archive."
+        ))),
+    }
+}
+
+/// Read the main text from `path`, or from standard input for `-`.
+fn read_body(path: &Path) -> anyhow::Result<String> {
+    if path == Path::new("-") {
+        let mut body = String::new();
+        std::io::stdin()
+            .read_to_string(&mut body)
+            .context("Failed to read main text from standard input")?;
+        return Ok(body);
+    }
+    fs::read_to_string(path).with_context(|| format!("Failed to read main text {:?}", path))
+}
+
+/// Hash `path` (a file, or every file below a folder) and append it to `attachments`.
+fn collect_attachments(path: &Path, attachments: &mut Vec<Attachment>) -> Result<(), CliError> {
+    if !path.is_dir() {
+        attachments.push(hashed_attachment(path, String::new())?);
+        return Ok(());
+    }
+    let scan = scan_folder(path, MAX_FOLDER_FILES)?;
+    if scan.truncated {
+        return Err(CliError::Invalid(format!(
+            "Folder {} contains more than {MAX_FOLDER_FILES} files.",
+            path.display()
+        )));
+    }
+    for file in scan.files {
+        attachments.push(hashed_attachment(
+            &file.path,
+            sanitize_folder(&file.relative_dir),
+        )?);
+    }
+    Ok(())
+}
+
+/// Parse an RFC 3339 timestamp for `--performed-at`.
+fn parse_language(value: &str) -> Result<String, String> {
+    match normalize_language_tag(value) {
+        Ok(Some(tag)) => Ok(tag),
+        Ok(None) => Err("expected a language tag like de or en-GB".into()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn parse_rfc3339(value: &str) -> Result<OffsetDateTime, String> {
+    OffsetDateTime::parse(value, &Rfc3339).map_err(|_| {
+        format!("expected an RFC 3339 timestamp like 2025-03-14T09:30:00Z, got '{value}'")
+    })
+}
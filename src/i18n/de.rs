@@ -0,0 +1,1735 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! German texts.
+
+pub(super) const DE: &[(&str, &str)] = &[
+    ("app.heading", "ELN-Eintrag"),
+    ("top.language.hover", "Sprache der Benutzeroberfläche"),
+    (
+        "top.compact.hover",
+        "Kompakte Ansicht für kleine Bildschirme",
+    ),
+    ("help.menu", "Hilfe"),
+    ("help.menu.hover", "Benutzerhandbuch und Ersteinrichtung"),
+    ("help.guide", "Benutzerhandbuch"),
+    (
+        "help.guide.hover",
+        "Das ELNPack-Benutzerhandbuch im Browser öffnen",
+    ),
+    ("help.setup", "Ersteinrichtung…"),
+    (
+        "help.setup.hover",
+        "Name, eLabFTW-Adresse und Vorgaben für Einträge erneut festlegen",
+    ),
+    ("help.trace", "Ereignisprotokoll exportieren…"),
+    (
+        "help.trace.hover",
+        "Letzte Aktionen für einen Fehlerbericht speichern; eingegebener Text wird auf seine Länge reduziert",
+    ),
+    ("help.trace.dialog", "Ereignisprotokoll exportieren"),
+    ("settings.button", "Einstellungen"),
+    (
+        "settings.button.hover",
+        "Erweiterte Exportoptionen einstellen",
+    ),
+    ("top.preview", "HTML-Vorschau"),
+    (
+        "top.preview.hover",
+        "Zusätzlich eine eigenständige index.html-Vorschau in das Archiv schreiben",
+    ),
+    ("top.upload", "Hochladen"),
+    (
+        "top.upload.hover",
+        "Das nächste gespeicherte Archiv in eLabFTW importieren; abwählen, um nur diesen Speichervorgang auszulassen",
+    ),
+    (
+        "top.crate_version.hover",
+        "Gespeicherte Archive verwenden {version}; für eLabFTW 4.x und ältere Werkzeuge RO-Crate 1.1 wählen",
+    ),
+    (
+        "top.compression.hover",
+        "Kompression des nächsten Speichervorgangs; Auto speichert Bilder, Videos und Archive unkomprimiert. Die Voreinstellung steht unter Einstellungen → Compression",
+    ),
+    ("top.templates", "Vorlagen"),
+    ("top.open", "Öffnen"),
+    (
+        "top.open.hover",
+        "Ein vorhandenes ELN-Archiv zum Bearbeiten öffnen",
+    ),
+    ("top.open.dialog", "ELN-Archiv öffnen"),
+    ("top.batch", "Stapel"),
+    (
+        "top.batch.hover",
+        "Ein Archiv je Unterordner mit einer body.md schreiben",
+    ),
+    (
+        "format.markdown.hover",
+        "Das unveränderte Markdown in den Archiv-Metadaten speichern",
+    ),
+    (
+        "format.html.hover",
+        "Markdown in den Archiv-Metadaten in HTML umwandeln",
+    ),
+    ("format.both", "Beides"),
+    (
+        "format.both.hover",
+        "Markdown in den Archiv-Metadaten in HTML umwandeln und das Markdown als body.md behalten",
+    ),
+    ("format.label", "Exportieren als"),
+    ("save.button", "ELN-Archiv speichern"),
+    ("save.button.hover", "Den Eintrag als ELN-Archiv speichern"),
+    (
+        "save.disabled_hint",
+        "Bitte einen Titel eingeben, Pflichtfelder und ungültige Felder korrigieren und warten, bis die Anhänge verarbeitet sind",
+    ),
+    ("save.cancel", "Speichern abbrechen"),
+    (
+        "save.cancel.hover",
+        "Das Schreiben des Archivs beenden und die unvollständige Datei löschen",
+    ),
+    ("save.save_as", "Speichern unter…"),
+    (
+        "save.save_as.hover",
+        "Den Eintrag unter einem neuen Namen als ELN-Archiv speichern",
+    ),
+    ("save.save", "Speichern"),
+    ("save.save.hover", "{path} überschreiben"),
+    (
+        "recent.heading",
+        "Über ein zuletzt gespeichertes Archiv speichern",
+    ),
+    ("recent.missing", "Dieses Archiv existiert nicht mehr."),
+    ("recent.remove_missing", "Fehlende Archive entfernen"),
+    ("recent.hover", "Zuletzt gespeicherte Archive"),
+    ("entries.heading", "Einträge"),
+    ("entries.add.hover", "Einen leeren Eintrag hinzufügen"),
+    (
+        "entries.duplicate.hover",
+        "Den ausgewählten Eintrag duplizieren",
+    ),
+    ("entries.remove.hover", "Den ausgewählten Eintrag entfernen"),
+    ("entries.copy_title", "{title} (Kopie)"),
+    ("entries.busy", "Warten, bis alle Anhänge verarbeitet sind"),
+    ("entry.label", "Eintrag {number}"),
+    ("entry.label_titled", "Eintrag {number} ({title})"),
+    ("title.label", "Titel"),
+    ("title.hint", "z. B. Zellviabilitätstest Tag 3"),
+    ("summary.label", "Zusammenfassung"),
+    (
+        "summary.hint",
+        "Ein oder zwei Sätze für Archivübersichten (optional)",
+    ),
+    ("summary.counter", "{count}/{max} Zeichen"),
+    ("body.label", "Haupttext"),
+    (
+        "body.hint",
+        "Text mit Markdown formatieren. Tab rückt ein; Strg+Tab springt zum nächsten Feld.",
+    ),
+    ("genre.experiment", "Experiment"),
+    ("genre.resource", "Ressource"),
+    ("genre.template", "Vorlage"),
+    ("meta.entry_type", "Eintragstyp"),
+    ("meta.performed_at", "Durchgeführt am"),
+    (
+        "meta.performed_at.hover",
+        "Durchgeführt am (Ortszeit, gespeichert als UTC)",
+    ),
+    ("meta.language", "Sprache"),
+    ("meta.language.hint", "auto"),
+    (
+        "meta.language.hover",
+        "Sprache des Haupttexts, z. B. en, de oder en-GB. Leer lassen, um sie beim Speichern zu erkennen.",
+    ),
+    ("meta.license", "Lizenz"),
+    (
+        "meta.license.hover",
+        "In den Archiv-Metadaten angegebene Lizenz des Eintrags; neue Einträge übernehmen die zuletzt gewählte",
+    ),
+    ("license.none", "Keine / proprietär"),
+    ("license.other", "Andere (URL)"),
+    ("license.url.hover", "Webadresse des Lizenztexts"),
+    (
+        "meta.utc_note",
+        "Zeiten werden in Ihrer lokalen Zeitzone angezeigt und im Archiv als UTC gespeichert.",
+    ),
+    ("summary.heading", "Zusammenfassung"),
+    ("summary.unknown", "unbekannt"),
+    ("summary.words", "Wörter im Haupttext"),
+    ("summary.figures", "Abbildungen eingebettet / angehängt"),
+    ("summary.required", "Ausgefüllte Pflichtfelder"),
+    ("summary.required_value", "{filled} von {total}"),
+    ("summary.data_volume", "Datenmenge"),
+    ("summary.data_value", "{size} in {count} Datei(en)"),
+    ("summary.acquired", "Aufgenommen"),
+    (
+        "summary.consistent",
+        "Haupttext und Anhänge stimmen überein.",
+    ),
+    (
+        "summary.missing_attachments",
+        "Der Haupttext verweist auf Dateien, die nicht angehängt sind: {files}",
+    ),
+    (
+        "summary.unmentioned",
+        "Im Haupttext nicht erwähnte Anhänge: {files}",
+    ),
+    ("summary.required_empty", "{count} Pflichtfeld(er) leer."),
+    (
+        "summary.missing_policy",
+        "Falls beim Speichern ein Anhang fehlt:",
+    ),
+    (
+        "draft.restored",
+        "Ungespeicherter Entwurf aus der letzten Sitzung wiederhergestellt.",
+    ),
+    ("draft.discard", "Entwurf verwerfen"),
+    (
+        "draft.discard.hover",
+        "Das Formular leeren und den gespeicherten Entwurf löschen",
+    ),
+    ("draft.keep", "Behalten"),
+    ("save.running", "Es läuft bereits ein Speichervorgang."),
+    (
+        "save.blocked.title",
+        "Vor dem Speichern einen Titel eingeben.",
+    ),
+    (
+        "save.blocked.fields",
+        "Vor dem Speichern die markierten Felder im Metadatenbereich korrigieren.",
+    ),
+    (
+        "save.blocked.attachments",
+        "Vor dem Speichern warten, bis die Anhänge verarbeitet sind.",
+    ),
+    ("save.cancelled", "Speichern abgebrochen."),
+    ("save.cancelling", "Speichern wird abgebrochen…"),
+    (
+        "save.skipped_files",
+        "{count} fehlende(r) Anhang/Anhänge ausgelassen ({names})",
+    ),
+    ("save.failed", "Speichern fehlgeschlagen"),
+    (
+        "save.error",
+        "Das Archiv konnte nicht gespeichert werden:\n\n{error}",
+    ),
+    (
+        "save.interrupted",
+        "Das Archiv konnte nicht gespeichert werden:\n\n{error}\n\nDie bereits geschriebenen Anhänge wurden behalten. Setzen Sie das Speichern fort, um nur den Rest zu schreiben.",
+    ),
+    (
+        "save.resumed",
+        "Speichern wird fortgesetzt; {count} Anhang/Anhänge bereits geschrieben.",
+    ),
+    (
+        "save.restarted",
+        "Das Speichern konnte nicht fortgesetzt werden ({reason}); das Archiv wird neu geschrieben.",
+    ),
+    (
+        "save.done.archive",
+        "Archiv gespeichert: {path} ({version})",
+    ),
+    (
+        "save.done.template",
+        "Vorlagenarchiv gespeichert: {path} ({version})",
+    ),
+    ("save.done.license", ". Lizenz: {license}"),
+    ("save.done.stats", " – {size} in {seconds} s"),
+    ("batch.busy", "Ein Stapel läuft noch."),
+    ("batch.error_title", "Stapelverarbeitung fehlgeschlagen"),
+    ("batch.started", "{count} Archive werden geschrieben…"),
+    (
+        "batch.done",
+        "Stapel fertig: {written} Archive geschrieben, {failed} fehlgeschlagen, {cancelled} abgebrochen.",
+    ),
+    ("save.done.warning", ". Warnung: {warning}"),
+    ("save.done.signed", ". Signiert: {path}"),
+    ("save.done.uploading", ". Wird zu eLabFTW hochgeladen…"),
+    ("save.signing_failed", "Signieren fehlgeschlagen"),
+    (
+        "save.signing_error",
+        "{message}\n\nDas Archiv wurde nicht signiert:\n\n{error}",
+    ),
+    ("save.validation_error", "Validierungsfehler"),
+    (
+        "save.invalid_html",
+        "Ungültige Einstellungen für den HTML-Export:\n{errors}",
+    ),
+    ("save.no_title", "Bitte einen Titel eingeben."),
+    (
+        "save.pending_hashes",
+        "Bitte warten, bis alle Anhänge verarbeitet sind.",
+    ),
+    ("save.invalid_language", "Ungültige Sprache: {error}"),
+    ("save.invalid_datetime", "Ungültiges Datum/Uhrzeit: {error}"),
+    ("save.author_error", "Autor {number}: {error}"),
+    (
+        "save.author_orcid_twice",
+        "Autor {number}: ORCID iD {orcid} ist doppelt aufgeführt.",
+    ),
+    ("common.cancel", "Abbrechen"),
+    ("common.remove", "Entfernen"),
+    ("common.apply", "Übernehmen"),
+    ("common.save", "Speichern"),
+    ("attachments.title", "Anhänge"),
+    ("attachments.state.unverified", "ungeprüft"),
+    ("attachments.state.verified", "geprüft"),
+    ("attachments.state.reverified", "erneut geprüft"),
+    ("attachments.state.changed", "geändert"),
+    ("attachments.state.unreadable", "nicht lesbar"),
+    (
+        "attachments.state.unverified.details",
+        "In dieser Sitzung nicht gelesen; der Inhalt wird beim Speichern geprüft.",
+    ),
+    (
+        "attachments.state.verified.details",
+        "Beim Hinzufügen gehasht ({when}).",
+    ),
+    (
+        "attachments.state.reverified.details",
+        "Stimmte am {when} noch mit seinem Hash überein.",
+    ),
+    (
+        "attachments.state.changed.details",
+        "Die Datei wurde seit dem Hinzufügen geändert. Entfernen Sie sie und fügen Sie sie erneut hinzu, um den neuen Inhalt aufzunehmen.",
+    ),
+    (
+        "attachments.state.unreadable.details",
+        "Die Datei konnte nicht gelesen werden.",
+    ),
+    ("attachments.processing", "Anhänge werden verarbeitet..."),
+    (
+        "attachments.folder.too_large",
+        "Der Ordner {folder} enthält mehr als {max} Dateien. Bitte fügen Sie einen kleineren Ordner hinzu.",
+    ),
+    (
+        "attachments.folder.empty",
+        "Der Ordner {folder} enthält keine Dateien.",
+    ),
+    (
+        "attachments.folder.failed",
+        "Ordner konnte nicht gelesen werden: {err}",
+    ),
+    ("attachments.folder.confirm", "Ordner hinzufügen?"),
+    (
+        "attachments.folder.confirm.text",
+        "{folder} enthält {count} Dateien. Alle hinzufügen?",
+    ),
+    (
+        "attachments.folder.processing",
+        "{count} Datei(en) aus {folder} werden verarbeitet...",
+    ),
+    (
+        "attachments.blocked",
+        "{name} wurde nicht hinzugefügt: {pattern}-Dateien sind durch die Anhangsrichtlinie in den Einstellungen gesperrt.",
+    ),
+    ("attachments.added", "Anhang hinzugefügt"),
+    (
+        "attachments.added_renamed",
+        "Anhang als {to} hinzugefügt; {from} ist für die eigenen Dateien des Archivs reserviert.",
+    ),
+    (
+        "attachments.skipped",
+        "Anhang übersprungen (doppelt oder ungültig)",
+    ),
+    (
+        "attachments.oversized",
+        "{name} ist {size} groß, mehr als die {limit}, die die Anhangsrichtlinie ohne Nachfrage erlaubt.",
+    ),
+    (
+        "attachments.oversized.hover",
+        "Größer als {limit}; beim Speichern wird nachgefragt, und eLabFTW lehnt die Datei eventuell ab.",
+    ),
+    ("attachments.cancelled", "Anhang abgebrochen"),
+    ("attachments.removed", "Anhang entfernt"),
+    ("attachments.broken", "{count} nicht lesbar oder geändert"),
+    (
+        "attachments.blocker",
+        "Das Speichern wartet, bis alle Anhänge verarbeitet sind.",
+    ),
+    ("attachments.add_files", "Dateien hinzufügen"),
+    ("attachments.add_folder", "Ordner hinzufügen"),
+    (
+        "attachments.add_folder.hover",
+        "Alle Dateien eines Ordners mit seiner Struktur hinzufügen",
+    ),
+    ("attachments.layout.table", "Tabelle"),
+    ("attachments.layout.cards", "Karten"),
+    (
+        "attachments.layout.hover",
+        "Zwischen ausführlichen Karten und einer sortierbaren Tabelle wechseln",
+    ),
+    ("attachments.sort", "Nach Name sortieren"),
+    (
+        "attachments.sort.hover",
+        "Anhänge nach Ordner und Dateiname ordnen",
+    ),
+    (
+        "attachments.set_acquired.one",
+        "Aufnahmezeit für 1 ausgewählte Datei festlegen",
+    ),
+    (
+        "attachments.set_acquired.many",
+        "Aufnahmezeit für {count} ausgewählte Dateien festlegen",
+    ),
+    ("attachments.none", "Keine Anhänge"),
+    (
+        "attachments.select.hover",
+        "Für Sammelaktionen auswählen (Umschalt-Klick wählt einen Bereich)",
+    ),
+    ("attachments.sanitized", "Dateiname bereinigt:"),
+    (
+        "attachments.name.hover",
+        "Umbenennen (Eingabe); Entf entfernt die Datei",
+    ),
+    ("attachments.edit_name", "Dateinamen bearbeiten"),
+    ("attachments.acquired", "aufgenommen {when}"),
+    ("attachments.acquired.unknown", "Aufnahmezeit unbekannt"),
+    ("attachments.acquired.set", "Aufnahmezeit festlegen"),
+    ("attachments.acquired.title", "Aufgenommen am: {name}"),
+    ("attachments.acquired.title.bare", "Aufgenommen am"),
+    (
+        "attachments.acquired.title.many",
+        "Aufgenommen am: {count} Dateien",
+    ),
+    (
+        "attachments.acquired.future",
+        "Die Aufnahmezeit darf nicht in der Zukunft liegen.",
+    ),
+    (
+        "attachments.acquired.done",
+        "Aufnahmezeit für {count} Datei(en) festgelegt.",
+    ),
+    ("attachments.figure", "In Abbildungsliste aufnehmen"),
+    (
+        "attachments.figure.hover",
+        "Dieses Bild mit seiner Beschriftung unter Abbildungen in der HTML-Vorschau aufführen",
+    ),
+    ("attachments.remove.hover", "Angehängte Datei entfernen"),
+    ("attachments.move_down", "Nach unten"),
+    ("attachments.move_up", "Nach oben"),
+    ("attachments.preview", "Inhalt anzeigen"),
+    ("attachments.renaming", "{name} umbenennen"),
+    ("attachments.column.name", "Name"),
+    ("attachments.column.type", "Typ"),
+    ("attachments.column.size", "Größe"),
+    (
+        "attachments.column.hover",
+        "Tabelle sortieren; das Archiv behält seine eigene Reihenfolge",
+    ),
+    ("attachments.rename", "Umbenennen"),
+    ("attachments.remove_ellipsis", "Entfernen…"),
+    ("attachments.remove.confirm", "Anhang entfernen?"),
+    (
+        "attachments.remove.confirm.text",
+        "{name} aus dem Eintrag entfernen?",
+    ),
+    ("attachments.preview.title", "Vorschau: {name}"),
+    ("attachments.copy_path", "Pfad kopieren"),
+    ("attachments.preview.loading", "Vorschau wird geladen…"),
+    ("attachments.preview.failed", "Keine Vorschau: {err}"),
+    (
+        "attachments.preview.lossy",
+        "Kein gültiges UTF-8; nicht lesbare Bytes werden als \u{fffd} angezeigt.",
+    ),
+    (
+        "attachments.preview.truncated",
+        "Die ersten {lines} Zeilen werden angezeigt.",
+    ),
+    (
+        "attachments.preview.binary",
+        "Keine Textvorschau für diesen Typ. Erste {shown} von {size}:",
+    ),
+    ("attachments.description", "Beschreibung"),
+    (
+        "attachments.description.count",
+        "Beschreibung ({count} Zeichen)",
+    ),
+    (
+        "attachments.description.hint",
+        "Beschriftung, die in eLabFTW mit der Datei angezeigt wird",
+    ),
+    ("attachments.description.characters", "{count} Zeichen"),
+    ("attachments.hashing", "sha256 wird berechnet…"),
+    (
+        "attachments.hashing.cancel",
+        "Hashen abbrechen und entfernen",
+    ),
+    (
+        "attachments.review",
+        "Der Name wird für das Archiv geändert:",
+    ),
+    ("attachments.review.accept", "Geänderten Namen verwenden"),
+    ("attachments.review.revise", "Weiter bearbeiten"),
+    (
+        "attachments.name.empty",
+        "Der Dateiname darf nicht leer sein.",
+    ),
+    (
+        "attachments.name.invalid",
+        "Der Dateiname ist nach der Bereinigung ungültig.",
+    ),
+    (
+        "attachments.name.reserved",
+        "{name} ist für die eigenen Dateien des Archivs reserviert.",
+    ),
+    (
+        "attachments.name.duplicate",
+        "Ein anderer Anhang verwendet diesen Dateinamen bereits im Archiv.",
+    ),
+    (
+        "attachments.name.updated",
+        "Dateiname des Anhangs aktualisiert.",
+    ),
+    (
+        "attachments.thumbnail.unsupported",
+        "Keine Vorschau: nicht unterstütztes Format\n{reason}",
+    ),
+    (
+        "attachments.thumbnail.decode",
+        "Keine Vorschau: das Bild konnte nicht gelesen werden\n{reason}",
+    ),
+    ("attachments.thumbnail.retry", "Vorschau erneut laden"),
+    (
+        "settings.api_key.failed",
+        "eLabFTW-API-Schlüssel konnte nicht gespeichert werden: {err}",
+    ),
+    ("settings.exported", "Einstellungen exportiert: {path}"),
+    (
+        "settings.export.failed",
+        "Einstellungen konnten nicht exportiert werden: {err}",
+    ),
+    (
+        "settings.import.failed",
+        "Einstellungen konnten nicht importiert werden: {err}",
+    ),
+    (
+        "settings.save.failed",
+        "Einstellungen konnten nicht gespeichert werden: {err}",
+    ),
+    (
+        "settings.upload.incomplete",
+        "Das Hochladen nach dem Speichern braucht die eLabFTW-Adresse und einen API-Schlüssel.",
+    ),
+    ("settings.horizon.years", "Jahre"),
+    ("settings.favorites.missing", "Nicht gefunden: {path}"),
+    (
+        "settings.share.key_warning",
+        "Jeder mit der exportierten Datei kann Ihr eLabFTW-Konto verwenden.",
+    ),
+    ("settings.share.export", "Einstellungen exportieren…"),
+    ("settings.share.import", "Einstellungen importieren…"),
+    ("settings.keywords.clear", "Schlagwortvorschläge löschen"),
+    ("settings.favorite.current", "Der Dialog beginnt in {path}"),
+    (
+        "settings.favorite.hover",
+        "Einen Lieblingsordner für diesen Dialog wählen",
+    ),
+    ("settings.imported", "Einstellungen importiert."),
+    ("settings.publisher.address", "Adresse des Herausgebers"),
+    (
+        "settings.keywords.cleared",
+        "Schlagwortvorschläge gelöscht.",
+    ),
+    ("settings.saved", "Einstellungen gespeichert."),
+    ("settings.import.title", "Einstellungen importieren"),
+    (
+        "settings.import.same",
+        "Die importierten Einstellungen entsprechen den aktuellen.",
+    ),
+    (
+        "settings.import.changes",
+        "Der Import ersetzt diese Einstellungen:",
+    ),
+    (
+        "settings.import.key",
+        "Die Datei enthält außerdem einen eLabFTW-API-Schlüssel, der Ihren ersetzt.",
+    ),
+    ("settings.import.confirm", "Importieren"),
+    ("settings.compression.method", "Verfahren"),
+    (
+        "settings.compression.method.hover",
+        "Auto speichert Bilder, Videos und Archive, die bereits komprimiert sind, unverändert und komprimiert alles andere mit Deflate",
+    ),
+    ("settings.compression.level", "Deflate-Stufe"),
+    (
+        "settings.compression.level.hover",
+        "Höhere Stufen schreiben kleinere Archive langsamer; die Metadaten werden immer komprimiert",
+    ),
+    ("settings.hashing.compute", "Berechnen"),
+    (
+        "settings.hashing.sha256",
+        "Immer berechnet: Archive speichern SHA-256 für jede Datei",
+    ),
+    (
+        "settings.hashing.primary_required",
+        "Wird in der Anhangsliste angezeigt und daher immer berechnet",
+    ),
+    (
+        "settings.hashing.hover",
+        "Gilt für ab jetzt hinzugefügte Dateien; Prüfsummen werden beim Speichern erneut geprüft",
+    ),
+    ("settings.hashing.primary", "In der Anhangsliste anzeigen"),
+    (
+        "settings.hashing.manifest",
+        "Manifest checksums.sha512 schreiben",
+    ),
+    (
+        "settings.hashing.manifest.hover",
+        "Eine sha512sum-kompatible Liste aller Anhänge im Wurzelverzeichnis des Archivs ablegen",
+    ),
+    ("settings.verify", "Archiv nach dem Speichern prüfen"),
+    (
+        "settings.verify.hover",
+        "Das gespeicherte Archiv erneut öffnen und Größe und SHA-256 jeder Datei prüfen, z. B. gegen abgebrochene Schreibvorgänge auf Netzlaufwerken",
+    ),
+    (
+        "settings.imported_metadata",
+        "Importierte Metadatendateien einschließen",
+    ),
+    (
+        "settings.imported_metadata.hover",
+        "Das eLabFTW-JSON, aus dem Zusatzfelder importiert wurden, unverändert als imported_metadata.json neben die rekonstruierten Metadaten kopieren, damit Prüfungen beide vergleichen können",
+    ),
+    ("settings.title", "Einstellungen"),
+    ("settings.advanced", "Erweiterter Export"),
+    (
+        "settings.advanced.text",
+        "Zusätzliches HTML, das beim Export des Haupttexts als HTML erhalten bleibt. Skripte, Stile, Frames und Formulare sind nie erlaubt.",
+    ),
+    ("settings.advanced.tags", "Erlaubte Tags"),
+    ("settings.advanced.tags.hint", "z. B. section, mark"),
+    ("settings.advanced.classes", "Erlaubte Klassen"),
+    (
+        "settings.advanced.classes.hint",
+        "z. B. callout-box, highlight",
+    ),
+    (
+        "settings.advanced.tip",
+        "Tipp: `::: note` … `:::`-Blöcke im Haupttext werden zu <div class=\"callout note\">-Kästen.",
+    ),
+    ("settings.archive", "Archiv"),
+    (
+        "settings.zip_comment",
+        "Zusammenfassung in den ZIP-Kommentar schreiben",
+    ),
+    (
+        "settings.zip_comment.hover",
+        "Titel, Datum und Schlagwörter werden in Dateimanagern und `unzip -l` ohne Entpacken sichtbar",
+    ),
+    ("settings.readme", "README.txt schreiben"),
+    (
+        "settings.readme.hover",
+        "Eine Klartextbeschreibung mit Titel, Datum, Autoren, Schlagwörtern, Haupttext und Anhängen für Repositorien hinzufügen, die eine verlangen",
+    ),
+    ("settings.readme.license", "README-Lizenz"),
+    ("settings.readme.license.hint", "z. B. CC-BY-4.0"),
+    (
+        "settings.readme.license.hover",
+        "In README.txt angegebene Lizenz; leer lassen, um sie wegzulassen",
+    ),
+    ("settings.signing_key", "Signaturschlüssel"),
+    (
+        "settings.signing_key.hint",
+        "z. B. /home/me/.ssh/elnpack_ed25519",
+    ),
+    (
+        "settings.signing_key.hover",
+        "Privater Ed25519-Schlüssel ohne Passphrase (ssh-keygen -t ed25519); jedes gespeicherte Archiv erhält daneben eine .sig-Datei. Leer lassen, um unsignierte Archive zu speichern",
+    ),
+    ("settings.elabftw.url", "eLabFTW-Adresse"),
+    (
+        "settings.elabftw.url.hint",
+        "z. B. https://elab.example.org",
+    ),
+    (
+        "settings.elabftw.url.hover",
+        "Die eLabFTW-Instanz, in die Ihre Archive importiert werden",
+    ),
+    ("settings.elabftw.key", "eLabFTW-API-Schlüssel"),
+    (
+        "settings.elabftw.key.hover",
+        "Erstellen Sie unter Benutzerbereich → API-Schlüssel einen Schlüssel mit Schreibzugriff; er wird in einer eigenen, nur für Sie lesbaren Datei gespeichert",
+    ),
+    (
+        "settings.elabftw.upload",
+        "Nach dem Speichern zu eLabFTW hochladen",
+    ),
+    (
+        "settings.elabftw.upload.hover",
+        "Jedes gespeicherte Archiv in die obige Instanz importieren; lässt sich pro Speichern neben der Schaltfläche Speichern überspringen",
+    ),
+    ("settings.publisher", "Herausgeber"),
+    (
+        "settings.publisher.text",
+        "Organisation, die als Herausgeber jedes Archivs genannt wird. Den Namen leer lassen, um ELNPack zu nennen.",
+    ),
+    ("settings.publisher.name", "Name"),
+    (
+        "settings.publisher.name.hint",
+        "z. B. Institut für Strukturbiologie",
+    ),
+    ("settings.publisher.url", "Webadresse"),
+    (
+        "settings.publisher.url.hint",
+        "z. B. https://www.example.org",
+    ),
+    (
+        "settings.publisher.url.hover",
+        "Leer lassen, um sie wegzulassen",
+    ),
+    ("settings.publisher.ror", "ROR-ID"),
+    (
+        "settings.publisher.ror.hint",
+        "z. B. https://ror.org/05a28rw58",
+    ),
+    (
+        "settings.publisher.ror.hover",
+        "Kennung der Research Organization Registry, siehe ror.org; bezeichnet die Organisation eindeutig",
+    ),
+    ("settings.compression", "Kompression"),
+    ("settings.checksums", "Prüfsummen"),
+    ("settings.validation", "Prüfung"),
+    (
+        "settings.horizon",
+        "Durchführungsdaten bestätigen lassen, die älter sind als",
+    ),
+    (
+        "settings.horizon.hover",
+        "Auf 0 setzen, um die Prüfung abzuschalten",
+    ),
+    ("settings.units", "Einheitencodes"),
+    (
+        "settings.units.text",
+        "Gespeicherte Archive nennen den UCUM-Code gängiger Einheiten. Fügen Sie Einheiten, die in der eingebauten Tabelle fehlen, als 'Einheit = Code' hinzu, eine pro Zeile.",
+    ),
+    ("settings.units.hint", "z. B. OD600 = {OD600}"),
+    ("settings.attachment_policy", "Anhangsrichtlinie"),
+    (
+        "settings.attachment_policy.text",
+        "Dateien ablehnen, die Ihre eLabFTW-Instanz nicht annimmt, nach Endung (exe) oder MIME-Typ (video/*), durch Kommas getrennt.",
+    ),
+    ("settings.attachment_policy.hint", "z. B. exe, bat, video/*"),
+    (
+        "settings.attachment_policy.size",
+        "Anhänge markieren, die größer sind als",
+    ),
+    (
+        "settings.attachment_policy.size.hover",
+        "Beim Speichern wird vor dem Archivieren nachgefragt. Auf 0 setzen, um die Prüfung abzuschalten",
+    ),
+    ("settings.keyword_fields", "Schlagwörter aus Feldern"),
+    (
+        "settings.keyword_fields.text",
+        "Metadatenfelder, deren Werte beim Speichern zu den Schlagwörtern hinzugefügt werden, eine Bezeichnung pro Zeile. Groß- und Kleinschreibung spielt keine Rolle.",
+    ),
+    ("settings.keyword_fields.hint", "z. B. Organismus"),
+    ("settings.favorites", "Lieblingsordner"),
+    (
+        "settings.favorites.text",
+        "Ein Ordner pro Zeile. Wählen Sie einen im Sternmenü neben Dateien hinzufügen, JSON importieren oder Speichern, damit der Dialog dort beginnt.",
+    ),
+    ("settings.favorites.hint", "z. B. /data/beamline"),
+    ("settings.share", "Einstellungen teilen"),
+    (
+        "settings.share.text",
+        "Die gespeicherten Einstellungen auf andere Computer übertragen. Fenstergröße, Design, gemerkte Ordner und der Signaturschlüssel bleiben auf dem jeweiligen Computer.",
+    ),
+    ("settings.share.key", "eLabFTW-API-Schlüssel einschließen"),
+    (
+        "settings.share.export.hover",
+        "Die gespeicherten Einstellungen in eine JSON-Datei schreiben",
+    ),
+    (
+        "settings.share.import.hover",
+        "Auf einem anderen Computer exportierte Einstellungen laden",
+    ),
+    ("settings.keywords", "Schlagwörter"),
+    (
+        "settings.keywords.clear.hover",
+        "Alle aus gespeicherten Archiven gemerkten Schlagwörter vergessen",
+    ),
+    ("settings.diagnostics", "Diagnose"),
+    ("settings.font_coverage", "Schriftabdeckung anzeigen"),
+    (
+        "settings.font_coverage.hover",
+        "Auflisten, welche Schriften einen Testtext darstellen können, z. B. wenn Symbole als Kästchen erscheinen",
+    ),
+    ("settings.favorite.default", "Systemvorgabe"),
+    (
+        "settings.favorite.none",
+        "Lieblingsordner in den Einstellungen hinzufügen.",
+    ),
+    ("settings.favorite.missing", "Ordner nicht gefunden"),
+    ("settings.section.defaults", "Vorgaben für Einträge"),
+    ("settings.section.archive", "Archivoptionen"),
+    ("settings.section.compact", "Kompakte Ansicht"),
+    (
+        "settings.compression.stored",
+        "Gespeichert (ohne Kompression)",
+    ),
+    ("missing_files.abort", "Speichern abbrechen"),
+    ("missing_files.skip", "Fehlende Dateien überspringen"),
+    ("missing_files.ask", "Für jede Datei nachfragen"),
+    ("fields.imported", "{count} Feld(er) aus {path} importiert"),
+    (
+        "fields.reimported.kept",
+        "{count} Feld(er) erneut aus {name} importiert; Ihre Werte wurden behalten.",
+    ),
+    (
+        "fields.reimported.replaced",
+        "{count} Feld(er) erneut aus {name} importiert.",
+    ),
+    (
+        "fields.filled",
+        "{count} Feld(er) mit der Bezeichnung '{label}' gefüllt.",
+    ),
+    (
+        "fields.filled.skipped",
+        "{readonly} schreibgeschützte und {invalid}, die den Wert nicht annehmen, übersprungen.",
+    ),
+    (
+        "fields.groups.merged",
+        "Gleichnamige Gruppen zusammengeführt: {names}.",
+    ),
+    (
+        "fields.group.renamed",
+        "Eine Gruppe namens '{wanted}' gibt es bereits; sie heißt jetzt '{name}'.",
+    ),
+    ("fields.ago.now", "gerade eben"),
+    ("fields.ago.minutes", "vor {count} min"),
+    ("fields.ago.hours", "vor {count} h"),
+    ("fields.ago.days", "vor {count} d"),
+    ("fields.add_group", "Gruppe hinzufügen"),
+    ("fields.add_field", "Feld hinzufügen"),
+    ("fields.import", "JSON importieren"),
+    ("fields.import_entry", "Aus eLabFTW-JSON importieren"),
+    (
+        "fields.import_entry.hover",
+        "Titel, Haupttext, Schlagwörter, Datum und Felder aus einem exportierten eLabFTW-Eintrag füllen",
+    ),
+    ("fields.bulk_fill.button", "Passende Felder füllen…"),
+    ("fields.source", "Importiert aus {name}, {ago}"),
+    ("fields.reimport", "Erneut importieren"),
+    (
+        "fields.import.text",
+        "Importiert extra_fields-JSON im eLabFTW-Format. Feldoptionen werden übernommen; Werte können Sie vor dem Speichern anpassen.",
+    ),
+    ("fields.expand_all", "Alle aufklappen"),
+    ("fields.collapse_all", "Alle zuklappen"),
+    ("fields.count.one", "1 Feld"),
+    ("fields.count.many", "{count} Felder"),
+    ("fields.invalid", "{count} ungültig"),
+    ("fields.add_field_to", "Feld zu {group} hinzufügen"),
+    (
+        "fields.filter.none",
+        "Keine Felder passen zu \u{201e}{filter}\u{201c}.",
+    ),
+    ("fields.reset.empty", "Auf Standardwert zurücksetzen: leer"),
+    ("fields.reset", "Auf Standardwert zurücksetzen: {default}"),
+    ("fields.range.from", "Von"),
+    ("fields.range.to", "bis"),
+    (
+        "fields.unit.code",
+        "Wird mit dem UCUM-Code {code} gespeichert",
+    ),
+    (
+        "fields.unit.unknown",
+        "Für diese Einheit ist kein UCUM-Code bekannt; fügen Sie einen unter Einstellungen → Einheitencodes hinzu",
+    ),
+    (
+        "fields.bulk_fill.changes",
+        "{count} Feld(er) werden geändert.",
+    ),
+    ("fields.bulk_fill.skipped", "{count} übersprungen: {reason}"),
+    (
+        "fields.reimport.changed",
+        "{name} wurde seit dem Import geändert.",
+    ),
+    (
+        "fields.reimport.unchanged",
+        "{name} ist seit dem Import unverändert.",
+    ),
+    (
+        "fields.reimport.counts",
+        "Sie definiert {count} Feld(er); der Eintrag hat derzeit {current}.",
+    ),
+    (
+        "fields.reimport.keep.hover",
+        "Die Felddefinitionen der Datei verwenden und Ihre eingegebenen Werte behalten",
+    ),
+    (
+        "fields.modal.default.hover",
+        "Wert, mit dem neue Felder beginnen und den Zurücksetzen wiederherstellt; leer lassen für keinen",
+    ),
+    (
+        "fields.editor_open.deferred",
+        "Schließen oder verwerfen Sie zuerst den Feldeditor; die neuen Felder werden danach übernommen.",
+    ),
+    (
+        "fields.editor_open",
+        "Schließen oder verwerfen Sie zuerst den Feldeditor.",
+    ),
+    ("fields.import.cancelled", "Metadatenimport abgebrochen."),
+    ("fields.name.duplicate", "Der Feldname muss eindeutig sein"),
+    (
+        "fields.bulk_fill.hover",
+        "Einen Wert in allen Feldern mit derselben Bezeichnung setzen",
+    ),
+    (
+        "fields.reimport.hover",
+        "Die Datei erneut lesen und wählen, wie sie angewendet wird",
+    ),
+    (
+        "fields.filter.hint",
+        "Nach Bezeichnung, Beschreibung oder Gruppe filtern",
+    ),
+    ("fields.filter.clear", "Filter leeren"),
+    ("fields.main_text", "Haupttext in eLabFTW anzeigen"),
+    (
+        "fields.main_text.hover",
+        "Wenn aus, zeigt eLabFTW nur die Zusatzfelder dieses Eintrags",
+    ),
+    ("fields.title", "Metadaten"),
+    (
+        "fields.blocker",
+        "Ungültige Felder verhindern das Speichern.",
+    ),
+    (
+        "fields.none",
+        "Noch keine Metadaten. Fügen Sie eine Gruppe hinzu oder importieren Sie JSON.",
+    ),
+    ("fields.group.empty", "Noch keine Felder in dieser Gruppe."),
+    ("fields.group.save", "Gruppennamen speichern"),
+    ("fields.group.name", "Gruppenname"),
+    (
+        "fields.group.drag",
+        "Auf eine andere Gruppe ziehen, um sie dorthin zu verschieben",
+    ),
+    ("fields.group.up", "Gruppe nach oben"),
+    ("fields.group.down", "Gruppe nach unten"),
+    ("fields.group.remove", "Gruppe entfernen"),
+    ("fields.group.rename", "Gruppe umbenennen"),
+    ("fields.remove", "Feld entfernen"),
+    ("fields.duplicate", "Feld duplizieren"),
+    ("fields.edit", "Feld bearbeiten"),
+    ("fields.checked", "Angekreuzt"),
+    ("fields.unit", "Einheit"),
+    ("fields.kind.number", "Zahl"),
+    ("fields.hint.id", "Numerische ID"),
+    ("fields.kind.text", "Text"),
+    ("fields.kind.select", "Auswahl"),
+    ("fields.kind.checkbox", "Kontrollkästchen"),
+    ("fields.kind.date", "Datum"),
+    ("fields.kind.date_range", "Zeitraum"),
+    ("fields.kind.datetime", "Datum/Uhrzeit"),
+    ("fields.kind.time", "Uhrzeit"),
+    ("fields.kind.url", "URL"),
+    ("fields.kind.email", "E-Mail"),
+    ("fields.kind.radio", "Optionsfeld"),
+    ("fields.kind.items", "Ressourcen"),
+    ("fields.kind.experiments", "Experimente"),
+    ("fields.kind.users", "Benutzer"),
+    ("fields.kind.unknown", "Unbekannt"),
+    ("fields.modal.title", "Titel"),
+    ("fields.modal.kind", "Feldtyp"),
+    ("fields.modal.description", "Beschreibung"),
+    ("fields.modal.default", "Standardwert"),
+    ("fields.modal.default.multi", "Durch Kommas getrennte Werte"),
+    ("fields.modal.required", "Pflichtfeld"),
+    ("fields.modal.readonly", "Schreibgeschützt"),
+    ("fields.modal.multi", "Mehrfachauswahl erlauben"),
+    ("fields.modal.options", "Optionen"),
+    ("fields.modal.option.remove", "Option entfernen"),
+    ("fields.modal.option.add", "Option hinzufügen"),
+    ("fields.modal.units", "Einheiten"),
+    ("fields.modal.unit.remove", "Einheit entfernen"),
+    ("fields.modal.unit.add", "Einheit hinzufügen"),
+    ("fields.modal.unit.default", "Standardeinheit"),
+    ("fields.modal.group", "Gruppenzuordnung"),
+    ("fields.modal.group.label", "Gruppe"),
+    ("fields.bulk_fill", "Passende Felder füllen"),
+    ("fields.bulk_fill.field", "Feld"),
+    ("fields.bulk_fill.value", "Wert"),
+    ("fields.bulk_fill.only_empty", "Nur leere Felder füllen"),
+    ("fields.bulk_fill.readonly", "schreibgeschützt"),
+    ("fields.bulk_fill.filled", "bereits gefüllt"),
+    ("fields.bulk_fill.invalid", "nehmen diesen Wert nicht an"),
+    ("fields.reimport.title", "Metadaten erneut importieren"),
+    ("fields.reimport.keep", "Meine Werte behalten"),
+    ("fields.reimport.replace", "Alles ersetzen"),
+    (
+        "fields.reimport.replace.hover",
+        "Ihre Änderungen verwerfen und die Datei unverändert verwenden",
+    ),
+    ("common.close", "Schließen"),
+    (
+        "templates.exists",
+        "Eine Vorlage namens '{name}' gibt es bereits.",
+    ),
+    ("templates.save", "Als Vorlage speichern…"),
+    ("templates.archive", "Als Vorlagenarchiv speichern…"),
+    (
+        "templates.archive.hover",
+        "Ein ELN-Archiv zum Import in eLabFTW als Vorlage schreiben, ohne Anhänge und mit leeren markierten Feldwerten",
+    ),
+    ("templates.manage.button", "Vorlagen verwalten…"),
+    ("templates.modified", "{name} (geändert)"),
+    ("templates.none_applied", "Keine Vorlage"),
+    (
+        "templates.replaced",
+        "Die Vorlage namens '{name}' wird ersetzt.",
+    ),
+    ("templates.replace", "Ersetzen"),
+    ("templates.new", "Neue Vorlage"),
+    ("templates.edit", "Vorlage '{name}' bearbeiten"),
+    ("keywords.cleared", "Alle {count} Schlagwörter entfernt."),
+    ("keywords.merged", "{count} neue(s) Schlagwort/Schlagwörter"),
+    (
+        "keywords.merged.skipped",
+        "({count} bereits vorhanden oder über der Grenze)",
+    ),
+    (
+        "keywords.remove_selected",
+        "Ausgewählte entfernen ({count})",
+    ),
+    ("keywords.clear", "Alle entfernen"),
+    (
+        "keywords.clear.text",
+        "Alle {count} Schlagwörter entfernen? Das lässt sich nicht rückgängig machen.",
+    ),
+    (
+        "keywords.remove.text",
+        "„{keyword}“ aus den Schlagwörtern entfernen?",
+    ),
+    (
+        "keywords.derived.hover",
+        "„{keyword}“ stammt aus einem Metadatenfeld; ändern Sie das Feld, um es zu ändern.",
+    ),
+    ("keywords.skipped.duplicates", "{count} doppelt"),
+    (
+        "keywords.skipped.empty",
+        "{count} leere(r) Eintrag/Einträge",
+    ),
+    (
+        "keywords.skipped.limit",
+        "{count} über der Grenze von {max}",
+    ),
+    ("keywords.and", "und"),
+    (
+        "keywords.added.skipped",
+        "{count} Schlagwort/Schlagwörter hinzugefügt; übersprungen: {skipped}.",
+    ),
+    (
+        "keywords.added",
+        "{count} Schlagwort/Schlagwörter hinzugefügt.",
+    ),
+    (
+        "keywords.added.none",
+        "Keine Schlagwörter hinzugefügt; doppelte oder leere Einträge übersprungen.",
+    ),
+    (
+        "keywords.removed_many",
+        "{count} Schlagwort/Schlagwörter entfernt.",
+    ),
+    (
+        "templates.name.empty",
+        "Der Vorlagenname darf nicht leer sein.",
+    ),
+    (
+        "templates.import_hint",
+        "Um eine Vorlage aus einer Datei zu beginnen, importieren Sie sie in einen Eintrag und speichern diesen als Vorlage.",
+    ),
+    ("templates.none", "Noch keine Vorlagen"),
+    (
+        "templates.apply.hover",
+        "Einen neuen Eintrag aus dieser Vorlage beginnen",
+    ),
+    (
+        "templates.save.hover",
+        "Titel, Haupttext, Schlagwörter, Typ und Metadatenfelder zur Wiederverwendung speichern",
+    ),
+    (
+        "templates.manage.hover",
+        "Vorlagen anlegen, bearbeiten, kopieren, umbenennen oder löschen",
+    ),
+    (
+        "templates.menu.hover",
+        "Mit einer Eintragsvorlage beginnen oder eine speichern",
+    ),
+    (
+        "templates.recent.none",
+        "Keine zuletzt verwendeten Vorlagen",
+    ),
+    (
+        "templates.modified.hover",
+        "Seit dem Anwenden der Vorlage wurden Felder hinzugefügt, entfernt oder geändert. Wählen Sie eine zuletzt verwendete Vorlage, um mit ihr zu beginnen.",
+    ),
+    (
+        "templates.recent.hover",
+        "Mit einer zuletzt verwendeten Vorlage beginnen",
+    ),
+    ("templates.save.title", "Als Vorlage speichern"),
+    ("templates.name.hint", "z. B. PCR-Lauf"),
+    (
+        "templates.save.text",
+        "Anhänge und Daten werden nicht gespeichert. Schreiben Sie {date} in den Titel, um das Datum jedes neuen Eintrags einzufügen.",
+    ),
+    ("templates.manage", "Vorlagen verwalten"),
+    (
+        "templates.edit.hover",
+        "Titel und Metadatenfelder bearbeiten",
+    ),
+    ("templates.duplicate", "Duplizieren"),
+    ("templates.delete", "Löschen"),
+    ("templates.title.hint", "z. B. PCR-Lauf {date}"),
+    (
+        "templates.editor.text",
+        "Hier eingegebene Werte werden zu Vorgaben neuer Einträge; Pflichtfelder dürfen leer bleiben.",
+    ),
+    ("templates.editor.save", "Vorlage speichern"),
+    ("keywords.removed", "Schlagwort entfernt"),
+    (
+        "keywords.tip",
+        "Tipp: Fügen Sie kommagetrennte Schlagwörter im Dialog ein; sie werden sicher aufgeteilt.",
+    ),
+    ("keywords.deselect", "Auswahl aufheben"),
+    ("keywords.clear.hover", "Alle Schlagwörter entfernen"),
+    (
+        "keywords.select.hint",
+        "Mit Strg/Cmd-Klick oder Umschalt-Klick mehrere Schlagwörter auswählen.",
+    ),
+    ("keywords.clear.title", "Alle Schlagwörter entfernen?"),
+    ("keywords.clear.confirm", "Alle entfernen"),
+    ("keywords.remove.title", "Schlagwort entfernen?"),
+    ("keywords.none", "Noch keine Schlagwörter hinzugefügt."),
+    (
+        "keywords.derived",
+        "Beim Speichern aus Metadatenfeldern hinzugefügt:",
+    ),
+    ("keywords.remove", "Schlagwort entfernen"),
+    ("keywords.edit", "Schlagwort bearbeiten"),
+    ("keywords.add", "Schlagwort(e) hinzufügen"),
+    ("keywords.modal.label", "Schlagwort(e)"),
+    (
+        "keywords.modal.hint",
+        "z. B. Mikroskopie oder Mikroskopie, Datensatz",
+    ),
+    ("keywords.modal.add", "Hinzufügen"),
+    (
+        "keywords.edit.empty",
+        "Das Schlagwort darf nicht leer sein.",
+    ),
+    ("keywords.edit.duplicate", "Das Schlagwort gibt es bereits."),
+    ("markdown.table.insert", "Tabelle einfügen"),
+    ("onboarding.step", "Schritt {number} von {total}"),
+    ("markdown.undo", "Rückgängig (Strg/Cmd+Z)"),
+    ("markdown.redo", "Wiederholen (Strg/Cmd+Umschalt+Z)"),
+    ("markdown.heading", "Überschrift (Strg/Cmd+1…6)"),
+    ("markdown.bold", "Fett (Strg/Cmd+B)"),
+    ("markdown.italic", "Kursiv (Strg/Cmd+I)"),
+    ("markdown.strikethrough", "Durchgestrichen"),
+    ("markdown.underline", "Unterstrichen"),
+    ("markdown.code.inline", "Code im Text (Strg/Cmd+Umschalt+C)"),
+    ("markdown.code.block", "Codeblock"),
+    ("markdown.code", "Code"),
+    ("markdown.list.bulleted", "Aufzählung"),
+    ("markdown.list.numbered", "Nummerierte Liste"),
+    ("markdown.list", "Liste"),
+    ("markdown.link", "Link (Strg/Cmd+K)"),
+    ("markdown.quote", "Zitat"),
+    ("markdown.image", "Bild"),
+    ("markdown.table", "Tabelle einfügen (Größe wählen)"),
+    ("markdown.rule", "Trennlinie"),
+    ("markdown.math.inline", "Formel im Text"),
+    ("markdown.math.display", "Abgesetzte Formel"),
+    ("markdown.math", "Formel"),
+    ("markdown.image.none", "Noch keine Bildanhänge"),
+    ("markdown.image.embed", "Diesen Anhang einbetten"),
+    ("markdown.image.url", "Bild aus URL"),
+    ("markdown.table.rows", "Zeilen"),
+    ("markdown.table.columns", "Spalten"),
+    ("onboarding.author", "Über Sie"),
+    ("onboarding.instance", "Ihr eLabFTW"),
+    ("onboarding.defaults", "Neue Einträge"),
+    ("onboarding.title", "Willkommen bei ELNPack"),
+    (
+        "onboarding.later",
+        "Alles lässt sich später in den Einstellungen und unter Autoren ändern.",
+    ),
+    ("onboarding.skip", "Einrichtung überspringen"),
+    ("onboarding.next", "Weiter"),
+    ("onboarding.finish", "Fertig"),
+    ("onboarding.back", "Zurück"),
+    (
+        "onboarding.author.text",
+        "Wird in jedem gespeicherten Archiv als Autor genannt. Leer lassen, um anonym zu bleiben.",
+    ),
+    ("onboarding.author.given", "Vorname"),
+    ("onboarding.author.family", "Nachname"),
+    (
+        "onboarding.instance.text",
+        "Die eLabFTW-Instanz, in die Sie Archive importieren. Optional.",
+    ),
+    ("onboarding.instance.address", "Adresse"),
+    (
+        "onboarding.defaults.text",
+        "Womit neue Einträge beginnen; pro Eintrag lässt es sich weiterhin ändern.",
+    ),
+    ("onboarding.defaults.format", "Haupttext exportieren als"),
+    (
+        "authors.save.failed",
+        "Autoren konnten nicht gespeichert werden: {err}",
+    ),
+    ("authors.title", "Autoren"),
+    ("authors.title.count", "Autoren ({count})"),
+    ("authors.add", "Autor hinzufügen"),
+    ("datetime.now", "Jetzt"),
+    (
+        "datetime.hour.invalid",
+        "Die Stunde muss zwischen 0 und 23 liegen",
+    ),
+    (
+        "datetime.minute.invalid",
+        "Die Minute muss zwischen 0 und 59 liegen",
+    ),
+    (
+        "datetime.skipped",
+        "Ungültige Ortszeit (wahrscheinlich durch eine Zeitumstellung übersprungen)",
+    ),
+    ("links.title", "Verwandte Links"),
+    ("links.title.count", "Verwandte Links ({count})"),
+    ("links.add", "Link hinzufügen"),
+    ("fonts.proportional", "Proportional (alle Schriften)"),
+    ("fonts.monospace", "Monospace (alle Schriften)"),
+    (
+        "authors.text",
+        "Werden in dieser Reihenfolge in jedem gespeicherten Archiv genannt. Bleiben zwischen Sitzungen erhalten.",
+    ),
+    ("authors.remove", "Autor entfernen"),
+    ("authors.affiliation", "Einrichtung"),
+    (
+        "datetime.now.hover",
+        "Datum/Uhrzeit auf Ihre aktuelle Ortszeit setzen (als UTC gespeichert)",
+    ),
+    (
+        "datetime.warning.hover",
+        "Beim Speichern werden Sie gebeten, das Datum zu bestätigen.",
+    ),
+    (
+        "links.text",
+        "Experimente oder Ressourcen, auf die sich dieser Eintrag bezieht, zu denen er gehört oder von denen er abgeleitet ist.",
+    ),
+    ("links.label", "Bezeichnung"),
+    ("links.remove", "Link entfernen"),
+    (
+        "draft.autosave.failed",
+        "Entwurf konnte nicht automatisch gesichert werden: {err}",
+    ),
+    (
+        "safe_mode.notice",
+        "Abgesicherter Modus: ELNPack wurde mit Standardeinstellungen gestartet und hat Ihre gespeicherten Einstellungen, Entwürfe, Vorlagen und Listen nicht angerührt. Laden Sie sie einzeln, um die Ursache eines Problems zu finden.",
+    ),
+    ("safe_mode.config", "Konfigurationsordner öffnen"),
+    ("safe_mode.load", "{file} laden"),
+    (
+        "safe_mode.load.hover",
+        "{path} lesen; ELNPack speichert die Datei ab dann auch wieder",
+    ),
+    ("safe_mode.loaded", "Geladen"),
+    ("fonts.coverage.complete", "alle Zeichen"),
+    ("fonts.coverage.missing", "fehlt: {chars}"),
+    ("error.title", "Fehler"),
+    ("error.resume", "Speichern fortsetzen"),
+    ("error.copy", "Details kopieren"),
+    (
+        "missing_file.text",
+        "Beim Speichern kann {path} nicht gelesen werden.",
+    ),
+    (
+        "template_confirm.text",
+        "Wenn Sie mit '{name}' beginnen, werden die aktuellen Einträge und ihre Anhänge verworfen.",
+    ),
+    (
+        "overwrite.text",
+        "{path} durch die aktuellen Einträge ersetzen?",
+    ),
+    ("batch.progress", "{done} von {total} Archiven fertig."),
+    ("status.working", "{count} in Arbeit…"),
+    (
+        "status.background",
+        "{count} Aufgabe(n) laufen im Hintergrund",
+    ),
+    ("status.writing", "Schreibe {file}: {done} / {total}"),
+    (
+        "status.uploading",
+        "Wird zu eLabFTW hochgeladen: {done} / {total}",
+    ),
+    ("batch.waiting", "Wartet"),
+    ("batch.writing", "Wird geschrieben…"),
+    ("batch.cancelled", "Abgebrochen"),
+    (
+        "safe_mode.config.hover",
+        "Den Ordner mit den Einstellungen im Dateimanager anzeigen",
+    ),
+    ("fonts.coverage", "Schriftabdeckung"),
+    ("fonts.coverage.hint", "Zu prüfende Zeichen"),
+    ("error.details", "Details"),
+    (
+        "error.resume.hover",
+        "Die bereits geschriebenen Anhänge behalten und die übrigen hinzufügen",
+    ),
+    ("common.ok", "OK"),
+    (
+        "error.copy.hover",
+        "Die vollständige Meldung kopieren, z. B. für einen Fehlerbericht",
+    ),
+    ("date_confirm.title", "Durchführungsdatum prüfen"),
+    (
+        "date_confirm.text",
+        "Ist das beabsichtigt, z. B. bei einem nachgetragenen Experiment?",
+    ),
+    ("date_confirm.yes", "Ja, das Datum stimmt"),
+    ("size_confirm.title", "Große Anhänge"),
+    (
+        "size_confirm.text",
+        "eLabFTW lehnt den Import so großer Archive eventuell ab. Trotzdem speichern?",
+    ),
+    ("size_confirm.yes", "Trotzdem speichern"),
+    ("missing_file.title", "Anhang fehlt"),
+    (
+        "missing_file.hint",
+        "Er wurde eventuell verschoben, gelöscht oder liegt auf einem getrennten Laufwerk.",
+    ),
+    ("missing_file.retry", "Erneut versuchen"),
+    ("missing_file.skip", "Überspringen"),
+    (
+        "missing_file.skip.hover",
+        "Das Archiv ohne diese Datei speichern",
+    ),
+    ("template_confirm.title", "Mit Vorlage beginnen"),
+    (
+        "template_confirm.hint",
+        "Speichern Sie sie zuerst, wenn Sie sie behalten möchten.",
+    ),
+    ("template_confirm.yes", "Verwerfen und mit Vorlage beginnen"),
+    ("overwrite.title", "Archiv überschreiben"),
+    ("overwrite.yes", "Überschreiben"),
+    ("batch.title", "Stapelverarbeitung"),
+    ("batch.cancel", "Alle abbrechen"),
+    (
+        "batch.cancel.hover",
+        "Die noch nicht fertigen Archive abbrechen",
+    ),
+    ("quit.title", "Ungespeicherte Änderungen"),
+    (
+        "quit.text",
+        "Die Einträge wurden seit dem letzten Speichern geändert.",
+    ),
+    ("quit.question", "Vor dem Beenden als ELN-Archiv speichern?"),
+    ("quit.save", "Speichern und beenden"),
+    ("quit.discard", "Verwerfen"),
+    (
+        "quit.discard.hover",
+        "Ohne Speichern beenden und den automatisch gesicherten Entwurf löschen",
+    ),
+    ("status.history", "Letzte Meldungen"),
+    (
+        "draft.delete_failed",
+        "Gespeicherter Entwurf konnte nicht gelöscht werden: {err}",
+    ),
+    (
+        "keywords.store_failed",
+        "Schlagwortvorschläge konnten nicht gespeichert werden: {err}",
+    ),
+    ("paste.added", "Eingefügtes Bild hinzugefügt ({size})"),
+    ("fields.import.with_tags", "{fields}. {tags} hinzugefügt."),
+    ("upload.done", "Zu eLabFTW hochgeladen: {url}"),
+    ("upload.failed", "Hochladen fehlgeschlagen"),
+    (
+        "upload.cancel.hover",
+        "Hochladen abbrechen; das Archiv bleibt gespeichert",
+    ),
+    ("upload.cancelling", "Hochladen wird abgebrochen…"),
+    ("upload.cancelled", "Hochladen abgebrochen."),
+    (
+        "upload.error",
+        "Das Hochladen zu eLabFTW ist fehlgeschlagen:\n\n{err}\n\nDas Archiv wurde gespeichert und kann manuell importiert werden.",
+    ),
+    ("open.opening", "{path} wird geöffnet…"),
+    ("open.cancelled", "Öffnen abgebrochen."),
+    (
+        "import.failed",
+        "{path} konnte nicht importiert werden: {err}",
+    ),
+    ("open.done", "Archiv geöffnet: {path}"),
+    ("open.failed", "Öffnen fehlgeschlagen"),
+    (
+        "open.error",
+        "Archiv {path} konnte nicht geöffnet werden:\n\n{err}",
+    ),
+    ("trace.exported", "Ereignisprotokoll exportiert: {path}"),
+    (
+        "trace.export_failed",
+        "Ereignisprotokoll konnte nicht exportiert werden: {err}",
+    ),
+    (
+        "help.open_failed",
+        "Hilfeseite konnte nicht geöffnet werden: {err}",
+    ),
+    (
+        "folder.open_failed",
+        "Ordner konnte nicht geöffnet werden: {err}",
+    ),
+    (
+        "import.read_failed",
+        "Datei konnte nicht gelesen werden: {err}",
+    ),
+    ("templates.saved", "Vorlage '{name}' gespeichert."),
+    (
+        "templates.load_failed",
+        "Vorlage '{name}' konnte nicht geladen werden: {err}",
+    ),
+    ("templates.renamed", "Vorlage '{from}' in '{to}' umbenannt."),
+    ("templates.deleted", "Vorlage '{name}' gelöscht."),
+    (
+        "fields.import.source_gone",
+        "Die Metadatendatei {path} existiert nicht mehr; importieren Sie sie erneut von ihrem neuen Ort.",
+    ),
+    (
+        "fields.import.read_failed",
+        "Metadatendatei konnte nicht gelesen werden: {err}",
+    ),
+    (
+        "draft.restored.status",
+        "Ungespeicherter Entwurf der letzten Sitzung wiederhergestellt.",
+    ),
+    (
+        "draft.restored.missing",
+        "Entwurf wiederhergestellt; {count} Anhang/Anhänge fehlten oder wurden geändert und wurden entfernt: {names}",
+    ),
+    ("templates.applied", "Neuer Eintrag aus Vorlage '{name}'."),
+    (
+        "save.keywords_adjusted",
+        "Schlagwörter angepasst: {problems}",
+    ),
+    (
+        "save.missing_references",
+        "der Haupttext verweist auf Anhänge, die nicht im Archiv sind: {problems}",
+    ),
+    (
+        "save.main_text_hidden",
+        "eLabFTW zeigt den Haupttext nicht an, da \"Haupttext in eLabFTW anzeigen\" ausgeschaltet ist",
+    ),
+    (
+        "save.main_text_hidden.of",
+        "eLabFTW zeigt den Haupttext nicht an von: {entries}",
+    ),
+    ("save.oversized.one", "{name} überschreitet {limit}"),
+    (
+        "save.oversized.many",
+        "{count} Anhänge überschreiten {limit} ({names})",
+    ),
+    (
+        "save.related_link_invalid",
+        "Verwandter Link {index}: {err}",
+    ),
+    ("draft.discarded", "Entwurf verworfen."),
+    (
+        "paste.unavailable",
+        "Das Einfügen von Bildern ist nicht verfügbar.",
+    ),
+    ("templates.unavailable", "Vorlagen sind nicht verfügbar."),
+    (
+        "templates.editor_open",
+        "Schließen oder verwerfen Sie zuerst den Feldeditor und wenden Sie dann die Vorlage an.",
+    ),
+    ("onboarding.saved", "Einrichtung gespeichert."),
+    (
+        "onboarding.skipped",
+        "Einrichtung übersprungen. Sie können sie jederzeit über das Hilfe-Menü erneut starten.",
+    ),
+    ("help.opening", "ELNPack-Handbuch wird im Browser geöffnet…"),
+    (
+        "trace.disabled",
+        "Starten Sie ELNPack mit --debug-events, um Ereignisse aufzuzeichnen.",
+    ),
+    ("help.opened", "Hilfe im Browser geöffnet."),
+    ("dialog.attachments", "Anhänge auswählen"),
+    ("dialog.attachment_folder", "Anhangsordner auswählen"),
+    ("dialog.metadata", "eLabFTW-Metadaten-JSON auswählen"),
+    ("dialog.entry", "eLabFTW-Eintrags-JSON auswählen"),
+    (
+        "dialog.batch_source",
+        "Ordner mit einem Unterordner pro Eintrag auswählen",
+    ),
+    ("dialog.batch_output", "Ordner für die Archive auswählen"),
+    ("dialog.export_settings", "Einstellungen exportieren"),
+    ("dialog.template_archive", "Vorlagenarchiv speichern"),
+    (
+        "entries.blocked.dialog",
+        "Bitte schließen Sie zuerst den Dateidialog.",
+    ),
+    (
+        "entries.blocked.pending",
+        "Bitte warten Sie, bis alle Anhänge verarbeitet wurden.",
+    ),
+    ("safe_mode.loaded_file", "{file} geladen."),
+    (
+        "persisted.settings",
+        "Einstellungen und zuletzt verwendete Archive",
+    ),
+    ("persisted.vocabulary", "Schlagwortvorschläge"),
+    ("persisted.templates", "Vorlagen"),
+    ("persisted.api_key", "eLabFTW-API-Schlüssel"),
+    ("persisted.draft", "Ungespeicherter Entwurf"),
+    (
+        "persisted.settings.failed",
+        "Einstellungen konnten nicht geladen werden; Standardwerte werden verwendet",
+    ),
+    (
+        "persisted.vocabulary.failed",
+        "Schlagwortvorschläge konnten nicht geladen werden",
+    ),
+    (
+        "persisted.authors.failed",
+        "Autoren konnten nicht geladen werden",
+    ),
+    (
+        "persisted.templates.failed",
+        "Vorlagen konnten nicht geladen werden",
+    ),
+    (
+        "persisted.api_key.failed",
+        "Der eLabFTW-API-Schlüssel konnte nicht geladen werden",
+    ),
+    (
+        "persisted.draft.failed",
+        "Gespeicherter Entwurf konnte nicht wiederhergestellt werden",
+    ),
+    (
+        "safe_mode.started",
+        "Im abgesicherten Modus mit Standardeinstellungen gestartet; gespeicherte Dateien wurden nicht geladen.",
+    ),
+    (
+        "validate.title_length",
+        "Der Titel hat {length} Zeichen; eLabFTW übernimmt höchstens {limit}.",
+    ),
+    ("validate.title", "Bitte geben Sie einen Titel ein."),
+    (
+        "validate.summary_length",
+        "Die Zusammenfassung hat {length} Zeichen; bitte kürzen Sie sie auf höchstens {limit}.",
+    ),
+    (
+        "validate.field_twice",
+        "Das Zusatzfeld \"{label}\" gibt es zweimal. eLabFTW erkennt Felder an ihrer Bezeichnung, bitte benennen Sie eines davon um.",
+    ),
+    (
+        "validate.field_case",
+        "Die Zusatzfelder \"{first}\" und \"{second}\" unterscheiden sich nur in der Groß-/Kleinschreibung. eLabFTW erkennt Felder an ihrer Bezeichnung, bitte benennen Sie eines davon um.",
+    ),
+    (
+        "validate.blocked_type",
+        "Gesperrter Anhangstyp '{pattern}': geben Sie eine Endung wie exe oder einen MIME-Typ wie video/* an.",
+    ),
+    (
+        "validate.reserved_name",
+        "Anhang {path} verwendet einen Namen, der für die Dateien des Archivs reserviert ist; benennen Sie ihn um.",
+    ),
+    (
+        "validate.acquired_future",
+        "Anhang {path} hat einen Aufnahmezeitpunkt in der Zukunft.",
+    ),
+    (
+        "validate.duplicate_path",
+        "Doppelter Anhangsname im Archiv: {path}",
+    ),
+    (
+        "validate.file_and_folder",
+        "Anhangspfad wird zugleich als Datei und als Ordner verwendet: {path}",
+    ),
+    (
+        "validate.author_name",
+        "Bitte geben Sie den Namen der Autorin oder des Autors ein.",
+    ),
+    ("validate.email", "Die E-Mail-Adresse {email} ist ungültig."),
+    (
+        "validate.orcid_form",
+        "Die ORCID iD {orcid} muss die Form xxxx-xxxx-xxxx-xxxx haben.",
+    ),
+    (
+        "validate.orcid_check",
+        "Die ORCID iD {orcid} hat eine ungültige Prüfziffer.",
+    ),
+    ("fields.error.required", "ist erforderlich"),
+    ("fields.error.url", "muss eine gültige http/https-URL sein"),
+    ("fields.error.number", "muss eine gültige Zahl sein"),
+    (
+        "fields.error.integer",
+        "muss eine gültige ganzzahlige ID sein",
+    ),
+    (
+        "fields.error.email",
+        "muss eine gültige E-Mail-Adresse sein",
+    ),
+    ("fields.error.date", "muss ein Datum wie 2024-05-31 sein"),
+    (
+        "fields.error.date_range",
+        "muss aus zwei Daten wie 2024-05-01/2024-05-31 bestehen, das Ende nicht vor dem Anfang",
+    ),
+    ("fields.error.time", "muss eine Uhrzeit wie 14:30 sein"),
+    (
+        "fields.error.datetime",
+        "muss Datum und Uhrzeit wie 2024-05-31T14:30 sein",
+    ),
+    ("fields.error.named", "Feld '{label}' {error}."),
+    ("fields.error.inline", "Dieses Feld {error}."),
+    (
+        "keywords.report.adjusted",
+        "\"{from}\" als \"{to}\" gespeichert",
+    ),
+    (
+        "keywords.report.empty",
+        "leeres Schlagwort \"{keyword}\" entfernt",
+    ),
+    (
+        "keywords.report.duplicate",
+        "\"{keyword}\" entfernt (Duplikat von \"{kept}\")",
+    ),
+    (
+        "keywords.report.over_limit",
+        "\"{keyword}\" entfernt (über der Schlagwortgrenze)",
+    ),
+    (
+        "validate.license_url",
+        "Bitte geben Sie die URL der Lizenz ein oder wählen Sie eine Lizenz aus der Liste.",
+    ),
+    (
+        "validate.license_invalid",
+        "Die Lizenz {url} muss eine gültige http/https-URL sein.",
+    ),
+    (
+        "date.warning.future",
+        "Das Durchführungsdatum liegt in der Zukunft.",
+    ),
+    (
+        "date.warning.old",
+        "Das Durchführungsdatum liegt mehr als {years} Jahr(e) zurück.",
+    ),
+    ("links.relation.references", "verweist auf"),
+    ("links.relation.part_of", "ist Teil von"),
+    ("links.relation.derived_from", "abgeleitet von"),
+    (
+        "validate.link_url",
+        "Bitte geben Sie die URL des Links ein.",
+    ),
+    (
+        "validate.link_invalid",
+        "{url} muss eine gültige http/https-URL sein.",
+    ),
+    (
+        "open_confirm.text",
+        "Beim Öffnen von {path} werden sie verworfen.",
+    ),
+    ("open_confirm.yes", "Verwerfen und öffnen"),
+    (
+        "open.cleanup_failed",
+        "Die entpackten Dateien des vorigen Archivs konnten nicht gelöscht werden: {err}",
+    ),
+    (
+        "save.insufficient_space",
+        "Nicht genug freier Speicherplatz: Das Archiv braucht etwa {needed} MB, auf dem Laufwerk sind aber nur {available} MB frei. Geben Sie Speicherplatz frei oder speichern Sie auf ein anderes Laufwerk.",
+    ),
+    (
+        "save.unreadable_archive",
+        "Das geschriebene Archiv {path} lässt sich nicht wieder lesen: {err}",
+    ),
+    (
+        "save.damaged_archive",
+        "Das geschriebene Archiv {path} ist beschädigt; diese Dateien passen nicht zu seinen Metadaten:\n\n{report}",
+    ),
+    (
+        "save.attachment_modified",
+        "Anhang wurde seit dem Hinzufügen geändert:\n  {path}\n  erwartet {key} {expected}\n  gefunden {key} {found}",
+    ),
+    (
+        "save.omitted_comment",
+        "Weggelassen, weil die Dateien beim Schreiben des Archivs nicht lesbar waren: {files}",
+    ),
+    (
+        "save.metadata_unreadable",
+        "Die importierte Metadatendatei {path} konnte nicht gelesen werden; schalten Sie Importierte Metadatendateien einschließen unter Einstellungen → Prüfsummen aus, um ohne sie zu speichern",
+    ),
+    (
+        "save.metadata_changed",
+        "Die Metadatendatei {path} hat sich geändert, seit die Zusatzfelder daraus importiert wurden. Importieren Sie sie erneut oder schalten Sie Importierte Metadatendateien einschließen unter Einstellungen → Prüfsummen aus.",
+    ),
+    (
+        "upload.no_target",
+        "Tragen Sie in den Einstellungen die eLabFTW-Adresse und den API-Schlüssel ein, um Archive hochzuladen.",
+    ),
+    (
+        "upload.unreadable",
+        "{path} konnte nicht gelesen werden: {err}",
+    ),
+    (
+        "upload.unreachable",
+        "eLabFTW unter {url} ist nicht erreichbar: {err}. Prüfen Sie die Adresse in den Einstellungen und Ihre Netzwerkverbindung.",
+    ),
+    (
+        "upload.rejected_key",
+        "eLabFTW hat den API-Schlüssel abgelehnt (HTTP {status}). Erstellen Sie in Ihren eLabFTW-Benutzereinstellungen einen Schlüssel mit Schreibrechten und tragen Sie ihn in den Einstellungen ein.",
+    ),
+    (
+        "upload.too_large",
+        "Das Archiv ist zu groß für diesen eLabFTW-Server (HTTP 413).",
+    ),
+    (
+        "upload.not_imported",
+        "eLabFTW konnte das Archiv nicht importieren (HTTP {status}).",
+    ),
+    (
+        "upload.not_imported.reply",
+        "eLabFTW konnte das Archiv nicht importieren (HTTP {status}): {reply}",
+    ),
+    (
+        "validate.language_code",
+        "Die Sprache muss ein Code wie \"en\", \"de\" oder \"en-GB\" sein.",
+    ),
+    (
+        "signing.wrong_key",
+        "Das Archiv wurde mit dem Schlüssel {signer} signiert, nicht mit dem erwarteten Schlüssel {expected}.",
+    ),
+    (
+        "signing.mismatch",
+        "Die Signatur passt nicht zum Archiv; es wurde nach dem Signieren verändert oder neu gespeichert.",
+    ),
+    (
+        "signing.recorded_key",
+        "Das Archiv nennt den Signaturschlüssel {recorded}, die Signatur wurde aber mit {signer} erstellt.",
+    ),
+    (
+        "entry_import.no_entry",
+        "Die Datei enthält keinen eLabFTW-Eintrag; exportieren Sie ein einzelnes Experiment oder eine einzelne Ressource als JSON.",
+    ),
+    (
+        "entry_import.date",
+        "Das Eintragsdatum {date} ist kein Datum, das ELNPack versteht.",
+    ),
+    (
+        "entry_import.empty",
+        "Der Eintrag hat weder Titel, Text, Schlagwörter, Datum noch Zusatzfelder zum Importieren.",
+    ),
+    (
+        "batch.no_jobs",
+        "Kein Unterordner von {folder} enthält eine {file}.",
+    ),
+    (
+        "settings.units.no_equals",
+        "Einheitencodes Zeile {line}: Schreiben Sie sie als 'Einheit = Code'.",
+    ),
+    (
+        "settings.units.invalid",
+        "Einheitencodes Zeile {line}: Erwartet werden eine Einheit und ein UCUM-Code ohne Leerzeichen.",
+    ),
+];
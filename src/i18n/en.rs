@@ -0,0 +1,1561 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! English texts; every key used in code has one.
+
+pub(super) const EN: &[(&str, &str)] = &[
+    ("app.heading", "ELN Entry"),
+    ("top.language.hover", "Language of the user interface"),
+    ("top.compact.hover", "Compact mode for small screens"),
+    ("help.menu", "Help"),
+    ("help.menu.hover", "User guide and first-run setup"),
+    ("help.guide", "User guide"),
+    (
+        "help.guide.hover",
+        "Open the ELNPack user guide in your browser",
+    ),
+    ("help.setup", "Welcome setup…"),
+    (
+        "help.setup.hover",
+        "Set your name, eLabFTW address and entry defaults again",
+    ),
+    ("help.trace", "Export event trace…"),
+    (
+        "help.trace.hover",
+        "Save recent actions for a bug report; typed text is reduced to its length",
+    ),
+    ("help.trace.dialog", "Export event trace"),
+    ("settings.button", "Settings"),
+    ("settings.button.hover", "Configure advanced export options"),
+    ("top.preview", "HTML preview"),
+    (
+        "top.preview.hover",
+        "Also write a standalone index.html preview into the archive",
+    ),
+    ("top.upload", "Upload"),
+    (
+        "top.upload.hover",
+        "Import the next saved archive into eLabFTW; uncheck to skip this save only",
+    ),
+    (
+        "top.crate_version.hover",
+        "Saved archives target {version}; choose RO-Crate 1.1 for eLabFTW 4.x and older tools",
+    ),
+    (
+        "top.compression.hover",
+        "Compression of the next save; Auto stores images, videos, and archives uncompressed. The default is set under Settings → Compression",
+    ),
+    ("top.templates", "Templates"),
+    ("top.open", "Open"),
+    ("top.open.hover", "Open an existing ELN archive for editing"),
+    ("top.open.dialog", "Open ELN archive"),
+    ("top.batch", "Batch"),
+    (
+        "top.batch.hover",
+        "Write one archive per subfolder holding a body.md",
+    ),
+    (
+        "format.markdown.hover",
+        "Store the raw markdown in the archive metadata",
+    ),
+    (
+        "format.html.hover",
+        "Convert markdown to HTML in the archive metadata",
+    ),
+    ("format.both", "Both"),
+    (
+        "format.both.hover",
+        "Convert markdown to HTML in the archive metadata and keep the markdown as body.md",
+    ),
+    ("format.label", "Export as"),
+    ("save.button", "Save ELN archive"),
+    ("save.button.hover", "Save the entry as an ELN archive"),
+    (
+        "save.disabled_hint",
+        "Please enter a title, fix required/invalid fields, and wait for attachments to finish processing",
+    ),
+    ("save.cancel", "Cancel save"),
+    (
+        "save.cancel.hover",
+        "Stop writing the archive and delete the partial file",
+    ),
+    ("save.save_as", "Save As…"),
+    (
+        "save.save_as.hover",
+        "Save the entry as an ELN archive under a new name",
+    ),
+    ("save.save", "Save"),
+    ("save.save.hover", "Overwrite {path}"),
+    ("recent.heading", "Save over a recent archive"),
+    ("recent.missing", "This archive no longer exists."),
+    ("recent.remove_missing", "Remove missing archives"),
+    ("recent.hover", "Recent archives"),
+    ("entries.heading", "Entries"),
+    ("entries.add.hover", "Add an empty entry"),
+    ("entries.duplicate.hover", "Duplicate the selected entry"),
+    ("entries.remove.hover", "Remove the selected entry"),
+    ("entries.copy_title", "{title} (copy)"),
+    (
+        "entries.busy",
+        "Wait until all attachments have been processed",
+    ),
+    ("entry.label", "Entry {number}"),
+    ("entry.label_titled", "Entry {number} ({title})"),
+    ("title.label", "Title"),
+    ("title.hint", "e.g., Cell viability assay day 3"),
+    ("summary.label", "Summary"),
+    (
+        "summary.hint",
+        "One or two sentences shown in archive listings (optional)",
+    ),
+    ("summary.counter", "{count}/{max} characters"),
+    ("body.label", "Main Text"),
+    (
+        "body.hint",
+        "Use Markdown to format text. Tab indents; Ctrl+Tab moves on to the next field.",
+    ),
+    ("genre.experiment", "Experiment"),
+    ("genre.resource", "Resource"),
+    ("genre.template", "Template"),
+    ("meta.entry_type", "Entry type"),
+    ("meta.performed_at", "Performed at"),
+    (
+        "meta.performed_at.hover",
+        "Performed at (local time, stored as UTC)",
+    ),
+    ("meta.language", "Language"),
+    ("meta.language.hint", "auto"),
+    (
+        "meta.language.hover",
+        "Language of the main text, e.g. en, de or en-GB. Leave empty to detect it when saving.",
+    ),
+    ("meta.license", "License"),
+    (
+        "meta.license.hover",
+        "License declared for the entry in the archive metadata; new entries start with the last one chosen",
+    ),
+    ("license.none", "None / proprietary"),
+    ("license.other", "Other (URL)"),
+    ("license.url.hover", "Web address of the license text"),
+    (
+        "meta.utc_note",
+        "Times are shown in your local time zone and stored as UTC in the archive.",
+    ),
+    ("summary.heading", "Summary"),
+    ("summary.unknown", "unknown"),
+    ("summary.words", "Words in main text"),
+    ("summary.figures", "Figures embedded / attached"),
+    ("summary.required", "Required fields filled"),
+    ("summary.required_value", "{filled} of {total}"),
+    ("summary.data_volume", "Data volume"),
+    ("summary.data_value", "{size} in {count} file(s)"),
+    ("summary.acquired", "Acquired"),
+    ("summary.consistent", "Main text and attachments agree."),
+    (
+        "summary.missing_attachments",
+        "The main text refers to files that are not attached: {files}",
+    ),
+    (
+        "summary.unmentioned",
+        "Attachments not mentioned in the main text: {files}",
+    ),
+    (
+        "summary.required_empty",
+        "{count} required field(s) are empty.",
+    ),
+    (
+        "summary.missing_policy",
+        "If an attachment is missing while saving:",
+    ),
+    (
+        "draft.restored",
+        "Restored unsaved draft from your previous session.",
+    ),
+    ("draft.discard", "Discard draft"),
+    (
+        "draft.discard.hover",
+        "Clear the form and delete the saved draft",
+    ),
+    ("draft.keep", "Keep"),
+    ("save.running", "A save is already running."),
+    ("save.blocked.title", "Enter a title before saving."),
+    (
+        "save.blocked.fields",
+        "Fix the fields marked in the metadata section before saving.",
+    ),
+    (
+        "save.blocked.attachments",
+        "Wait for the attachments to finish processing before saving.",
+    ),
+    ("save.cancelled", "Save cancelled."),
+    ("save.cancelling", "Cancelling save…"),
+    (
+        "save.skipped_files",
+        "{count} missing attachment(s) were left out ({names})",
+    ),
+    ("save.failed", "Save failed"),
+    ("save.error", "Failed to save archive:\n\n{error}"),
+    (
+        "save.interrupted",
+        "Failed to save archive:\n\n{error}\n\nThe attachments written so far were kept. Resume the save to write only the rest.",
+    ),
+    (
+        "save.resumed",
+        "Resuming the save; {count} attachment(s) were already written.",
+    ),
+    (
+        "save.restarted",
+        "Could not resume the save ({reason}); writing the archive again.",
+    ),
+    ("save.done.archive", "Archive saved: {path} ({version})"),
+    (
+        "save.done.template",
+        "Template archive saved: {path} ({version})",
+    ),
+    ("save.done.license", ". License: {license}"),
+    ("save.done.stats", " – {size} in {seconds} s"),
+    ("batch.busy", "A batch is still running."),
+    ("batch.error_title", "Batch packaging failed"),
+    ("batch.started", "Writing {count} archives…"),
+    (
+        "batch.done",
+        "Batch finished: {written} archives written, {failed} failed, {cancelled} cancelled.",
+    ),
+    ("save.done.warning", ". Warning: {warning}"),
+    ("save.done.signed", ". Signed: {path}"),
+    ("save.done.uploading", ". Uploading to eLabFTW…"),
+    ("save.signing_failed", "Signing failed"),
+    (
+        "save.signing_error",
+        "{message}\n\nThe archive was not signed:\n\n{error}",
+    ),
+    ("save.validation_error", "Validation error"),
+    (
+        "save.invalid_html",
+        "Invalid HTML export settings:\n{errors}",
+    ),
+    ("save.no_title", "Please enter a title."),
+    (
+        "save.pending_hashes",
+        "Please wait until all attachments have been processed.",
+    ),
+    ("save.invalid_language", "Invalid language: {error}"),
+    ("save.invalid_datetime", "Invalid date/time: {error}"),
+    ("save.author_error", "Author {number}: {error}"),
+    (
+        "save.author_orcid_twice",
+        "Author {number}: ORCID iD {orcid} is listed twice.",
+    ),
+    ("common.cancel", "Cancel"),
+    ("common.remove", "Remove"),
+    ("common.apply", "Apply"),
+    ("common.save", "Save"),
+    ("attachments.title", "Attachments"),
+    ("attachments.state.unverified", "unverified"),
+    ("attachments.state.verified", "verified"),
+    ("attachments.state.reverified", "re-verified"),
+    ("attachments.state.changed", "changed"),
+    ("attachments.state.unreadable", "unreadable"),
+    (
+        "attachments.state.unverified.details",
+        "Not read in this session; the content is checked when saving.",
+    ),
+    (
+        "attachments.state.verified.details",
+        "Hashed when added ({when}).",
+    ),
+    (
+        "attachments.state.reverified.details",
+        "Still matched its hash on {when}.",
+    ),
+    (
+        "attachments.state.changed.details",
+        "The file changed since it was added. Remove and add it again to include the new content.",
+    ),
+    (
+        "attachments.state.unreadable.details",
+        "The file could not be read.",
+    ),
+    ("attachments.processing", "Processing attachments..."),
+    (
+        "attachments.folder.too_large",
+        "Folder {folder} contains more than {max} files. Please add a smaller folder.",
+    ),
+    (
+        "attachments.folder.empty",
+        "Folder {folder} contains no files.",
+    ),
+    ("attachments.folder.failed", "Failed to read folder: {err}"),
+    ("attachments.folder.confirm", "Add folder?"),
+    (
+        "attachments.folder.confirm.text",
+        "{folder} contains {count} files. Add all of them?",
+    ),
+    (
+        "attachments.folder.processing",
+        "Processing {count} file(s) from {folder}...",
+    ),
+    (
+        "attachments.blocked",
+        "{name} was not added: {pattern} files are blocked by the attachment policy in Settings.",
+    ),
+    ("attachments.added", "Attachment added"),
+    (
+        "attachments.added_renamed",
+        "Attachment added as {to}; {from} is reserved for the archive's own files.",
+    ),
+    (
+        "attachments.skipped",
+        "Attachment skipped (duplicate or invalid)",
+    ),
+    (
+        "attachments.oversized",
+        "{name} is {size}, larger than the {limit} the attachment policy allows without asking.",
+    ),
+    (
+        "attachments.oversized.hover",
+        "Larger than {limit}; saving asks for confirmation and eLabFTW may reject the file.",
+    ),
+    ("attachments.cancelled", "Attachment cancelled"),
+    ("attachments.removed", "Attachment removed"),
+    ("attachments.broken", "{count} unreadable or changed"),
+    (
+        "attachments.blocker",
+        "Saving waits until all attachments have been processed.",
+    ),
+    ("attachments.add_files", "Add files"),
+    ("attachments.add_folder", "Add folder"),
+    (
+        "attachments.add_folder.hover",
+        "Add all files of a folder, keeping its structure",
+    ),
+    ("attachments.layout.table", "Table"),
+    ("attachments.layout.cards", "Cards"),
+    (
+        "attachments.layout.hover",
+        "Switch between detailed cards and a sortable table",
+    ),
+    ("attachments.sort", "Sort by name"),
+    (
+        "attachments.sort.hover",
+        "Order attachments by folder and file name",
+    ),
+    (
+        "attachments.set_acquired.one",
+        "Set acquired time for 1 selected file",
+    ),
+    (
+        "attachments.set_acquired.many",
+        "Set acquired time for {count} selected files",
+    ),
+    ("attachments.none", "No attachments"),
+    (
+        "attachments.select.hover",
+        "Select for bulk actions (shift-click selects a range)",
+    ),
+    ("attachments.sanitized", "Filename sanitized:"),
+    (
+        "attachments.name.hover",
+        "Rename (Enter); Delete removes the file",
+    ),
+    ("attachments.edit_name", "Edit filename"),
+    ("attachments.acquired", "acquired {when}"),
+    ("attachments.acquired.unknown", "acquisition time unknown"),
+    ("attachments.acquired.set", "Set acquisition time"),
+    ("attachments.acquired.title", "Acquired at: {name}"),
+    ("attachments.acquired.title.bare", "Acquired at"),
+    (
+        "attachments.acquired.title.many",
+        "Acquired at: {count} files",
+    ),
+    (
+        "attachments.acquired.future",
+        "Acquisition time cannot be in the future.",
+    ),
+    (
+        "attachments.acquired.done",
+        "Acquisition time set for {count} file(s).",
+    ),
+    ("attachments.figure", "Include in figure list"),
+    (
+        "attachments.figure.hover",
+        "List this image with its caption under Figures in the HTML preview",
+    ),
+    ("attachments.remove.hover", "Remove attached file"),
+    ("attachments.move_down", "Move down"),
+    ("attachments.move_up", "Move up"),
+    ("attachments.preview", "Preview contents"),
+    ("attachments.renaming", "Renaming {name}"),
+    ("attachments.column.name", "Name"),
+    ("attachments.column.type", "Type"),
+    ("attachments.column.size", "Size"),
+    (
+        "attachments.column.hover",
+        "Sort the table; the archive keeps its own order",
+    ),
+    ("attachments.rename", "Rename"),
+    ("attachments.remove_ellipsis", "Remove…"),
+    ("attachments.remove.confirm", "Remove attachment?"),
+    (
+        "attachments.remove.confirm.text",
+        "Remove {name} from the entry?",
+    ),
+    ("attachments.preview.title", "Preview: {name}"),
+    ("attachments.copy_path", "Copy path"),
+    ("attachments.preview.loading", "Loading preview…"),
+    ("attachments.preview.failed", "No preview: {err}"),
+    (
+        "attachments.preview.lossy",
+        "Not valid UTF-8; unreadable bytes are shown as \u{fffd}.",
+    ),
+    (
+        "attachments.preview.truncated",
+        "Showing the first {lines} lines.",
+    ),
+    (
+        "attachments.preview.binary",
+        "No text preview for this type. First {shown} of {size}:",
+    ),
+    ("attachments.description", "Description"),
+    (
+        "attachments.description.count",
+        "Description ({count} chars)",
+    ),
+    (
+        "attachments.description.hint",
+        "Caption shown with the file in eLabFTW",
+    ),
+    ("attachments.description.characters", "{count} characters"),
+    ("attachments.hashing", "sha256 computing…"),
+    ("attachments.hashing.cancel", "Cancel hashing and remove"),
+    (
+        "attachments.review",
+        "The name will be changed for the archive:",
+    ),
+    ("attachments.review.accept", "Use changed name"),
+    ("attachments.review.revise", "Keep editing"),
+    ("attachments.name.empty", "Filename cannot be empty."),
+    (
+        "attachments.name.invalid",
+        "Filename is invalid after sanitization.",
+    ),
+    (
+        "attachments.name.reserved",
+        "{name} is reserved for the archive's own files.",
+    ),
+    (
+        "attachments.name.duplicate",
+        "Another attachment already uses this filename in the archive.",
+    ),
+    ("attachments.name.updated", "Attachment filename updated."),
+    (
+        "attachments.thumbnail.unsupported",
+        "No preview: unsupported format\n{reason}",
+    ),
+    (
+        "attachments.thumbnail.decode",
+        "No preview: the image could not be read\n{reason}",
+    ),
+    (
+        "attachments.thumbnail.retry",
+        "Try loading the preview again",
+    ),
+    (
+        "settings.api_key.failed",
+        "Failed to store the eLabFTW API key: {err}",
+    ),
+    ("settings.exported", "Settings exported: {path}"),
+    ("settings.export.failed", "Failed to export settings: {err}"),
+    ("settings.import.failed", "Failed to import settings: {err}"),
+    ("settings.save.failed", "Failed to save settings: {err}"),
+    (
+        "settings.upload.incomplete",
+        "Uploading after saving needs the eLabFTW address and an API key.",
+    ),
+    ("settings.horizon.years", "years"),
+    ("settings.favorites.missing", "Not found: {path}"),
+    (
+        "settings.share.key_warning",
+        "Anyone with the exported file can use your eLabFTW account.",
+    ),
+    ("settings.share.export", "Export settings…"),
+    ("settings.share.import", "Import settings…"),
+    ("settings.keywords.clear", "Clear keyword suggestions"),
+    ("settings.favorite.current", "Dialog starts in {path}"),
+    (
+        "settings.favorite.hover",
+        "Choose a favorite folder for this dialog",
+    ),
+    ("settings.imported", "Settings imported."),
+    ("settings.publisher.address", "Publisher address"),
+    ("settings.keywords.cleared", "Keyword suggestions cleared."),
+    ("settings.saved", "Settings saved."),
+    ("settings.import.title", "Import settings"),
+    (
+        "settings.import.same",
+        "The imported settings match the current ones.",
+    ),
+    (
+        "settings.import.changes",
+        "Importing replaces these settings:",
+    ),
+    (
+        "settings.import.key",
+        "The file also contains an eLabFTW API key, which replaces yours.",
+    ),
+    ("settings.import.confirm", "Import"),
+    ("settings.compression.method", "Method"),
+    (
+        "settings.compression.method.hover",
+        "Auto stores images, videos, and archives, which are compressed already, and deflates everything else",
+    ),
+    ("settings.compression.level", "Deflate level"),
+    (
+        "settings.compression.level.hover",
+        "Higher levels write smaller archives more slowly; the metadata is always deflated",
+    ),
+    ("settings.hashing.compute", "Compute"),
+    (
+        "settings.hashing.sha256",
+        "Always computed: archives record SHA-256 for every file",
+    ),
+    (
+        "settings.hashing.primary_required",
+        "Shown in the attachments list, so always computed",
+    ),
+    (
+        "settings.hashing.hover",
+        "Applies to files added from now on; digests are checked again when saving",
+    ),
+    ("settings.hashing.primary", "Show in attachments list"),
+    (
+        "settings.hashing.manifest",
+        "Write checksums.sha512 manifest",
+    ),
+    (
+        "settings.hashing.manifest.hover",
+        "Add a sha512sum-compatible list of all attachments to the archive root",
+    ),
+    ("settings.verify", "Verify archive after saving"),
+    (
+        "settings.verify.hover",
+        "Reopen the saved archive and check every file's size and SHA-256, e.g. against truncated writes to network shares",
+    ),
+    (
+        "settings.imported_metadata",
+        "Include imported metadata files",
+    ),
+    (
+        "settings.imported_metadata.hover",
+        "Copy the eLabFTW JSON that extra fields were imported from, unchanged, as imported_metadata.json next to the reconstructed metadata, so audits can compare both",
+    ),
+    ("settings.title", "Settings"),
+    ("settings.advanced", "Advanced export"),
+    (
+        "settings.advanced.text",
+        "Extra HTML kept when exporting the body as HTML. Scripts, styles, frames and forms are never allowed.",
+    ),
+    ("settings.advanced.tags", "Allowed tags"),
+    ("settings.advanced.tags.hint", "e.g., section, mark"),
+    ("settings.advanced.classes", "Allowed classes"),
+    (
+        "settings.advanced.classes.hint",
+        "e.g., callout-box, highlight",
+    ),
+    (
+        "settings.advanced.tip",
+        "Tip: `::: note` … `:::` blocks in the body become <div class=\"callout note\"> boxes.",
+    ),
+    ("settings.archive", "Archive"),
+    ("settings.zip_comment", "Write summary into zip comment"),
+    (
+        "settings.zip_comment.hover",
+        "Title, date and keywords become visible in file managers and `unzip -l` without extracting",
+    ),
+    ("settings.readme", "Write README.txt"),
+    (
+        "settings.readme.hover",
+        "Add a plain-text description with title, date, authors, keywords, main text and attachments for repositories that require one",
+    ),
+    ("settings.readme.license", "README license"),
+    ("settings.readme.license.hint", "e.g., CC-BY-4.0"),
+    (
+        "settings.readme.license.hover",
+        "License stated in README.txt; leave empty to omit it",
+    ),
+    ("settings.signing_key", "Signing key"),
+    (
+        "settings.signing_key.hint",
+        "e.g., /home/me/.ssh/elnpack_ed25519",
+    ),
+    (
+        "settings.signing_key.hover",
+        "Ed25519 private key without passphrase (ssh-keygen -t ed25519); each saved archive gets a .sig file next to it. Leave empty to save unsigned archives",
+    ),
+    ("settings.elabftw.url", "eLabFTW address"),
+    (
+        "settings.elabftw.url.hint",
+        "e.g., https://elab.example.org",
+    ),
+    (
+        "settings.elabftw.url.hover",
+        "The eLabFTW instance your archives are imported into",
+    ),
+    ("settings.elabftw.key", "eLabFTW API key"),
+    (
+        "settings.elabftw.key.hover",
+        "Create a key with write access under User panel → API keys; it is stored in a separate file readable only by you",
+    ),
+    ("settings.elabftw.upload", "Upload to eLabFTW after saving"),
+    (
+        "settings.elabftw.upload.hover",
+        "Import each saved archive into the instance above; can be skipped per save next to the Save button",
+    ),
+    ("settings.publisher", "Publisher"),
+    (
+        "settings.publisher.text",
+        "Organization credited as publisher of every archive. Leave the name empty to credit ELNPack.",
+    ),
+    ("settings.publisher.name", "Name"),
+    (
+        "settings.publisher.name.hint",
+        "e.g., Institute of Structural Biology",
+    ),
+    ("settings.publisher.url", "Web address"),
+    (
+        "settings.publisher.url.hint",
+        "e.g., https://www.example.org",
+    ),
+    ("settings.publisher.url.hover", "Leave empty to omit it"),
+    ("settings.publisher.ror", "ROR ID"),
+    (
+        "settings.publisher.ror.hint",
+        "e.g., https://ror.org/05a28rw58",
+    ),
+    (
+        "settings.publisher.ror.hover",
+        "Research Organization Registry identifier, see ror.org; identifies the organization unambiguously",
+    ),
+    ("settings.compression", "Compression"),
+    ("settings.checksums", "Checksums"),
+    ("settings.validation", "Validation"),
+    ("settings.horizon", "Confirm performed-at dates older than"),
+    ("settings.horizon.hover", "Set to 0 to disable the check"),
+    ("settings.units", "Unit codes"),
+    (
+        "settings.units.text",
+        "Saved archives name the UCUM code of common units. Add units the built-in table lacks as 'unit = code', one per line.",
+    ),
+    ("settings.units.hint", "e.g., OD600 = {OD600}"),
+    ("settings.attachment_policy", "Attachment policy"),
+    (
+        "settings.attachment_policy.text",
+        "Refuse files your eLabFTW instance does not accept, by extension (exe) or MIME type (video/*), separated by commas.",
+    ),
+    ("settings.attachment_policy.hint", "e.g., exe, bat, video/*"),
+    (
+        "settings.attachment_policy.size",
+        "Flag attachments larger than",
+    ),
+    (
+        "settings.attachment_policy.size.hover",
+        "Saving asks before archiving them. Set to 0 to disable the check",
+    ),
+    ("settings.keyword_fields", "Keywords from fields"),
+    (
+        "settings.keyword_fields.text",
+        "Metadata fields whose values are added to the keywords when saving, one label per line. Case does not matter.",
+    ),
+    ("settings.keyword_fields.hint", "e.g., Organism"),
+    ("settings.favorites", "Favorite folders"),
+    (
+        "settings.favorites.text",
+        "One folder per line. Pick one from the star menu next to Add files, Import JSON, or Save to start the dialog there.",
+    ),
+    ("settings.favorites.hint", "e.g., /data/beamline"),
+    ("settings.share", "Share settings"),
+    (
+        "settings.share.text",
+        "Copy the saved settings to other computers. Window size, theme, remembered folders and the signing key stay on each computer.",
+    ),
+    ("settings.share.key", "Include eLabFTW API key"),
+    (
+        "settings.share.export.hover",
+        "Write the saved settings to a JSON file",
+    ),
+    (
+        "settings.share.import.hover",
+        "Load settings exported on another computer",
+    ),
+    ("settings.keywords", "Keywords"),
+    (
+        "settings.keywords.clear.hover",
+        "Forget all keywords remembered from saved archives",
+    ),
+    ("settings.diagnostics", "Diagnostics"),
+    ("settings.font_coverage", "Show font coverage panel"),
+    (
+        "settings.font_coverage.hover",
+        "List which fonts can render a test text, e.g. when symbols show as boxes",
+    ),
+    ("settings.favorite.default", "System default"),
+    (
+        "settings.favorite.none",
+        "Add favorite folders in Settings.",
+    ),
+    ("settings.favorite.missing", "Folder not found"),
+    ("settings.section.defaults", "Entry defaults"),
+    ("settings.section.archive", "Archive options"),
+    ("settings.section.compact", "Compact mode"),
+    ("settings.compression.stored", "Stored (no compression)"),
+    ("missing_files.abort", "Abort save"),
+    ("missing_files.skip", "Skip missing files"),
+    ("missing_files.ask", "Ask per file"),
+    ("fields.imported", "Imported {count} field(s) from {path}"),
+    (
+        "fields.reimported.kept",
+        "Re-imported {count} field(s) from {name}, keeping your values.",
+    ),
+    (
+        "fields.reimported.replaced",
+        "Re-imported {count} field(s) from {name}.",
+    ),
+    (
+        "fields.filled",
+        "Filled {count} field(s) labelled '{label}'.",
+    ),
+    (
+        "fields.filled.skipped",
+        "Skipped {readonly} read-only and {invalid} that do not accept the value.",
+    ),
+    (
+        "fields.groups.merged",
+        "Merged groups with the same name: {names}.",
+    ),
+    (
+        "fields.group.renamed",
+        "A group named '{wanted}' already exists; named it '{name}'.",
+    ),
+    ("fields.ago.now", "just now"),
+    ("fields.ago.minutes", "{count} min ago"),
+    ("fields.ago.hours", "{count} h ago"),
+    ("fields.ago.days", "{count} d ago"),
+    ("fields.add_group", "Add group"),
+    ("fields.add_field", "Add field"),
+    ("fields.import", "Import JSON"),
+    ("fields.import_entry", "Import from eLabFTW JSON"),
+    (
+        "fields.import_entry.hover",
+        "Fill title, body, keywords, date and fields from an exported eLabFTW entry",
+    ),
+    ("fields.bulk_fill.button", "Fill matching fields…"),
+    ("fields.source", "Imported from {name}, {ago}"),
+    ("fields.reimport", "Re-import"),
+    (
+        "fields.import.text",
+        "Imports eLabFTW-style extra_fields JSON. Field options are imported; you can adjust values before saving.",
+    ),
+    ("fields.expand_all", "Expand all"),
+    ("fields.collapse_all", "Collapse all"),
+    ("fields.count.one", "1 field"),
+    ("fields.count.many", "{count} fields"),
+    ("fields.invalid", "{count} invalid"),
+    ("fields.add_field_to", "Add field to {group}"),
+    (
+        "fields.filter.none",
+        "No fields match \u{201c}{filter}\u{201d}.",
+    ),
+    ("fields.reset.empty", "Reset to default: empty"),
+    ("fields.reset", "Reset to default: {default}"),
+    ("fields.range.from", "From"),
+    ("fields.range.to", "to"),
+    ("fields.unit.code", "Saved with the UCUM code {code}"),
+    (
+        "fields.unit.unknown",
+        "No UCUM code known for this unit; add one under Settings → Unit codes",
+    ),
+    ("fields.bulk_fill.changes", "{count} field(s) will change."),
+    ("fields.bulk_fill.skipped", "{count} skipped: {reason}"),
+    (
+        "fields.reimport.changed",
+        "{name} has changed since it was imported.",
+    ),
+    (
+        "fields.reimport.unchanged",
+        "{name} is unchanged since it was imported.",
+    ),
+    (
+        "fields.reimport.counts",
+        "It defines {count} field(s); the entry currently has {current}.",
+    ),
+    (
+        "fields.reimport.keep.hover",
+        "Use the file's field definitions and keep the values you entered",
+    ),
+    (
+        "fields.modal.default.hover",
+        "Value new fields start with and Reset restores; leave empty for none",
+    ),
+    (
+        "fields.editor_open.deferred",
+        "Finish or cancel the field editor first; the new fields are applied once it closes.",
+    ),
+    (
+        "fields.editor_open",
+        "Finish or cancel the field editor first.",
+    ),
+    ("fields.import.cancelled", "Metadata import cancelled."),
+    ("fields.name.duplicate", "Field name must be unique"),
+    (
+        "fields.bulk_fill.hover",
+        "Set one value on every field with the same label",
+    ),
+    (
+        "fields.reimport.hover",
+        "Read the file again and choose how to apply it",
+    ),
+    (
+        "fields.filter.hint",
+        "Filter by label, description or group",
+    ),
+    ("fields.filter.clear", "Clear filter"),
+    ("fields.main_text", "Show main text in eLabFTW"),
+    (
+        "fields.main_text.hover",
+        "When off, eLabFTW shows only the extra fields of this entry",
+    ),
+    ("fields.title", "Metadata"),
+    ("fields.blocker", "Invalid fields prevent saving."),
+    (
+        "fields.none",
+        "No metadata yet. Add a group or import JSON to begin.",
+    ),
+    ("fields.group.empty", "No fields in this group yet."),
+    ("fields.group.save", "Save group name"),
+    ("fields.group.name", "Group name"),
+    (
+        "fields.group.drag",
+        "Drag onto another group to move it there",
+    ),
+    ("fields.group.up", "Move group up"),
+    ("fields.group.down", "Move group down"),
+    ("fields.group.remove", "Remove group"),
+    ("fields.group.rename", "Rename group"),
+    ("fields.remove", "Remove field"),
+    ("fields.duplicate", "Duplicate field"),
+    ("fields.edit", "Edit field"),
+    ("fields.checked", "Checked"),
+    ("fields.unit", "Unit"),
+    ("fields.kind.number", "Number"),
+    ("fields.hint.id", "Numeric ID"),
+    ("fields.kind.text", "Text"),
+    ("fields.kind.select", "Select"),
+    ("fields.kind.checkbox", "Checkbox"),
+    ("fields.kind.date", "Date"),
+    ("fields.kind.date_range", "Date range"),
+    ("fields.kind.datetime", "Date/time"),
+    ("fields.kind.time", "Time"),
+    ("fields.kind.url", "URL"),
+    ("fields.kind.email", "Email"),
+    ("fields.kind.radio", "Radio"),
+    ("fields.kind.items", "Items"),
+    ("fields.kind.experiments", "Experiments"),
+    ("fields.kind.users", "Users"),
+    ("fields.kind.unknown", "Unknown"),
+    ("fields.modal.title", "Title"),
+    ("fields.modal.kind", "Field type"),
+    ("fields.modal.description", "Description"),
+    ("fields.modal.default", "Default value"),
+    ("fields.modal.default.multi", "Values separated by commas"),
+    ("fields.modal.required", "Required"),
+    ("fields.modal.readonly", "Read-only"),
+    ("fields.modal.multi", "Allow multiple"),
+    ("fields.modal.options", "Options"),
+    ("fields.modal.option.remove", "Remove option"),
+    ("fields.modal.option.add", "Add option"),
+    ("fields.modal.units", "Units"),
+    ("fields.modal.unit.remove", "Remove unit"),
+    ("fields.modal.unit.add", "Add unit"),
+    ("fields.modal.unit.default", "Default unit"),
+    ("fields.modal.group", "Group assignment"),
+    ("fields.modal.group.label", "Group"),
+    ("fields.bulk_fill", "Fill matching fields"),
+    ("fields.bulk_fill.field", "Field"),
+    ("fields.bulk_fill.value", "Value"),
+    ("fields.bulk_fill.only_empty", "Only fill empty fields"),
+    ("fields.bulk_fill.readonly", "read-only"),
+    ("fields.bulk_fill.filled", "already filled"),
+    ("fields.bulk_fill.invalid", "do not accept this value"),
+    ("fields.reimport.title", "Re-import metadata"),
+    ("fields.reimport.keep", "Keep my values"),
+    ("fields.reimport.replace", "Replace everything"),
+    (
+        "fields.reimport.replace.hover",
+        "Discard your edits and use the file as is",
+    ),
+    ("common.close", "Close"),
+    (
+        "templates.exists",
+        "A template named '{name}' already exists.",
+    ),
+    ("templates.save", "Save as template…"),
+    ("templates.archive", "Save as template archive…"),
+    (
+        "templates.archive.hover",
+        "Write an ELN archive to import into eLabFTW as a template, without attachments and with flagged field values left blank",
+    ),
+    ("templates.manage.button", "Manage templates…"),
+    ("templates.modified", "{name} (modified)"),
+    ("templates.none_applied", "No template"),
+    (
+        "templates.replaced",
+        "A template named '{name}' will be replaced.",
+    ),
+    ("templates.replace", "Replace"),
+    ("templates.new", "New template"),
+    ("templates.edit", "Edit template '{name}'"),
+    ("keywords.cleared", "Removed all {count} keyword(s)."),
+    ("keywords.merged", "{count} new keyword(s)"),
+    (
+        "keywords.merged.skipped",
+        "({count} already present or over the limit)",
+    ),
+    ("keywords.remove_selected", "Remove selected ({count})"),
+    ("keywords.clear", "Clear all"),
+    (
+        "keywords.clear.text",
+        "Remove all {count} keyword(s)? This cannot be undone.",
+    ),
+    (
+        "keywords.remove.text",
+        "Remove \"{keyword}\" from the keywords?",
+    ),
+    (
+        "keywords.derived.hover",
+        "\"{keyword}\" is taken from a metadata field; change the field to change it.",
+    ),
+    ("keywords.skipped.duplicates", "{count} duplicate(s)"),
+    ("keywords.skipped.empty", "{count} empty entry/entries"),
+    (
+        "keywords.skipped.limit",
+        "{count} beyond the limit of {max}",
+    ),
+    ("keywords.and", "and"),
+    (
+        "keywords.added.skipped",
+        "Added {count} keyword(s); skipped {skipped}.",
+    ),
+    ("keywords.added", "Added {count} keyword(s)."),
+    (
+        "keywords.added.none",
+        "No keywords added; skipped duplicates or empty entries.",
+    ),
+    ("keywords.removed_many", "Removed {count} keyword(s)."),
+    ("templates.name.empty", "Template name cannot be empty."),
+    (
+        "templates.import_hint",
+        "To start a template from a file, import it into an entry and save that as a template.",
+    ),
+    ("templates.none", "No templates yet"),
+    (
+        "templates.apply.hover",
+        "Start a new entry from this template",
+    ),
+    (
+        "templates.save.hover",
+        "Store title, body, keywords, type, and metadata fields for reuse",
+    ),
+    (
+        "templates.manage.hover",
+        "Create, edit, copy, rename, or delete templates",
+    ),
+    (
+        "templates.menu.hover",
+        "Start from or save an entry template",
+    ),
+    ("templates.recent.none", "No recent templates"),
+    (
+        "templates.modified.hover",
+        "Fields were added, removed, or changed since the template was applied. Pick a recent template to start from it.",
+    ),
+    (
+        "templates.recent.hover",
+        "Start from a recently used template",
+    ),
+    ("templates.save.title", "Save as template"),
+    ("templates.name.hint", "e.g. PCR run"),
+    (
+        "templates.save.text",
+        "Attachments and dates are not stored. Write {date} in the title to insert the date of each new entry.",
+    ),
+    ("templates.manage", "Manage templates"),
+    ("templates.edit.hover", "Edit title and metadata fields"),
+    ("templates.duplicate", "Duplicate"),
+    ("templates.delete", "Delete"),
+    ("templates.title.hint", "e.g. PCR run {date}"),
+    (
+        "templates.editor.text",
+        "Values entered here become the defaults of new entries; required fields may stay empty.",
+    ),
+    ("templates.editor.save", "Save template"),
+    ("keywords.removed", "Keyword removed"),
+    (
+        "keywords.tip",
+        "Tip: Paste comma-separated keywords in the dialog; they will be split safely.",
+    ),
+    ("keywords.deselect", "Deselect"),
+    ("keywords.clear.hover", "Remove every keyword"),
+    (
+        "keywords.select.hint",
+        "Ctrl/Cmd-click or Shift-click keywords to select several.",
+    ),
+    ("keywords.clear.title", "Clear all keywords?"),
+    ("keywords.clear.confirm", "Remove all"),
+    ("keywords.remove.title", "Remove keyword?"),
+    ("keywords.none", "No keywords added yet."),
+    (
+        "keywords.derived",
+        "Added from metadata fields when saving:",
+    ),
+    ("keywords.remove", "Remove keyword"),
+    ("keywords.edit", "Edit keyword"),
+    ("keywords.add", "Add keyword(s)"),
+    ("keywords.modal.label", "Keyword(s)"),
+    (
+        "keywords.modal.hint",
+        "e.g., microscopy or microscopy, dataset",
+    ),
+    ("keywords.modal.add", "Add"),
+    ("keywords.edit.empty", "Keyword cannot be empty."),
+    ("keywords.edit.duplicate", "Keyword already exists."),
+    ("markdown.table.insert", "Insert table"),
+    ("onboarding.step", "Step {number} of {total}"),
+    ("markdown.undo", "Undo (Ctrl/Cmd+Z)"),
+    ("markdown.redo", "Redo (Ctrl/Cmd+Shift+Z)"),
+    ("markdown.heading", "Heading (Ctrl/Cmd+1…6)"),
+    ("markdown.bold", "Bold (Ctrl/Cmd+B)"),
+    ("markdown.italic", "Italic (Ctrl/Cmd+I)"),
+    ("markdown.strikethrough", "Strikethrough"),
+    ("markdown.underline", "Underline"),
+    ("markdown.code.inline", "Inline code (Ctrl/Cmd+Shift+C)"),
+    ("markdown.code.block", "Code block"),
+    ("markdown.code", "Code"),
+    ("markdown.list.bulleted", "Bulleted list"),
+    ("markdown.list.numbered", "Numbered list"),
+    ("markdown.list", "List"),
+    ("markdown.link", "Link (Ctrl/Cmd+K)"),
+    ("markdown.quote", "Quote"),
+    ("markdown.image", "Image"),
+    ("markdown.table", "Insert table (choose size)"),
+    ("markdown.rule", "Rule"),
+    ("markdown.math.inline", "Inline math"),
+    ("markdown.math.display", "Display math"),
+    ("markdown.math", "Math"),
+    ("markdown.image.none", "No image attachments yet"),
+    ("markdown.image.embed", "Embed this attachment"),
+    ("markdown.image.url", "Image from URL"),
+    ("markdown.table.rows", "Rows"),
+    ("markdown.table.columns", "Columns"),
+    ("onboarding.author", "About you"),
+    ("onboarding.instance", "Your eLabFTW"),
+    ("onboarding.defaults", "New entries"),
+    ("onboarding.title", "Welcome to ELNPack"),
+    (
+        "onboarding.later",
+        "Everything can be changed later in Settings and Authors.",
+    ),
+    ("onboarding.skip", "Skip setup"),
+    ("onboarding.next", "Next"),
+    ("onboarding.finish", "Finish"),
+    ("onboarding.back", "Back"),
+    (
+        "onboarding.author.text",
+        "Credited as author in every archive you save. Leave blank to stay anonymous.",
+    ),
+    ("onboarding.author.given", "Given name"),
+    ("onboarding.author.family", "Family name"),
+    (
+        "onboarding.instance.text",
+        "The eLabFTW instance you import archives into. Optional.",
+    ),
+    ("onboarding.instance.address", "Address"),
+    (
+        "onboarding.defaults.text",
+        "What new entries start with; you can still change it per entry.",
+    ),
+    ("onboarding.defaults.format", "Export main text as"),
+    ("authors.save.failed", "Failed to save authors: {err}"),
+    ("authors.title", "Authors"),
+    ("authors.title.count", "Authors ({count})"),
+    ("authors.add", "Add author"),
+    ("datetime.now", "Now"),
+    ("datetime.hour.invalid", "Hour must be 0-23"),
+    ("datetime.minute.invalid", "Minute must be 0-59"),
+    (
+        "datetime.skipped",
+        "Invalid local date/time (likely skipped by offset)",
+    ),
+    ("links.title", "Related links"),
+    ("links.title.count", "Related links ({count})"),
+    ("links.add", "Add link"),
+    ("fonts.proportional", "Proportional (all fonts)"),
+    ("fonts.monospace", "Monospace (all fonts)"),
+    (
+        "authors.text",
+        "Credited in every saved archive, in this order. Kept between sessions.",
+    ),
+    ("authors.remove", "Remove author"),
+    ("authors.affiliation", "Affiliation"),
+    (
+        "datetime.now.hover",
+        "Set date/time to your current local time (stored as UTC)",
+    ),
+    (
+        "datetime.warning.hover",
+        "You will be asked to confirm the date when saving.",
+    ),
+    (
+        "links.text",
+        "Experiments or resources this entry references, belongs to, or is derived from.",
+    ),
+    ("links.label", "Label"),
+    ("links.remove", "Remove link"),
+    ("draft.autosave.failed", "Could not autosave draft: {err}"),
+    (
+        "safe_mode.notice",
+        "Safe mode: ELNPack started with default settings and left your saved settings, draft, templates and lists untouched. Load them one by one to find one that causes trouble.",
+    ),
+    ("safe_mode.config", "Open config folder"),
+    ("safe_mode.load", "Load {file}"),
+    (
+        "safe_mode.load.hover",
+        "Read {path}; ELNPack also saves it again from then on",
+    ),
+    ("safe_mode.loaded", "Loaded"),
+    ("fonts.coverage.complete", "all characters"),
+    ("fonts.coverage.missing", "missing: {chars}"),
+    ("error.title", "Error"),
+    ("error.resume", "Resume save"),
+    ("error.copy", "Copy details"),
+    ("missing_file.text", "The save cannot read {path}."),
+    (
+        "template_confirm.text",
+        "Starting from '{name}' discards the current entries and their attachments.",
+    ),
+    ("overwrite.text", "Replace {path} with the current entries?"),
+    ("batch.progress", "{done} of {total} archives finished."),
+    ("status.working", "{count} working…"),
+    ("status.background", "{count} task(s) running in background"),
+    ("status.writing", "Writing {file}: {done} / {total}"),
+    ("status.uploading", "Uploading to eLabFTW: {done} / {total}"),
+    ("batch.waiting", "Waiting"),
+    ("batch.writing", "Writing…"),
+    ("batch.cancelled", "Cancelled"),
+    (
+        "safe_mode.config.hover",
+        "Show the folder holding the settings in the file manager",
+    ),
+    ("fonts.coverage", "Font coverage"),
+    ("fonts.coverage.hint", "Characters to check"),
+    ("error.details", "Details"),
+    (
+        "error.resume.hover",
+        "Keep the attachments already written and add the rest",
+    ),
+    ("common.ok", "OK"),
+    (
+        "error.copy.hover",
+        "Copy the full message, e.g. for a bug report",
+    ),
+    ("date_confirm.title", "Check performed-at date"),
+    (
+        "date_confirm.text",
+        "Is this intended, e.g. for a backfilled experiment?",
+    ),
+    ("date_confirm.yes", "Yes, the date is correct"),
+    ("size_confirm.title", "Large attachments"),
+    (
+        "size_confirm.text",
+        "eLabFTW may refuse to import archives this large. Save anyway?",
+    ),
+    ("size_confirm.yes", "Save anyway"),
+    ("missing_file.title", "Attachment missing"),
+    (
+        "missing_file.hint",
+        "It may have been moved, deleted, or be on a disconnected drive.",
+    ),
+    ("missing_file.retry", "Retry"),
+    ("missing_file.skip", "Skip"),
+    (
+        "missing_file.skip.hover",
+        "Save the archive without this file",
+    ),
+    ("template_confirm.title", "Start from template"),
+    (
+        "template_confirm.hint",
+        "Save them first if you want to keep them.",
+    ),
+    ("template_confirm.yes", "Discard and start from template"),
+    ("overwrite.title", "Overwrite archive"),
+    ("overwrite.yes", "Overwrite"),
+    ("batch.title", "Batch packaging"),
+    ("batch.cancel", "Cancel all"),
+    (
+        "batch.cancel.hover",
+        "Stop the archives that are not finished yet",
+    ),
+    ("quit.title", "Unsaved changes"),
+    (
+        "quit.text",
+        "The entries were changed since they were last saved.",
+    ),
+    (
+        "quit.question",
+        "Save them as an ELN archive before quitting?",
+    ),
+    ("quit.save", "Save and quit"),
+    ("quit.discard", "Discard"),
+    (
+        "quit.discard.hover",
+        "Quit without saving and delete the autosaved draft",
+    ),
+    ("status.history", "Recent messages"),
+    ("draft.delete_failed", "Could not delete saved draft: {err}"),
+    (
+        "keywords.store_failed",
+        "Could not store keyword suggestions: {err}",
+    ),
+    ("paste.added", "Pasted image added ({size})"),
+    ("fields.import.with_tags", "{fields}. Added {tags}."),
+    ("upload.done", "Uploaded to eLabFTW: {url}"),
+    ("upload.failed", "Upload failed"),
+    (
+        "upload.cancel.hover",
+        "Stop the upload; the archive stays saved",
+    ),
+    ("upload.cancelling", "Cancelling upload…"),
+    ("upload.cancelled", "Upload cancelled."),
+    (
+        "upload.error",
+        "Upload to eLabFTW failed:\n\n{err}\n\nThe archive was saved and can be imported manually.",
+    ),
+    ("open.opening", "Opening {path}…"),
+    ("open.cancelled", "Open cancelled."),
+    ("import.failed", "Failed to import {path}: {err}"),
+    ("open.done", "Opened archive: {path}"),
+    ("open.failed", "Open failed"),
+    ("open.error", "Failed to open archive {path}:\n\n{err}"),
+    ("trace.exported", "Event trace exported: {path}"),
+    ("trace.export_failed", "Failed to export event trace: {err}"),
+    ("help.open_failed", "Could not open help page: {err}"),
+    ("folder.open_failed", "Could not open folder: {err}"),
+    ("import.read_failed", "Failed to read file: {err}"),
+    ("templates.saved", "Saved template '{name}'."),
+    (
+        "templates.load_failed",
+        "Could not load template '{name}': {err}",
+    ),
+    ("templates.renamed", "Renamed template '{from}' to '{to}'."),
+    ("templates.deleted", "Deleted template '{name}'."),
+    (
+        "fields.import.source_gone",
+        "Metadata file {path} no longer exists; import it again from its new location.",
+    ),
+    (
+        "fields.import.read_failed",
+        "Failed to read metadata file: {err}",
+    ),
+    (
+        "draft.restored.status",
+        "Restored unsaved draft from your previous session.",
+    ),
+    (
+        "draft.restored.missing",
+        "Restored draft; {count} attachment(s) were missing or changed and were removed: {names}",
+    ),
+    ("templates.applied", "New entry from template '{name}'."),
+    ("save.keywords_adjusted", "keywords adjusted: {problems}"),
+    (
+        "save.missing_references",
+        "the main text links to attachments that are not in the archive: {problems}",
+    ),
+    (
+        "save.main_text_hidden",
+        "eLabFTW will not show the main text, since \"Show main text in eLabFTW\" is off",
+    ),
+    (
+        "save.main_text_hidden.of",
+        "eLabFTW will not show the main text of: {entries}",
+    ),
+    ("save.oversized.one", "{name} exceeds {limit}"),
+    (
+        "save.oversized.many",
+        "{count} attachments exceed {limit} ({names})",
+    ),
+    ("save.related_link_invalid", "Related link {index}: {err}"),
+    ("draft.discarded", "Draft discarded."),
+    ("paste.unavailable", "Pasting images is not available."),
+    ("templates.unavailable", "Templates are not available."),
+    (
+        "templates.editor_open",
+        "Finish or cancel the field editor first, then apply the template.",
+    ),
+    ("onboarding.saved", "Setup saved."),
+    (
+        "onboarding.skipped",
+        "Setup skipped. Run it again any time from the Help menu.",
+    ),
+    (
+        "help.opening",
+        "Opening ELNPack user guide in your browser…",
+    ),
+    (
+        "trace.disabled",
+        "Start ELNPack with --debug-events to record events.",
+    ),
+    ("help.opened", "Help opened in browser."),
+    ("dialog.attachments", "Select attachments"),
+    ("dialog.attachment_folder", "Select attachment folder"),
+    ("dialog.metadata", "Select eLabFTW metadata JSON"),
+    ("dialog.entry", "Select eLabFTW entry JSON"),
+    (
+        "dialog.batch_source",
+        "Select folder with one subfolder per entry",
+    ),
+    ("dialog.batch_output", "Select folder for the archives"),
+    ("dialog.export_settings", "Export settings"),
+    ("dialog.template_archive", "Save template archive"),
+    (
+        "entries.blocked.dialog",
+        "Please close the file dialog first.",
+    ),
+    (
+        "entries.blocked.pending",
+        "Please wait until all attachments have been processed.",
+    ),
+    ("safe_mode.loaded_file", "Loaded {file}."),
+    ("persisted.settings", "Settings and recent archives"),
+    ("persisted.vocabulary", "Keyword suggestions"),
+    ("persisted.templates", "Templates"),
+    ("persisted.api_key", "eLabFTW API key"),
+    ("persisted.draft", "Unsaved draft"),
+    (
+        "persisted.settings.failed",
+        "Could not load settings; using defaults",
+    ),
+    (
+        "persisted.vocabulary.failed",
+        "Could not load keyword suggestions",
+    ),
+    ("persisted.authors.failed", "Could not load authors"),
+    ("persisted.templates.failed", "Could not load templates"),
+    (
+        "persisted.api_key.failed",
+        "Could not load the eLabFTW API key",
+    ),
+    ("persisted.draft.failed", "Could not restore saved draft"),
+    (
+        "safe_mode.started",
+        "Started in safe mode with default settings; saved files were not loaded.",
+    ),
+    (
+        "validate.title_length",
+        "Title has {length} characters; eLabFTW keeps at most {limit}.",
+    ),
+    ("validate.title", "Please enter a title."),
+    (
+        "validate.summary_length",
+        "The summary has {length} characters; please shorten it to at most {limit}.",
+    ),
+    (
+        "validate.field_twice",
+        "The extra field \"{label}\" exists twice. eLabFTW identifies fields by label, so please rename one of them.",
+    ),
+    (
+        "validate.field_case",
+        "The extra fields \"{first}\" and \"{second}\" differ only in letter case. eLabFTW identifies fields by label, so please rename one of them.",
+    ),
+    (
+        "validate.blocked_type",
+        "Blocked attachment type '{pattern}': write an extension such as exe or a MIME type such as video/*.",
+    ),
+    (
+        "validate.reserved_name",
+        "Attachment {path} uses a name reserved for the archive's own files; rename it.",
+    ),
+    (
+        "validate.acquired_future",
+        "Attachment {path} has an acquisition time in the future.",
+    ),
+    (
+        "validate.duplicate_path",
+        "Duplicate attachment filename in archive: {path}",
+    ),
+    (
+        "validate.file_and_folder",
+        "Attachment path is used both as a file and a folder: {path}",
+    ),
+    ("validate.author_name", "Please enter the author's name."),
+    ("validate.email", "Email address {email} is invalid."),
+    (
+        "validate.orcid_form",
+        "ORCID iD {orcid} must have the form xxxx-xxxx-xxxx-xxxx.",
+    ),
+    (
+        "validate.orcid_check",
+        "ORCID iD {orcid} has an invalid check digit.",
+    ),
+    ("fields.error.required", "is required"),
+    ("fields.error.url", "must be a valid http/https URL"),
+    ("fields.error.number", "must be a valid number"),
+    ("fields.error.integer", "must be a valid integer ID"),
+    ("fields.error.email", "must be a valid email address"),
+    ("fields.error.date", "must be a date like 2024-05-31"),
+    (
+        "fields.error.date_range",
+        "must be two dates like 2024-05-01/2024-05-31, the end not before the start",
+    ),
+    ("fields.error.time", "must be a time like 14:30"),
+    (
+        "fields.error.datetime",
+        "must be a date and time like 2024-05-31T14:30",
+    ),
+    ("fields.error.named", "Field '{label}' {error}."),
+    ("fields.error.inline", "This field {error}."),
+    ("keywords.report.adjusted", "stored \"{from}\" as \"{to}\""),
+    (
+        "keywords.report.empty",
+        "dropped empty keyword \"{keyword}\"",
+    ),
+    (
+        "keywords.report.duplicate",
+        "dropped \"{keyword}\" (duplicate of \"{kept}\")",
+    ),
+    (
+        "keywords.report.over_limit",
+        "dropped \"{keyword}\" (over the keyword limit)",
+    ),
+    (
+        "validate.license_url",
+        "Please enter the license's URL or choose a license from the list.",
+    ),
+    (
+        "validate.license_invalid",
+        "License {url} must be a valid http/https URL.",
+    ),
+    (
+        "date.warning.future",
+        "The performed-at date lies in the future.",
+    ),
+    (
+        "date.warning.old",
+        "The performed-at date is more than {years} year(s) in the past.",
+    ),
+    ("links.relation.references", "references"),
+    ("links.relation.part_of", "is part of"),
+    ("links.relation.derived_from", "derived from"),
+    ("validate.link_url", "Please enter the link's URL."),
+    (
+        "validate.link_invalid",
+        "{url} must be a valid http/https URL.",
+    ),
+    ("open_confirm.text", "Opening {path} discards them."),
+    ("open_confirm.yes", "Discard and open"),
+    (
+        "open.cleanup_failed",
+        "Could not delete the extracted files of the previous archive: {err}",
+    ),
+    (
+        "save.insufficient_space",
+        "Not enough free disk space: the archive needs about {needed} MB, but only {available} MB are free on that drive. Free up some space or save to another drive.",
+    ),
+    (
+        "save.unreadable_archive",
+        "The written archive {path} cannot be read back: {err}",
+    ),
+    (
+        "save.damaged_archive",
+        "The written archive {path} is damaged; these files do not match its metadata:\n\n{report}",
+    ),
+    (
+        "save.attachment_modified",
+        "Attachment modified since it was added:\n  {path}\n  expected {key} {expected}\n  found {key} {found}",
+    ),
+    (
+        "save.omitted_comment",
+        "Left out because the files could not be read when the archive was written: {files}",
+    ),
+    (
+        "save.metadata_unreadable",
+        "Failed to read the imported metadata file {path}; turn off Include imported metadata files under Settings → Checksums to save without it",
+    ),
+    (
+        "save.metadata_changed",
+        "The metadata file {path} changed since the extra fields were imported from it. Re-import it, or turn off Include imported metadata files under Settings → Checksums.",
+    ),
+    (
+        "upload.no_target",
+        "Set the eLabFTW address and API key in Settings to upload archives.",
+    ),
+    ("upload.unreadable", "Could not read {path}: {err}"),
+    (
+        "upload.unreachable",
+        "Could not reach eLabFTW at {url}: {err}. Check the address in Settings and your network connection.",
+    ),
+    (
+        "upload.rejected_key",
+        "eLabFTW rejected the API key (HTTP {status}). Create a key with write access in your eLabFTW user settings and enter it in Settings.",
+    ),
+    (
+        "upload.too_large",
+        "The archive is too large for this eLabFTW server (HTTP 413).",
+    ),
+    (
+        "upload.not_imported",
+        "eLabFTW could not import the archive (HTTP {status}).",
+    ),
+    (
+        "upload.not_imported.reply",
+        "eLabFTW could not import the archive (HTTP {status}): {reply}",
+    ),
+    (
+        "validate.language_code",
+        "Language must be a code like \"en\", \"de\" or \"en-GB\".",
+    ),
+    (
+        "signing.wrong_key",
+        "The archive was signed with key {signer}, not with the expected key {expected}.",
+    ),
+    (
+        "signing.mismatch",
+        "The signature does not match the archive; it was altered or re-saved after signing.",
+    ),
+    (
+        "signing.recorded_key",
+        "The archive names signing key {recorded}, but the signature was made with {signer}.",
+    ),
+    (
+        "entry_import.no_entry",
+        "The file holds no eLabFTW entry; export a single experiment or resource as JSON.",
+    ),
+    (
+        "entry_import.date",
+        "The entry date {date} is not a date ELNPack understands.",
+    ),
+    (
+        "entry_import.empty",
+        "The entry has no title, body, tags, date, or extra fields to import.",
+    ),
+    (
+        "batch.no_jobs",
+        "No subfolder of {folder} contains a {file}.",
+    ),
+    (
+        "settings.units.no_equals",
+        "Unit codes line {line}: write it as 'unit = code'.",
+    ),
+    (
+        "settings.units.invalid",
+        "Unit codes line {line}: expected a unit and a UCUM code without spaces.",
+    ),
+];
@@ -0,0 +1,236 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Translations of user-visible texts.
+//!
+//! Texts are looked up by key with the [`t!`] macro, which fills in `{name}` placeholders
+//! from its arguments. Each language has a catalog of key/text pairs in its own file; a key
+//! missing in the current language falls back to English, and a key missing there too shows
+//! as is. Models and logic use it for the messages they show to the user, so it sits outside
+//! `ui`.
+//!
+//! The language is process-wide rather than part of the model, so background workers and
+//! `mvu::update` translate their status messages without threading it through. The UI shell
+//! sets it from the settings every frame; tests never change it and see English.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::logic::settings::Language;
+
+mod de;
+mod en;
+
+use de::DE;
+use en::EN;
+
+/// Translate a key, filling `{name}` placeholders from `name = value` arguments.
+///
+/// Without arguments the result is a `&'static str`, otherwise a `String`.
+macro_rules! t {
+    ($key:literal) => {
+        $crate::i18n::tr($key)
+    };
+    ($key:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::tr_args(
+            $key,
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+],
+        )
+    };
+}
+pub(crate) use t;
+
+/// Index of the current language in [`Language::ALL`].
+static LANGUAGE: AtomicU8 = AtomicU8::new(Language::English as u8);
+
+/// Switch every later translation to `language`.
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+/// Language texts are currently translated to.
+pub fn language() -> Language {
+    Language::ALL
+        .get(usize::from(LANGUAGE.load(Ordering::Relaxed)))
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Text for `key` in the current language.
+pub fn tr(key: &'static str) -> &'static str {
+    resolve(catalog(language()), key)
+}
+
+/// Text for `key` in the current language with its placeholders filled from `args`.
+pub fn tr_args(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    fill(tr(key), args)
+}
+
+/// Text for `key` in `primary`, else in English, else the key itself.
+fn resolve(primary: &HashMap<&'static str, &'static str>, key: &'static str) -> &'static str {
+    primary
+        .get(key)
+        .or_else(|| catalog(Language::English).get(key))
+        .copied()
+        .unwrap_or(key)
+}
+
+/// `template` with every `{name}` replaced by the matching value in `args`.
+///
+/// The template is scanned once, so braces inside the values are kept as they are; unknown
+/// placeholders stay in the text.
+fn fill(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        text.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let value = after.find('}').and_then(|close| {
+            let name = &after[..close];
+            let (_, value) = args.iter().find(|(arg, _)| *arg == name)?;
+            Some((value, close))
+        });
+        match value {
+            Some((value, close)) => {
+                text.push_str(&value.to_string());
+                rest = &after[close + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = after;
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+/// Key/text pairs of `language`.
+fn catalog(language: Language) -> &'static HashMap<&'static str, &'static str> {
+    static ENGLISH: LazyLock<HashMap<&str, &str>> = LazyLock::new(|| EN.iter().copied().collect());
+    static GERMAN: LazyLock<HashMap<&str, &str>> = LazyLock::new(|| DE.iter().copied().collect());
+    match language {
+        Language::English => &ENGLISH,
+        Language::German => &GERMAN,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use std::path::Path;
+
+    /// Keys passed to `t!` in the Rust files below `dir`.
+    fn keys_used_in(dir: &Path, keys: &mut BTreeSet<String>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                keys_used_in(&path, keys);
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                let source = std::fs::read_to_string(&path).unwrap();
+                for (at, _) in source.match_indices("t!(\"") {
+                    // Skip `format!(` and the like.
+                    let before = source[..at].chars().next_back();
+                    if before.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                        continue;
+                    }
+                    let key = source[at + 4..].split('"').next().unwrap();
+                    keys.insert(key.to_string());
+                }
+            }
+        }
+    }
+
+    /// Names of the `{name}` placeholders in `text`.
+    fn placeholders(text: &str) -> BTreeSet<&str> {
+        text.split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn every_key_used_in_code_is_translated() {
+        let mut keys = BTreeSet::new();
+        keys_used_in(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut keys,
+        );
+        assert!(keys.contains("save.cancelled"), "{keys:?}");
+        for language in Language::ALL {
+            let missing: Vec<&String> = keys
+                .iter()
+                .filter(|key| !catalog(language).contains_key(key.as_str()))
+                .collect();
+            assert!(missing.is_empty(), "{language:?} lacks {missing:?}");
+        }
+    }
+
+    #[test]
+    fn every_language_translates_every_english_key() {
+        for language in Language::ALL {
+            let missing: Vec<&str> = EN
+                .iter()
+                .map(|(key, _)| *key)
+                .filter(|key| !catalog(language).contains_key(key))
+                .collect();
+            assert!(missing.is_empty(), "{language:?} lacks {missing:?}");
+        }
+    }
+
+    #[test]
+    fn catalogs_match_english_keys_and_placeholders() {
+        for (language, entries) in [(Language::English, EN), (Language::German, DE)] {
+            assert_eq!(
+                catalog(language).len(),
+                entries.len(),
+                "{language:?} lists a key twice"
+            );
+            for (key, text) in entries {
+                let english = catalog(Language::English).get(key);
+                assert!(english.is_some(), "{language:?} has unknown key {key}");
+                assert_eq!(
+                    placeholders(text),
+                    placeholders(english.unwrap()),
+                    "{language:?} {key}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn missing_texts_fall_back_to_english_then_the_key() {
+        let partial = HashMap::from([("save.cancelled", "Speichern abgebrochen.")]);
+        assert_eq!(
+            resolve(&partial, "save.cancelled"),
+            "Speichern abgebrochen."
+        );
+        assert_eq!(resolve(&partial, "save.failed"), "Save failed");
+        assert_eq!(resolve(&partial, "no.such.key"), "no.such.key");
+        assert_eq!(
+            fill(
+                "{filled} of {total}",
+                &[("filled", &2 as &dyn Display), ("total", &"3")]
+            ),
+            "2 of 3"
+        );
+    }
+
+    #[test]
+    fn values_are_not_scanned_for_placeholders() {
+        assert_eq!(
+            fill(
+                "{name} has {count} entries",
+                &[("name", &"{count}" as &dyn Display), ("count", &2)]
+            ),
+            "{count} has 2 entries"
+        );
+        assert_eq!(
+            fill("{unknown} and {count", &[("count", &2 as &dyn Display)]),
+            "{unknown} and {count"
+        );
+    }
+}
@@ -0,0 +1,16 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! ELNPack as a library: the desktop application, the CLI and the archive writer they share.
+//!
+//! The `elnpack` binary only calls [`cli::run`]; the library exists so integration tests under
+//! `tests/` can exercise the writer directly.
+
+pub mod app;
+pub mod cli;
+pub mod i18n;
+pub mod logic;
+pub mod models;
+pub mod mvu;
+pub mod ui;
+pub mod utils;
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Persistent author list credited in every saved archive.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::models::author::Author;
+use crate::utils::storage::write_atomic;
+
+/// Version of the on-disk author list schema.
+pub const AUTHORS_SCHEMA_VERSION: u32 = 1;
+
+/// On-disk representation of the author list.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+struct AuthorsFile {
+    version: u32,
+    authors: Vec<Author>,
+}
+
+/// Load the stored authors from `path`, returning an empty list when the file does not exist.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be read, is not valid JSON, or was written by a
+/// newer, incompatible schema version.
+pub fn load_authors(path: &Path) -> Result<Vec<Author>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to read authors {:?}", path));
+        }
+    };
+
+    let file: AuthorsFile = serde_json::from_str(&content).context("Authors file is corrupt")?;
+    if file.version > AUTHORS_SCHEMA_VERSION {
+        anyhow::bail!(
+            "Authors were written by a newer ELNPack (schema {}, supported {})",
+            file.version,
+            AUTHORS_SCHEMA_VERSION
+        );
+    }
+    Ok(file.authors)
+}
+
+/// Serialize and atomically write `authors` to `path`, keeping their order.
+///
+/// # Errors
+///
+/// Returns an error when serialization or the atomic write fails.
+pub fn save_authors(path: &Path, authors: &[Author]) -> Result<()> {
+    let file = AuthorsFile {
+        version: AUTHORS_SCHEMA_VERSION,
+        authors: authors.to_vec(),
+    };
+    let bytes = serde_json::to_vec_pretty(&file).context("Failed to serialize authors")?;
+    write_atomic(path, &bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn authors_round_trip_in_order_and_default_to_empty() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("authors.json");
+        assert!(load_authors(&path).unwrap().is_empty());
+
+        let authors = vec![
+            Author {
+                family_name: "Curie".into(),
+                ..Default::default()
+            },
+            Author {
+                given_name: "Lise".into(),
+                family_name: "Meitner".into(),
+                orcid: "0000-0002-1825-0097".into(),
+                ..Default::default()
+            },
+        ];
+        save_authors(&path, &authors).unwrap();
+
+        assert_eq!(load_authors(&path).unwrap(), authors);
+    }
+}
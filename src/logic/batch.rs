@@ -0,0 +1,357 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Batch packaging: one archive per subfolder of a parent folder.
+//!
+//! Every subfolder holding a [`BATCH_BODY_FILE`] becomes one entry titled after the folder.
+//! Extra fields and keywords come from an optional [`BATCH_METADATA_FILE`] in eLabFTW format,
+//! and all other files below the subfolder are attached, keeping their subdirectories. Each
+//! entry is written to `<folder>.eln` in the output folder.
+
+use std::fs;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{Context, Result, anyhow, bail};
+use time::OffsetDateTime;
+
+use crate::i18n::t;
+use crate::logic::eln::{
+    ArchiveEntry, ArchiveGenre, BodyFormat, ExportOptions, WriteCancelled,
+    build_and_write_archive_with_progress,
+};
+use crate::models::attachment::{Attachment, unreserved_name};
+use crate::models::extra_fields::{ExtraFieldsSource, parse_elabftw_extra_fields};
+use crate::models::keywords::Keywords;
+use crate::models::license::License;
+use crate::utils::folder_scan::{MAX_FOLDER_FILES, scan_folder};
+use crate::utils::hash::HashAlgorithm;
+use crate::utils::hash_file_with_progress;
+use crate::utils::sanitize_component::{sanitize_component, sanitize_folder};
+
+/// Main text of a batch entry, as Markdown.
+pub const BATCH_BODY_FILE: &str = "body.md";
+/// Optional eLabFTW metadata of a batch entry.
+pub const BATCH_METADATA_FILE: &str = "metadata.json";
+
+/// One archive to build from a subfolder.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchJob {
+    /// Subfolder holding the main text, metadata, and attachments.
+    pub folder: PathBuf,
+    /// Entry title, the name of the subfolder.
+    pub title: String,
+    /// Archive to write.
+    pub output: PathBuf,
+}
+
+/// Settings shared by all archives of a batch.
+#[derive(Clone, Debug, Default)]
+pub struct BatchOptions {
+    pub genre: ArchiveGenre,
+    pub license: License,
+    pub body_format: BodyFormat,
+    pub export: ExportOptions,
+}
+
+/// List the subfolders of `parent` that hold a [`BATCH_BODY_FILE`], sorted by name, as jobs
+/// writing to `output_dir`.
+///
+/// # Errors
+///
+/// Returns an error when `parent` cannot be read or no subfolder holds a main text.
+pub fn find_batch_jobs(parent: &Path, output_dir: &Path) -> Result<Vec<BatchJob>> {
+    let mut folders = fs::read_dir(parent)
+        .with_context(|| format!("Failed to read folder {:?}", parent))?
+        .collect::<std::io::Result<Vec<_>>>()
+        .with_context(|| format!("Failed to read folder {:?}", parent))?;
+    folders.sort_by_key(|entry| entry.file_name());
+
+    let jobs: Vec<BatchJob> = folders
+        .into_iter()
+        .filter(|entry| entry.path().join(BATCH_BODY_FILE).is_file())
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            BatchJob {
+                folder: entry.path(),
+                output: output_dir.join(format!("{name}.eln")),
+                title: name.trim().to_string(),
+            }
+        })
+        .collect();
+    if jobs.is_empty() {
+        bail!(t!(
+            "batch.no_jobs",
+            folder = parent.display(),
+            file = BATCH_BODY_FILE
+        ));
+    }
+    Ok(jobs)
+}
+
+/// Write the archive of `job`, returning its path.
+///
+/// The archive is not written when `cancel` is set before or while it is written.
+///
+/// # Errors
+///
+/// Returns [`WriteCancelled`] when cancelled, and an error when the archive already exists,
+/// the folder cannot be read, or the entry is invalid.
+pub fn run_batch_job(
+    job: &BatchJob,
+    options: &BatchOptions,
+    cancel: &AtomicBool,
+) -> Result<PathBuf> {
+    if cancel.load(Ordering::Relaxed) {
+        return Err(WriteCancelled.into());
+    }
+    if job.output.exists() {
+        bail!("{} already exists.", job.output.display());
+    }
+    let entry = batch_entry(job, options)?;
+    entry.validate(OffsetDateTime::now_utc())?;
+    build_and_write_archive_with_progress(
+        &job.output,
+        &[entry],
+        options.body_format,
+        &options.export,
+        |_| {
+            if cancel.load(Ordering::Relaxed) {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        },
+    )?;
+    Ok(job.output.clone())
+}
+
+/// Read the entry of `job` from its folder.
+fn batch_entry(job: &BatchJob, options: &BatchOptions) -> Result<ArchiveEntry> {
+    let body_path = job.folder.join(BATCH_BODY_FILE);
+    let body = fs::read_to_string(&body_path)
+        .with_context(|| format!("Failed to read main text {:?}", body_path))?;
+
+    let metadata_path = job.folder.join(BATCH_METADATA_FILE);
+    let (mut extra_fields, extra_groups, tags, source, hide_main_text) = if metadata_path.is_file()
+    {
+        let json = fs::read_to_string(&metadata_path)
+            .with_context(|| format!("Failed to read metadata file {:?}", metadata_path))?;
+        let import = parse_elabftw_extra_fields(&json)
+            .with_context(|| format!("Invalid metadata file {:?}", metadata_path))?;
+        let source = ExtraFieldsSource::new(metadata_path, &json, OffsetDateTime::now_utc());
+        let hide_main_text = import.hide_main_text;
+        (
+            import.fields,
+            import.groups,
+            import.tags,
+            Some(source),
+            hide_main_text,
+        )
+    } else {
+        (Vec::new(), Vec::new(), Vec::new(), None, false)
+    };
+    extra_fields.sort_by(|a, b| a.cmp_key().cmp(&b.cmp_key()));
+
+    let scan = scan_folder(&job.folder, MAX_FOLDER_FILES)?;
+    if scan.truncated {
+        bail!(
+            "Folder {} contains more than {MAX_FOLDER_FILES} files.",
+            job.folder.display()
+        );
+    }
+    let mut attachments = Vec::new();
+    for file in scan.files {
+        // Directories relative to the subfolder itself rather than to the parent folder.
+        let dir: PathBuf = file.relative_dir.components().skip(1).collect();
+        let top_level = dir.as_os_str().is_empty();
+        if top_level
+            && (file.path.file_name() == Some(BATCH_BODY_FILE.as_ref())
+                || file.path.file_name() == Some(BATCH_METADATA_FILE.as_ref()))
+        {
+            continue;
+        }
+        attachments.push(hashed_attachment(&file.path, sanitize_folder(&dir))?);
+    }
+
+    Ok(ArchiveEntry {
+        title: job.title.clone(),
+        body: body.trim().to_string(),
+        attachments,
+        extra_fields,
+        extra_groups,
+        performed_at: file_modified(&body_path).unwrap_or_else(OffsetDateTime::now_utc),
+        genre: options.genre,
+        keywords: Keywords::new(tags).into_vec(),
+        language: None,
+        related_links: Vec::new(),
+        imported_metadata: source,
+        license: options.license.clone(),
+        summary: String::new(),
+        hide_main_text,
+    })
+}
+
+/// Describe one file as an attachment in `folder`, hashing its content.
+///
+/// Names reserved for the archive's own files get a `_1` suffix, as in the desktop app.
+pub(crate) fn hashed_attachment(path: &Path, folder: String) -> Result<Attachment> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| anyhow!("{} is not a file", path.display()))?
+        .to_string_lossy();
+    let size = fs::metadata(path)
+        .with_context(|| format!("Failed to read attachment {:?}", path))?
+        .len();
+    let sha256 = hash_file_with_progress(path, &[HashAlgorithm::Sha256], |_, _| {
+        ControlFlow::Continue(())
+    })?
+    .and_then(|mut digests| digests.remove(&HashAlgorithm::Sha256))
+    .context("Hashing stopped unexpectedly")?;
+    let mime = mime_guess::from_path(path)
+        .first_or_octet_stream()
+        .essence_str()
+        .to_string();
+    Ok(Attachment::new(
+        path.to_path_buf(),
+        unreserved_name(&sanitize_component(&name)),
+        mime,
+        sha256,
+        size,
+    )
+    .in_folder(folder)
+    .with_acquired_at(file_modified(path)))
+}
+
+/// File modification time.
+fn file_modified(path: &Path) -> Option<OffsetDateTime> {
+    path.metadata()
+        .and_then(|m| m.modified())
+        .ok()
+        .map(OffsetDateTime::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+
+    use serde_json::Value;
+    use tempfile::TempDir;
+    use zip::ZipArchive;
+
+    use super::*;
+
+    /// Parent folder with two samples, the second with metadata and a nested attachment,
+    /// and a folder without a main text.
+    fn sample_tree(root: &Path) -> PathBuf {
+        let parent = root.join("reports");
+        fs::create_dir_all(parent.join("sample-02/raw")).unwrap();
+        fs::create_dir_all(parent.join("sample-01")).unwrap();
+        fs::create_dir_all(parent.join("scratch")).unwrap();
+        fs::write(
+            parent.join("sample-01/body.md"),
+            "# Sample 1\n\nAll reads passed.",
+        )
+        .unwrap();
+        fs::write(parent.join("sample-02/body.md"), "Sample 2").unwrap();
+        fs::write(
+            parent.join("sample-02/raw/reads.fastq"),
+            "@r1\nACGT\n+\nIIII\n",
+        )
+        .unwrap();
+        fs::write(
+            parent.join("sample-02/metadata.json"),
+            r#"{"extra_fields":{"Reads":{"type":"number","value":"1200"}},"tags":"ngs|qc"}"#,
+        )
+        .unwrap();
+        fs::write(parent.join("scratch/notes.txt"), "not a sample").unwrap();
+        fs::write(parent.join("summary.md"), "not a folder").unwrap();
+        parent
+    }
+
+    fn read_graph(archive: &Path, root: &str) -> Vec<Value> {
+        let mut zip = ZipArchive::new(File::open(archive).unwrap()).unwrap();
+        let mut buf = String::new();
+        zip.by_name(&format!("{root}/ro-crate-metadata.json"))
+            .unwrap()
+            .read_to_string(&mut buf)
+            .unwrap();
+        let meta: Value = serde_json::from_str(&buf).unwrap();
+        meta["@graph"].as_array().unwrap().clone()
+    }
+
+    #[test]
+    fn subfolders_with_a_main_text_become_jobs() {
+        let tmp = TempDir::new().unwrap();
+        let parent = sample_tree(tmp.path());
+        let out = tmp.path().join("out");
+
+        let jobs = find_batch_jobs(&parent, &out).unwrap();
+        assert_eq!(
+            jobs.iter()
+                .map(|job| job.title.as_str())
+                .collect::<Vec<_>>(),
+            ["sample-01", "sample-02"]
+        );
+        assert_eq!(jobs[1].output, out.join("sample-02.eln"));
+        assert_eq!(jobs[1].folder, parent.join("sample-02"));
+
+        let err = find_batch_jobs(&parent.join("scratch"), &out).unwrap_err();
+        assert!(err.to_string().contains("body.md"), "{err}");
+    }
+
+    #[test]
+    fn jobs_write_metadata_and_attachments() {
+        let tmp = TempDir::new().unwrap();
+        let parent = sample_tree(tmp.path());
+        let out = tmp.path().join("out");
+        fs::create_dir_all(&out).unwrap();
+        let jobs = find_batch_jobs(&parent, &out).unwrap();
+        let options = BatchOptions {
+            license: License::Cc0,
+            ..Default::default()
+        };
+
+        let written = run_batch_job(&jobs[1], &options, &AtomicBool::new(false)).unwrap();
+        assert_eq!(written, out.join("sample-02.eln"));
+        let graph = read_graph(&written, "sample-02");
+        let dataset = graph
+            .iter()
+            .find(|node| node["@id"] == "./experiment/")
+            .unwrap();
+        assert_eq!(dataset["name"], "sample-02");
+        assert_eq!(dataset["keywords"], serde_json::json!(["ngs", "qc"]));
+        let ids: Vec<&str> = graph.iter().filter_map(|n| n["@id"].as_str()).collect();
+        assert!(ids.contains(&"./experiment/raw/reads.fastq"), "{ids:?}");
+        assert!(
+            !ids.iter().any(|id| id.ends_with("/metadata.json")),
+            "the metadata file is read, not attached: {ids:?}"
+        );
+        assert!(
+            graph
+                .iter()
+                .any(|n| n["@type"] == "PropertyValue" && n["propertyID"] == "Reads"),
+            "extra fields are exported"
+        );
+
+        // Existing archives are left alone; the error names them.
+        let err = run_batch_job(&jobs[1], &options, &AtomicBool::new(false)).unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{err}");
+    }
+
+    #[test]
+    fn cancelled_jobs_write_nothing() {
+        let tmp = TempDir::new().unwrap();
+        let parent = sample_tree(tmp.path());
+        let out = tmp.path().join("out");
+        fs::create_dir_all(&out).unwrap();
+        let jobs = find_batch_jobs(&parent, &out).unwrap();
+
+        let err =
+            run_batch_job(&jobs[0], &BatchOptions::default(), &AtomicBool::new(true)).unwrap_err();
+        assert!(err.is::<WriteCancelled>());
+        assert!(!jobs[0].output.exists());
+    }
+}
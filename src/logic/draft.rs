@@ -0,0 +1,344 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Draft persistence: snapshot unsaved entries to disk and restore them on startup.
+//!
+//! Drafts are plain JSON written atomically to the per-user data directory. Attachments
+//! are stored by path and hash only; their contents stay where they are on disk.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::logic::eln::{ArchiveGenre, BodyFormat};
+use crate::models::attachment::Attachment;
+use crate::models::extra_fields::{ExtraField, ExtraFieldGroup, ExtraFieldsSource};
+use crate::models::license::License;
+use crate::models::related_link::RelatedLink;
+use crate::utils::hash_file;
+use crate::utils::storage::write_atomic;
+
+/// Version of the on-disk draft schema; bump when the layout changes incompatibly.
+pub const DRAFT_SCHEMA_VERSION: u32 = 1;
+
+/// Serializable snapshot of an unsaved entry.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Draft {
+    /// Schema version for forward compatibility checks.
+    pub version: u32,
+    /// Entry title.
+    pub title: String,
+    /// Selected archive genre.
+    pub genre: ArchiveGenre,
+    /// Selected body storage format.
+    pub body_format: BodyFormat,
+    /// Raw markdown body.
+    pub body: String,
+    /// Keywords in display order.
+    pub keywords: Vec<String>,
+    /// Extra fields including their current values.
+    pub extra_fields: Vec<ExtraField>,
+    /// Extra field groups.
+    pub extra_groups: Vec<ExtraFieldGroup>,
+    /// Metadata file the extra fields were imported from, for re-importing it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra_fields_source: Option<ExtraFieldsSource>,
+    /// Performed-at date in ISO 8601 (`YYYY-MM-DD`).
+    pub performed_date: String,
+    /// Performed-at hour (0-23).
+    pub performed_hour: i32,
+    /// Performed-at minute (0-59).
+    pub performed_minute: i32,
+    /// Attachments stored by path, archive name, and recorded hash.
+    pub attachments: Vec<Attachment>,
+    /// Language tag chosen for the body; empty to detect it when saving.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub language: String,
+    /// Links to related experiments and resources.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related_links: Vec<RelatedLink>,
+    /// License declared for the entry.
+    #[serde(default, skip_serializing_if = "License::is_none")]
+    pub license: License,
+    /// Short summary of the entry.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub summary: String,
+    /// eLabFTW shows only the extra fields of the entry.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hide_main_text: bool,
+    /// Further entries of a multi-entry session, in list order; their `version` and
+    /// `body_format` are ignored and they carry no further entries themselves.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub more_entries: Vec<Draft>,
+}
+
+impl Draft {
+    /// Return true when the draft carries no user content worth restoring.
+    pub fn is_empty(&self) -> bool {
+        self.title.trim().is_empty()
+            && self.body.trim().is_empty()
+            && self.keywords.is_empty()
+            && self.extra_fields.is_empty()
+            && self.attachments.is_empty()
+            && self.related_links.is_empty()
+            && self.more_entries.iter().all(Draft::is_empty)
+    }
+}
+
+/// Serialize and atomically write `draft` to `path`.
+///
+/// # Errors
+///
+/// Returns an error when serialization or the atomic write fails.
+pub fn save_draft(path: &Path, draft: &Draft) -> Result<()> {
+    let bytes = serde_json::to_vec_pretty(draft).context("Failed to serialize draft")?;
+    write_atomic(path, &bytes)
+}
+
+/// Load a draft from `path`.
+///
+/// Returns `Ok(None)` when no draft file exists or the stored draft is empty.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be read, is not valid JSON, or was written by
+/// an incompatible schema version.
+pub fn load_draft(path: &Path) -> Result<Option<Draft>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err).with_context(|| format!("Failed to read draft {:?}", path)),
+    };
+
+    let draft: Draft = serde_json::from_str(&content).context("Draft file is corrupt")?;
+    if draft.version != DRAFT_SCHEMA_VERSION {
+        anyhow::bail!(
+            "Unsupported draft version {} (expected {})",
+            draft.version,
+            DRAFT_SCHEMA_VERSION
+        );
+    }
+
+    Ok((!draft.is_empty()).then_some(draft))
+}
+
+/// Delete the draft at `path`; a missing file is not an error.
+///
+/// # Errors
+///
+/// Returns an error when an existing draft cannot be removed.
+pub fn delete_draft(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err).with_context(|| format!("Failed to delete draft {:?}", path)),
+    }
+}
+
+/// Remove the attachments of `draft` and its further entries that vanished or changed on
+/// disk, and return them.
+pub fn drop_unrestorable_attachments(draft: &mut Draft) -> Vec<Attachment> {
+    let (present, mut missing) =
+        partition_restorable_attachments(std::mem::take(&mut draft.attachments));
+    draft.attachments = present;
+    for entry in &mut draft.more_entries {
+        missing.extend(drop_unrestorable_attachments(entry));
+    }
+    missing
+}
+
+/// Split restored attachments into those still present on disk and those missing or changed.
+///
+/// A file counts as changed when its SHA-256 differs from the recorded one, which re-reads
+/// every file. Attachments whose hash was unavailable when added are compared by size.
+pub fn partition_restorable_attachments(
+    attachments: Vec<Attachment>,
+) -> (Vec<Attachment>, Vec<Attachment>) {
+    attachments.into_iter().partition(is_unchanged_on_disk)
+}
+
+/// Whether `att` still has its recorded size and SHA-256 on disk.
+fn is_unchanged_on_disk(att: &Attachment) -> bool {
+    let same_size = att
+        .path
+        .metadata()
+        .is_ok_and(|meta| meta.is_file() && meta.len() == att.size);
+    if !same_size || att.sha256 == "unavailable" {
+        return same_size;
+    }
+    hash_file(&att.path).is_ok_and(|digest| digest == att.sha256)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::extra_fields::ExtraFieldKind;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn sample_draft() -> Draft {
+        Draft {
+            version: DRAFT_SCHEMA_VERSION,
+            title: "Cell viability".into(),
+            genre: ArchiveGenre::Resource,
+            body_format: BodyFormat::Markdown,
+            body: "# Notes".into(),
+            keywords: vec!["cells".into()],
+            extra_fields: vec![ExtraField {
+                label: "Temp".into(),
+                kind: ExtraFieldKind::DateTimeLocal,
+                value: "2025-01-01T10:00".into(),
+                position: Some(1),
+                group_id: Some(1),
+                ..Default::default()
+            }],
+            extra_groups: vec![ExtraFieldGroup {
+                id: 1,
+                name: "Default".into(),
+                position: 0,
+            }],
+            extra_fields_source: Some(ExtraFieldsSource {
+                path: PathBuf::from("/templates/template_v3.json"),
+                imported_at: 1_741_942_800,
+                sha256: "ab".repeat(32),
+            }),
+            performed_date: "2025-03-14".into(),
+            performed_hour: 9,
+            performed_minute: 30,
+            attachments: Vec::new(),
+            language: String::new(),
+            related_links: Vec::new(),
+            license: License::None,
+            summary: String::new(),
+            hide_main_text: false,
+            more_entries: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn draft_round_trips_through_disk() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("draft.json");
+        let draft = sample_draft();
+
+        save_draft(&path, &draft).unwrap();
+        let loaded = load_draft(&path).unwrap().expect("draft present");
+
+        assert_eq!(loaded, draft);
+        let raw = fs::read_to_string(&path).unwrap();
+        assert!(
+            raw.contains("\"datetime-local\""),
+            "kinds use eLabFTW tokens"
+        );
+    }
+
+    #[test]
+    fn drafts_without_an_import_source_still_load() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("draft.json");
+        let draft = Draft {
+            extra_fields_source: None,
+            ..sample_draft()
+        };
+
+        save_draft(&path, &draft).unwrap();
+        let raw = fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("extra_fields_source"), "omitted when unset");
+        assert_eq!(load_draft(&path).unwrap(), Some(draft));
+    }
+
+    #[test]
+    fn missing_or_empty_draft_loads_as_none() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("draft.json");
+        assert!(load_draft(&path).unwrap().is_none());
+
+        let mut empty = sample_draft();
+        empty.title.clear();
+        empty.body.clear();
+        empty.keywords.clear();
+        empty.extra_fields.clear();
+        save_draft(&path, &empty).unwrap();
+        assert!(load_draft(&path).unwrap().is_none());
+    }
+
+    #[test]
+    fn corrupt_or_future_draft_is_an_error() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("draft.json");
+
+        fs::write(&path, "{ not json").unwrap();
+        assert!(load_draft(&path).is_err());
+
+        let mut future = sample_draft();
+        future.version = DRAFT_SCHEMA_VERSION + 1;
+        save_draft(&path, &future).unwrap();
+        assert!(load_draft(&path).is_err());
+    }
+
+    #[test]
+    fn delete_draft_ignores_missing_file() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("draft.json");
+        save_draft(&path, &sample_draft()).unwrap();
+
+        delete_draft(&path).unwrap();
+        assert!(!path.exists());
+        delete_draft(&path).unwrap();
+    }
+
+    #[test]
+    fn partition_separates_missing_resized_and_edited_attachments() {
+        let tmp = TempDir::new().unwrap();
+        let present = tmp.path().join("a.txt");
+        let resized = tmp.path().join("b.txt");
+        let edited = tmp.path().join("c.txt");
+        fs::write(&present, b"abc").unwrap();
+        fs::write(&resized, b"abcdef").unwrap();
+        fs::write(&edited, b"xyz").unwrap();
+        let abc_sha256 = hash_file(&present).unwrap();
+
+        let att = |path: PathBuf, sha256: &str, size| {
+            Attachment::new(path, "x".into(), "text/plain".into(), sha256.into(), size)
+        };
+        let (ok, missing) = partition_restorable_attachments(vec![
+            att(present.clone(), &abc_sha256, 3),
+            att(resized.clone(), "unavailable", 3),
+            att(edited.clone(), &abc_sha256, 3),
+            att(tmp.path().join("gone.txt"), "unavailable", 1),
+        ]);
+
+        assert_eq!(ok.len(), 1);
+        assert_eq!(ok[0].path, present);
+        assert_eq!(missing.len(), 3);
+    }
+
+    #[test]
+    fn unrestorable_attachments_are_dropped_from_every_entry() {
+        let tmp = TempDir::new().unwrap();
+        let gone = |name: &str| {
+            Attachment::new(
+                tmp.path().join(name),
+                name.into(),
+                "text/plain".into(),
+                "unavailable".into(),
+                1,
+            )
+        };
+        let mut draft = sample_draft();
+        draft.attachments = vec![gone("a.txt")];
+        draft.more_entries = vec![Draft {
+            attachments: vec![gone("b.txt")],
+            ..sample_draft()
+        }];
+
+        let missing = drop_unrestorable_attachments(&mut draft);
+
+        assert_eq!(missing.len(), 2);
+        assert!(draft.attachments.is_empty());
+        assert!(draft.more_entries[0].attachments.is_empty());
+    }
+}
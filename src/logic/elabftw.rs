@@ -0,0 +1,477 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Upload of saved archives to an eLabFTW instance through its import API.
+//!
+//! The HTTP layer sits behind [`HttpClient`] so the request building and the translation
+//! of failures into actionable messages can be tested without a server. The archive is
+//! streamed from disk rather than loaded into memory, reporting the bytes sent to a callback
+//! that can also cancel the upload. The API key is stored in its own file in the config
+//! directory, readable only by the user on Unix.
+
+use std::fs::{self, File};
+use std::io::{self, Cursor, ErrorKind, Read};
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use crate::i18n::t;
+use crate::utils::storage::write_private;
+use crate::utils::text::ellipsize;
+
+/// Path of the import endpoint below the instance address.
+const IMPORT_ENDPOINT: &str = "api/v2/import";
+
+/// Upper bound for one upload, including the server-side import.
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Longest part of a server reply quoted in an error message.
+const MAX_REPLY_CHARS: usize = 200;
+
+/// Instance and credentials an archive is uploaded with.
+#[derive(Clone, PartialEq, Eq)]
+pub struct UploadTarget {
+    /// Normalized instance address such as `https://elab.example.org`.
+    pub url: String,
+    pub api_key: String,
+}
+
+impl std::fmt::Debug for UploadTarget {
+    /// Never print the API key, e.g. in event traces.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UploadTarget")
+            .field("url", &self.url)
+            .field("api_key", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Bytes of the request body sent so far, reported while uploading.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UploadProgress {
+    pub bytes_sent: u64,
+    /// Size of the whole body: the archive and its multipart framing.
+    pub bytes_total: u64,
+}
+
+/// Reader over the request body that reports every chunk read through it, and fails the
+/// request once the callback breaks.
+struct ProgressReader<'a, R> {
+    inner: R,
+    progress: UploadProgress,
+    on_progress: &'a mut dyn FnMut(&UploadProgress) -> ControlFlow<()>,
+    cancelled: bool,
+}
+
+impl<R: Read> Read for ProgressReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.progress.bytes_sent += read as u64;
+        if (self.on_progress)(&self.progress).is_break() {
+            self.cancelled = true;
+            return Err(io::Error::other("upload cancelled"));
+        }
+        Ok(read)
+    }
+}
+
+/// Status, `Location` header and body of an HTTP response.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub location: Option<String>,
+    pub body: String,
+}
+
+/// Minimal HTTP client for the upload; [`UreqClient`] in the app, a mock in tests.
+pub trait HttpClient {
+    /// Send a POST request with a body read from `body` and return the response, whatever
+    /// its status.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when no response was received, e.g. the host is unreachable.
+    fn post(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: &mut dyn Read,
+    ) -> io::Result<HttpResponse>;
+}
+
+/// [`HttpClient`] backed by `ureq`.
+pub struct UreqClient {
+    agent: ureq::Agent,
+}
+
+impl Default for UreqClient {
+    fn default() -> Self {
+        let config = ureq::Agent::config_builder()
+            .timeout_global(Some(UPLOAD_TIMEOUT))
+            .http_status_as_error(false)
+            .build();
+        Self {
+            agent: config.into(),
+        }
+    }
+}
+
+impl HttpClient for UreqClient {
+    fn post(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: &mut dyn Read,
+    ) -> io::Result<HttpResponse> {
+        let mut request = self.agent.post(url);
+        for &(name, value) in headers {
+            request = request.header(name, value);
+        }
+        let mut response = request
+            .send(ureq::SendBody::from_reader(body))
+            .map_err(io::Error::other)?;
+        let location = response
+            .headers()
+            .get("location")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        Ok(HttpResponse {
+            status: response.status().as_u16(),
+            location,
+            body: response.body_mut().read_to_string().unwrap_or_default(),
+        })
+    }
+}
+
+/// Upload the archive at `path` to `target` and return the address of the imported entry.
+///
+/// `on_progress` sees the bytes sent after every chunk; breaking from it cancels the upload.
+///
+/// Falls back to the instance address when the server does not report where the entry
+/// was created.
+///
+/// # Errors
+///
+/// Returns a message for the status bar when the archive cannot be read, the server is
+/// unreachable, the API key is rejected, the import fails, or the upload was cancelled.
+pub fn upload_archive(
+    client: &dyn HttpClient,
+    target: &UploadTarget,
+    path: &Path,
+    mut on_progress: impl FnMut(&UploadProgress) -> ControlFlow<()>,
+) -> Result<String, String> {
+    if target.url.is_empty() || target.api_key.is_empty() {
+        return Err(t!("upload.no_target").into());
+    }
+    let read_error = |err: io::Error| t!("upload.unreadable", path = path.display(), err = err);
+    let archive = File::open(path).map_err(read_error)?;
+    let archive_len = archive.metadata().map_err(read_error)?.len();
+    let file_name = path
+        .file_name()
+        .map_or_else(|| "entry.eln".into(), |n| n.to_string_lossy().into_owned());
+    let boundary = format!("elnpack-{}", uuid::Uuid::new_v4().simple());
+    let (body, body_len) = multipart_body(&boundary, &file_name, archive, archive_len);
+    let content_type = format!("multipart/form-data; boundary={boundary}");
+    let content_length = body_len.to_string();
+    let url = format!("{}/{IMPORT_ENDPOINT}", target.url.trim_end_matches('/'));
+    let headers = [
+        ("Authorization", target.api_key.as_str()),
+        ("Content-Type", content_type.as_str()),
+        ("Content-Length", content_length.as_str()),
+        ("Accept", "application/json"),
+    ];
+
+    let mut body = ProgressReader {
+        inner: body,
+        progress: UploadProgress {
+            bytes_sent: 0,
+            bytes_total: body_len,
+        },
+        on_progress: &mut on_progress,
+        cancelled: false,
+    };
+    let response = client.post(&url, &headers, &mut body).map_err(|err| {
+        if body.cancelled {
+            t!("upload.cancelled").to_string()
+        } else {
+            t!("upload.unreachable", url = target.url, err = err)
+        }
+    })?;
+    match response.status {
+        200..=299 => Ok(response
+            .location
+            .map(|location| absolute_url(&target.url, &location))
+            .unwrap_or_else(|| target.url.clone())),
+        401 | 403 => Err(t!("upload.rejected_key", status = response.status)),
+        413 => Err(t!("upload.too_large").into()),
+        status => {
+            let reply = ellipsize(response.body.trim(), MAX_REPLY_CHARS);
+            Err(if reply.is_empty() {
+                t!("upload.not_imported", status = status)
+            } else {
+                t!("upload.not_imported.reply", status = status, reply = reply)
+            })
+        }
+    }
+}
+
+/// Encode `content` of `content_len` bytes as the `file` field of a `multipart/form-data`
+/// body, returned as a reader together with its total length.
+fn multipart_body(
+    boundary: &str,
+    file_name: &str,
+    content: impl Read,
+    content_len: u64,
+) -> (impl Read, u64) {
+    let name = file_name.replace(['"', '\r', '\n'], "_");
+    let head = format!(
+        "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{name}\"\r\nContent-Type: application/vnd.eln+zip\r\n\r\n"
+    )
+    .into_bytes();
+    let tail = format!("\r\n--{boundary}--\r\n").into_bytes();
+    let len = head.len() as u64 + content_len + tail.len() as u64;
+    // `take` keeps the body consistent with the announced length if the file grows.
+    let body = Cursor::new(head)
+        .chain(content.take(content_len))
+        .chain(Cursor::new(tail));
+    (body, len)
+}
+
+/// Resolve a `Location` header that may be relative to the instance address.
+fn absolute_url(base: &str, location: &str) -> String {
+    if location.starts_with("http://") || location.starts_with("https://") {
+        location.to_string()
+    } else {
+        format!(
+            "{}/{}",
+            base.trim_end_matches('/'),
+            location.trim_start_matches('/')
+        )
+    }
+}
+
+/// Read the stored API key; a missing file means none is stored.
+///
+/// # Errors
+///
+/// Returns an error when the file exists but cannot be read.
+pub fn load_api_key(path: &Path) -> Result<String> {
+    match fs::read_to_string(path) {
+        Ok(key) => Ok(key.trim().to_string()),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(String::new()),
+        Err(err) => Err(err).with_context(|| format!("Failed to read API key {:?}", path)),
+    }
+}
+
+/// Store `key`, or delete the file when it is empty.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be written or removed.
+pub fn save_api_key(path: &Path, key: &str) -> Result<()> {
+    let key = key.trim();
+    if key.is_empty() {
+        return match fs::remove_file(path) {
+            Err(err) if err.kind() != ErrorKind::NotFound => {
+                Err(err).with_context(|| format!("Failed to delete API key {:?}", path))
+            }
+            _ => Ok(()),
+        };
+    }
+    write_private(path, key.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /// URL, headers, and body of a recorded request.
+    type Request = (String, Vec<(String, String)>, Vec<u8>);
+
+    /// Records requests and answers with a canned response or transport error.
+    struct MockServer {
+        reply: io::Result<HttpResponse>,
+        requests: RefCell<Vec<Request>>,
+    }
+
+    impl MockServer {
+        fn replying(status: u16, location: Option<&str>, body: &str) -> Self {
+            Self {
+                reply: Ok(HttpResponse {
+                    status,
+                    location: location.map(str::to_string),
+                    body: body.into(),
+                }),
+                requests: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl HttpClient for MockServer {
+        fn post(
+            &self,
+            url: &str,
+            headers: &[(&str, &str)],
+            body: &mut dyn Read,
+        ) -> io::Result<HttpResponse> {
+            let mut content = Vec::new();
+            body.read_to_end(&mut content)?;
+            self.requests.borrow_mut().push((
+                url.into(),
+                headers
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect(),
+                content,
+            ));
+            match &self.reply {
+                Ok(response) => Ok(response.clone()),
+                Err(err) => Err(io::Error::new(err.kind(), err.to_string())),
+            }
+        }
+    }
+
+    fn target() -> UploadTarget {
+        UploadTarget {
+            url: "https://elab.example.org".into(),
+            api_key: "3-secret".into(),
+        }
+    }
+
+    /// Upload without watching the progress.
+    fn upload(
+        client: &dyn HttpClient,
+        target: &UploadTarget,
+        path: &Path,
+    ) -> Result<String, String> {
+        upload_archive(client, target, path, |_| ControlFlow::Continue(()))
+    }
+
+    fn archive(tmp: &TempDir) -> std::path::PathBuf {
+        let path = tmp.path().join("run.eln");
+        fs::write(&path, b"PK-archive").unwrap();
+        path
+    }
+
+    #[test]
+    fn upload_posts_the_archive_and_returns_the_created_entry() {
+        let tmp = TempDir::new().unwrap();
+        let server = MockServer::replying(201, Some("/api/v2/experiments/42"), "");
+
+        let url = upload(&server, &target(), &archive(&tmp)).unwrap();
+        assert_eq!(url, "https://elab.example.org/api/v2/experiments/42");
+
+        let requests = server.requests.borrow();
+        let (url, headers, body) = &requests[0];
+        assert_eq!(url, "https://elab.example.org/api/v2/import");
+        assert!(headers.contains(&("Authorization".into(), "3-secret".into())));
+        let content_type = &headers.iter().find(|(n, _)| n == "Content-Type").unwrap().1;
+        let boundary = content_type.split("boundary=").nth(1).unwrap();
+        let length = &headers
+            .iter()
+            .find(|(n, _)| n == "Content-Length")
+            .unwrap()
+            .1;
+        assert_eq!(length, &body.len().to_string());
+        let body = String::from_utf8_lossy(body);
+        assert!(body.starts_with(&format!("--{boundary}\r\n")), "{body}");
+        assert!(body.contains("filename=\"run.eln\""), "{body}");
+        assert!(
+            body.ends_with(&format!("PK-archive\r\n--{boundary}--\r\n")),
+            "{body}"
+        );
+    }
+
+    #[test]
+    fn failures_become_actionable_messages() {
+        let tmp = TempDir::new().unwrap();
+        let path = archive(&tmp);
+
+        let rejected = MockServer::replying(401, None, "{\"code\":401}");
+        let err = upload(&rejected, &target(), &path).unwrap_err();
+        assert!(err.contains("rejected the API key"), "{err}");
+
+        let offline = MockServer {
+            reply: Err(io::Error::new(
+                ErrorKind::ConnectionRefused,
+                "connection refused",
+            )),
+            requests: RefCell::new(Vec::new()),
+        };
+        let err = upload(&offline, &target(), &path).unwrap_err();
+        assert!(err.starts_with("Could not reach eLabFTW"), "{err}");
+
+        let broken = MockServer::replying(500, None, "  Internal error  ");
+        let err = upload(&broken, &target(), &path).unwrap_err();
+        assert_eq!(
+            err,
+            "eLabFTW could not import the archive (HTTP 500): Internal error"
+        );
+
+        let unconfigured = UploadTarget {
+            api_key: String::new(),
+            ..target()
+        };
+        let err = upload(&rejected, &unconfigured, &path).unwrap_err();
+        assert!(err.contains("in Settings"), "{err}");
+        assert_eq!(
+            rejected.requests.borrow().len(),
+            1,
+            "nothing sent without a key"
+        );
+        assert!(!format!("{:?}", target()).contains("secret"));
+    }
+
+    #[test]
+    fn progress_counts_the_body_and_breaking_cancels_the_upload() {
+        let tmp = TempDir::new().unwrap();
+        let path = archive(&tmp);
+        let server = MockServer::replying(201, None, "");
+
+        let mut reported = Vec::new();
+        upload_archive(&server, &target(), &path, |progress| {
+            reported.push(*progress);
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        let sent = server.requests.borrow()[0].2.len() as u64;
+        let last = reported.last().unwrap();
+        assert_eq!((last.bytes_sent, last.bytes_total), (sent, sent));
+        assert!(
+            reported
+                .windows(2)
+                .all(|pair| pair[0].bytes_sent <= pair[1].bytes_sent)
+        );
+
+        let err =
+            upload_archive(&server, &target(), &path, |_| ControlFlow::Break(())).unwrap_err();
+        assert_eq!(err, "Upload cancelled.");
+        assert_eq!(server.requests.borrow().len(), 1, "nothing was received");
+    }
+
+    #[test]
+    fn api_key_is_stored_privately_and_removed_when_cleared() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("elabftw-api-key");
+        assert_eq!(load_api_key(&path).unwrap(), "");
+
+        save_api_key(&path, " 3-secret\n").unwrap();
+        assert_eq!(load_api_key(&path).unwrap(), "3-secret");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        save_api_key(&path, "").unwrap();
+        assert!(!path.exists());
+        save_api_key(&path, "").unwrap();
+    }
+}
@@ -0,0 +1,280 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Reading a single entry from eLabFTW's JSON export into the form.
+//!
+//! eLabFTW exports experiments and resources as JSON objects (or an array of them) carrying
+//! `title`, an HTML or markdown `body`, pipe-separated `tags`, the `date` and the extra
+//! fields as a `metadata` JSON blob. Every section is optional; missing ones are skipped.
+
+use anyhow::{Context, Result, bail};
+use serde_json::Value;
+use time::macros::format_description;
+use time::{Date, PrimitiveDateTime, Time};
+
+use crate::i18n::t;
+use crate::logic::html_markdown::html_to_markdown;
+use crate::models::extra_fields::{ExtraField, ExtraFieldGroup, parse_elabftw_extra_fields};
+
+/// eLabFTW `content_type` of bodies written in its markdown editor; others are HTML.
+const CONTENT_TYPE_MARKDOWN: i64 = 2;
+
+/// Entry content found in an eLabFTW JSON export.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ElabftwEntry {
+    pub title: Option<String>,
+    /// Body as markdown; HTML bodies are converted.
+    pub body: Option<String>,
+    pub keywords: Vec<String>,
+    /// Date the entry was performed.
+    pub date: Option<Date>,
+    /// Time of day, when the export records one along with the date.
+    pub time: Option<Time>,
+    pub extra_fields: Vec<ExtraField>,
+    pub extra_groups: Vec<ExtraFieldGroup>,
+    /// The metadata turned off `display_main_text`.
+    pub hide_main_text: bool,
+    /// Entries in the file; only the first one is read.
+    pub entries_in_file: usize,
+}
+
+impl ElabftwEntry {
+    /// Status line naming what was found, e.g. `Imported title, body, 4 keywords, 7 fields`.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.title.is_some() {
+            parts.push("title".to_string());
+        }
+        if self.body.is_some() {
+            parts.push("body".to_string());
+        }
+        if !self.keywords.is_empty() {
+            parts.push(count(self.keywords.len(), "keyword"));
+        }
+        if self.date.is_some() {
+            parts.push("date".to_string());
+        }
+        if !self.extra_fields.is_empty() {
+            parts.push(count(self.extra_fields.len(), "field"));
+        }
+        let mut summary = format!("Imported {}", parts.join(", "));
+        if self.entries_in_file > 1 {
+            summary.push_str(&format!(
+                " from the first of {} entries",
+                self.entries_in_file
+            ));
+        }
+        summary
+    }
+}
+
+fn count(n: usize, noun: &str) -> String {
+    format!("{n} {noun}{}", if n == 1 { "" } else { "s" })
+}
+
+/// Parse an eLabFTW entry export; arrays contribute their first entry.
+///
+/// # Errors
+///
+/// Returns an error when `json` is not valid JSON, holds no entry object, its metadata blob
+/// cannot be parsed, or the entry has none of the sections ELNPack imports.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let entry = parse_elabftw_entry(r#"{"title": "Run 4", "tags": "PCR|gel"}"#)?;
+/// assert_eq!(entry.keywords, ["PCR", "gel"]);
+/// ```
+pub fn parse_elabftw_entry(json: &str) -> Result<ElabftwEntry> {
+    let value: Value = serde_json::from_str(json).context("Failed to parse eLabFTW JSON")?;
+    let (object, entries_in_file) = match &value {
+        Value::Array(entries) => (entries.first(), entries.len()),
+        Value::Object(_) => (Some(&value), 1),
+        _ => (None, 0),
+    };
+    let Some(object) = object.and_then(Value::as_object) else {
+        bail!(t!("entry_import.no_entry"));
+    };
+
+    let text = |key: &str| {
+        object
+            .get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    };
+    let markdown_body =
+        object.get("content_type").and_then(Value::as_i64) == Some(CONTENT_TYPE_MARKDOWN);
+    let body = text("body")
+        .map(|body| {
+            if markdown_body {
+                body.to_string()
+            } else {
+                html_to_markdown(body)
+            }
+        })
+        .filter(|body| !body.is_empty());
+    let (date, time) = match text("date") {
+        Some(raw) => match parse_entry_date(raw) {
+            Some((date, time)) => (Some(date), time),
+            None => bail!(t!("entry_import.date", date = format!("{raw:?}"))),
+        },
+        None => (None, None),
+    };
+    let (extra_fields, extra_groups, hide_main_text) = match object.get("metadata") {
+        Some(Value::String(blob)) if !blob.trim().is_empty() => {
+            let import = parse_elabftw_extra_fields(blob)
+                .context("Failed to read the entry's extra fields")?;
+            (import.fields, import.groups, import.hide_main_text)
+        }
+        Some(blob @ Value::Object(_)) => {
+            let import = parse_elabftw_extra_fields(&blob.to_string())
+                .context("Failed to read the entry's extra fields")?;
+            (import.fields, import.groups, import.hide_main_text)
+        }
+        _ => (Vec::new(), Vec::new(), false),
+    };
+
+    let entry = ElabftwEntry {
+        title: text("title").map(str::to_string),
+        body,
+        keywords: parse_tags(object.get("tags")),
+        date,
+        time,
+        extra_fields,
+        extra_groups,
+        hide_main_text,
+        entries_in_file,
+    };
+    if entry.title.is_none()
+        && entry.body.is_none()
+        && entry.keywords.is_empty()
+        && entry.date.is_none()
+        && entry.extra_fields.is_empty()
+    {
+        bail!(t!("entry_import.empty"));
+    }
+    Ok(entry)
+}
+
+/// Tags as exported: `a|b`, `a, b`, a list of names, or a list of `{"tag": …}` objects.
+fn parse_tags(value: Option<&Value>) -> Vec<String> {
+    let raw: Vec<&str> = match value {
+        Some(Value::String(list)) => list.split(['|', ',']).collect(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| item.as_str().or_else(|| item["tag"].as_str()))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let mut tags: Vec<String> = Vec::new();
+    for tag in raw.into_iter().map(str::trim).filter(|t| !t.is_empty()) {
+        if !tags.iter().any(|known| known == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// Parse `2024-03-14`, `2024-03-14 10:22:01`, RFC 3339 timestamps (as written, without
+/// converting the offset), and the `20240314` of older eLabFTW versions.
+fn parse_entry_date(raw: &str) -> Option<(Date, Option<Time>)> {
+    let compact = format_description!("[year][month][day]");
+    let iso = format_description!("[year]-[month]-[day]");
+    let datetime = format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
+    if raw.len() == 10 {
+        return Date::parse(raw, iso).ok().map(|date| (date, None));
+    }
+    if raw.len() == 8 {
+        return Date::parse(raw, compact).ok().map(|date| (date, None));
+    }
+    // Drop fractional seconds and the offset; eLabFTW writes local wall-clock time.
+    let wall_clock = raw.get(..19)?.replacen('T', " ", 1);
+    PrimitiveDateTime::parse(&wall_clock, datetime)
+        .ok()
+        .map(|at| (at.date(), Some(at.time())))
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::{date, time};
+
+    use super::*;
+
+    /// Trimmed-down experiment as returned by eLabFTW 5's JSON export.
+    const EXPERIMENT_EXPORT: &str = r#"[
+      {
+        "id": 412,
+        "title": "Miniprep of pUC19 clones",
+        "date": "2024-03-14",
+        "body": "<h2>Procedure</h2><p>Resuspend pellet in <strong>250&nbsp;&micro;l</strong> P1.</p><ul><li>Lyse</li><li>Neutralize</li></ul>",
+        "content_type": 1,
+        "category_title": "Cloning",
+        "tags": "plasmid|miniprep|E. coli",
+        "tags_id": "3|7|12",
+        "metadata": "{\"extra_fields\":{\"Strain\":{\"type\":\"select\",\"value\":\"DH5a\",\"options\":[\"DH5a\",\"BL21\"],\"group_id\":1,\"position\":1},\"Yield\":{\"type\":\"number\",\"value\":\"86.5\",\"unit\":\"ng/µl\",\"units\":[\"ng/µl\"],\"group_id\":1,\"position\":2}},\"elabftw\":{\"extra_fields_groups\":[{\"id\":1,\"name\":\"Result\"}]}}",
+        "created_at": "2024-03-14 09:12:44",
+        "modified_at": "2024-03-15 17:02:10"
+      },
+      { "id": 413, "title": "Second", "body": null, "tags": null, "metadata": null }
+    ]"#;
+
+    #[test]
+    fn experiment_exports_fill_every_section() {
+        let entry = parse_elabftw_entry(EXPERIMENT_EXPORT).unwrap();
+        assert_eq!(entry.title.as_deref(), Some("Miniprep of pUC19 clones"));
+        assert_eq!(
+            entry.body.as_deref(),
+            Some("## Procedure\n\nResuspend pellet in **250 µl** P1.\n\n- Lyse\n- Neutralize")
+        );
+        assert_eq!(entry.keywords, ["plasmid", "miniprep", "E. coli"]);
+        assert_eq!(entry.date, Some(date!(2024 - 03 - 14)));
+        assert_eq!(entry.time, None);
+        let labels: Vec<&str> = entry
+            .extra_fields
+            .iter()
+            .map(|f| f.label.as_str())
+            .collect();
+        assert_eq!(labels, ["Strain", "Yield"]);
+        assert_eq!(entry.extra_groups.len(), 1);
+        assert_eq!(
+            entry.summary(),
+            "Imported title, body, 3 keywords, date, 2 fields from the first of 2 entries"
+        );
+    }
+
+    #[test]
+    fn resource_exports_with_markdown_bodies_and_missing_sections() {
+        let json = r#"{
+          "id": 9,
+          "title": "Anti-GFP antibody",
+          "body": "Store at **-20 °C**.",
+          "content_type": 2,
+          "date": "2023-11-02T14:30:00+01:00",
+          "tags": [{"id": 1, "tag": "antibody"}, {"id": 2, "tag": "GFP"}],
+          "metadata": null
+        }"#;
+        let entry = parse_elabftw_entry(json).unwrap();
+        assert_eq!(entry.body.as_deref(), Some("Store at **-20 °C**."));
+        assert_eq!(entry.keywords, ["antibody", "GFP"]);
+        assert_eq!(entry.date, Some(date!(2023 - 11 - 02)));
+        assert_eq!(entry.time, Some(time!(14:30)));
+        assert_eq!(entry.summary(), "Imported title, body, 2 keywords, date");
+
+        let legacy = parse_elabftw_entry(r#"{"date": "20190521", "tags": "a, b|a"}"#).unwrap();
+        assert_eq!(legacy.date, Some(date!(2019 - 05 - 21)));
+        assert_eq!(legacy.keywords, ["a", "b"]);
+        assert_eq!(legacy.summary(), "Imported 2 keywords, date");
+    }
+
+    #[test]
+    fn files_without_entries_or_with_broken_metadata_are_rejected() {
+        for json in ["[]", "\"text\"", r#"{"id": 3, "body": ""}"#] {
+            assert!(parse_elabftw_entry(json).is_err(), "{json}");
+        }
+        let err = parse_elabftw_entry(r#"{"title": "x", "metadata": "{oops"}"#).unwrap_err();
+        assert!(format!("{err:#}").contains("extra fields"), "{err:#}");
+        let err = parse_elabftw_entry(r#"{"title": "x", "date": "yesterday"}"#).unwrap_err();
+        assert!(err.to_string().contains("yesterday"), "{err}");
+    }
+}
@@ -8,23 +8,55 @@
 //! - Package experiment content and attachments into a ZIP with RO-Crate metadata.
 //! - Provide lightweight helpers for MIME guessing and markdown rendering.
 
-use std::fs::{self, File};
-use std::io::{Read, Write};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::ops::ControlFlow;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
-use pulldown_cmark::{Options, Parser, html};
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, html};
+use serde::{Deserialize, Serialize};
 use time::{OffsetDateTime, format_description::well_known::Rfc3339};
 use uuid::Uuid;
-use zip::{CompressionMethod, write::FileOptions};
+use zip::{CompressionMethod, ZipArchive, ZipWriter, write::FileOptions};
 
-use crate::models::attachment::{Attachment, assert_unique_sanitized_names};
-use crate::models::extra_fields::{ExtraField, ExtraFieldGroup};
-use crate::utils::{hash_file, sanitize_component};
+use crate::i18n::t;
+use crate::logic::eln_import::read_metadata;
+use crate::logic::html_export::{HtmlPolicy, expand_callouts};
+use crate::logic::language::entry_language;
+use crate::logic::preview::{PreviewContent, render_preview_html};
+use crate::logic::readme::{ReadmeEntry, render_readme};
+use crate::logic::save_resume::{
+    CompletedFile, checkpoint, has_resume_state, partial_path, plan_fingerprint,
+    remove_resume_state, restore,
+};
+use crate::logic::signing::FINGERPRINT_PROPERTY;
+use crate::logic::units::UnitSettings;
+use crate::models::attachment::{
+    Attachment, assert_acquired_not_in_future, assert_no_reserved_names,
+    assert_unique_sanitized_names,
+};
+use crate::models::author::{Author, ORCID_URL_PREFIX};
+use crate::models::extra_fields::{
+    ExtraField, ExtraFieldGroup, ExtraFieldsSource, field_error_message,
+};
+use crate::models::license::License;
+use crate::models::related_link::RelatedLink;
+use crate::utils::hash::{Digests, HashAlgorithm};
+use crate::utils::storage::{available_space, replace_file};
+use crate::utils::{
+    MAX_COMPONENT_LEN, clamp_component, copy_and_hash, copy_and_hash_with_progress,
+    sanitize_component,
+};
 
-/// Internal ELN/RO-Crate format version (eLabFTW expects 103+ for id-based `variableMeasured`).
+/// ELN format version of RO-Crate 1.2 archives (eLabFTW expects 103+ for id-based
+/// `variableMeasured`).
 const ELN_FORMAT_VERSION: i32 = 103;
 
+/// ELN format version understood by eLabFTW 4.x, which embeds `variableMeasured` inline.
+const LEGACY_ELN_FORMAT_VERSION: i32 = 101;
+
 /// Export-ready packaging of extra fields.
 struct ExtraFieldsExport {
     /// PropertyValue nodes for each field.
@@ -33,40 +65,463 @@ struct ExtraFieldsExport {
     metadata_property: serde_json::Value,
     /// List of @id strings to be linked from the experiment variableMeasured.
     variable_measured_ids: Vec<String>,
+    /// `CreativeWork` node per field group, in group order; fields link to theirs via
+    /// `isPartOf`.
+    group_nodes: Vec<serde_json::Value>,
 }
 
+/// Title length above which eLabFTW truncates or rejects titles on import.
+pub const ELABFTW_TITLE_LIMIT: usize = 255;
+
 /// Suggest a safe archive filename from a user-facing title.
 ///
 /// Uses [`crate::utils::sanitize_component()`] for the base name and lowercases it, then
 /// appends the `.eln` extension. Falls back to `eln_entry.eln` when the
-/// sanitized title is empty.
+/// sanitized title is empty. Long titles are clamped to [`MAX_COMPONENT_LEN`] with a hash
+/// suffix, matching the root folder written into the archive.
 pub fn suggested_archive_name(title: &str) -> String {
     let base = sanitize_component(title).to_ascii_lowercase();
     let final_base = if base.is_empty() { "eln_entry" } else { &base };
-    format!("{}.eln", final_base)
+    format!("{}.eln", clamp_component(final_base, MAX_COMPONENT_LEN))
+}
+
+/// Warning for titles longer than eLabFTW accepts, counted in characters.
+pub fn title_length_warning(title: &str) -> Option<String> {
+    let length = title.trim().chars().count();
+    (length > ELABFTW_TITLE_LIMIT).then(|| {
+        t!(
+            "validate.title_length",
+            length = length,
+            limit = ELABFTW_TITLE_LIMIT
+        )
+    })
 }
 
 /// Allowed archive genres for RO-Crate metadata.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ArchiveGenre {
     #[default]
     Experiment,
     Resource,
+    /// Template to start eLabFTW entries from; only written by "Save as template archive".
+    Template,
 }
 
 /// How to store the main body in the RO-Crate metadata.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum BodyFormat {
     #[default]
     Html,
     Markdown,
+    /// HTML in the metadata, plus the markdown source as `body.md` in the entry folder.
+    Both,
+}
+
+/// RO-Crate specification version the archive metadata conforms to, chosen by the eLabFTW
+/// release that should import the archive.
+///
+/// The graph only uses terms defined in both versions. Besides the `@context` and the
+/// descriptor's `conformsTo`, 1.1 archives declare ELN format version 101 and embed the
+/// extra field `PropertyValue`s in `variableMeasured` instead of linking them by `@id`, as
+/// eLabFTW 4.x expects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RoCrateVersion {
+    /// For consumers that only understand RO-Crate 1.1 contexts.
+    #[serde(rename = "1.1")]
+    V1_1,
+    #[default]
+    #[serde(rename = "1.2")]
+    V1_2,
+}
+
+impl RoCrateVersion {
+    /// All supported versions, oldest first.
+    pub const ALL: [RoCrateVersion; 2] = [RoCrateVersion::V1_1, RoCrateVersion::V1_2];
+
+    /// User-facing name, e.g. `RO-Crate 1.2`.
+    pub fn label(self) -> &'static str {
+        match self {
+            RoCrateVersion::V1_1 => "RO-Crate 1.1",
+            RoCrateVersion::V1_2 => "RO-Crate 1.2",
+        }
+    }
+
+    /// Name including the eLabFTW releases that import it, e.g. for the version selector.
+    pub fn compatibility(self) -> &'static str {
+        match self {
+            RoCrateVersion::V1_1 => "eLabFTW 4.x (RO-Crate 1.1, v101)",
+            RoCrateVersion::V1_2 => "eLabFTW ≥ 5.1 (RO-Crate 1.2, v103)",
+        }
+    }
+
+    /// ELN format version written as `version` of the root dataset.
+    fn format_version(self) -> i32 {
+        match self {
+            RoCrateVersion::V1_1 => LEGACY_ELN_FORMAT_VERSION,
+            RoCrateVersion::V1_2 => ELN_FORMAT_VERSION,
+        }
+    }
+
+    /// Permalink of the specification, used as `conformsTo` of the metadata descriptor.
+    fn spec_url(self) -> &'static str {
+        match self {
+            RoCrateVersion::V1_1 => "https://w3id.org/ro/crate/1.1",
+            RoCrateVersion::V1_2 => "https://w3id.org/ro/crate/1.2",
+        }
+    }
+
+    /// JSON-LD context URL of the specification.
+    fn context_url(self) -> String {
+        format!("{}/context", self.spec_url())
+    }
+}
+
+/// How files are compressed in the archive.
+///
+/// Zstandard is not offered: eLabFTW's importer and common unzip tools, e.g. the Windows
+/// Explorer, cannot read it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    /// Store attachments that are already compressed, e.g. images, videos, and archives,
+    /// and deflate everything else.
+    #[default]
+    Auto,
+    /// Write files uncompressed.
+    Stored,
+    Deflate,
+}
+
+impl Compression {
+    /// All methods, in the order of the selector.
+    pub const ALL: [Compression; 3] =
+        [Compression::Auto, Compression::Stored, Compression::Deflate];
+
+    /// User-facing name.
+    pub fn label(self) -> &'static str {
+        match self {
+            Compression::Auto => "Auto",
+            Compression::Stored => t!("settings.compression.stored"),
+            Compression::Deflate => "Deflate",
+        }
+    }
+}
+
+/// Lowest and highest deflate level; higher levels write smaller archives more slowly.
+pub const DEFLATE_LEVELS: std::ops::RangeInclusive<u8> = 1..=9;
+
+/// Compression method and deflate level of saved archives.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompressionOptions {
+    pub method: Compression,
+    /// Deflate level from [`DEFLATE_LEVELS`], also used by `Auto` for the files it deflates.
+    pub level: u8,
+}
+
+impl Default for CompressionOptions {
+    fn default() -> Self {
+        Self {
+            method: Compression::Auto,
+            level: 6,
+        }
+    }
+}
+
+impl CompressionOptions {
+    /// Options of a file with the media type `mime`.
+    fn file_options(self, mime: &str) -> FileOptions<'static, ()> {
+        match self.method {
+            Compression::Stored => {
+                FileOptions::default().compression_method(CompressionMethod::Stored)
+            }
+            Compression::Auto if is_precompressed(mime) => {
+                FileOptions::default().compression_method(CompressionMethod::Stored)
+            }
+            Compression::Auto | Compression::Deflate => self.deflated(),
+        }
+    }
+
+    /// Deflate options whatever the method, for the metadata, which is always compressed.
+    fn deflated(self) -> FileOptions<'static, ()> {
+        let level = self
+            .level
+            .clamp(*DEFLATE_LEVELS.start(), *DEFLATE_LEVELS.end());
+        FileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .compression_level(Some(i64::from(level)))
+    }
+}
+
+/// Whether files of the media type `mime` are compressed already, so deflating them costs
+/// time without making the archive noticeably smaller.
+fn is_precompressed(mime: &str) -> bool {
+    let mime = mime.split(';').next().unwrap_or_default().trim();
+    match mime.split_once('/') {
+        Some(("image", subtype)) => !matches!(subtype, "svg+xml" | "bmp" | "x-ms-bmp"),
+        Some(("video", _)) => true,
+        Some(("audio", subtype)) => !matches!(subtype, "wav" | "x-wav" | "vnd.wave"),
+        Some(("application", subtype)) => matches!(
+            subtype,
+            "zip"
+                | "gzip"
+                | "x-gzip"
+                | "x-bzip2"
+                | "x-xz"
+                | "zstd"
+                | "x-7z-compressed"
+                | "vnd.rar"
+                | "x-rar-compressed"
+        ),
+        _ => false,
+    }
+}
+
+/// Export-time options that shape the archive but are not part of the entry itself.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExportOptions {
+    /// Sanitizer policy applied when the body is exported as HTML.
+    pub html_policy: HtmlPolicy,
+    /// Also write a standalone `index.html` preview into the archive root.
+    pub include_preview: bool,
+    /// Store a one-line summary (title, date, keywords) as the zip archive comment.
+    pub write_zip_comment: bool,
+    /// Validated authors in credit order, written as `Person` nodes of every entry.
+    pub authors: Vec<Author>,
+    /// RO-Crate version the metadata conforms to.
+    pub crate_version: RoCrateVersion,
+    /// Write a `checksums.sha512` manifest of all attachments into the archive root.
+    pub checksums_manifest: bool,
+    /// Write a plain-text `README.txt` describing the entries into the archive root.
+    pub include_readme: bool,
+    /// License named in `README.txt`, e.g. `CC-BY-4.0`; empty when not stated.
+    pub license: String,
+    /// Fingerprint of the key that signs the archive once written, recorded on the
+    /// metadata descriptor; see [`crate::logic::signing`].
+    pub signing_fingerprint: Option<String>,
+    /// Organization credited as publisher of the metadata.
+    pub publisher: PublisherInfo,
+    /// User additions to the table of UCUM codes written for number field units.
+    pub units: UnitSettings,
+    /// Reopen the written archive and check it against its metadata before it replaces the
+    /// file at the output path.
+    pub verify_written: bool,
+    /// Copy each entry's imported metadata file unchanged into its folder, next to the
+    /// metadata reconstructed from the fields.
+    pub include_imported_metadata: bool,
+    /// Compression of the attachments and generated files; the metadata is always deflated.
+    pub compression: CompressionOptions,
+}
+
+/// Organization written as `sdPublisher` of the metadata, e.g. the lab's institute.
+///
+/// Consumers such as eLabFTW show it as the archive's publisher. A blank name credits
+/// ELNPack, as archives did before the publisher could be configured.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PublisherInfo {
+    pub name: String,
+    /// Web address; omitted from the metadata when empty.
+    pub url: String,
+    /// ROR identifier as `https://ror.org/…`; empty when the organization has none.
+    pub ror: String,
+}
+
+impl Default for PublisherInfo {
+    fn default() -> Self {
+        Self {
+            name: "elnPack".into(),
+            url: "https://github.com/cbm343e/elnPack".into(),
+            ror: String::new(),
+        }
+    }
+}
+
+impl PublisherInfo {
+    /// `Organization` node of the publisher. The ROR identifier, when known, also serves as
+    /// the node's `@id`, like ORCID iDs do for authors.
+    fn organization_node(&self) -> serde_json::Value {
+        if self.name.trim().is_empty() {
+            return Self::default().organization_node();
+        }
+        let id = if self.ror.is_empty() {
+            "https://elnpack.app/#organization"
+        } else {
+            self.ror.as_str()
+        };
+        let mut node = serde_json::json!({
+            "@id": id,
+            "@type": "Organization",
+            "name": self.name.trim(),
+        });
+        if !self.url.is_empty() {
+            node["url"] = serde_json::Value::String(self.url.clone());
+        }
+        if !self.ror.is_empty() {
+            node["identifier"] = serde_json::Value::String(self.ror.clone());
+        }
+        node
+    }
+}
+
+/// Attachment that could not be read, or no longer matched its recorded digests, while
+/// writing an archive.
+#[derive(Debug)]
+pub struct AttachmentIntegrityError {
+    /// File on disk that failed the check.
+    pub path: PathBuf,
+    /// The file could not be opened; otherwise its content changed.
+    pub unreadable: bool,
+    message: String,
+}
+
+impl std::fmt::Display for AttachmentIntegrityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for AttachmentIntegrityError {}
+
+/// The drive of the output has less free space than the archive's attachments take up.
+#[derive(Debug)]
+pub struct InsufficientSpace {
+    /// Attachment bytes the archive will hold.
+    pub needed: u64,
+    /// Bytes free on the drive.
+    pub available: u64,
+}
+
+impl std::fmt::Display for InsufficientSpace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let megabytes = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+        f.write_str(&t!(
+            "save.insufficient_space",
+            needed = format!("{:.1}", megabytes(self.needed)),
+            available = format!("{:.1}", megabytes(self.available))
+        ))
+    }
+}
+
+impl std::error::Error for InsufficientSpace {}
+
+/// Attachment bytes written so far while saving an archive.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WriteProgress {
+    /// Archive path of the attachment being copied, relative to its entry folder.
+    pub current_file: String,
+    /// Bytes of all entries' attachments copied so far.
+    pub bytes_done: u64,
+    /// Recorded size of all entries' attachments together.
+    pub bytes_total: u64,
+}
+
+/// The write was stopped by its progress callback; the partial archive was deleted and a
+/// file previously at the output path was left as it was.
+#[derive(Debug)]
+pub struct WriteCancelled;
+
+impl std::fmt::Display for WriteCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(t!("save.cancelled"))
+    }
+}
+
+impl std::error::Error for WriteCancelled {}
+
+/// What a save does when an attachment can no longer be opened while the archive is written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MissingFilePolicy {
+    /// Stop the save and delete the partial archive.
+    #[default]
+    Abort,
+    /// Write the archive without the file and note the omission in its metadata.
+    Skip,
+    /// Pause the save until the user decides for each file.
+    Ask,
+}
+
+impl MissingFilePolicy {
+    /// All policies, in the order they are offered.
+    pub const ALL: [MissingFilePolicy; 3] = [
+        MissingFilePolicy::Abort,
+        MissingFilePolicy::Skip,
+        MissingFilePolicy::Ask,
+    ];
+
+    /// User-facing name, e.g. for the policy selector.
+    pub fn label(self) -> &'static str {
+        match self {
+            MissingFilePolicy::Abort => t!("missing_files.abort"),
+            MissingFilePolicy::Skip => t!("missing_files.skip"),
+            MissingFilePolicy::Ask => t!("missing_files.ask"),
+        }
+    }
+}
+
+/// Decision on one attachment that could not be opened while writing an archive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MissingFileAction {
+    /// Try to open the file again, e.g. after the network share is back.
+    Retry,
+    /// Leave the file out of the archive.
+    Skip,
+    /// Fail with an [`AttachmentIntegrityError`].
+    Abort,
+}
+
+/// Checkpointing of a save that can be resumed after it fails; see
+/// [`crate::logic::save_resume`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Resumable {
+    /// Attachment bytes written between two checkpoints.
+    pub checkpoint_bytes: u64,
+    /// Continue from the checkpoint an interrupted save of the same attachments left.
+    pub resume: bool,
+}
+
+/// How a resumable save started.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResumeOutcome {
+    /// Written from the start, as asked.
+    Fresh,
+    /// Continued after the given number of attachments an interrupted save had written.
+    Resumed { attachments: usize },
+    /// Resuming was asked for but failed for the given reason; written from the start.
+    Restarted(String),
+}
+
+/// Running [`WriteProgress`] and the callback it is reported to.
+struct CopyProgress<'a> {
+    state: WriteProgress,
+    on_progress: &'a mut dyn FnMut(&WriteProgress) -> ControlFlow<()>,
 }
 
+/// Name of the optional plain-text description in the archive root.
+const README_FILE: &str = "README.txt";
+
+/// Name of the markdown copy of the main text in each entry folder, written with
+/// [`BodyFormat::Both`].
+pub(crate) const BODY_MARKDOWN_FILE: &str = "body.md";
+
+/// Name of the unchanged copy of an imported metadata file in each entry folder, written
+/// with [`ExportOptions::include_imported_metadata`].
+pub(crate) const IMPORTED_METADATA_FILE: &str = "imported_metadata.json";
+
+/// Name of the optional checksum manifest in the archive root.
+const CHECKSUMS_MANIFEST: &str = "checksums.sha512";
+
+/// Upper bound for the zip archive comment, in characters.
+const ZIP_COMMENT_MAX_CHARS: usize = 200;
+
 impl ArchiveGenre {
     fn as_str(&self) -> &'static str {
         match self {
             ArchiveGenre::Experiment => "experiment",
             ArchiveGenre::Resource => "resource",
+            ArchiveGenre::Template => "template",
         }
     }
 }
@@ -86,9 +541,85 @@ pub fn ensure_extension(mut path: PathBuf, extension: &str) -> PathBuf {
     path
 }
 
-/// Create a RO-Crate ZIP at `output` containing the experiment text, generated RO-Crate JSON-LD metadata, and the provided attachments.
+/// Content of one entry; every entry becomes its own `Dataset` in the archive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    /// Entry title.
+    pub title: String,
+    /// Markdown text from the editor.
+    pub body: String,
+    /// Attachment metadata (already sanitized and hashed).
+    pub attachments: Vec<Attachment>,
+    /// Extra fields exported as `PropertyValue` nodes.
+    pub extra_fields: Vec<ExtraField>,
+    pub extra_groups: Vec<ExtraFieldGroup>,
+    /// Timestamp for when the entry was performed.
+    pub performed_at: OffsetDateTime,
+    /// Selected archive genre.
+    pub genre: ArchiveGenre,
+    /// Normalized keywords.
+    pub keywords: Vec<String>,
+    /// Language tag chosen in the editor; detected from the body when `None`.
+    pub language: Option<String>,
+    /// Links to related works, already validated.
+    pub related_links: Vec<RelatedLink>,
+    /// File the extra fields were imported from, copied into the entry folder when
+    /// [`ExportOptions::include_imported_metadata`] is set.
+    pub imported_metadata: Option<ExtraFieldsSource>,
+    /// License declared on the entry's `Dataset`, already validated.
+    pub license: License,
+    /// Short abstract exported as the `Dataset`'s `description`; omitted when empty.
+    pub summary: String,
+    /// eLabFTW shows only the extra fields; written as `display_main_text: false`.
+    pub hide_main_text: bool,
+}
+
+/// Longest accepted entry summary, in characters.
+pub const SUMMARY_MAX_CHARS: usize = 500;
+
+impl ArchiveEntry {
+    /// Check the entry before it is written: a title, a summary within [`SUMMARY_MAX_CHARS`],
+    /// unique archive paths, no names reserved for the archive's own files, no future
+    /// acquisition times (relative to `now`), and valid metadata fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found as a user-facing message.
+    pub fn validate(&self, now: OffsetDateTime) -> Result<()> {
+        if self.title.trim().is_empty() {
+            anyhow::bail!(t!("validate.title"));
+        }
+        let summary_chars = self.summary.chars().count();
+        if summary_chars > SUMMARY_MAX_CHARS {
+            anyhow::bail!(t!(
+                "validate.summary_length",
+                length = summary_chars,
+                limit = SUMMARY_MAX_CHARS
+            ));
+        }
+        assert_unique_sanitized_names(&self.attachments)?;
+        assert_no_reserved_names(&self.attachments)?;
+        assert_acquired_not_in_future(&self.attachments, now)?;
+        if let Some(message) = self.extra_fields.iter().find_map(field_error_message) {
+            anyhow::bail!(message);
+        }
+        Ok(())
+    }
+}
+
+/// Archive folder of the entry at `index`: `experiment` for a single entry, otherwise
+/// `experiment-1`, `experiment-2`, …
+fn entry_dir_name(index: usize, count: usize) -> String {
+    if count == 1 {
+        "experiment".to_string()
+    } else {
+        format!("experiment-{}", index + 1)
+    }
+}
+
+/// Create a RO-Crate ZIP at `output` containing one dataset per entry, generated RO-Crate JSON-LD metadata, and the provided attachments.
 ///
-/// Parent directories for `output` are created if missing. Attachment filenames are sanitized and checked for duplicates; attachments with a recorded SHA-256 will be rehashed and rejected if the hash no longer matches. The archive contains a root directory, an `experiment/` directory with the body and attachments, and a `ro-crate-metadata.json` graph including per-file `File` nodes and extra fields exported as `PropertyValue` nodes.
+/// Parent directories for `output` are created if missing. Attachment filenames are sanitized and checked for duplicates and names reserved for the archive's own files per entry; each attachment is hashed while it is copied into the archive, and an attachment whose recorded digests (SHA-256 plus any SHA-512 or BLAKE3) no longer match is rolled back and rejected. The archive contains a root directory, one directory per entry (`experiment/` for a single entry, `experiment-1/`, `experiment-2/`, … otherwise) with the body and attachments, a `ro-crate-metadata.json` graph including per-file `File` nodes and extra fields exported as `PropertyValue` nodes, and optionally a `checksums.sha512` manifest.
 ///
 /// Returns `Ok(())` on success or an error describing any I/O, hashing, or metadata construction failure.
 ///
@@ -97,208 +628,1282 @@ pub fn ensure_extension(mut path: PathBuf, extension: &str) -> PathBuf {
 /// ```rust,ignore
 /// use time::OffsetDateTime;
 /// let output = std::path::Path::new("example.eln");
-/// let title = "My Experiment";
-/// let body = "# Notes\n\nExperiment body";
-/// let attachments: Vec<crate::models::Attachment> = Vec::new();
-/// let extra_fields: Vec<crate::models::ExtraField> = Vec::new();
-/// let extra_groups: Vec<crate::models::ExtraFieldGroup> = Vec::new();
-/// let performed_at = OffsetDateTime::now_utc();
-/// let genre = crate::logic::eln::ArchiveGenre::Experiment;
-/// let keywords: Vec<String> = vec!["test".into()];
-/// let body_format = crate::logic::eln::BodyFormat::Markdown;
+/// let entry = crate::logic::eln::ArchiveEntry {
+///     title: "My Experiment".into(),
+///     body: "# Notes\n\nExperiment body".into(),
+///     attachments: Vec::new(),
+///     extra_fields: Vec::new(),
+///     extra_groups: Vec::new(),
+///     performed_at: OffsetDateTime::now_utc(),
+///     genre: crate::logic::eln::ArchiveGenre::Experiment,
+///     keywords: vec!["test".into()],
+///     language: None,
+/// };
 ///
 /// crate::logic::eln::build_and_write_archive(
 ///     output,
-///     title,
-///     body,
-///     &attachments,
-///     &extra_fields,
-///     &extra_groups,
-///     performed_at,
-///     genre,
-///     &keywords,
-///     body_format,
+///     &[entry],
+///     crate::logic::eln::BodyFormat::Markdown,
+///     &crate::logic::eln::ExportOptions::default(),
 /// ).unwrap();
 /// ```
-#[allow(clippy::too_many_arguments)] // Builder needs explicit, validated inputs; grouping would obscure the contract.
 pub fn build_and_write_archive(
     output: &Path,
-    title: &str,
-    body: &str,
-    attachments: &[Attachment],
-    extra_fields: &[ExtraField],
-    extra_groups: &[ExtraFieldGroup],
-    performed_at: OffsetDateTime,
-    genre: ArchiveGenre,
-    keywords: &[String],
+    entries: &[ArchiveEntry],
     body_format: BodyFormat,
+    export: &ExportOptions,
 ) -> Result<()> {
-    // Ensure parent exists so the archive can be written without IO errors.
-    if let Some(parent) = output.parent()
-        && !parent.exists()
-    {
-        fs::create_dir_all(parent)
-            .with_context(|| format!("Failed to create output directory {:?}", parent))?;
-    }
+    build_and_write_archive_with_progress(output, entries, body_format, export, |_| {
+        ControlFlow::Continue(())
+    })
+}
+
+/// [`build_and_write_archive`] reporting the attachment bytes written after every chunk.
+///
+/// Returning [`ControlFlow::Break`] from `on_progress` stops the write, deletes the partial
+/// archive at `output`, and fails with [`WriteCancelled`].
+///
+/// # Errors
+///
+/// Returns the errors of [`build_and_write_archive`], or [`WriteCancelled`].
+pub fn build_and_write_archive_with_progress<F>(
+    output: &Path,
+    entries: &[ArchiveEntry],
+    body_format: BodyFormat,
+    export: &ExportOptions,
+    mut on_progress: F,
+) -> Result<()>
+where
+    F: FnMut(&WriteProgress) -> ControlFlow<()>,
+{
+    write_with_progress(
+        output,
+        entries,
+        body_format,
+        export,
+        None,
+        &mut on_progress,
+        &mut |_| MissingFileAction::Abort,
+    )
+    .map(|_| ())
+}
 
-    // Guard against duplicate archive paths before writing anything.
-    assert_unique_sanitized_names(attachments)?;
+/// [`build_and_write_archive_with_progress`] checkpointing the partial archive, so that a
+/// failed save can be resumed instead of written again; see [`crate::logic::save_resume`].
+///
+/// The checkpoint is deleted once the archive is complete or the save is cancelled, and kept
+/// when the save fails otherwise. Resuming falls back to writing the archive from the start
+/// when the checkpoint does not match the partial archive or the attachments.
+///
+/// `on_missing` decides about every attachment that cannot be opened. Skipped attachments
+/// are left out of the archive, its preview, and its README, and a `Comment` on their entry
+/// lists them.
+///
+/// # Errors
+///
+/// Returns the errors of [`build_and_write_archive_with_progress`], or an error writing the
+/// checkpoint. An attachment that could not be read deletes the partial archive unless a
+/// checkpoint allows resuming it.
+pub fn build_and_write_archive_resumable<F, M>(
+    output: &Path,
+    entries: &[ArchiveEntry],
+    body_format: BodyFormat,
+    export: &ExportOptions,
+    resumable: Resumable,
+    mut on_progress: F,
+    mut on_missing: M,
+) -> Result<ResumeOutcome>
+where
+    F: FnMut(&WriteProgress) -> ControlFlow<()>,
+    M: FnMut(&Path) -> MissingFileAction,
+{
+    write_with_progress(
+        output,
+        entries,
+        body_format,
+        export,
+        Some(resumable),
+        &mut on_progress,
+        &mut on_missing,
+    )
+}
 
-    let root_folder = sanitize_component(
-        output
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("eln-entry"),
+fn write_with_progress(
+    output: &Path,
+    entries: &[ArchiveEntry],
+    body_format: BodyFormat,
+    export: &ExportOptions,
+    resumable: Option<Resumable>,
+    on_progress: &mut dyn FnMut(&WriteProgress) -> ControlFlow<()>,
+    on_missing: &mut dyn FnMut(&Path) -> MissingFileAction,
+) -> Result<ResumeOutcome> {
+    let mut progress = CopyProgress {
+        state: WriteProgress {
+            bytes_total: entries
+                .iter()
+                .flat_map(|entry| &entry.attachments)
+                .map(|meta| meta.size)
+                .sum(),
+            ..WriteProgress::default()
+        },
+        on_progress,
+    };
+    let result = write_archive(
+        output,
+        entries,
+        body_format,
+        export,
+        resumable,
+        &mut progress,
+        on_missing,
     );
-    let root_prefix = format!("{}/", root_folder);
-    let experiment_dir = format!("{}experiment/", root_prefix);
+    if let Err(err) = &result {
+        if err.is::<WriteCancelled>() {
+            let _ = fs::remove_file(partial_path(output));
+            let _ = remove_resume_state(output);
+        } else if !has_resume_state(output) {
+            // Nothing to resume from, so the partial archive is of no use.
+            let _ = fs::remove_file(partial_path(output));
+        }
+    }
+    result
+}
 
-    let file = File::create(output)
-        .with_context(|| format!("Failed to write archive file {:?}", output))?;
-    let mut zip = zip::ZipWriter::new(file);
-    let options: FileOptions<'_, ()> =
-        FileOptions::default().compression_method(CompressionMethod::Deflated);
+/// Zip writer of the archive being saved, checkpointed when the save is resumable.
+///
+/// The archive is written to the [`partial_path`] of `output` and only moved there once
+/// complete.
+struct ArchiveWriter<'a> {
+    output: &'a Path,
+    /// `None` only while a checkpoint reopens the archive.
+    zip: Option<ZipWriter<File>>,
+    /// Names an interrupted save already wrote, including directories.
+    existing: HashSet<String>,
+    /// Digests of the attachments an interrupted save already wrote, by archive path.
+    resumed: HashMap<String, Digests>,
+    checkpoints: Option<Checkpoints<'a>>,
+}
 
-    zip.add_directory(&root_prefix, options)
-        .context("Failed to create root directory in archive")?;
-    zip.add_directory(&experiment_dir, options)
-        .context("Failed to create experiment directory in archive")?;
+/// Checkpoint bookkeeping of a resumable save.
+struct Checkpoints<'a> {
+    output: &'a Path,
+    plan: String,
+    every_bytes: u64,
+    /// Attachment bytes written since the last checkpoint.
+    pending_bytes: u64,
+    completed: Vec<CompletedFile>,
+}
 
-    let mut file_nodes = Vec::new();
-    for meta in attachments.iter() {
-        // Verify attachment integrity: rehash and compare with stored hash
-        if meta.sha256 != "unavailable" {
-            let current_hash = hash_file(&meta.path)
-                .with_context(|| format!("Failed to rehash attachment {:?}", meta.path))?;
-
-            if current_hash != meta.sha256 {
-                anyhow::bail!(
-                    "Attachment modified since it was added:\n  {:?}\n  expected sha256 {}\n  found sha256 {}",
-                    meta.path,
-                    meta.sha256,
-                    current_hash,
-                );
+impl<'a> ArchiveWriter<'a> {
+    /// Start the archive at `output`, continuing an interrupted save when asked to and
+    /// possible.
+    fn open(
+        output: &'a Path,
+        root_prefix: &str,
+        entries: &[ArchiveEntry],
+        export: &ExportOptions,
+        resumable: Option<Resumable>,
+    ) -> Result<(Self, ResumeOutcome)> {
+        let Some(resumable) = resumable else {
+            return Ok((Self::create(output, None)?, ResumeOutcome::Fresh));
+        };
+        let plan = plan_fingerprint(root_prefix, entries, export.checksums_manifest);
+        let mut outcome = ResumeOutcome::Fresh;
+        if resumable.resume {
+            match restore(output, &plan) {
+                Ok(restored) => {
+                    let outcome = ResumeOutcome::Resumed {
+                        attachments: restored.state.len(),
+                    };
+                    let writer = Self {
+                        output,
+                        zip: Some(restored.zip),
+                        existing: restored.names,
+                        resumed: restored.completed,
+                        checkpoints: Some(Checkpoints {
+                            output,
+                            plan,
+                            every_bytes: resumable.checkpoint_bytes,
+                            pending_bytes: 0,
+                            completed: restored.state,
+                        }),
+                    };
+                    return Ok((writer, outcome));
+                }
+                Err(err) => outcome = ResumeOutcome::Restarted(format!("{err:#}")),
             }
         }
+        remove_resume_state(output)?;
+        let checkpoints = Checkpoints {
+            output,
+            plan,
+            every_bytes: resumable.checkpoint_bytes,
+            pending_bytes: 0,
+            completed: Vec::new(),
+        };
+        Ok((Self::create(output, Some(checkpoints))?, outcome))
+    }
 
-        let display_name = &meta.sanitized_name;
-        let archive_path = format!("{}{}", experiment_dir, display_name);
-        let id = format!("./experiment/{}", display_name);
+    fn create(output: &'a Path, checkpoints: Option<Checkpoints<'a>>) -> Result<Self> {
+        // Readable too, so checkpoints can reopen the archive for appending.
+        let partial = partial_path(output);
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&partial)
+            .with_context(|| format!("Failed to write archive file {:?}", partial))?;
+        Ok(Self {
+            output,
+            zip: Some(ZipWriter::new(file)),
+            existing: HashSet::new(),
+            resumed: HashMap::new(),
+            checkpoints,
+        })
+    }
 
-        zip.start_file(&archive_path, options)
-            .with_context(|| format!("Failed to add file {} to archive", archive_path))?;
+    fn zip(&mut self) -> &mut ZipWriter<File> {
+        self.zip
+            .as_mut()
+            .expect("the archive is open between checkpoints")
+    }
 
-        let mut reader = File::open(&meta.path)
-            .with_context(|| format!("Failed to read attachment {:?}", meta.path))?;
-        let mut _written = 0u64;
-        let mut buffer = [0u8; 8192];
-        loop {
-            let read = reader
-                .read(&mut buffer)
-                .with_context(|| format!("Failed to read from {:?}", meta.path))?;
-            if read == 0 {
-                break;
-            }
-            zip.write_all(&buffer[..read])
-                .with_context(|| format!("Failed to write {} into archive", archive_path))?;
-            _written += read as u64;
+    /// Add the directory `name` unless an interrupted save already did.
+    fn add_directory(&mut self, name: &str, options: FileOptions<'_, ()>) -> Result<()> {
+        if !self.existing.contains(name) {
+            self.zip().add_directory(name, options)?;
         }
+        Ok(())
+    }
 
-        let sha256 = meta.sha256.clone();
-        let encoding = meta.mime.clone();
+    /// Record the attachment just written to `path`, checkpointing when enough bytes were
+    /// written since the last checkpoint.
+    fn attachment_written(&mut self, path: String, digests: &Digests, size: u64) -> Result<()> {
+        let Some(checkpoints) = &mut self.checkpoints else {
+            return Ok(());
+        };
+        checkpoints.completed.push(CompletedFile {
+            path,
+            digests: digests.clone(),
+        });
+        checkpoints.pending_bytes += size;
+        if checkpoints.pending_bytes < checkpoints.every_bytes {
+            return Ok(());
+        }
+        checkpoints.pending_bytes = 0;
+        let zip = self
+            .zip
+            .take()
+            .expect("the archive is open between checkpoints");
+        self.zip = Some(checkpoint(
+            zip,
+            checkpoints.output,
+            &checkpoints.plan,
+            &checkpoints.completed,
+        )?);
+        Ok(())
+    }
 
-        file_nodes.push(serde_json::json!({
-            "@id": id,
-            "@type": "File",
-            "name": display_name,
-            "encodingFormat": encoding,
-            "contentSize": meta.size.to_string(),
-            "sha256": sha256,
-        }));
+    /// Write the central directory, flush the archive to disk, optionally `verify` it, move
+    /// it to the output path, and drop the checkpoint, which is no longer needed.
+    fn finish(mut self, verify: bool) -> Result<()> {
+        let zip = self
+            .zip
+            .take()
+            .expect("the archive is open between checkpoints");
+        let file = zip.finish().context("Failed to finalize archive")?;
+        file.sync_all().context("Failed to flush archive to disk")?;
+        drop(file);
+        let partial = partial_path(self.output);
+        if verify {
+            check_written_archive(&partial, self.output)?;
+        }
+        replace_file(&partial, self.output)?;
+        if let Some(checkpoints) = &self.checkpoints {
+            remove_resume_state(checkpoints.output)?;
+        }
+        Ok(())
+    }
+}
+
+/// Name of the archive's root folder followed by `/`, derived from the file name of `output`.
+fn root_prefix(output: &Path) -> String {
+    let root_folder = clamp_component(
+        &sanitize_component(
+            output
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("eln-entry"),
+        ),
+        MAX_COMPONENT_LEN,
+    );
+    format!("{}/", root_folder)
+}
+
+fn write_archive(
+    output: &Path,
+    entries: &[ArchiveEntry],
+    body_format: BodyFormat,
+    export: &ExportOptions,
+    resumable: Option<Resumable>,
+    progress: &mut CopyProgress<'_>,
+    on_missing: &mut dyn FnMut(&Path) -> MissingFileAction,
+) -> Result<ResumeOutcome> {
+    let Some(first) = entries.first() else {
+        anyhow::bail!("An archive needs at least one entry");
+    };
+
+    // Ensure parent exists so the archive can be written without IO errors.
+    if let Some(parent) = output.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create output directory {:?}", parent))?;
+    }
+
+    // Fail before writing anything when the attachments alone would not fit. An earlier
+    // attempt's partial archive is either continued or overwritten, so its space counts too.
+    let needed = progress.state.bytes_total;
+    let reusable = fs::metadata(partial_path(output)).map_or(0, |meta| meta.len());
+    let dir = output
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    if let Some(available) = available_space(dir)
+        && available.saturating_add(reusable) < needed
+    {
+        return Err(InsufficientSpace { needed, available }.into());
+    }
+
+    // Guard against duplicate or reserved archive paths before writing anything.
+    for entry in entries {
+        assert_unique_sanitized_names(&entry.attachments)?;
+        assert_no_reserved_names(&entry.attachments)?;
     }
+    // Read imported metadata files up front, so one that changed fails the save before the
+    // attachments are copied.
+    let imported: Vec<Option<Vec<u8>>> = entries
+        .iter()
+        .map(|entry| match &entry.imported_metadata {
+            Some(source) if export.include_imported_metadata => {
+                read_imported_metadata(source).map(Some)
+            }
+            _ => Ok(None),
+        })
+        .collect::<Result<_>>()?;
+
+    let root_prefix = root_prefix(output);
+    let (mut writer, outcome) =
+        ArchiveWriter::open(output, &root_prefix, entries, export, resumable)?;
+    // Options of the generated text files; attachments get their own per media type.
+    let options = export.compression.file_options("text/plain");
 
-    let timestamp = performed_at
+    writer
+        .add_directory(&root_prefix, options)
+        .context("Failed to create root directory in archive")?;
+
+    let timestamp = first
+        .performed_at
         .format(&Rfc3339)
         .map_err(|err| anyhow::anyhow!("Failed to format performed_at timestamp: {}", err))?;
-    let (body_text, encoding_format) = match body_format {
-        BodyFormat::Html => (markdown_to_html(body, false), "text/html"),
-        BodyFormat::Markdown => (body.to_string(), "text/markdown"),
-    };
-    let org_id = "https://elnpack.app/#organization";
+    let mut entities = NodeRegistry::default();
+    let publisher_id = entities.add(export.publisher.organization_node())?;
+    let mut author_refs: Vec<serde_json::Value> = Vec::new();
+    for node in person_nodes(&export.authors) {
+        let reference = serde_json::json!({ "@id": entities.add(node)? });
+        if !author_refs.contains(&reference) {
+            author_refs.push(reference);
+        }
+    }
 
-    let ExtraFieldsExport {
-        property_values,
-        metadata_property,
-        variable_measured_ids,
-    } = build_extra_fields_export(extra_fields, extra_groups)?;
+    let languages: Vec<Option<String>> = entries
+        .iter()
+        .map(|entry| entry_language(entry.language.as_deref(), &entry.body))
+        .collect();
+    let mut dataset_nodes = Vec::with_capacity(entries.len());
+    let mut file_nodes = Vec::new();
+    let mut property_nodes = Vec::new();
+    let mut link_nodes: Vec<serde_json::Value> = Vec::new();
+    let mut comment_nodes = Vec::new();
+    // Attachments actually written, for the preview and the README.
+    let mut written: Vec<Vec<Attachment>> = Vec::with_capacity(entries.len());
+    for (index, entry) in entries.iter().enumerate() {
+        let dir_name = entry_dir_name(index, entries.len());
+        let mut nodes = write_entry(
+            &mut writer,
+            options,
+            &root_prefix,
+            &dir_name,
+            entry,
+            body_format,
+            export,
+            &author_refs,
+            progress,
+            on_missing,
+        )?;
+        if let Some(language) = &languages[index] {
+            nodes.dataset["inLanguage"] = serde_json::Value::String(language.clone());
+        }
+        if let Some(node) = license_node(&entry.license) {
+            nodes.dataset["license"] = serde_json::json!({ "@id": entities.add(node)? });
+        }
+        if !entry.summary.is_empty() {
+            nodes.dataset["description"] = serde_json::Value::String(entry.summary.clone());
+        }
+        written.push(
+            entry
+                .attachments
+                .iter()
+                .filter(|meta| !nodes.omitted.contains(&meta.archive_path()))
+                .cloned()
+                .collect(),
+        );
+        dataset_nodes.push(nodes.dataset);
+        file_nodes.extend(nodes.files);
+        property_nodes.extend(nodes.properties);
+        property_nodes.extend(nodes.groups);
+        comment_nodes.extend(nodes.omission);
+        // Entries linking the same URL share one node.
+        for node in nodes.links {
+            if !link_nodes.iter().any(|known| known["@id"] == node["@id"]) {
+                link_nodes.push(node);
+            }
+        }
+    }
 
-    let experiment_node = serde_json::json!({
-        "@id": "./experiment/",
+    let mut root_node = serde_json::json!({
+        "@id": "./",
         "@type": "Dataset",
-        "name": title,
-        "encodingFormat": encoding_format,
-        "text": body_text,
-        "dateCreated": timestamp,
-        "dateModified": timestamp,
-        "author": { "@id": org_id },
-        "genre": genre.as_str(),
-        "keywords": keywords,
-        "variableMeasured": variable_measured_ids
-            .iter()
-            .map(|id| serde_json::json!({"@id": id}))
-            .collect::<Vec<_>>(),
-        "hasPart": file_nodes
+        "name": first.title,
+        "hasPart": dataset_nodes
             .iter()
-            .map(|node| {
-                serde_json::json!({"@id": node["@id"].as_str().unwrap_or("./experiment/") })
-            })
+            .map(|node| serde_json::json!({ "@id": node["@id"] }))
             .collect::<Vec<_>>(),
+        "version": export.crate_version.format_version(),
     });
+    // The crate as a whole is licensed only when all of its entries share the license.
+    if entries.iter().all(|entry| entry.license == first.license)
+        && let Some(url) = first.license.url()
+    {
+        root_node["license"] = serde_json::json!({ "@id": url });
+    }
+    // A single entry's summary also describes the crate.
+    if entries.len() == 1 && !first.summary.is_empty() {
+        root_node["description"] = serde_json::Value::String(first.summary.clone());
+    }
 
-    let root_node = serde_json::json!({
-        "@id": "./",
-        "@type": "Dataset",
-        "name": title,
-        "hasPart": [ { "@id": "./experiment/" } ],
-        "version": ELN_FORMAT_VERSION,
-    });
-
-    let metadata_node = serde_json::json!({
+    // The metadata descriptor cannot record its own size or hash, so it is the only emitted
+    // file without `contentSize` and `sha256`; every `File` node carries both.
+    let mut metadata_node = serde_json::json!({
         "@id": "ro-crate-metadata.json",
         "@type": "CreativeWork",
         "about": { "@id": "./" },
-        "conformsTo": { "@id": "https://w3id.org/ro/crate/1.2" },
+        "conformsTo": { "@id": export.crate_version.spec_url() },
         "dateCreated": timestamp,
-        "sdPublisher": { "@id": org_id },
+        "sdPublisher": { "@id": publisher_id },
     });
+    if let Some(fingerprint) = &export.signing_fingerprint {
+        metadata_node[FINGERPRINT_PROPERTY] = serde_json::Value::String(fingerprint.clone());
+    }
 
-    let organization_node = serde_json::json!({
-        "@id": org_id,
-        "@type": "Organization",
-        "name": "elnPack",
-        "url": "https://github.com/cbm343e/elnPack",
-    });
+    let zip = writer.zip();
+    // Bodies may change between attempts of a resumable save, so like the preview their
+    // markdown copies are written after the last checkpoint.
+    if body_format == BodyFormat::Both {
+        for (index, entry) in entries.iter().enumerate() {
+            let dir_name = entry_dir_name(index, entries.len());
+            let node = write_body_markdown(zip, options, &root_prefix, &dir_name, &entry.body)?;
+            dataset_nodes[index]["hasPart"]
+                .as_array_mut()
+                .expect("dataset hasPart is an array")
+                .push(serde_json::json!({ "@id": node["@id"] }));
+            file_nodes.push(node);
+        }
+    }
+    for (index, (entry, content)) in entries.iter().zip(&imported).enumerate() {
+        let (Some(source), Some(content)) = (&entry.imported_metadata, content) else {
+            continue;
+        };
+        let dir_name = entry_dir_name(index, entries.len());
+        let node = write_imported_metadata(zip, options, &root_prefix, &dir_name, source, content)?;
+        dataset_nodes[index]["hasPart"]
+            .as_array_mut()
+            .expect("dataset hasPart is an array")
+            .push(serde_json::json!({ "@id": node["@id"] }));
+        file_nodes.push(node);
+    }
 
-    let mut graph = vec![metadata_node, root_node, experiment_node, organization_node];
-    graph.extend(file_nodes);
-    graph.push(metadata_property);
-    graph.extend(property_values);
+    let mut preview_node = None;
+    if export.include_preview {
+        let bodies: Vec<String> = entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let dir = entry_dir_name(index, entries.len());
+                markdown_to_html(&entry.body, false, &export.html_policy, Some(&dir))
+            })
+            .collect();
+        let dirs: Vec<String> = (0..entries.len())
+            .map(|index| format!("{}/", entry_dir_name(index, entries.len())))
+            .collect();
+        let sections: Vec<PreviewContent<'_>> = entries
+            .iter()
+            .zip(&bodies)
+            .zip(&dirs)
+            .zip(&languages)
+            .zip(&written)
+            .map(
+                |((((entry, body_html), dir), language), attachments)| PreviewContent {
+                    title: &entry.title,
+                    summary: &entry.summary,
+                    body_html,
+                    performed_at: entry.performed_at,
+                    genre: entry.genre.as_str(),
+                    keywords: &entry.keywords,
+                    extra_fields: &entry.extra_fields,
+                    extra_groups: &entry.extra_groups,
+                    attachments,
+                    attachment_dir: dir,
+                    language: language.as_deref(),
+                },
+            )
+            .collect();
+        let preview = render_preview_html(&sections);
+        zip.start_file(format!("{}index.html", root_prefix), options)
+            .context("Failed to create preview file")?;
+        let (sha256, size) =
+            copy_and_hash(&mut preview.as_bytes(), zip).context("Failed to write preview file")?;
+        root_node["hasPart"]
+            .as_array_mut()
+            .expect("root hasPart is an array")
+            .push(serde_json::json!({ "@id": "./index.html" }));
+        preview_node = Some(serde_json::json!({
+            "@id": "./index.html",
+            "@type": "File",
+            "name": "index.html",
+            "description": "Human-readable preview of the entry",
+            "encodingFormat": "text/html",
+            "contentSize": size.to_string(),
+            "sha256": sha256,
+        }));
+    }
 
-    let metadata = serde_json::json!({
-        "@context": "https://w3id.org/ro/crate/1.2/context",
-        "@graph": graph,
-    });
+    let mut readme_node = None;
+    if export.include_readme {
+        let dirs: Vec<String> = (0..entries.len())
+            .map(|index| format!("{}/", entry_dir_name(index, entries.len())))
+            .collect();
+        let sections: Vec<ReadmeEntry<'_>> = entries
+            .iter()
+            .zip(&dirs)
+            .zip(&written)
+            .map(|((entry, dir), attachments)| ReadmeEntry {
+                title: &entry.title,
+                performed_at: entry.performed_at,
+                keywords: &entry.keywords,
+                body: &entry.body,
+                attachments,
+                attachment_dir: dir,
+            })
+            .collect();
+        let readme = render_readme(&sections, &export.authors, &export.license);
+        zip.start_file(format!("{}{README_FILE}", root_prefix), options)
+            .context("Failed to create README")?;
+        let (sha256, size) =
+            copy_and_hash(&mut readme.as_bytes(), zip).context("Failed to write README")?;
+        let id = format!("./{README_FILE}");
+        root_node["hasPart"]
+            .as_array_mut()
+            .expect("root hasPart is an array")
+            .push(serde_json::json!({ "@id": id }));
+        readme_node = Some(serde_json::json!({
+            "@id": id,
+            "@type": "File",
+            "name": README_FILE,
+            "description": "Plain-text description of the entries and their attachments",
+            "encodingFormat": "text/plain",
+            "contentSize": size.to_string(),
+            "sha256": sha256,
+        }));
+    }
+
+    let mut manifest_node = None;
+    if export.checksums_manifest {
+        let manifest = checksums_manifest(&file_nodes);
+        zip.start_file(format!("{}{CHECKSUMS_MANIFEST}", root_prefix), options)
+            .context("Failed to create checksum manifest")?;
+        let (sha256, size) = copy_and_hash(&mut manifest.as_bytes(), zip)
+            .context("Failed to write checksum manifest")?;
+        let id = format!("./{CHECKSUMS_MANIFEST}");
+        root_node["hasPart"]
+            .as_array_mut()
+            .expect("root hasPart is an array")
+            .push(serde_json::json!({ "@id": id }));
+        manifest_node = Some(serde_json::json!({
+            "@id": id,
+            "@type": "File",
+            "name": CHECKSUMS_MANIFEST,
+            "description": "SHA-512 checksums of all attachments, as written by sha512sum",
+            "encodingFormat": "text/plain",
+            "contentSize": size.to_string(),
+            "sha256": sha256,
+        }));
+    }
+
+    let context = export.crate_version.context_url();
+    let metadata = CrateMetadata {
+        context: &context,
+        graph: &[
+            std::slice::from_ref(&metadata_node),
+            std::slice::from_ref(&root_node),
+            &dataset_nodes,
+            entities.nodes(),
+            preview_node.as_slice(),
+            readme_node.as_slice(),
+            manifest_node.as_slice(),
+            &file_nodes,
+            &property_nodes,
+            &link_nodes,
+            &comment_nodes,
+        ],
+    };
+
+    zip.start_file(
+        format!("{}ro-crate-metadata.json", root_prefix),
+        export.compression.deflated(),
+    )
+    .context("Failed to create metadata file")?;
+    write_metadata(zip, &metadata).context("Failed to write metadata file")?;
+
+    if export.write_zip_comment {
+        let title = match entries.len() {
+            1 => first.title.clone(),
+            n => format!("{} (+{} more)", first.title.trim(), n - 1),
+        };
+        zip.set_comment(archive_comment(&title, first.performed_at, &first.keywords))
+            .context("Failed to set archive comment")?;
+    }
+
+    writer.finish(export.verify_written)?;
+    Ok(outcome)
+}
+
+/// Reopen the archive just written to `partial` and fail when a file differs from its
+/// metadata; `output` names it in the error.
+fn check_written_archive(partial: &Path, output: &Path) -> Result<()> {
+    let report = verify_archive(partial).map_err(|err| {
+        anyhow::anyhow!(t!(
+            "save.unreadable_archive",
+            path = output.display(),
+            err = format!("{err:#}")
+        ))
+    })?;
+    if !report.is_ok() {
+        anyhow::bail!(t!(
+            "save.damaged_archive",
+            path = output.display(),
+            report = report
+        ));
+    }
+    Ok(())
+}
 
-    zip.start_file(format!("{}ro-crate-metadata.json", root_prefix), options)
-        .context("Failed to create metadata file")?;
-    let metadata_bytes = serde_json::to_vec_pretty(&metadata)?;
-    zip.write_all(&metadata_bytes)
-        .context("Failed to write metadata file")?;
+/// Content of `ro-crate-metadata.json`, serialized straight from the node lists it is made of.
+///
+/// Entries with thousands of extra fields make for a large graph, so it is neither copied into
+/// one list nor printed into a buffer before it is written.
+pub struct CrateMetadata<'a> {
+    /// JSON-LD context URL.
+    pub context: &'a str,
+    /// Graph nodes in output order.
+    pub graph: &'a [&'a [serde_json::Value]],
+}
+
+impl Serialize for CrateMetadata<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        /// Nodes of all lists as one JSON array.
+        struct Graph<'a>(&'a [&'a [serde_json::Value]]);
+
+        impl Serialize for Graph<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq(self.0.iter().flat_map(|nodes| nodes.iter()))
+            }
+        }
+
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("@context", self.context)?;
+        map.serialize_entry("@graph", &Graph(self.graph))?;
+        map.end()
+    }
+}
 
-    zip.finish().context("Failed to finalize archive")?;
+/// Pretty-print `metadata` into `out` as it is serialized.
+pub fn write_metadata(out: &mut impl Write, metadata: &CrateMetadata<'_>) -> Result<()> {
+    let mut out = std::io::BufWriter::new(out);
+    serde_json::to_writer_pretty(&mut out, metadata)?;
+    out.flush()?;
     Ok(())
 }
 
+/// Outcome of [`verify_archive`]: the files that are missing or differ from their metadata.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerificationReport {
+    /// Number of `File` nodes checked.
+    pub checked: usize,
+    /// Archive-relative paths with what is wrong with each.
+    pub mismatches: Vec<(String, String)>,
+}
+
+impl VerificationReport {
+    /// Whether every file matched its recorded size and SHA-256.
+    pub fn is_ok(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl std::fmt::Display for VerificationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (path, problem) in &self.mismatches {
+            writeln!(f, "  {path}: {problem}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Reopen the archive at `path` and check it against its own metadata.
+///
+/// Reading the archive validates the zip central directory. Every `File` node of
+/// `ro-crate-metadata.json` must name an entry of the archive whose content still has the
+/// recorded `contentSize` and `sha256`; entries that cannot be read, e.g. because they were
+/// truncated, are reported as mismatches too.
+///
+/// # Errors
+///
+/// Returns an error when the archive cannot be opened as a zip file or its metadata is
+/// missing or invalid.
+pub fn verify_archive(path: &Path) -> Result<VerificationReport> {
+    let file = File::open(path).with_context(|| format!("Failed to open archive {:?}", path))?;
+    let mut zip = ZipArchive::new(file)
+        .with_context(|| format!("Archive {:?} is not a readable zip file", path))?;
+    let (root_prefix, metadata) = read_metadata(&mut zip)?;
+    let graph = metadata["@graph"]
+        .as_array()
+        .context("RO-Crate metadata has no @graph")?;
+
+    let mut report = VerificationReport::default();
+    for node in graph.iter().filter(|node| node["@type"] == "File") {
+        let Some(id) = node["@id"].as_str() else {
+            continue;
+        };
+        report.checked += 1;
+        let relative = id.trim_start_matches("./");
+        let problem = match zip.by_name(&format!("{root_prefix}{relative}")) {
+            Err(_) => Some("missing from the archive".to_string()),
+            Ok(mut entry) => match copy_and_hash(&mut entry, &mut std::io::sink()) {
+                Err(err) => Some(format!("could not be read: {err}")),
+                Ok((sha256, size)) => {
+                    let recorded_size = match &node["contentSize"] {
+                        serde_json::Value::String(text) => text.parse::<u64>().ok(),
+                        value => value.as_u64(),
+                    };
+                    match recorded_size {
+                        Some(recorded) if recorded != size => {
+                            Some(format!("{size} bytes, but {recorded} recorded"))
+                        }
+                        _ if node["sha256"].as_str().is_some_and(|hash| hash != sha256) => {
+                            Some("SHA-256 differs from the recorded hash".to_string())
+                        }
+                        _ => None,
+                    }
+                }
+            },
+        };
+        if let Some(problem) = problem {
+            report.mismatches.push((relative.to_string(), problem));
+        }
+    }
+    Ok(report)
+}
+
+/// List every attachment as `<sha512>  <path>` with paths relative to the archive root, the
+/// format `sha512sum --check` reads.
+fn checksums_manifest(file_nodes: &[serde_json::Value]) -> String {
+    file_nodes
+        .iter()
+        .filter_map(|node| {
+            let path = node["@id"].as_str()?.trim_start_matches("./");
+            let digest = node[HashAlgorithm::Sha512.key()].as_str()?;
+            Some(format!("{digest}  {path}\n"))
+        })
+        .collect()
+}
+
+/// Graph nodes produced for one entry.
+struct EntryNodes {
+    /// Dataset node describing the entry.
+    dataset: serde_json::Value,
+    /// File nodes for the entry's attachments.
+    files: Vec<serde_json::Value>,
+    /// Metadata blob followed by per-field PropertyValue nodes.
+    properties: Vec<serde_json::Value>,
+    /// `CreativeWork` nodes of the entry's field groups.
+    groups: Vec<serde_json::Value>,
+    /// `CreativeWork` nodes of the entry's related links.
+    links: Vec<serde_json::Value>,
+    /// Archive paths, relative to the entry folder, of the attachments left out.
+    omitted: Vec<String>,
+    /// `Comment` node listing the attachments left out, if any.
+    omission: Option<serde_json::Value>,
+}
+
+/// Write one entry's attachments below `<root>/<dir_name>/` and build its graph nodes.
+#[allow(clippy::too_many_arguments)] // Shares the open writer and archive-wide settings with the caller.
+fn write_entry(
+    writer: &mut ArchiveWriter<'_>,
+    options: FileOptions<'_, ()>,
+    root_prefix: &str,
+    dir_name: &str,
+    entry: &ArchiveEntry,
+    body_format: BodyFormat,
+    export: &ExportOptions,
+    author_refs: &[serde_json::Value],
+    progress: &mut CopyProgress<'_>,
+    on_missing: &mut dyn FnMut(&Path) -> MissingFileAction,
+) -> Result<EntryNodes> {
+    let experiment_dir = format!("{}{}/", root_prefix, dir_name);
+    let dataset_id = format!("./{}/", dir_name);
+    writer
+        .add_directory(&experiment_dir, options)
+        .context("Failed to create experiment directory in archive")?;
+
+    let mut file_nodes = Vec::new();
+    let mut omitted = Vec::new();
+    for meta in entry.attachments.iter() {
+        // Folder attachments keep their relative directory below the experiment folder.
+        let relative_path = meta.archive_path();
+        let archive_path = format!("{}{}", experiment_dir, relative_path);
+        let id = format!("{}{}", dataset_id, relative_path);
+
+        if let Some(digests) = writer.resumed.get(&archive_path) {
+            // Written and verified by the interrupted save this one continues.
+            progress.state.bytes_done += meta.size;
+            file_nodes.push(attachment_node(meta, &id, digests.clone())?);
+            continue;
+        }
+
+        let opened = loop {
+            match File::open(&meta.path) {
+                Ok(file) => break Some(file),
+                Err(err) => match on_missing(&meta.path) {
+                    MissingFileAction::Retry => {}
+                    MissingFileAction::Skip => break None,
+                    MissingFileAction::Abort => {
+                        return Err(AttachmentIntegrityError {
+                            path: meta.path.clone(),
+                            unreadable: true,
+                            message: format!("Failed to read attachment {:?}: {err}", meta.path),
+                        }
+                        .into());
+                    }
+                },
+            }
+        };
+        let Some(mut reader) = opened else {
+            progress.state.bytes_done += meta.size;
+            omitted.push(relative_path);
+            continue;
+        };
+
+        let zip = writer.zip();
+        zip.start_file(&archive_path, export.compression.file_options(&meta.mime))
+            .with_context(|| format!("Failed to add file {} to archive", archive_path))?;
+
+        // Hash while copying so every attachment is read only once. SHA-256 is always
+        // recorded; SHA-512 also feeds the checksum manifest.
+        let mut algorithms = vec![HashAlgorithm::Sha256];
+        algorithms.extend(meta.digests.keys().copied());
+        if export.checksums_manifest {
+            algorithms.push(HashAlgorithm::Sha512);
+        }
+        progress.state.current_file = relative_path.clone();
+        let start = progress.state.bytes_done;
+        let copied = copy_and_hash_with_progress(&mut reader, zip, &algorithms, |written| {
+            progress.state.bytes_done = start + written;
+            (progress.on_progress)(&progress.state)
+        })
+        .with_context(|| format!("Failed to copy {:?} into archive", meta.path))?;
+        let Some((digests, _written)) = copied else {
+            zip.abort_file()
+                .with_context(|| format!("Failed to roll back {} in archive", archive_path))?;
+            return Err(WriteCancelled.into());
+        };
+
+        // Verify integrity with every algorithm recorded when the attachment was added.
+        let mismatch = HashAlgorithm::ALL.into_iter().find_map(|algorithm| {
+            let expected = meta.digest(algorithm)?;
+            let found = digests.get(&algorithm)?;
+            (found != expected).then_some((algorithm, expected, found))
+        });
+        if let Some((algorithm, expected, found)) = mismatch {
+            zip.abort_file()
+                .with_context(|| format!("Failed to roll back {} in archive", archive_path))?;
+            return Err(AttachmentIntegrityError {
+                path: meta.path.clone(),
+                unreadable: false,
+                message: t!(
+                    "save.attachment_modified",
+                    path = meta.path.display(),
+                    expected = expected,
+                    found = found,
+                    key = algorithm.key(),
+                ),
+            }
+            .into());
+        }
+
+        writer.attachment_written(archive_path, &digests, meta.size)?;
+        file_nodes.push(attachment_node(meta, &id, digests)?);
+    }
+
+    let timestamp = entry
+        .performed_at
+        .format(&Rfc3339)
+        .map_err(|err| anyhow::anyhow!("Failed to format performed_at timestamp: {}", err))?;
+    let (body_text, encoding_format) = match body_format {
+        BodyFormat::Html | BodyFormat::Both => (
+            markdown_to_html(&entry.body, false, &export.html_policy, Some(dir_name)),
+            "text/html",
+        ),
+        BodyFormat::Markdown => (entry.body.clone(), "text/markdown"),
+    };
+
+    let ExtraFieldsExport {
+        property_values,
+        metadata_property,
+        variable_measured_ids,
+        group_nodes,
+    } = build_extra_fields_export(
+        &entry.extra_fields,
+        &entry.extra_groups,
+        entry.hide_main_text,
+        &export.units,
+    )?;
+
+    let mut properties = vec![metadata_property];
+    properties.extend(property_values);
+    let variable_measured = match export.crate_version {
+        RoCrateVersion::V1_2 => variable_measured_ids
+            .iter()
+            .map(|id| serde_json::json!({"@id": id}))
+            .collect::<Vec<_>>(),
+        // Format 101 has no separate property nodes; their ids would dangle.
+        RoCrateVersion::V1_1 => std::mem::take(&mut properties)
+            .into_iter()
+            .map(|mut property| {
+                if let Some(node) = property.as_object_mut() {
+                    node.remove("@id");
+                }
+                property
+            })
+            .collect(),
+    };
+
+    let mut experiment_node = serde_json::json!({
+        "@id": dataset_id,
+        "@type": "Dataset",
+        "name": entry.title,
+        "encodingFormat": encoding_format,
+        "text": body_text,
+        "dateCreated": timestamp,
+        "dateModified": timestamp,
+        "genre": entry.genre.as_str(),
+        "keywords": entry.keywords,
+        "variableMeasured": variable_measured,
+        "hasPart": file_nodes
+            .iter()
+            .map(|node| serde_json::json!({"@id": node["@id"] }))
+            .collect::<Vec<_>>(),
+    });
+
+    if !author_refs.is_empty() {
+        experiment_node["author"] = serde_json::Value::Array(author_refs.to_vec());
+    }
+    let omission = (!omitted.is_empty()).then(|| {
+        let id = format!("#omitted-attachments-{dir_name}");
+        experiment_node["comment"] = serde_json::json!([{ "@id": id }]);
+        serde_json::json!({
+            "@id": id,
+            "@type": "Comment",
+            "text": t!("save.omitted_comment", files = omitted.join(", ")),
+            "dateCreated": OffsetDateTime::now_utc().format(&Rfc3339).unwrap_or_default(),
+        })
+    });
+    for link in &entry.related_links {
+        let property = link.relation.property();
+        if experiment_node.get(property).is_none() {
+            experiment_node[property] = serde_json::Value::Array(Vec::new());
+        }
+        if let Some(refs) = experiment_node[property].as_array_mut() {
+            refs.push(serde_json::json!({ "@id": link.url }));
+        }
+    }
+
+    Ok(EntryNodes {
+        dataset: experiment_node,
+        files: file_nodes,
+        properties,
+        groups: group_nodes,
+        links: entry.related_links.iter().map(link_node).collect(),
+        omitted,
+        omission,
+    })
+}
+
+/// Write the markdown `body` as `body.md` into the entry folder `dir_name` and return its
+/// `File` node.
+fn write_body_markdown(
+    zip: &mut ZipWriter<File>,
+    options: FileOptions<'_, ()>,
+    root_prefix: &str,
+    dir_name: &str,
+    body: &str,
+) -> Result<serde_json::Value> {
+    zip.start_file(
+        format!("{root_prefix}{dir_name}/{BODY_MARKDOWN_FILE}"),
+        options,
+    )
+    .context("Failed to create markdown body file")?;
+    let (sha256, size) =
+        copy_and_hash(&mut body.as_bytes(), zip).context("Failed to write markdown body file")?;
+    Ok(serde_json::json!({
+        "@id": format!("./{dir_name}/{BODY_MARKDOWN_FILE}"),
+        "@type": "File",
+        "name": BODY_MARKDOWN_FILE,
+        "description": "Markdown source of the main text",
+        "encodingFormat": "text/markdown",
+        "contentSize": size.to_string(),
+        "sha256": sha256,
+    }))
+}
+
+/// Content of the imported metadata file `source`, as long as it is unchanged since the import.
+///
+/// # Errors
+///
+/// Returns an error when the file can no longer be read or its SHA-256 changed.
+fn read_imported_metadata(source: &ExtraFieldsSource) -> Result<Vec<u8>> {
+    let content = fs::read(&source.path)
+        .with_context(|| t!("save.metadata_unreadable", path = source.path.display()))?;
+    let (sha256, _) = copy_and_hash(&mut content.as_slice(), &mut std::io::sink())?;
+    if sha256 != source.sha256 {
+        anyhow::bail!(t!("save.metadata_changed", path = source.path.display()));
+    }
+    Ok(content)
+}
+
+/// Write `content` of the imported metadata file `source` unchanged as
+/// `imported_metadata.json` into the entry folder `dir_name` and return its `File` node.
+fn write_imported_metadata(
+    zip: &mut ZipWriter<File>,
+    options: FileOptions<'_, ()>,
+    root_prefix: &str,
+    dir_name: &str,
+    source: &ExtraFieldsSource,
+    content: &[u8],
+) -> Result<serde_json::Value> {
+    zip.start_file(
+        format!("{root_prefix}{dir_name}/{IMPORTED_METADATA_FILE}"),
+        options,
+    )
+    .context("Failed to create imported metadata file")?;
+    let (sha256, size) =
+        copy_and_hash(&mut &content[..], zip).context("Failed to write imported metadata file")?;
+    Ok(serde_json::json!({
+        "@id": format!("./{dir_name}/{IMPORTED_METADATA_FILE}"),
+        "@type": "File",
+        "name": IMPORTED_METADATA_FILE,
+        "alternateName": source.file_name(),
+        "description": "Unchanged copy of the eLabFTW metadata file the extra fields were imported from; the elabftw_metadata property holds the metadata reconstructed from the fields as saved",
+        "encodingFormat": "application/json",
+        "contentSize": size.to_string(),
+        "sha256": sha256,
+    }))
+}
+
+/// `CreativeWork` node of a related link, identified by its URL.
+fn link_node(link: &RelatedLink) -> serde_json::Value {
+    let name = if link.label.is_empty() {
+        &link.url
+    } else {
+        &link.label
+    };
+    serde_json::json!({
+        "@id": link.url,
+        "@type": "CreativeWork",
+        "name": name,
+        "url": link.url,
+    })
+}
+
+/// `File` node of the attachment `meta` written as `id` with the given `digests`.
+fn attachment_node(meta: &Attachment, id: &str, digests: Digests) -> Result<serde_json::Value> {
+    let mut file_node = serde_json::json!({
+        "@id": id,
+        "@type": "File",
+        "name": meta.sanitized_name,
+        "encodingFormat": meta.mime,
+        "contentSize": meta.size.to_string(),
+    });
+    for (algorithm, digest) in digests {
+        file_node[algorithm.key()] = serde_json::Value::String(digest);
+    }
+    if let Some(acquired_at) = meta.acquired_at {
+        let acquired_at = acquired_at.format(&Rfc3339).map_err(|err| {
+            anyhow::anyhow!("Failed to format acquisition time of {}: {}", id, err)
+        })?;
+        file_node["dateCreated"] = serde_json::Value::String(acquired_at);
+    }
+    if let Some(description) = &meta.description {
+        file_node["description"] = serde_json::Value::String(description.clone());
+    }
+    Ok(file_node)
+}
+
+/// People and organizations of the graph, each emitted once however often it is referenced.
+///
+/// Nodes are kept in the order their `@id` was first added.
+#[derive(Debug, Default)]
+pub struct NodeRegistry {
+    nodes: Vec<serde_json::Value>,
+    /// Position of each `@id` in `nodes`.
+    index: HashMap<String, usize>,
+}
+
+impl NodeRegistry {
+    /// Add `node` and return its `@id`. A node with an `@id` added before is merged into the
+    /// earlier one, which gains the properties only the new node has.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `node` has no string `@id`, or when both nodes have a property
+    /// with different values.
+    pub fn add(&mut self, node: serde_json::Value) -> Result<String> {
+        let serde_json::Value::Object(properties) = node else {
+            anyhow::bail!("Graph node is not a JSON object: {node}");
+        };
+        let id = properties
+            .get("@id")
+            .and_then(serde_json::Value::as_str)
+            .context("Graph node has no @id")?
+            .to_string();
+        let Some(&position) = self.index.get(&id) else {
+            self.index.insert(id.clone(), self.nodes.len());
+            self.nodes.push(serde_json::Value::Object(properties));
+            return Ok(id);
+        };
+        let existing = self.nodes[position]
+            .as_object_mut()
+            .expect("registered nodes are objects");
+        for (key, value) in properties {
+            match existing.get(&key) {
+                None => {
+                    existing.insert(key, value);
+                }
+                Some(current) if *current == value => {}
+                Some(current) => anyhow::bail!(
+                    "Conflicting metadata for {id}: \"{key}\" is both {current} and {value}"
+                ),
+            }
+        }
+        Ok(id)
+    }
+
+    /// Registered nodes, one per `@id`.
+    pub fn nodes(&self) -> &[serde_json::Value] {
+        &self.nodes
+    }
+}
+
+/// `CreativeWork` node describing `license`; `None` when no license is declared.
+fn license_node(license: &License) -> Option<serde_json::Value> {
+    let mut node = serde_json::json!({
+        "@id": license.url()?,
+        "@type": "CreativeWork",
+        "name": license.name()?,
+    });
+    if let Some(id) = license.spdx_id() {
+        node["identifier"] = serde_json::Value::String(id.to_string());
+    }
+    Some(node)
+}
+
+/// Build one `Person` node per author.
+///
+/// Authors with an ORCID iD are identified by its resolver URL; the others get a local
+/// `#author` fragment (`#author-2`, … when there are several).
+fn person_nodes(authors: &[Author]) -> Vec<serde_json::Value> {
+    authors
+        .iter()
+        .enumerate()
+        .map(|(index, author)| {
+            let id = if !author.orcid.is_empty() {
+                format!("{ORCID_URL_PREFIX}{}", author.orcid)
+            } else if authors.len() == 1 {
+                "#author".to_string()
+            } else {
+                format!("#author-{}", index + 1)
+            };
+            let mut node = serde_json::json!({
+                "@id": id,
+                "@type": "Person",
+                "name": author.display_name(),
+            });
+            for (key, value) in [
+                ("givenName", &author.given_name),
+                ("familyName", &author.family_name),
+                ("email", &author.email),
+                ("affiliation", &author.affiliation),
+            ] {
+                if !value.is_empty() {
+                    node[key] = serde_json::Value::String(value.clone());
+                }
+            }
+            node
+        })
+        .collect()
+}
+
+/// Build the single-line zip comment `ELNPack: <title> — <date> — keywords: a, b`.
+///
+/// Control characters (including newlines) become spaces and the result is truncated to
+/// [`ZIP_COMMENT_MAX_CHARS`] so listings such as `unzip -l` stay readable.
+fn archive_comment(title: &str, performed_at: OffsetDateTime, keywords: &[String]) -> String {
+    let date = performed_at
+        .to_offset(time::UtcOffset::UTC)
+        .date()
+        .to_string();
+    let mut comment = format!("ELNPack: {} — {date}", title.trim());
+    if !keywords.is_empty() {
+        comment.push_str(&format!(" — keywords: {}", keywords.join(", ")));
+    }
+
+    let single_line: String = comment
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    if single_line.chars().count() <= ZIP_COMMENT_MAX_CHARS {
+        return single_line;
+    }
+    let mut truncated: String = single_line
+        .chars()
+        .take(ZIP_COMMENT_MAX_CHARS - 1)
+        .collect();
+    truncated.push('…');
+    truncated
+}
+
 /// Builds semantic PropertyValue nodes and a reconstructed eLabFTW metadata blob for extra fields.
 ///
 /// The function converts each `ExtraField` into a `PropertyValue` JSON node and produces an
@@ -306,31 +1911,90 @@ pub fn build_and_write_archive(
 /// The returned `variable_measured_ids` lists the `@id` values for the metadata blob followed by
 /// each field's PropertyValue `@id`, suitable for inclusion in an experiment's `variableMeasured`.
 ///
+/// Each `ExtraFieldGroup` becomes a `CreativeWork` node with its `name` and `position`, and every
+/// field names its group with `isPartOf`, so consumers can rebuild the grouping without the
+/// metadata blob. Fields without a known group belong to the `Default` group, which is added
+/// when the entry has none. Fields are ordered by group position, then by field position.
+///
 /// # Returns
 ///
 /// An `ExtraFieldsExport` containing:
 /// - `property_values`: an array of `PropertyValue` JSON objects, one per extra field;
 /// - `metadata_property`: a `PropertyValue` JSON object whose `value` is the eLabFTW metadata JSON string;
-/// - `variable_measured_ids`: an array of `@id` strings (metadata `@id` first, then field `@id`s).
+/// - `variable_measured_ids`: an array of `@id` strings (metadata `@id` first, then field `@id`s);
+/// - `group_nodes`: an array of `CreativeWork` JSON objects, one per group.
 ///
 /// # Examples
 ///
 /// ```rust,ignore
-/// let export = build_extra_fields_export(&[], &[]).unwrap();
+/// let export = build_extra_fields_export(&[], &[], false, &UnitSettings::default()).unwrap();
 /// assert!(export.property_values.is_empty());
 /// assert!(export.variable_measured_ids.len() >= 1); // metadata property id is always present
 /// ```
 fn build_extra_fields_export(
     extra_fields: &[ExtraField],
     extra_groups: &[ExtraFieldGroup],
+    hide_main_text: bool,
+    units: &UnitSettings,
 ) -> Result<ExtraFieldsExport> {
-    let metadata_json = reconstruct_elabftw_metadata(extra_fields, extra_groups)?;
+    let metadata_json = reconstruct_elabftw_metadata(extra_fields, extra_groups, hide_main_text)?;
+
+    let mut groups: Vec<ExtraFieldGroup> = extra_groups.to_vec();
+    let known_group = |field: &ExtraField| {
+        field
+            .group_id
+            .filter(|id| extra_groups.iter().any(|group| group.id == *id))
+    };
+    let default_id = match groups.iter().find(|group| group.name == "Default") {
+        Some(group) => group.id,
+        None => {
+            let id = groups.iter().map(|group| group.id).max().unwrap_or(0) + 1;
+            if extra_fields
+                .iter()
+                .any(|field| known_group(field).is_none())
+            {
+                groups.push(ExtraFieldGroup {
+                    id,
+                    name: "Default".into(),
+                    position: groups.len() as i32,
+                });
+            }
+            id
+        }
+    };
+    groups.sort_by_key(|group| (group.position, group.id));
+    let group_node_ids: HashMap<i32, String> = groups
+        .iter()
+        .map(|group| (group.id, format!("#field-group-{}", Uuid::new_v4())))
+        .collect();
+    let group_nodes = groups
+        .iter()
+        .map(|group| {
+            serde_json::json!({
+                "@id": group_node_ids[&group.id],
+                "@type": "CreativeWork",
+                "name": group.name,
+                "position": group.position,
+            })
+        })
+        .collect();
+
+    let group_of = |field: &ExtraField| known_group(field).unwrap_or(default_id);
+    let group_rank = |field: &ExtraField| {
+        groups
+            .iter()
+            .position(|group| group.id == group_of(field))
+            .unwrap_or(groups.len())
+    };
+    let mut ordered: Vec<&ExtraField> = extra_fields.iter().collect();
+    // Stable, so fields without a position keep their order in the form.
+    ordered.sort_by_key(|field| (group_rank(field), field.position.is_none(), field.position));
 
     let mut property_values = Vec::with_capacity(extra_fields.len() + 1);
     let mut variable_measured_ids = Vec::with_capacity(extra_fields.len() + 1);
 
     // Emit per-field PropertyValue nodes following eLabFTW style.
-    for field in extra_fields {
+    for field in ordered {
         let id = format!("pv://{}", Uuid::new_v4());
         variable_measured_ids.push(id.clone());
 
@@ -352,6 +2016,9 @@ fn build_extra_fields_export(
 
         if let Some(unit) = &field.unit {
             node.insert("unitText".into(), serde_json::Value::String(unit.clone()));
+            if let Some(code) = units.ucum_code(unit) {
+                node.insert("unitCode".into(), serde_json::Value::String(code.into()));
+            }
         }
         if let Some(desc) = &field.description {
             node.insert(
@@ -359,6 +2026,10 @@ fn build_extra_fields_export(
                 serde_json::Value::String(desc.clone()),
             );
         }
+        node.insert(
+            "isPartOf".into(),
+            serde_json::json!({ "@id": group_node_ids[&group_of(field)] }),
+        );
         // Keep node minimal to mirror eLabFTW exports.
         property_values.push(serde_json::Value::Object(node));
     }
@@ -378,29 +2049,39 @@ fn build_extra_fields_export(
         property_values,
         metadata_property,
         variable_measured_ids,
+        group_nodes,
     })
 }
 
 /// Builds a JSON blob compatible with eLabFTW that describes extra fields and groups.
 ///
 /// The returned string contains two top-level keys:
-/// - `"elabftw"`: metadata including `display_main_text` and `extra_fields_groups`.
-/// - `"extra_fields"`: a map from field label to the field definition and value.
+/// - `"elabftw"`: metadata including `display_main_text` (false when `hide_main_text` is set)
+///   and `extra_fields_groups`.
+/// - `"extra_fields"`: a map from field label to the field definition and value, ordered by
+///   position (unpositioned fields last), then label, so reordering fields in the form does
+///   not change the archive.
 ///
 /// # Returns
 ///
 /// A JSON string containing the eLabFTW-compatible metadata.
 ///
+/// # Errors
+///
+/// Returns an error naming the labels when two fields share a label, ignoring letter case;
+/// eLabFTW keys fields by label and would keep only one of them.
+///
 /// # Examples
 ///
 /// ```rust,ignore
-/// let json = reconstruct_elabftw_metadata(&[], &[]).unwrap();
+/// let json = reconstruct_elabftw_metadata(&[], &[], false).unwrap();
 /// assert!(json.contains(r#""elabftw""#));
 /// assert!(json.contains(r#""extra_fields""#));
 /// ```
 fn reconstruct_elabftw_metadata(
     extra_fields: &[ExtraField],
     extra_groups: &[ExtraFieldGroup],
+    hide_main_text: bool,
 ) -> Result<String> {
     let groups_json: Vec<serde_json::Value> = extra_groups
         .iter()
@@ -412,8 +2093,8 @@ fn reconstruct_elabftw_metadata(
         })
         .collect();
 
-    let mut fields = serde_json::Map::new();
-    for field in extra_fields {
+    let mut fields = Vec::with_capacity(extra_fields.len());
+    for field in fields_in_export_order(extra_fields)? {
         let mut obj = serde_json::Map::new();
         obj.insert(
             "type".into(),
@@ -463,6 +2144,14 @@ fn reconstruct_elabftw_metadata(
         } else {
             obj.insert("value".into(), value_to_json(field));
         }
+        if let Some(default) = &field.default_value {
+            let default = ExtraField {
+                value: default.clone(),
+                value_multi: field.default_multi.clone(),
+                ..field.clone()
+            };
+            obj.insert("default_value".into(), value_to_json(&default));
+        }
 
         if let Some(position) = field.position {
             obj.insert(
@@ -498,21 +2187,66 @@ fn reconstruct_elabftw_metadata(
             obj.insert("readonly".into(), serde_json::Value::Bool(true));
         }
 
-        fields.insert(field.label.clone(), serde_json::Value::Object(obj));
+        fields.push((field.label.as_str(), serde_json::Value::Object(obj)));
     }
 
-    let root = serde_json::json!({
-        "elabftw": {
-            "display_main_text": true,
+    let root = ElabftwMetadata {
+        elabftw: serde_json::json!({
+            "display_main_text": !hide_main_text,
             "extra_fields_groups": groups_json,
-        },
-        "extra_fields": serde_json::Value::Object(fields),
-    });
+        }),
+        extra_fields: OrderedFields(fields),
+    };
 
     let json = serde_json::to_string(&root)?;
     Ok(json)
 }
 
+/// Root of the `elabftw_metadata` blob.
+#[derive(Serialize)]
+struct ElabftwMetadata<'a> {
+    elabftw: serde_json::Value,
+    extra_fields: OrderedFields<'a>,
+}
+
+/// Extra fields keyed by label, serialized in the given order instead of sorted by key.
+struct OrderedFields<'a>(Vec<(&'a str, serde_json::Value)>);
+
+impl Serialize for OrderedFields<'_> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(label, field)| (label, field)))
+    }
+}
+
+/// Extra fields sorted by position, unpositioned ones last, then by label.
+///
+/// # Errors
+///
+/// Returns an error when two labels are equal ignoring letter case.
+fn fields_in_export_order(extra_fields: &[ExtraField]) -> Result<Vec<&ExtraField>> {
+    let mut seen = std::collections::HashMap::new();
+    for field in extra_fields {
+        if let Some(other) = seen.insert(field.label.to_lowercase(), field.label.as_str()) {
+            if other == field.label {
+                anyhow::bail!(t!("validate.field_twice", label = other));
+            }
+            anyhow::bail!(t!(
+                "validate.field_case",
+                first = other,
+                second = field.label
+            ));
+        }
+    }
+
+    let mut ordered: Vec<&ExtraField> = extra_fields.iter().collect();
+    // Labels are unique ignoring case by now, so this order is total.
+    ordered.sort_by_cached_key(|f| (f.position.is_none(), f.position, f.label.to_lowercase()));
+    Ok(ordered)
+}
+
 /// Convert an `ExtraField` value into a serde_json::Value suitable for export.
 ///
 /// The result is:
@@ -540,6 +2274,8 @@ fn reconstruct_elabftw_metadata(
 ///     blank_value_on_duplicate: false,
 ///     group_id: None,
 ///     readonly: false,
+///     default_value: None,
+///     default_multi: Vec::new(),
 /// };
 /// let v = crate::logic::eln::value_to_json(&f_multi);
 /// assert_eq!(v, Value::Array(vec![Value::String("a".into()), Value::String("b".into())]));
@@ -559,6 +2295,8 @@ fn reconstruct_elabftw_metadata(
 ///     blank_value_on_duplicate: false,
 ///     group_id: None,
 ///     readonly: false,
+///     default_value: None,
+///     default_multi: Vec::new(),
 /// };
 /// let v2 = crate::logic::eln::value_to_json(&f_num);
 /// assert_eq!(v2, Value::String("3.14".into()));
@@ -593,13 +2331,55 @@ fn value_to_json(field: &ExtraField) -> serde_json::Value {
     }
 }
 
+/// Attachment path a `./<path>` link or image in the body points to, if any.
+///
+/// The image picker inserts such links relative to the entry folder; `../` escapes are
+/// not attachment references.
+pub(crate) fn attachment_reference(dest: &str) -> Option<&str> {
+    dest.strip_prefix("./")
+        .filter(|path| !path.is_empty() && !path.split('/').any(|part| part == ".."))
+}
+
+/// Attachment paths the Markdown `body` links to or embeds as `./<path>` that are not among
+/// `attachments`, in order of appearance and without duplicates.
+///
+/// Used to warn when an attachment referenced in the body was renamed or removed.
+pub fn missing_attachment_references(body: &str, attachments: &[Attachment]) -> Vec<String> {
+    let mut missing: Vec<String> = Vec::new();
+    for event in Parser::new_ext(body, Options::ENABLE_TABLES) {
+        let dest = match &event {
+            Event::Start(Tag::Image { dest_url, .. } | Tag::Link { dest_url, .. }) => dest_url,
+            _ => continue,
+        };
+        let Some(path) = attachment_reference(dest) else {
+            continue;
+        };
+        if !attachments.iter().any(|att| att.archive_path() == path)
+            && !missing.iter().any(|known| known == path)
+        {
+            missing.push(path.to_string());
+        }
+    }
+    missing
+}
+
 /// Render markdown to sanitized HTML for embedding in RO-Crate metadata.
 ///
 /// When `parse_math` is true, this enables pulldown-cmark math extensions and
 /// preserves KaTeX/MathJax-style span classes so that inline and display math
 /// can still be styled by consumers while the HTML is sanitized by Ammonia.
-fn markdown_to_html(body: &str, parse_math: bool) -> String {
+/// `::: kind` container blocks become callout `<div>`s, and `policy` widens the
+/// sanitizer allow-list with user-configured tags and classes. With an `entry_dir`,
+/// `./<path>` links and images are rewritten to `./<entry_dir>/<path>`, the crate path of
+/// the attachment, so they resolve from the archive root.
+fn markdown_to_html(
+    body: &str,
+    parse_math: bool,
+    policy: &HtmlPolicy,
+    entry_dir: Option<&str>,
+) -> String {
     let mut builder = ammonia::Builder::default();
+    policy.configure(&mut builder);
     let mut options = Options::empty();
     options.insert(Options::ENABLE_FOOTNOTES);
     options.insert(Options::ENABLE_STRIKETHROUGH);
@@ -610,7 +2390,38 @@ fn markdown_to_html(body: &str, parse_math: bool) -> String {
         // for inline and display math styling (e.g. KaTeX/MathJax renderers).
         builder.add_allowed_classes("span", &["math", "math-inline", "math-display"]);
     }
-    let parser = Parser::new_ext(body, options);
+    let expanded = expand_callouts(body);
+    fn rebase<'a>(dest: CowStr<'a>, entry_dir: Option<&str>) -> CowStr<'a> {
+        match (entry_dir, attachment_reference(&dest)) {
+            (Some(dir), Some(path)) => format!("./{dir}/{path}").into(),
+            _ => dest,
+        }
+    }
+    let parser = Parser::new_ext(&expanded, options).map(|event| match event {
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: rebase(dest_url, entry_dir),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: rebase(dest_url, entry_dir),
+            title,
+            id,
+        }),
+        other => other,
+    });
     let mut html_output = String::new();
     html::push_html(&mut html_output, parser);
     builder.clean(&html_output).to_string()
@@ -621,18 +2432,109 @@ mod tests {
     use std::path::PathBuf;
     use std::{fs::File, io::Read};
 
+    use super::ArchiveEntry;
     use super::ArchiveGenre;
     use super::BodyFormat;
+    use super::ELABFTW_TITLE_LIMIT;
+    use super::ExportOptions;
+    use super::NodeRegistry;
+    use super::PublisherInfo;
+    use super::RoCrateVersion;
+    use super::SUMMARY_MAX_CHARS;
+    use super::archive_comment;
     use super::build_and_write_archive;
+    use super::build_and_write_archive_with_progress;
+    use super::build_extra_fields_export;
     use super::ensure_extension;
     use super::markdown_to_html;
+    use super::reconstruct_elabftw_metadata;
     use super::suggested_archive_name;
+    use super::title_length_warning;
+    use super::{Compression, CompressionOptions, is_precompressed};
+    use super::{
+        MissingFileAction, Resumable, ResumeOutcome, WriteCancelled, WriteProgress,
+        build_and_write_archive_resumable, verify_archive,
+    };
+    use crate::logic::html_export::{HtmlExportSettings, HtmlPolicy};
+    use crate::logic::units::UnitSettings;
     use crate::models::attachment::Attachment;
+    use crate::models::author::Author;
     use crate::models::extra_fields::{ExtraField, ExtraFieldGroup, ExtraFieldKind};
-    use crate::utils::sanitize_component;
+    use crate::models::license::License;
+    use crate::utils::hash::{Digests, HashAlgorithm, copy_and_digest};
+    use crate::utils::{MAX_COMPONENT_LEN, sanitize_component};
     use serde_json::Value;
     use time::OffsetDateTime;
-    use zip::ZipArchive;
+    use zip::{CompressionMethod, ZipArchive};
+
+    /// Field with `label`, `kind` and `value` and every other property at its default.
+    fn field(label: &str, kind: ExtraFieldKind, value: &str) -> ExtraField {
+        ExtraField {
+            label: label.into(),
+            kind,
+            value: value.into(),
+            ..ExtraField::default()
+        }
+    }
+
+    /// Assert that no `@id` occurs twice in `graph`.
+    fn assert_ids_unique(graph: &[Value]) {
+        let mut seen = std::collections::HashSet::new();
+        for node in graph {
+            let id = node["@id"].as_str().expect("every node has an @id");
+            assert!(seen.insert(id), "{id} occurs more than once in @graph");
+        }
+    }
+
+    #[test]
+    fn node_registry_emits_each_id_once_and_merges_properties() {
+        let mut registry = NodeRegistry::default();
+        let org = serde_json::json!({ "@id": "https://ror.org/05gq02987", "@type": "Organization", "name": "Brown University" });
+        assert_eq!(
+            registry.add(org.clone()).unwrap(),
+            "https://ror.org/05gq02987"
+        );
+        registry
+            .add(serde_json::json!({ "@id": "#author", "@type": "Person", "name": "Curie" }))
+            .unwrap();
+        registry.add(org).unwrap();
+        registry
+            .add(serde_json::json!({ "@id": "https://ror.org/05gq02987", "url": "https://www.brown.edu" }))
+            .unwrap();
+
+        let nodes = registry.nodes();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0]["name"], "Brown University");
+        assert_eq!(
+            nodes[0]["url"], "https://www.brown.edu",
+            "merged into the first node"
+        );
+        assert_eq!(nodes[1]["@id"], "#author");
+    }
+
+    #[test]
+    fn node_registry_rejects_conflicting_values_and_missing_ids() {
+        let mut registry = NodeRegistry::default();
+        registry
+            .add(serde_json::json!({ "@id": "#author", "email": "a@example.org" }))
+            .unwrap();
+        let err = registry
+            .add(serde_json::json!({ "@id": "#author", "email": "b@example.org" }))
+            .unwrap_err();
+        assert!(err.to_string().contains("\"email\""), "{err}");
+        assert_eq!(
+            registry.nodes()[0]["email"],
+            "a@example.org",
+            "the first value is kept"
+        );
+
+        assert!(
+            registry
+                .add(serde_json::json!({ "name": "Anonymous" }))
+                .is_err()
+        );
+        assert!(registry.add(serde_json::json!("#author")).is_err());
+    }
 
     #[test]
     fn suggested_archive_name_reuses_sanitizer_and_lowercases() {
@@ -640,6 +2542,18 @@ mod tests {
         assert_eq!(result, "angstrom_study_v1.eln");
     }
 
+    #[test]
+    fn long_titles_get_clamped_names_and_a_warning() {
+        let title = "Very long title ".repeat(32);
+        let name = suggested_archive_name(&title);
+        assert_eq!(name.len(), MAX_COMPONENT_LEN + ".eln".len());
+        assert!(name.starts_with("very_long_title_very"));
+
+        assert_eq!(title_length_warning(&"x".repeat(ELABFTW_TITLE_LIMIT)), None);
+        let warning = title_length_warning(&"é".repeat(ELABFTW_TITLE_LIMIT + 1)).unwrap();
+        assert!(warning.contains("256 characters"), "{warning}");
+    }
+
     // Should leave an existing matching extension untouched, ignoring case.
     #[test]
     fn ensure_extension_preserves_matching_extension_case_insensitive() {
@@ -661,7 +2575,12 @@ mod tests {
     // Markdown HTML rendering should sanitize scripts while retaining formatting like strikethrough.
     #[test]
     fn markdown_to_html_sanitizes_and_keeps_formatting() {
-        let html = markdown_to_html("Hello <script>alert('x')</script> ~~gone~~", false);
+        let html = markdown_to_html(
+            "Hello <script>alert('x')</script> ~~gone~~",
+            false,
+            &HtmlPolicy::default(),
+            None,
+        );
 
         assert!(html.contains("<del>gone</del>"));
         assert!(!html.contains("script"));
@@ -669,7 +2588,12 @@ mod tests {
 
     #[test]
     fn markdown_to_html_keeps_math_styles_if_math_parsing_enabled() {
-        let html = markdown_to_html("Hello $\\frac{1}{2}$ world $$\\frac{1}{2}$$", true);
+        let html = markdown_to_html(
+            "Hello $\\frac{1}{2}$ world $$\\frac{1}{2}$$",
+            true,
+            &HtmlPolicy::default(),
+            None,
+        );
 
         assert!(html.contains("<span class=\"math math-inline\">"));
         assert!(html.contains("<span class=\"math math-display\">"));
@@ -677,7 +2601,12 @@ mod tests {
 
     #[test]
     fn markdown_to_html_leaves_math_raw_when_parsing_disabled() {
-        let html = markdown_to_html("E = mc$^2$ and $$F=ma$$", false);
+        let html = markdown_to_html(
+            "E = mc$^2$ and $$F=ma$$",
+            false,
+            &HtmlPolicy::default(),
+            None,
+        );
 
         assert!(
             html.contains("E = mc$^2$"),
@@ -693,6 +2622,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn markdown_to_html_renders_callouts_with_configured_classes() {
+        let policy = HtmlPolicy::from_settings(&HtmlExportSettings {
+            extra_tags: Vec::new(),
+            extra_classes: vec!["boxed".into()],
+        })
+        .unwrap();
+
+        let html = markdown_to_html(
+            "::: note\nRemember **this**.\n:::\n\n<p class=\"boxed\">x</p><iframe></iframe>",
+            false,
+            &policy,
+            None,
+        );
+
+        assert!(html.contains("<div class=\"callout note\">"));
+        assert!(html.contains("<strong>this</strong>"));
+        assert!(html.contains("<p class=\"boxed\">x</p>"));
+        assert!(!html.contains("iframe"));
+    }
+
+    #[test]
+    fn markdown_to_html_rebases_attachment_links_into_the_entry_folder() {
+        let body = "![Gel](./raw/gel.png) [data](./plate.csv) [up](./../x.txt) \
+                    [site](https://example.org/a.png)";
+
+        let html = markdown_to_html(body, false, &HtmlPolicy::default(), Some("experiment-2"));
+
+        assert!(
+            html.contains(r#"src="./experiment-2/raw/gel.png""#),
+            "{html}"
+        );
+        assert!(
+            html.contains(r#"href="./experiment-2/plate.csv""#),
+            "{html}"
+        );
+        assert!(html.contains(r#"href="./../x.txt""#), "{html}");
+        assert!(
+            html.contains(r#"href="https://example.org/a.png""#),
+            "{html}"
+        );
+
+        let untouched = markdown_to_html(body, false, &HtmlPolicy::default(), None);
+        assert!(untouched.contains(r#"src="./raw/gel.png""#), "{untouched}");
+    }
+
+    #[test]
+    fn missing_attachment_references_lists_renamed_or_removed_files_once() {
+        let attachments = vec![
+            Attachment::new(
+                PathBuf::from("/data/gel.png"),
+                "gel.png".into(),
+                "image/png".into(),
+                "unavailable".into(),
+                1,
+            )
+            .in_folder("raw".into()),
+        ];
+        let body = "![a](./raw/gel.png) ![b](./old.png)\n\n[again](./old.png) \
+                    [c](./raw/blot.tif) [web](https://example.org/old.png)";
+
+        assert_eq!(
+            super::missing_attachment_references(body, &attachments),
+            vec!["old.png".to_string(), "raw/blot.tif".to_string()]
+        );
+        assert!(
+            super::missing_attachment_references("![a](./raw/gel.png)", &attachments).is_empty()
+        );
+    }
+
     /// Verifies building an archive embeds extra fields as eLabFTW-style PropertyValue nodes and a reconstructed `elabftw_metadata` blob.
     ///
     /// The test asserts that:
@@ -707,20 +2706,12 @@ mod tests {
         let out = tmp.path().join("extra.eln");
 
         let extra_fields = vec![ExtraField {
-            label: "Detector".into(),
-            kind: ExtraFieldKind::Select,
-            value: "Pilatus".into(),
-            value_multi: Vec::new(),
             options: vec!["Pilatus".into(), "Eiger".into()],
             unit: Some("model".into()),
-            units: Vec::new(),
             position: Some(1),
             required: true,
-            description: None,
-            allow_multi_values: false,
-            blank_value_on_duplicate: false,
             group_id: Some(1),
-            readonly: false,
+            ..field("Detector", ExtraFieldKind::Select, "Pilatus")
         }];
         let groups = vec![ExtraFieldGroup {
             id: 1,
@@ -730,15 +2721,24 @@ mod tests {
 
         build_and_write_archive(
             &out,
-            "Title",
-            "Body",
-            &[],
-            &extra_fields,
-            &groups,
-            OffsetDateTime::from_unix_timestamp(0).unwrap(),
-            ArchiveGenre::Experiment,
-            &[],
+            &[ArchiveEntry {
+                title: "Title".into(),
+                body: "Body".into(),
+                attachments: Vec::new(),
+                extra_fields: extra_fields.clone(),
+                extra_groups: groups.clone(),
+                performed_at: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+                genre: ArchiveGenre::Experiment,
+                keywords: Vec::new(),
+                language: None,
+                related_links: Vec::new(),
+                imported_metadata: None,
+                license: License::None,
+                summary: String::new(),
+                hide_main_text: false,
+            }],
             BodyFormat::Html,
+            &ExportOptions::default(),
         )
         .unwrap();
 
@@ -753,6 +2753,7 @@ mod tests {
 
         let meta: Value = serde_json::from_str(&buf).unwrap();
         let graph = meta["@graph"].as_array().unwrap();
+        assert_ids_unique(graph);
         let experiment = graph
             .iter()
             .find(|n| n["@id"] == "./experiment/")
@@ -775,6 +2776,10 @@ mod tests {
         assert_eq!(detector["valueReference"], "select");
         assert_eq!(detector["value"], "Pilatus");
         assert_eq!(detector["unitText"], "model");
+        assert!(
+            detector.get("unitCode").is_none(),
+            "unknown units keep only unitText"
+        );
         assert!(ids.contains(&detector["@id"].as_str().unwrap()));
 
         // Metadata blob must be present and contain the field.
@@ -789,6 +2794,254 @@ mod tests {
         assert_eq!(fields["Detector"]["value"], "Pilatus");
     }
 
+    #[test]
+    fn field_groups_can_be_rebuilt_from_the_graph_alone() {
+        use tempfile::TempDir;
+
+        let grouped = |label: &str, group_id: Option<i32>, position: i32| ExtraField {
+            position: Some(position),
+            group_id,
+            ..field(label, ExtraFieldKind::Text, "x")
+        };
+        let tmp = TempDir::new().unwrap();
+        let out = tmp.path().join("grouped.eln");
+        build_and_write_archive(
+            &out,
+            &[ArchiveEntry {
+                title: "Grouped".into(),
+                body: String::new(),
+                attachments: Vec::new(),
+                extra_fields: vec![
+                    grouped("Notes", None, 0),
+                    grouped("Detector", Some(2), 2),
+                    grouped("Buffer", Some(1), 0),
+                    grouped("Voltage", Some(2), 1),
+                    grouped("Orphan", Some(9), 3),
+                ],
+                extra_groups: vec![
+                    ExtraFieldGroup {
+                        id: 1,
+                        name: "Sample".into(),
+                        position: 1,
+                    },
+                    ExtraFieldGroup {
+                        id: 2,
+                        name: "Instrument".into(),
+                        position: 0,
+                    },
+                ],
+                performed_at: OffsetDateTime::UNIX_EPOCH,
+                genre: ArchiveGenre::Experiment,
+                keywords: Vec::new(),
+                language: None,
+                related_links: Vec::new(),
+                imported_metadata: None,
+                license: License::None,
+                summary: String::new(),
+                hide_main_text: false,
+            }],
+            BodyFormat::Markdown,
+            &ExportOptions::default(),
+        )
+        .unwrap();
+
+        let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        let mut buf = String::new();
+        archive
+            .by_name("grouped/ro-crate-metadata.json")
+            .unwrap()
+            .read_to_string(&mut buf)
+            .unwrap();
+        let metadata: Value = serde_json::from_str(&buf).unwrap();
+        let graph = metadata["@graph"].as_array().unwrap();
+        let by_id = |id: &Value| graph.iter().find(|node| node["@id"] == *id).unwrap();
+        let dataset = by_id(&Value::from("./experiment/"));
+        let grouping: Vec<(String, i64, String)> = dataset["variableMeasured"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|link| by_id(&link["@id"]))
+            .filter(|property| property["propertyID"] != "elabftw_metadata")
+            .map(|property| {
+                let group = by_id(&property["isPartOf"]["@id"]);
+                assert_eq!(group["@type"], "CreativeWork");
+                (
+                    group["name"].as_str().unwrap().to_string(),
+                    group["position"].as_i64().unwrap(),
+                    property["propertyID"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect();
+        let expected = [
+            ("Instrument", 0, "Voltage"),
+            ("Instrument", 0, "Detector"),
+            ("Sample", 1, "Buffer"),
+            ("Default", 2, "Notes"),
+            ("Default", 2, "Orphan"),
+        ];
+        assert_eq!(
+            grouping,
+            expected.map(|(group, position, field)| (group.into(), position, field.into()))
+        );
+    }
+
+    #[test]
+    fn number_units_carry_ucum_codes_when_known() {
+        let volume = |unit: &str| ExtraField {
+            unit: Some(unit.into()),
+            units: vec![unit.into()],
+            ..field(&format!("Volume {unit}"), ExtraFieldKind::Number, "5")
+        };
+        let fields = [volume("µl"), volume("ul."), volume("OD600")];
+        let units = UnitSettings {
+            codes: [("OD600".to_string(), "{OD600}".to_string())].into(),
+        };
+
+        let export = build_extra_fields_export(&fields, &[], false, &units).unwrap();
+        let codes: Vec<_> = export
+            .property_values
+            .iter()
+            .map(|node| (node["unitText"].as_str(), node.get("unitCode")))
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                (Some("µl"), Some(&Value::from("uL"))),
+                (Some("ul."), None),
+                (Some("OD600"), Some(&Value::from("{OD600}"))),
+            ]
+        );
+    }
+
+    #[test]
+    fn elabftw_metadata_orders_fields_by_position_regardless_of_input_order() {
+        let positioned = |label: &str, position: Option<i32>| ExtraField {
+            position,
+            ..field(label, ExtraFieldKind::Text, "")
+        };
+        let fields = [
+            positioned("Zeta", Some(0)),
+            positioned("alpha", None),
+            positioned("Buffer", Some(2)),
+            positioned("Amount", Some(2)),
+            positioned("Notes", None),
+        ];
+
+        let expected = reconstruct_elabftw_metadata(&fields, &[], false).unwrap();
+        let labels: Vec<String> = serde_json::from_str::<Value>(&expected).unwrap()["extra_fields"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        assert_eq!(labels.len(), 5);
+        let order: Vec<usize> = ["Zeta", "Amount", "Buffer", "alpha", "Notes"]
+            .iter()
+            .map(|label| expected.find(&format!("\"{label}\":{{")).unwrap())
+            .collect();
+        assert!(order.is_sorted(), "{expected}");
+
+        for shift in 1..fields.len() {
+            let mut permuted = fields.to_vec();
+            permuted.rotate_left(shift);
+            permuted.swap(0, 1);
+            assert_eq!(
+                reconstruct_elabftw_metadata(&permuted, &[], false).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn elabftw_metadata_rejects_labels_differing_only_in_case() {
+        let mut field = ExtraField {
+            unit: Some("°C".into()),
+            ..field("Temperature", ExtraFieldKind::Number, "37")
+        };
+        let mut other = field.clone();
+        other.label = "temperature".into();
+        let err = reconstruct_elabftw_metadata(&[field.clone(), other], &[], false).unwrap_err();
+        let message = err.to_string();
+        assert!(
+            message.contains("\"Temperature\"") && message.contains("\"temperature\""),
+            "{message}"
+        );
+
+        field.position = Some(3);
+        let err = reconstruct_elabftw_metadata(&[field.clone(), field], &[], false).unwrap_err();
+        assert!(err.to_string().contains("exists twice"), "{err}");
+    }
+
+    #[test]
+    fn elabftw_metadata_round_trips_default_values() {
+        use crate::models::extra_fields::parse_elabftw_extra_fields;
+
+        let power = ExtraField {
+            position: Some(0),
+            default_value: Some("10".into()),
+            ..field("Power", ExtraFieldKind::Number, "12")
+        };
+        let filters = ExtraField {
+            label: "Filters".into(),
+            kind: ExtraFieldKind::Select,
+            value: "UV".into(),
+            value_multi: vec!["UV".into()],
+            options: vec!["UV".into(), "IR".into()],
+            position: Some(1),
+            allow_multi_values: true,
+            default_value: Some("UV, IR".into()),
+            default_multi: vec!["UV".into(), "IR".into()],
+            ..power.clone()
+        };
+        let plain = ExtraField {
+            label: "Notes".into(),
+            kind: ExtraFieldKind::Text,
+            position: Some(2),
+            default_value: None,
+            ..power.clone()
+        };
+        let fields = [power, filters, plain];
+
+        let json = reconstruct_elabftw_metadata(&fields, &[], false).unwrap();
+        let raw: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            raw["extra_fields"]["Filters"]["default_value"],
+            serde_json::json!(["UV", "IR"])
+        );
+        assert!(raw["extra_fields"]["Notes"].get("default_value").is_none());
+
+        let import = parse_elabftw_extra_fields(&json).unwrap();
+        assert_eq!(import.fields, fields, "values and defaults survive");
+    }
+
+    #[test]
+    fn date_ranges_export_as_iso_intervals_and_round_trip() {
+        use crate::models::extra_fields::parse_elabftw_extra_fields;
+
+        let campaign = ExtraField {
+            position: Some(0),
+            required: true,
+            ..field(
+                "Campaign",
+                ExtraFieldKind::DateRange,
+                "2024-05-01/2024-05-03",
+            )
+        };
+        let fields = [campaign];
+
+        let export =
+            build_extra_fields_export(&fields, &[], false, &UnitSettings::default()).unwrap();
+        let node = &export.property_values[0];
+        assert_eq!(node["valueReference"], "daterange");
+        assert_eq!(node["value"], "2024-05-01/2024-05-03");
+
+        let json = reconstruct_elabftw_metadata(&fields, &[], false).unwrap();
+        let raw: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(raw["extra_fields"]["Campaign"]["type"], "daterange");
+        let import = parse_elabftw_extra_fields(&json).unwrap();
+        assert_eq!(import.fields, fields);
+    }
+
     #[test]
     fn build_and_write_archive_rejects_duplicate_sanitized_names() {
         use std::fs;
@@ -820,20 +3073,2416 @@ mod tests {
 
         let result = build_and_write_archive(
             &out,
-            "Title",
-            "Body",
-            &attachments,
-            &[],
-            &[],
-            OffsetDateTime::from_unix_timestamp(0).unwrap(),
-            ArchiveGenre::Experiment,
-            &[],
+            &[ArchiveEntry {
+                title: "Title".into(),
+                body: "Body".into(),
+                attachments: attachments.clone(),
+                extra_fields: Vec::new(),
+                extra_groups: Vec::new(),
+                performed_at: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+                genre: ArchiveGenre::Experiment,
+                keywords: Vec::new(),
+                language: None,
+                related_links: Vec::new(),
+                imported_metadata: None,
+                license: License::None,
+                summary: String::new(),
+                hide_main_text: false,
+            }],
             BodyFormat::Html,
+            &ExportOptions::default(),
         );
 
         assert!(result.is_err(), "duplicate names should be rejected");
     }
 
+    #[test]
+    fn build_and_write_archive_rolls_back_modified_attachment() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let out = tmp.path().join("modified.eln");
+        let file = tmp.path().join("data.txt");
+        fs::write(&file, b"changed after adding").unwrap();
+
+        let attachments = vec![Attachment::new(
+            file,
+            "data.txt".into(),
+            "text/plain".into(),
+            // sha256 of "abc", i.e. the content before it was modified.
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".into(),
+            3,
+        )];
+
+        let err = build_and_write_archive(
+            &out,
+            &[ArchiveEntry {
+                title: "Title".into(),
+                body: "Body".into(),
+                attachments: attachments.clone(),
+                extra_fields: Vec::new(),
+                extra_groups: Vec::new(),
+                performed_at: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+                genre: ArchiveGenre::Experiment,
+                keywords: Vec::new(),
+                language: None,
+                related_links: Vec::new(),
+                imported_metadata: None,
+                license: License::None,
+                summary: String::new(),
+                hide_main_text: false,
+            }],
+            BodyFormat::Html,
+            &ExportOptions::default(),
+        )
+        .expect_err("hash mismatch must fail the save");
+        assert!(err.to_string().contains("modified since it was added"));
+
+        // The partially written archive must not survive.
+        assert!(!out.exists());
+        assert!(!crate::logic::save_resume::partial_path(&out).exists());
+    }
+
+    #[test]
+    fn both_format_keeps_html_text_and_markdown_file() {
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let data = tmp.path().join("od.csv");
+        std::fs::write(&data, "t,od\n0,0.1\n").unwrap();
+        let sha256 = crate::utils::hash_file(&data).unwrap();
+        let body = "# Growth\n\nOD rose *quickly*.";
+        let entry = ArchiveEntry {
+            title: "Growth curve".into(),
+            body: body.into(),
+            attachments: vec![Attachment::new(
+                data,
+                "od.csv".into(),
+                "text/csv".into(),
+                sha256,
+                11,
+            )],
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+            performed_at: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+            genre: ArchiveGenre::Experiment,
+            keywords: Vec::new(),
+            language: None,
+            related_links: Vec::new(),
+            imported_metadata: None,
+            license: License::None,
+            summary: String::new(),
+            hide_main_text: false,
+        };
+
+        let out = tmp.path().join("both.eln");
+        build_and_write_archive(&out, &[entry], BodyFormat::Both, &ExportOptions::default())
+            .unwrap();
+
+        let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        let mut markdown = String::new();
+        archive
+            .by_name("both/experiment/body.md")
+            .unwrap()
+            .read_to_string(&mut markdown)
+            .unwrap();
+        assert_eq!(markdown, body);
+
+        let metadata: Value =
+            serde_json::from_reader(archive.by_name("both/ro-crate-metadata.json").unwrap())
+                .unwrap();
+        let graph = metadata["@graph"].as_array().unwrap();
+        let dataset = graph.iter().find(|n| n["@id"] == "./experiment/").unwrap();
+        assert_eq!(dataset["encodingFormat"], "text/html");
+        assert!(
+            dataset["text"]
+                .as_str()
+                .unwrap()
+                .contains("<em>quickly</em>")
+        );
+        assert_eq!(
+            dataset["hasPart"],
+            serde_json::json!([
+                { "@id": "./experiment/od.csv" },
+                { "@id": "./experiment/body.md" },
+            ])
+        );
+        let node = graph
+            .iter()
+            .find(|n| n["@id"] == "./experiment/body.md")
+            .unwrap();
+        assert_eq!(node["@type"], "File");
+        assert_eq!(node["encodingFormat"], "text/markdown");
+        assert_eq!(node["contentSize"], body.len().to_string());
+        let (digests, _) = copy_and_digest(
+            &mut body.as_bytes(),
+            &mut std::io::sink(),
+            &[HashAlgorithm::Sha256],
+        )
+        .unwrap();
+        assert_eq!(node["sha256"], digests[&HashAlgorithm::Sha256]);
+        let report = verify_archive(&out).unwrap();
+        assert!(report.is_ok() && report.checked == 2, "{report}");
+
+        // Opening the archive again edits the markdown, not the HTML or an attachment.
+        let opened =
+            crate::logic::eln_import::open_archive(&out, &tmp.path().join("opened")).unwrap();
+        assert_eq!(opened.body, body);
+        assert_eq!(opened.body_format, BodyFormat::Both);
+        let names: Vec<&str> = opened
+            .attachments
+            .iter()
+            .map(|att| att.sanitized_name.as_str())
+            .collect();
+        assert_eq!(names, ["od.csv"]);
+    }
+
+    #[test]
+    fn build_and_write_archive_writes_readme_as_file_node() {
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let entry = ArchiveEntry {
+            title: "Growth curve".into(),
+            body: "- step one\n- step two".into(),
+            attachments: Vec::new(),
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+            performed_at: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+            genre: ArchiveGenre::Experiment,
+            keywords: vec!["growth".into()],
+            language: None,
+            related_links: Vec::new(),
+            imported_metadata: None,
+            license: License::None,
+            summary: String::new(),
+            hide_main_text: false,
+        };
+        let export = ExportOptions {
+            include_readme: true,
+            license: "CC0-1.0".into(),
+            ..Default::default()
+        };
+
+        let out = tmp.path().join("readme.eln");
+        build_and_write_archive(&out, &[entry], BodyFormat::Markdown, &export).unwrap();
+
+        let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        let mut readme = String::new();
+        archive
+            .by_name("readme/README.txt")
+            .unwrap()
+            .read_to_string(&mut readme)
+            .unwrap();
+        assert!(readme.starts_with("Growth curve\n"), "{readme}");
+        assert!(
+            readme.contains("License: CC0-1.0\nKeywords: growth\n"),
+            "{readme}"
+        );
+        assert!(readme.contains("- step one\n- step two"), "{readme}");
+
+        let metadata: Value =
+            serde_json::from_reader(archive.by_name("readme/ro-crate-metadata.json").unwrap())
+                .unwrap();
+        let graph = metadata["@graph"].as_array().unwrap();
+        assert_ids_unique(graph);
+        let node = graph.iter().find(|n| n["@id"] == "./README.txt").unwrap();
+        assert_eq!(node["@type"], "File");
+        assert_eq!(node["contentSize"], readme.len().to_string());
+        assert!(
+            graph[1]["hasPart"]
+                .as_array()
+                .unwrap()
+                .contains(&serde_json::json!({ "@id": "./README.txt" }))
+        );
+    }
+
+    #[test]
+    fn imported_metadata_file_is_embedded_byte_for_byte() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        use crate::models::extra_fields::{ExtraFieldsSource, parse_elabftw_extra_fields};
+
+        // Spacing, key order and escapes the reconstruction does not keep.
+        let fixture = concat!(
+            "{\"elabftw\":{\"extra_fields_groups\":[]},\r\n",
+            r#"  "extra_fields": {"Temp\u00e9rature": {"type": "number", "unit": "\u00b0C", "value": "37.0"}}}"#,
+            "\n"
+        );
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("eLabFTW export.json");
+        fs::write(&path, fixture).unwrap();
+        let import = parse_elabftw_extra_fields(fixture).unwrap();
+        let source = ExtraFieldsSource::new(path.clone(), fixture, OffsetDateTime::UNIX_EPOCH);
+        let entry = ArchiveEntry {
+            title: "Imported".into(),
+            body: String::new(),
+            attachments: Vec::new(),
+            extra_fields: import.fields,
+            extra_groups: import.groups,
+            performed_at: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+            genre: ArchiveGenre::Experiment,
+            keywords: Vec::new(),
+            language: None,
+            related_links: Vec::new(),
+            imported_metadata: Some(source),
+            license: License::None,
+            summary: String::new(),
+            hide_main_text: false,
+        };
+        let export = ExportOptions {
+            include_imported_metadata: true,
+            ..Default::default()
+        };
+
+        let out = tmp.path().join("imported.eln");
+        build_and_write_archive(
+            &out,
+            std::slice::from_ref(&entry),
+            BodyFormat::Markdown,
+            &export,
+        )
+        .unwrap();
+        let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        let mut embedded = Vec::new();
+        archive
+            .by_name("imported/experiment/imported_metadata.json")
+            .unwrap()
+            .read_to_end(&mut embedded)
+            .unwrap();
+        assert_eq!(embedded, fixture.as_bytes());
+        let metadata: Value =
+            serde_json::from_reader(archive.by_name("imported/ro-crate-metadata.json").unwrap())
+                .unwrap();
+        let graph = metadata["@graph"].as_array().unwrap();
+        let node = graph
+            .iter()
+            .find(|n| n["@id"] == "./experiment/imported_metadata.json")
+            .unwrap();
+        assert_eq!(node["alternateName"], "eLabFTW export.json");
+        assert!(verify_archive(&out).unwrap().is_ok());
+
+        // Left out unless asked for.
+        build_and_write_archive(
+            &out,
+            std::slice::from_ref(&entry),
+            BodyFormat::Markdown,
+            &ExportOptions::default(),
+        )
+        .unwrap();
+        let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        assert!(
+            archive
+                .by_name("imported/experiment/imported_metadata.json")
+                .is_err()
+        );
+
+        // A file edited since the import is not passed off as the imported one.
+        fs::write(&path, fixture.replace("37.0", "38.0")).unwrap();
+        let err =
+            build_and_write_archive(&out, &[entry], BodyFormat::Markdown, &export).unwrap_err();
+        assert!(err.to_string().contains("changed since"), "{err}");
+    }
+
+    #[test]
+    fn build_and_write_archive_verifies_recorded_digests_and_writes_manifest() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("plate.csv");
+        fs::write(&file, b"a,b\n1,2\n").unwrap();
+        let (digests, size) = copy_and_digest(
+            &mut File::open(&file).unwrap(),
+            &mut std::io::sink(),
+            &[HashAlgorithm::Sha256, HashAlgorithm::Blake3],
+        )
+        .unwrap();
+        let (sha512, _) = copy_and_digest(
+            &mut File::open(&file).unwrap(),
+            &mut std::io::sink(),
+            &[HashAlgorithm::Sha512],
+        )
+        .unwrap();
+        let entry = |digests: Digests| ArchiveEntry {
+            title: "Checksums".into(),
+            body: String::new(),
+            attachments: vec![
+                Attachment::new(
+                    file.clone(),
+                    "plate.csv".into(),
+                    "text/csv".into(),
+                    digests[&HashAlgorithm::Sha256].clone(),
+                    size,
+                )
+                .in_folder("raw".into())
+                .with_digests(digests),
+            ],
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+            performed_at: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+            genre: ArchiveGenre::Experiment,
+            keywords: Vec::new(),
+            language: None,
+            related_links: Vec::new(),
+            imported_metadata: None,
+            license: License::None,
+            summary: String::new(),
+            hide_main_text: false,
+        };
+        let export = ExportOptions {
+            checksums_manifest: true,
+            ..Default::default()
+        };
+
+        let out = tmp.path().join("checksums.eln");
+        build_and_write_archive(&out, &[entry(digests.clone())], BodyFormat::Html, &export)
+            .unwrap();
+
+        let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        let mut manifest = String::new();
+        archive
+            .by_name("checksums/checksums.sha512")
+            .unwrap()
+            .read_to_string(&mut manifest)
+            .unwrap();
+        assert_eq!(
+            manifest,
+            format!(
+                "{}  experiment/raw/plate.csv\n",
+                sha512[&HashAlgorithm::Sha512]
+            )
+        );
+        let metadata: serde_json::Value =
+            serde_json::from_reader(archive.by_name("checksums/ro-crate-metadata.json").unwrap())
+                .unwrap();
+        let graph = metadata["@graph"].as_array().unwrap();
+        let file_node = graph
+            .iter()
+            .find(|n| n["@id"] == "./experiment/raw/plate.csv")
+            .unwrap();
+        assert_eq!(file_node["sha256"], digests[&HashAlgorithm::Sha256]);
+        assert_eq!(file_node["blake3"], digests[&HashAlgorithm::Blake3]);
+        assert_eq!(file_node["sha512"], sha512[&HashAlgorithm::Sha512]);
+        assert!(
+            graph[1]["hasPart"]
+                .as_array()
+                .unwrap()
+                .contains(&serde_json::json!({ "@id": "./checksums.sha512" }))
+        );
+
+        // A stale BLAKE3 digest fails the save even when SHA-256 still matches.
+        let mut stale = digests;
+        stale.insert(HashAlgorithm::Blake3, "0".repeat(64));
+        let err = build_and_write_archive(
+            &tmp.path().join("stale.eln"),
+            &[entry(stale)],
+            BodyFormat::Html,
+            &export,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("expected blake3"), "{err}");
+    }
+
+    #[test]
+    fn compression_is_chosen_per_file_and_the_metadata_is_always_deflated() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let attachments: Vec<Attachment> = [("scan.png", "image/png"), ("notes.txt", "text/plain")]
+            .into_iter()
+            .map(|(name, mime)| {
+                let path = tmp.path().join(name);
+                fs::write(&path, "repetitive content ".repeat(200)).unwrap();
+                let (digest, size) = crate::utils::copy_and_hash(
+                    &mut File::open(&path).unwrap(),
+                    &mut std::io::sink(),
+                )
+                .unwrap();
+                Attachment::new(path, name.into(), mime.into(), digest, size)
+            })
+            .collect();
+        let entry = ArchiveEntry {
+            title: "Compression".into(),
+            body: String::new(),
+            attachments,
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+            performed_at: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+            genre: ArchiveGenre::Experiment,
+            keywords: Vec::new(),
+            language: None,
+            related_links: Vec::new(),
+            imported_metadata: None,
+            license: License::None,
+            summary: String::new(),
+            hide_main_text: false,
+        };
+
+        let methods = |method: Compression| {
+            let out = tmp.path().join("compression.eln");
+            let export = ExportOptions {
+                compression: CompressionOptions { method, level: 9 },
+                ..Default::default()
+            };
+            build_and_write_archive(
+                &out,
+                std::slice::from_ref(&entry),
+                BodyFormat::Markdown,
+                &export,
+            )
+            .unwrap();
+            let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+            ["scan.png", "notes.txt"]
+                .map(|name| format!("compression/experiment/{name}"))
+                .into_iter()
+                .chain(["compression/ro-crate-metadata.json".to_string()])
+                .map(|name| archive.by_name(&name).unwrap().compression())
+                .collect::<Vec<_>>()
+        };
+
+        use CompressionMethod::{Deflated, Stored};
+        assert_eq!(methods(Compression::Auto), [Stored, Deflated, Deflated]);
+        assert_eq!(methods(Compression::Stored), [Stored, Stored, Deflated]);
+        assert_eq!(
+            methods(Compression::Deflate),
+            [Deflated, Deflated, Deflated]
+        );
+    }
+
+    #[test]
+    fn already_compressed_media_types_are_recognized() {
+        for mime in [
+            "image/png",
+            "image/tiff",
+            "video/mp4",
+            "application/zip",
+            "application/gzip",
+        ] {
+            assert!(is_precompressed(mime), "{mime}");
+        }
+        for mime in [
+            "image/svg+xml",
+            "text/plain",
+            "application/json",
+            "application/octet-stream",
+        ] {
+            assert!(!is_precompressed(mime), "{mime}");
+        }
+    }
+
+    #[test]
+    fn progress_covers_all_attachments_and_cancelling_keeps_the_previous_archive() {
+        use std::fs;
+        use std::ops::ControlFlow;
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let attachments: Vec<Attachment> = [("a.txt", "abc"), ("b.txt", "defgh")]
+            .into_iter()
+            .map(|(name, content)| {
+                let path = tmp.path().join(name);
+                fs::write(&path, content).unwrap();
+                let (digest, size) = crate::utils::copy_and_hash(
+                    &mut File::open(&path).unwrap(),
+                    &mut std::io::sink(),
+                )
+                .unwrap();
+                Attachment::new(path, name.into(), "text/plain".into(), digest, size)
+            })
+            .collect();
+        let entry = ArchiveEntry {
+            title: "Progress".into(),
+            body: String::new(),
+            attachments,
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+            performed_at: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+            genre: ArchiveGenre::Experiment,
+            keywords: Vec::new(),
+            language: None,
+            related_links: Vec::new(),
+            imported_metadata: None,
+            license: License::None,
+            summary: String::new(),
+            hide_main_text: false,
+        };
+        let export = ExportOptions::default();
+
+        let out = tmp.path().join("progress.eln");
+        let mut reports = Vec::new();
+        build_and_write_archive_with_progress(
+            &out,
+            std::slice::from_ref(&entry),
+            BodyFormat::Markdown,
+            &export,
+            |progress| {
+                reports.push(progress.clone());
+                ControlFlow::Continue(())
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            reports,
+            [
+                WriteProgress {
+                    current_file: "a.txt".into(),
+                    bytes_done: 3,
+                    bytes_total: 8,
+                },
+                WriteProgress {
+                    current_file: "b.txt".into(),
+                    bytes_done: 8,
+                    bytes_total: 8,
+                },
+            ]
+        );
+        let saved = fs::read(&out).unwrap();
+
+        let err = build_and_write_archive_with_progress(
+            &out,
+            &[entry],
+            BodyFormat::Markdown,
+            &export,
+            |progress| {
+                if progress.current_file == "b.txt" {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            },
+        )
+        .unwrap_err();
+        assert!(err.is::<WriteCancelled>(), "{err}");
+        assert_eq!(fs::read(&out).unwrap(), saved, "previous archive kept");
+        assert!(
+            !crate::logic::save_resume::partial_path(&out).exists(),
+            "partial archive removed"
+        );
+    }
+
+    #[test]
+    fn failed_saves_leave_the_previous_archive_untouched() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        use crate::logic::save_resume::partial_path;
+
+        let tmp = TempDir::new().unwrap();
+        let mut entry = resumable_entry(tmp.path(), &["a.bin"]);
+        let out = tmp.path().join("run.eln");
+        build_and_write_archive(
+            &out,
+            &[entry.clone()],
+            BodyFormat::Markdown,
+            &Default::default(),
+        )
+        .unwrap();
+        let saved = fs::read(&out).unwrap();
+
+        // The second attachment is gone by the time the first one has been written.
+        let mut second = resumable_entry(tmp.path(), &["b.bin"]);
+        entry.attachments.append(&mut second.attachments);
+        fs::remove_file(tmp.path().join("b.bin")).unwrap();
+        let err = build_and_write_archive(
+            &out,
+            &[entry.clone()],
+            BodyFormat::Markdown,
+            &Default::default(),
+        )
+        .unwrap_err();
+        assert!(err.is::<super::AttachmentIntegrityError>(), "{err}");
+        assert_eq!(fs::read(&out).unwrap(), saved);
+        assert!(!partial_path(&out).exists());
+
+        // Attachments larger than any drive are refused before anything is written.
+        entry.attachments.truncate(1);
+        entry.attachments[0].size = u64::MAX / 2;
+        let err =
+            build_and_write_archive(&out, &[entry], BodyFormat::Markdown, &Default::default())
+                .unwrap_err();
+        let space = err
+            .downcast_ref::<super::InsufficientSpace>()
+            .expect("free space checked");
+        assert_eq!(space.needed, u64::MAX / 2);
+        assert!(err.to_string().contains("Not enough free disk space"));
+        assert_eq!(fs::read(&out).unwrap(), saved);
+        assert!(!partial_path(&out).exists());
+    }
+
+    /// Entry with one attachment of 4 KiB of distinct bytes per name.
+    fn resumable_entry(dir: &std::path::Path, names: &[&str]) -> ArchiveEntry {
+        let attachments = names
+            .iter()
+            .enumerate()
+            .map(|(seed, name)| {
+                let path = dir.join(name);
+                let content: Vec<u8> = (0..4096_u32)
+                    .map(|i| (i * 31 + seed as u32 * 7) as u8)
+                    .collect();
+                std::fs::write(&path, &content).unwrap();
+                let (digest, size) =
+                    crate::utils::copy_and_hash(&mut content.as_slice(), &mut std::io::sink())
+                        .unwrap();
+                Attachment::new(
+                    path,
+                    name.to_string(),
+                    "application/octet-stream".into(),
+                    digest,
+                    size,
+                )
+            })
+            .collect();
+        ArchiveEntry {
+            title: "Large run".into(),
+            body: String::new(),
+            attachments,
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+            performed_at: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+            genre: ArchiveGenre::Experiment,
+            keywords: Vec::new(),
+            language: None,
+            related_links: Vec::new(),
+            imported_metadata: None,
+            license: License::None,
+            summary: String::new(),
+            hide_main_text: false,
+        }
+    }
+
+    #[test]
+    fn killed_resumable_saves_continue_from_their_last_checkpoint() {
+        use std::fs;
+        use std::io::Write;
+        use std::ops::ControlFlow;
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+        use tempfile::TempDir;
+
+        use crate::logic::save_resume::{has_resume_state, state_path};
+
+        let tmp = TempDir::new().unwrap();
+        let entry = resumable_entry(tmp.path(), &["a.bin", "b.bin", "c.bin", "d.bin"]);
+        let out = tmp.path().join("large.eln");
+        let export = ExportOptions {
+            checksums_manifest: true,
+            ..Default::default()
+        };
+        let every_attachment = Resumable {
+            checkpoint_bytes: 1,
+            resume: false,
+        };
+
+        // The writer dies while copying the third attachment.
+        let killed = catch_unwind(AssertUnwindSafe(|| {
+            build_and_write_archive_resumable(
+                &out,
+                std::slice::from_ref(&entry),
+                BodyFormat::Markdown,
+                &export,
+                every_attachment,
+                |progress| {
+                    assert_ne!(progress.current_file, "c.bin", "writer killed");
+                    ControlFlow::Continue(())
+                },
+                |_| MissingFileAction::Abort,
+            )
+        }));
+        assert!(killed.is_err());
+        assert!(has_resume_state(&out));
+        // Bytes written after the checkpoint are cut off when resuming.
+        let mut partial = fs::OpenOptions::new()
+            .append(true)
+            .open(crate::logic::save_resume::partial_path(&out))
+            .unwrap();
+        partial.write_all(b"half-written local header").unwrap();
+        drop(partial);
+
+        // Attachments in the partial archive are not read again.
+        fs::remove_file(tmp.path().join("a.bin")).unwrap();
+        let mut copied = Vec::new();
+        let outcome = build_and_write_archive_resumable(
+            &out,
+            &[entry],
+            BodyFormat::Markdown,
+            &export,
+            Resumable {
+                resume: true,
+                ..every_attachment
+            },
+            |progress| {
+                copied.push((progress.current_file.clone(), progress.bytes_done));
+                ControlFlow::Continue(())
+            },
+            |_| MissingFileAction::Abort,
+        )
+        .unwrap();
+        assert_eq!(outcome, ResumeOutcome::Resumed { attachments: 2 });
+        assert_eq!(
+            copied,
+            [
+                ("c.bin".to_string(), 3 * 4096),
+                ("d.bin".to_string(), 4 * 4096)
+            ]
+        );
+        assert!(
+            !state_path(&out).exists(),
+            "checkpoint removed once complete"
+        );
+
+        let report = verify_archive(&out).unwrap();
+        assert!(report.is_ok(), "{report}");
+        assert_eq!(report.checked, 5, "four attachments and the manifest");
+        let mut zip = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        let names: Vec<&str> = zip.file_names().filter(|n| n.ends_with(".bin")).collect();
+        assert_eq!(names.len(), 4, "{names:?}");
+        let mut manifest = String::new();
+        zip.by_name("large/checksums.sha512")
+            .unwrap()
+            .read_to_string(&mut manifest)
+            .unwrap();
+        assert_eq!(manifest.lines().count(), 4);
+    }
+
+    #[test]
+    fn attachments_vanishing_during_the_save_follow_the_missing_file_action() {
+        use std::fs;
+        use std::ops::ControlFlow;
+        use tempfile::TempDir;
+
+        use crate::logic::save_resume::has_resume_state;
+
+        let tmp = TempDir::new().unwrap();
+        let entry = resumable_entry(tmp.path(), &["a.bin", "b.bin", "c.bin"]);
+        let out = tmp.path().join("large.eln");
+        let export = ExportOptions {
+            include_preview: true,
+            ..Default::default()
+        };
+        let vanishing = tmp.path().join("b.bin");
+        let content = fs::read(&vanishing).unwrap();
+        let write = |on_missing: &mut dyn FnMut(&std::path::Path) -> MissingFileAction| {
+            build_and_write_archive_resumable(
+                &out,
+                std::slice::from_ref(&entry),
+                BodyFormat::Markdown,
+                &export,
+                Resumable {
+                    checkpoint_bytes: u64::MAX,
+                    resume: false,
+                },
+                |progress| {
+                    // The share drops the second attachment while the first is copied.
+                    if progress.current_file == "a.bin" && vanishing.exists() {
+                        fs::remove_file(&vanishing).unwrap();
+                    }
+                    ControlFlow::Continue(())
+                },
+                on_missing,
+            )
+        };
+
+        let mut asked = Vec::new();
+        let err = write(&mut |path| {
+            asked.push(path.to_path_buf());
+            MissingFileAction::Abort
+        })
+        .unwrap_err();
+        assert!(err.is::<super::AttachmentIntegrityError>(), "{err}");
+        assert_eq!(asked, std::slice::from_ref(&vanishing));
+        assert!(!out.exists(), "partial archive removed");
+        assert!(!has_resume_state(&out));
+
+        fs::write(&vanishing, &content).unwrap();
+        write(&mut |_| MissingFileAction::Skip).unwrap();
+        assert!(verify_archive(&out).unwrap().is_ok());
+        let mut zip = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        let names: Vec<&str> = zip.file_names().filter(|n| n.ends_with(".bin")).collect();
+        assert_eq!(names.len(), 2, "{names:?}");
+        let mut metadata = String::new();
+        zip.by_name("large/ro-crate-metadata.json")
+            .unwrap()
+            .read_to_string(&mut metadata)
+            .unwrap();
+        let metadata: serde_json::Value = serde_json::from_str(&metadata).unwrap();
+        let graph = metadata["@graph"].as_array().unwrap();
+        let dataset = graph
+            .iter()
+            .find(|node| node["@id"] == "./experiment/")
+            .unwrap();
+        let comment = graph
+            .iter()
+            .find(|node| node["@id"] == dataset["comment"][0]["@id"])
+            .unwrap();
+        assert_eq!(comment["@type"], "Comment");
+        assert!(comment["text"].as_str().unwrap().ends_with(": b.bin"));
+        assert_eq!(dataset["hasPart"].as_array().unwrap().len(), 2);
+        let mut preview = String::new();
+        zip.by_name("large/index.html")
+            .unwrap()
+            .read_to_string(&mut preview)
+            .unwrap();
+        assert!(!preview.contains("b.bin"));
+
+        let mut retries = 0;
+        write(&mut |path| {
+            retries += 1;
+            // The file is back by the time the user retries.
+            fs::write(path, &content).unwrap();
+            MissingFileAction::Retry
+        })
+        .unwrap();
+        assert_eq!(retries, 1);
+        assert!(verify_archive(&out).unwrap().is_ok());
+        let zip = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        assert_eq!(zip.file_names().filter(|n| n.ends_with(".bin")).count(), 3);
+    }
+
+    #[test]
+    fn resuming_an_inconsistent_partial_archive_starts_over() {
+        use std::fs;
+        use std::ops::ControlFlow;
+        use tempfile::TempDir;
+
+        use crate::logic::save_resume::has_resume_state;
+
+        let tmp = TempDir::new().unwrap();
+        let entry = resumable_entry(tmp.path(), &["a.bin", "b.bin", "c.bin"]);
+        let out = tmp.path().join("large.eln");
+        let resumable = Resumable {
+            checkpoint_bytes: 1,
+            resume: true,
+        };
+        let interrupt = |out: &std::path::Path| {
+            // A transient read failure on the last attachment stops the save.
+            let moved = tmp.path().join("c.moved");
+            fs::rename(tmp.path().join("c.bin"), &moved).unwrap();
+            let err = build_and_write_archive_resumable(
+                out,
+                std::slice::from_ref(&entry),
+                BodyFormat::Markdown,
+                &ExportOptions::default(),
+                Resumable {
+                    resume: false,
+                    ..resumable
+                },
+                |_| ControlFlow::Continue(()),
+                |_| MissingFileAction::Abort,
+            )
+            .unwrap_err();
+            assert!(err.is::<super::AttachmentIntegrityError>(), "{err}");
+            fs::rename(&moved, tmp.path().join("c.bin")).unwrap();
+            assert!(has_resume_state(out));
+        };
+        let resume = |entry: &ArchiveEntry| {
+            build_and_write_archive_resumable(
+                &out,
+                std::slice::from_ref(entry),
+                BodyFormat::Markdown,
+                &ExportOptions::default(),
+                resumable,
+                |_| ControlFlow::Continue(()),
+                |_| MissingFileAction::Abort,
+            )
+            .unwrap()
+        };
+
+        interrupt(&out);
+        let partial = crate::logic::save_resume::partial_path(&out);
+        let length = fs::metadata(&partial).unwrap().len();
+        fs::OpenOptions::new()
+            .write(true)
+            .open(&partial)
+            .unwrap()
+            .set_len(length / 4)
+            .unwrap();
+        let ResumeOutcome::Restarted(reason) = resume(&entry) else {
+            panic!("truncated partial archive resumed");
+        };
+        assert!(reason.contains("shorter"), "{reason}");
+        assert!(verify_archive(&out).unwrap().is_ok());
+        assert!(!has_resume_state(&out));
+
+        interrupt(&out);
+        let mut changed = entry.clone();
+        changed.attachments.swap(0, 1);
+        let ResumeOutcome::Restarted(reason) = resume(&changed) else {
+            panic!("reordered attachments resumed");
+        };
+        assert!(reason.contains("attachments changed"), "{reason}");
+        assert!(verify_archive(&out).unwrap().is_ok());
+    }
+
+    #[test]
+    fn verify_archive_catches_altered_and_missing_files() {
+        use std::fs;
+        use std::io::Write;
+        use tempfile::TempDir;
+        use zip::write::SimpleFileOptions;
+
+        let tmp = TempDir::new().unwrap();
+        let mut attachments = Vec::new();
+        for (name, content) in [("a.txt", "alpha"), ("b.txt", "bravo")] {
+            let path = tmp.path().join(name);
+            fs::write(&path, content).unwrap();
+            let (sha256, size) =
+                crate::utils::copy_and_hash(&mut File::open(&path).unwrap(), &mut std::io::sink())
+                    .unwrap();
+            attachments.push(Attachment::new(
+                path,
+                name.into(),
+                "text/plain".into(),
+                sha256,
+                size,
+            ));
+        }
+        let out = tmp.path().join("run.eln");
+        build_and_write_archive(
+            &out,
+            &[ArchiveEntry {
+                title: "Run".into(),
+                body: String::new(),
+                attachments,
+                extra_fields: Vec::new(),
+                extra_groups: Vec::new(),
+                performed_at: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+                genre: ArchiveGenre::Experiment,
+                keywords: Vec::new(),
+                language: None,
+                related_links: Vec::new(),
+                imported_metadata: None,
+                license: License::None,
+                summary: String::new(),
+                hide_main_text: false,
+            }],
+            BodyFormat::Markdown,
+            &ExportOptions {
+                include_preview: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let report = verify_archive(&out).unwrap();
+        assert!(report.is_ok(), "{report}");
+        assert_eq!(report.checked, 3, "two attachments and the preview");
+
+        // Rewrite the archive with one attachment altered and the other dropped.
+        let mut source = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        let damaged = tmp.path().join("damaged.eln");
+        let mut writer = zip::ZipWriter::new(File::create(&damaged).unwrap());
+        for index in 0..source.len() {
+            let mut entry = source.by_index(index).unwrap();
+            let name = entry.name().to_string();
+            if entry.is_dir() || name.ends_with("b.txt") {
+                continue;
+            }
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes).unwrap();
+            if name.ends_with("a.txt") {
+                bytes = b"alphX".to_vec();
+            }
+            writer
+                .start_file(name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(&bytes).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let report = verify_archive(&damaged).unwrap();
+        assert_eq!(
+            report.mismatches,
+            [
+                (
+                    "experiment/a.txt".to_string(),
+                    "SHA-256 differs from the recorded hash".to_string()
+                ),
+                (
+                    "experiment/b.txt".to_string(),
+                    "missing from the archive".to_string()
+                ),
+            ]
+        );
+
+        fs::write(&damaged, b"PK truncated").unwrap();
+        assert!(verify_archive(&damaged).is_err());
+    }
+
+    #[test]
+    fn build_and_write_archive_copies_attachment_and_records_hash() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let out = tmp.path().join("single.eln");
+        let file = tmp.path().join("data.txt");
+        fs::write(&file, b"abc").unwrap();
+
+        let attachments = vec![
+            Attachment::new(
+                file,
+                "data.txt".into(),
+                "text/plain".into(),
+                "unavailable".into(),
+                3,
+            )
+            .with_acquired_at(Some(
+                OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap(),
+            )),
+        ];
+
+        build_and_write_archive(
+            &out,
+            &[ArchiveEntry {
+                title: "Title".into(),
+                body: "Body".into(),
+                attachments: attachments.clone(),
+                extra_fields: Vec::new(),
+                extra_groups: Vec::new(),
+                performed_at: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+                genre: ArchiveGenre::Experiment,
+                keywords: Vec::new(),
+                language: None,
+                related_links: Vec::new(),
+                imported_metadata: None,
+                license: License::None,
+                summary: String::new(),
+                hide_main_text: false,
+            }],
+            BodyFormat::Html,
+            &ExportOptions::default(),
+        )
+        .unwrap();
+
+        let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        let mut content = String::new();
+        archive
+            .by_name("single/experiment/data.txt")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "abc");
+
+        let mut buf = String::new();
+        archive
+            .by_name("single/ro-crate-metadata.json")
+            .unwrap()
+            .read_to_string(&mut buf)
+            .unwrap();
+        let meta: Value = serde_json::from_str(&buf).unwrap();
+        let node = meta["@graph"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|n| n["@id"] == "./experiment/data.txt")
+            .expect("file node present");
+        // A missing recorded hash is filled in from the single-pass copy.
+        assert_eq!(
+            node["sha256"],
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(node["dateCreated"], "2023-11-14T22:13:20Z");
+    }
+
+    #[test]
+    fn file_nodes_carry_descriptions_only_when_annotated() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let out = tmp.path().join("captions.eln");
+        let mut attachments = Vec::new();
+        for (name, description) in [
+            ("gel.png", Some(" Lane 3: ladder ")),
+            ("raw.csv", None),
+            ("notes.txt", Some("  \n ")),
+        ] {
+            let path = tmp.path().join(name);
+            fs::write(&path, name).unwrap();
+            attachments.push(
+                Attachment::new(
+                    path,
+                    name.into(),
+                    "application/octet-stream".into(),
+                    "unavailable".into(),
+                    name.len() as u64,
+                )
+                .with_description(description.map(str::to_string)),
+            );
+        }
+
+        build_and_write_archive(
+            &out,
+            &[ArchiveEntry {
+                title: "Captions".into(),
+                body: String::new(),
+                attachments,
+                extra_fields: Vec::new(),
+                extra_groups: Vec::new(),
+                performed_at: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+                genre: ArchiveGenre::Experiment,
+                keywords: Vec::new(),
+                language: None,
+                related_links: Vec::new(),
+                imported_metadata: None,
+                license: License::None,
+                summary: String::new(),
+                hide_main_text: false,
+            }],
+            BodyFormat::Markdown,
+            &ExportOptions::default(),
+        )
+        .unwrap();
+
+        let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        let mut buf = String::new();
+        archive
+            .by_name("captions/ro-crate-metadata.json")
+            .unwrap()
+            .read_to_string(&mut buf)
+            .unwrap();
+        let meta: Value = serde_json::from_str(&buf).unwrap();
+        let described: Vec<(&str, &str)> = meta["@graph"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|n| n["@type"] == "File")
+            .filter_map(|n| Some((n["name"].as_str()?, n.get("description")?.as_str()?)))
+            .collect();
+        assert_eq!(described, [("gel.png", "Lane 3: ladder")]);
+    }
+
+    #[test]
+    fn build_and_write_archive_rejects_reserved_attachment_names() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("page.html");
+        fs::write(&path, "<p>x</p>").unwrap();
+        let out = tmp.path().join("reserved.eln");
+        let entry = ArchiveEntry {
+            title: "Reserved".into(),
+            body: String::new(),
+            attachments: vec![Attachment::new(
+                path,
+                "Index.HTML".into(),
+                "text/html".into(),
+                "unavailable".into(),
+                8,
+            )],
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+            performed_at: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+            genre: ArchiveGenre::Experiment,
+            keywords: Vec::new(),
+            language: None,
+            related_links: Vec::new(),
+            imported_metadata: None,
+            license: License::None,
+            summary: String::new(),
+            hide_main_text: false,
+        };
+
+        let err = build_and_write_archive(
+            &out,
+            std::slice::from_ref(&entry),
+            BodyFormat::Html,
+            &ExportOptions::default(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Index.HTML"), "{err}");
+        assert!(!out.exists());
+        assert!(entry.validate(OffsetDateTime::now_utc()).is_err());
+    }
+
+    #[test]
+    fn build_and_write_archive_keeps_attachment_order() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let out = tmp.path().join("ordered.eln");
+        let attachments: Vec<Attachment> = ["zeta.txt", "alpha.txt", "mid.txt"]
+            .iter()
+            .map(|name| {
+                let path = tmp.path().join(name);
+                fs::write(&path, name.as_bytes()).unwrap();
+                Attachment::new(
+                    path,
+                    name.to_string(),
+                    "text/plain".into(),
+                    "unavailable".into(),
+                    name.len() as u64,
+                )
+            })
+            .collect();
+
+        build_and_write_archive(
+            &out,
+            &[ArchiveEntry {
+                title: "Ordered".into(),
+                body: String::new(),
+                attachments,
+                extra_fields: Vec::new(),
+                extra_groups: Vec::new(),
+                performed_at: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+                genre: ArchiveGenre::Experiment,
+                keywords: Vec::new(),
+                language: None,
+                related_links: Vec::new(),
+                imported_metadata: None,
+                license: License::None,
+                summary: String::new(),
+                hide_main_text: false,
+            }],
+            BodyFormat::Html,
+            &ExportOptions::default(),
+        )
+        .unwrap();
+
+        let expected = [
+            "./experiment/zeta.txt",
+            "./experiment/alpha.txt",
+            "./experiment/mid.txt",
+        ];
+        let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        let written: Vec<String> = archive
+            .file_names()
+            .filter(|name| name.ends_with(".txt"))
+            .map(|name| name.replacen("ordered/", "./", 1))
+            .collect();
+        assert_eq!(written, expected);
+
+        let mut buf = String::new();
+        archive
+            .by_name("ordered/ro-crate-metadata.json")
+            .unwrap()
+            .read_to_string(&mut buf)
+            .unwrap();
+        let meta: Value = serde_json::from_str(&buf).unwrap();
+        let graph = meta["@graph"].as_array().unwrap();
+        let dataset = graph.iter().find(|n| n["@id"] == "./experiment/").unwrap();
+        let has_part: Vec<&str> = dataset["hasPart"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|part| part["@id"].as_str().unwrap())
+            .collect();
+        assert_eq!(has_part, expected);
+        let file_nodes: Vec<&str> = graph
+            .iter()
+            .filter(|n| n["@type"] == "File")
+            .map(|n| n["@id"].as_str().unwrap())
+            .collect();
+        assert_eq!(file_nodes, expected);
+    }
+
+    #[test]
+    fn build_and_write_archive_writes_preview_linking_every_attachment() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let out = tmp.path().join("preview.eln");
+        let mut attachments = Vec::new();
+        for name in ["a.txt", "plot.png"] {
+            let path = tmp.path().join(name);
+            fs::write(&path, name).unwrap();
+            attachments.push(Attachment::new(
+                path,
+                name.into(),
+                "application/octet-stream".into(),
+                "unavailable".into(),
+                name.len() as u64,
+            ));
+        }
+
+        build_and_write_archive(
+            &out,
+            &[ArchiveEntry {
+                title: "Preview title".into(),
+                body: "Some **bold** text".into(),
+                attachments: attachments.clone(),
+                extra_fields: Vec::new(),
+                extra_groups: Vec::new(),
+                performed_at: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+                genre: ArchiveGenre::Experiment,
+                keywords: vec!["kw".into()],
+                language: None,
+                related_links: Vec::new(),
+                imported_metadata: None,
+                license: License::None,
+                summary: String::new(),
+                hide_main_text: false,
+            }],
+            BodyFormat::Markdown,
+            &ExportOptions {
+                include_preview: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        let mut html = String::new();
+        archive
+            .by_name("preview/index.html")
+            .expect("preview written to archive root")
+            .read_to_string(&mut html)
+            .unwrap();
+        assert!(html.contains("<strong>bold</strong>"));
+        assert!(html.contains("Preview title"));
+        for attachment in &attachments {
+            assert!(html.contains(&format!(
+                "href=\"experiment/{}\"",
+                attachment.sanitized_name
+            )));
+        }
+
+        let mut buf = String::new();
+        archive
+            .by_name("preview/ro-crate-metadata.json")
+            .unwrap()
+            .read_to_string(&mut buf)
+            .unwrap();
+        let meta: Value = serde_json::from_str(&buf).unwrap();
+        let graph = meta["@graph"].as_array().unwrap();
+        assert_ids_unique(graph);
+        let node = graph
+            .iter()
+            .find(|n| n["@id"] == "./index.html")
+            .expect("preview node present");
+        assert_eq!(node["@type"], "File");
+        assert_eq!(node["encodingFormat"], "text/html");
+        let root = graph.iter().find(|n| n["@id"] == "./").unwrap();
+        assert!(
+            root["hasPart"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|p| p["@id"] == "./index.html")
+        );
+    }
+
+    #[test]
+    fn every_emitted_file_node_has_size_and_hash_except_the_descriptor() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let out = tmp.path().join("hashes.eln");
+        let path = tmp.path().join("a.txt");
+        fs::write(&path, "a").unwrap();
+        let attachment = Attachment::new(
+            path,
+            "a.txt".into(),
+            "text/plain".into(),
+            "unavailable".into(),
+            1,
+        );
+
+        build_and_write_archive(
+            &out,
+            &[ArchiveEntry {
+                title: "Hashes".into(),
+                body: "Body".into(),
+                attachments: vec![attachment.clone()],
+                extra_fields: Vec::new(),
+                extra_groups: Vec::new(),
+                performed_at: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+                genre: ArchiveGenre::Experiment,
+                keywords: Vec::new(),
+                language: None,
+                related_links: Vec::new(),
+                imported_metadata: None,
+                license: License::None,
+                summary: String::new(),
+                hide_main_text: false,
+            }],
+            BodyFormat::Html,
+            &ExportOptions {
+                include_preview: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        let mut buf = String::new();
+        archive
+            .by_name("hashes/ro-crate-metadata.json")
+            .unwrap()
+            .read_to_string(&mut buf)
+            .unwrap();
+        let meta: Value = serde_json::from_str(&buf).unwrap();
+        let graph = meta["@graph"].as_array().unwrap();
+        assert_ids_unique(graph);
+
+        let files: Vec<&Value> = graph.iter().filter(|n| n["@type"] == "File").collect();
+        assert_eq!(files.len(), 2, "attachment and preview");
+        for node in files {
+            let id = node["@id"].as_str().unwrap();
+            let entry = format!("hashes/{}", id.trim_start_matches("./"));
+            let mut bytes = Vec::new();
+            archive
+                .by_name(&entry)
+                .unwrap()
+                .read_to_end(&mut bytes)
+                .unwrap();
+            assert_eq!(node["contentSize"], bytes.len().to_string(), "{id}");
+            let (sha256, _) =
+                crate::utils::copy_and_hash(&mut bytes.as_slice(), &mut std::io::sink()).unwrap();
+            assert_eq!(node["sha256"], sha256, "{id}");
+        }
+
+        let descriptor = graph
+            .iter()
+            .find(|n| n["@id"] == "ro-crate-metadata.json")
+            .unwrap();
+        assert!(descriptor.get("sha256").is_none());
+        assert!(descriptor.get("contentSize").is_none());
+    }
+
+    #[test]
+    fn build_and_write_archive_writes_one_dataset_per_entry() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let out = tmp.path().join("multi.eln");
+        let path = tmp.path().join("a.txt");
+        fs::write(&path, "a").unwrap();
+        let attachment = Attachment::new(
+            path,
+            "a.txt".into(),
+            "text/plain".into(),
+            "unavailable".into(),
+            1,
+        );
+        let entry = |title: &str, attachments: Vec<Attachment>| ArchiveEntry {
+            title: title.into(),
+            body: "Body".into(),
+            attachments,
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+            performed_at: OffsetDateTime::UNIX_EPOCH,
+            genre: ArchiveGenre::Experiment,
+            keywords: Vec::new(),
+            language: None,
+            related_links: Vec::new(),
+            imported_metadata: None,
+            license: License::None,
+            summary: String::new(),
+            hide_main_text: false,
+        };
+
+        // The same file name in both entries must not collide.
+        build_and_write_archive(
+            &out,
+            &[
+                entry("First", vec![attachment.clone()]),
+                entry("Second", vec![attachment]),
+            ],
+            BodyFormat::Html,
+            &ExportOptions::default(),
+        )
+        .unwrap();
+
+        let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        assert!(archive.by_name("multi/experiment-1/a.txt").is_ok());
+        assert!(archive.by_name("multi/experiment-2/a.txt").is_ok());
+        assert!(archive.by_name("multi/experiment/").is_err());
+
+        let mut buf = String::new();
+        archive
+            .by_name("multi/ro-crate-metadata.json")
+            .unwrap()
+            .read_to_string(&mut buf)
+            .unwrap();
+        let meta: Value = serde_json::from_str(&buf).unwrap();
+        let graph = meta["@graph"].as_array().unwrap();
+        assert_ids_unique(graph);
+        let root = graph.iter().find(|n| n["@id"] == "./").unwrap();
+        assert_eq!(
+            root["hasPart"],
+            serde_json::json!([{ "@id": "./experiment-1/" }, { "@id": "./experiment-2/" }])
+        );
+        for (id, title) in [("./experiment-1/", "First"), ("./experiment-2/", "Second")] {
+            let dataset = graph.iter().find(|n| n["@id"] == id).unwrap();
+            assert_eq!(dataset["@type"], "Dataset");
+            assert_eq!(dataset["name"], title);
+            assert_eq!(
+                dataset["hasPart"],
+                serde_json::json!([{ "@id": format!("{id}a.txt") }])
+            );
+        }
+    }
+
+    #[test]
+    fn build_and_write_archive_credits_authors_as_persons() {
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let out = tmp.path().join("authors.eln");
+        let export = ExportOptions {
+            authors: vec![
+                Author {
+                    given_name: "Josiah".into(),
+                    family_name: "Carberry".into(),
+                    orcid: "0000-0002-1825-0097".into(),
+                    affiliation: "Brown University".into(),
+                    ..Default::default()
+                },
+                Author {
+                    family_name: "Curie".into(),
+                    email: "curie@example.org".into(),
+                    ..Default::default()
+                },
+                // Listed twice, with details spread over both entries.
+                Author {
+                    given_name: "Josiah".into(),
+                    family_name: "Carberry".into(),
+                    orcid: "0000-0002-1825-0097".into(),
+                    email: "josiah@example.org".into(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        build_and_write_archive(
+            &out,
+            &[ArchiveEntry {
+                title: "Credited".into(),
+                body: "Body".into(),
+                attachments: Vec::new(),
+                extra_fields: Vec::new(),
+                extra_groups: Vec::new(),
+                performed_at: OffsetDateTime::UNIX_EPOCH,
+                genre: ArchiveGenre::Experiment,
+                keywords: Vec::new(),
+                language: None,
+                related_links: Vec::new(),
+                imported_metadata: None,
+                license: License::None,
+                summary: String::new(),
+                hide_main_text: false,
+            }],
+            BodyFormat::Html,
+            &export,
+        )
+        .unwrap();
+
+        let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        let mut buf = String::new();
+        archive
+            .by_name("authors/ro-crate-metadata.json")
+            .unwrap()
+            .read_to_string(&mut buf)
+            .unwrap();
+        let meta: Value = serde_json::from_str(&buf).unwrap();
+        let graph = meta["@graph"].as_array().unwrap();
+        let orcid = "https://orcid.org/0000-0002-1825-0097";
+
+        let dataset = graph.iter().find(|n| n["@id"] == "./experiment/").unwrap();
+        assert_eq!(
+            dataset["author"],
+            serde_json::json!([{ "@id": orcid }, { "@id": "#author-2" }])
+        );
+        assert_ids_unique(graph);
+        let person = graph.iter().find(|n| n["@id"] == orcid).unwrap();
+        assert_eq!(person["@type"], "Person");
+        assert_eq!(person["name"], "Josiah Carberry");
+        assert_eq!(person["affiliation"], "Brown University");
+        assert_eq!(person["email"], "josiah@example.org");
+        let second = graph.iter().find(|n| n["@id"] == "#author-2").unwrap();
+        assert_eq!(second["email"], "curie@example.org");
+        assert!(second.get("givenName").is_none());
+
+        // The tool is only the publisher of the metadata, never an author.
+        let descriptor = graph
+            .iter()
+            .find(|n| n["@id"] == "ro-crate-metadata.json")
+            .unwrap();
+        let publisher = descriptor["sdPublisher"]["@id"].as_str().unwrap();
+        assert_eq!(
+            graph.iter().find(|n| n["@id"] == publisher).unwrap()["@type"],
+            "Organization"
+        );
+    }
+
+    #[test]
+    fn entry_language_is_detected_or_overridden() {
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let german = "Die Proben wurden über Nacht inkubiert und anschließend zehn Minuten \
+                      zentrifugiert, bevor der Überstand verworfen wurde.";
+        let entry = |body: &str, language: Option<&str>| ArchiveEntry {
+            title: "Sprache".into(),
+            body: body.into(),
+            attachments: Vec::new(),
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+            performed_at: OffsetDateTime::UNIX_EPOCH,
+            genre: ArchiveGenre::Experiment,
+            keywords: Vec::new(),
+            language: language.map(str::to_string),
+            related_links: Vec::new(),
+            imported_metadata: None,
+            license: License::None,
+            summary: String::new(),
+            hide_main_text: false,
+        };
+        let out = tmp.path().join("lang.eln");
+        build_and_write_archive(
+            &out,
+            &[
+                entry(german, None),
+                entry(german, Some("en-GB")),
+                entry("Gel 2", None),
+            ],
+            BodyFormat::Markdown,
+            &ExportOptions {
+                include_preview: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        let mut buf = String::new();
+        archive
+            .by_name("lang/ro-crate-metadata.json")
+            .unwrap()
+            .read_to_string(&mut buf)
+            .unwrap();
+        let meta: Value = serde_json::from_str(&buf).unwrap();
+        let languages: Vec<Value> = (1..=3)
+            .map(|n| {
+                meta["@graph"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .find(|node| node["@id"] == format!("./experiment-{n}/"))
+                    .unwrap()["inLanguage"]
+                    .clone()
+            })
+            .collect();
+        assert_eq!(
+            languages,
+            [Value::from("de"), Value::from("en-GB"), Value::Null]
+        );
+
+        let mut preview = String::new();
+        archive
+            .by_name("lang/index.html")
+            .unwrap()
+            .read_to_string(&mut preview)
+            .unwrap();
+        assert!(preview.contains("<html lang=\"de\">"));
+        assert!(preview.contains("<article lang=\"en-GB\">"));
+        assert!(preview.contains("<article>"));
+    }
+
+    #[test]
+    fn related_links_become_creative_work_nodes() {
+        use crate::logic::eln_import::open_archive;
+        use crate::models::related_link::{RelatedLink, Relation};
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let protocol = "https://protocols.example.org/pcr";
+        let project = "https://elab.example.org/items.php?mode=view&id=3";
+        let link = |label: &str, url: &str, relation| RelatedLink {
+            label: label.into(),
+            url: url.into(),
+            relation,
+        };
+        let entry = |title: &str, related_links| ArchiveEntry {
+            title: title.into(),
+            body: String::new(),
+            attachments: Vec::new(),
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+            performed_at: OffsetDateTime::UNIX_EPOCH,
+            genre: ArchiveGenre::Experiment,
+            keywords: Vec::new(),
+            language: None,
+            related_links,
+            imported_metadata: None,
+            license: License::None,
+            summary: String::new(),
+            hide_main_text: false,
+        };
+        let out = tmp.path().join("links.eln");
+        build_and_write_archive(
+            &out,
+            &[
+                entry(
+                    "Run 1",
+                    vec![
+                        link("PCR protocol", protocol, Relation::DerivedFrom),
+                        link("", project, Relation::IsPartOf),
+                    ],
+                ),
+                entry("Run 2", vec![link("", project, Relation::IsPartOf)]),
+            ],
+            BodyFormat::Markdown,
+            &ExportOptions::default(),
+        )
+        .unwrap();
+
+        let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        let mut buf = String::new();
+        archive
+            .by_name("links/ro-crate-metadata.json")
+            .unwrap()
+            .read_to_string(&mut buf)
+            .unwrap();
+        let meta: Value = serde_json::from_str(&buf).unwrap();
+        let graph = meta["@graph"].as_array().unwrap();
+
+        let first = graph
+            .iter()
+            .find(|n| n["@id"] == "./experiment-1/")
+            .unwrap();
+        assert_eq!(first["isBasedOn"], serde_json::json!([{ "@id": protocol }]));
+        assert_eq!(first["isPartOf"], serde_json::json!([{ "@id": project }]));
+        assert!(first.get("citation").is_none());
+
+        // Both entries link the project, which is described once.
+        let project_nodes: Vec<&Value> = graph.iter().filter(|n| n["@id"] == project).collect();
+        assert_eq!(project_nodes.len(), 1);
+        assert_eq!(project_nodes[0]["@type"], "CreativeWork");
+        assert_eq!(project_nodes[0]["name"], project);
+        let protocol_node = graph.iter().find(|n| n["@id"] == protocol).unwrap();
+        assert_eq!(protocol_node["name"], "PCR protocol");
+        assert_eq!(protocol_node["url"], protocol);
+
+        let opened = open_archive(&out, &tmp.path().join("extracted")).unwrap();
+        assert_eq!(
+            opened.related_links,
+            vec![
+                link("", project, Relation::IsPartOf),
+                link("PCR protocol", protocol, Relation::DerivedFrom),
+            ]
+        );
+    }
+
+    #[test]
+    fn licenses_are_declared_on_datasets_and_root() {
+        use crate::logic::eln_import::open_archive;
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let entry = |title: &str, license| ArchiveEntry {
+            title: title.into(),
+            body: String::new(),
+            attachments: Vec::new(),
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+            performed_at: OffsetDateTime::UNIX_EPOCH,
+            genre: ArchiveGenre::Experiment,
+            keywords: Vec::new(),
+            language: None,
+            related_links: Vec::new(),
+            imported_metadata: None,
+            license,
+            summary: String::new(),
+            hide_main_text: false,
+        };
+        let graph_of = |name: &str, entries: &[ArchiveEntry]| {
+            let out = tmp.path().join(format!("{name}.eln"));
+            build_and_write_archive(
+                &out,
+                entries,
+                BodyFormat::Markdown,
+                &ExportOptions::default(),
+            )
+            .unwrap();
+            let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+            let mut buf = String::new();
+            archive
+                .by_name(&format!("{name}/ro-crate-metadata.json"))
+                .unwrap()
+                .read_to_string(&mut buf)
+                .unwrap();
+            let meta: Value = serde_json::from_str(&buf).unwrap();
+            (out, meta["@graph"].as_array().unwrap().clone())
+        };
+        let node = |graph: &[Value], id: &str| graph.iter().find(|n| n["@id"] == id).cloned();
+        let cc_by = "https://spdx.org/licenses/CC-BY-4.0";
+
+        let (out, graph) = graph_of(
+            "licensed",
+            &[
+                entry("Run 1", License::CcBy4),
+                entry("Run 2", License::CcBy4),
+            ],
+        );
+        assert_ids_unique(&graph);
+        let reference = serde_json::json!({ "@id": cc_by });
+        assert_eq!(node(&graph, "./").unwrap()["license"], reference);
+        assert_eq!(
+            node(&graph, "./experiment-2/").unwrap()["license"],
+            reference
+        );
+        let license = node(&graph, cc_by).unwrap();
+        assert_eq!(license["@type"], "CreativeWork");
+        assert_eq!(
+            license["name"],
+            "Creative Commons Attribution 4.0 International"
+        );
+        assert_eq!(license["identifier"], "CC-BY-4.0");
+        let opened = open_archive(&out, &tmp.path().join("extracted")).unwrap();
+        assert_eq!(opened.license, License::CcBy4);
+
+        // Entries with different licenses leave the crate as a whole without one.
+        let terms = "https://example.org/terms";
+        let (_, graph) = graph_of(
+            "mixed",
+            &[
+                entry("Run 1", License::Url(terms.into())),
+                entry("Run 2", License::None),
+            ],
+        );
+        assert!(node(&graph, "./").unwrap().get("license").is_none());
+        assert_eq!(
+            node(&graph, "./experiment-1/").unwrap()["license"],
+            serde_json::json!({ "@id": terms })
+        );
+        assert!(
+            node(&graph, "./experiment-2/")
+                .unwrap()
+                .get("license")
+                .is_none()
+        );
+        assert_eq!(node(&graph, terms).unwrap()["name"], terms);
+
+        let (_, graph) = graph_of("unlicensed", &[entry("Run", License::None)]);
+        assert!(graph.iter().all(|n| n.get("license").is_none()));
+        assert!(
+            graph
+                .iter()
+                .all(|n| n["@type"] != "CreativeWork" || n["@id"] == "ro-crate-metadata.json")
+        );
+    }
+
+    #[test]
+    fn summaries_are_written_as_descriptions_and_read_back() {
+        use crate::logic::eln_import::open_archive;
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let entry = |title: &str, summary: &str| ArchiveEntry {
+            title: title.into(),
+            body: String::new(),
+            attachments: Vec::new(),
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+            performed_at: OffsetDateTime::UNIX_EPOCH,
+            genre: ArchiveGenre::Experiment,
+            keywords: Vec::new(),
+            language: None,
+            related_links: Vec::new(),
+            imported_metadata: None,
+            license: License::None,
+            summary: summary.into(),
+            hide_main_text: false,
+        };
+        let write = |name: &str, entries: &[ArchiveEntry]| {
+            let out = tmp.path().join(format!("{name}.eln"));
+            build_and_write_archive(
+                &out,
+                entries,
+                BodyFormat::Markdown,
+                &ExportOptions::default(),
+            )
+            .unwrap();
+            let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+            let mut buf = String::new();
+            archive
+                .by_name(&format!("{name}/ro-crate-metadata.json"))
+                .unwrap()
+                .read_to_string(&mut buf)
+                .unwrap();
+            let meta: Value = serde_json::from_str(&buf).unwrap();
+            (out, meta["@graph"].as_array().unwrap().clone())
+        };
+        let node = |graph: &[Value], id: &str| graph.iter().find(|n| n["@id"] == id).cloned();
+        let summary = "Viability of HeLa cells after 72 h.";
+
+        let (out, graph) = write("single", &[entry("Run", summary)]);
+        assert_eq!(
+            node(&graph, "./experiment/").unwrap()["description"],
+            summary
+        );
+        assert_eq!(node(&graph, "./").unwrap()["description"], summary);
+        let opened = open_archive(&out, &tmp.path().join("single-extracted")).unwrap();
+        assert_eq!(opened.summary, summary);
+
+        // With several entries the root is not described by any single one of them.
+        let (out, graph) = write("multi", &[entry("Run 1", ""), entry("Run 2", summary)]);
+        assert!(node(&graph, "./").unwrap().get("description").is_none());
+        assert!(
+            node(&graph, "./experiment-1/")
+                .unwrap()
+                .get("description")
+                .is_none()
+        );
+        assert_eq!(
+            node(&graph, "./experiment-2/").unwrap()["description"],
+            summary
+        );
+        let opened = open_archive(&out, &tmp.path().join("multi-extracted")).unwrap();
+        assert_eq!(opened.summary, "");
+        assert_eq!(opened.more_entries[0].summary, summary);
+    }
+
+    #[test]
+    fn summaries_longer_than_the_limit_are_rejected() {
+        let mut entry = ArchiveEntry {
+            title: "Run".into(),
+            body: String::new(),
+            attachments: Vec::new(),
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+            performed_at: OffsetDateTime::UNIX_EPOCH,
+            genre: ArchiveGenre::Experiment,
+            keywords: Vec::new(),
+            language: None,
+            related_links: Vec::new(),
+            imported_metadata: None,
+            license: License::None,
+            summary: "µ".repeat(SUMMARY_MAX_CHARS),
+            hide_main_text: false,
+        };
+        // The limit counts characters, not bytes.
+        entry.validate(OffsetDateTime::now_utc()).unwrap();
+
+        entry.summary.push('x');
+        let err = entry.validate(OffsetDateTime::now_utc()).unwrap_err();
+        assert!(err.to_string().contains("501 characters"), "{err}");
+    }
+
+    #[test]
+    fn configured_publisher_becomes_the_sd_publisher_organization() {
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let graph_for = |name: &str, publisher: PublisherInfo| {
+            let out = tmp.path().join(format!("{name}.eln"));
+            build_and_write_archive(
+                &out,
+                &[ArchiveEntry {
+                    title: "Published".into(),
+                    body: "Body".into(),
+                    attachments: Vec::new(),
+                    extra_fields: Vec::new(),
+                    extra_groups: Vec::new(),
+                    performed_at: OffsetDateTime::UNIX_EPOCH,
+                    genre: ArchiveGenre::Experiment,
+                    keywords: Vec::new(),
+                    language: None,
+                    related_links: Vec::new(),
+                    imported_metadata: None,
+                    license: License::None,
+                    summary: String::new(),
+                    hide_main_text: false,
+                }],
+                BodyFormat::Markdown,
+                &ExportOptions {
+                    publisher,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+            let mut buf = String::new();
+            archive
+                .by_name(&format!("{name}/ro-crate-metadata.json"))
+                .unwrap()
+                .read_to_string(&mut buf)
+                .unwrap();
+            let meta: Value = serde_json::from_str(&buf).unwrap();
+            let graph = meta["@graph"].as_array().unwrap().clone();
+            let descriptor = graph
+                .iter()
+                .find(|n| n["@id"] == "ro-crate-metadata.json")
+                .unwrap();
+            let id = descriptor["sdPublisher"]["@id"].clone();
+            graph.iter().find(|n| n["@id"] == id).unwrap().clone()
+        };
+
+        let ror = "https://ror.org/05a28rw58";
+        let institute = graph_for(
+            "institute",
+            PublisherInfo {
+                name: "ETH Zurich".into(),
+                url: "https://ethz.ch".into(),
+                ror: ror.into(),
+            },
+        );
+        assert_eq!(
+            institute,
+            serde_json::json!({
+                "@id": ror,
+                "@type": "Organization",
+                "name": "ETH Zurich",
+                "url": "https://ethz.ch",
+                "identifier": ror,
+            })
+        );
+
+        let without_url = graph_for(
+            "lab",
+            PublisherInfo {
+                name: "Miller lab".into(),
+                url: String::new(),
+                ror: String::new(),
+            },
+        );
+        assert_eq!(without_url["name"], "Miller lab");
+        assert!(without_url.get("url").is_none());
+        assert!(without_url.get("identifier").is_none());
+
+        let unnamed = graph_for(
+            "unnamed",
+            PublisherInfo {
+                name: " ".into(),
+                url: "https://example.org".into(),
+                ror: String::new(),
+            },
+        );
+        assert_eq!(unnamed["name"], "elnPack");
+        assert_eq!(unnamed["url"], "https://github.com/cbm343e/elnPack");
+    }
+
+    #[test]
+    fn build_and_write_archive_omits_author_without_authors() {
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let out = tmp.path().join("anonymous.eln");
+        build_and_write_archive(
+            &out,
+            &[ArchiveEntry {
+                title: "Anonymous".into(),
+                body: "Body".into(),
+                attachments: Vec::new(),
+                extra_fields: Vec::new(),
+                extra_groups: Vec::new(),
+                performed_at: OffsetDateTime::UNIX_EPOCH,
+                genre: ArchiveGenre::Experiment,
+                keywords: Vec::new(),
+                language: None,
+                related_links: Vec::new(),
+                imported_metadata: None,
+                license: License::None,
+                summary: String::new(),
+                hide_main_text: false,
+            }],
+            BodyFormat::Html,
+            &ExportOptions::default(),
+        )
+        .unwrap();
+
+        let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        let mut buf = String::new();
+        archive
+            .by_name("anonymous/ro-crate-metadata.json")
+            .unwrap()
+            .read_to_string(&mut buf)
+            .unwrap();
+        let meta: Value = serde_json::from_str(&buf).unwrap();
+        let graph = meta["@graph"].as_array().unwrap();
+        let dataset = graph.iter().find(|n| n["@id"] == "./experiment/").unwrap();
+        assert!(dataset.get("author").is_none());
+        assert!(graph.iter().all(|n| n["@type"] != "Person"));
+    }
+
+    #[test]
+    fn build_and_write_archive_clamps_long_root_folder_uniquely() {
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let prefix = "a".repeat(200);
+        let roots: Vec<String> = ["1", "2"]
+            .iter()
+            .map(|tail| {
+                let out = tmp.path().join(format!("{prefix}{tail}.eln"));
+                build_and_write_archive(
+                    &out,
+                    &[ArchiveEntry {
+                        title: "Long".into(),
+                        body: "Body".into(),
+                        attachments: Vec::new(),
+                        extra_fields: Vec::new(),
+                        extra_groups: Vec::new(),
+                        performed_at: OffsetDateTime::UNIX_EPOCH,
+                        genre: ArchiveGenre::Experiment,
+                        keywords: Vec::new(),
+                        language: None,
+                        related_links: Vec::new(),
+                        imported_metadata: None,
+                        license: License::None,
+                        summary: String::new(),
+                        hide_main_text: false,
+                    }],
+                    BodyFormat::Html,
+                    &ExportOptions::default(),
+                )
+                .unwrap();
+                let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+                let root = archive.by_index(0).unwrap().name().to_string();
+                let root = root.trim_end_matches('/').to_string();
+                assert!(
+                    archive
+                        .by_name(&format!("{root}/ro-crate-metadata.json"))
+                        .is_ok()
+                );
+                root
+            })
+            .collect();
+
+        assert!(roots.iter().all(|root| root.len() == MAX_COMPONENT_LEN));
+        assert!(roots[0].starts_with("aaaa"));
+        assert_ne!(roots[0], roots[1]);
+    }
+
+    #[test]
+    fn crate_versions_differ_in_context_format_version_and_variables() {
+        use tempfile::TempDir;
+
+        /// Metadata of an archive written for `version`, with random ids masked.
+        fn metadata(dir: &std::path::Path, version: RoCrateVersion) -> Value {
+            let out = archive(dir, version);
+            let root = out.file_stem().unwrap().to_string_lossy().into_owned();
+            let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+            let mut buf = String::new();
+            archive
+                .by_name(&format!("{root}/ro-crate-metadata.json"))
+                .unwrap()
+                .read_to_string(&mut buf)
+                .unwrap();
+            let masked = mask_uuids(&buf);
+            serde_json::from_str(&masked).unwrap()
+        }
+
+        /// Write an archive with one extra field for `version` and return its path.
+        fn archive(dir: &std::path::Path, version: RoCrateVersion) -> std::path::PathBuf {
+            let out = dir.join(format!("v{}.eln", version.label().replace(['.', ' '], "")));
+            let path = dir.join("data.csv");
+            std::fs::write(&path, "a,b").unwrap();
+            let field = ExtraField {
+                unit: Some("C".into()),
+                units: vec!["C".into()],
+                ..field("Temp", ExtraFieldKind::Number, "37")
+            };
+            build_and_write_archive(
+                &out,
+                &[ArchiveEntry {
+                    title: "Snapshot".into(),
+                    body: "Body".into(),
+                    attachments: vec![Attachment::new(
+                        path,
+                        "data.csv".into(),
+                        "text/csv".into(),
+                        "unavailable".into(),
+                        3,
+                    )],
+                    extra_fields: vec![field],
+                    extra_groups: Vec::new(),
+                    performed_at: OffsetDateTime::UNIX_EPOCH,
+                    genre: ArchiveGenre::Experiment,
+                    keywords: vec!["k".into()],
+                    language: None,
+                    related_links: Vec::new(),
+                    imported_metadata: None,
+                    license: License::None,
+                    summary: String::new(),
+                    hide_main_text: false,
+                }],
+                BodyFormat::Html,
+                &ExportOptions {
+                    include_preview: true,
+                    authors: vec![Author {
+                        family_name: "Curie".into(),
+                        ..Default::default()
+                    }],
+                    crate_version: version,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            out
+        }
+
+        /// Replace the random UUIDs of `pv://` and field group ids with a fixed placeholder.
+        fn mask_uuids(json: &str) -> String {
+            ["pv://", "#field-group-"]
+                .into_iter()
+                .fold(json.to_string(), |json, prefix| {
+                    let mut out = String::with_capacity(json.len());
+                    let mut rest = json.as_str();
+                    while let Some(pos) = rest.find(prefix) {
+                        out.push_str(&rest[..pos + prefix.len()]);
+                        rest = &rest[pos + prefix.len() + 36..];
+                        out.push_str("uuid");
+                    }
+                    out.push_str(rest);
+                    out
+                })
+        }
+
+        let tmp = TempDir::new().unwrap();
+        let mut v11 = metadata(tmp.path(), RoCrateVersion::V1_1);
+        let mut v12 = metadata(tmp.path(), RoCrateVersion::V1_2);
+
+        assert_eq!(v11["@context"], "https://w3id.org/ro/crate/1.1/context");
+        assert_eq!(v12["@context"], "https://w3id.org/ro/crate/1.2/context");
+        let descriptor = |meta: &Value| {
+            meta["@graph"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .position(|n| n["@id"] == "ro-crate-metadata.json")
+                .unwrap()
+        };
+        let (d11, d12) = (descriptor(&v11), descriptor(&v12));
+        assert_eq!(
+            v11["@graph"][d11]["conformsTo"]["@id"],
+            "https://w3id.org/ro/crate/1.1"
+        );
+        assert_eq!(
+            v12["@graph"][d12]["conformsTo"]["@id"],
+            "https://w3id.org/ro/crate/1.2"
+        );
+
+        let node = |meta: &Value, id: &str| {
+            meta["@graph"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .position(|n| n["@id"] == id)
+                .unwrap()
+        };
+        let entry = |meta: &Value| {
+            meta["@graph"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .position(|n| n["@type"] == "Dataset" && n["@id"] != "./")
+                .unwrap()
+        };
+        let (r11, r12) = (node(&v11, "./"), node(&v12, "./"));
+        assert_eq!(v11["@graph"][r11]["version"], 101);
+        assert_eq!(v12["@graph"][r12]["version"], 103);
+
+        // 1.2 links the properties by id; 1.1 embeds them without one.
+        let (e11, e12) = (entry(&v11), entry(&v12));
+        let linked = v12["@graph"][e12]["variableMeasured"].clone();
+        let inline = v11["@graph"][e11]["variableMeasured"].clone();
+        assert_eq!(
+            linked,
+            serde_json::json!([{ "@id": "pv://uuid" }, { "@id": "pv://uuid" }])
+        );
+        let mut resolved: Vec<Value> = v12["@graph"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|n| n["@type"] == "PropertyValue")
+            .cloned()
+            .collect();
+        for property in &mut resolved {
+            property.as_object_mut().unwrap().remove("@id");
+        }
+        assert_eq!(inline, Value::Array(resolved));
+        assert!(
+            v11["@graph"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .all(|n| n["@type"] != "PropertyValue"),
+            "no dangling property nodes"
+        );
+
+        // Apart from these keys, the graphs are identical.
+        let strip = |meta: &mut Value| {
+            let (index, root, entry) = (descriptor(meta), node(meta, "./"), entry(meta));
+            meta["@context"] = Value::Null;
+            meta["@graph"][index]["conformsTo"] = Value::Null;
+            meta["@graph"][root]["version"] = Value::Null;
+            meta["@graph"][entry]["variableMeasured"] = Value::Null;
+            let graph = meta["@graph"].as_array_mut().unwrap();
+            graph.retain(|n| n["@type"] != "PropertyValue");
+        };
+        strip(&mut v11);
+        strip(&mut v12);
+        assert_eq!(v11, v12);
+
+        // Both profiles open again with their extra fields.
+        for version in RoCrateVersion::ALL {
+            let out = archive(tmp.path(), version);
+            assert!(verify_archive(&out).unwrap().is_ok());
+            let opened =
+                crate::logic::eln_import::open_archive(&out, &tmp.path().join("opened")).unwrap();
+            assert_eq!(opened.extra_fields.len(), 1, "{}", version.label());
+            assert_eq!(opened.extra_fields[0].value, "37");
+        }
+    }
+
+    #[test]
+    fn build_and_write_archive_sets_summary_comment() {
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let out = tmp.path().join("comment.eln");
+
+        build_and_write_archive(
+            &out,
+            &[ArchiveEntry {
+                title: "Gel run\nsecond line".into(),
+                body: "Body".into(),
+                attachments: Vec::new(),
+                extra_fields: Vec::new(),
+                extra_groups: Vec::new(),
+                performed_at: OffsetDateTime::from_unix_timestamp(1_741_953_600).unwrap(),
+                genre: ArchiveGenre::Experiment,
+                keywords: vec!["a".into(), "b".into(), "c".into()],
+                language: None,
+                related_links: Vec::new(),
+                imported_metadata: None,
+                license: License::None,
+                summary: String::new(),
+                hide_main_text: false,
+            }],
+            BodyFormat::Markdown,
+            &ExportOptions {
+                write_zip_comment: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+        assert_eq!(
+            archive.comment(),
+            "ELNPack: Gel run second line — 2025-03-14 — keywords: a, b, c".as_bytes()
+        );
+    }
+
+    #[test]
+    fn archive_comment_is_truncated() {
+        let comment = archive_comment(&"x".repeat(500), OffsetDateTime::UNIX_EPOCH, &[]);
+        assert_eq!(comment.chars().count(), 200);
+        assert!(comment.ends_with('…'));
+    }
+
     #[test]
     fn archive_genre_serializes_to_expected_str() {
         assert_eq!(ArchiveGenre::Resource.as_str(), "resource");
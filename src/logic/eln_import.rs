@@ -0,0 +1,859 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Reading existing `.eln` archives back into editable entries.
+//!
+//! Responsibilities:
+//! - Locate and parse `ro-crate-metadata.json` inside the ZIP.
+//! - Map each entry dataset's metadata back to title, body, keywords, genre, date, license,
+//!   summary and extra fields.
+//! - Extract attached files into a working directory, verifying recorded SHA-256 hashes.
+//!
+//! Archives written by ELNPack round-trip; eLabFTW exports are read on a best-effort basis.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{Read, Seek};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+use serde_json::Value;
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use zip::ZipArchive;
+
+use crate::logic::eln::{ArchiveGenre, BODY_MARKDOWN_FILE, BodyFormat};
+use crate::models::attachment::Attachment;
+use crate::models::extra_fields::{
+    ExtraField, ExtraFieldGroup, is_web_url, parse_elabftw_extra_fields,
+};
+use crate::models::license::License;
+use crate::models::related_link::{RelatedLink, Relation};
+use crate::utils::{copy_and_hash, sanitize_component};
+
+/// File name of the RO-Crate metadata descriptor.
+const METADATA_FILE_NAME: &str = "ro-crate-metadata.json";
+
+/// Entry content recovered from an archive.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpenedArchive {
+    /// Entry title.
+    pub title: String,
+    /// Body text as stored (markdown source or rendered HTML); the markdown copy when the
+    /// archive has one.
+    pub body: String,
+    /// Format of `body`, derived from the dataset `encodingFormat` and a markdown copy.
+    pub body_format: BodyFormat,
+    /// Archive genre.
+    pub genre: ArchiveGenre,
+    /// Keywords in archive order.
+    pub keywords: Vec<String>,
+    /// Performed-at timestamp, when the archive records a parseable one.
+    pub performed_at: Option<OffsetDateTime>,
+    /// Extra fields reconstructed from the `elabftw_metadata` property.
+    pub extra_fields: Vec<ExtraField>,
+    /// Extra field groups reconstructed from the `elabftw_metadata` property.
+    pub extra_groups: Vec<ExtraFieldGroup>,
+    /// Attachments extracted into the working directory.
+    pub attachments: Vec<Attachment>,
+    /// Links to related works, from the dataset's relation properties.
+    pub related_links: Vec<RelatedLink>,
+    /// License of the dataset, or of the crate root when the dataset declares none.
+    pub license: License,
+    /// Summary from the dataset's `description`; empty when it has none.
+    pub summary: String,
+    /// The `elabftw_metadata` property turned off `display_main_text`.
+    pub hide_main_text: bool,
+    /// Further entries of a multi-entry archive, in archive order; each has none of its own.
+    pub more_entries: Vec<OpenedArchive>,
+}
+
+/// Open the `.eln` archive at `path`, extracting attachments into `extract_dir`.
+///
+/// `extract_dir` is created if missing. Every dataset listed by the crate root becomes an
+/// entry; with several entries, each one's attachments go to `entry-1/`, `entry-2/`, … below
+/// `extract_dir`. Extracted files are named from their sanitized archive names, so entry paths inside
+/// the ZIP never decide where data is written.
+///
+/// # Errors
+///
+/// Returns an error when the file is not a ZIP, lacks RO-Crate metadata, references missing
+/// files, or when an attachment's contents do not match its recorded SHA-256.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let opened = open_archive(Path::new("entry.eln"), Path::new("/tmp/elnpack-open"))?;
+/// println!("{} ({} attachments)", opened.title, opened.attachments.len());
+/// ```
+pub fn open_archive(path: &Path, extract_dir: &Path) -> Result<OpenedArchive> {
+    let file = File::open(path).with_context(|| format!("Failed to open archive {:?}", path))?;
+    let mut zip = ZipArchive::new(file).context("Not a valid ELN archive (unreadable ZIP)")?;
+    let (root_prefix, metadata) = read_metadata(&mut zip)?;
+    let graph = metadata
+        .get("@graph")
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow!("RO-Crate metadata has no @graph"))?;
+
+    let datasets = find_entry_datasets(graph);
+    if datasets.is_empty() {
+        anyhow::bail!("Archive does not contain an ELN entry dataset");
+    }
+
+    let mut entries = Vec::with_capacity(datasets.len());
+    for (index, dataset) in datasets.iter().enumerate() {
+        let entry_dir = if datasets.len() == 1 {
+            extract_dir.to_path_buf()
+        } else {
+            extract_dir.join(format!("entry-{}", index + 1))
+        };
+        entries.push(read_entry(
+            &mut zip,
+            &root_prefix,
+            graph,
+            dataset,
+            &entry_dir,
+        )?);
+    }
+    let mut opened = entries.remove(0);
+    opened.more_entries = entries;
+    Ok(opened)
+}
+
+/// Map one entry dataset back to editable content, extracting its files into `extract_dir`.
+fn read_entry<R: Read + Seek>(
+    zip: &mut ZipArchive<R>,
+    root_prefix: &str,
+    graph: &[Value],
+    dataset: &Value,
+    extract_dir: &Path,
+) -> Result<OpenedArchive> {
+    let title = dataset
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let mut body = dataset
+        .get("text")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let mut body_format = match dataset.get("encodingFormat").and_then(Value::as_str) {
+        Some(format) if format.eq_ignore_ascii_case("text/markdown") => BodyFormat::Markdown,
+        _ => BodyFormat::Html,
+    };
+    let genre = match dataset.get("genre").and_then(Value::as_str) {
+        Some(genre) if genre.eq_ignore_ascii_case("resource") => ArchiveGenre::Resource,
+        _ => ArchiveGenre::Experiment,
+    };
+    let performed_at = ["dateCreated", "dateModified"]
+        .iter()
+        .filter_map(|key| dataset.get(*key).and_then(Value::as_str))
+        .find_map(|raw| OffsetDateTime::parse(raw, &Rfc3339).ok());
+
+    let (extra_fields, extra_groups, hide_main_text) = match find_elabftw_metadata(graph, dataset) {
+        Some(json) => {
+            let import = parse_elabftw_extra_fields(json)?;
+            (import.fields, import.groups, import.hide_main_text)
+        }
+        None => (Vec::new(), Vec::new(), false),
+    };
+
+    fs::create_dir_all(extract_dir)
+        .with_context(|| format!("Failed to create directory {:?}", extract_dir))?;
+    let dataset_id = dataset.get("@id").and_then(Value::as_str).unwrap_or("./");
+    let mut used_names = HashMap::new();
+    let mut attachments = Vec::new();
+    let body_markdown_id = format!("{dataset_id}{BODY_MARKDOWN_FILE}");
+    for id in linked_ids(dataset.get("hasPart")) {
+        let Some(node) = find_node(graph, id) else {
+            continue;
+        };
+        if !has_type(node, "File") {
+            continue;
+        }
+        if id == body_markdown_id
+            && body_format == BodyFormat::Html
+            && node.get("encodingFormat").and_then(Value::as_str) == Some("text/markdown")
+        {
+            body = read_body_markdown(zip, root_prefix, node, id)?;
+            body_format = BodyFormat::Both;
+            continue;
+        }
+        attachments.push(extract_attachment(
+            zip,
+            root_prefix,
+            node,
+            id,
+            &attachment_folder(dataset_id, id),
+            extract_dir,
+            &mut used_names,
+        )?);
+    }
+
+    Ok(OpenedArchive {
+        title,
+        body,
+        body_format,
+        genre,
+        keywords: parse_keywords(dataset.get("keywords")),
+        performed_at,
+        extra_fields,
+        extra_groups,
+        attachments,
+        related_links: parse_related_links(graph, dataset),
+        license: parse_license(graph, dataset),
+        summary: dataset
+            .get("description")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+        hide_main_text,
+        more_entries: Vec::new(),
+    })
+}
+
+/// Read the markdown copy of the main text written next to the HTML body, checking its
+/// recorded SHA-256.
+fn read_body_markdown<R: Read + Seek>(
+    zip: &mut ZipArchive<R>,
+    root_prefix: &str,
+    node: &Value,
+    id: &str,
+) -> Result<String> {
+    let entry_name = format!("{}{}", root_prefix, id.trim_start_matches("./"));
+    let mut entry = zip
+        .by_name(&entry_name)
+        .with_context(|| format!("Archive is missing {id}"))?;
+    let mut bytes = Vec::new();
+    let (sha256, _) =
+        copy_and_hash(&mut entry, &mut bytes).with_context(|| format!("Failed to read {id}"))?;
+    if let Some(expected) = node.get("sha256").and_then(Value::as_str)
+        && !expected.is_empty()
+        && !expected.eq_ignore_ascii_case(&sha256)
+    {
+        anyhow::bail!("{id} is corrupt:\n  expected sha256 {expected}\n  found sha256 {sha256}");
+    }
+    String::from_utf8(bytes).with_context(|| format!("{id} is not valid UTF-8"))
+}
+
+/// Read and parse the RO-Crate metadata of the archive at `path` without extracting files.
+///
+/// # Errors
+///
+/// Returns an error when the archive cannot be opened or its metadata is missing or invalid.
+pub fn read_crate_metadata(path: &Path) -> Result<Value> {
+    let file = File::open(path).with_context(|| format!("Failed to open archive {:?}", path))?;
+    let mut zip = ZipArchive::new(file).context("Not a valid ELN archive (unreadable ZIP)")?;
+    read_metadata(&mut zip).map(|(_, metadata)| metadata)
+}
+
+/// Parse the archive's metadata file, returning it with the archive's root prefix.
+pub(crate) fn read_metadata<R: Read + Seek>(zip: &mut ZipArchive<R>) -> Result<(String, Value)> {
+    let metadata_entry = find_metadata_entry(zip)
+        .ok_or_else(|| anyhow!("Not a valid ELN archive: {METADATA_FILE_NAME} is missing"))?;
+    let root_prefix = metadata_entry
+        .strip_suffix(METADATA_FILE_NAME)
+        .unwrap_or_default()
+        .to_string();
+
+    let mut metadata_json = String::new();
+    zip.by_name(&metadata_entry)
+        .context("Failed to read RO-Crate metadata")?
+        .read_to_string(&mut metadata_json)
+        .context("RO-Crate metadata is not valid UTF-8")?;
+    let metadata: Value =
+        serde_json::from_str(&metadata_json).context("RO-Crate metadata is not valid JSON")?;
+    Ok((root_prefix, metadata))
+}
+
+/// Return the shallowest `ro-crate-metadata.json` entry name.
+fn find_metadata_entry<R: Read + Seek>(zip: &ZipArchive<R>) -> Option<String> {
+    zip.file_names()
+        .filter(|name| {
+            *name == METADATA_FILE_NAME || name.ends_with(&format!("/{METADATA_FILE_NAME}"))
+        })
+        .min_by_key(|name| name.matches('/').count())
+        .map(str::to_string)
+}
+
+/// Find the datasets describing entries: the `Dataset` parts of the crate root, in order.
+fn find_entry_datasets(graph: &[Value]) -> Vec<&Value> {
+    let Some(root) = find_node(graph, "./") else {
+        return Vec::new();
+    };
+    linked_ids(root.get("hasPart"))
+        .into_iter()
+        .filter_map(|id| find_node(graph, id))
+        .filter(|node| has_type(node, "Dataset"))
+        .collect()
+}
+
+/// Look up a graph node by `@id`.
+fn find_node<'a>(graph: &'a [Value], id: &str) -> Option<&'a Value> {
+    graph
+        .iter()
+        .find(|node| node.get("@id").and_then(Value::as_str) == Some(id))
+}
+
+/// Return true when the node's `@type` is (or contains) `kind`.
+fn has_type(node: &Value, kind: &str) -> bool {
+    match node.get("@type") {
+        Some(Value::String(t)) => t == kind,
+        Some(Value::Array(types)) => types.iter().any(|t| t.as_str() == Some(kind)),
+        _ => false,
+    }
+}
+
+/// Links of the dataset, one per web URL referenced by a relation property.
+///
+/// Labels come from the `name` of the linked node, unless that merely repeats the URL.
+fn parse_related_links(graph: &[Value], dataset: &Value) -> Vec<RelatedLink> {
+    let mut links = Vec::new();
+    for relation in Relation::ALL {
+        for id in linked_ids(dataset.get(relation.property())) {
+            let node = find_node(graph, id);
+            let url = node
+                .and_then(|node| node.get("url"))
+                .and_then(Value::as_str)
+                .unwrap_or(id);
+            if !is_web_url(url) {
+                continue;
+            }
+            let label = node
+                .and_then(|node| node.get("name"))
+                .and_then(Value::as_str)
+                .filter(|name| *name != url)
+                .unwrap_or_default();
+            links.push(RelatedLink {
+                label: label.to_string(),
+                url: url.to_string(),
+                relation,
+            });
+        }
+    }
+    links
+}
+
+/// License of `dataset`, or of the crate root when the dataset declares none.
+///
+/// Both a reference to a license node and a plain URL are accepted.
+fn parse_license(graph: &[Value], dataset: &Value) -> License {
+    let declared = |node: &Value| match node.get("license") {
+        Some(Value::String(url)) => Some(url.clone()),
+        reference => linked_ids(reference).first().map(|id| id.to_string()),
+    };
+    declared(dataset)
+        .or_else(|| find_node(graph, "./").and_then(declared))
+        .filter(|url| is_web_url(url))
+        .map_or(License::None, |url| License::from_url(&url))
+}
+
+/// Collect `@id` references from a single reference or an array of references.
+fn linked_ids(value: Option<&Value>) -> Vec<&str> {
+    match value {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| item.get("@id").and_then(Value::as_str))
+            .collect(),
+        Some(item) => item
+            .get("@id")
+            .and_then(Value::as_str)
+            .into_iter()
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Find the eLabFTW metadata JSON among the dataset's `variableMeasured` properties.
+///
+/// Properties may be linked by `@id` (ELNPack, eLabFTW 103+) or embedded inline.
+fn find_elabftw_metadata<'a>(graph: &'a [Value], dataset: &'a Value) -> Option<&'a str> {
+    let properties: Vec<&Value> = match dataset.get("variableMeasured") {
+        Some(Value::Array(items)) => items.iter().collect(),
+        Some(item) => vec![item],
+        None => Vec::new(),
+    };
+    properties
+        .into_iter()
+        .filter_map(|prop| match prop.get("@id").and_then(Value::as_str) {
+            Some(id) if prop.get("propertyID").is_none() => find_node(graph, id),
+            _ => Some(prop),
+        })
+        .find(|prop| prop.get("propertyID").and_then(Value::as_str) == Some("elabftw_metadata"))
+        .and_then(|prop| prop.get("value").and_then(Value::as_str))
+}
+
+/// Accept keywords as a JSON array or a comma-separated string (eLabFTW style).
+fn parse_keywords(value: Option<&Value>) -> Vec<String> {
+    let raw: Vec<&str> = match value {
+        Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).collect(),
+        Some(Value::String(list)) => list.split(',').collect(),
+        _ => Vec::new(),
+    };
+    raw.into_iter()
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Sanitized subdirectory of a file below its dataset, e.g. `run/csv` for
+/// `./experiment/run/csv/a.csv` in `./experiment/`.
+fn attachment_folder(dataset_id: &str, id: &str) -> String {
+    let relative = id
+        .strip_prefix(dataset_id)
+        .unwrap_or_else(|| id.trim_start_matches("./"));
+    let Some((dir, _)) = relative.rsplit_once('/') else {
+        return String::new();
+    };
+    dir.split('/')
+        .filter(|c| !c.is_empty() && *c != "." && *c != "..")
+        .map(sanitize_component)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Copy one `File` node's data out of the archive and describe it as an attachment.
+///
+/// The file is extracted to `folder` below `extract_dir`; names are kept unique per folder.
+fn extract_attachment<R: Read + Seek>(
+    zip: &mut ZipArchive<R>,
+    root_prefix: &str,
+    node: &Value,
+    id: &str,
+    folder: &str,
+    extract_dir: &Path,
+    used_names: &mut HashMap<String, HashSet<String>>,
+) -> Result<Attachment> {
+    let entry_name = format!("{}{}", root_prefix, id.trim_start_matches("./"));
+    let display_name = node
+        .get("name")
+        .and_then(Value::as_str)
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| id.rsplit('/').next().unwrap_or(id));
+    let sanitized_name = unique_name(
+        sanitize_component(display_name),
+        used_names.entry(folder.to_string()).or_default(),
+    );
+    let target_dir = extract_dir.join(folder);
+    fs::create_dir_all(&target_dir)
+        .with_context(|| format!("Failed to create directory {:?}", target_dir))?;
+    let target = target_dir.join(&sanitized_name);
+
+    let mut entry = zip
+        .by_name(&entry_name)
+        .with_context(|| format!("Archive is missing attachment {id}"))?;
+    let mut out =
+        File::create(&target).with_context(|| format!("Failed to create {:?}", target))?;
+    let (sha256, size) = copy_and_hash(&mut entry, &mut out)
+        .with_context(|| format!("Failed to extract attachment {id}"))?;
+
+    if let Some(expected) = node.get("sha256").and_then(Value::as_str)
+        && !expected.is_empty()
+        && !expected.eq_ignore_ascii_case(&sha256)
+    {
+        anyhow::bail!(
+            "Attachment {id} is corrupt:\n  expected sha256 {expected}\n  found sha256 {sha256}"
+        );
+    }
+
+    let mime = node
+        .get("encodingFormat")
+        .and_then(Value::as_str)
+        .filter(|m| !m.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            mime_guess::from_path(&sanitized_name)
+                .first_or_octet_stream()
+                .essence_str()
+                .to_string()
+        });
+
+    let acquired_at = node
+        .get("dateCreated")
+        .and_then(Value::as_str)
+        .and_then(|value| OffsetDateTime::parse(value, &Rfc3339).ok());
+
+    let description = node
+        .get("description")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+
+    Ok(Attachment::new(target, sanitized_name, mime, sha256, size)
+        .in_folder(folder.to_string())
+        .with_acquired_at(acquired_at)
+        .with_description(description))
+}
+
+/// Make `name` unique within `used` by inserting a numeric suffix before the extension.
+fn unique_name(name: String, used: &mut HashSet<String>) -> String {
+    let name = if name.is_empty() {
+        "attachment".to_string()
+    } else {
+        name
+    };
+    if used.insert(name.clone()) {
+        return name;
+    }
+    let path = PathBuf::from(&name);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(&name)
+        .to_string();
+    let ext = path.extension().and_then(|e| e.to_str());
+    (2..)
+        .map(|n| match ext {
+            Some(ext) => format!("{stem}_{n}.{ext}"),
+            None => format!("{stem}_{n}"),
+        })
+        .find(|candidate| used.insert(candidate.clone()))
+        .expect("unbounded suffix range always yields a free name")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::eln::{ArchiveEntry, ExportOptions, build_and_write_archive};
+    use crate::models::extra_fields::ExtraFieldKind;
+    use std::io::Write;
+    use tempfile::TempDir;
+    use zip::write::SimpleFileOptions;
+
+    fn sample_field() -> ExtraField {
+        ExtraField {
+            label: "Detector".into(),
+            kind: ExtraFieldKind::Select,
+            value: "Eiger".into(),
+            options: vec!["Pilatus".into(), "Eiger".into()],
+            position: Some(1),
+            required: true,
+            description: Some("Camera".into()),
+            group_id: Some(1),
+            ..Default::default()
+        }
+    }
+
+    fn write_zip(path: &Path, entries: &[(&str, &[u8])]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, data) in entries {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn round_trips_archives_written_by_elnpack() {
+        let tmp = TempDir::new().unwrap();
+        let source = tmp.path().join("data.csv");
+        fs::write(&source, b"a,b\n1,2\n").unwrap();
+        let attachment = Attachment::new(
+            source.clone(),
+            "data.csv".into(),
+            "text/csv".into(),
+            crate::utils::hash_file(&source).unwrap(),
+            8,
+        );
+        let groups = vec![ExtraFieldGroup {
+            id: 1,
+            name: "Setup".into(),
+            position: 0,
+        }];
+        let performed_at = OffsetDateTime::parse("2024-05-06T07:08:00Z", &Rfc3339).unwrap();
+        let output = tmp.path().join("entry.eln");
+
+        build_and_write_archive(
+            &output,
+            &[ArchiveEntry {
+                title: "Round trip".into(),
+                body: "# Notes\n\nBody **text**".into(),
+                attachments: vec![attachment.clone()],
+                extra_fields: vec![sample_field()],
+                extra_groups: groups.clone(),
+                performed_at,
+                genre: ArchiveGenre::Resource,
+                keywords: vec!["alpha".into(), "beta".into()],
+                language: None,
+                related_links: Vec::new(),
+                imported_metadata: None,
+                license: License::None,
+                summary: String::new(),
+                hide_main_text: false,
+            }],
+            BodyFormat::Markdown,
+            &ExportOptions::default(),
+        )
+        .unwrap();
+
+        let extract = tmp.path().join("opened");
+        let opened = open_archive(&output, &extract).unwrap();
+
+        assert_eq!(opened.title, "Round trip");
+        assert_eq!(opened.body, "# Notes\n\nBody **text**");
+        assert_eq!(opened.body_format, BodyFormat::Markdown);
+        assert_eq!(opened.genre, ArchiveGenre::Resource);
+        assert_eq!(opened.keywords, vec!["alpha", "beta"]);
+        assert_eq!(opened.performed_at, Some(performed_at));
+        assert_eq!(opened.extra_fields, vec![sample_field()]);
+        assert_eq!(opened.extra_groups.len(), 1);
+        assert_eq!(opened.extra_groups[0].name, "Setup");
+
+        assert_eq!(opened.attachments.len(), 1);
+        let restored = &opened.attachments[0];
+        assert_eq!(restored.sanitized_name, "data.csv");
+        assert_eq!(restored.sha256, attachment.sha256);
+        assert_eq!(restored.size, 8);
+        assert!(restored.path.starts_with(&extract));
+        assert_eq!(fs::read(&restored.path).unwrap(), b"a,b\n1,2\n");
+    }
+
+    #[test]
+    fn html_bodies_are_detected() {
+        let tmp = TempDir::new().unwrap();
+        let output = tmp.path().join("html.eln");
+        build_and_write_archive(
+            &output,
+            &[ArchiveEntry {
+                title: "Html".into(),
+                body: "Some *emphasis*".into(),
+                attachments: Vec::new(),
+                extra_fields: Vec::new(),
+                extra_groups: Vec::new(),
+                performed_at: OffsetDateTime::now_utc(),
+                genre: ArchiveGenre::Experiment,
+                keywords: Vec::new(),
+                language: None,
+                related_links: Vec::new(),
+                imported_metadata: None,
+                license: License::None,
+                summary: String::new(),
+                hide_main_text: false,
+            }],
+            BodyFormat::Html,
+            &ExportOptions::default(),
+        )
+        .unwrap();
+
+        let opened = open_archive(&output, &tmp.path().join("x")).unwrap();
+
+        assert_eq!(opened.body_format, BodyFormat::Html);
+        assert!(opened.body.contains("<em>emphasis</em>"));
+        assert!(opened.attachments.is_empty());
+    }
+
+    #[test]
+    fn reads_elabftw_style_metadata() {
+        let tmp = TempDir::new().unwrap();
+        let output = tmp.path().join("elab.eln");
+        let metadata = serde_json::json!({
+            "@context": "https://w3id.org/ro/crate/1.1/context",
+            "@graph": [
+                { "@id": "./", "@type": "Dataset", "hasPart": [{ "@id": "./Entry - abc/" }] },
+                {
+                    "@id": "./Entry - abc/",
+                    "@type": "Dataset",
+                    "name": "From eLabFTW",
+                    "text": "<p>Hello</p>",
+                    "keywords": "one, two",
+                    "dateCreated": "2023-01-02T03:04:05+01:00",
+                    "hasPart": [{ "@id": "./Entry - abc/plot.png" }],
+                },
+                {
+                    "@id": "./Entry - abc/plot.png",
+                    "@type": "File",
+                    "name": "plot.png",
+                },
+            ],
+        });
+        write_zip(
+            &output,
+            &[
+                (
+                    "export/ro-crate-metadata.json",
+                    metadata.to_string().as_bytes(),
+                ),
+                ("export/Entry - abc/plot.png", b"png"),
+            ],
+        );
+
+        let opened = open_archive(&output, &tmp.path().join("x")).unwrap();
+
+        assert_eq!(opened.title, "From eLabFTW");
+        assert_eq!(opened.body_format, BodyFormat::Html);
+        assert_eq!(opened.keywords, vec!["one", "two"]);
+        assert!(opened.performed_at.is_some());
+        assert_eq!(opened.attachments[0].mime, "image/png");
+        assert_eq!(opened.attachments[0].size, 3);
+    }
+
+    #[test]
+    fn foreign_and_corrupt_archives_are_rejected() {
+        let tmp = TempDir::new().unwrap();
+
+        let not_zip = tmp.path().join("plain.eln");
+        fs::write(&not_zip, b"hello").unwrap();
+        let err = open_archive(&not_zip, tmp.path()).unwrap_err();
+        assert!(err.to_string().contains("unreadable ZIP"));
+
+        let no_metadata = tmp.path().join("empty.eln");
+        write_zip(&no_metadata, &[("readme.txt", b"hi")]);
+        let err = open_archive(&no_metadata, tmp.path()).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("ro-crate-metadata.json is missing")
+        );
+
+        let bad_json = tmp.path().join("bad.eln");
+        write_zip(&bad_json, &[("e/ro-crate-metadata.json", b"{not json")]);
+        let err = open_archive(&bad_json, tmp.path()).unwrap_err();
+        assert!(err.to_string().contains("not valid JSON"));
+    }
+
+    #[test]
+    fn tampered_attachments_are_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let output = tmp.path().join("tampered.eln");
+        let metadata = serde_json::json!({
+            "@graph": [
+                { "@id": "./", "@type": "Dataset", "hasPart": [{ "@id": "./experiment/" }] },
+                {
+                    "@id": "./experiment/",
+                    "@type": "Dataset",
+                    "name": "T",
+                    "hasPart": [{ "@id": "./experiment/a.txt" }],
+                },
+                {
+                    "@id": "./experiment/a.txt",
+                    "@type": "File",
+                    "name": "a.txt",
+                    "sha256": "00",
+                },
+            ],
+        });
+        write_zip(
+            &output,
+            &[
+                ("t/ro-crate-metadata.json", metadata.to_string().as_bytes()),
+                ("t/experiment/a.txt", b"data"),
+            ],
+        );
+
+        let err = open_archive(&output, &tmp.path().join("x")).unwrap_err();
+
+        assert!(err.to_string().contains("is corrupt"));
+    }
+
+    #[test]
+    fn reads_every_entry_of_multi_entry_archives() {
+        let tmp = TempDir::new().unwrap();
+        let source = tmp.path().join("a.txt");
+        fs::write(&source, b"x").unwrap();
+        let attachment = Attachment::new(
+            source,
+            "a.txt".into(),
+            "text/plain".into(),
+            "unavailable".into(),
+            1,
+        );
+        let entry = |title: &str| ArchiveEntry {
+            title: title.into(),
+            body: String::new(),
+            attachments: vec![attachment.clone()],
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+            performed_at: OffsetDateTime::UNIX_EPOCH,
+            genre: ArchiveGenre::Experiment,
+            keywords: Vec::new(),
+            language: None,
+            related_links: Vec::new(),
+            imported_metadata: None,
+            license: License::None,
+            summary: String::new(),
+            hide_main_text: false,
+        };
+        let output = tmp.path().join("multi.eln");
+        build_and_write_archive(
+            &output,
+            &[entry("One"), entry("Two")],
+            BodyFormat::Markdown,
+            &ExportOptions::default(),
+        )
+        .unwrap();
+
+        let extract = tmp.path().join("opened");
+        let opened = open_archive(&output, &extract).unwrap();
+
+        assert_eq!(opened.title, "One");
+        assert_eq!(opened.more_entries.len(), 1);
+        let second = &opened.more_entries[0];
+        assert_eq!(second.title, "Two");
+        assert!(second.more_entries.is_empty());
+        assert_eq!(opened.attachments[0].path, extract.join("entry-1/a.txt"));
+        assert_eq!(second.attachments[0].path, extract.join("entry-2/a.txt"));
+    }
+
+    #[test]
+    fn round_trips_attachment_folders() {
+        let tmp = TempDir::new().unwrap();
+        let source = tmp.path().join("a.csv");
+        fs::write(&source, b"x").unwrap();
+        let attachment = Attachment::new(
+            source.clone(),
+            "a.csv".into(),
+            "text/csv".into(),
+            crate::utils::hash_file(&source).unwrap(),
+            1,
+        )
+        .in_folder("run42/csv".into())
+        .with_acquired_at(Some(
+            OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap(),
+        ))
+        .with_description(Some("Plate reader export".into()));
+        let output = tmp.path().join("entry.eln");
+
+        build_and_write_archive(
+            &output,
+            &[ArchiveEntry {
+                title: "Folders".into(),
+                body: "".into(),
+                attachments: vec![attachment.clone()],
+                extra_fields: Vec::new(),
+                extra_groups: Vec::new(),
+                performed_at: OffsetDateTime::UNIX_EPOCH,
+                genre: ArchiveGenre::Experiment,
+                keywords: Vec::new(),
+                language: None,
+                related_links: Vec::new(),
+                imported_metadata: None,
+                license: License::None,
+                summary: String::new(),
+                hide_main_text: false,
+            }],
+            BodyFormat::Html,
+            &ExportOptions::default(),
+        )
+        .unwrap();
+
+        let mut zip = ZipArchive::new(File::open(&output).unwrap()).unwrap();
+        assert!(zip.by_name("entry/experiment/run42/csv/a.csv").is_ok());
+
+        let extract = tmp.path().join("opened");
+        let opened = open_archive(&output, &extract).unwrap();
+        let restored = &opened.attachments[0];
+        assert_eq!(restored.folder, "run42/csv");
+        assert_eq!(restored.archive_path(), "run42/csv/a.csv");
+        assert_eq!(restored.path, extract.join("run42/csv/a.csv"));
+        assert_eq!(restored.acquired_at, attachment.acquired_at);
+        assert_eq!(restored.description, attachment.description);
+    }
+
+    #[test]
+    fn unique_name_suffixes_duplicates() {
+        let mut used = HashSet::new();
+        assert_eq!(unique_name("a.txt".into(), &mut used), "a.txt");
+        assert_eq!(unique_name("a.txt".into(), &mut used), "a_2.txt");
+        assert_eq!(unique_name("a.txt".into(), &mut used), "a_3.txt");
+    }
+}
@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Favorite folders that file dialogs can start in.
+//!
+//! Each kind of dialog remembers which favorite was chosen last. Resolving the start
+//! directory is pure: the caller supplies the existence check so it can run off the UI thread.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// File dialogs that can start in a favorite folder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PickerKind {
+    /// "Add files" and "Add folder".
+    Attachments,
+    /// eLabFTW metadata JSON import.
+    Metadata,
+    /// Saving and opening ELN archives.
+    Archive,
+}
+
+/// User-defined favorite folders and the last choice per dialog kind.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FavoriteSettings {
+    /// Favorite folders in the order the user listed them.
+    pub locations: Vec<PathBuf>,
+    /// Favorite pre-selected for the next dialog of each kind.
+    pub last_used: BTreeMap<PickerKind, PathBuf>,
+    /// Folder the last dialog of each kind was confirmed in.
+    pub last_dirs: BTreeMap<PickerKind, PathBuf>,
+}
+
+impl FavoriteSettings {
+    /// Favorite pre-selected for `kind`, ignoring choices that were removed from the list.
+    pub fn selected(&self, kind: PickerKind) -> Option<&Path> {
+        self.last_used
+            .get(&kind)
+            .filter(|path| self.locations.contains(path))
+            .map(PathBuf::as_path)
+    }
+
+    /// Folder the next dialog of `kind` opens in: the selected favorite, else the folder
+    /// last used for that dialog.
+    pub fn start_dir(&self, kind: PickerKind) -> Option<&Path> {
+        self.selected(kind)
+            .or_else(|| self.last_dirs.get(&kind).map(PathBuf::as_path))
+    }
+}
+
+/// Directory a dialog should open in for the `selected` favorite.
+///
+/// Falls back to the nearest existing parent when the favorite itself is gone (e.g. a
+/// renamed run folder), and to `None` — the system default — when nothing below the
+/// filesystem root exists (e.g. an unmounted network share).
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let dir = resolve_start_dir(Some(Path::new("/data/beamline/run7")), |p| p == Path::new("/data"));
+/// assert_eq!(dir, Some(PathBuf::from("/data")));
+/// ```
+pub fn resolve_start_dir(
+    selected: Option<&Path>,
+    is_dir: impl Fn(&Path) -> bool,
+) -> Option<PathBuf> {
+    selected?
+        .ancestors()
+        .filter(|dir| dir.parent().is_some() && !dir.as_os_str().is_empty())
+        .find(|dir| is_dir(dir))
+        .map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_dir_falls_back_to_existing_parent_then_default() {
+        let exists = |dir: &Path| dir == Path::new("/data") || dir == Path::new("/data/beamline");
+
+        assert_eq!(
+            resolve_start_dir(Some(Path::new("/data/beamline")), exists),
+            Some(PathBuf::from("/data/beamline"))
+        );
+        assert_eq!(
+            resolve_start_dir(Some(Path::new("/data/beamline/run7/raw")), exists),
+            Some(PathBuf::from("/data/beamline"))
+        );
+        assert_eq!(
+            resolve_start_dir(Some(Path::new("/mnt/share")), exists),
+            None
+        );
+        assert_eq!(resolve_start_dir(None, exists), None);
+    }
+
+    #[test]
+    fn selection_is_ignored_once_the_favorite_is_removed() {
+        let mut favorites = FavoriteSettings {
+            locations: vec![PathBuf::from("/data")],
+            ..Default::default()
+        };
+        favorites
+            .last_used
+            .insert(PickerKind::Archive, PathBuf::from("/data"));
+        assert_eq!(
+            favorites.selected(PickerKind::Archive),
+            Some(Path::new("/data"))
+        );
+        assert_eq!(favorites.selected(PickerKind::Metadata), None);
+
+        favorites
+            .last_dirs
+            .insert(PickerKind::Archive, PathBuf::from("/runs"));
+        assert_eq!(
+            favorites.start_dir(PickerKind::Archive),
+            Some(Path::new("/data")),
+            "a selected favorite wins over the last folder"
+        );
+
+        favorites.locations.clear();
+        assert_eq!(favorites.selected(PickerKind::Archive), None);
+        assert_eq!(
+            favorites.start_dir(PickerKind::Archive),
+            Some(Path::new("/runs"))
+        );
+    }
+}
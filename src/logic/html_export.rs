@@ -0,0 +1,348 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Sanitizer policy and markdown extensions for the exported HTML body.
+//!
+//! Users may widen the Ammonia allow-list with extra tags and classes, but only within a
+//! fixed set of inert, presentational tags. Container blocks (`::: note`) are expanded into
+//! `<div class="callout note">` wrappers before the markdown is rendered.
+
+use serde::{Deserialize, Serialize};
+
+/// Extra tags users may opt into; everything else (script, style, iframe, forms, …) is refused.
+pub const SAFE_EXTRA_TAGS: &[&str] = &[
+    "abbr",
+    "address",
+    "article",
+    "aside",
+    "caption",
+    "details",
+    "dfn",
+    "div",
+    "figcaption",
+    "figure",
+    "kbd",
+    "main",
+    "mark",
+    "samp",
+    "section",
+    "small",
+    "span",
+    "summary",
+    "time",
+    "var",
+];
+
+/// Callout kinds recognised by the `::: kind` container syntax.
+pub const CALLOUT_KINDS: &[&str] = &["note", "tip", "info", "important", "warning", "caution"];
+
+/// Tags that always accept the configured extra classes.
+const CLASS_TARGET_TAGS: &[&str] = &["div", "span", "p", "blockquote", "table", "pre", "code"];
+
+/// User-editable HTML export settings as persisted on disk.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HtmlExportSettings {
+    /// Additional tags to keep when sanitizing (must be in [`SAFE_EXTRA_TAGS`]).
+    pub extra_tags: Vec<String>,
+    /// Additional CSS classes to keep on container-like tags.
+    pub extra_classes: Vec<String>,
+}
+
+/// Validated sanitizer configuration applied when rendering the body to HTML.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HtmlPolicy {
+    tags: Vec<String>,
+    classes: Vec<String>,
+}
+
+impl HtmlPolicy {
+    /// Validate user settings into a policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns one message per rejected tag or class so the settings UI can list them all.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let settings = HtmlExportSettings {
+    ///     extra_tags: vec!["script".into()],
+    ///     extra_classes: Vec::new(),
+    /// };
+    /// assert!(HtmlPolicy::from_settings(&settings).is_err());
+    /// ```
+    pub fn from_settings(settings: &HtmlExportSettings) -> Result<Self, Vec<String>> {
+        let mut errors = Vec::new();
+        let mut tags = Vec::new();
+        let mut classes = Vec::new();
+
+        for raw in &settings.extra_tags {
+            match validate_extra_tag(raw) {
+                Ok(tag) if !tags.contains(&tag) => tags.push(tag),
+                Ok(_) => {}
+                Err(err) => errors.push(err),
+            }
+        }
+        for raw in &settings.extra_classes {
+            match validate_extra_class(raw) {
+                Ok(class) if !classes.contains(&class) => classes.push(class),
+                Ok(_) => {}
+                Err(err) => errors.push(err),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(Self { tags, classes })
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Widen an Ammonia builder with the policy's tags, classes, and the callout classes.
+    pub fn configure<'a>(&'a self, builder: &mut ammonia::Builder<'a>) {
+        builder.add_tags(self.tags.iter().map(String::as_str));
+        builder.add_tags(["div"]);
+        builder.add_allowed_classes(
+            "div",
+            ["callout"]
+                .into_iter()
+                .chain(CALLOUT_KINDS.iter().copied())
+                .chain(self.classes.iter().map(String::as_str)),
+        );
+
+        if self.classes.is_empty() {
+            return;
+        }
+        for tag in CLASS_TARGET_TAGS
+            .iter()
+            .copied()
+            .chain(self.tags.iter().map(String::as_str))
+            .filter(|tag| *tag != "div")
+        {
+            builder.add_tags([tag]);
+            builder.add_allowed_classes(tag, self.classes.iter().map(String::as_str));
+        }
+    }
+}
+
+/// Normalize and validate a user-supplied extra tag against [`SAFE_EXTRA_TAGS`].
+///
+/// # Errors
+///
+/// Returns a user-facing message when the tag is not on the safe list.
+pub fn validate_extra_tag(raw: &str) -> Result<String, String> {
+    let tag = raw
+        .trim()
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .to_ascii_lowercase();
+    if SAFE_EXTRA_TAGS.contains(&tag.as_str()) {
+        Ok(tag)
+    } else {
+        Err(format!(
+            "Tag '{}' is not allowed in exported HTML.",
+            raw.trim()
+        ))
+    }
+}
+
+/// Validate a user-supplied CSS class name (`[A-Za-z0-9_-]`, not starting with a digit).
+///
+/// # Errors
+///
+/// Returns a user-facing message when the class contains other characters.
+pub fn validate_extra_class(raw: &str) -> Result<String, String> {
+    let class = raw.trim().trim_start_matches('.');
+    let valid = !class.is_empty()
+        && !class.starts_with(|c: char| c.is_ascii_digit())
+        && class
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(class.to_string())
+    } else {
+        Err(format!(
+            "Class '{}' is not a valid CSS class name.",
+            raw.trim()
+        ))
+    }
+}
+
+/// Expand `::: kind` … `:::` container blocks into `<div class="callout kind">` wrappers.
+///
+/// Containers may nest; an unclosed container is closed at the end of the document. Lines
+/// inside fenced code blocks are left untouched, and opening lines whose kind is not a plain
+/// identifier are kept as regular text.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let html = expand_callouts("::: note\nHello\n:::");
+/// assert!(html.contains("<div class=\"callout note\">"));
+/// ```
+pub fn expand_callouts(markdown: &str) -> String {
+    let mut out = String::with_capacity(markdown.len());
+    let mut depth = 0usize;
+    let mut fence: Option<(char, usize)> = None;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+
+        if let Some((ch, len)) = fence {
+            if trimmed.len() >= len && trimmed.chars().all(|c| c == ch) {
+                fence = None;
+            }
+            out.push_str(line);
+            out.push('\n');
+            continue;
+        }
+        if let Some(open) = code_fence(trimmed) {
+            fence = Some(open);
+            out.push_str(line);
+            out.push('\n');
+            continue;
+        }
+
+        if trimmed == ":::" && depth > 0 {
+            depth -= 1;
+            out.push_str("\n</div>\n\n");
+            continue;
+        }
+        if let Some(kind) = trimmed.strip_prefix(":::").map(str::trim)
+            && is_callout_kind(kind)
+        {
+            depth += 1;
+            out.push_str(&format!(
+                "\n<div class=\"callout {}\">\n\n",
+                kind.to_ascii_lowercase()
+            ));
+            continue;
+        }
+
+        out.push_str(line);
+        out.push('\n');
+    }
+
+    for _ in 0..depth {
+        out.push_str("\n</div>\n");
+    }
+    out
+}
+
+/// Detect an opening code fence (``` or ~~~) and return its character and length.
+fn code_fence(trimmed: &str) -> Option<(char, usize)> {
+    let ch = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = trimmed.chars().take_while(|c| *c == ch).count();
+    (len >= 3).then_some((ch, len))
+}
+
+/// Return true when `kind` is a non-empty identifier usable as a class name.
+fn is_callout_kind(kind: &str) -> bool {
+    !kind.is_empty()
+        && kind
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sanitize(html: &str, policy: &HtmlPolicy) -> String {
+        let mut builder = ammonia::Builder::default();
+        policy.configure(&mut builder);
+        builder.clean(html).to_string()
+    }
+
+    #[test]
+    fn expands_callout_blocks_with_markdown_inside() {
+        let out = expand_callouts("Intro\n\n::: warning\n**Hot** surface\n:::\n\nAfter");
+
+        assert!(out.contains("<div class=\"callout warning\">\n\n**Hot** surface"));
+        assert!(out.contains("</div>"));
+        assert!(out.trim_end().ends_with("After"));
+    }
+
+    #[test]
+    fn nested_and_unclosed_callouts_are_balanced() {
+        let out = expand_callouts("::: note\nouter\n::: tip\ninner\n:::");
+
+        assert_eq!(out.matches("<div").count(), 2);
+        assert_eq!(out.matches("</div>").count(), 2);
+    }
+
+    #[test]
+    fn callout_syntax_inside_code_fences_is_untouched() {
+        let src = "```\n::: note\n```\n";
+        assert_eq!(expand_callouts(src), src);
+    }
+
+    #[test]
+    fn invalid_callout_kinds_stay_text() {
+        let src = "::: <script>\n";
+        assert_eq!(expand_callouts(src), src);
+    }
+
+    #[test]
+    fn policy_rejects_dangerous_tags_and_bad_classes() {
+        let settings = HtmlExportSettings {
+            extra_tags: vec![
+                "script".into(),
+                "<style>".into(),
+                "IFRAME".into(),
+                "mark".into(),
+            ],
+            extra_classes: vec!["ok-class".into(), "bad class".into(), "x\"onload".into()],
+        };
+
+        let errors = HtmlPolicy::from_settings(&settings).unwrap_err();
+
+        assert_eq!(errors.len(), 5);
+        assert!(errors.iter().any(|e| e.contains("script")));
+        assert!(errors.iter().any(|e| e.contains("bad class")));
+    }
+
+    #[test]
+    fn policy_normalizes_and_dedupes_entries() {
+        let settings = HtmlExportSettings {
+            extra_tags: vec!["<Mark>".into(), "mark".into()],
+            extra_classes: vec![".theme-box".into(), "theme-box".into()],
+        };
+
+        let policy = HtmlPolicy::from_settings(&settings).unwrap();
+
+        assert_eq!(policy.tags, vec!["mark"]);
+        assert_eq!(policy.classes, vec!["theme-box"]);
+    }
+
+    #[test]
+    fn sanitizer_keeps_callouts_and_configured_classes_only() {
+        let policy = HtmlPolicy::from_settings(&HtmlExportSettings {
+            extra_tags: vec!["mark".into()],
+            extra_classes: vec!["theme-box".into()],
+        })
+        .unwrap();
+
+        let html = sanitize(
+            "<div class=\"callout note theme-box evil\">x</div><mark>m</mark><span class=\"theme-box\">s</span><script>alert(1)</script>",
+            &policy,
+        );
+
+        assert!(html.contains("class=\"callout note theme-box\""));
+        assert!(html.contains("<mark>m</mark>"));
+        assert!(html.contains("<span class=\"theme-box\">s</span>"));
+        assert!(!html.contains("evil"));
+        assert!(!html.contains("script"));
+    }
+
+    #[test]
+    fn default_policy_still_strips_style_and_iframe() {
+        let html = sanitize(
+            "<style>p{}</style><iframe src=\"x\"></iframe><p>ok</p>",
+            &HtmlPolicy::default(),
+        );
+
+        assert_eq!(html, "<p>ok</p>");
+    }
+}
@@ -0,0 +1,618 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Conversion of eLabFTW's HTML entry bodies back into markdown for the editor.
+//!
+//! eLabFTW stores bodies written in its rich-text editor as HTML. The converter covers what
+//! that editor produces: paragraphs, headings, emphasis, code, links, images, lists, block
+//! quotes, rules and simple tables. Unknown tags are dropped and their text kept, and
+//! `script`/`style` contents are skipped, so any input yields readable markdown.
+
+/// Convert an HTML fragment to markdown.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let md = html_to_markdown("<p>Add <strong>5 µl</strong> buffer.</p>");
+/// assert_eq!(md, "Add **5 µl** buffer.");
+/// ```
+pub fn html_to_markdown(html: &str) -> String {
+    let mut converter = Converter::default();
+    for token in tokenize(html) {
+        converter.token(token);
+    }
+    converter.finish()
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Text(String),
+    Open {
+        name: String,
+        attrs: Vec<(String, String)>,
+    },
+    Close(String),
+}
+
+/// Elements without content or closing tag.
+const VOID_ELEMENTS: &[&str] = &["br", "hr", "img", "input", "meta", "link", "col", "wbr"];
+
+/// Elements whose content is not text for the reader.
+const SKIPPED_ELEMENTS: &[&str] = &["script", "style", "head", "title"];
+
+/// Split `html` into text and tags; comments and declarations are dropped.
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(lt) = rest.find('<') {
+        text.push_str(&rest[..lt]);
+        rest = &rest[lt..];
+        let after = &rest[1..];
+        if let Some(comment) = after.strip_prefix("!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let starts_tag = after
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || matches!(c, '/' | '!' | '?'));
+        let Some(gt) = starts_tag.then(|| after.find('>')).flatten() else {
+            text.push('<');
+            rest = after;
+            continue;
+        };
+        let inner = &after[..gt];
+        rest = &after[gt + 1..];
+        if inner.starts_with(['!', '?']) {
+            continue;
+        }
+        if !text.is_empty() {
+            tokens.push(Token::Text(decode_entities(&std::mem::take(&mut text))));
+        }
+        if let Some(name) = inner.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim().to_ascii_lowercase()));
+            continue;
+        }
+        let (name, attrs) = parse_tag(inner.trim_end_matches('/'));
+        if SKIPPED_ELEMENTS.contains(&name.as_str()) {
+            let closing = format!("</{name}");
+            rest = find_ascii_case_insensitive(rest, &closing)
+                .and_then(|start| rest[start..].find('>').map(|end| &rest[start + end + 1..]))
+                .unwrap_or("");
+            continue;
+        }
+        let void = VOID_ELEMENTS.contains(&name.as_str());
+        tokens.push(Token::Open {
+            name: name.clone(),
+            attrs,
+        });
+        if void {
+            tokens.push(Token::Close(name));
+        }
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        tokens.push(Token::Text(decode_entities(&text)));
+    }
+    tokens
+}
+
+fn find_ascii_case_insensitive(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+/// Lowercased tag name and attributes of the tag contents `inner`, e.g. `a href="x"`.
+fn parse_tag(inner: &str) -> (String, Vec<(String, String)>) {
+    let name_end = inner
+        .find(|c: char| c.is_whitespace())
+        .unwrap_or(inner.len());
+    let name = inner[..name_end].to_ascii_lowercase();
+    let mut attrs = Vec::new();
+    let mut rest = inner[name_end..].trim_start();
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = rest[..key_end].to_ascii_lowercase();
+        rest = rest[key_end..].trim_start();
+        let mut value = String::new();
+        if let Some(after_eq) = rest.strip_prefix('=') {
+            let after_eq = after_eq.trim_start();
+            let (raw, remaining) = match after_eq.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let body = &after_eq[1..];
+                    let end = body.find(quote).unwrap_or(body.len());
+                    (&body[..end], body.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = after_eq.find(char::is_whitespace).unwrap_or(after_eq.len());
+                    (&after_eq[..end], &after_eq[end..])
+                }
+            };
+            value = decode_entities(raw);
+            rest = remaining.trim_start();
+        }
+        if !key.is_empty() {
+            attrs.push((key, value));
+        }
+    }
+    (name, attrs)
+}
+
+/// Replace character references such as `&amp;`, `&#181;` and `&#xb5;`; unknown named
+/// references are kept as written.
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let name = &rest[1..end];
+            let c = match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                "micro" => Some('µ'),
+                "deg" => Some('°'),
+                _ => name
+                    .strip_prefix("#x")
+                    .or_else(|| name.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| name.strip_prefix('#').map(str::parse))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Element whose markdown is only known once it is closed.
+#[derive(Debug)]
+enum FrameKind {
+    Root,
+    Heading(usize),
+    Quote,
+    Item(String),
+    Link(String),
+    Pre,
+    Cell,
+}
+
+#[derive(Debug)]
+struct Frame {
+    kind: FrameKind,
+    out: String,
+}
+
+/// Open list: whether it is numbered, and the next number.
+struct List {
+    ordered: bool,
+    next: usize,
+}
+
+#[derive(Default)]
+struct Table {
+    rows: Vec<Vec<String>>,
+    header_rows: usize,
+    in_head: bool,
+}
+
+#[derive(Default)]
+struct Converter {
+    frames: Vec<Frame>,
+    lists: Vec<List>,
+    tables: Vec<Table>,
+}
+
+impl Converter {
+    fn out(&mut self) -> &mut String {
+        if self.frames.is_empty() {
+            self.frames.push(Frame {
+                kind: FrameKind::Root,
+                out: String::new(),
+            });
+        }
+        &mut self.frames.last_mut().expect("root frame").out
+    }
+
+    fn in_pre(&self) -> bool {
+        self.frames
+            .iter()
+            .any(|frame| matches!(frame.kind, FrameKind::Pre))
+    }
+
+    fn push_frame(&mut self, kind: FrameKind) {
+        self.out();
+        self.frames.push(Frame {
+            kind,
+            out: String::new(),
+        });
+    }
+
+    /// Close the innermost frame of the given kind and everything opened inside it.
+    fn pop_frame(&mut self, matches: impl Fn(&FrameKind) -> bool) -> Option<Frame> {
+        let index = self.frames.iter().rposition(|frame| matches(&frame.kind))?;
+        if index == 0 {
+            return None;
+        }
+        while self.frames.len() > index + 1 {
+            let inner = self.frames.pop().expect("inner frame");
+            self.out().push_str(&inner.out);
+        }
+        self.frames.pop()
+    }
+
+    /// End the current paragraph so the next content starts a new block.
+    fn block_break(&mut self) {
+        let in_item = matches!(
+            self.frames.last().map(|frame| &frame.kind),
+            Some(FrameKind::Item(_))
+        );
+        let out = self.out();
+        let trimmed = out.trim_end_matches([' ', '\n']).len();
+        out.truncate(trimmed);
+        if !out.is_empty() {
+            out.push_str(if in_item { "\n" } else { "\n\n" });
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.in_pre() {
+            self.out().push_str(text);
+            return;
+        }
+        let out = self.out();
+        for (i, word) in text.split_ascii_whitespace().enumerate() {
+            let at_line_start = out.is_empty() || out.ends_with(['\n', ' ']);
+            let spaced = i > 0 || text.starts_with(|c: char| c.is_ascii_whitespace());
+            if spaced && !at_line_start {
+                out.push(' ');
+            }
+            for c in word.chars() {
+                if matches!(c, '\\' | '*' | '_' | '`') {
+                    out.push('\\');
+                }
+                out.push(c);
+            }
+        }
+        if text.ends_with(|c: char| c.is_ascii_whitespace())
+            && !(out.is_empty() || out.ends_with(['\n', ' ']))
+        {
+            out.push(' ');
+        }
+    }
+
+    fn token(&mut self, token: Token) {
+        match token {
+            Token::Text(text) => self.text(&text),
+            Token::Open { name, attrs } => self.open(&name, &attrs),
+            Token::Close(name) => self.close(&name),
+        }
+    }
+
+    fn open(&mut self, name: &str, attrs: &[(String, String)]) {
+        let attr = |key: &str| {
+            attrs
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+                .unwrap_or_default()
+        };
+        if self.in_pre() {
+            if name == "br" {
+                self.out().push('\n');
+            }
+            return;
+        }
+        match name {
+            "p" | "div" | "section" | "article" | "figure" | "dl" | "dt" | "dd" => {
+                self.block_break()
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.block_break();
+                self.push_frame(FrameKind::Heading(usize::from(name.as_bytes()[1] - b'0')));
+            }
+            "br" => {
+                // Headings and table cells are single lines.
+                let single_line = matches!(
+                    self.frames.last().map(|frame| &frame.kind),
+                    Some(FrameKind::Heading(_) | FrameKind::Cell)
+                );
+                let out = self.out();
+                let trimmed = out.trim_end_matches(' ').len();
+                out.truncate(trimmed);
+                out.push_str(if single_line { " " } else { "\\\n" });
+            }
+            "hr" => {
+                self.block_break();
+                self.out().push_str("---");
+                self.block_break();
+            }
+            "strong" | "b" => self.out().push_str("**"),
+            "em" | "i" => self.out().push('*'),
+            "s" | "del" | "strike" => self.out().push_str("~~"),
+            "code" | "kbd" | "samp" => self.out().push('`'),
+            "a" => self.push_frame(FrameKind::Link(attr("href").to_string())),
+            "img" => {
+                let image = format!("![{}]({})", attr("alt"), attr("src"));
+                self.out().push_str(&image);
+            }
+            "pre" => {
+                self.block_break();
+                self.push_frame(FrameKind::Pre);
+            }
+            "blockquote" => {
+                self.block_break();
+                self.push_frame(FrameKind::Quote);
+            }
+            "ul" | "ol" => {
+                self.block_break();
+                let start = attr("start").parse().unwrap_or(1);
+                self.lists.push(List {
+                    ordered: name == "ol",
+                    next: start,
+                });
+            }
+            "li" => {
+                let marker = match self.lists.last_mut() {
+                    Some(List {
+                        ordered: true,
+                        next,
+                    }) => {
+                        *next += 1;
+                        format!("{}. ", *next - 1)
+                    }
+                    _ => "- ".to_string(),
+                };
+                self.push_frame(FrameKind::Item(marker));
+            }
+            "table" => {
+                self.block_break();
+                self.tables.push(Table::default());
+            }
+            "thead" => {
+                if let Some(table) = self.tables.last_mut() {
+                    table.in_head = true;
+                }
+            }
+            "tr" => {
+                if let Some(table) = self.tables.last_mut() {
+                    table.rows.push(Vec::new());
+                    if table.in_head {
+                        table.header_rows = table.rows.len();
+                    }
+                }
+            }
+            "td" | "th" => self.push_frame(FrameKind::Cell),
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        if self.in_pre() && name != "pre" {
+            return;
+        }
+        match name {
+            "p" | "div" | "section" | "article" | "figure" | "dl" | "dt" | "dd" => {
+                self.block_break()
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                if let Some(frame) = self.pop_frame(|k| matches!(k, FrameKind::Heading(_))) {
+                    let FrameKind::Heading(level) = frame.kind else {
+                        unreachable!("popped a heading frame");
+                    };
+                    let text = frame.out.split_whitespace().collect::<Vec<_>>().join(" ");
+                    if !text.is_empty() {
+                        let heading = format!("{} {text}", "#".repeat(level));
+                        self.out().push_str(&heading);
+                    }
+                    self.block_break();
+                }
+            }
+            "strong" | "b" => self.close_inline("**"),
+            "em" | "i" => self.close_inline("*"),
+            "s" | "del" | "strike" => self.close_inline("~~"),
+            "code" | "kbd" | "samp" => self.close_inline("`"),
+            "a" => {
+                if let Some(frame) = self.pop_frame(|k| matches!(k, FrameKind::Link(_))) {
+                    let FrameKind::Link(href) = frame.kind else {
+                        unreachable!("popped a link frame");
+                    };
+                    let text = frame.out.trim();
+                    let link = if href.is_empty() || href.starts_with("javascript:") {
+                        text.to_string()
+                    } else if text.is_empty() {
+                        format!("<{href}>")
+                    } else {
+                        format!("[{text}]({href})")
+                    };
+                    self.out().push_str(&link);
+                }
+            }
+            "pre" => {
+                if let Some(frame) = self.pop_frame(|k| matches!(k, FrameKind::Pre)) {
+                    let code = frame.out.trim_matches('\n');
+                    let fence = if code.contains("```") { "~~~" } else { "```" };
+                    let block = format!("{fence}\n{code}\n{fence}");
+                    self.out().push_str(&block);
+                    self.block_break();
+                }
+            }
+            "blockquote" => {
+                if let Some(frame) = self.pop_frame(|k| matches!(k, FrameKind::Quote)) {
+                    let quoted = frame
+                        .out
+                        .trim()
+                        .lines()
+                        .map(|line| {
+                            if line.is_empty() {
+                                ">".to_string()
+                            } else {
+                                format!("> {line}")
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    self.out().push_str(&quoted);
+                    self.block_break();
+                }
+            }
+            "ul" | "ol" => {
+                self.lists.pop();
+                self.block_break();
+            }
+            "li" => {
+                if let Some(frame) = self.pop_frame(|k| matches!(k, FrameKind::Item(_))) {
+                    let FrameKind::Item(marker) = frame.kind else {
+                        unreachable!("popped a list item frame");
+                    };
+                    let indent = " ".repeat(marker.len());
+                    let mut item = marker;
+                    for (i, line) in frame.out.trim().lines().enumerate() {
+                        if i > 0 {
+                            item.push('\n');
+                            if !line.is_empty() {
+                                item.push_str(&indent);
+                            }
+                        }
+                        item.push_str(line);
+                    }
+                    let out = self.out();
+                    if !out.is_empty() && !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str(&item);
+                    out.push('\n');
+                }
+            }
+            "thead" => {
+                if let Some(table) = self.tables.last_mut() {
+                    table.in_head = false;
+                }
+            }
+            "td" | "th" => {
+                if let Some(frame) = self.pop_frame(|k| matches!(k, FrameKind::Cell)) {
+                    let cell = frame
+                        .out
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                        .replace('|', "\\|");
+                    if let Some(row) = self.tables.last_mut().and_then(|t| t.rows.last_mut()) {
+                        row.push(cell);
+                    }
+                }
+            }
+            "table" => {
+                if let Some(table) = self.tables.pop() {
+                    let rendered = render_table(table);
+                    self.out().push_str(&rendered);
+                    self.block_break();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Close an inline marker, moving trailing spaces outside so the emphasis still applies.
+    fn close_inline(&mut self, marker: &str) {
+        let out = self.out();
+        let trimmed = out.trim_end_matches(' ').len();
+        let spaces = out.len() - trimmed;
+        out.truncate(trimmed);
+        out.push_str(marker);
+        out.push_str(&" ".repeat(spaces));
+    }
+
+    fn finish(mut self) -> String {
+        while self.frames.len() > 1 {
+            let inner = self.frames.pop().expect("inner frame");
+            self.out().push_str(&inner.out);
+        }
+        let out = self.frames.pop().map(|frame| frame.out).unwrap_or_default();
+        out.lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string()
+    }
+}
+
+/// GitHub-style pipe table; the first row is the header when the table has no `thead`.
+fn render_table(table: Table) -> String {
+    let columns = table.rows.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return String::new();
+    }
+    let header_rows = table.header_rows.max(1);
+    let mut lines = Vec::new();
+    for (i, mut row) in table.rows.into_iter().enumerate() {
+        row.resize(columns, String::new());
+        lines.push(format!("| {} |", row.join(" | ")));
+        if i + 1 == header_rows {
+            lines.push(format!("|{}", " --- |".repeat(columns)));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rich_text_bodies_become_markdown() {
+        let html = "<h2>Protocol&nbsp;</h2>\n<p>Add <strong>5&#181;l</strong> of <em>buffer_A</em>, \
+                    see <a href=\"https://example.org/sop\">the SOP</a>.<br>Incubate at 37&deg;C.</p>\
+                    <ol><li>Spin down</li><li>Wash<ul><li>twice</li></ul></li></ol>\
+                    <blockquote><p>Keep on ice.</p></blockquote>\
+                    <pre><code>x &lt; 3\n  y</code></pre><!-- editor note --><hr>";
+        assert_eq!(
+            html_to_markdown(html),
+            "## Protocol\n\n\
+             Add **5µl** of *buffer\\_A*, see [the SOP](https://example.org/sop).\\\n\
+             Incubate at 37°C.\n\n\
+             1. Spin down\n\
+             2. Wash\n   \
+             - twice\n\n\
+             > Keep on ice.\n\n\
+             ```\nx < 3\n  y\n```\n\n\
+             ---"
+        );
+    }
+
+    #[test]
+    fn tables_keep_their_cells_and_unknown_markup_keeps_its_text() {
+        let html = "<table><tbody><tr><td>Sample</td><td>OD<sub>600</sub></td></tr>\
+                    <tr><td>A|1</td><td>0.42</td></tr></tbody></table>\
+                    <script>alert(1)</script><span style=\"color:red\">Done</span> & dusted";
+        assert_eq!(
+            html_to_markdown(html),
+            "| Sample | OD600 |\n| --- | --- |\n| A\\|1 | 0.42 |\n\nDone & dusted"
+        );
+        assert_eq!(html_to_markdown("a < b"), "a < b");
+        assert_eq!(html_to_markdown(""), "");
+    }
+}
@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Language of an entry, exported as `inLanguage` and as the `lang` of the HTML preview.
+//!
+//! An override set in the editor always wins; otherwise the language is detected from the
+//! body's plain text when saving. Short or ambiguous texts get no language rather than a
+//! wrong one.
+
+use anyhow::{Result, bail};
+
+use crate::i18n::t;
+use crate::logic::readme::markdown_to_plain_text;
+
+/// Fewest characters of plain text that detection is attempted on.
+const MIN_DETECTION_CHARS: usize = 40;
+
+/// Two-letter ISO 639-1 codes of the ISO 639-3 codes `whatlang` reports; languages missing
+/// here keep their three-letter code, which is a valid language tag as well.
+const ISO_639_1: [(&str, &str); 30] = [
+    ("ara", "ar"),
+    ("ces", "cs"),
+    ("cmn", "zh"),
+    ("dan", "da"),
+    ("deu", "de"),
+    ("ell", "el"),
+    ("eng", "en"),
+    ("est", "et"),
+    ("fin", "fi"),
+    ("fra", "fr"),
+    ("heb", "he"),
+    ("hin", "hi"),
+    ("hun", "hu"),
+    ("ita", "it"),
+    ("jpn", "ja"),
+    ("kor", "ko"),
+    ("lav", "lv"),
+    ("lit", "lt"),
+    ("nld", "nl"),
+    ("nob", "nb"),
+    ("pol", "pl"),
+    ("por", "pt"),
+    ("ron", "ro"),
+    ("rus", "ru"),
+    ("slk", "sk"),
+    ("slv", "sl"),
+    ("spa", "es"),
+    ("swe", "sv"),
+    ("tur", "tr"),
+    ("ukr", "uk"),
+];
+
+/// Language tag of the markdown `body`, if it is long enough to tell reliably.
+pub fn detect_language(body: &str) -> Option<String> {
+    let text = markdown_to_plain_text(body, usize::MAX);
+    if text.chars().filter(|c| c.is_alphabetic()).count() < MIN_DETECTION_CHARS {
+        return None;
+    }
+    let info = whatlang::detect(&text).filter(whatlang::Info::is_reliable)?;
+    let code = info.lang().code();
+    let tag = ISO_639_1
+        .iter()
+        .find(|(three, _)| *three == code)
+        .map_or(code, |(_, two)| two);
+    Some(tag.to_string())
+}
+
+/// Language of an entry: the `override_tag` when set, else the one detected in `body`.
+pub fn entry_language(override_tag: Option<&str>, body: &str) -> Option<String> {
+    match override_tag {
+        Some(tag) => Some(tag.to_string()),
+        None => detect_language(body),
+    }
+}
+
+/// Check a language tag typed as override, e.g. `de` or `en-GB`; blank input means none.
+///
+/// Accepts a two- or three-letter language with optional subtags of two to eight letters
+/// or digits, and returns it with the language lowercase and a region uppercase.
+///
+/// # Errors
+///
+/// Returns an error naming the expected form when `raw` is not such a tag.
+pub fn normalize_language_tag(raw: &str) -> Result<Option<String>> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Ok(None);
+    }
+    let mut parts = raw.split(['-', '_']);
+    let language = parts.next().unwrap_or_default();
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        bail!(t!("validate.language_code"));
+    }
+    let mut tag = language.to_ascii_lowercase();
+    for subtag in parts {
+        if !(2..=8).contains(&subtag.len()) || !subtag.chars().all(|c| c.is_ascii_alphanumeric()) {
+            bail!(t!("validate.language_code"));
+        }
+        tag.push('-');
+        if subtag.len() == 2 && subtag.chars().all(|c| c.is_ascii_alphabetic()) {
+            tag.push_str(&subtag.to_ascii_uppercase());
+        } else {
+            tag.push_str(subtag);
+        }
+    }
+    Ok(Some(tag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_english_and_german_bodies() {
+        assert_eq!(
+            detect_language(
+                "# PCR\n\nThe samples were incubated overnight at **37 °C** and then \
+                 centrifuged for ten minutes before the supernatant was discarded."
+            )
+            .as_deref(),
+            Some("en")
+        );
+        assert_eq!(
+            detect_language(
+                "Die Proben wurden über Nacht bei 37 °C inkubiert und anschließend zehn \
+                 Minuten zentrifugiert, bevor der Überstand verworfen wurde."
+            )
+            .as_deref(),
+            Some("de")
+        );
+        assert_eq!(detect_language("Gel 2, lane 4"), None);
+        assert_eq!(detect_language(""), None);
+    }
+
+    #[test]
+    fn override_wins_over_detection() {
+        let english = "The samples were incubated overnight and then centrifuged for ten minutes.";
+        assert_eq!(entry_language(Some("de"), english).as_deref(), Some("de"));
+        assert_eq!(entry_language(None, english).as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn language_tags_are_checked_and_normalized() {
+        assert_eq!(normalize_language_tag("  ").unwrap(), None);
+        assert_eq!(normalize_language_tag("DE").unwrap().as_deref(), Some("de"));
+        assert_eq!(
+            normalize_language_tag("en_gb").unwrap().as_deref(),
+            Some("en-GB")
+        );
+        assert_eq!(
+            normalize_language_tag("zh-Hant").unwrap().as_deref(),
+            Some("zh-Hant")
+        );
+        assert!(normalize_language_tag("english").is_err());
+        assert!(normalize_language_tag("en-").is_err());
+    }
+}
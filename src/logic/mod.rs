@@ -3,4 +3,23 @@
 
 //! Business logic for ELN RO-Crate generation.
 
+pub mod authors;
+pub mod batch;
+pub mod draft;
+pub mod elabftw;
+pub mod elabftw_entry;
 pub mod eln;
+pub mod eln_import;
+pub mod favorites;
+pub mod html_export;
+pub mod html_markdown;
+pub mod language;
+pub mod preview;
+pub mod readme;
+pub mod save_resume;
+pub mod settings;
+pub mod signing;
+pub mod summary;
+pub mod templates;
+pub mod units;
+pub mod vocabulary;
@@ -0,0 +1,800 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Standalone HTML preview of the archive's entries, written as `index.html` into the archive root.
+//!
+//! The page is self-contained: styles are inlined, figure thumbnails are embedded as `data:`
+//! URIs, and the only links point at attachments inside the archive, so it can be read in any
+//! browser without network access.
+//!
+//! It is also meant to work with screen readers: a `nav` landmark links every section, table
+//! headers carry a `scope`, images have alt text taken from attachment descriptions, and
+//! headings of the main text are shifted below the entry title without skipping levels.
+
+use std::path::Path;
+
+use image::codecs::jpeg::JpegEncoder;
+use time::OffsetDateTime;
+use time::macros::format_description;
+
+use crate::models::attachment::Attachment;
+use crate::models::extra_fields::{ExtraField, ExtraFieldGroup};
+
+/// Inline stylesheet for the preview page.
+const PREVIEW_CSS: &str = "\
+body{font-family:system-ui,-apple-system,'Segoe UI',sans-serif;max-width:52rem;margin:2rem auto;padding:0 1rem;line-height:1.5;color:#222}\
+h1{margin-bottom:.25rem}\
+.meta{color:#666;margin-top:0}\
+.summary{font-size:1.1rem}\
+.keywords span{display:inline-block;background:#eef;border-radius:.75rem;padding:0 .6rem;margin:0 .25rem .25rem 0}\
+table{border-collapse:collapse;width:100%;margin:.5rem 0 1rem}\
+th,td{border:1px solid #ccc;padding:.3rem .5rem;text-align:left;vertical-align:top}\
+th{background:#f4f4f4}\
+tbody th{background:none;font-weight:600}\
+nav ul{margin:0;padding-left:1.25rem}\
+pre{background:#f6f6f6;padding:.75rem;overflow:auto}\
+.callout{border-left:4px solid #4a7bd0;background:#f3f7ff;padding:.25rem 1rem;margin:1rem 0}\
+.callout.warning,.callout.caution{border-color:#d08a2a;background:#fff7ec}\
+.callout.important{border-color:#c0392b;background:#fdf0ef}\
+.callout.tip{border-color:#2e9d5b;background:#effaf3}\
+figure{margin:0 0 1rem;break-inside:avoid}\
+figure img{display:block;max-width:240px;border:1px solid #ccc}\
+figcaption{color:#444;font-size:.9rem}";
+
+/// Longest side of a figure thumbnail, in pixels.
+const THUMBNAIL_SIDE: u32 = 240;
+
+/// Largest encoded thumbnail embedded in the page; figures with bigger ones are listed
+/// without an image.
+const THUMBNAIL_MAX_BYTES: usize = 48 * 1024;
+
+/// Entry content rendered into the preview page.
+pub struct PreviewContent<'a> {
+    pub title: &'a str,
+    /// Short summary shown below the title; empty when the entry has none.
+    pub summary: &'a str,
+    /// Body already rendered to sanitized HTML.
+    pub body_html: &'a str,
+    pub performed_at: OffsetDateTime,
+    pub genre: &'a str,
+    pub keywords: &'a [String],
+    pub extra_fields: &'a [ExtraField],
+    pub extra_groups: &'a [ExtraFieldGroup],
+    pub attachments: &'a [Attachment],
+    /// Archive-relative directory holding the attachments (e.g. `experiment/`).
+    pub attachment_dir: &'a str,
+    /// Language tag of the entry, if known.
+    pub language: Option<&'a str>,
+}
+
+/// Render the self-contained preview page with one section per entry.
+///
+/// The page title and language are taken from the first entry; every entry also carries its
+/// own language. All user-provided text except the pre-sanitized bodies is HTML-escaped.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let html = render_preview_html(&[content]);
+/// assert!(html.starts_with("<!DOCTYPE html>"));
+/// ```
+pub fn render_preview_html(entries: &[PreviewContent<'_>]) -> String {
+    let body_len: usize = entries.iter().map(|e| e.body_html.len()).sum();
+    let mut html = String::with_capacity(body_len + 4096 * entries.len().max(1));
+    let page_title = escape(entries.first().map_or("", |e| e.title));
+
+    html.push_str(&format!(
+        "<!DOCTYPE html>\n<html{}>\n<head>\n<meta charset=\"utf-8\">\n",
+        lang_attribute(entries.first().and_then(|e| e.language))
+    ));
+    html.push_str(
+        "<meta http-equiv=\"Content-Security-Policy\" content=\"default-src 'none'; style-src 'unsafe-inline'; img-src 'self' data:\">\n",
+    );
+    html.push_str(&format!(
+        "<title>{page_title}</title>\n<style>{PREVIEW_CSS}</style>\n"
+    ));
+    html.push_str("</head>\n<body>\n");
+    render_nav(&mut html, entries);
+    html.push_str("<main>\n");
+
+    for (index, content) in entries.iter().enumerate() {
+        if index > 0 {
+            html.push_str("<hr>\n");
+        }
+        render_entry(&mut html, index, content);
+    }
+
+    html.push_str("</main>\n</body>\n</html>\n");
+    html
+}
+
+/// Sections of an entry in page order, as `(id suffix, heading)`; empty ones are left out.
+fn sections(content: &PreviewContent<'_>) -> Vec<(&'static str, &'static str)> {
+    [
+        ("body", "Main text", !content.body_html.trim().is_empty()),
+        ("metadata", "Metadata", !content.extra_fields.is_empty()),
+        (
+            "figures",
+            "Figures",
+            !figure_list(content.attachments).is_empty(),
+        ),
+        (
+            "attachments",
+            "Attachments",
+            !content.attachments.is_empty(),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, _, present)| *present)
+    .map(|(id, heading, _)| (id, heading))
+    .collect()
+}
+
+/// Append the `nav` landmark linking every section; with several entries the links are
+/// grouped below each entry's title.
+fn render_nav(html: &mut String, entries: &[PreviewContent<'_>]) {
+    html.push_str("<nav aria-label=\"Contents\">\n<ul>\n");
+    for (index, content) in entries.iter().enumerate() {
+        let entry = index + 1;
+        let links: String = sections(content)
+            .into_iter()
+            .map(|(id, heading)| format!("<li><a href=\"#entry-{entry}-{id}\">{heading}</a></li>"))
+            .collect();
+        if entries.len() == 1 {
+            html.push_str(&links);
+        } else {
+            html.push_str(&format!(
+                "<li><a href=\"#entry-{entry}\">{}</a><ul>{links}</ul></li>",
+                escape(content.title)
+            ));
+        }
+        html.push('\n');
+    }
+    html.push_str("</ul>\n</nav>\n");
+}
+
+/// Open the section `id` of entry number `entry`, labelled `heading`.
+fn open_section(html: &mut String, entry: usize, id: &str, heading: &str) {
+    html.push_str(&format!(
+        "<section id=\"entry-{entry}-{id}\" aria-label=\"{heading}\">\n"
+    ));
+}
+
+/// Append one entry as an `<article>` with its metadata, body, fields and attachments, each
+/// in a `<section>` the navigation links to.
+fn render_entry(html: &mut String, index: usize, content: &PreviewContent<'_>) {
+    let entry = index + 1;
+    html.push_str(&format!("<article{}>\n", lang_attribute(content.language)));
+    html.push_str(&format!(
+        "<h1 id=\"entry-{entry}\">{}</h1>\n",
+        escape(content.title)
+    ));
+    html.push_str(&format!(
+        "<p class=\"meta\">{} &middot; performed {}</p>\n",
+        escape(content.genre),
+        format_timestamp(content.performed_at)
+    ));
+    if !content.summary.is_empty() {
+        html.push_str(&format!(
+            "<p class=\"summary\">{}</p>\n",
+            escape(content.summary)
+        ));
+    }
+
+    if !content.keywords.is_empty() {
+        html.push_str("<p class=\"keywords\">");
+        for keyword in content.keywords {
+            html.push_str(&format!("<span>{}</span>", escape(keyword)));
+        }
+        html.push_str("</p>\n");
+    }
+
+    for (id, heading) in sections(content) {
+        open_section(html, entry, id, heading);
+        match id {
+            "body" => {
+                html.push_str("<div class=\"body\">\n");
+                html.push_str(&accessible_body(
+                    content.body_html,
+                    content.attachments,
+                    content.attachment_dir,
+                ));
+                html.push_str("\n</div>\n");
+            }
+            "metadata" => render_extra_fields(html, content.extra_fields, content.extra_groups),
+            "figures" => render_figures(html, content.attachments, content.attachment_dir),
+            _ => render_attachments(html, content.attachments, content.attachment_dir),
+        }
+        html.push_str("</section>\n");
+    }
+    html.push_str("</article>\n");
+}
+
+/// Adapt the sanitized body for assistive technology: headings start at `<h2>` below the
+/// entry title and skip no level, header cells get `scope="col"`, and images without alt
+/// text are described by their attachment.
+fn accessible_body(body_html: &str, attachments: &[Attachment], dir: &str) -> String {
+    let body = shift_headings(body_html);
+    let body = body
+        .replace("<th>", "<th scope=\"col\">")
+        .replace("<th ", "<th scope=\"col\" ");
+    describe_images(&body, attachments, dir)
+}
+
+/// Renumber body headings in document order so they start at `h2` and each one is at most
+/// one level below the heading before it.
+fn shift_headings(body_html: &str) -> String {
+    let mut out = String::with_capacity(body_html.len());
+    // Open sections as (level in the body, level in the preview).
+    let mut outline: Vec<(u8, u8)> = Vec::new();
+    let mut rest = body_html;
+    while let Some((start, level)) = next_heading(rest) {
+        out.push_str(&rest[..start]);
+        while outline.last().is_some_and(|&(body, _)| body > level) {
+            outline.pop();
+        }
+        let shifted = match outline.last() {
+            Some(&(body, shifted)) if body == level => shifted,
+            Some(&(_, shifted)) => (shifted + 1).min(6),
+            None => 2,
+        };
+        if outline.last().is_none_or(|&(body, _)| body != level) {
+            outline.push((level, shifted));
+        }
+        let after = &rest[start + 3..];
+        let close = format!("</h{level}>");
+        let len = after.find(&close).unwrap_or(after.len());
+        out.push_str(&format!("<h{shifted}{}", &after[..len]));
+        rest = &after[len..];
+        if rest.starts_with(&close) {
+            out.push_str(&format!("</h{shifted}>"));
+            rest = &rest[close.len()..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Offset and level of the next `<h1>`–`<h6>` start tag in `html`.
+fn next_heading(html: &str) -> Option<(usize, u8)> {
+    html.match_indices("<h")
+        .find_map(|(start, _)| match &html.as_bytes()[start + 2..] {
+            [level @ b'1'..=b'6', b'>' | b' ', ..] => Some((start, level - b'0')),
+            _ => None,
+        })
+}
+
+/// Fill empty or missing `alt` attributes of body images that show an attachment with its
+/// description, or its path when it has none.
+fn describe_images(body_html: &str, attachments: &[Attachment], dir: &str) -> String {
+    let mut out = String::with_capacity(body_html.len());
+    let mut rest = body_html;
+    while let Some(start) = rest.find("<img ") {
+        let Some(len) = rest[start..].find('>') else {
+            break;
+        };
+        let (before, tag, after) = (
+            &rest[..start],
+            &rest[start..start + len],
+            &rest[start + len..],
+        );
+        out.push_str(before);
+        let src = attribute(tag, "src").unwrap_or_default();
+        let alt = attribute(tag, "alt");
+        let attachment = attachments.iter().find(|att| {
+            let target = src.strip_prefix("./").unwrap_or(src);
+            target == attachment_href(att, dir) || target == format!("{dir}{}", att.archive_path())
+        });
+        match (alt, attachment) {
+            (None | Some(""), Some(att)) => {
+                let text = escape(att.description.as_deref().unwrap_or(&att.archive_path()));
+                let tag = tag.replace(" alt=\"\"", "");
+                let tag = tag.trim_end_matches('/').trim_end();
+                out.push_str(&format!("{tag} alt=\"{text}\""));
+            }
+            _ => out.push_str(tag),
+        }
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Value of the double-quoted attribute `name` in the start tag `tag`.
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!(" {name}=\""))? + name.len() + 3;
+    let len = tag[start..].find('"')?;
+    Some(&tag[start..start + len])
+}
+
+/// Append extra fields as one table per group, ordered like the editor.
+fn render_extra_fields(html: &mut String, fields: &[ExtraField], groups: &[ExtraFieldGroup]) {
+    if fields.is_empty() {
+        return;
+    }
+    html.push_str("<h2>Metadata</h2>\n");
+
+    let mut ordered_groups: Vec<&ExtraFieldGroup> = groups.iter().collect();
+    ordered_groups.sort_by_key(|g| (g.position, g.id));
+
+    let mut sections: Vec<(String, Vec<&ExtraField>)> = ordered_groups
+        .iter()
+        .map(|group| {
+            let members = fields
+                .iter()
+                .filter(|f| f.group_id == Some(group.id))
+                .collect();
+            (group.name.clone(), members)
+        })
+        .collect();
+    let ungrouped: Vec<&ExtraField> = fields
+        .iter()
+        .filter(|f| {
+            !f.group_id
+                .is_some_and(|id| groups.iter().any(|g| g.id == id))
+        })
+        .collect();
+    sections.push(("Other".to_string(), ungrouped));
+
+    for (name, mut members) in sections {
+        if members.is_empty() {
+            continue;
+        }
+        members.sort_by(|a, b| a.cmp_key().cmp(&b.cmp_key()));
+        html.push_str(&format!("<h3>{}</h3>\n<table>\n", escape(&name)));
+        html.push_str(
+            "<thead><tr><th scope=\"col\">Field</th><th scope=\"col\">Value</th></tr></thead>\n<tbody>\n",
+        );
+        for field in members {
+            html.push_str(&format!(
+                "<tr><th scope=\"row\">{}</th><td>{}</td></tr>\n",
+                escape(&field.label),
+                escape(&display_value(field))
+            ));
+        }
+        html.push_str("</tbody>\n</table>\n");
+    }
+}
+
+/// Image attachment listed under Figures.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Figure<'a> {
+    /// 1-based figure number.
+    pub number: usize,
+    pub attachment: &'a Attachment,
+}
+
+/// Number the image attachments in attachment order, skipping those left out of the list.
+pub fn figure_list(attachments: &[Attachment]) -> Vec<Figure<'_>> {
+    attachments
+        .iter()
+        .filter(|att| att.mime.starts_with("image/") && !att.exclude_from_figures)
+        .enumerate()
+        .map(|(index, attachment)| Figure {
+            number: index + 1,
+            attachment,
+        })
+        .collect()
+}
+
+/// Append the figure list: a thumbnail linking to each image and its caption, or its path
+/// when it has none.
+fn render_figures(html: &mut String, attachments: &[Attachment], dir: &str) {
+    let figures = figure_list(attachments);
+    if figures.is_empty() {
+        return;
+    }
+    html.push_str("<h2>Figures</h2>\n");
+    for Figure { number, attachment } in figures {
+        let caption = attachment
+            .description
+            .clone()
+            .unwrap_or_else(|| attachment.archive_path());
+        html.push_str(&format!("<figure id=\"figure-{number}\">"));
+        if let Some(uri) = thumbnail_data_uri(&attachment.path) {
+            html.push_str(&format!(
+                "<a href=\"{}\"><img src=\"{uri}\" alt=\"{}\"></a>",
+                attachment_href(attachment, dir),
+                escape(&caption)
+            ));
+        }
+        html.push_str(&format!(
+            "<figcaption><strong>Figure {number}.</strong> {}</figcaption></figure>\n",
+            escape(&caption)
+        ));
+    }
+}
+
+/// Small JPEG of the image at `path` as a `data:` URI; `None` when it cannot be decoded
+/// (e.g. SVG) or the thumbnail exceeds [`THUMBNAIL_MAX_BYTES`].
+fn thumbnail_data_uri(path: &Path) -> Option<String> {
+    let mut thumbnail = image::open(path)
+        .ok()?
+        .thumbnail(THUMBNAIL_SIDE, THUMBNAIL_SIDE)
+        .to_rgba8();
+    // JPEG has no alpha channel; put transparent areas on white instead of black.
+    for pixel in thumbnail.pixels_mut() {
+        let alpha = u16::from(pixel[3]);
+        for channel in &mut pixel.0[..3] {
+            *channel = ((u16::from(*channel) * alpha + 255 * (255 - alpha)) / 255) as u8;
+        }
+    }
+    let rgb = image::DynamicImage::ImageRgba8(thumbnail).to_rgb8();
+    let mut bytes = Vec::new();
+    rgb.write_with_encoder(JpegEncoder::new_with_quality(&mut bytes, 80))
+        .ok()?;
+    (bytes.len() <= THUMBNAIL_MAX_BYTES)
+        .then(|| format!("data:image/jpeg;base64,{}", base64_encode(&bytes)))
+}
+
+/// Standard base64 with padding, as used in `data:` URIs.
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let triple = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, &b)| acc | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(triple >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Relative link to an attachment below the archive-internal `dir`.
+fn attachment_href(attachment: &Attachment, dir: &str) -> String {
+    let path = attachment
+        .archive_path()
+        .split('/')
+        .map(url_path_segment)
+        .collect::<Vec<_>>()
+        .join("/");
+    format!("{dir}{path}")
+}
+
+/// Append a table linking the attachments stored in the archive with their acquisition time.
+///
+/// `dir` is an archive-internal constant and is not escaped.
+fn render_attachments(html: &mut String, attachments: &[Attachment], dir: &str) {
+    if attachments.is_empty() {
+        return;
+    }
+    html.push_str("<h2>Attachments</h2>\n<table>\n");
+    html.push_str(
+        "<thead><tr><th scope=\"col\">File</th><th scope=\"col\">Type</th><th scope=\"col\">Acquired</th></tr></thead>\n<tbody>\n",
+    );
+    for attachment in attachments {
+        let name = escape(&attachment.archive_path());
+        let acquired = attachment
+            .acquired_at
+            .map(format_timestamp)
+            .unwrap_or_default();
+        html.push_str(&format!(
+            "<tr><th scope=\"row\"><a href=\"{}\">{name}</a></th><td>{}</td><td>{acquired}</td></tr>\n",
+            attachment_href(attachment, dir),
+            escape(&attachment.mime)
+        ));
+    }
+    html.push_str("</tbody>\n</table>\n");
+}
+
+/// ` lang="<tag>"` for a known language, else nothing.
+fn lang_attribute(language: Option<&str>) -> String {
+    language
+        .map(|tag| format!(" lang=\"{}\"", escape(tag)))
+        .unwrap_or_default()
+}
+
+/// Field value as shown to readers, including multi-values and the unit.
+fn display_value(field: &ExtraField) -> String {
+    let value = if field.allow_multi_values && !field.value_multi.is_empty() {
+        field.value_multi.join(", ")
+    } else {
+        field.value.clone()
+    };
+    match &field.unit {
+        Some(unit) if !unit.is_empty() && !value.is_empty() => format!("{value} {unit}"),
+        _ => value,
+    }
+}
+
+/// Format a timestamp as `YYYY-MM-DD HH:MM UTC`.
+fn format_timestamp(at: OffsetDateTime) -> String {
+    let format = format_description!("[year]-[month]-[day] [hour]:[minute] UTC");
+    at.to_offset(time::UtcOffset::UTC)
+        .format(&format)
+        .unwrap_or_else(|_| at.to_string())
+}
+
+/// Escape text for HTML element content and attribute values.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Percent-encode characters that are not safe in a relative URL path segment.
+fn url_path_segment(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for byte in name.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn field(label: &str, value: &str, group_id: Option<i32>) -> ExtraField {
+        ExtraField {
+            label: label.into(),
+            value: value.into(),
+            group_id,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn preview_escapes_text_and_groups_fields() {
+        let fields = vec![
+            field("Temp", "37", Some(1)),
+            field("Loose <b>", "x & y", None),
+        ];
+        let groups = vec![ExtraFieldGroup {
+            id: 1,
+            name: "Conditions".into(),
+            position: 0,
+        }];
+        let attachments = vec![
+            crate::models::attachment::Attachment::new(
+                PathBuf::from("/tmp/a b.txt"),
+                "a b.txt".into(),
+                "text/plain".into(),
+                "unavailable".into(),
+                1,
+            )
+            .with_acquired_at(Some(OffsetDateTime::UNIX_EPOCH + time::Duration::days(1))),
+        ];
+
+        let html = render_preview_html(&[PreviewContent {
+            title: "<script>x</script>",
+            summary: "Viability of <HeLa> cells",
+            body_html: "<p>Body</p>",
+            performed_at: OffsetDateTime::UNIX_EPOCH,
+            genre: "experiment",
+            keywords: &["k1".into()],
+            extra_fields: &fields,
+            extra_groups: &groups,
+            attachments: &attachments,
+            attachment_dir: "experiment/",
+            language: Some("de"),
+        }]);
+
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("<html lang=\"de\">") && html.contains("<article lang=\"de\">"));
+        assert!(html.contains("1970-01-01 00:00 UTC"));
+        assert!(html.contains("<p class=\"summary\">Viability of &lt;HeLa&gt; cells</p>"));
+        assert!(html.contains("<h3>Conditions</h3>"));
+        assert!(html.contains("<h3>Other</h3>"));
+        assert!(html.contains("x &amp; y"));
+        assert!(html.contains("href=\"experiment/a%20b.txt\""));
+        assert!(html.contains("<td>1970-01-02 00:00 UTC</td>"));
+        assert!(!html.contains("http://") && !html.contains("https://"));
+    }
+
+    fn image(dir: &std::path::Path, name: &str) -> crate::models::attachment::Attachment {
+        let path = dir.join(name);
+        image::RgbaImage::from_pixel(600, 300, image::Rgba([200, 30, 30, 128]))
+            .save(&path)
+            .unwrap();
+        crate::models::attachment::Attachment::new(
+            path,
+            name.into(),
+            "image/png".into(),
+            "unavailable".into(),
+            1,
+        )
+    }
+
+    #[test]
+    fn figure_list_numbers_included_images_in_attachment_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let table = crate::models::attachment::Attachment::new(
+            PathBuf::from("/tmp/od.csv"),
+            "od.csv".into(),
+            "text/csv".into(),
+            "unavailable".into(),
+            1,
+        );
+        let attachments = vec![
+            image(dir.path(), "gel.png").with_description(Some("Agarose <gel>".into())),
+            table,
+            image(dir.path(), "blot.png").excluded_from_figures(true),
+            image(dir.path(), "scan.png").in_folder("raw".into()),
+        ];
+
+        let numbered: Vec<(usize, String)> = figure_list(&attachments)
+            .iter()
+            .map(|f| (f.number, f.attachment.archive_path()))
+            .collect();
+        assert_eq!(
+            numbered,
+            [(1, "gel.png".to_string()), (2, "raw/scan.png".to_string())]
+        );
+
+        let html = render_preview_html(&[PreviewContent {
+            title: "Gel",
+            summary: "",
+            body_html: "",
+            performed_at: OffsetDateTime::UNIX_EPOCH,
+            genre: "experiment",
+            keywords: &[],
+            extra_fields: &[],
+            extra_groups: &[],
+            attachments: &attachments,
+            attachment_dir: "experiment/",
+            language: None,
+        }]);
+        let first = html
+            .find("<strong>Figure 1.</strong> Agarose &lt;gel&gt;")
+            .unwrap();
+        let second = html
+            .find("<strong>Figure 2.</strong> raw/scan.png")
+            .unwrap();
+        assert!(first < second);
+        assert!(!html.contains("Figure 3") && !html.contains("blot.png</figcaption>"));
+        assert!(html.contains("<a href=\"experiment/gel.png\"><img src=\"data:image/jpeg;base64,"));
+        assert!(html.find("<h2>Figures</h2>") < html.find("<h2>Attachments</h2>"));
+    }
+
+    /// A start tag's name and its attributes.
+    type StartTag = (String, Vec<(String, String)>);
+
+    /// Start tags of `html` with their attributes, as a browser's tokenizer sees them.
+    fn start_tags(html: &str) -> Vec<StartTag> {
+        use html5ever::tendril::StrTendril;
+        use html5ever::tokenizer::{
+            BufferQueue, TagKind, Token, TokenSink, TokenSinkResult, Tokenizer, TokenizerOpts,
+        };
+        use std::cell::RefCell;
+
+        #[derive(Default)]
+        struct Tags(RefCell<Vec<StartTag>>);
+
+        impl TokenSink for Tags {
+            type Handle = ();
+
+            fn process_token(&self, token: Token, _: u64) -> TokenSinkResult<()> {
+                if let Token::TagToken(tag) = token
+                    && tag.kind == TagKind::StartTag
+                {
+                    let attrs = tag
+                        .attrs
+                        .iter()
+                        .map(|a| (a.name.local.to_string(), a.value.to_string()))
+                        .collect();
+                    self.0.borrow_mut().push((tag.name.to_string(), attrs));
+                }
+                TokenSinkResult::Continue
+            }
+        }
+
+        let tokenizer = Tokenizer::new(Tags::default(), TokenizerOpts::default());
+        let input = BufferQueue::default();
+        input.push_back(StrTendril::from_slice(html));
+        let _ = tokenizer.feed(&input);
+        tokenizer.end();
+        tokenizer.sink.0.take()
+    }
+
+    fn attr<'a>(attrs: &'a [(String, String)], name: &str) -> Option<&'a str> {
+        attrs
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn preview_is_navigable_with_assistive_technology() {
+        let dir = tempfile::tempdir().unwrap();
+        let attachments = vec![
+            image(dir.path(), "gel.png").with_description(Some("Agarose gel".into())),
+            image(dir.path(), "scan.png").in_folder("raw".into()),
+        ];
+        let fields = vec![field("Temp", "37", None)];
+        let body = "<h3>Setup</h3><h5>Buffers</h5><p><img src=\"./experiment/raw/scan.png\" alt=\"\"></p>\
+                    <table><thead><tr><th>Lane</th><th align=\"right\">kDa</th></tr></thead></table>\
+                    <h4>Run</h4>";
+        let entry = |title| PreviewContent {
+            title,
+            summary: "",
+            body_html: body,
+            performed_at: OffsetDateTime::UNIX_EPOCH,
+            genre: "experiment",
+            keywords: &[],
+            extra_fields: &fields,
+            extra_groups: &[],
+            attachments: &attachments,
+            attachment_dir: "experiment/",
+            language: None,
+        };
+
+        for entries in [vec![entry("Gel")], vec![entry("Gel"), entry("Blot")]] {
+            let html = render_preview_html(&entries);
+            let tags = start_tags(&html);
+            let ids: Vec<&str> = tags.iter().filter_map(|(_, a)| attr(a, "id")).collect();
+
+            let navs: Vec<usize> = (0..tags.len()).filter(|&i| tags[i].0 == "nav").collect();
+            assert_eq!(navs.len(), 1);
+            let main = tags.iter().position(|(name, _)| name == "main").unwrap();
+            let targets: Vec<&str> = tags[navs[0]..main]
+                .iter()
+                .filter_map(|(_, a)| attr(a, "href")?.strip_prefix('#'))
+                .collect();
+            for section in ["body", "metadata", "figures", "attachments"] {
+                for entry in 1..=entries.len() {
+                    assert!(targets.contains(&format!("entry-{entry}-{section}").as_str()));
+                }
+            }
+            assert!(
+                targets.iter().all(|target| ids.contains(target)),
+                "{targets:?}"
+            );
+
+            for (name, attrs) in &tags {
+                match name.as_str() {
+                    "img" => assert!(attr(attrs, "alt").is_some_and(|alt| !alt.is_empty())),
+                    "th" => assert!(matches!(attr(attrs, "scope"), Some("col" | "row"))),
+                    _ => {}
+                }
+            }
+            assert!(tags.iter().any(|(name, a)| name == "img"
+                && attr(a, "src") == Some("./experiment/raw/scan.png")
+                && attr(a, "alt") == Some("raw/scan.png")));
+            assert!(
+                tags.iter()
+                    .any(|(_, a)| attr(a, "alt") == Some("Agarose gel"))
+            );
+
+            let levels: Vec<u8> = tags
+                .iter()
+                .filter_map(|(name, _)| name.strip_prefix('h')?.parse().ok())
+                .collect();
+            assert_eq!(levels.first(), Some(&1));
+            assert!(
+                levels.windows(2).all(|pair| pair[1] <= pair[0] + 1),
+                "{levels:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn base64_pads_partial_groups() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+}
@@ -0,0 +1,480 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Plain-text `README.txt` for the archive root, for repositories that require plain-text
+//! documentation next to the crate metadata.
+//!
+//! The body is rendered from markdown to plain text and wrapped at [`README_WIDTH`] columns;
+//! tables and code blocks keep their lines so columns stay aligned.
+
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::models::attachment::Attachment;
+use crate::models::author::Author;
+
+/// Line width of the generated README.
+pub const README_WIDTH: usize = 80;
+
+/// Entry content written into the README.
+pub struct ReadmeEntry<'a> {
+    pub title: &'a str,
+    pub performed_at: OffsetDateTime,
+    pub keywords: &'a [String],
+    /// Markdown body.
+    pub body: &'a str,
+    pub attachments: &'a [Attachment],
+    /// Archive-relative directory holding the attachments (e.g. `experiment/`).
+    pub attachment_dir: &'a str,
+}
+
+/// Render the README with one section per entry.
+///
+/// Authors and the license apply to the whole archive and are listed under the first
+/// entry only; an empty license is left out.
+pub fn render_readme(entries: &[ReadmeEntry<'_>], authors: &[Author], license: &str) -> String {
+    let mut sections = Vec::with_capacity(entries.len());
+    for (index, entry) in entries.iter().enumerate() {
+        let mut lines = vec![entry.title.trim().to_string()];
+        lines.push("=".repeat(entry.title.trim().chars().count().clamp(3, README_WIDTH)));
+        lines.push(String::new());
+        lines.push(format!(
+            "Performed: {}",
+            entry.performed_at.format(&Rfc3339).unwrap_or_default()
+        ));
+        if index == 0 {
+            let names: Vec<String> = authors.iter().map(Author::display_name).collect();
+            if !names.is_empty() {
+                lines.push(wrap(
+                    &names.join(", "),
+                    README_WIDTH,
+                    "Authors: ",
+                    "         ",
+                ));
+            }
+            if !license.trim().is_empty() {
+                lines.push(format!("License: {}", license.trim()));
+            }
+        }
+        if !entry.keywords.is_empty() {
+            lines.push(wrap(
+                &entry.keywords.join(", "),
+                README_WIDTH,
+                "Keywords: ",
+                "          ",
+            ));
+        }
+
+        let body = markdown_to_plain_text(entry.body, README_WIDTH);
+        if !body.is_empty() {
+            lines.push(String::new());
+            lines.push(body);
+        }
+
+        if !entry.attachments.is_empty() {
+            lines.push(String::new());
+            lines.push("Attachments".into());
+            lines.push("-----------".into());
+            for attachment in entry.attachments {
+                lines.push(format!(
+                    "{}{}",
+                    entry.attachment_dir,
+                    attachment.archive_path()
+                ));
+                lines.push(format!(
+                    "    {} bytes, {}, sha256 {}",
+                    attachment.size, attachment.mime, attachment.sha256
+                ));
+            }
+        }
+        sections.push(lines.join("\n"));
+    }
+    let mut readme = sections.join("\n\n\n");
+    readme.push('\n');
+    readme
+}
+
+/// Render markdown as plain text, wrapping paragraphs, list items and quotes at `width`.
+///
+/// Emphasis and inline code lose their markers, links keep their target in parentheses,
+/// lists are indented below their marker, tables become aligned `|`-separated columns, and
+/// code blocks are indented by four spaces. Raw HTML is dropped.
+pub fn markdown_to_plain_text(markdown: &str, width: usize) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_FOOTNOTES);
+    options.insert(Options::ENABLE_TASKLISTS);
+    let mut renderer = PlainText {
+        width,
+        ..PlainText::default()
+    };
+    for event in Parser::new_ext(markdown, options) {
+        renderer.event(event);
+    }
+    renderer.lines.join("\n")
+}
+
+/// Wrap `text` into lines of at most `width` characters.
+///
+/// The first line starts with `first`, later ones with `rest`; whitespace runs collapse to
+/// single spaces and `\n` forces a break. Words longer than a line are kept whole.
+pub fn wrap(text: &str, width: usize, first: &str, rest: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for segment in text.split('\n') {
+        let prefix = if lines.is_empty() { first } else { rest };
+        let mut line = prefix.to_string();
+        let mut line_len = prefix.chars().count();
+        let mut empty = true;
+        for word in segment.split_whitespace() {
+            let word_len = word.chars().count();
+            if !empty && line_len + 1 + word_len > width {
+                lines.push(std::mem::replace(&mut line, rest.to_string()));
+                line_len = rest.chars().count();
+                empty = true;
+            }
+            if !empty {
+                line.push(' ');
+                line_len += 1;
+            }
+            line.push_str(word);
+            line_len += word_len;
+            empty = false;
+        }
+        lines.push(line.trim_end().to_string());
+    }
+    lines.join("\n")
+}
+
+/// Event consumer building the plain-text lines.
+#[derive(Default)]
+struct PlainText {
+    width: usize,
+    lines: Vec<String>,
+    /// Text of the paragraph, heading, item or table cell being read.
+    inline: String,
+    /// Put a blank line before the next block.
+    gap: bool,
+    quote_depth: usize,
+    /// Next number of each open list; `None` for bullet lists.
+    lists: Vec<Option<u64>>,
+    /// Continuation indent of each open list item.
+    indents: Vec<String>,
+    /// Marker line prefix of a list item whose text has not been written yet.
+    marker: Option<String>,
+    /// Code block being read.
+    code: Option<String>,
+    /// Rows of the table being read; the first is the header.
+    table: Option<Vec<Vec<String>>>,
+    /// Link and image targets with the `inline` length where their label starts.
+    links: Vec<(String, usize)>,
+}
+
+impl PlainText {
+    fn event(&mut self, event: Event<'_>) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => match self.code.as_mut() {
+                Some(code) => code.push_str(&text),
+                None => self.inline.push_str(&text),
+            },
+            Event::Code(text) | Event::InlineMath(text) | Event::DisplayMath(text) => {
+                self.inline.push_str(&text)
+            }
+            Event::SoftBreak => self.inline.push(' '),
+            Event::HardBreak => self.inline.push('\n'),
+            Event::FootnoteReference(label) => self.inline.push_str(&format!("[{label}]")),
+            Event::TaskListMarker(checked) => {
+                self.inline.push_str(if checked { "[x] " } else { "[ ] " })
+            }
+            Event::Rule => {
+                self.flush_inline();
+                let rule = format!("{}{}", self.prefix(), "-".repeat(20));
+                self.emit(vec![rule]);
+            }
+            Event::Html(_) | Event::InlineHtml(_) => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Paragraph | Tag::Heading { .. } => self.flush_inline(),
+            Tag::BlockQuote(_) => {
+                self.flush_inline();
+                self.quote_depth += 1;
+            }
+            Tag::CodeBlock(_) => {
+                self.flush_inline();
+                self.code = Some(String::new());
+            }
+            Tag::List(start) => {
+                // Text of the enclosing item comes before its nested list.
+                self.flush_inline();
+                if self.indents.is_empty() {
+                    self.gap = true;
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush_inline();
+                let indent = self.indents.last().cloned().unwrap_or_default();
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "- ".to_string(),
+                };
+                self.indents
+                    .push(format!("{indent}{}", " ".repeat(marker.chars().count())));
+                self.marker = Some(format!("{indent}{marker}"));
+            }
+            Tag::Table(_) => {
+                self.flush_inline();
+                self.table = Some(Vec::new());
+            }
+            Tag::TableHead | Tag::TableRow => {
+                if let Some(table) = self.table.as_mut() {
+                    table.push(Vec::new());
+                }
+            }
+            Tag::TableCell => self.inline.clear(),
+            Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                self.links.push((dest_url.to_string(), self.inline.len()));
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph | TagEnd::Item => self.flush_inline(),
+            TagEnd::Heading(level) => {
+                let text = std::mem::take(&mut self.inline);
+                let text = text.trim();
+                let prefix = self.prefix();
+                let mut block = vec![wrap(text, self.width, &prefix, &prefix)];
+                let underline = match level {
+                    HeadingLevel::H1 => Some('='),
+                    HeadingLevel::H2 => Some('-'),
+                    _ => None,
+                };
+                if let Some(ch) = underline {
+                    let len = text.chars().count().min(self.width);
+                    block.push(format!("{prefix}{}", ch.to_string().repeat(len)));
+                }
+                self.emit(block);
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush_inline();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+            }
+            TagEnd::CodeBlock => {
+                let code = self.code.take().unwrap_or_default();
+                let prefix = format!("{}    ", self.prefix());
+                let block = code
+                    .trim_end_matches('\n')
+                    .lines()
+                    .map(|line| format!("{prefix}{line}").trim_end().to_string())
+                    .collect();
+                self.emit(block);
+            }
+            TagEnd::List(_) => {
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.gap = true;
+                }
+            }
+            TagEnd::TableCell => {
+                let cell = std::mem::take(&mut self.inline);
+                if let Some(row) = self.table.as_mut().and_then(|table| table.last_mut()) {
+                    row.push(cell.split_whitespace().collect::<Vec<_>>().join(" "));
+                }
+            }
+            TagEnd::Table => {
+                let rows = self.table.take().unwrap_or_default();
+                let block = render_table(&rows, &self.prefix());
+                self.emit(block);
+            }
+            TagEnd::Link | TagEnd::Image => {
+                if let Some((dest, start)) = self.links.pop() {
+                    let label = self.inline.get(start..).unwrap_or_default().trim();
+                    if label != dest && !dest.is_empty() {
+                        self.inline.push_str(&format!(" ({dest})"));
+                    }
+                }
+            }
+            _ => {}
+        }
+        if tag == TagEnd::Item {
+            self.indents.pop();
+            self.marker = None;
+        }
+    }
+
+    /// Quote markers and list indent in front of every line of the current block.
+    fn prefix(&self) -> String {
+        format!(
+            "{}{}",
+            "> ".repeat(self.quote_depth),
+            self.indents.last().map_or("", String::as_str)
+        )
+    }
+
+    /// Wrap and emit the collected inline text as one block.
+    fn flush_inline(&mut self) {
+        let text = std::mem::take(&mut self.inline);
+        if text.trim().is_empty() {
+            return;
+        }
+        let quote = "> ".repeat(self.quote_depth);
+        let rest = self.prefix();
+        let first = match self.marker.take() {
+            Some(marker) => format!("{quote}{marker}"),
+            None => rest.clone(),
+        };
+        let wrapped = wrap(text.trim(), self.width, &first, &rest);
+        self.emit(vec![wrapped]);
+    }
+
+    /// Append `block`, separated by a blank line from the previous one outside lists.
+    fn emit(&mut self, block: Vec<String>) {
+        if self.gap && !self.lines.is_empty() {
+            self.lines.push(String::new());
+        }
+        self.gap = self.indents.is_empty();
+        self.lines.extend(block);
+    }
+}
+
+/// Lay out `rows` as columns padded to their widest cell, the header underlined.
+fn render_table(rows: &[Vec<String>], prefix: &str) -> Vec<String> {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|col| {
+            rows.iter()
+                .filter_map(|row| row.get(col))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let mut lines = Vec::with_capacity(rows.len() + 1);
+    for (index, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(col, width)| {
+                let cell = row.get(col).map_or("", String::as_str);
+                format!("{cell}{}", " ".repeat(width - cell.chars().count()))
+            })
+            .collect();
+        lines.push(
+            format!("{prefix}{}", cells.join(" | "))
+                .trim_end()
+                .to_string(),
+        );
+        if index == 0 {
+            let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+            lines.push(format!("{prefix}{}", rule.join("-+-")));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn wrap_breaks_at_the_width_and_keeps_long_words() {
+        let text = "The quick brown fox jumps over the lazy dog";
+        assert_eq!(
+            wrap(text, 16, "- ", "  "),
+            "- The quick\n  brown fox\n  jumps over the\n  lazy dog"
+        );
+        assert_eq!(
+            wrap("see https://example.org/a/very/long/path now", 12, "", ""),
+            "see\nhttps://example.org/a/very/long/path\nnow"
+        );
+        assert_eq!(wrap("one\ntwo", 80, "> ", "> "), "> one\n> two");
+        let long = "word ".repeat(40);
+        assert!(
+            wrap(&long, README_WIDTH, "", "")
+                .lines()
+                .all(|line| line.chars().count() <= README_WIDTH)
+        );
+    }
+
+    #[test]
+    fn nested_lists_indent_below_their_marker() {
+        let markdown =
+            "Steps:\n\n1. Mix **buffer**\n   - 10 mM Tris\n   - 1 mM `EDTA`\n2. Spin down\n\nDone.";
+        assert_eq!(
+            markdown_to_plain_text(markdown, 80),
+            "Steps:\n\n1. Mix buffer\n   - 10 mM Tris\n   - 1 mM EDTA\n2. Spin down\n\nDone."
+        );
+
+        let long = format!("- {}", "lorem ipsum ".repeat(10));
+        let text = markdown_to_plain_text(&long, 40);
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].starts_with("- lorem"));
+        assert!(lines[1..].iter().all(|line| line.starts_with("  lorem")));
+    }
+
+    #[test]
+    fn tables_become_aligned_columns() {
+        let markdown = "| Sample | OD600 |\n|---|---|\n| A | 0.41 |\n| Blank | 0 |";
+        assert_eq!(
+            markdown_to_plain_text(markdown, 80),
+            "Sample | OD600\n-------+------\nA      | 0.41\nBlank  | 0"
+        );
+    }
+
+    #[test]
+    fn links_keep_their_target_and_headings_are_underlined() {
+        let markdown = "# Results\n\nSee [the protocol](https://example.org/p) and <https://example.org>.\n\n![gel](./gel.png)\n\n> quoted *note*";
+        assert_eq!(
+            markdown_to_plain_text(markdown, 80),
+            "Results\n=======\n\nSee the protocol (https://example.org/p) and https://example.org.\n\ngel (./gel.png)\n\n> quoted note"
+        );
+    }
+
+    #[test]
+    fn readme_lists_metadata_body_and_attachments() {
+        let attachments = vec![
+            Attachment::new(
+                PathBuf::from("/data/od.csv"),
+                "od.csv".into(),
+                "text/csv".into(),
+                "abc123".into(),
+                42,
+            )
+            .in_folder("raw".into()),
+        ];
+        let keywords = vec!["growth".to_string(), "E. coli".to_string()];
+        let entry = ReadmeEntry {
+            title: "Growth curve",
+            performed_at: OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap(),
+            keywords: &keywords,
+            body: "Measured **OD600**.",
+            attachments: &attachments,
+            attachment_dir: "experiment/",
+        };
+        let author = Author {
+            given_name: "Ada".into(),
+            family_name: "Lovelace".into(),
+            ..Default::default()
+        };
+
+        let readme = render_readme(&[entry], &[author], "CC-BY-4.0");
+        assert_eq!(
+            readme,
+            "Growth curve\n============\n\nPerformed: 2023-11-14T22:13:20Z\nAuthors: Ada Lovelace\nLicense: CC-BY-4.0\nKeywords: growth, E. coli\n\nMeasured OD600.\n\nAttachments\n-----------\nexperiment/raw/od.csv\n    42 bytes, text/csv, sha256 abc123\n"
+        );
+    }
+}
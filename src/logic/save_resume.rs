@@ -0,0 +1,248 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Checkpoints that let an interrupted archive save continue where it stopped.
+//!
+//! A resumable save finalizes the partial archive, `<archive>.partial`, every
+//! [`CHECKPOINT_BYTES`] of attachments and records next to it, in `<archive>.resume.json`, which attachments it already holds,
+//! their digests, and the zip central directory at that point. A later attempt at the same
+//! save restores that central directory, re-hashes the attachments already written, and only
+//! appends the rest. When anything no longer matches, the save starts over instead.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zip::{ZipArchive, ZipWriter};
+
+use crate::logic::eln::ArchiveEntry;
+use crate::utils::hash::{Digests, copy_and_digest};
+use crate::utils::storage::write_atomic;
+
+/// Attachment bytes written between two checkpoints of a resumable save.
+pub const CHECKPOINT_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Format version of the state file; other versions are not resumed.
+const STATE_VERSION: u32 = 1;
+
+/// Path of the resume state of `archive`, e.g. `entry.eln.resume.json`.
+pub fn state_path(archive: &Path) -> PathBuf {
+    let mut name = archive.as_os_str().to_owned();
+    name.push(".resume.json");
+    PathBuf::from(name)
+}
+
+/// Path `archive` is written to until it is complete, e.g. `entry.eln.partial`.
+///
+/// The finished archive replaces whatever is at `archive` only then, so a failed save never
+/// damages an archive saved earlier.
+pub fn partial_path(archive: &Path) -> PathBuf {
+    let mut name = archive.as_os_str().to_owned();
+    name.push(".partial");
+    PathBuf::from(name)
+}
+
+/// Whether an interrupted save left a checkpoint of `archive` to resume from.
+pub fn has_resume_state(archive: &Path) -> bool {
+    state_path(archive).is_file()
+}
+
+/// Delete the resume state of `archive`, if there is one.
+///
+/// # Errors
+///
+/// Returns an error when an existing state file cannot be removed.
+pub fn remove_resume_state(archive: &Path) -> Result<()> {
+    let path = state_path(archive);
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err).with_context(|| format!("Failed to remove resume state {:?}", path)),
+    }
+}
+
+/// Attachment the partial archive holds completely.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompletedFile {
+    /// Full path inside the archive.
+    pub path: String,
+    /// Digests computed while the attachment was written.
+    pub digests: Digests,
+}
+
+/// Content of the state file written at every checkpoint.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResumeState {
+    pub version: u32,
+    /// [`plan_fingerprint`] of the save, telling whether a retry writes the same files.
+    pub plan: String,
+    /// Bytes of the archive before its central directory.
+    pub data_len: u64,
+    /// Central directory and end record of the checkpoint, hex encoded.
+    pub central_directory: String,
+    /// Attachments written before the checkpoint, in write order.
+    pub completed: Vec<CompletedFile>,
+}
+
+impl ResumeState {
+    /// Read the state left next to `archive`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the file cannot be read or parsed.
+    pub fn load(archive: &Path) -> Result<Self> {
+        let path = state_path(archive);
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read resume state {:?}", path))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("Failed to parse resume state {:?}", path))
+    }
+
+    fn store(&self, archive: &Path) -> Result<()> {
+        write_atomic(&state_path(archive), &serde_json::to_vec_pretty(self)?)
+    }
+}
+
+/// Fingerprint of everything a save writes before its final metadata: the root folder, the
+/// entry folders, and every attachment's archive path, size, SHA-256, and digest algorithms.
+///
+/// Titles, bodies, and fields are only written at the end, so editing them between attempts
+/// does not prevent resuming.
+pub fn plan_fingerprint(root_prefix: &str, entries: &[ArchiveEntry], sha512: bool) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{root_prefix}\n{}\n{sha512}\n", entries.len()));
+    for (index, entry) in entries.iter().enumerate() {
+        for meta in &entry.attachments {
+            let algorithms: Vec<&str> = meta.digests.keys().map(|a| a.key()).collect();
+            hasher.update(format!(
+                "{index}\t{}\t{}\t{}\t{}\n",
+                meta.archive_path(),
+                meta.size,
+                meta.sha256,
+                algorithms.join(",")
+            ));
+        }
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Partial archive reopened for appending by [`restore`].
+pub struct Restored {
+    pub zip: ZipWriter<File>,
+    /// Names already in the archive, including directories.
+    pub names: HashSet<String>,
+    /// Digests of the attachments already written, by archive path.
+    pub completed: HashMap<String, Digests>,
+    /// The same attachments in write order, carried into later checkpoints.
+    pub state: Vec<CompletedFile>,
+}
+
+/// Reopen the partial archive of `output`, see [`partial_path`], as of its last checkpoint.
+///
+/// Cuts off whatever was written after the checkpoint, puts its central directory back, and
+/// re-hashes every attachment the state lists.
+///
+/// # Errors
+///
+/// Returns an error describing the first inconsistency: a state of another version or save
+/// `plan`, a partial archive that is shorter than recorded or unreadable, or an attachment
+/// that is missing, unexpected, or no longer matches its digests.
+pub fn restore(output: &Path, plan: &str) -> Result<Restored> {
+    let state = ResumeState::load(output)?;
+    if state.version != STATE_VERSION {
+        bail!("the checkpoint was written by another version of ELNPack");
+    }
+    if state.plan != plan {
+        bail!("the attachments changed since the interrupted save");
+    }
+    let central_directory =
+        hex::decode(&state.central_directory).context("the checkpoint is damaged")?;
+    let partial = partial_path(output);
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(&partial)
+        .with_context(|| format!("Failed to open the partial archive {:?}", partial))?;
+    if file.metadata()?.len() < state.data_len {
+        bail!("the partial archive is shorter than its checkpoint");
+    }
+    file.set_len(state.data_len)?;
+    file.seek(SeekFrom::End(0))?;
+    file.write_all(&central_directory)?;
+
+    let mut archive = ZipArchive::new(&mut file).context("the partial archive is not readable")?;
+    let completed: HashMap<String, Digests> = state
+        .completed
+        .iter()
+        .map(|file| (file.path.clone(), file.digests.clone()))
+        .collect();
+    let names: HashSet<String> = archive.file_names().map(str::to_string).collect();
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let Some(expected) = completed.get(entry.name()) else {
+            bail!(
+                "the partial archive holds the unexpected file {}",
+                entry.name()
+            );
+        };
+        let algorithms: Vec<_> = expected.keys().copied().collect();
+        let (digests, _) = copy_and_digest(&mut entry, &mut std::io::sink(), &algorithms)
+            .with_context(|| format!("{} could not be read", entry.name()))?;
+        if &digests != expected {
+            bail!("{} no longer matches its checkpoint", entry.name());
+        }
+    }
+    if let Some(missing) = completed.keys().find(|path| !names.contains(*path)) {
+        bail!("{missing} is missing from the partial archive");
+    }
+    drop(archive);
+
+    let zip = ZipWriter::new_append(file).context("Failed to reopen the partial archive")?;
+    Ok(Restored {
+        zip,
+        names,
+        completed,
+        state: state.completed,
+    })
+}
+
+/// Finalize the archive written by `zip` so far, record it as a checkpoint of `output`, and
+/// reopen it for appending.
+///
+/// # Errors
+///
+/// Returns an error when the archive cannot be finalized, reread, or reopened, or the state
+/// file cannot be written.
+pub fn checkpoint(
+    zip: ZipWriter<File>,
+    output: &Path,
+    plan: &str,
+    completed: &[CompletedFile],
+) -> Result<ZipWriter<File>> {
+    let mut file = zip.finish().context("Failed to checkpoint archive")?;
+    let data_len = ZipArchive::new(&mut file)
+        .context("Failed to reread checkpointed archive")?
+        .central_directory_start();
+    file.seek(SeekFrom::Start(data_len))?;
+    let mut central_directory = Vec::new();
+    file.read_to_end(&mut central_directory)?;
+    // The state must never describe data that is not on disk yet.
+    file.sync_data()
+        .context("Failed to flush checkpointed archive")?;
+    ResumeState {
+        version: STATE_VERSION,
+        plan: plan.to_string(),
+        data_len,
+        central_directory: hex::encode(central_directory),
+        completed: completed.to_vec(),
+    }
+    .store(output)?;
+    ZipWriter::new_append(file).context("Failed to reopen checkpointed archive")
+}
@@ -0,0 +1,789 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Persistent user settings stored as schema-versioned JSON in the config directory.
+//!
+//! Every section uses `#[serde(default)]` so files written by older versions keep loading
+//! when new settings are added. [`SettingsExport`] carries them to other machines, e.g. to
+//! configure every computer in a lab the same way.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::i18n::t;
+use crate::logic::eln::{
+    ArchiveGenre, BodyFormat, CompressionOptions, PublisherInfo, RoCrateVersion,
+};
+use crate::logic::favorites::FavoriteSettings;
+use crate::logic::html_export::HtmlExportSettings;
+use crate::logic::templates::TemplateSettings;
+use crate::logic::units::UnitSettings;
+use crate::models::attachment::AttachmentPolicy;
+use crate::models::keywords::KeywordSettings;
+use crate::models::license::License;
+use crate::models::performed_at::DEFAULT_DATE_HORIZON_YEARS;
+use crate::utils::hash::HashAlgorithm;
+use crate::utils::storage::write_atomic;
+
+/// Version of the on-disk settings schema.
+pub const SETTINGS_SCHEMA_VERSION: u32 = 1;
+
+/// Version of the settings export format.
+pub const SETTINGS_EXPORT_VERSION: u32 = 1;
+
+/// Archives listed under "Recent archives".
+pub const MAX_RECENT_ARCHIVES: usize = 10;
+
+/// All user-configurable settings.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Schema version for forward compatibility checks.
+    pub version: u32,
+    /// Advanced options for the exported HTML body.
+    pub html_export: HtmlExportSettings,
+    /// Extra content written alongside the entry when saving.
+    pub archive: ArchiveSettings,
+    /// Organization credited as publisher of saved archives.
+    pub publisher: PublisherInfo,
+    /// Plausibility checks applied before saving.
+    pub validation: ValidationSettings,
+    /// Window layout preferences.
+    pub appearance: AppearanceSettings,
+    /// Favorite folders for file dialogs.
+    pub favorites: FavoriteSettings,
+    /// Main window geometry restored at startup.
+    pub window: WindowSettings,
+    /// Choices new entries start with.
+    pub defaults: EntryDefaults,
+    /// eLabFTW instance the archives are meant for.
+    pub elabftw: ElabftwSettings,
+    /// Digests computed for new attachments.
+    pub hashing: HashingSettings,
+    /// Recently applied entry templates.
+    pub templates: TemplateSettings,
+    /// Archives saved most recently.
+    pub recent_archives: RecentArchives,
+    /// UCUM codes for units missing from the bundled table.
+    pub units: UnitSettings,
+    /// Extra fields whose values are added to the keywords when saving.
+    pub keywords: KeywordSettings,
+    /// Attachment types refused when adding, and the size from which files are flagged.
+    pub attachments: AttachmentPolicy,
+}
+
+/// The user's eLabFTW instance.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ElabftwSettings {
+    /// Base address such as `https://elab.example.org`; empty when not configured.
+    pub url: String,
+    /// Import each saved archive into the instance; the API key is stored separately.
+    pub upload_after_save: bool,
+}
+
+/// Options controlling what is written into saved archives.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArchiveSettings {
+    /// Write a standalone `index.html` preview into the archive root.
+    pub include_preview: bool,
+    /// Store title, date and keywords in the zip archive comment.
+    ///
+    /// Off by default: the comment is readable without opening the archive.
+    pub write_zip_comment: bool,
+    /// RO-Crate version of the metadata; 1.1 serves older consumers.
+    pub crate_version: RoCrateVersion,
+    /// Write a `checksums.sha512` manifest of all attachments into the archive root.
+    pub checksums_manifest: bool,
+    /// Write a plain-text `README.txt` into the archive root.
+    pub include_readme: bool,
+    /// License named in `README.txt`; empty when not stated.
+    pub license: String,
+    /// Unencrypted OpenSSH Ed25519 key that signs saved archives; unsigned when `None`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing_key: Option<PathBuf>,
+    /// Reopen each saved archive and check every file against its metadata.
+    pub verify_after_save: bool,
+    /// Copy the metadata file extra fields were imported from into the entry folder.
+    pub include_imported_metadata: bool,
+    /// Compression of the files in saved archives.
+    pub compression: CompressionOptions,
+}
+
+impl Default for ArchiveSettings {
+    fn default() -> Self {
+        Self {
+            include_preview: false,
+            write_zip_comment: false,
+            crate_version: RoCrateVersion::default(),
+            checksums_manifest: false,
+            include_readme: false,
+            license: String::new(),
+            signing_key: None,
+            verify_after_save: true,
+            include_imported_metadata: false,
+            compression: CompressionOptions::default(),
+        }
+    }
+}
+
+/// Digest algorithms computed for new attachments.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HashingSettings {
+    /// Algorithms computed besides SHA-256, which every archive needs.
+    pub extra: BTreeSet<HashAlgorithm>,
+    /// Digest shown in the attachments list; always computed.
+    pub primary: HashAlgorithm,
+}
+
+impl HashingSettings {
+    /// Algorithms to compute for a new attachment: SHA-256, the primary and the extras.
+    pub fn algorithms(&self) -> Vec<HashAlgorithm> {
+        let mut algorithms = self.extra.clone();
+        algorithms.insert(HashAlgorithm::Sha256);
+        algorithms.insert(self.primary);
+        algorithms.into_iter().collect()
+    }
+}
+
+/// Window layout preferences.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppearanceSettings {
+    /// Tighter spacing and icon-only toolbar for small screens.
+    pub compact_mode: bool,
+    /// Light, dark, or following the operating system.
+    pub theme: ThemeSetting,
+    /// Show a panel listing which fonts can render a probe text, to diagnose missing glyphs.
+    pub show_font_coverage: bool,
+    /// Language of the user interface.
+    pub language: Language,
+}
+
+/// Language of the user interface; English is used for texts without a translation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "de")]
+    German,
+}
+
+impl Language {
+    /// Every supported language, in the order of the language selector.
+    pub const ALL: [Self; 2] = [Self::English, Self::German];
+
+    /// Name of the language in that language, as shown in the selector.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::German => "Deutsch",
+        }
+    }
+}
+
+/// Color theme preference.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeSetting {
+    /// Follow the operating system.
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+/// Main window geometry in logical points.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowSettings {
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+}
+
+impl WindowSettings {
+    /// Smallest size restored at startup, guarding against unusable stored values.
+    pub const MIN_SIZE: [u32; 2] = [600, 400];
+
+    /// Stored size clamped to [`Self::MIN_SIZE`].
+    pub fn inner_size(&self) -> [f32; 2] {
+        [
+            self.width.max(Self::MIN_SIZE[0]) as f32,
+            self.height.max(Self::MIN_SIZE[1]) as f32,
+        ]
+    }
+}
+
+impl Default for WindowSettings {
+    fn default() -> Self {
+        Self {
+            width: 1024,
+            height: 768,
+            maximized: false,
+        }
+    }
+}
+
+/// Paths of the archives saved most recently, newest first.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecentArchives {
+    /// Up to [`MAX_RECENT_ARCHIVES`] paths.
+    pub paths: Vec<PathBuf>,
+}
+
+impl RecentArchives {
+    /// Move `path` to the front of the list; returns whether the list changed.
+    pub fn record(&mut self, path: &Path) -> bool {
+        let before = self.paths.clone();
+        self.paths.retain(|known| known != path);
+        self.paths.insert(0, path.to_path_buf());
+        self.paths.truncate(MAX_RECENT_ARCHIVES);
+        self.paths != before
+    }
+
+    /// Remove `paths` from the list; returns whether the list changed.
+    pub fn forget(&mut self, paths: &[PathBuf]) -> bool {
+        let before = self.paths.len();
+        self.paths.retain(|known| !paths.contains(known));
+        self.paths.len() != before
+    }
+}
+
+/// Body format, genre, and license new entries start with; updated whenever the user picks
+/// one.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EntryDefaults {
+    pub body_format: BodyFormat,
+    pub genre: ArchiveGenre,
+    pub license: License,
+}
+
+/// Thresholds for save-time plausibility warnings.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValidationSettings {
+    /// Performed-at dates older than this many years need confirmation; `0` disables the check.
+    pub date_horizon_years: u32,
+}
+
+impl Default for ValidationSettings {
+    fn default() -> Self {
+        Self {
+            date_horizon_years: DEFAULT_DATE_HORIZON_YEARS,
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_SCHEMA_VERSION,
+            html_export: HtmlExportSettings::default(),
+            archive: ArchiveSettings::default(),
+            publisher: PublisherInfo::default(),
+            validation: ValidationSettings::default(),
+            appearance: AppearanceSettings::default(),
+            favorites: FavoriteSettings::default(),
+            window: WindowSettings::default(),
+            defaults: EntryDefaults::default(),
+            elabftw: ElabftwSettings::default(),
+            hashing: HashingSettings::default(),
+            templates: TemplateSettings::default(),
+            recent_archives: RecentArchives::default(),
+            units: UnitSettings::default(),
+            keywords: KeywordSettings::default(),
+            attachments: AttachmentPolicy::default(),
+        }
+    }
+}
+
+/// Normalize an eLabFTW address to `scheme://host[/path]` without a trailing slash.
+///
+/// Blank input stays empty (no instance configured) and a missing scheme defaults to
+/// `https://`.
+///
+/// # Errors
+///
+/// Returns an error when the address cannot be parsed, has no host, or uses a scheme
+/// other than `http` or `https`.
+pub fn normalize_instance_url(raw: &str) -> Result<String> {
+    normalize_web_address(raw, "eLabFTW address")
+}
+
+/// [`normalize_instance_url`] for any web address, named `label` in errors.
+///
+/// # Errors
+///
+/// Returns the errors of [`normalize_instance_url`].
+pub fn normalize_web_address(raw: &str, label: &str) -> Result<String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Ok(String::new());
+    }
+    let candidate = if trimmed.contains("://") {
+        trimmed.to_string()
+    } else {
+        format!("https://{trimmed}")
+    };
+    let url = match Url::parse(&candidate) {
+        Ok(url) if url.host_str().is_some_and(|host| !host.is_empty()) => url,
+        _ => bail!("{trimmed} is not a valid web address."),
+    };
+    if !matches!(url.scheme(), "http" | "https") {
+        bail!("{label} {trimmed} must start with https:// or http://.");
+    }
+    Ok(url.as_str().trim_end_matches('/').to_string())
+}
+
+/// Resolver prefix of ROR identifiers.
+const ROR_URL_PREFIX: &str = "https://ror.org/";
+
+/// Digits of ROR identifiers: Crockford's base32, lowercase.
+const ROR_ALPHABET: &str = "0123456789abcdefghjkmnpqrstvwxyz";
+
+/// Normalize a ROR identifier such as `05a28rw58` or `ror.org/05a28rw58` to its
+/// `https://ror.org/…` form; blank input stays empty.
+///
+/// # Errors
+///
+/// Returns an error when the identifier is not nine characters starting with `0`, uses
+/// characters outside the ROR alphabet, or its two-digit checksum does not match.
+pub fn normalize_ror(raw: &str) -> Result<String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Ok(String::new());
+    }
+    let lower = trimmed.to_ascii_lowercase();
+    let id = lower
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("ror.org/");
+    let invalid = || anyhow::anyhow!("{trimmed} is not a valid ROR identifier.");
+    if id.len() != 9 || !id.starts_with('0') {
+        return Err(invalid());
+    }
+    let (body, checksum) = id.split_at(7);
+    let mut value: u64 = 0;
+    for c in body.chars() {
+        let digit = ROR_ALPHABET.find(c).ok_or_else(invalid)?;
+        value = value * 32 + digit as u64;
+    }
+    // ISO/IEC 7064 MOD 97-10 over the base32 value.
+    let expected = 98 - (value * 100) % 97;
+    if !checksum.bytes().all(|b| b.is_ascii_digit()) || checksum.parse::<u64>()? != expected {
+        return Err(invalid());
+    }
+    Ok(format!("{ROR_URL_PREFIX}{id}"))
+}
+
+/// Load settings from `path`, returning defaults when the file does not exist.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be read, is not valid JSON, or was written by a
+/// newer, incompatible schema version.
+pub fn load_settings(path: &Path) -> Result<Settings> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Settings::default()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to read settings {:?}", path));
+        }
+    };
+
+    let mut settings: Settings =
+        serde_json::from_str(&content).context("Settings file is corrupt")?;
+    if settings.version > SETTINGS_SCHEMA_VERSION {
+        anyhow::bail!(
+            "Settings were written by a newer ELNPack (schema {}, supported {})",
+            settings.version,
+            SETTINGS_SCHEMA_VERSION
+        );
+    }
+    settings.version = SETTINGS_SCHEMA_VERSION;
+    Ok(settings)
+}
+
+/// Serialize and atomically write `settings` to `path`.
+///
+/// # Errors
+///
+/// Returns an error when serialization or the atomic write fails.
+pub fn save_settings(path: &Path, settings: &Settings) -> Result<()> {
+    let bytes = serde_json::to_vec_pretty(settings).context("Failed to serialize settings")?;
+    write_atomic(path, &bytes)
+}
+
+/// Settings written by "Export settings…" for use on other machines.
+///
+/// Window geometry, theme, language, remembered dialog folders, and the signing key belong to
+/// the machine and the person using it, so they are neither exported nor overwritten on import.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SettingsExport {
+    /// Format version for forward compatibility checks.
+    pub export_version: u32,
+    pub settings: Settings,
+    /// Only present when the user chose to include it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elabftw_api_key: Option<String>,
+}
+
+impl SettingsExport {
+    /// Capture the portable part of `settings`, plus `api_key` when given.
+    pub fn new(settings: &Settings, api_key: Option<&str>) -> Self {
+        Self {
+            export_version: SETTINGS_EXPORT_VERSION,
+            settings: Self::keep_local(settings.clone(), &Settings::default()),
+            elabftw_api_key: api_key.map(str::to_string),
+        }
+    }
+
+    /// `current` with the exported settings applied and its machine-specific parts kept.
+    pub fn apply_to(&self, current: &Settings) -> Settings {
+        Self::keep_local(self.settings.clone(), current)
+    }
+
+    /// Names of the sections of `current` that importing would change.
+    pub fn changes(&self, current: &Settings) -> Vec<&'static str> {
+        let next = self.apply_to(current);
+        let mut changes = Vec::new();
+        let sections = [
+            (
+                next.defaults != current.defaults,
+                t!("settings.section.defaults"),
+            ),
+            (
+                next.archive != current.archive,
+                t!("settings.section.archive"),
+            ),
+            (
+                next.publisher != current.publisher,
+                t!("settings.publisher"),
+            ),
+            (next.hashing != current.hashing, t!("settings.checksums")),
+            (
+                next.html_export != current.html_export,
+                t!("settings.advanced"),
+            ),
+            (
+                next.validation != current.validation,
+                t!("settings.validation"),
+            ),
+            (
+                next.favorites != current.favorites,
+                t!("settings.favorites"),
+            ),
+            (next.elabftw != current.elabftw, "eLabFTW"),
+            (
+                next.appearance != current.appearance,
+                t!("settings.section.compact"),
+            ),
+            (next.units != current.units, t!("settings.units")),
+            (
+                next.keywords != current.keywords,
+                t!("settings.keyword_fields"),
+            ),
+            (
+                next.attachments != current.attachments,
+                t!("settings.attachment_policy"),
+            ),
+        ];
+        for (changed, name) in sections {
+            if changed {
+                changes.push(name);
+            }
+        }
+        changes
+    }
+
+    /// `settings` with window, theme, language, remembered folders, recent templates and
+    /// archives, and signing key taken from `local`.
+    fn keep_local(mut settings: Settings, local: &Settings) -> Settings {
+        settings.version = SETTINGS_SCHEMA_VERSION;
+        settings.archive.signing_key = local.archive.signing_key.clone();
+        settings.window = local.window.clone();
+        settings.appearance.theme = local.appearance.theme;
+        settings.appearance.language = local.appearance.language;
+        settings.favorites.last_dirs = local.favorites.last_dirs.clone();
+        settings.favorites.last_used = local.favorites.last_used.clone();
+        settings.templates = local.templates.clone();
+        settings.recent_archives = local.recent_archives.clone();
+        settings
+    }
+}
+
+/// Write `export` to `path` as pretty-printed JSON.
+///
+/// # Errors
+///
+/// Returns an error when serialization or the atomic write fails.
+pub fn write_settings_export(path: &Path, export: &SettingsExport) -> Result<()> {
+    let bytes = serde_json::to_vec_pretty(export).context("Failed to serialize settings")?;
+    write_atomic(path, &bytes)
+}
+
+/// Read a settings export from `path`.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be read, is not a settings export, or was
+/// written by a newer ELNPack.
+pub fn read_settings_export(path: &Path) -> Result<SettingsExport> {
+    let content = fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+    let export: SettingsExport =
+        serde_json::from_str(&content).context("Not an ELNPack settings export")?;
+    if export.export_version > SETTINGS_EXPORT_VERSION
+        || export.settings.version > SETTINGS_SCHEMA_VERSION
+    {
+        bail!("Settings were exported by a newer ELNPack");
+    }
+    Ok(export)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn recent_archives_keep_the_latest_ten_and_forget_missing_ones() {
+        let mut recent = RecentArchives::default();
+        for i in 0..12 {
+            assert!(recent.record(Path::new(&format!("/data/run{i}.eln"))));
+        }
+        assert_eq!(recent.paths.len(), MAX_RECENT_ARCHIVES);
+        assert_eq!(recent.paths[0], Path::new("/data/run11.eln"));
+
+        assert!(recent.record(Path::new("/data/run5.eln")));
+        assert_eq!(recent.paths[0], Path::new("/data/run5.eln"));
+        assert_eq!(recent.paths.len(), MAX_RECENT_ARCHIVES);
+        assert!(!recent.record(Path::new("/data/run5.eln")));
+
+        assert!(recent.forget(&[PathBuf::from("/data/run5.eln")]));
+        assert!(!recent.forget(&[PathBuf::from("/data/run5.eln")]));
+        assert_eq!(recent.paths.len(), MAX_RECENT_ARCHIVES - 1);
+    }
+
+    #[test]
+    fn missing_file_yields_defaults() {
+        let tmp = TempDir::new().unwrap();
+        let settings = load_settings(&tmp.path().join("settings.json")).unwrap();
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[test]
+    fn settings_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("settings.json");
+        let mut settings = Settings::default();
+        settings.html_export.extra_classes = vec!["theme-box".into()];
+
+        save_settings(&path, &settings).unwrap();
+
+        assert_eq!(load_settings(&path).unwrap(), settings);
+    }
+
+    #[test]
+    fn window_theme_defaults_and_folders_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("settings.json");
+        let mut settings = Settings::default();
+        settings.appearance.theme = ThemeSetting::Dark;
+        settings.window = WindowSettings {
+            width: 1600,
+            height: 900,
+            maximized: true,
+        };
+        settings.defaults = EntryDefaults {
+            body_format: BodyFormat::Markdown,
+            genre: ArchiveGenre::Resource,
+            license: License::Cc0,
+        };
+        settings.favorites.last_dirs.insert(
+            crate::logic::favorites::PickerKind::Metadata,
+            "/data/exports".into(),
+        );
+
+        save_settings(&path, &settings).unwrap();
+
+        assert_eq!(load_settings(&path).unwrap(), settings);
+    }
+
+    #[test]
+    fn hashing_always_includes_sha256_and_the_primary() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("settings.json");
+        let mut settings = Settings::default();
+        assert_eq!(settings.hashing.algorithms(), vec![HashAlgorithm::Sha256]);
+
+        settings.hashing.extra.insert(HashAlgorithm::Blake3);
+        settings.hashing.primary = HashAlgorithm::Sha512;
+        settings.archive.checksums_manifest = true;
+        assert_eq!(
+            settings.hashing.algorithms(),
+            vec![
+                HashAlgorithm::Sha256,
+                HashAlgorithm::Sha512,
+                HashAlgorithm::Blake3
+            ]
+        );
+
+        save_settings(&path, &settings).unwrap();
+        let json = fs::read_to_string(&path).unwrap();
+        assert!(json.contains(r#""primary": "sha512""#), "{json}");
+        assert_eq!(load_settings(&path).unwrap(), settings);
+    }
+
+    #[test]
+    fn corrupt_file_is_reported_and_tiny_windows_are_clamped() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("settings.json");
+        fs::write(&path, "{\"version\": 1, \"window\": ").unwrap();
+        assert!(load_settings(&path).is_err());
+
+        let window = WindowSettings {
+            width: 10,
+            height: 2000,
+            maximized: false,
+        };
+        assert_eq!(window.inner_size(), [600.0, 2000.0]);
+    }
+
+    #[test]
+    fn instance_urls_are_normalized_or_rejected() {
+        assert_eq!(normalize_instance_url("  ").unwrap(), "");
+        assert_eq!(
+            normalize_instance_url("elab.example.org/").unwrap(),
+            "https://elab.example.org"
+        );
+        assert_eq!(
+            normalize_instance_url("http://lab.local:3148/elabftw/").unwrap(),
+            "http://lab.local:3148/elabftw"
+        );
+        assert!(normalize_instance_url("ftp://elab.example.org").is_err());
+        assert!(normalize_instance_url("https://").is_err());
+        assert!(normalize_instance_url("not a url").is_err());
+    }
+
+    #[test]
+    fn ror_identifiers_are_normalized_and_checksummed() {
+        assert_eq!(normalize_ror(" ").unwrap(), "");
+        for raw in [
+            "05a28rw58",
+            "ror.org/05a28rw58",
+            "https://ror.org/05A28RW58",
+        ] {
+            assert_eq!(
+                normalize_ror(raw).unwrap(),
+                "https://ror.org/05a28rw58",
+                "{raw}"
+            );
+        }
+        assert_eq!(
+            normalize_ror("013meh722").unwrap(),
+            "https://ror.org/013meh722"
+        );
+        for raw in [
+            "05a28rw59",
+            "15a28rw58",
+            "05a28rw5",
+            "05a28iw58",
+            "05a28rw+8",
+        ] {
+            assert!(normalize_ror(raw).is_err(), "{raw}");
+        }
+    }
+
+    #[test]
+    fn partial_files_fill_in_defaults() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("settings.json");
+        fs::write(&path, r#"{"version": 1}"#).unwrap();
+
+        assert_eq!(load_settings(&path).unwrap(), Settings::default());
+    }
+
+    #[test]
+    fn exports_round_trip_without_machine_specific_parts() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("lab-settings.json");
+        let mut source = Settings::default();
+        source.elabftw.url = "https://elab.example.org".into();
+        source.defaults.genre = ArchiveGenre::Resource;
+        source.archive.license = "CC-BY-4.0".into();
+        source.window.width = 2400;
+        source.appearance.theme = ThemeSetting::Dark;
+        source.appearance.language = Language::German;
+        source.archive.signing_key = Some("/home/alice/.ssh/id_ed25519".into());
+
+        write_settings_export(&path, &SettingsExport::new(&source, None)).unwrap();
+        let export = read_settings_export(&path).unwrap();
+
+        let mut local = Settings::default();
+        local.window.width = 1280;
+        local.appearance.theme = ThemeSetting::Light;
+        local.archive.signing_key = Some("/home/bob/.ssh/elnpack".into());
+        assert_eq!(
+            export.changes(&local),
+            ["Entry defaults", "Archive options", "eLabFTW"]
+        );
+        let imported = export.apply_to(&local);
+        assert_eq!(imported.elabftw.url, "https://elab.example.org");
+        assert_eq!(imported.defaults.genre, ArchiveGenre::Resource);
+        assert_eq!(imported.archive.license, "CC-BY-4.0");
+        assert_eq!(imported.window.width, 1280, "window geometry stays local");
+        assert_eq!(imported.appearance.theme, ThemeSetting::Light);
+        assert_eq!(imported.appearance.language, Language::English);
+        assert_eq!(
+            imported.archive.signing_key, local.archive.signing_key,
+            "signing keys stay local"
+        );
+        assert!(export.changes(&imported).is_empty());
+    }
+
+    #[test]
+    fn api_key_is_exported_only_on_request() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("lab-settings.json");
+
+        write_settings_export(&path, &SettingsExport::new(&Settings::default(), None)).unwrap();
+        let json = fs::read_to_string(&path).unwrap();
+        assert!(!json.contains("api_key"), "{json}");
+        assert_eq!(read_settings_export(&path).unwrap().elabftw_api_key, None);
+
+        let export = SettingsExport::new(&Settings::default(), Some("3-secret"));
+        write_settings_export(&path, &export).unwrap();
+        assert_eq!(
+            read_settings_export(&path)
+                .unwrap()
+                .elabftw_api_key
+                .as_deref(),
+            Some("3-secret")
+        );
+
+        fs::write(&path, r#"{"export_version": 2, "settings": {}}"#).unwrap();
+        let err = read_settings_export(&path).unwrap_err();
+        assert!(err.to_string().contains("newer ELNPack"), "{err}");
+    }
+
+    #[test]
+    fn newer_schema_is_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("settings.json");
+        fs::write(&path, r#"{"version": 99}"#).unwrap();
+
+        assert!(load_settings(&path).is_err());
+    }
+}
@@ -0,0 +1,404 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Detached SSH signatures proving that a saved archive was not altered after export.
+//!
+//! The signature is written next to the archive as `<archive>.sig` in the format of
+//! `ssh-keygen -Y sign`, so it can also be checked without ELNPack:
+//! `ssh-keygen -Y check-novalidate -n file -f key.pub -s entry.eln.sig < entry.eln`.
+//! The signing key's fingerprint is recorded on the archive's metadata descriptor, telling a
+//! verifier which key to expect. That record travels with the archive, so it only proves
+//! authorship when the verifier trusts the key independently. Only unencrypted Ed25519 keys
+//! are supported. Archives are hashed as a stream, so their size does not matter.
+
+use std::fs::{self, File};
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde_json::Value;
+use signature::{Signer as _, Verifier as _};
+use ssh_key::{Algorithm, HashAlg, LineEnding, PrivateKey, PublicKey, SshSig};
+
+use crate::i18n::t;
+use crate::logic::eln_import::read_crate_metadata;
+use crate::utils::hash::{HashAlgorithm, copy_and_digest};
+use crate::utils::storage::write_atomic;
+
+/// Namespace signed together with the archive; `ssh-keygen` uses `file` for files.
+pub const SIGNATURE_NAMESPACE: &str = "file";
+
+/// Property of the metadata descriptor holding the signing key's fingerprint.
+pub const FINGERPRINT_PROPERTY: &str = "signingKeyFingerprint";
+
+/// Outcome of a signature that matches its archive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Signer {
+    /// Made with the key the verifier trusts; holds its fingerprint.
+    Trusted(String),
+    /// Made with a key nobody vouched for; holds its fingerprint. Anyone who alters an
+    /// archive can re-sign it with their own key and record that key in the metadata.
+    Untrusted(String),
+}
+
+/// Path of the signature sidecar of `archive`, e.g. `entry.eln.sig`.
+pub fn signature_path(archive: &Path) -> PathBuf {
+    let mut name = archive.as_os_str().to_owned();
+    name.push(".sig");
+    PathBuf::from(name)
+}
+
+/// Read the OpenSSH private key at `path` for signing archives.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be read or parsed, is protected by a passphrase,
+/// or holds a key type other than Ed25519.
+pub fn load_signing_key(path: &Path) -> Result<PrivateKey> {
+    let key = PrivateKey::read_openssh_file(path)
+        .with_context(|| format!("Failed to read signing key {:?}", path))?;
+    if key.is_encrypted() {
+        bail!(
+            "Signing key {:?} is protected by a passphrase, which ELNPack cannot ask for.",
+            path
+        );
+    }
+    if key.algorithm() != Algorithm::Ed25519 {
+        bail!(
+            "Signing key {:?} is a {} key; only Ed25519 keys are supported.",
+            path,
+            key.algorithm()
+        );
+    }
+    Ok(key)
+}
+
+/// SHA-256 fingerprint of `key` as printed by `ssh-keygen -l`, e.g. `SHA256:…`.
+pub fn fingerprint(key: &PublicKey) -> String {
+    key.fingerprint(HashAlg::Sha256).to_string()
+}
+
+/// Sign the finished `archive` with `key` and write the signature next to it.
+///
+/// Returns the signature path. The archive itself is only read, so a failure leaves it as
+/// it is; a signature left over from an earlier save of the same path is removed, since it
+/// no longer matches.
+///
+/// # Errors
+///
+/// Returns an error when the archive cannot be read or the signature cannot be created or
+/// written.
+pub fn sign_archive(archive: &Path, key: &PrivateKey) -> Result<PathBuf> {
+    let path = signature_path(archive);
+    if let Err(err) = write_signature(archive, key, &path) {
+        remove_signature(archive)?;
+        return Err(err);
+    }
+    Ok(path)
+}
+
+/// Delete the signature sidecar of `archive`, e.g. after the archive was re-saved unsigned
+/// because signing failed; a missing sidecar is not an error.
+///
+/// # Errors
+///
+/// Returns an error when an existing sidecar cannot be removed.
+pub fn remove_signature(archive: &Path) -> Result<()> {
+    let path = signature_path(archive);
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+        Err(err) => {
+            Err(err).with_context(|| format!("Failed to remove stale signature {:?}", path))
+        }
+    }
+}
+
+fn write_signature(archive: &Path, key: &PrivateKey, path: &Path) -> Result<()> {
+    let digest = archive_digest(archive, HashAlg::Sha512)?;
+    let data = signed_data(SIGNATURE_NAMESPACE, &[], HashAlg::Sha512, &digest);
+    let signature = key
+        .try_sign(&data)
+        .map_err(|err| anyhow!("Failed to sign archive: {err}"))?;
+    let signature = SshSig::new(
+        key.public_key().key_data().clone(),
+        SIGNATURE_NAMESPACE,
+        HashAlg::Sha512,
+        signature,
+    )
+    .map_err(|err| anyhow!("Failed to sign archive: {err}"))?;
+    let pem = signature
+        .to_pem(LineEnding::LF)
+        .map_err(|err| anyhow!("Failed to encode signature: {err}"))?;
+    write_atomic(path, pem.as_bytes())
+}
+
+/// Digest of `archive` with `hash_alg`, read as a stream.
+fn archive_digest(archive: &Path, hash_alg: HashAlg) -> Result<Vec<u8>> {
+    let algorithm = match hash_alg {
+        HashAlg::Sha256 => HashAlgorithm::Sha256,
+        HashAlg::Sha512 => HashAlgorithm::Sha512,
+        other => bail!("Signatures using {other} are not supported."),
+    };
+    let read_error = || format!("Failed to read archive {:?}", archive);
+    let mut file = File::open(archive).with_context(read_error)?;
+    let (mut digests, _) =
+        copy_and_digest(&mut file, &mut io::sink(), &[algorithm]).with_context(read_error)?;
+    let digest = digests.remove(&algorithm).expect("digest was requested");
+    Ok(hex::decode(digest)?)
+}
+
+/// Bytes an SSH signature covers for a message with the given `digest`, as laid out in
+/// OpenSSH's `PROTOCOL.sshsig`.
+fn signed_data(namespace: &str, reserved: &[u8], hash_alg: HashAlg, digest: &[u8]) -> Vec<u8> {
+    let mut data = b"SSHSIG".to_vec();
+    for field in [
+        namespace.as_bytes(),
+        reserved,
+        hash_alg.as_str().as_bytes(),
+        digest,
+    ] {
+        let len = u32::try_from(field.len()).expect("signature fields are short");
+        data.extend_from_slice(&len.to_be_bytes());
+        data.extend_from_slice(field);
+    }
+    data
+}
+
+/// Check `archive` against the detached `signature` and report who signed it.
+///
+/// With `trusted`, the signature must have been made with that key and yields
+/// [`Signer::Trusted`]. Without it, a matching signature yields [`Signer::Untrusted`]: the
+/// archive is unchanged since signing, but not necessarily by its author. The fingerprint
+/// recorded in the archive's metadata, if any, must name the signer in both cases.
+///
+/// # Errors
+///
+/// Returns an error when a file cannot be read, the signature does not match the archive,
+/// or the signer is not the expected key.
+pub fn verify_archive(
+    archive: &Path,
+    signature: &Path,
+    trusted: Option<&PublicKey>,
+) -> Result<Signer> {
+    let pem = fs::read_to_string(signature)
+        .with_context(|| format!("Failed to read signature {:?}", signature))?;
+    let sig = SshSig::from_pem(pem.trim())
+        .map_err(|err| anyhow!("{:?} is not an SSH signature: {err}", signature))?;
+    let signer = PublicKey::from(sig.public_key().clone());
+    let signer_fingerprint = fingerprint(&signer);
+    if let Some(trusted) = trusted
+        && trusted.key_data() != signer.key_data()
+    {
+        bail!(t!(
+            "signing.wrong_key",
+            signer = signer_fingerprint,
+            expected = fingerprint(trusted)
+        ));
+    }
+
+    if sig.namespace() != SIGNATURE_NAMESPACE {
+        bail!(
+            "{:?} signs {:?} data, not a file.",
+            signature,
+            sig.namespace()
+        );
+    }
+    let digest = archive_digest(archive, sig.hash_alg())?;
+    let data = signed_data(sig.namespace(), sig.reserved(), sig.hash_alg(), &digest);
+    signer
+        .key_data()
+        .verify(&data, sig.signature())
+        .map_err(|_| anyhow!(t!("signing.mismatch")))?;
+
+    if let Some(recorded) = recorded_fingerprint(archive)?
+        && recorded != signer_fingerprint
+    {
+        bail!(t!(
+            "signing.recorded_key",
+            recorded = recorded,
+            signer = signer_fingerprint
+        ));
+    }
+    Ok(if trusted.is_some() {
+        Signer::Trusted(signer_fingerprint)
+    } else {
+        Signer::Untrusted(signer_fingerprint)
+    })
+}
+
+/// Fingerprint recorded on the metadata descriptor of `archive`, if any.
+///
+/// # Errors
+///
+/// Returns an error when the archive's metadata cannot be read.
+pub fn recorded_fingerprint(archive: &Path) -> Result<Option<String>> {
+    let metadata = read_crate_metadata(archive)?;
+    let fingerprint = metadata["@graph"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|node| {
+            node["@id"]
+                .as_str()
+                .is_some_and(|id| id.ends_with("ro-crate-metadata.json"))
+        })
+        .find_map(|node| node.get(FINGERPRINT_PROPERTY).and_then(Value::as_str))
+        .map(str::to_string);
+    Ok(fingerprint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::eln::{
+        ArchiveEntry, ArchiveGenre, BodyFormat, ExportOptions, build_and_write_archive,
+    };
+    use crate::models::license::License;
+    use ssh_key::rand_core::OsRng;
+    use tempfile::TempDir;
+    use time::OffsetDateTime;
+
+    fn write_key(dir: &Path, name: &str) -> (PathBuf, PrivateKey) {
+        let key = PrivateKey::random(&mut OsRng, Algorithm::Ed25519).unwrap();
+        let path = dir.join(name);
+        key.write_openssh_file(&path, LineEnding::LF).unwrap();
+        (path, key)
+    }
+
+    fn write_archive(path: &Path, fingerprint: Option<String>) {
+        build_and_write_archive(
+            path,
+            &[ArchiveEntry {
+                title: "Signed run".into(),
+                body: "Body".into(),
+                attachments: Vec::new(),
+                extra_fields: Vec::new(),
+                extra_groups: Vec::new(),
+                performed_at: OffsetDateTime::UNIX_EPOCH,
+                genre: ArchiveGenre::Experiment,
+                keywords: Vec::new(),
+                language: None,
+                related_links: Vec::new(),
+                imported_metadata: None,
+                license: License::None,
+                summary: String::new(),
+                hide_main_text: false,
+            }],
+            BodyFormat::Markdown,
+            &ExportOptions {
+                signing_fingerprint: fingerprint,
+                ..ExportOptions::default()
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn signed_archives_verify_and_record_the_key() {
+        let tmp = TempDir::new().unwrap();
+        let (key_path, _) = write_key(tmp.path(), "id_ed25519");
+        let key = load_signing_key(&key_path).unwrap();
+        let expected = fingerprint(key.public_key());
+        let archive = tmp.path().join("entry.eln");
+        write_archive(&archive, Some(expected.clone()));
+
+        let sig = sign_archive(&archive, &key).unwrap();
+        assert_eq!(sig, tmp.path().join("entry.eln.sig"));
+        assert_eq!(
+            recorded_fingerprint(&archive).unwrap(),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            verify_archive(&archive, &sig, None).unwrap(),
+            Signer::Untrusted(expected.clone())
+        );
+        assert_eq!(
+            verify_archive(&archive, &sig, Some(key.public_key())).unwrap(),
+            Signer::Trusted(expected)
+        );
+
+        let (_, other) = write_key(tmp.path(), "other");
+        let err = verify_archive(&archive, &sig, Some(other.public_key())).unwrap_err();
+        assert!(
+            err.to_string().contains("not with the expected key"),
+            "{err}"
+        );
+
+        let mut bytes = fs::read(&archive).unwrap();
+        bytes.push(0);
+        fs::write(&archive, bytes).unwrap();
+        let err = verify_archive(&archive, &sig, None).unwrap_err();
+        assert!(err.to_string().contains("does not match"), "{err}");
+    }
+
+    #[test]
+    fn streamed_signatures_match_what_ssh_keygen_would_write() {
+        let tmp = TempDir::new().unwrap();
+        let (_, key) = write_key(tmp.path(), "id_ed25519");
+        let archive = tmp.path().join("entry.eln");
+        write_archive(&archive, None);
+
+        let sig = sign_archive(&archive, &key).unwrap();
+        // Ed25519 signatures are deterministic, so both encodings must be identical.
+        let one_shot = key
+            .sign(
+                SIGNATURE_NAMESPACE,
+                HashAlg::Sha512,
+                &fs::read(&archive).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&sig).unwrap(),
+            one_shot.to_pem(LineEnding::LF).unwrap()
+        );
+
+        let sha256 = key
+            .sign(
+                SIGNATURE_NAMESPACE,
+                HashAlg::Sha256,
+                &fs::read(&archive).unwrap(),
+            )
+            .unwrap();
+        fs::write(&sig, sha256.to_pem(LineEnding::LF).unwrap()).unwrap();
+        assert_eq!(
+            verify_archive(&archive, &sig, Some(key.public_key())).unwrap(),
+            Signer::Trusted(fingerprint(key.public_key()))
+        );
+    }
+
+    #[test]
+    fn signatures_by_another_key_than_recorded_are_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let (_, recorded) = write_key(tmp.path(), "recorded");
+        let (_, signer) = write_key(tmp.path(), "signer");
+        let archive = tmp.path().join("entry.eln");
+        write_archive(&archive, Some(fingerprint(recorded.public_key())));
+
+        let sig = sign_archive(&archive, &signer).unwrap();
+        let err = verify_archive(&archive, &sig, None).unwrap_err();
+        assert!(err.to_string().contains("names signing key"), "{err}");
+    }
+
+    #[test]
+    fn failed_signing_keeps_the_archive_and_drops_stale_signatures() {
+        let tmp = TempDir::new().unwrap();
+        let (_, key) = write_key(tmp.path(), "id_ed25519");
+        let archive = tmp.path().join("entry.eln");
+        write_archive(&archive, None);
+        let stale = signature_path(&archive);
+        fs::write(&stale, "old signature").unwrap();
+        let before = fs::read(&archive).unwrap();
+
+        // A directory where the signature goes makes the write fail after reading.
+        let blocked = tmp.path().join("blocked.eln");
+        fs::copy(&archive, &blocked).unwrap();
+        fs::create_dir(signature_path(&blocked)).unwrap();
+        assert!(sign_archive(&blocked, &key).is_err());
+        assert_eq!(fs::read(&blocked).unwrap(), before);
+
+        fs::remove_file(&archive).unwrap();
+        assert!(sign_archive(&archive, &key).is_err());
+        assert!(!stale.exists(), "stale signature removed");
+    }
+}
@@ -0,0 +1,271 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Overview of an entry's composition for a last check before handing an archive on.
+//!
+//! Counts words, embedded figures, required fields and data volume, and flags mismatches
+//! between the body and the attachments: files embedded as `./<path>` that are not
+//! attached, and attachments the body never mentions.
+
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use time::{Duration, OffsetDateTime};
+
+use crate::logic::eln::attachment_reference;
+use crate::models::attachment::Attachment;
+use crate::models::extra_fields::ExtraField;
+
+/// Composition of one entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntrySummary {
+    /// Words in the body, without markup.
+    pub words: usize,
+    /// Distinct attachments embedded as images in the body.
+    pub figures_referenced: usize,
+    /// Attachments with an image type.
+    pub figures_attached: usize,
+    /// Required extra fields that have a value.
+    pub required_filled: usize,
+    /// Required extra fields.
+    pub required_total: usize,
+    /// Total size of all attachments in bytes.
+    pub data_bytes: u64,
+    /// Earliest and latest attachment acquisition time, if any is known.
+    pub acquisition_span: Option<(OffsetDateTime, OffsetDateTime)>,
+    /// Paths the body links to or embeds that are not attached.
+    pub missing_attachments: Vec<String>,
+    /// Archive paths of attachments the body never links to or embeds.
+    pub unmentioned_attachments: Vec<String>,
+}
+
+impl EntrySummary {
+    /// Whether the body and the attachments disagree.
+    pub fn has_inconsistencies(&self) -> bool {
+        !self.missing_attachments.is_empty() || !self.unmentioned_attachments.is_empty()
+    }
+}
+
+/// Summarize an entry from its markdown `body`, `attachments` and extra `fields`.
+pub fn summarize_entry(
+    body: &str,
+    attachments: &[Attachment],
+    fields: &[ExtraField],
+) -> EntrySummary {
+    let references = body_references(body);
+    let is_attached = |path: &str| attachments.iter().any(|att| att.archive_path() == path);
+    let (required_filled, required_total) = required_fields(fields);
+    EntrySummary {
+        words: word_count(body),
+        figures_referenced: references.images.len(),
+        figures_attached: attachments
+            .iter()
+            .filter(|att| att.mime.starts_with("image/"))
+            .count(),
+        required_filled,
+        required_total,
+        data_bytes: attachments.iter().map(|att| att.size).sum(),
+        acquisition_span: acquisition_span(attachments),
+        missing_attachments: references
+            .all()
+            .filter(|path| !is_attached(path))
+            .map(str::to_string)
+            .collect(),
+        unmentioned_attachments: attachments
+            .iter()
+            .map(Attachment::archive_path)
+            .filter(|path| !references.all().any(|known| known == path))
+            .collect(),
+    }
+}
+
+/// Number of words in the rendered text of the markdown `body`; bare punctuation is not a word.
+pub fn word_count(body: &str) -> usize {
+    let mut text = String::new();
+    for event in Parser::new_ext(body, Options::ENABLE_TABLES) {
+        match event {
+            Event::Text(part) | Event::Code(part) => text.push_str(&part),
+            Event::SoftBreak | Event::HardBreak | Event::End(_) => text.push(' '),
+            _ => {}
+        }
+    }
+    text.split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .count()
+}
+
+/// Attachment paths the body refers to as `./<path>`, each listed once in order of appearance.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct BodyReferences {
+    /// Embedded as images.
+    pub images: Vec<String>,
+    /// Linked to, and not also embedded.
+    pub links: Vec<String>,
+}
+
+impl BodyReferences {
+    fn all(&self) -> impl Iterator<Item = &str> {
+        self.images.iter().chain(&self.links).map(String::as_str)
+    }
+}
+
+/// Collect the attachment images and links of the markdown `body`.
+pub fn body_references(body: &str) -> BodyReferences {
+    let mut refs = BodyReferences::default();
+    for event in Parser::new_ext(body, Options::ENABLE_TABLES) {
+        let (dest, image) = match &event {
+            Event::Start(Tag::Image { dest_url, .. }) => (dest_url, true),
+            Event::Start(Tag::Link { dest_url, .. }) => (dest_url, false),
+            _ => continue,
+        };
+        let Some(path) = attachment_reference(dest) else {
+            continue;
+        };
+        if image && !refs.images.iter().any(|known| known == path) {
+            refs.links.retain(|known| known != path);
+            refs.images.push(path.to_string());
+        } else if !image && !refs.all().any(|known| known == path) {
+            refs.links.push(path.to_string());
+        }
+    }
+    refs
+}
+
+/// Required fields with a value, and all required fields.
+pub fn required_fields(fields: &[ExtraField]) -> (usize, usize) {
+    let required: Vec<&ExtraField> = fields.iter().filter(|f| f.required).collect();
+    let filled = required
+        .iter()
+        .filter(|f| !f.value.trim().is_empty() || f.value_multi.iter().any(|v| !v.is_empty()))
+        .count();
+    (filled, required.len())
+}
+
+/// Earliest and latest known acquisition time among `attachments`.
+pub fn acquisition_span(attachments: &[Attachment]) -> Option<(OffsetDateTime, OffsetDateTime)> {
+    let mut times = attachments.iter().filter_map(|att| att.acquired_at);
+    let first = times.next()?;
+    Some(times.fold((first, first), |(min, max), at| (min.min(at), max.max(at))))
+}
+
+/// Rough length of `span` for display, e.g. `3 d 4 h`, `25 min`, or `under a minute`.
+pub fn format_span(span: Duration) -> String {
+    let minutes = span.whole_minutes().abs();
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    match (days, hours, minutes) {
+        (0, 0, 0) => "under a minute".to_string(),
+        (0, 0, m) => format!("{m} min"),
+        (0, h, m) => format!("{h} h {m} min"),
+        (d, h, _) => format!("{d} d {h} h"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn attachment(folder: &str, name: &str, mime: &str, size: u64) -> Attachment {
+        let mut att = Attachment::new(
+            PathBuf::from("/data").join(name),
+            name.into(),
+            mime.into(),
+            "unavailable".into(),
+            size,
+        );
+        att.folder = folder.into();
+        att
+    }
+
+    fn field(label: &str, required: bool, value: &str) -> ExtraField {
+        ExtraField {
+            label: label.into(),
+            value: value.into(),
+            required,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn words_are_counted_without_markup() {
+        assert_eq!(
+            word_count("# Run 4\n\nAdd **5 µl** `TE` to [tube](./a.csv)."),
+            8
+        );
+        assert_eq!(word_count("| a | b |\n|---|---|\n| c d | e |"), 5);
+        assert_eq!(word_count(""), 0);
+    }
+
+    #[test]
+    fn references_distinguish_embedded_figures_from_links() {
+        let refs = body_references(
+            "[raw](./gel.png) ![gel](./gel.png) ![again](./gel.png) [csv](./run/od.csv) \
+             ![web](https://example.org/x.png) [up](../secret)",
+        );
+        assert_eq!(refs.images, ["gel.png"]);
+        assert_eq!(refs.links, ["run/od.csv"]);
+    }
+
+    #[test]
+    fn required_fields_count_single_and_multi_values() {
+        let mut multi = field("Buffers", true, "");
+        multi.value_multi = vec!["PBS".into()];
+        let fields = [
+            field("Operator", true, "ada"),
+            field("Sample", true, "  "),
+            field("Notes", false, ""),
+            multi,
+        ];
+        assert_eq!(required_fields(&fields), (2, 3));
+    }
+
+    #[test]
+    fn acquisition_spans_cover_known_times_only() {
+        let mut early = attachment("", "a.csv", "text/csv", 1);
+        early.acquired_at = Some(datetime!(2024-03-14 09:00 UTC));
+        let mut late = attachment("", "b.csv", "text/csv", 1);
+        late.acquired_at = Some(datetime!(2024-03-15 13:30 UTC));
+        let unknown = attachment("", "c.csv", "text/csv", 1);
+        assert_eq!(
+            acquisition_span(&[late.clone(), unknown.clone(), early.clone()]),
+            Some((
+                datetime!(2024-03-14 09:00 UTC),
+                datetime!(2024-03-15 13:30 UTC)
+            ))
+        );
+        assert_eq!(acquisition_span(&[unknown]), None);
+        assert_eq!(format_span(Duration::minutes(1710)), "1 d 4 h");
+        assert_eq!(format_span(Duration::minutes(75)), "1 h 15 min");
+        assert_eq!(format_span(Duration::seconds(20)), "under a minute");
+    }
+
+    #[test]
+    fn summaries_flag_missing_and_unmentioned_attachments() {
+        let attachments = [
+            attachment("", "gel.png", "image/png", 2048),
+            attachment("raw", "blot.tif", "image/tiff", 4096),
+            attachment("raw", "od.csv", "text/csv", 100),
+        ];
+        let summary = summarize_entry(
+            "Gel: ![gel](./gel.png), old: ![old](./old.png), data: [od](./raw/od.csv)",
+            &attachments,
+            &[field("Operator", true, "")],
+        );
+        assert_eq!(
+            summary,
+            EntrySummary {
+                words: 6,
+                figures_referenced: 2,
+                figures_attached: 2,
+                required_filled: 0,
+                required_total: 1,
+                data_bytes: 6244,
+                acquisition_span: None,
+                missing_attachments: vec!["old.png".into()],
+                unmentioned_attachments: vec!["raw/blot.tif".into()],
+            }
+        );
+        assert!(summary.has_inconsistencies());
+    }
+}
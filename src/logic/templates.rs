@@ -0,0 +1,467 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Named entry templates for recurring entry types, stored as JSON files in the config
+//! directory.
+//!
+//! A template keeps what repeats between entries of one kind: the title pattern, body
+//! skeleton, keywords, genre, and extra fields with their groups. Attachments, dates, and
+//! the values of fields flagged `blank_value_on_duplicate` are left out.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::t;
+use crate::logic::draft::Draft;
+use crate::logic::eln::ArchiveGenre;
+use crate::models::extra_fields::{
+    ExtraField, ExtraFieldGroup, FieldErrorKind, field_error_message, validate_field,
+};
+use crate::utils::sanitize_component::{clamp_component, sanitize_component};
+use crate::utils::storage::write_atomic;
+
+/// Version of the on-disk template schema.
+pub const TEMPLATE_SCHEMA_VERSION: u32 = 1;
+
+/// Placeholder in a title pattern replaced by the new entry's performed-at date.
+pub const DATE_PLACEHOLDER: &str = "{date}";
+
+/// Longest file name stem derived from a template name.
+const MAX_FILE_STEM: usize = 64;
+
+/// Templates offered for quick switching in the metadata header.
+pub const MAX_RECENT_TEMPLATES: usize = 5;
+
+/// Reusable skeleton of an entry.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Template {
+    /// Schema version for forward compatibility checks.
+    pub version: u32,
+    /// Name shown in the template menu.
+    pub name: String,
+    /// Title of new entries; `{date}` becomes their performed-at date.
+    pub title: String,
+    pub genre: ArchiveGenre,
+    /// Markdown body skeleton.
+    pub body: String,
+    pub keywords: Vec<String>,
+    pub extra_fields: Vec<ExtraField>,
+    pub extra_groups: Vec<ExtraFieldGroup>,
+}
+
+impl Template {
+    /// Capture the reusable parts of `draft` under `name`.
+    ///
+    /// Fields flagged `blank_value_on_duplicate` lose their value, as in eLabFTW.
+    pub fn from_draft(name: &str, draft: &Draft) -> Self {
+        let mut extra_fields = draft.extra_fields.clone();
+        for field in extra_fields
+            .iter_mut()
+            .filter(|f| f.blank_value_on_duplicate)
+        {
+            field.value.clear();
+            field.value_multi.clear();
+        }
+        Self {
+            version: TEMPLATE_SCHEMA_VERSION,
+            name: name.trim().to_string(),
+            title: draft.title.trim().to_string(),
+            genre: draft.genre,
+            body: draft.body.clone(),
+            keywords: draft.keywords.clone(),
+            extra_fields,
+            extra_groups: draft.extra_groups.clone(),
+        }
+    }
+
+    /// Fill `draft` from the template, expanding `{date}` in the title with the draft's
+    /// performed-at date. Date, time, and attachments of the draft are kept.
+    pub fn apply_to(&self, draft: &mut Draft) {
+        draft.title = self.title.replace(DATE_PLACEHOLDER, &draft.performed_date);
+        draft.genre = self.genre;
+        draft.body = self.body.clone();
+        draft.keywords = self.keywords.clone();
+        draft.extra_fields = self.extra_fields.clone();
+        draft.extra_groups = self.extra_groups.clone();
+        draft.extra_fields_source = None;
+    }
+
+    /// Template called `name` with no content, as a start for the template editor.
+    pub fn blank(name: &str) -> Self {
+        Self {
+            version: TEMPLATE_SCHEMA_VERSION,
+            name: name.trim().to_string(),
+            title: String::new(),
+            genre: ArchiveGenre::default(),
+            body: String::new(),
+            keywords: Vec::new(),
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+        }
+    }
+
+    /// Check the template before it is written: a name, unique field labels, and default
+    /// values that are valid for their field kind.
+    ///
+    /// Required fields may be left empty, since entries fill them in.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first problem found.
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            anyhow::bail!(t!("templates.name.empty"));
+        }
+        let mut labels = std::collections::HashSet::new();
+        for field in &self.extra_fields {
+            if !labels.insert(field.label.trim()) {
+                anyhow::bail!(
+                    "Field label '{}' is used more than once.",
+                    field.label.trim()
+                );
+            }
+            if validate_field(field).is_some_and(|error| error != FieldErrorKind::Required)
+                && let Some(message) = field_error_message(field)
+            {
+                anyhow::bail!(message);
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `fields` and `groups` still define what the template defines; values are
+    /// ignored, so only added, removed, reordered, or reconfigured fields count as changes.
+    pub fn defines_same_fields(&self, fields: &[ExtraField], groups: &[ExtraFieldGroup]) -> bool {
+        fn definition(field: &ExtraField) -> ExtraField {
+            ExtraField {
+                value: String::new(),
+                value_multi: Vec::new(),
+                ..field.clone()
+            }
+        }
+        groups == self.extra_groups.as_slice()
+            && fields.len() == self.extra_fields.len()
+            && fields
+                .iter()
+                .zip(&self.extra_fields)
+                .all(|(field, own)| definition(field) == definition(own))
+    }
+}
+
+/// Recently applied templates, most recent first.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TemplateSettings {
+    /// Names of up to [`MAX_RECENT_TEMPLATES`] templates.
+    pub recent: Vec<String>,
+}
+
+impl TemplateSettings {
+    /// Move `name` to the front of the recent templates; returns whether the list changed.
+    pub fn record_use(&mut self, name: &str) -> bool {
+        let before = self.recent.clone();
+        self.recent
+            .retain(|known| !known.eq_ignore_ascii_case(name));
+        self.recent.insert(0, name.to_string());
+        self.recent.truncate(MAX_RECENT_TEMPLATES);
+        self.recent != before
+    }
+}
+
+/// Path of the file storing the template called `name` in `dir`.
+pub fn template_path(dir: &Path, name: &str) -> PathBuf {
+    let stem = clamp_component(&sanitize_component(name.trim()), MAX_FILE_STEM);
+    dir.join(format!("{stem}.json"))
+}
+
+/// Names of all templates in `dir`, sorted case-insensitively; a missing directory has none.
+///
+/// Files that are not valid templates are skipped.
+///
+/// # Errors
+///
+/// Returns an error when the directory exists but cannot be listed.
+pub fn list_templates(dir: &Path) -> Result<Vec<String>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to list templates in {:?}", dir));
+        }
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| read_template(&path).ok())
+        .map(|template| template.name)
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+    Ok(names)
+}
+
+/// Load the template called `name` from `dir`.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be read, is not valid JSON, or was written by a
+/// newer, incompatible schema version.
+pub fn load_template(dir: &Path, name: &str) -> Result<Template> {
+    read_template(&template_path(dir, name))
+}
+
+/// Write `template` to `dir`, replacing a template of the same name.
+///
+/// # Errors
+///
+/// Returns an error when serialization or the atomic write fails.
+pub fn save_template(dir: &Path, template: &Template) -> Result<()> {
+    let bytes = serde_json::to_vec_pretty(template).context("Failed to serialize template")?;
+    write_atomic(&template_path(dir, &template.name), &bytes)
+}
+
+/// Rename the template `from` in `dir` to `to`.
+///
+/// # Errors
+///
+/// Returns an error when `to` is empty, another template already uses the name
+/// (case-insensitively), or the template cannot be read, written, or removed.
+pub fn rename_template(dir: &Path, from: &str, to: &str) -> Result<()> {
+    let to = to.trim();
+    if to.is_empty() {
+        anyhow::bail!(t!("templates.name.empty"));
+    }
+    let taken = list_templates(dir)?
+        .iter()
+        .any(|name| name.eq_ignore_ascii_case(to) && !name.eq_ignore_ascii_case(from.trim()));
+    if taken {
+        anyhow::bail!("A template named '{to}' already exists.");
+    }
+    let old_path = template_path(dir, from);
+    let template = read_template(&old_path)?;
+    // Remove first: on case-insensitive file systems a case-only rename is the same file.
+    fs::remove_file(&old_path)
+        .with_context(|| format!("Failed to remove template {:?}", old_path))?;
+    let renamed = Template {
+        name: to.to_string(),
+        ..template.clone()
+    };
+    if let Err(err) = save_template(dir, &renamed) {
+        let _ = save_template(dir, &template);
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Delete the template called `name` from `dir`; a missing file is not an error.
+///
+/// # Errors
+///
+/// Returns an error when an existing template cannot be removed.
+pub fn delete_template(dir: &Path, name: &str) -> Result<()> {
+    let path = template_path(dir, name);
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err).with_context(|| format!("Failed to delete template {:?}", path)),
+    }
+}
+
+fn read_template(path: &Path) -> Result<Template> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read template {:?}", path))?;
+    let template: Template = serde_json::from_str(&content).context("Template file is corrupt")?;
+    if template.version > TEMPLATE_SCHEMA_VERSION {
+        anyhow::bail!(
+            "Template was written by a newer ELNPack (schema {}, supported {})",
+            template.version,
+            TEMPLATE_SCHEMA_VERSION
+        );
+    }
+    Ok(template)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::draft::DRAFT_SCHEMA_VERSION;
+    use crate::logic::eln::BodyFormat;
+    use crate::models::extra_fields::ExtraFieldKind;
+    use crate::models::license::License;
+    use tempfile::TempDir;
+
+    fn field(label: &str, value: &str, blank_on_duplicate: bool) -> ExtraField {
+        ExtraField {
+            label: label.into(),
+            value: value.into(),
+            blank_value_on_duplicate: blank_on_duplicate,
+            group_id: Some(1),
+            ..Default::default()
+        }
+    }
+
+    fn draft() -> Draft {
+        Draft {
+            version: DRAFT_SCHEMA_VERSION,
+            title: "PCR run {date}".into(),
+            genre: ArchiveGenre::Experiment,
+            body_format: BodyFormat::Markdown,
+            body: "## Mix\n\n## Cycles\n".into(),
+            keywords: vec!["pcr".into()],
+            extra_fields: vec![
+                field("Polymerase", "Taq", false),
+                field("Sample", "S-17", true),
+            ],
+            extra_groups: vec![ExtraFieldGroup {
+                id: 1,
+                name: "Setup".into(),
+                position: 0,
+            }],
+            extra_fields_source: None,
+            performed_date: "2025-03-14".into(),
+            performed_hour: 9,
+            performed_minute: 30,
+            attachments: Vec::new(),
+            language: String::new(),
+            related_links: Vec::new(),
+            license: License::None,
+            summary: String::new(),
+            hide_main_text: false,
+            more_entries: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn templates_round_trip_without_duplicate_blanked_values() {
+        let tmp = TempDir::new().unwrap();
+        let template = Template::from_draft(" PCR run ", &draft());
+        save_template(tmp.path(), &template).unwrap();
+
+        let loaded = load_template(tmp.path(), "PCR run").unwrap();
+        assert_eq!(loaded, template);
+        assert_eq!(loaded.name, "PCR run");
+        let values: Vec<&str> = loaded
+            .extra_fields
+            .iter()
+            .map(|f| f.value.as_str())
+            .collect();
+        assert_eq!(values, ["Taq", ""]);
+        assert!(template_path(tmp.path(), "PCR run").ends_with("PCR_run.json"));
+    }
+
+    #[test]
+    fn applying_expands_the_date_and_keeps_schedule() {
+        let template = Template::from_draft("PCR", &draft());
+        let mut fresh = Draft {
+            title: String::new(),
+            body: String::new(),
+            keywords: Vec::new(),
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+            performed_date: "2025-04-01".into(),
+            ..draft()
+        };
+        template.apply_to(&mut fresh);
+
+        assert_eq!(fresh.title, "PCR run 2025-04-01");
+        assert_eq!(fresh.body, "## Mix\n\n## Cycles\n");
+        assert_eq!(fresh.keywords, ["pcr"]);
+        assert_eq!(fresh.extra_fields.len(), 2);
+        assert_eq!(fresh.performed_date, "2025-04-01");
+    }
+
+    #[test]
+    fn field_definitions_diverge_only_when_fields_change_beyond_values() {
+        let template = Template::from_draft("PCR", &draft());
+        let mut fields = draft().extra_fields;
+        let groups = draft().extra_groups;
+        assert!(template.defines_same_fields(&fields, &groups));
+
+        fields[0].value = "Pfu".into();
+        fields[1].value_multi = vec!["S-18".into()];
+        assert!(template.defines_same_fields(&fields, &groups));
+
+        fields[0].unit = Some("U".into());
+        assert!(!template.defines_same_fields(&fields, &groups));
+        fields[0].unit = None;
+        fields.swap(0, 1);
+        assert!(!template.defines_same_fields(&fields, &groups));
+        fields.swap(0, 1);
+        assert!(!template.defines_same_fields(&fields[..1], &groups));
+        assert!(!template.defines_same_fields(&fields, &[]));
+    }
+
+    #[test]
+    fn recent_templates_keep_the_five_latest_without_duplicates() {
+        let mut settings = TemplateSettings::default();
+        for name in ["a", "b", "c", "d", "e", "f"] {
+            assert!(settings.record_use(name));
+        }
+        assert_eq!(settings.recent, ["f", "e", "d", "c", "b"]);
+
+        assert!(settings.record_use("C"));
+        assert_eq!(settings.recent, ["C", "f", "e", "d", "b"]);
+        assert!(
+            !settings.record_use("C"),
+            "re-applying the latest changes nothing"
+        );
+    }
+
+    #[test]
+    fn list_rename_and_delete_manage_template_files() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join("templates");
+        assert!(list_templates(&dir).unwrap().is_empty());
+
+        for name in ["western blot", "PCR run"] {
+            save_template(&dir, &Template::from_draft(name, &draft())).unwrap();
+        }
+        fs::write(dir.join("notes.json"), "not a template").unwrap();
+        assert_eq!(list_templates(&dir).unwrap(), ["PCR run", "western blot"]);
+
+        let err = rename_template(&dir, "PCR run", "Western Blot ").unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{err}");
+        rename_template(&dir, "PCR run", "qPCR").unwrap();
+        assert_eq!(list_templates(&dir).unwrap(), ["qPCR", "western blot"]);
+        assert_eq!(load_template(&dir, "qPCR").unwrap().name, "qPCR");
+
+        delete_template(&dir, "qPCR").unwrap();
+        delete_template(&dir, "qPCR").unwrap();
+        assert_eq!(list_templates(&dir).unwrap(), ["western blot"]);
+    }
+
+    #[test]
+    fn templates_from_newer_versions_are_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let mut template = Template::from_draft("future", &draft());
+        template.version = TEMPLATE_SCHEMA_VERSION + 1;
+        save_template(tmp.path(), &template).unwrap();
+
+        let err = load_template(tmp.path(), "future").unwrap_err();
+        assert!(err.to_string().contains("newer ELNPack"), "{err}");
+    }
+
+    #[test]
+    fn validate_accepts_empty_required_fields_but_not_invalid_defaults_or_duplicates() {
+        let mut template = Template::from_draft("PCR", &draft());
+        template.extra_fields[1].required = true;
+        template.extra_fields[1].value.clear();
+        assert!(template.validate().is_ok());
+
+        template.extra_fields[0].kind = ExtraFieldKind::Number;
+        template.extra_fields[0].value = "12 cycles".into();
+        let err = template.validate().unwrap_err().to_string();
+        assert_eq!(err, "Field 'Polymerase' must be a valid number.");
+
+        template.extra_fields[0].value = "12".into();
+        template.extra_fields[1].label = " Polymerase".into();
+        let err = template.validate().unwrap_err().to_string();
+        assert_eq!(err, "Field label 'Polymerase' is used more than once.");
+
+        assert!(Template::blank(" ").validate().is_err());
+    }
+}
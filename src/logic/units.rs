@@ -0,0 +1,288 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! UCUM codes for the free-text units of number fields.
+//!
+//! Repositories match units by code rather than by spelling, so saved archives carry the
+//! [UCUM](https://ucum.org) code of every unit found in the bundled table or in the user's
+//! own [`UnitSettings::codes`]. Lookups tolerate the usual spellings of the same unit, e.g.
+//! `µl`, `μL`, and `uL`, but not typos such as `ul.`.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::t;
+
+/// Common laboratory units and their UCUM codes; several spellings may share a code.
+const UCUM_UNITS: &[(&str, &str)] = &[
+    // Length
+    ("km", "km"),
+    ("m", "m"),
+    ("meter", "m"),
+    ("metre", "m"),
+    ("cm", "cm"),
+    ("mm", "mm"),
+    ("um", "um"),
+    ("micrometer", "um"),
+    ("micron", "um"),
+    ("nm", "nm"),
+    ("nanometer", "nm"),
+    ("pm", "pm"),
+    ("Å", "Ao"),
+    ("angstrom", "Ao"),
+    // Area and volume
+    ("m2", "m2"),
+    ("cm2", "cm2"),
+    ("mm2", "mm2"),
+    ("um2", "um2"),
+    ("m3", "m3"),
+    ("cm3", "cm3"),
+    ("L", "L"),
+    ("liter", "L"),
+    ("litre", "L"),
+    ("mL", "mL"),
+    ("uL", "uL"),
+    ("microliter", "uL"),
+    ("nL", "nL"),
+    // Mass
+    ("kg", "kg"),
+    ("g", "g"),
+    ("gram", "g"),
+    ("mg", "mg"),
+    ("ug", "ug"),
+    ("mcg", "ug"),
+    ("ng", "ng"),
+    ("pg", "pg"),
+    // Time
+    ("h", "h"),
+    ("hr", "h"),
+    ("hour", "h"),
+    ("hours", "h"),
+    ("min", "min"),
+    ("minute", "min"),
+    ("minutes", "min"),
+    ("s", "s"),
+    ("sec", "s"),
+    ("second", "s"),
+    ("seconds", "s"),
+    ("ms", "ms"),
+    ("us", "us"),
+    ("ns", "ns"),
+    ("d", "d"),
+    ("day", "d"),
+    ("days", "d"),
+    // Temperature
+    ("°C", "Cel"),
+    ("degC", "Cel"),
+    ("celsius", "Cel"),
+    ("K", "K"),
+    ("kelvin", "K"),
+    ("°F", "[degF]"),
+    ("degF", "[degF]"),
+    // Amount and concentration
+    ("mol", "mol"),
+    ("mmol", "mmol"),
+    ("umol", "umol"),
+    ("nmol", "nmol"),
+    ("pmol", "pmol"),
+    ("M", "mol/L"),
+    ("mM", "mmol/L"),
+    ("uM", "umol/L"),
+    ("nM", "nmol/L"),
+    ("pM", "pmol/L"),
+    ("mol/L", "mol/L"),
+    ("mmol/L", "mmol/L"),
+    ("g/L", "g/L"),
+    ("mg/mL", "mg/mL"),
+    ("ug/mL", "ug/mL"),
+    ("ng/uL", "ng/uL"),
+    ("g/mol", "g/mol"),
+    ("%", "%"),
+    ("pH", "[pH]"),
+    // Pressure, energy, and electricity
+    ("Pa", "Pa"),
+    ("kPa", "kPa"),
+    ("MPa", "MPa"),
+    ("bar", "bar"),
+    ("mbar", "mbar"),
+    ("atm", "atm"),
+    ("J", "J"),
+    ("kJ", "kJ"),
+    ("eV", "eV"),
+    ("keV", "keV"),
+    ("W", "W"),
+    ("mW", "mW"),
+    ("V", "V"),
+    ("mV", "mV"),
+    ("kV", "kV"),
+    ("A", "A"),
+    ("mA", "mA"),
+    ("uA", "uA"),
+    ("Ω", "Ohm"),
+    ("ohm", "Ohm"),
+    // Frequency
+    ("Hz", "Hz"),
+    ("kHz", "kHz"),
+    ("MHz", "MHz"),
+    ("rpm", "{rpm}"),
+];
+
+/// User additions to the bundled unit table.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UnitSettings {
+    /// Unit spelling mapped to its UCUM code; consulted before the bundled table.
+    pub codes: BTreeMap<String, String>,
+}
+
+impl UnitSettings {
+    /// UCUM code of `unit`, from the user's codes or the bundled table.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let units = UnitSettings::default();
+    /// assert_eq!(units.ucum_code("µl"), Some("uL"));
+    /// assert_eq!(units.ucum_code("ul."), None);
+    /// ```
+    pub fn ucum_code(&self, unit: &str) -> Option<&str> {
+        let custom = self
+            .codes
+            .iter()
+            .map(|(unit, code)| (unit.as_str(), code.as_str()));
+        lookup(unit, custom).or_else(|| lookup(unit, UCUM_UNITS.iter().copied()))
+    }
+}
+
+/// Find `unit` in `table`, first as spelled and then ignoring case.
+///
+/// Matching ignores case only when that does not make it ambiguous, so `MM` maps to
+/// neither millimetre nor millimolar.
+fn lookup<'a>(unit: &str, table: impl Iterator<Item = (&'a str, &'a str)>) -> Option<&'a str> {
+    let key = normalize(unit);
+    if key.is_empty() {
+        return None;
+    }
+    let folded_key = key.to_lowercase();
+    let mut folded: Option<&str> = None;
+    let mut ambiguous = false;
+    for (alias, code) in table {
+        let alias = normalize(alias);
+        if alias == key {
+            return Some(code);
+        }
+        if alias.to_lowercase() == folded_key {
+            match folded {
+                Some(known) if known != code => ambiguous = true,
+                _ => folded = Some(code),
+            }
+        }
+    }
+    if ambiguous { None } else { folded }
+}
+
+/// `unit` with spacing, micro signs, exponents, and multiplication dots written one way.
+fn normalize(unit: &str) -> String {
+    unit.trim()
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '^')
+        .map(|c| match c {
+            // Micro sign and Greek small letter mu.
+            '\u{b5}' | '\u{3bc}' => 'u',
+            '²' => '2',
+            '³' => '3',
+            '·' | '*' => '.',
+            // Ring above and the Angstrom sign.
+            '\u{212b}' => 'Å',
+            '\u{2126}' => 'Ω',
+            other => other,
+        })
+        .collect()
+}
+
+/// Parse unit codes written one per line as `unit = code`; blank lines are skipped.
+///
+/// # Errors
+///
+/// Returns an error naming the first line without a unit, a code, or the `=` between them.
+pub fn parse_unit_codes(text: &str) -> Result<BTreeMap<String, String>> {
+    let mut codes = BTreeMap::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Some((unit, code)) = line.split_once('=') else {
+            bail!(t!("settings.units.no_equals", line = number + 1));
+        };
+        let (unit, code) = (unit.trim(), code.trim());
+        if unit.is_empty() || code.is_empty() || code.contains(char::is_whitespace) {
+            bail!(t!("settings.units.invalid", line = number + 1));
+        }
+        codes.insert(unit.to_string(), code.to_string());
+    }
+    Ok(codes)
+}
+
+/// Write `codes` in the form read by [`parse_unit_codes`].
+pub fn format_unit_codes(codes: &BTreeMap<String, String>) -> String {
+    codes
+        .iter()
+        .map(|(unit, code)| format!("{unit} = {code}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_tolerates_spelling_but_not_typos() {
+        let units = UnitSettings::default();
+        for unit in ["µl", "μL", "uL", "ul", " UL ", "microliter"] {
+            assert_eq!(units.ucum_code(unit), Some("uL"), "{unit}");
+        }
+        assert_eq!(units.ucum_code("°C"), Some("Cel"));
+        assert_eq!(units.ucum_code("m²"), Some("m2"));
+        assert_eq!(units.ucum_code("m^2"), Some("m2"));
+        assert_eq!(units.ucum_code("mg / mL"), Some("mg/mL"));
+        assert_eq!(units.ucum_code("mm"), Some("mm"));
+        assert_eq!(units.ucum_code("mM"), Some("mmol/L"));
+        assert_eq!(units.ucum_code("m"), Some("m"));
+        assert_eq!(units.ucum_code("M"), Some("mol/L"));
+
+        assert_eq!(units.ucum_code("MM"), None, "ambiguous without case");
+        assert_eq!(units.ucum_code("ul."), None);
+        assert_eq!(units.ucum_code("model"), None);
+        assert_eq!(units.ucum_code(""), None);
+    }
+
+    #[test]
+    fn bundled_table_is_consistent() {
+        let units = UnitSettings::default();
+        for (unit, code) in UCUM_UNITS {
+            assert_eq!(units.ucum_code(unit), Some(*code), "{unit}");
+            assert!(!code.contains(char::is_whitespace), "{code}");
+        }
+    }
+
+    #[test]
+    fn user_codes_extend_and_override_the_table() {
+        let codes = parse_unit_codes("OD600 = {OD600}\n\n rpm = /min ").unwrap();
+        let units = UnitSettings { codes };
+        assert_eq!(units.ucum_code("od600"), Some("{OD600}"));
+        assert_eq!(units.ucum_code("rpm"), Some("/min"));
+        assert_eq!(units.ucum_code("mL"), Some("mL"));
+        assert_eq!(
+            parse_unit_codes(&format_unit_codes(&units.codes)).unwrap(),
+            units.codes
+        );
+
+        assert!(parse_unit_codes("OD600").is_err());
+        assert!(parse_unit_codes("OD600 = ").is_err());
+        assert!(parse_unit_codes("x = a b").is_err());
+    }
+}
@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Persistent keyword vocabulary used for autocompletion.
+//!
+//! Every keyword of a successfully saved archive is recorded so it can be suggested again
+//! when tagging later entries.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::storage::write_atomic;
+
+/// Version of the on-disk vocabulary schema.
+pub const VOCABULARY_SCHEMA_VERSION: u32 = 1;
+
+/// On-disk representation of the keyword vocabulary.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+struct VocabularyFile {
+    version: u32,
+    keywords: Vec<String>,
+}
+
+/// Load the stored vocabulary from `path`, returning an empty list when the file does not exist.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be read, is not valid JSON, or was written by a
+/// newer, incompatible schema version.
+pub fn load_vocabulary(path: &Path) -> Result<Vec<String>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to read keywords {:?}", path));
+        }
+    };
+
+    let file: VocabularyFile =
+        serde_json::from_str(&content).context("Keyword vocabulary file is corrupt")?;
+    if file.version > VOCABULARY_SCHEMA_VERSION {
+        anyhow::bail!(
+            "Keyword vocabulary was written by a newer ELNPack (schema {}, supported {})",
+            file.version,
+            VOCABULARY_SCHEMA_VERSION
+        );
+    }
+    Ok(file.keywords)
+}
+
+/// Serialize and atomically write `keywords` to `path`.
+///
+/// # Errors
+///
+/// Returns an error when serialization or the atomic write fails.
+pub fn save_vocabulary(path: &Path, keywords: &[String]) -> Result<()> {
+    let file = VocabularyFile {
+        version: VOCABULARY_SCHEMA_VERSION,
+        keywords: keywords.to_vec(),
+    };
+    let bytes = serde_json::to_vec_pretty(&file).context("Failed to serialize keywords")?;
+    write_atomic(path, &bytes)
+}
+
+/// Add `keywords` to `vocabulary`, ignoring case-insensitive duplicates and blank entries.
+///
+/// The vocabulary stays sorted case-insensitively; the first spelling seen is kept.
+/// Returns `true` when at least one keyword was added.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let mut vocabulary = vec!["Microscopy".to_string()];
+/// assert!(merge_keywords(&mut vocabulary, &["dataset".into(), "microscopy".into()]));
+/// assert_eq!(vocabulary, vec!["dataset", "Microscopy"]);
+/// ```
+pub fn merge_keywords(vocabulary: &mut Vec<String>, keywords: &[String]) -> bool {
+    let mut added = false;
+    for keyword in keywords.iter().map(|k| k.trim()).filter(|k| !k.is_empty()) {
+        if vocabulary
+            .iter()
+            .any(|known| known.to_lowercase() == keyword.to_lowercase())
+        {
+            continue;
+        }
+        vocabulary.push(keyword.to_string());
+        added = true;
+    }
+    if added {
+        vocabulary.sort_by_key(|k| k.to_lowercase());
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn vocabulary_round_trips_and_defaults_to_empty() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("keywords.json");
+        assert!(load_vocabulary(&path).unwrap().is_empty());
+
+        save_vocabulary(&path, &["alpha".into(), "beta".into()]).unwrap();
+
+        assert_eq!(load_vocabulary(&path).unwrap(), vec!["alpha", "beta"]);
+    }
+
+    #[test]
+    fn merge_skips_case_insensitive_duplicates_and_sorts() {
+        let mut vocabulary = vec!["Microscopy".to_string()];
+
+        assert!(merge_keywords(
+            &mut vocabulary,
+            &["dataset".into(), "MICROSCOPY".into(), " ".into()]
+        ));
+        assert!(!merge_keywords(&mut vocabulary, &["Dataset".into()]));
+
+        assert_eq!(vocabulary, vec!["dataset", "Microscopy"]);
+    }
+}
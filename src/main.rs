@@ -1,16 +1,11 @@
 // SPDX-License-Identifier: MIT
 // SPDX-FileCopyrightText: 2025 Alexander Minges
 
-//! Binary entry point that boots the egui application.
+//! Binary entry point that boots the egui application or runs a CLI command.
 
-mod app;
-mod logic;
-mod models;
-mod mvu;
-mod ui;
-mod utils;
+use std::process::ExitCode;
 
-/// Launch the ELNPack desktop application.
-fn main() -> eframe::Result<()> {
-    app::run()
+/// Launch the ELNPack desktop application, or run the CLI command given as argument.
+fn main() -> ExitCode {
+    elnpack::cli::run(std::env::args_os())
 }
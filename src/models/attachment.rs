@@ -7,9 +7,14 @@ use std::collections::HashSet;
 use std::path::PathBuf;
 
 use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::i18n::t;
+use crate::utils::hash::{Digests, HashAlgorithm};
 
 /// Sanitized attachment metadata used for archive creation.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Attachment {
     /// Original absolute path on disk.
     pub path: PathBuf,
@@ -21,6 +26,25 @@ pub struct Attachment {
     pub sha256: String,
     /// File size in bytes.
     pub size: u64,
+    /// Sanitized directory below the entry folder (`/`-separated, empty for top-level files).
+    #[serde(default)]
+    pub folder: String,
+    /// When the data was acquired; defaults to the file's modification time when added.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "rfc3339_option"
+    )]
+    pub acquired_at: Option<OffsetDateTime>,
+    /// Free-text caption written to the `File` node's `description`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Digests besides SHA-256 computed when the file was added; re-verified when saving.
+    #[serde(default, skip_serializing_if = "Digests::is_empty")]
+    pub digests: Digests,
+    /// Leave this image out of the preview's figure list.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub exclude_from_figures: bool,
 }
 
 impl Attachment {
@@ -57,15 +81,239 @@ impl Attachment {
             mime,
             sha256,
             size,
+            folder: String::new(),
+            acquired_at: None,
+            description: None,
+            digests: Digests::new(),
+            exclude_from_figures: false,
         }
     }
+
+    /// Place the attachment in a sanitized subdirectory of the entry folder.
+    pub fn in_folder(mut self, folder: String) -> Self {
+        self.folder = folder;
+        self
+    }
+
+    /// Record when the attachment's data was acquired.
+    pub fn with_acquired_at(mut self, acquired_at: Option<OffsetDateTime>) -> Self {
+        self.acquired_at = acquired_at;
+        self
+    }
+
+    /// Attach a caption; blank text leaves the attachment without one.
+    pub fn with_description(mut self, description: Option<String>) -> Self {
+        self.description = description
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty());
+        self
+    }
+
+    /// Keep an image out of the preview's figure list, or put it back.
+    pub fn excluded_from_figures(mut self, excluded: bool) -> Self {
+        self.exclude_from_figures = excluded;
+        self
+    }
+
+    /// Record digests computed when the file was added; SHA-256 stays in `sha256`.
+    pub fn with_digests(mut self, mut digests: Digests) -> Self {
+        digests.remove(&HashAlgorithm::Sha256);
+        self.digests = digests;
+        self
+    }
+
+    /// Recorded digest for `algorithm`, if it was computed.
+    pub fn digest(&self, algorithm: HashAlgorithm) -> Option<&str> {
+        match algorithm {
+            HashAlgorithm::Sha256 => (self.sha256 != "unavailable").then_some(&*self.sha256),
+            other => self.digests.get(&other).map(String::as_str),
+        }
+    }
+
+    /// Path relative to the entry folder, e.g. `raw/plate1.csv`.
+    pub fn archive_path(&self) -> String {
+        archive_path(&self.folder, &self.sanitized_name)
+    }
+}
+
+/// Join a sanitized folder and file name into an entry-relative archive path.
+pub fn archive_path(folder: &str, name: &str) -> String {
+    if folder.is_empty() {
+        name.to_string()
+    } else {
+        format!("{folder}/{name}")
+    }
+}
+
+/// Names the archive writer uses for its own files next to attachments: the RO-Crate
+/// metadata, the HTML preview, the checksum manifest, the entry folder, the markdown
+/// copy of the main text and the copy of an imported metadata file.
+///
+/// Compared case-insensitively because zip consumers on Windows treat `INDEX.HTML` and
+/// `index.html` as the same file. Numbered entry folders (`experiment-2`) are reserved too.
+pub const RESERVED_ARCHIVE_NAMES: [&str; 6] = [
+    "ro-crate-metadata.json",
+    "index.html",
+    "checksums.sha512",
+    "experiment",
+    "body.md",
+    "imported_metadata.json",
+];
+
+/// Return true when `name` collides with a file or folder the archive writer creates.
+pub fn is_reserved_archive_name(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    RESERVED_ARCHIVE_NAMES.contains(&lower.as_str())
+        || lower
+            .strip_prefix("experiment-")
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Rename a reserved `name` by appending `_1` to its stem (`index.html` → `index_1.html`);
+/// other names are returned unchanged.
+pub fn unreserved_name(name: &str) -> String {
+    if !is_reserved_archive_name(name) {
+        return name.to_string();
+    }
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => format!("{stem}_1.{ext}"),
+        _ => format!("{name}_1"),
+    }
 }
 
-/// Ensure there are no duplicate archive paths produced by sanitized names.
+/// Bytes per megabyte in [`AttachmentPolicy::warn_above_mb`], matching the sizes shown in
+/// the attachments list.
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// Which files may be attached, and from which size on they are flagged.
+///
+/// The default blocks nothing and flags nothing.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AttachmentPolicy {
+    /// File extensions (`exe`, `tar.gz`) or MIME patterns (`video/*`) refused when adding.
+    pub blocked: Vec<String>,
+    /// Files larger than this many megabytes are flagged and need confirming when saving;
+    /// 0 turns the warning off.
+    pub warn_above_mb: u64,
+}
+
+impl AttachmentPolicy {
+    /// The blocked pattern matching the file `name` or its `mime` type, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let policy = AttachmentPolicy { blocked: vec!["exe".into(), "video/*".into()], ..Default::default() };
+    /// assert_eq!(policy.blocked_by("setup.EXE", "application/octet-stream"), Some("exe"));
+    /// assert_eq!(policy.blocked_by("run.mp4", "video/mp4"), Some("video/*"));
+    /// ```
+    pub fn blocked_by(&self, name: &str, mime: &str) -> Option<&str> {
+        let name = name.to_lowercase();
+        let mime = mime.to_lowercase();
+        self.blocked
+            .iter()
+            .find(|pattern| {
+                let pattern = pattern.to_lowercase();
+                match pattern.strip_suffix("/*") {
+                    Some(kind) => mime.split('/').next() == Some(kind),
+                    None if pattern.contains('/') => mime == pattern,
+                    None => name.ends_with(&format!(".{pattern}")),
+                }
+            })
+            .map(String::as_str)
+    }
+
+    /// Size from which files are flagged, in bytes; `None` when the warning is off.
+    pub fn warn_above_bytes(&self) -> Option<u64> {
+        (self.warn_above_mb > 0).then(|| self.warn_above_mb.saturating_mul(BYTES_PER_MB))
+    }
+
+    /// Whether a file of `size` bytes is larger than the warning threshold.
+    pub fn is_oversized(&self, size: u64) -> bool {
+        self.warn_above_bytes().is_some_and(|limit| size > limit)
+    }
+}
+
+/// Parse blocked attachment types separated by commas or new lines.
+///
+/// Extensions may be written as `exe`, `.exe`, or `*.exe`; MIME patterns name a type and
+/// subtype (`application/x-msdownload`) or a whole type (`video/*`). Duplicates are dropped.
 ///
 /// # Errors
 ///
-/// Returns an error when two attachments share the same `sanitized_name`.
+/// Returns an error naming the first entry that is neither an extension nor a MIME pattern.
+pub fn parse_blocked_types(text: &str) -> Result<Vec<String>> {
+    let mut blocked: Vec<String> = Vec::new();
+    for raw in text.split([',', '\n']).map(str::trim) {
+        let pattern = raw
+            .trim_start_matches('*')
+            .trim_start_matches('.')
+            .to_lowercase();
+        if pattern.is_empty() {
+            continue;
+        }
+        let valid = match pattern.split_once('/') {
+            Some((kind, subtype)) => {
+                !kind.is_empty() && !subtype.is_empty() && !subtype.contains('/')
+            }
+            None => true,
+        };
+        if !valid || pattern.contains(char::is_whitespace) {
+            return Err(anyhow!(t!("validate.blocked_type", pattern = raw)));
+        }
+        if !blocked.contains(&pattern) {
+            blocked.push(pattern);
+        }
+    }
+    Ok(blocked)
+}
+
+/// Ensure no attachment uses a name reserved for the archive's own files.
+///
+/// # Errors
+///
+/// Returns an error naming the first attachment with a reserved name.
+pub fn assert_no_reserved_names(attachments: &[Attachment]) -> Result<()> {
+    match attachments
+        .iter()
+        .find(|att| is_reserved_archive_name(&att.sanitized_name))
+    {
+        Some(att) => Err(anyhow!(t!(
+            "validate.reserved_name",
+            path = att.archive_path()
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Ensure no attachment claims to have been acquired after `now`.
+///
+/// # Errors
+///
+/// Returns an error naming the first attachment with a future acquisition time.
+pub fn assert_acquired_not_in_future(
+    attachments: &[Attachment],
+    now: OffsetDateTime,
+) -> Result<()> {
+    match attachments
+        .iter()
+        .find(|att| att.acquired_at.is_some_and(|at| at > now))
+    {
+        Some(att) => Err(anyhow!(t!(
+            "validate.acquired_future",
+            path = att.archive_path()
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Ensure there are no duplicate archive paths produced by sanitized names and folders.
+///
+/// # Errors
+///
+/// Returns an error when two attachments share the same archive path, or when a file's
+/// path is also used as a folder by another attachment.
 ///
 /// # Examples
 ///
@@ -82,12 +330,169 @@ impl Attachment {
 pub fn assert_unique_sanitized_names(attachments: &[Attachment]) -> Result<()> {
     let mut seen = HashSet::new();
     for att in attachments {
-        if !seen.insert(att.sanitized_name.clone()) {
-            return Err(anyhow!(
-                "Duplicate attachment filename in archive: {}",
-                att.sanitized_name
-            ));
+        let path = att.archive_path();
+        if !seen.insert(path.clone()) {
+            return Err(anyhow!(t!("validate.duplicate_path", path = path)));
+        }
+    }
+
+    for att in attachments {
+        let mut prefix = String::new();
+        for component in att.folder.split('/').filter(|c| !c.is_empty()) {
+            if !prefix.is_empty() {
+                prefix.push('/');
+            }
+            prefix.push_str(component);
+            if seen.contains(&prefix) {
+                return Err(anyhow!(t!("validate.file_and_folder", path = prefix)));
+            }
         }
     }
     Ok(())
 }
+
+/// Serde adapter storing an optional timestamp as an RFC 3339 string.
+mod rfc3339_option {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<OffsetDateTime>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(at) => {
+                serializer.serialize_str(&at.format(&Rfc3339).map_err(serde::ser::Error::custom)?)
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<OffsetDateTime>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|raw| OffsetDateTime::parse(&raw, &Rfc3339).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attachment(name: &str, folder: &str) -> Attachment {
+        Attachment::new(
+            PathBuf::from(format!("/tmp/{folder}/{name}")),
+            name.into(),
+            "text/plain".into(),
+            "unavailable".into(),
+            1,
+        )
+        .in_folder(folder.into())
+    }
+
+    #[test]
+    fn same_name_in_different_folders_is_allowed() {
+        let attachments = vec![attachment("a.csv", "run1"), attachment("a.csv", "run2")];
+        assert!(assert_unique_sanitized_names(&attachments).is_ok());
+    }
+
+    #[test]
+    fn colliding_folder_paths_are_rejected() {
+        let duplicate = vec![attachment("a.csv", "run1"), attachment("a.csv", "run1")];
+        assert!(assert_unique_sanitized_names(&duplicate).is_err());
+
+        let file_and_folder = vec![attachment("raw", ""), attachment("a.csv", "raw/sub")];
+        assert!(assert_unique_sanitized_names(&file_and_folder).is_err());
+    }
+
+    #[test]
+    fn acquisition_time_round_trips_and_must_not_be_in_the_future() {
+        let now = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        let acquired = attachment("a.csv", "").with_acquired_at(Some(now));
+
+        let json = serde_json::to_string(&acquired).unwrap();
+        assert!(json.contains(r#""acquired_at":"2023-11-14T22:13:20Z""#));
+        assert_eq!(serde_json::from_str::<Attachment>(&json).unwrap(), acquired);
+        let legacy = serde_json::to_string(&attachment("a.csv", "")).unwrap();
+        assert!(!legacy.contains("acquired_at"));
+        assert_eq!(
+            serde_json::from_str::<Attachment>(&legacy)
+                .unwrap()
+                .acquired_at,
+            None
+        );
+
+        assert!(assert_acquired_not_in_future(std::slice::from_ref(&acquired), now).is_ok());
+        let later = now - time::Duration::seconds(1);
+        let err = assert_acquired_not_in_future(&[acquired], later).unwrap_err();
+        assert!(err.to_string().contains("a.csv"));
+    }
+
+    #[test]
+    fn reserved_names_match_case_insensitively_and_get_renamed() {
+        for name in [
+            "ro-crate-metadata.json",
+            "RO-Crate-Metadata.JSON",
+            "index.html",
+            "INDEX.HTML",
+            "checksums.sha512",
+            "Checksums.SHA512",
+            "experiment",
+            "Experiment",
+            "experiment-2",
+            "EXPERIMENT-10",
+        ] {
+            assert!(is_reserved_archive_name(name), "{name}");
+            let renamed = unreserved_name(name);
+            assert!(!is_reserved_archive_name(&renamed), "{renamed}");
+            let err = assert_no_reserved_names(&[attachment(name, "raw")]).unwrap_err();
+            assert!(err.to_string().contains(&format!("raw/{name}")), "{err}");
+        }
+        assert_eq!(unreserved_name("Index.html"), "Index_1.html");
+        assert_eq!(unreserved_name("experiment"), "experiment_1");
+
+        for name in [
+            "index.htm",
+            "experiment-",
+            "experiment-a",
+            "experiments",
+            "notes.json",
+        ] {
+            assert!(!is_reserved_archive_name(name), "{name}");
+            assert_eq!(unreserved_name(name), name);
+        }
+    }
+
+    #[test]
+    fn policy_blocks_extensions_and_mime_patterns() {
+        let blocked = parse_blocked_types(" *.EXE, .bat\nvideo/*, application/x-msi, exe").unwrap();
+        assert_eq!(blocked, ["exe", "bat", "video/*", "application/x-msi"]);
+        let policy = AttachmentPolicy {
+            blocked,
+            warn_above_mb: 1024,
+        };
+
+        assert_eq!(
+            policy.blocked_by("Setup.Exe", "application/octet-stream"),
+            Some("exe")
+        );
+        assert_eq!(policy.blocked_by("run.MP4", "video/mp4"), Some("video/*"));
+        assert_eq!(
+            policy.blocked_by("a.msi", "application/x-msi"),
+            Some("application/x-msi")
+        );
+        assert_eq!(policy.blocked_by("exe", "text/plain"), None, "no extension");
+        assert_eq!(policy.blocked_by("notes.exe.txt", "text/plain"), None);
+
+        assert!(!policy.is_oversized(1024 * 1024 * 1024));
+        assert!(policy.is_oversized(1024 * 1024 * 1024 + 1));
+        assert!(!AttachmentPolicy::default().is_oversized(u64::MAX));
+        assert_eq!(AttachmentPolicy::default().blocked_by("x.exe", ""), None);
+
+        assert!(parse_blocked_types("video/").is_err());
+        assert!(parse_blocked_types("raw data").is_err());
+        assert!(parse_blocked_types(" , \n").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Author metadata exported as `Person` entities, including ORCID iD validation.
+
+use anyhow::{Result, bail};
+use email_address::EmailAddress;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::t;
+
+/// Resolver prefix of ORCID iDs; also accepted (with `http://`) when entering an iD.
+pub const ORCID_URL_PREFIX: &str = "https://orcid.org/";
+
+/// Person credited as author of the exported entries.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Author {
+    pub given_name: String,
+    pub family_name: String,
+    /// ORCID iD, either bare (`0000-0002-1825-0097`) or as resolver URL.
+    pub orcid: String,
+    pub email: String,
+    pub affiliation: String,
+}
+
+impl Author {
+    /// Whether every field is blank; blank authors are skipped on export.
+    pub fn is_blank(&self) -> bool {
+        [
+            &self.given_name,
+            &self.family_name,
+            &self.orcid,
+            &self.email,
+            &self.affiliation,
+        ]
+        .iter()
+        .all(|value| value.trim().is_empty())
+    }
+
+    /// Given and family name separated by a space.
+    pub fn display_name(&self) -> String {
+        format!("{} {}", self.given_name.trim(), self.family_name.trim())
+            .trim()
+            .to_string()
+    }
+}
+
+/// Check an author and return a copy with trimmed fields and a bare, normalized ORCID iD.
+///
+/// # Errors
+///
+/// Returns an error when the author has no name, the ORCID iD is malformed or fails its
+/// checksum, or the email address is invalid.
+pub fn validate_author(author: &Author) -> Result<Author> {
+    let normalized = Author {
+        given_name: author.given_name.trim().to_string(),
+        family_name: author.family_name.trim().to_string(),
+        orcid: match author.orcid.trim() {
+            "" => String::new(),
+            orcid => normalize_orcid(orcid)?,
+        },
+        email: author.email.trim().to_string(),
+        affiliation: author.affiliation.trim().to_string(),
+    };
+    if normalized.given_name.is_empty() && normalized.family_name.is_empty() {
+        bail!(t!("validate.author_name"));
+    }
+    if !normalized.email.is_empty()
+        && EmailAddress::parse_with_options(&normalized.email, Default::default()).is_err()
+    {
+        bail!(t!("validate.email", email = normalized.email));
+    }
+    Ok(normalized)
+}
+
+/// Normalize an ORCID iD to its bare `xxxx-xxxx-xxxx-xxxx` form and verify its checksum.
+///
+/// The resolver URL prefix is stripped and a lowercase check character `x` is uppercased.
+/// The last character is the ISO 7064 MOD 11-2 check digit over the preceding 15 digits.
+///
+/// # Errors
+///
+/// Returns an error when the iD does not have four dash-separated groups of four digits
+/// or its check digit does not match.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// assert_eq!(
+///     normalize_orcid("https://orcid.org/0000-0002-1694-233x").unwrap(),
+///     "0000-0002-1694-233X"
+/// );
+/// assert!(normalize_orcid("0000-0002-1694-2330").is_err());
+/// ```
+pub fn normalize_orcid(raw: &str) -> Result<String> {
+    let trimmed = raw.trim();
+    let bare = trimmed
+        .strip_prefix(ORCID_URL_PREFIX)
+        .or_else(|| trimmed.strip_prefix("http://orcid.org/"))
+        .unwrap_or(trimmed)
+        .to_ascii_uppercase();
+
+    let groups: Vec<&str> = bare.split('-').collect();
+    let well_formed = groups.len() == 4
+        && groups.iter().all(|group| group.len() == 4)
+        && bare
+            .chars()
+            .filter(|c| *c != '-')
+            .enumerate()
+            .all(|(i, c)| c.is_ascii_digit() || (i == 15 && c == 'X'));
+    if !well_formed {
+        bail!(t!("validate.orcid_form", orcid = trimmed));
+    }
+
+    let digits: Vec<char> = bare.chars().filter(|c| *c != '-').collect();
+    let total = digits[..15].iter().fold(0u32, |total, c| {
+        (total + c.to_digit(10).unwrap_or_default()) * 2
+    });
+    let check = match (12 - total % 11) % 11 {
+        10 => 'X',
+        digit => char::from_digit(digit, 10).unwrap_or('?'),
+    };
+    if digits[15] != check {
+        bail!(t!("validate.orcid_check", orcid = trimmed));
+    }
+    Ok(bare)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orcid_accepts_valid_ids_and_urls() {
+        assert_eq!(
+            normalize_orcid("0000-0002-1825-0097").unwrap(),
+            "0000-0002-1825-0097"
+        );
+        assert_eq!(
+            normalize_orcid(" https://orcid.org/0000-0002-1694-233x ").unwrap(),
+            "0000-0002-1694-233X"
+        );
+        assert_eq!(
+            normalize_orcid("http://orcid.org/0000-0001-5109-3700").unwrap(),
+            "0000-0001-5109-3700"
+        );
+    }
+
+    #[test]
+    fn orcid_rejects_malformed_ids_and_bad_checksums() {
+        for raw in [
+            "0000-0002-1825-009",
+            "0000000218250097",
+            "0000-0002-1825-00970",
+            "0000-000X-1825-0097",
+            "abcd-0002-1825-0097",
+        ] {
+            let err = normalize_orcid(raw).unwrap_err().to_string();
+            assert!(err.contains("must have the form"), "{raw}: {err}");
+        }
+        let err = normalize_orcid("0000-0002-1825-0098").unwrap_err();
+        assert!(err.to_string().contains("check digit"));
+    }
+
+    #[test]
+    fn validate_author_requires_name_and_checks_email() {
+        let author = Author {
+            given_name: " Josiah ".into(),
+            family_name: "Carberry".into(),
+            orcid: "https://orcid.org/0000-0002-1825-0097".into(),
+            ..Default::default()
+        };
+        let normalized = validate_author(&author).unwrap();
+        assert_eq!(normalized.given_name, "Josiah");
+        assert_eq!(normalized.orcid, "0000-0002-1825-0097");
+        assert_eq!(normalized.display_name(), "Josiah Carberry");
+
+        let nameless = Author {
+            affiliation: "Brown University".into(),
+            ..Default::default()
+        };
+        assert!(!nameless.is_blank());
+        assert!(validate_author(&nameless).is_err());
+
+        let bad_email = Author {
+            email: "not-an-address".into(),
+            ..author
+        };
+        assert!(validate_author(&bad_email).is_err());
+        assert!(Author::default().is_blank());
+    }
+}
@@ -5,21 +5,45 @@
 //! Parsing is kept pure so it can be reused by UI and archive logic.
 
 use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
 
 use anyhow::{Context, Result};
 use email_address::EmailAddress;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
+use time::format_description::BorrowedFormatItem;
+use time::macros::format_description;
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
 use url::Url;
 
+use crate::i18n::t;
+
+/// `YYYY-MM-DD`, as produced by HTML date inputs.
+const DATE_FORMAT: &[BorrowedFormatItem<'_>] = format_description!("[year]-[month]-[day]");
+/// `HH:MM` with optional seconds, as produced by HTML time inputs.
+const TIME_FORMAT: &[BorrowedFormatItem<'_>] =
+    format_description!("[hour]:[minute][optional [:[second]]]");
+/// `YYYY-MM-DDTHH:MM` with optional seconds, as produced by HTML datetime-local inputs.
+const DATETIME_FORMAT: &[BorrowedFormatItem<'_>] =
+    format_description!("[year]-[month]-[day]T[hour]:[minute][optional [:[second]]]");
+
 /// Supported eLabFTW field kinds we know how to render.
-#[derive(Clone, Debug, PartialEq, Eq)]
+///
+/// Serializes as the eLabFTW type token (e.g. `"datetime-local"`).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", from = "String")]
 pub enum ExtraFieldKind {
+    #[default]
     Text,
     Number,
     Select,
     Checkbox,
     Date,
+    /// Two dates stored as an ISO 8601 interval `YYYY-MM-DD/YYYY-MM-DD`; not an eLabFTW
+    /// type, which shows it as text.
+    DateRange,
     DateTimeLocal,
     Time,
     Url,
@@ -52,6 +76,7 @@ impl ExtraFieldKind {
             "select" => Self::Select,
             "checkbox" => Self::Checkbox,
             "date" => Self::Date,
+            "daterange" => Self::DateRange,
             "datetime-local" => Self::DateTimeLocal,
             "time" => Self::Time,
             "url" => Self::Url,
@@ -72,6 +97,7 @@ impl ExtraFieldKind {
             Self::Select => "select",
             Self::Checkbox => "checkbox",
             Self::Date => "date",
+            Self::DateRange => "daterange",
             Self::DateTimeLocal => "datetime-local",
             Self::Time => "time",
             Self::Url => "url",
@@ -85,8 +111,20 @@ impl ExtraFieldKind {
     }
 }
 
+impl From<String> for ExtraFieldKind {
+    fn from(raw: String) -> Self {
+        Self::from_str(&raw)
+    }
+}
+
+impl From<ExtraFieldKind> for String {
+    fn from(kind: ExtraFieldKind) -> Self {
+        kind.as_str().to_string()
+    }
+}
+
 /// Single extra field definition + value.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExtraField {
     pub label: String,
     pub kind: ExtraFieldKind,
@@ -102,6 +140,12 @@ pub struct ExtraField {
     pub blank_value_on_duplicate: bool,
     pub group_id: Option<i32>,
     pub readonly: bool,
+    /// Value the template starts the field with, in the form of `value`; `None` without one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<String>,
+    /// Default values of a multi-value field, like `value_multi`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub default_multi: Vec<String>,
 }
 
 impl ExtraField {
@@ -109,23 +153,88 @@ impl ExtraField {
     pub fn cmp_key(&self) -> (i32, &str) {
         (self.position.unwrap_or(i32::MAX), &self.label)
     }
+
+    /// Whether the field has a default value and no longer holds it.
+    pub fn differs_from_default(&self) -> bool {
+        self.default_value
+            .as_ref()
+            .is_some_and(|default| *default != self.value || self.default_multi != self.value_multi)
+    }
+
+    /// Put the default value back; fields without one are left alone.
+    pub fn reset_to_default(&mut self) {
+        if let Some(default) = &self.default_value {
+            self.value = default.clone();
+            self.value_multi = self.default_multi.clone();
+        }
+    }
 }
 
-/// Validate a single `ExtraField` and return a reason code when the field is invalid.
+/// Why a field fails [`validate_field`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldErrorKind {
+    /// A required field is blank.
+    Required,
+    InvalidUrl,
+    InvalidNumber,
+    /// Not an integer ID, for fields linking items, experiments, or users.
+    InvalidInteger,
+    InvalidEmail,
+    InvalidDate,
+    /// A part is no date, or the range ends before it starts.
+    InvalidDateRange,
+    InvalidTime,
+    InvalidDateTime,
+}
+
+impl FieldErrorKind {
+    /// Every kind, in declaration order.
+    #[cfg(test)]
+    pub const ALL: [Self; 9] = [
+        Self::Required,
+        Self::InvalidUrl,
+        Self::InvalidNumber,
+        Self::InvalidInteger,
+        Self::InvalidEmail,
+        Self::InvalidDate,
+        Self::InvalidDateRange,
+        Self::InvalidTime,
+        Self::InvalidDateTime,
+    ];
+}
+
+/// Predicate describing the error, shared by every message about it, e.g. `is required`.
+impl fmt::Display for FieldErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Required => t!("fields.error.required"),
+            Self::InvalidUrl => t!("fields.error.url"),
+            Self::InvalidNumber => t!("fields.error.number"),
+            Self::InvalidInteger => t!("fields.error.integer"),
+            Self::InvalidEmail => t!("fields.error.email"),
+            Self::InvalidDate => t!("fields.error.date"),
+            Self::InvalidDateRange => t!("fields.error.date_range"),
+            Self::InvalidTime => t!("fields.error.time"),
+            Self::InvalidDateTime => t!("fields.error.datetime"),
+        })
+    }
+}
+
+/// Validate a single `ExtraField` and return why it is invalid.
 ///
 /// Performs required-field checks and kind-specific validations:
-/// - `required`: returns `Some("required")` when the trimmed value is empty.
-/// - `Url`: returns `Some("invalid_url")` if the non-empty value is not an `http`/`https` URL with a host.
-/// - `Number`: returns `Some("invalid_number")` if the non-empty value is not a valid floating-point number.
-/// - `Items`, `Experiments`, `Users`: return `Some("invalid_integer")` if the non-empty value is not a valid integer.
-/// - `Email`: returns `Some("invalid_email")` if the non-empty value is not a valid email address.
+/// - `required`: [`FieldErrorKind::Required`] when the trimmed value is empty.
+/// - `Url`: [`FieldErrorKind::InvalidUrl`] if the non-empty value is not an `http`/`https` URL with a host.
+/// - `Number`: [`FieldErrorKind::InvalidNumber`] if the non-empty value is not a valid floating-point number.
+/// - `Items`, `Experiments`, `Users`: [`FieldErrorKind::InvalidInteger`] if the non-empty value is not a valid integer.
+/// - `Email`: [`FieldErrorKind::InvalidEmail`] if the non-empty value is not a valid email address.
+/// - `Date`, `Time`, `DateTimeLocal`: the matching `Invalid*` kind if the non-empty value does
+///   not parse in the HTML input format.
+/// - `DateRange`: [`FieldErrorKind::InvalidDateRange`] unless the non-empty value holds two
+///   such dates, the end not before the start; see [`parse_date_range`].
 ///
 /// For other kinds or when the value is empty (and not required), validation returns `None`.
 ///
-/// # Returns
-///
-/// `Some(reason_code)` if the field is invalid, `None` if the field is valid or no validation applies.
-///
 /// # Examples
 ///
 /// ```rust,ignore
@@ -146,17 +255,19 @@ impl ExtraField {
 ///     blank_value_on_duplicate: false,
 ///     group_id: None,
 ///     readonly: false,
+///     default_value: None,
+///     default_multi: Vec::new(),
 /// };
 /// assert_eq!(validate_field(&valid_number), None);
 ///
 /// let missing_required = ExtraField { required: true, value: "".into(), ..valid_number.clone() };
-/// assert_eq!(validate_field(&missing_required), Some("required"));
+/// assert_eq!(validate_field(&missing_required), Some(FieldErrorKind::Required));
 /// ```
-pub fn validate_field(field: &ExtraField) -> Option<&'static str> {
+pub fn validate_field(field: &ExtraField) -> Option<FieldErrorKind> {
     let value = field.value.trim();
 
     if field.required && value.is_empty() {
-        return Some("required");
+        return Some(FieldErrorKind::Required);
     }
 
     match field.kind {
@@ -164,11 +275,7 @@ pub fn validate_field(field: &ExtraField) -> Option<&'static str> {
             if value.is_empty() {
                 return None;
             }
-            Url::parse(value)
-                .ok()
-                .filter(|u| matches!(u.scheme(), "http" | "https") && u.host_str().is_some())
-                .map(|_| None)
-                .unwrap_or(Some("invalid_url"))
+            (!is_web_url(value)).then_some(FieldErrorKind::InvalidUrl)
         }
         ExtraFieldKind::Number => {
             if value.is_empty() {
@@ -177,7 +284,7 @@ pub fn validate_field(field: &ExtraField) -> Option<&'static str> {
             if value.parse::<f64>().is_ok() {
                 None
             } else {
-                Some("invalid_number")
+                Some(FieldErrorKind::InvalidNumber)
             }
         }
         ExtraFieldKind::Items | ExtraFieldKind::Experiments | ExtraFieldKind::Users => {
@@ -187,7 +294,7 @@ pub fn validate_field(field: &ExtraField) -> Option<&'static str> {
             if value.parse::<i64>().is_ok() {
                 None
             } else {
-                Some("invalid_integer")
+                Some(FieldErrorKind::InvalidInteger)
             }
         }
         ExtraFieldKind::Email => {
@@ -197,24 +304,104 @@ pub fn validate_field(field: &ExtraField) -> Option<&'static str> {
             if EmailAddress::parse_with_options(value, Default::default()).is_ok() {
                 None
             } else {
-                Some("invalid_email")
+                Some(FieldErrorKind::InvalidEmail)
+            }
+        }
+        ExtraFieldKind::Date => {
+            if value.is_empty() || Date::parse(value, DATE_FORMAT).is_ok() {
+                None
+            } else {
+                Some(FieldErrorKind::InvalidDate)
+            }
+        }
+        ExtraFieldKind::DateRange => {
+            if value.is_empty() || parse_date_range(value).is_some() {
+                None
+            } else {
+                Some(FieldErrorKind::InvalidDateRange)
+            }
+        }
+        ExtraFieldKind::Time => {
+            if value.is_empty() || Time::parse(value, TIME_FORMAT).is_ok() {
+                None
+            } else {
+                Some(FieldErrorKind::InvalidTime)
+            }
+        }
+        ExtraFieldKind::DateTimeLocal => {
+            if value.is_empty() || PrimitiveDateTime::parse(value, DATETIME_FORMAT).is_ok() {
+                None
+            } else {
+                Some(FieldErrorKind::InvalidDateTime)
             }
         }
         _ => None,
     }
 }
 
+/// Start and end of a date range value `YYYY-MM-DD/YYYY-MM-DD`, trimmed; the end is empty
+/// when the value has no `/`.
+pub fn split_date_range(value: &str) -> (&str, &str) {
+    let value = value.trim();
+    let (start, end) = value.split_once('/').unwrap_or((value, ""));
+    (start.trim(), end.trim())
+}
+
+/// Date range value of `start` and `end`; empty when both are.
+pub fn join_date_range(start: &str, end: &str) -> String {
+    match (start.trim(), end.trim()) {
+        ("", "") => String::new(),
+        (start, end) => format!("{start}/{end}"),
+    }
+}
+
+/// Start and end of a date range value; `None` unless both are dates and the range does not
+/// end before it starts.
+pub fn parse_date_range(value: &str) -> Option<(Date, Date)> {
+    let (start, end) = value.trim().split_once('/')?;
+    let start = Date::parse(start.trim(), DATE_FORMAT).ok()?;
+    let end = Date::parse(end.trim(), DATE_FORMAT).ok()?;
+    (start <= end).then_some((start, end))
+}
+
+/// Whether `value` parses as an absolute `http` or `https` URL with a host.
+pub fn is_web_url(value: &str) -> bool {
+    Url::parse(value)
+        .is_ok_and(|u| matches!(u.scheme(), "http" | "https") && u.host_str().is_some())
+}
+
+/// User-facing message naming the field when it fails [`validate_field`].
+pub fn field_error_message(field: &ExtraField) -> Option<String> {
+    validate_field(field).map(|error| t!("fields.error.named", label = field.label, error = error))
+}
+
+/// Message shown under the field's input when it fails [`validate_field`].
+///
+/// A missing required value only counts once `show_required` is set, so untouched fields
+/// of a fresh form are not flagged before the user had a chance to fill them in.
+pub fn field_inline_error(field: &ExtraField, show_required: bool) -> Option<String> {
+    validate_field(field)
+        .filter(|error| *error != FieldErrorKind::Required || show_required)
+        .map(|error| t!("fields.error.inline", error = error))
+}
+
 #[derive(Debug, Deserialize)]
 struct ExtraFieldsEnvelope {
     extra_fields: BTreeMap<String, ExtraFieldRaw>,
     #[serde(default)]
     elabftw: Option<ElabFtWBlock>,
+    /// Entry tags, as an array or as one `|`-separated string.
+    #[serde(default)]
+    tags: Option<Value>,
 }
 
 #[derive(Debug, Deserialize, Default)]
 struct ElabFtWBlock {
     #[serde(default)]
     extra_fields_groups: Vec<ExtraFieldGroupRaw>,
+    /// Whether eLabFTW shows the main text; shown when absent.
+    #[serde(default)]
+    display_main_text: Option<Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -224,7 +411,7 @@ struct ExtraFieldGroupRaw {
 }
 
 /// Group information for display ordering.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExtraFieldGroup {
     pub id: i32,
     pub name: String,
@@ -257,12 +444,49 @@ struct ExtraFieldRaw {
     readonly: bool,
     #[serde(default)]
     group_id: Option<Value>,
+    #[serde(default)]
+    default_value: Option<Value>,
+}
+
+/// Metadata file the fields of an entry were imported from, kept for re-importing it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtraFieldsSource {
+    /// Path of the imported JSON file.
+    pub path: PathBuf,
+    /// When the file was imported, as Unix seconds.
+    pub imported_at: i64,
+    /// SHA-256 of the file content at import time, to notice later changes.
+    pub sha256: String,
+}
+
+impl ExtraFieldsSource {
+    /// Describe `content` read from `path` at `imported_at`.
+    pub fn new(path: PathBuf, content: &str, imported_at: OffsetDateTime) -> Self {
+        Self {
+            path,
+            imported_at: imported_at.unix_timestamp(),
+            sha256: hex::encode(Sha256::digest(content.as_bytes())),
+        }
+    }
+
+    /// File name for display, falling back to the full path.
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .unwrap_or(self.path.as_os_str())
+            .to_string_lossy()
+            .into_owned()
+    }
 }
 
-/// Parsed payload: fields plus optional groups metadata.
+/// Parsed payload: fields plus optional groups metadata and tags.
 pub struct ExtraFieldsImport {
     pub fields: Vec<ExtraField>,
     pub groups: Vec<ExtraFieldGroup>,
+    /// Top-level `tags` of the export, split at `|`; empty when it has none.
+    pub tags: Vec<String>,
+    /// eLabFTW hides the main text of the entry (`display_main_text` is false).
+    pub hide_main_text: bool,
 }
 
 /// Parse extra field definitions and groups from an eLabFTW metadata JSON string.
@@ -271,7 +495,8 @@ pub struct ExtraFieldsImport {
 /// and `elabftw.extra_fields_groups` into a structured `ExtraFieldsImport` containing normalized
 /// `ExtraField` entries (with single and multi values, options, units, group association, etc.)
 /// and ordered `ExtraFieldGroup` entries. It treats absent or empty shapes as defaults and converts
-/// JSON numbers/strings to the appropriate Rust types where possible.
+/// JSON numbers/strings to the appropriate Rust types where possible. A top-level `tags` array or
+/// `|`-separated tag string is returned as `tags`.
 ///
 /// # Returns
 ///
@@ -313,22 +538,22 @@ pub fn parse_elabftw_extra_fields(json: &str) -> Result<ExtraFieldsImport> {
             .filter_map(|v| value_to_string(Some(v)))
             .collect::<Vec<_>>();
 
-        let (value, value_multi) = match raw.value.as_ref() {
-            Some(Value::Array(arr)) => {
-                let vals = arr
-                    .iter()
-                    .filter_map(|v| value_to_string(Some(v)))
-                    .collect::<Vec<_>>();
-                let joined = vals.join(", ");
-                (joined, vals)
+        let (mut value, mut value_multi) = split_value(raw.value.as_ref());
+        let (default_value, default_multi) = match raw.default_value.as_ref() {
+            None | Some(Value::Null) => (None, Vec::new()),
+            default => {
+                let (default, multi) = split_value(default);
+                (Some(default), multi)
             }
-            other => (
-                other
-                    .and_then(|v| value_to_string(Some(v)))
-                    .unwrap_or_else(String::new),
-                Vec::new(),
-            ),
         };
+        // Fields without a value start at their default.
+        if value.is_empty()
+            && value_multi.is_empty()
+            && let Some(default) = &default_value
+        {
+            value = default.clone();
+            value_multi = default_multi.clone();
+        }
 
         let group_id = match raw.group_id.as_ref() {
             Some(Value::Number(n)) => n.as_i64().map(|v| v as i32),
@@ -351,13 +576,15 @@ pub fn parse_elabftw_extra_fields(json: &str) -> Result<ExtraFieldsImport> {
             blank_value_on_duplicate: raw.blank_value_on_duplicate,
             group_id,
             readonly: raw.readonly,
+            default_value,
+            default_multi,
         });
     }
 
     fields.sort_by(|a, b| a.cmp_key().cmp(&b.cmp_key()));
-    let groups = env
-        .elabftw
-        .unwrap_or_default()
+    let block = env.elabftw.unwrap_or_default();
+    let hide_main_text = block.display_main_text.as_ref().is_some_and(flag_is_off);
+    let groups = block
         .extra_fields_groups
         .into_iter()
         .enumerate()
@@ -373,7 +600,57 @@ pub fn parse_elabftw_extra_fields(json: &str) -> Result<ExtraFieldsImport> {
         })
         .collect();
 
-    Ok(ExtraFieldsImport { fields, groups })
+    Ok(ExtraFieldsImport {
+        fields,
+        groups,
+        tags: split_tags(env.tags.as_ref()),
+        hide_main_text,
+    })
+}
+
+/// Single value of a field and, for arrays, its items; arrays are joined with `, `.
+fn split_value(raw: Option<&Value>) -> (String, Vec<String>) {
+    match raw {
+        Some(Value::Array(arr)) => {
+            let vals = arr
+                .iter()
+                .filter_map(|v| value_to_string(Some(v)))
+                .collect::<Vec<_>>();
+            (vals.join(", "), vals)
+        }
+        other => (
+            other
+                .and_then(|v| value_to_string(Some(v)))
+                .unwrap_or_default(),
+            Vec::new(),
+        ),
+    }
+}
+
+/// Whether an eLabFTW flag is switched off: `false`, `0`, `"0"`, or `"false"`.
+fn flag_is_off(value: &Value) -> bool {
+    match value {
+        Value::Bool(flag) => !flag,
+        Value::Number(n) => n.as_i64() == Some(0),
+        Value::String(s) => matches!(s.trim(), "0" | "false"),
+        _ => false,
+    }
+}
+
+/// Tags from an array of strings or a `|`-separated string, trimmed and without blanks.
+fn split_tags(tags: Option<&Value>) -> Vec<String> {
+    let parts: Vec<&str> = match tags {
+        Some(Value::String(joined)) => vec![joined.as_str()],
+        Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    parts
+        .into_iter()
+        .flat_map(|part| part.split('|'))
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
 }
 
 /// Convert a JSON `Value` reference into an optional `String` representation.
@@ -406,7 +683,154 @@ fn value_to_string(val: Option<&Value>) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::parse_elabftw_extra_fields;
+    use std::collections::HashSet;
+
+    use super::{
+        ExtraField, ExtraFieldKind, FieldErrorKind, field_error_message, field_inline_error,
+        join_date_range, parse_elabftw_extra_fields, split_date_range, validate_field,
+    };
+
+    fn field(kind: ExtraFieldKind, value: &str) -> ExtraField {
+        ExtraField {
+            label: "When".into(),
+            kind,
+            value: value.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn date_and_time_values_are_validated() {
+        let check = |kind, value| validate_field(&field(kind, value));
+
+        assert_eq!(check(ExtraFieldKind::Date, "2024-02-29"), None);
+        assert_eq!(
+            check(ExtraFieldKind::Date, "2023-02-29"),
+            Some(FieldErrorKind::InvalidDate)
+        );
+        assert_eq!(
+            check(ExtraFieldKind::Date, "banana"),
+            Some(FieldErrorKind::InvalidDate)
+        );
+        assert_eq!(check(ExtraFieldKind::Date, ""), None);
+
+        assert_eq!(check(ExtraFieldKind::Time, "23:59"), None);
+        assert_eq!(check(ExtraFieldKind::Time, "08:15:30"), None);
+        assert_eq!(
+            check(ExtraFieldKind::Time, "24:00"),
+            Some(FieldErrorKind::InvalidTime)
+        );
+        assert_eq!(
+            check(ExtraFieldKind::Time, "8 am"),
+            Some(FieldErrorKind::InvalidTime)
+        );
+
+        assert_eq!(
+            check(ExtraFieldKind::DateTimeLocal, "2024-02-29T00:00"),
+            None
+        );
+        assert_eq!(
+            check(ExtraFieldKind::DateTimeLocal, "2024-03-01T24:00"),
+            Some(FieldErrorKind::InvalidDateTime)
+        );
+        assert_eq!(
+            check(ExtraFieldKind::DateTimeLocal, "2024-03-01"),
+            Some(FieldErrorKind::InvalidDateTime)
+        );
+        assert_eq!(check(ExtraFieldKind::DateTimeLocal, "  "), None);
+
+        let required = ExtraField {
+            required: true,
+            ..field(ExtraFieldKind::Date, "")
+        };
+        assert_eq!(validate_field(&required), Some(FieldErrorKind::Required));
+    }
+
+    #[test]
+    fn date_ranges_need_two_dates_in_order() {
+        let check = |value| validate_field(&field(ExtraFieldKind::DateRange, value));
+
+        assert_eq!(check("2024-05-01/2024-05-03"), None);
+        assert_eq!(check(" 2024-05-01 / 2024-05-01 "), None, "one day");
+        assert_eq!(check(""), None);
+        for invalid in [
+            "2024-05-03/2024-05-01",
+            "2024-05-01",
+            "2024-05-01/",
+            "/2024-05-01",
+            "2024-02-30/2024-03-01",
+            "2024-05-01/2024-05-02/2024-05-03",
+        ] {
+            assert_eq!(
+                check(invalid),
+                Some(FieldErrorKind::InvalidDateRange),
+                "{invalid}"
+            );
+        }
+
+        assert_eq!(split_date_range("2024-05-01 / "), ("2024-05-01", ""));
+        assert_eq!(join_date_range("", "2024-05-03"), "/2024-05-03");
+        assert_eq!(join_date_range(" ", ""), "");
+    }
+
+    #[test]
+    fn daterange_type_is_imported_as_date_range() {
+        let import = parse_elabftw_extra_fields(
+            r#"{"extra_fields": {"Campaign": {"type": "daterange", "value": "2024-05-01/2024-05-03"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(import.fields[0].kind, ExtraFieldKind::DateRange);
+        assert_eq!(validate_field(&import.fields[0]), None);
+    }
+
+    #[test]
+    fn inline_and_save_messages_share_their_wording() {
+        let number = field(ExtraFieldKind::Number, "1,5");
+        assert_eq!(
+            field_error_message(&number).as_deref(),
+            Some("Field 'When' must be a valid number.")
+        );
+        assert_eq!(
+            field_inline_error(&number, false).as_deref(),
+            Some("This field must be a valid number.")
+        );
+
+        let required = ExtraField {
+            required: true,
+            ..field(ExtraFieldKind::Text, " ")
+        };
+        assert_eq!(
+            field_error_message(&required).as_deref(),
+            Some("Field 'When' is required.")
+        );
+        assert_eq!(field_inline_error(&required, false), None);
+        assert_eq!(
+            field_inline_error(&required, true).as_deref(),
+            Some("This field is required.")
+        );
+
+        let email = field(ExtraFieldKind::Email, "lab at example");
+        assert_eq!(
+            field_error_message(&email).as_deref(),
+            Some("Field 'When' must be a valid email address.")
+        );
+    }
+
+    // Each error kind says what is wrong instead of a generic "is invalid".
+    #[test]
+    fn every_error_kind_has_its_own_message() {
+        let messages: HashSet<String> = FieldErrorKind::ALL
+            .iter()
+            .map(|kind| kind.to_string())
+            .collect();
+        assert_eq!(messages.len(), FieldErrorKind::ALL.len());
+        for message in &messages {
+            assert!(
+                message.starts_with("is required") || message.starts_with("must be "),
+                "{message}"
+            );
+        }
+    }
 
     #[test]
     fn parses_sample_extra_fields() {
@@ -427,5 +851,72 @@ mod tests {
             vec!["\u{212b}".to_string(), "nm".to_string()]
         );
         assert_eq!(import.fields[1].value, "1.540562");
+        assert!(import.tags.is_empty());
+    }
+
+    // eLabFTW writes tags either as an array or as one pipe-separated string.
+    #[test]
+    fn tags_are_read_from_arrays_and_pipe_separated_strings() {
+        const ARRAY: &str =
+            r#"{"extra_fields":{},"tags":["buffer prep", " PCR ", "", "gel|agarose"]}"#;
+        const PIPES: &str = r#"{"extra_fields":{"Notes":{"type":"text","value":"x"}},"tags":"buffer prep|PCR||gel "}"#;
+        const OTHER: &str = r#"{"extra_fields":{},"tags":null}"#;
+
+        assert_eq!(
+            parse_elabftw_extra_fields(ARRAY).unwrap().tags,
+            vec!["buffer prep", "PCR", "gel", "agarose"]
+        );
+        let import = parse_elabftw_extra_fields(PIPES).unwrap();
+        assert_eq!(import.tags, vec!["buffer prep", "PCR", "gel"]);
+        assert_eq!(import.fields.len(), 1);
+        assert!(parse_elabftw_extra_fields(OTHER).unwrap().tags.is_empty());
+    }
+
+    #[test]
+    fn display_main_text_is_read_from_the_elabftw_block() {
+        let hidden = |json: &str| parse_elabftw_extra_fields(json).unwrap().hide_main_text;
+        assert!(!hidden(r#"{"extra_fields":{}}"#));
+        assert!(!hidden(
+            r#"{"elabftw":{"display_main_text":true},"extra_fields":{}}"#
+        ));
+        assert!(!hidden(
+            r#"{"elabftw":{"display_main_text":null},"extra_fields":{}}"#
+        ));
+        assert!(hidden(
+            r#"{"elabftw":{"display_main_text":false},"extra_fields":{}}"#
+        ));
+        assert!(hidden(
+            r#"{"elabftw":{"display_main_text":0},"extra_fields":{}}"#
+        ));
+        assert!(hidden(
+            r#"{"elabftw":{"display_main_text":"0"},"extra_fields":{}}"#
+        ));
+    }
+
+    #[test]
+    fn default_values_are_read_and_fill_empty_values() {
+        const JSON: &str = r#"{"extra_fields":{
+            "Buffer":{"type":"text","value":"","default_value":"PBS"},
+            "Power":{"type":"number","value":"5","default_value":10},
+            "Filters":{"type":"select","allow_multi_values":true,"default_value":["UV","IR"]},
+            "Notes":{"type":"text","value":"x","default_value":null}
+        }}"#;
+
+        let import = parse_elabftw_extra_fields(JSON).unwrap();
+        let field = |label: &str| import.fields.iter().find(|f| f.label == label).unwrap();
+        assert_eq!(field("Buffer").value, "PBS");
+        assert_eq!(field("Buffer").default_value.as_deref(), Some("PBS"));
+        assert_eq!(field("Power").value, "5", "set values are kept");
+        assert_eq!(field("Power").default_value.as_deref(), Some("10"));
+        assert_eq!(field("Filters").value_multi, ["UV", "IR"]);
+        assert_eq!(field("Filters").default_multi, ["UV", "IR"]);
+        assert_eq!(field("Notes").default_value, None);
+
+        let mut power = field("Power").clone();
+        assert!(power.differs_from_default());
+        power.reset_to_default();
+        assert_eq!(power.value, "10");
+        assert!(!power.differs_from_default());
+        assert!(!field("Notes").differs_from_default());
     }
 }
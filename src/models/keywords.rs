@@ -2,31 +2,137 @@
 // SPDX-FileCopyrightText: 2025 Alexander Minges
 
 //! Keyword collection domain helper.
+//!
+//! eLabFTW stores an entry's tags joined by `|` and treats tags differing only by case as the
+//! same, so keywords are normalized before they reach an archive: whitespace is trimmed and
+//! collapsed, separator characters become spaces, overlong keywords are cut, and duplicates
+//! and keywords beyond the limit are dropped. [`KeywordReport`] records what changed.
+//!
+//! Values of the extra fields named in [`KeywordSettings`] are added to the keywords when
+//! saving; see [`derived_keywords`].
 
-/// Simple wrapper to keep keyword normalization in one place.
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::t;
+use crate::models::extra_fields::{ExtraField, ExtraFieldKind};
+
+/// Longest keyword, in characters; longer ones are cut.
+pub const MAX_KEYWORD_CHARS: usize = 100;
+
+/// Most keywords per entry; later ones are dropped.
+pub const MAX_KEYWORDS: usize = 50;
+
+/// Limits applied by [`Keywords::normalize`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeywordRules {
+    /// Characters that separate tags in eLabFTW or CSV exports; replaced by spaces.
+    pub separators: Vec<char>,
+    pub max_chars: usize,
+    pub max_count: usize,
+}
+
+impl Default for KeywordRules {
+    fn default() -> Self {
+        Self {
+            separators: vec!['|', ','],
+            max_chars: MAX_KEYWORD_CHARS,
+            max_count: MAX_KEYWORDS,
+        }
+    }
+}
+
+/// Why a keyword was left out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DropReason {
+    /// Nothing was left after normalizing.
+    Empty,
+    /// Same as an earlier keyword apart from case; holds the kept one.
+    Duplicate(String),
+    /// Beyond [`KeywordRules::max_count`].
+    OverLimit,
+}
+
+/// What [`Keywords::normalize`] changed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeywordReport {
+    /// Keywords stored in another form, as `(original, stored)`.
+    pub adjusted: Vec<(String, String)>,
+    /// Keywords left out, with the reason.
+    pub dropped: Vec<(String, DropReason)>,
+}
+
+impl KeywordReport {
+    /// Whether every keyword was kept as given.
+    pub fn is_empty(&self) -> bool {
+        self.adjusted.is_empty() && self.dropped.is_empty()
+    }
+
+    /// One-line description for the status bar, e.g.
+    /// `stored "a|b" as "a b"; dropped "DNA" (duplicate of "dna")`.
+    pub fn summary(&self) -> String {
+        let adjusted = self
+            .adjusted
+            .iter()
+            .map(|(from, to)| t!("keywords.report.adjusted", from = from, to = to));
+        let dropped = self.dropped.iter().map(|(keyword, reason)| match reason {
+            DropReason::Empty => t!("keywords.report.empty", keyword = keyword),
+            DropReason::Duplicate(kept) => {
+                t!("keywords.report.duplicate", keyword = keyword, kept = kept)
+            }
+            DropReason::OverLimit => t!("keywords.report.over_limit", keyword = keyword),
+        });
+        adjusted.chain(dropped).collect::<Vec<_>>().join("; ")
+    }
+}
+
+/// Normalized keyword list of one entry.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Keywords {
     items: Vec<String>,
 }
 
 impl Keywords {
-    /// Create a keyword collection, normalizing duplicates case-insensitively.
-    ///
-    /// Leading/trailing whitespace is preserved; only duplicate tokens
-    /// (case-insensitive) are removed, keeping the first occurrence's casing.
+    /// Create a keyword collection normalized with the default [`KeywordRules`].
     ///
     /// # Examples
     ///
     /// ```rust,ignore
     /// use elnpack::models::keywords::Keywords;
     ///
-    /// let kw = Keywords::new(vec!["DNA".into(), "dna".into(), "RNA".into()]);
-    /// assert_eq!(kw.items(), &["DNA", "RNA"]);
+    /// let kw = Keywords::new(vec![" DNA ".into(), "dna".into(), "RNA|seq".into()]);
+    /// assert_eq!(kw.items(), &["DNA", "RNA seq"]);
     /// ```
     pub fn new(items: Vec<String>) -> Self {
-        let mut kw = Self { items };
-        kw.normalize();
-        kw
+        Self::normalize(items, &KeywordRules::default()).0
+    }
+
+    /// Normalize `items` under `rules`, keeping the first-seen casing of duplicates, and
+    /// report what was adjusted or dropped.
+    pub fn normalize(items: Vec<String>, rules: &KeywordRules) -> (Self, KeywordReport) {
+        let mut kept: Vec<String> = Vec::new();
+        let mut report = KeywordReport::default();
+        for raw in items {
+            let keyword = normalize_keyword(&raw, rules);
+            if keyword.is_empty() {
+                report.dropped.push((raw, DropReason::Empty));
+                continue;
+            }
+            if let Some(first) = kept.iter().find(|k| same_keyword(k, &keyword)) {
+                report
+                    .dropped
+                    .push((raw, DropReason::Duplicate(first.clone())));
+                continue;
+            }
+            if kept.len() == rules.max_count {
+                report.dropped.push((raw, DropReason::OverLimit));
+                continue;
+            }
+            if keyword != raw {
+                report.adjusted.push((raw, keyword.clone()));
+            }
+            kept.push(keyword);
+        }
+        (Self { items: kept }, report)
     }
 
     #[allow(dead_code)]
@@ -54,18 +160,241 @@ impl Keywords {
     pub fn into_vec(self) -> Vec<String> {
         self.items
     }
+}
 
-    fn normalize(&mut self) {
-        // Dedup case-insensitively while preserving original casing of first occurrence.
-        let mut seen = Vec::<String>::new();
-        self.items.retain(|kw| {
-            let lower = kw.to_ascii_lowercase();
-            if seen.contains(&lower) {
-                false
+/// Stored form of one keyword: separators become spaces, whitespace is trimmed and collapsed,
+/// and the result is cut to `rules.max_chars` characters. Empty when nothing is left.
+pub fn normalize_keyword(raw: &str, rules: &KeywordRules) -> String {
+    let spaced: String = raw
+        .chars()
+        .map(|c| {
+            if rules.separators.contains(&c) {
+                ' '
             } else {
-                seen.push(lower);
-                true
+                c
             }
-        });
+        })
+        .collect();
+    let collapsed = spaced.split_whitespace().collect::<Vec<_>>().join(" ");
+    collapsed
+        .chars()
+        .take(rules.max_chars)
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
+
+/// Extra fields whose values are mirrored as keywords when saving.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeywordSettings {
+    /// Field labels, matched ignoring case; every field with the label contributes.
+    pub from_fields: Vec<String>,
+}
+
+/// Keywords taken from the values of the fields named in `settings`, normalized and without
+/// duplicates.
+///
+/// Fields are read in the order of [`KeywordSettings::from_fields`]. Each selected value of
+/// a multi-value field becomes its own keyword; empty values and checkboxes add nothing.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let settings = KeywordSettings { from_fields: vec!["organism".into()] };
+/// // A field "Organism" with the value "E. coli" yields `["E. coli"]`.
+/// let keywords = derived_keywords(&fields, &settings);
+/// ```
+pub fn derived_keywords(fields: &[ExtraField], settings: &KeywordSettings) -> Vec<String> {
+    let mut values = Vec::new();
+    for label in &settings.from_fields {
+        let label = label.trim();
+        if label.is_empty() {
+            continue;
+        }
+        for field in fields {
+            if !same_keyword(field.label.trim(), label) || field.kind == ExtraFieldKind::Checkbox {
+                continue;
+            }
+            if field.allow_multi_values {
+                values.extend(field.value_multi.iter().cloned());
+            } else {
+                values.push(field.value.clone());
+            }
+        }
+    }
+    Keywords::new(values).into_vec()
+}
+
+/// Keywords of `derived` that saving adds to `manual`: ones already entered by hand, in any
+/// case, and ones beyond the keyword limit are left out.
+pub fn added_keywords(manual: &[String], derived: &[String]) -> Vec<String> {
+    let kept = Keywords::new(manual.to_vec()).items.len();
+    let mut all = manual.to_vec();
+    all.extend_from_slice(derived);
+    Keywords::new(all).items.split_off(kept)
+}
+
+/// Whether two keywords are the same to eLabFTW, which ignores case.
+pub fn same_keyword(a: &str, b: &str) -> bool {
+    a.to_lowercase() == b.to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Small deterministic generator for property-style checks.
+    fn pseudo_random_keywords(seed: u64, count: usize) -> Vec<String> {
+        const PIECES: [&str; 12] = [
+            "DNA",
+            "dna",
+            " ",
+            "|",
+            ",",
+            "\t",
+            "Ångström",
+            "ÅNGSTRÖM",
+            "x",
+            "  gel ",
+            "",
+            "-",
+        ];
+        let mut state = seed;
+        (0..count)
+            .map(|_| {
+                let mut keyword = String::new();
+                for _ in 0..(state % 5) {
+                    state = state
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    keyword.push_str(PIECES[(state >> 33) as usize % PIECES.len()]);
+                }
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                keyword
+            })
+            .collect()
+    }
+
+    #[test]
+    fn normalize_trims_collapses_strips_separators_and_dedupes() {
+        let (keywords, report) = Keywords::normalize(
+            vec![
+                "  cell   culture ".into(),
+                "PCR|gel".into(),
+                "Cell culture".into(),
+                " , ".into(),
+                "x".repeat(120),
+            ],
+            &KeywordRules::default(),
+        );
+        assert_eq!(
+            keywords.items(),
+            ["cell culture", "PCR gel", &"x".repeat(100)]
+        );
+        assert_eq!(report.adjusted.len(), 3);
+        assert_eq!(
+            report.dropped,
+            [
+                (
+                    "Cell culture".to_string(),
+                    DropReason::Duplicate("cell culture".into())
+                ),
+                (" , ".to_string(), DropReason::Empty),
+            ]
+        );
+        assert!(
+            report
+                .summary()
+                .contains("stored \"PCR|gel\" as \"PCR gel\"")
+        );
+    }
+
+    #[test]
+    fn keywords_beyond_the_limit_are_dropped() {
+        let rules = KeywordRules {
+            max_count: 2,
+            ..KeywordRules::default()
+        };
+        let (keywords, report) =
+            Keywords::normalize(vec!["a".into(), "b".into(), "c".into()], &rules);
+        assert_eq!(keywords.items(), ["a", "b"]);
+        assert_eq!(report.dropped, [("c".to_string(), DropReason::OverLimit)]);
+    }
+
+    #[test]
+    fn normalized_keywords_satisfy_the_rules_for_any_input() {
+        let rules = KeywordRules {
+            max_chars: 12,
+            max_count: 6,
+            ..KeywordRules::default()
+        };
+        for seed in 0..500 {
+            let input = pseudo_random_keywords(seed, (seed % 11) as usize);
+            let (keywords, report) = Keywords::normalize(input.clone(), &rules);
+            let items = keywords.items();
+
+            assert!(items.len() <= rules.max_count);
+            for (i, keyword) in items.iter().enumerate() {
+                assert!(!keyword.is_empty());
+                assert!(keyword.chars().count() <= rules.max_chars);
+                assert_eq!(keyword.trim(), keyword);
+                assert!(!keyword.contains("  ") && !keyword.contains(['|', ',', '\t']));
+                assert!(!items[..i].iter().any(|k| same_keyword(k, keyword)));
+            }
+            // Every input is either kept or reported as dropped.
+            assert_eq!(items.len() + report.dropped.len(), input.len());
+            // Normalizing again changes nothing.
+            let (again, second) = Keywords::normalize(items.to_vec(), &rules);
+            assert_eq!(again.items(), items);
+            assert!(second.is_empty(), "seed {seed}: {second:?}");
+        }
+    }
+
+    fn field(label: &str, value: &str, multi: &[&str]) -> ExtraField {
+        ExtraField {
+            label: label.into(),
+            kind: ExtraFieldKind::Select,
+            value: value.into(),
+            value_multi: multi.iter().map(|v| v.to_string()).collect(),
+            allow_multi_values: !multi.is_empty(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn derived_keywords_expand_multi_values_and_skip_empty_ones() {
+        let settings = KeywordSettings {
+            from_fields: vec!["instrument".into(), " Organism ".into(), "".into()],
+        };
+        let fields = vec![
+            field("Organism", "E. coli", &[]),
+            field("Instrument", "Krios, Glacios", &["Krios", "Glacios"]),
+            field("Organism", "  ", &[]),
+            field("Operator", "Alice", &[]),
+            field("INSTRUMENT", "krios", &[]),
+            ExtraField {
+                kind: ExtraFieldKind::Checkbox,
+                ..field("Organism", "on", &[])
+            },
+        ];
+
+        assert_eq!(
+            derived_keywords(&fields, &settings),
+            ["Krios", "Glacios", "E. coli"]
+        );
+        assert!(derived_keywords(&fields, &KeywordSettings::default()).is_empty());
+    }
+
+    #[test]
+    fn manual_keywords_win_collisions_with_derived_ones() {
+        let manual = vec!["krios".to_string(), "Cryo-EM".to_string()];
+        let derived = vec!["Krios".to_string(), "E. coli".to_string()];
+
+        assert_eq!(added_keywords(&manual, &derived), ["E. coli"]);
+        assert!(added_keywords(&manual, &[]).is_empty());
+
+        let full: Vec<String> = (0..MAX_KEYWORDS).map(|i| format!("k{i}")).collect();
+        assert!(added_keywords(&full, &derived).is_empty(), "over the limit");
     }
 }
@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! License of an entry, exported as a `CreativeWork` node its `Dataset` refers to via
+//! `license`.
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::t;
+use crate::models::extra_fields::is_web_url;
+
+/// Base of the SPDX license list URLs identifying the preset licenses.
+const SPDX_BASE_URL: &str = "https://spdx.org/licenses/";
+
+/// License chosen for an entry.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum License {
+    /// No license is declared, e.g. for proprietary data.
+    #[default]
+    #[serde(rename = "none")]
+    None,
+    #[serde(rename = "CC-BY-4.0")]
+    CcBy4,
+    #[serde(rename = "CC0-1.0")]
+    Cc0,
+    #[serde(rename = "MIT")]
+    Mit,
+    /// Any other license, identified by the URL of its text.
+    #[serde(rename = "url")]
+    Url(String),
+}
+
+impl License {
+    /// Licenses offered in the picker besides a custom URL.
+    pub const PRESETS: [License; 4] = [License::None, License::CcBy4, License::Cc0, License::Mit];
+
+    /// Whether no license is declared.
+    pub fn is_none(&self) -> bool {
+        *self == License::None
+    }
+
+    /// SPDX identifier of a preset license.
+    pub fn spdx_id(&self) -> Option<&'static str> {
+        match self {
+            License::CcBy4 => Some("CC-BY-4.0"),
+            License::Cc0 => Some("CC0-1.0"),
+            License::Mit => Some("MIT"),
+            License::None | License::Url(_) => None,
+        }
+    }
+
+    /// URL identifying the license; `None` when no license is declared.
+    pub fn url(&self) -> Option<String> {
+        match self {
+            License::None => None,
+            License::Url(url) => Some(url.trim().to_string()),
+            preset => preset.spdx_id().map(|id| format!("{SPDX_BASE_URL}{id}")),
+        }
+    }
+
+    /// Full name of the license; the URL for a custom license.
+    pub fn name(&self) -> Option<String> {
+        match self {
+            License::None => None,
+            License::CcBy4 => Some("Creative Commons Attribution 4.0 International".into()),
+            License::Cc0 => Some("Creative Commons Zero v1.0 Universal".into()),
+            License::Mit => Some("MIT License".into()),
+            License::Url(url) => Some(url.trim().to_string()),
+        }
+    }
+
+    /// Short form for messages: the SPDX identifier or the URL.
+    pub fn summary(&self) -> Option<String> {
+        match self {
+            License::Url(url) => Some(url.trim().to_string()),
+            other => other.spdx_id().map(str::to_string),
+        }
+    }
+
+    /// License identified by `url`, recognizing the SPDX URLs of the presets.
+    pub fn from_url(url: &str) -> License {
+        let url = url.trim();
+        let id = url
+            .strip_prefix(SPDX_BASE_URL)
+            .or_else(|| url.strip_prefix("http://spdx.org/licenses/"))
+            .map(|id| id.trim_end_matches(".html"));
+        Self::PRESETS
+            .into_iter()
+            .find(|preset| preset.spdx_id().is_some() && preset.spdx_id() == id)
+            .unwrap_or_else(|| License::Url(url.to_string()))
+    }
+}
+
+/// Check a license and return a copy with a trimmed URL.
+///
+/// # Errors
+///
+/// Returns an error when a custom license has no absolute http/https URL.
+pub fn validate_license(license: &License) -> Result<License> {
+    let License::Url(url) = license else {
+        return Ok(license.clone());
+    };
+    let url = url.trim();
+    if url.is_empty() {
+        bail!(t!("validate.license_url"));
+    }
+    if !is_web_url(url) {
+        bail!(t!("validate.license_invalid", url = url));
+    }
+    Ok(License::Url(url.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_are_identified_by_spdx_urls() {
+        assert_eq!(
+            License::CcBy4.url().as_deref(),
+            Some("https://spdx.org/licenses/CC-BY-4.0")
+        );
+        assert_eq!(License::None.url(), None);
+        for preset in License::PRESETS.into_iter().skip(1) {
+            assert_eq!(License::from_url(&preset.url().unwrap()), preset);
+        }
+        assert_eq!(
+            License::from_url("https://example.org/terms"),
+            License::Url("https://example.org/terms".into())
+        );
+    }
+
+    #[test]
+    fn custom_licenses_need_a_web_url() {
+        assert!(validate_license(&License::Url(" ".into())).is_err());
+        assert!(validate_license(&License::Url("terms.txt".into())).is_err());
+        assert_eq!(
+            validate_license(&License::Url(" https://example.org/terms ".into())).unwrap(),
+            License::Url("https://example.org/terms".into())
+        );
+        assert_eq!(validate_license(&License::Mit).unwrap(), License::Mit);
+    }
+}
@@ -4,5 +4,9 @@
 //! Domain layer: pure data types and validation helpers shared between UI and archive logic.
 
 pub mod attachment;
+pub mod author;
 pub mod extra_fields;
 pub mod keywords;
+pub mod license;
+pub mod performed_at;
+pub mod related_link;
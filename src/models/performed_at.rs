@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Plausibility checks for the performed-at timestamp.
+//!
+//! Implausible dates are warnings, not errors: backfilling old experiments is legitimate, so
+//! the user can confirm the date and save anyway.
+
+use time::{Duration, Month, OffsetDateTime};
+
+use crate::i18n::t;
+
+/// Default age (in years) beyond which a performed-at date is flagged.
+pub const DEFAULT_DATE_HORIZON_YEARS: u32 = 10;
+
+/// How far into the future a timestamp may lie before it is flagged (clock skew, time zones).
+const FUTURE_TOLERANCE: Duration = Duration::hours(24);
+
+/// Reason a performed-at timestamp looks like a typo.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateWarning {
+    /// More than 24 hours after now.
+    InFuture,
+    /// Older than the configured horizon.
+    OlderThan { years: u32 },
+}
+
+impl DateWarning {
+    /// User-facing explanation of the warning.
+    pub fn message(&self) -> String {
+        match self {
+            DateWarning::InFuture => t!("date.warning.future").to_string(),
+            DateWarning::OlderThan { years } => t!("date.warning.old", years = years),
+        }
+    }
+}
+
+/// Check `at` against `now`, flagging future dates and dates older than `horizon_years`.
+///
+/// A horizon of `0` disables the age check.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let now = OffsetDateTime::now_utc();
+/// assert_eq!(check_performed_at(now, now, 10), None);
+/// assert_eq!(
+///     check_performed_at(now + Duration::days(2), now, 10),
+///     Some(DateWarning::InFuture)
+/// );
+/// ```
+pub fn check_performed_at(
+    at: OffsetDateTime,
+    now: OffsetDateTime,
+    horizon_years: u32,
+) -> Option<DateWarning> {
+    if at > now + FUTURE_TOLERANCE {
+        return Some(DateWarning::InFuture);
+    }
+    if horizon_years > 0
+        && let Some(oldest) = years_before(now, horizon_years)
+        && at < oldest
+    {
+        return Some(DateWarning::OlderThan {
+            years: horizon_years,
+        });
+    }
+    None
+}
+
+/// Same instant `years` calendar years earlier; 29 February maps to 28 February.
+fn years_before(now: OffsetDateTime, years: u32) -> Option<OffsetDateTime> {
+    let year = now.year().checked_sub(i32::try_from(years).ok()?)?;
+    let now = if now.month() == Month::February && now.day() == 29 {
+        now.replace_day(28).ok()?
+    } else {
+        now
+    };
+    now.replace_year(year).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    const NOW: OffsetDateTime = datetime!(2025-03-14 12:00 UTC);
+
+    #[test]
+    fn future_tolerance_boundary() {
+        assert_eq!(check_performed_at(NOW + Duration::hours(24), NOW, 10), None);
+        assert_eq!(
+            check_performed_at(NOW + Duration::hours(24) + Duration::seconds(1), NOW, 10),
+            Some(DateWarning::InFuture)
+        );
+    }
+
+    #[test]
+    fn horizon_boundary() {
+        assert_eq!(
+            check_performed_at(datetime!(2015-03-14 12:00 UTC), NOW, 10),
+            None
+        );
+        assert_eq!(
+            check_performed_at(datetime!(2015-03-14 11:59 UTC), NOW, 10),
+            Some(DateWarning::OlderThan { years: 10 })
+        );
+    }
+
+    #[test]
+    fn zero_horizon_disables_age_check() {
+        assert_eq!(
+            check_performed_at(datetime!(1925-01-01 0:00 UTC), NOW, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn leap_day_horizon_uses_february_28() {
+        let leap_now = datetime!(2024-02-29 08:00 UTC);
+        assert_eq!(
+            years_before(leap_now, 1),
+            Some(datetime!(2023-02-28 08:00 UTC))
+        );
+    }
+}
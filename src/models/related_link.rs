@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Links from an entry to related experiments or resources, exported as `CreativeWork` nodes.
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::t;
+use crate::models::extra_fields::is_web_url;
+
+/// How the entry relates to the linked work.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Relation {
+    /// The entry cites the linked work.
+    #[default]
+    References,
+    /// The entry belongs to the linked work, e.g. a project or series.
+    IsPartOf,
+    /// The entry was derived from the linked work, e.g. a protocol it follows.
+    DerivedFrom,
+}
+
+impl Relation {
+    pub const ALL: [Relation; 3] = [
+        Relation::References,
+        Relation::IsPartOf,
+        Relation::DerivedFrom,
+    ];
+
+    /// Label shown in the relation picker.
+    pub fn label(self) -> &'static str {
+        match self {
+            Relation::References => t!("links.relation.references"),
+            Relation::IsPartOf => t!("links.relation.part_of"),
+            Relation::DerivedFrom => t!("links.relation.derived_from"),
+        }
+    }
+
+    /// schema.org property of the entry's `Dataset` node that holds the link.
+    ///
+    /// Derivation uses `isBasedOn`, the schema.org term the RO-Crate context defines for it.
+    pub fn property(self) -> &'static str {
+        match self {
+            Relation::References => "citation",
+            Relation::IsPartOf => "isPartOf",
+            Relation::DerivedFrom => "isBasedOn",
+        }
+    }
+}
+
+/// Typed link to another experiment or resource.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RelatedLink {
+    /// Name of the linked work; the URL is shown when empty.
+    pub label: String,
+    pub url: String,
+    pub relation: Relation,
+}
+
+impl RelatedLink {
+    /// Whether label and URL are blank; blank links are skipped on export.
+    pub fn is_blank(&self) -> bool {
+        self.label.trim().is_empty() && self.url.trim().is_empty()
+    }
+}
+
+/// Check a link and return a copy with trimmed fields.
+///
+/// # Errors
+///
+/// Returns an error when the URL is missing or not an absolute http/https URL.
+pub fn validate_related_link(link: &RelatedLink) -> Result<RelatedLink> {
+    let url = link.url.trim();
+    if url.is_empty() {
+        bail!(t!("validate.link_url"));
+    }
+    if !is_web_url(url) {
+        bail!(t!("validate.link_invalid", url = url));
+    }
+    Ok(RelatedLink {
+        label: link.label.trim().to_string(),
+        url: url.to_string(),
+        relation: link.relation,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_need_a_web_url() {
+        let link = RelatedLink {
+            label: " Gel run 6 ".into(),
+            url: " https://elab.example.org/experiments.php?mode=view&id=6 ".into(),
+            relation: Relation::DerivedFrom,
+        };
+        let valid = validate_related_link(&link).unwrap();
+        assert_eq!(valid.label, "Gel run 6");
+        assert_eq!(
+            valid.url,
+            "https://elab.example.org/experiments.php?mode=view&id=6"
+        );
+
+        for url in ["", "elab.example.org/6", "ftp://example.org/x", "https://"] {
+            let link = RelatedLink {
+                url: url.into(),
+                ..link.clone()
+            };
+            assert!(validate_related_link(&link).is_err(), "{url:?}");
+        }
+    }
+}
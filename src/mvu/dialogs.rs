@@ -0,0 +1,165 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Native file dialogs behind a trait, so commands that ask for paths run without a display.
+//!
+//! The app uses [`RfdDialogs`]; tests hand the command runner a [`ScriptedDialogs`] that
+//! answers with canned paths and records what was asked.
+
+use std::path::{Path, PathBuf};
+
+use crate::logic::favorites;
+
+/// File type filter offered in a dialog.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FileFilter {
+    pub name: &'static str,
+    pub extensions: &'static [&'static str],
+}
+
+/// JSON files: metadata, entry exports, settings, and event traces.
+pub const JSON_FILTER: FileFilter = FileFilter {
+    name: "JSON",
+    extensions: &["json"],
+};
+
+/// ELN archives.
+pub const ELN_FILTER: FileFilter = FileFilter {
+    name: "ELN archive",
+    extensions: &["eln"],
+};
+
+/// Source of the paths users pick; every method returns nothing when the dialog is cancelled.
+///
+/// `start_dir` is the favorite folder to open in, if any; implementations fall back to the
+/// nearest existing parent or the system default.
+pub trait DialogProvider: Send + Sync {
+    /// Pick any number of files.
+    fn pick_files(&self, title: &str, start_dir: Option<&Path>) -> Vec<PathBuf>;
+
+    /// Pick one folder.
+    fn pick_folder(&self, title: &str, start_dir: Option<&Path>) -> Option<PathBuf>;
+
+    /// Pick one file matching `filter`.
+    fn pick_file_with_filter(
+        &self,
+        title: &str,
+        start_dir: Option<&Path>,
+        filter: FileFilter,
+    ) -> Option<PathBuf>;
+
+    /// Choose where to save a file, suggesting `file_name`.
+    fn save_file(
+        &self,
+        title: &str,
+        start_dir: Option<&Path>,
+        filter: FileFilter,
+        file_name: &str,
+    ) -> Option<PathBuf>;
+}
+
+/// Platform dialogs shown through `rfd`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RfdDialogs;
+
+impl RfdDialogs {
+    /// Dialog titled `title` that opens in the favorite `start_dir`, or the nearest existing
+    /// parent of it; missing favorites fall back to the system default.
+    fn dialog(title: &str, start_dir: Option<&Path>) -> rfd::FileDialog {
+        let dialog = rfd::FileDialog::new().set_title(title);
+        match favorites::resolve_start_dir(start_dir, Path::is_dir) {
+            Some(dir) => dialog.set_directory(dir),
+            None => dialog,
+        }
+    }
+}
+
+impl DialogProvider for RfdDialogs {
+    fn pick_files(&self, title: &str, start_dir: Option<&Path>) -> Vec<PathBuf> {
+        Self::dialog(title, start_dir)
+            .pick_files()
+            .unwrap_or_default()
+    }
+
+    fn pick_folder(&self, title: &str, start_dir: Option<&Path>) -> Option<PathBuf> {
+        Self::dialog(title, start_dir).pick_folder()
+    }
+
+    fn pick_file_with_filter(
+        &self,
+        title: &str,
+        start_dir: Option<&Path>,
+        filter: FileFilter,
+    ) -> Option<PathBuf> {
+        Self::dialog(title, start_dir)
+            .add_filter(filter.name, filter.extensions)
+            .pick_file()
+    }
+
+    fn save_file(
+        &self,
+        title: &str,
+        start_dir: Option<&Path>,
+        filter: FileFilter,
+        file_name: &str,
+    ) -> Option<PathBuf> {
+        Self::dialog(title, start_dir)
+            .add_filter(filter.name, filter.extensions)
+            .set_file_name(file_name)
+            .save_file()
+    }
+}
+
+/// Dialogs that answer from a script instead of asking anyone.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct ScriptedDialogs {
+    answers: std::sync::Mutex<std::collections::VecDeque<Vec<PathBuf>>>,
+    asked: std::sync::Mutex<Vec<String>>,
+}
+
+#[cfg(test)]
+impl ScriptedDialogs {
+    /// Answer the next dialogs with `answers` in order; an empty answer cancels. Dialogs
+    /// beyond the script are cancelled too.
+    pub fn new(answers: impl IntoIterator<Item = Vec<PathBuf>>) -> Self {
+        Self {
+            answers: std::sync::Mutex::new(answers.into_iter().collect()),
+            asked: std::sync::Mutex::default(),
+        }
+    }
+
+    /// Titles of the dialogs shown so far, in order.
+    pub fn asked(&self) -> Vec<String> {
+        self.asked.lock().unwrap().clone()
+    }
+
+    fn answer(&self, title: &str) -> Vec<PathBuf> {
+        self.asked.lock().unwrap().push(title.to_string());
+        self.answers.lock().unwrap().pop_front().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+impl DialogProvider for ScriptedDialogs {
+    fn pick_files(&self, title: &str, _: Option<&Path>) -> Vec<PathBuf> {
+        self.answer(title)
+    }
+
+    fn pick_folder(&self, title: &str, _: Option<&Path>) -> Option<PathBuf> {
+        self.answer(title).into_iter().next()
+    }
+
+    fn pick_file_with_filter(
+        &self,
+        title: &str,
+        _: Option<&Path>,
+        _: FileFilter,
+    ) -> Option<PathBuf> {
+        self.answer(title).into_iter().next()
+    }
+
+    fn save_file(&self, title: &str, _: Option<&Path>, _: FileFilter, _: &str) -> Option<PathBuf> {
+        self.answer(title).into_iter().next()
+    }
+}
@@ -3,31 +3,89 @@
 
 //! Root Model-View-Update kernel wiring component state, messages, and commands.
 
-use std::path::PathBuf;
+pub mod dialogs;
+pub mod startup;
+pub mod status;
+pub mod trace;
+pub mod workers;
 
-use crate::logic::eln::{ArchiveGenre, build_and_write_archive};
-use crate::models::attachment::Attachment;
-use crate::models::extra_fields::{ExtraField, ExtraFieldGroup};
-use crate::models::keywords::Keywords;
+use std::cell::RefCell;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::i18n::t;
+use crate::logic::authors;
+use crate::logic::batch::{BatchJob, BatchOptions, find_batch_jobs, run_batch_job};
+use crate::logic::draft::{self, DRAFT_SCHEMA_VERSION, Draft};
+use crate::logic::elabftw::{self, UploadProgress, UploadTarget};
+use crate::logic::elabftw_entry::{ElabftwEntry, parse_elabftw_entry};
+use crate::logic::eln::{
+    ArchiveEntry, ArchiveGenre, AttachmentIntegrityError, Compression, CompressionOptions,
+    ExportOptions, MissingFileAction, MissingFilePolicy, Resumable, ResumeOutcome, WriteCancelled,
+    WriteProgress, build_and_write_archive_resumable, ensure_extension,
+    missing_attachment_references, suggested_archive_name,
+};
+use crate::logic::eln_import::{OpenedArchive, open_archive};
+use crate::logic::favorites::PickerKind;
+use crate::logic::html_export::HtmlPolicy;
+use crate::logic::language::normalize_language_tag;
+use crate::logic::save_resume::{
+    CHECKPOINT_BYTES, has_resume_state, partial_path, remove_resume_state,
+};
+use crate::logic::settings::{self, Settings, SettingsExport};
+use crate::logic::signing;
+use crate::logic::templates::{self, Template};
+use crate::logic::vocabulary;
+use crate::models::attachment::{Attachment, archive_path};
+use crate::models::author::{Author, validate_author};
+use crate::models::extra_fields::{
+    ExtraField, ExtraFieldsImport, ExtraFieldsSource, parse_elabftw_extra_fields,
+};
+use crate::models::keywords::{KeywordRules, KeywordSettings, Keywords, derived_keywords};
+use crate::models::license::{License, validate_license};
+use crate::models::performed_at::{DateWarning, check_performed_at};
+use crate::models::related_link::{RelatedLink, validate_related_link};
 use crate::ui::components::attachments::{
-    self, AttachmentsCommand, AttachmentsModel, AttachmentsMsg,
+    self, AttachmentsCommand, AttachmentsModel, AttachmentsMsg, MAX_FOLDER_FILES, PreviewContent,
+    ThumbnailError, format_bytes,
+};
+use crate::ui::components::authors::{
+    self as authors_ui, AuthorsCommand, AuthorsModel, AuthorsMsg,
 };
 use crate::ui::components::datetime_picker::{self, DateTimeModel, DateTimeMsg};
 use crate::ui::components::extra_fields::{
     self, ExtraFieldsCommand, ExtraFieldsModel, ExtraFieldsMsg,
 };
 use crate::ui::components::keywords::{self, KeywordsModel, KeywordsMsg};
-use crate::ui::components::markdown::{MarkdownModel, MarkdownMsg};
+use crate::ui::components::markdown::{self, MarkdownModel, MarkdownMsg};
+use crate::ui::components::onboarding::{
+    self, OnboardingAnswers, OnboardingEvent, OnboardingModel, OnboardingMsg,
+};
+use crate::ui::components::related_links::{self, RelatedLinksModel, RelatedLinksMsg};
+use crate::ui::components::settings::{
+    self as settings_ui, SettingsCommand, SettingsModel, SettingsMsg,
+};
+use crate::ui::components::templates::{
+    self as templates_ui, TemplatesCommand, TemplatesModel, TemplatesMsg,
+};
+use crate::utils::file_preview;
+use crate::utils::folder_scan::scan_folder;
+use crate::utils::hash::{Digests, HashAlgorithm};
 
-/// Top-level application state.
+use dialogs::{DialogProvider, ELN_FILTER, JSON_FILTER};
+use startup::PersistedFile;
+use status::{Severity, StatusHistory, StatusMessage};
+
+/// Editor state of one entry; the archive stores one dataset per entry.
 #[derive(Default)]
-pub struct AppModel {
+pub struct EntryModel {
     /// User-facing entry title.
-    pub entry_title: String,
+    pub title: String,
     /// Selected archive genre for metadata.
-    pub archive_genre: ArchiveGenre,
-    /// How the body should be stored (raw markdown or rendered HTML).
-    pub body_format: crate::logic::eln::BodyFormat,
+    pub genre: ArchiveGenre,
     /// Markdown editor state.
     pub markdown: MarkdownModel,
     /// Attachment picker state.
@@ -38,24 +96,347 @@ pub struct AppModel {
     pub extra_fields: ExtraFieldsModel,
     /// Date/time picker state.
     pub datetime: DateTimeModel,
+    /// User confirmed the current performed-at date despite a plausibility warning.
+    pub performed_at_confirmed: bool,
+    /// Template the entry was started from, compared with its fields to show edits.
+    pub template: Option<Box<Template>>,
+    /// Language tag typed as override; empty to detect the body's language when saving.
+    pub language: String,
+    /// Links to related experiments and resources.
+    pub related_links: RelatedLinksModel,
+    /// License declared for the entry; a custom URL is validated when saving.
+    pub license: License,
+    /// Short abstract shown next to the title in crate listings; trimmed when saving.
+    pub summary: String,
+}
+
+/// Top-level application state.
+#[derive(Default)]
+pub struct AppModel {
+    /// Entry shown in the editor; component messages are routed here.
+    pub entry: EntryModel,
+    /// The other entries of the archive, in list order without the active one.
+    pub other_entries: Vec<EntryModel>,
+    /// Position of `entry` in the entry list.
+    pub active_entry: usize,
+    /// How the body should be stored (raw markdown or rendered HTML).
+    pub body_format: crate::logic::eln::BodyFormat,
+    /// Persistent user settings and the settings window state.
+    pub settings: SettingsModel,
+    /// Authors credited in saved archives; shared by all entries.
+    pub authors: AuthorsModel,
+    /// First-run setup dialog.
+    pub onboarding: OnboardingModel,
     /// Latest status message to display.
-    pub status: Option<String>,
+    pub status: Option<StatusMessage>,
+    /// Recent status messages, for the history popup.
+    pub status_history: StatusHistory,
     /// Latest error message to display in modal.
     pub error: Option<String>,
+    /// Title of the error modal; a generic one when `None`.
+    pub error_title: Option<&'static str>,
     /// Count of queued background commands.
     pub pending_commands: usize,
+    /// Count of file dialogs waiting for an answer.
+    pub open_dialogs: usize,
+    /// Location of the autosaved draft; `None` disables draft persistence.
+    pub draft_path: Option<PathBuf>,
+    /// Whether the current form content was restored from a previous session's draft.
+    pub draft_restored: bool,
+    /// Location of the settings file; `None` keeps settings in memory only.
+    pub settings_path: Option<PathBuf>,
+    /// Location of the keyword vocabulary; `None` keeps suggestions in memory only.
+    pub vocabulary_path: Option<PathBuf>,
+    /// Location of the author list; `None` keeps authors in memory only.
+    pub authors_path: Option<PathBuf>,
+    /// Folder holding entry templates; `None` disables templates.
+    pub templates_path: Option<PathBuf>,
+    /// Location of the eLabFTW API key; `None` keeps the key in memory only.
+    pub api_key_path: Option<PathBuf>,
+    /// Template menu and dialogs.
+    pub templates: TemplatesModel,
+    /// Loaded template waiting for the user to agree to replace the current entries.
+    pub template_confirmation: Option<Box<Template>>,
+    /// Folder pasted images are written to; `None` disables pasting images.
+    pub paste_dir: Option<PathBuf>,
+    /// Folder the shown archive was extracted to; deleted when another archive is opened.
+    pub open_dir: Option<PathBuf>,
+    /// Opened archive waiting for the user to agree to replace the unsaved entries.
+    pub open_confirmation: Option<OpenConfirmation>,
+    /// Save waiting for the user to confirm an implausible performed-at date.
+    pub date_confirmation: Option<DateConfirmation>,
+    /// Save waiting for the user to accept attachments above the size warning.
+    pub size_confirmation: Option<SizeConfirmation>,
+    /// Large attachments were accepted for the save being started.
+    pub sizes_confirmed: bool,
+    /// The save being started or running writes a template archive; see [`template_archive`].
+    pub saving_template: bool,
+    /// Archive of the last successful save; "Save" writes there again.
+    pub last_saved: Option<PathBuf>,
+    /// Existing archive the user asked to save over, waiting for confirmation.
+    pub overwrite_confirmation: Option<PathBuf>,
+    /// Warning about the running save, shown together with its result.
+    pub save_warning: Option<String>,
+    /// What saves do with attachments that can no longer be read while they are written.
+    pub missing_files: MissingFilePolicy,
+    /// Attachment the running save could not read, waiting for the user to decide.
+    pub missing_file_prompt: Option<PathBuf>,
+    /// Recent messages for bug reports; `None` unless started with `--debug-events`.
+    pub event_trace: Option<trace::EventTrace>,
+    /// Entries changed since they were last saved, opened, or discarded.
+    pub dirty: bool,
+    /// Progress of closing the window while there are unsaved changes.
+    pub quit: QuitState,
+    /// Do not upload the next saved archive, although uploading is enabled.
+    pub skip_upload: bool,
+    /// Compression of the next saved archive when it differs from the settings.
+    pub compression: Option<Compression>,
+    /// A saved archive is being uploaded to eLabFTW.
+    pub uploading: bool,
+    /// Bytes the running upload sent so far, for the status bar.
+    pub upload_progress: Option<UploadProgress>,
+    /// An archive is being written; the Save button cancels it meanwhile.
+    pub saving: bool,
+    /// Attachment bytes written by the running save, for the status bar.
+    pub save_progress: Option<WriteProgress>,
+    /// Size and writing time of the archive just written, reported with the save.
+    pub save_stats: Option<(u64, Duration)>,
+    /// Archive whose failed save left a checkpoint; the error modal offers to resume it.
+    pub resumable_save: Option<PathBuf>,
+    /// Started without loading persisted files; `None` outside safe mode.
+    pub safe_mode: Option<startup::SafeMode>,
+    /// Batch packaging run shown in the batch window; kept after it finishes until closed.
+    pub batch: Option<BatchRun>,
+}
+
+/// Archives written by batch packaging, one per job.
+pub struct BatchRun {
+    pub jobs: Vec<BatchJob>,
+    /// State of each job, by position in `jobs`.
+    pub states: Vec<BatchJobState>,
+    /// Read by the queued jobs; set to cancel all that have not finished.
+    pub cancel: Arc<AtomicBool>,
+}
+
+impl BatchRun {
+    /// Whether no job is waiting or running any more.
+    pub fn finished(&self) -> bool {
+        self.states.iter().all(BatchJobState::is_finished)
+    }
+
+    /// Count of jobs in a state matching `filter`.
+    pub fn count(&self, filter: impl Fn(&BatchJobState) -> bool) -> usize {
+        self.states.iter().filter(|state| filter(state)).count()
+    }
+}
+
+/// Progress of one batch job.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BatchJobState {
+    Pending,
+    Running,
+    /// Written to the given archive.
+    Done(PathBuf),
+    Failed(String),
+    Cancelled,
+}
+
+impl BatchJobState {
+    /// Whether the job will not change any more.
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, BatchJobState::Pending | BatchJobState::Running)
+    }
+}
+
+/// Steps of closing the window with unsaved changes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QuitState {
+    /// No close requested.
+    #[default]
+    Idle,
+    /// Asking whether to save, discard, or keep working.
+    Confirming,
+    /// Close once the save started from the prompt succeeds.
+    AwaitingSave,
+    /// Close once the discarded draft is deleted.
+    AwaitingDraftDelete,
+    /// Close once the saved archive is uploaded.
+    AwaitingUpload,
+    /// The window may close now.
+    Ready,
+}
+
+/// Save request held back until the performed-at date is confirmed.
+pub struct DateConfirmation {
+    pub warning: DateWarning,
+    /// Position of the entry whose date needs confirming.
+    pub entry: usize,
+    /// Archive path the save was requested for.
+    pub output: PathBuf,
+    /// The save writes a template archive.
+    pub template: bool,
+}
+
+/// Opened archive held back until the user agrees to discard the unsaved entries.
+pub struct OpenConfirmation {
+    /// Archive file that was opened.
+    pub path: PathBuf,
+    /// Folder its attachments were extracted to.
+    pub extract_dir: PathBuf,
+    pub archive: Box<OpenedArchive>,
+}
+
+/// Save request held back until large attachments are accepted.
+pub struct SizeConfirmation {
+    /// Which attachments exceed the size warning, e.g. "2 attachments exceed 1.0 GB".
+    pub message: String,
+    /// Archive path the save was requested for.
+    pub output: PathBuf,
+    /// The save writes a template archive.
+    pub template: bool,
 }
 
 /// Application messages routed through the update function.
+#[derive(Debug)]
 pub enum Msg {
+    /// Append an empty entry and show it in the editor.
+    AddEntry,
+    /// Append a copy of the active entry and show it in the editor.
+    DuplicateEntry,
+    /// Delete the entry at the given list position; the last entry cannot be removed.
+    RemoveEntry(usize),
+    /// Show the entry at the given list position in the editor.
+    SelectEntry(usize),
     EntryTitleChanged(String),
+    /// Short summary of the active entry, exported as the dataset's `description`.
+    SummaryChanged(String),
+    /// Language override of the active entry; blank to detect it.
+    SetLanguage(String),
     SetGenre(ArchiveGenre),
+    /// License of the active entry; remembered as the default for new entries once valid.
+    SetLicense(License),
     SetBodyFormat(crate::logic::eln::BodyFormat),
     SaveRequested(PathBuf),
+    /// Save again to a known archive path without the file dialog, after confirmation.
+    ResaveRequested(PathBuf),
+    /// Overwrite the archive waiting in `overwrite_confirmation`.
+    ConfirmOverwrite,
+    CancelOverwrite,
     SaveCancelled,
+    /// A disabled save button was clicked; point out what keeps the entry from saving.
+    SaveBlocked,
+    /// Answer of the file dialog opened for `TemplatesMsg::SaveArchive`; `None` if cancelled.
+    TemplateArchivePicked(Option<PathBuf>),
     SaveCompleted(Result<PathBuf, String>),
+    /// The running save copied more attachment bytes.
+    SaveProgress {
+        current_file: String,
+        bytes_done: u64,
+        bytes_total: u64,
+    },
+    /// Stop the running save and delete the partial archive.
+    CancelSave,
+    MissingFilePolicyChanged(MissingFilePolicy),
+    /// The running save cannot read this attachment and waits for `ResolveMissingFile`.
+    SaveMissingFile(PathBuf),
+    /// Answer the running save about the attachment in `missing_file_prompt`.
+    ResolveMissingFile(MissingFileAction),
+    /// The running save left out these attachments because they could not be read.
+    SaveSkippedFiles(Vec<PathBuf>),
+    /// The save to `output` failed after a checkpoint, so it can be resumed.
+    SaveInterrupted {
+        output: PathBuf,
+        error: String,
+    },
+    /// Retry the save in `resumable_save`, keeping the attachments it already wrote.
+    ResumeSave,
+    /// A save asked to resume started, continuing or starting over.
+    SaveResumed(ResumeOutcome),
+    /// The running save wrote an archive of `size` bytes in `elapsed`.
+    SaveWritten {
+        size: u64,
+        elapsed: Duration,
+    },
+    /// The archive was saved and then signed; `signature` is the sidecar or why signing
+    /// failed, which leaves the archive as written.
+    ArchiveSigned {
+        archive: PathBuf,
+        signature: Result<PathBuf, String>,
+    },
+    /// Upload the next saved archive to eLabFTW; unchecking skips that one save.
+    SetUploadOnSave(bool),
+    /// Compress the next saved archive with this method instead of the one in the settings.
+    SetSaveCompression(Compression),
+    /// Upload finished with the address of the imported entry.
+    UploadCompleted(Result<String, String>),
+    /// The running upload sent more of the archive.
+    UploadProgress(UploadProgress),
+    /// Stop the running upload; the saved archive is kept.
+    CancelUpload,
+    UploadCancelled,
+    /// Saving stopped at an attachment that could not be read or no longer matches its hash.
+    AttachmentVerificationFailed {
+        path: PathBuf,
+        unreadable: bool,
+    },
+    /// User confirmed the flagged performed-at date; resumes the held-back save.
+    ConfirmPerformedAt,
+    /// User declined to save with the flagged performed-at date.
+    CancelPerformedAtConfirmation,
+    /// User accepted the attachments above the size warning; resumes the held-back save.
+    ConfirmAttachmentSizes,
+    /// User declined to save the attachments above the size warning.
+    CancelAttachmentSizeConfirmation,
+    /// User picked an existing archive to load into the form.
+    OpenArchiveRequested(PathBuf),
+    OpenArchiveCancelled,
+    /// Pick a folder of entry subfolders and an output folder for batch packaging.
+    BatchRequested,
+    /// Jobs found in the picked folders; `None` when a dialog was cancelled.
+    BatchPrepared(Result<Option<Vec<BatchJob>>, String>),
+    /// A batch job started or finished.
+    BatchJobUpdate {
+        index: usize,
+        state: BatchJobState,
+    },
+    /// Cancel all batch jobs that have not finished.
+    CancelBatch,
+    /// Close the window of a finished batch.
+    CloseBatch,
+    /// eLabFTW entry export read for the active entry.
+    ElabftwEntryImported {
+        path: PathBuf,
+        result: Result<Box<ElabftwEntry>, String>,
+    },
+    /// Result of [`Command::OpenArchive`]; the attachments are extracted to `extract_dir`.
+    ArchiveOpened {
+        path: PathBuf,
+        extract_dir: PathBuf,
+        result: Result<Box<OpenedArchive>, String>,
+    },
+    /// Replace the unsaved entries with the opened archive.
+    ConfirmOpen,
+    CancelOpen,
+    /// Result of deleting the extracted files of an archive no longer shown.
+    ExtractedRemoved(Result<(), String>),
     OpenHelp,
     HelpOpened(Result<(), String>),
+    /// Load one persisted file skipped in safe mode.
+    LoadPersisted(PersistedFile),
+    /// A file asked for with `LoadPersisted` was read; the message applies it.
+    PersistedLoaded {
+        file: PersistedFile,
+        path: PathBuf,
+        result: Result<Option<Box<Msg>>, String>,
+    },
+    /// Show the folder holding the settings in the file manager.
+    OpenConfigFolder,
+    FolderOpened(Result<(), String>),
+    /// Show the first-run setup, prefilled with the current settings and first author.
+    OpenOnboarding,
+    /// Write the recorded event trace to the given file.
+    ExportEventTrace(PathBuf),
+    EventTraceExported(Result<PathBuf, String>),
     /// Decoded thumbnail image staged for UI-side texture realization.
     ///
     /// `ElnPackApp::realize_pending_thumbnail_textures` consumes this runtime message
@@ -73,55 +454,255 @@ pub enum Msg {
     ThumbnailFailed {
         path: PathBuf,
         request_id: u64,
+        reason: ThumbnailError,
+    },
+    /// Full-size image for the preview dialog, staged for UI-side texture realization.
+    ///
+    /// Like [`Msg::ThumbnailDecoded`], the runtime shell consumes this message and forwards
+    /// [`AttachmentsMsg::PreviewReady`] once the texture exists.
+    PreviewImageDecoded {
+        path: PathBuf,
+        image: eframe::egui::ColorImage,
     },
     DismissError,
+    /// Draft loaded from disk at startup; its attachments are checked before it is applied.
+    DraftRestored(Box<Draft>),
+    /// Draft whose attachments were re-hashed, to be applied to the form; `missing` lists
+    /// those that vanished or changed on disk and were dropped from it.
+    DraftChecked {
+        draft: Box<Draft>,
+        missing: Vec<Attachment>,
+    },
+    /// Keep the restored draft and hide the notice.
+    DismissDraftNotice,
+    /// Clear the form and delete the persisted draft.
+    DiscardDraft,
+    DraftDeleted(Result<(), String>),
+    /// Result of writing the keyword vocabulary to disk.
+    VocabularySaved(Result<(), String>),
+    /// Ctrl+V outside text fields; attach the clipboard image, if any.
+    PasteImage,
+    /// Clipboard image written to the given file of the given size in bytes.
+    ImagePasted(Result<(PathBuf, u64), String>),
     Markdown(MarkdownMsg),
     Attachments(AttachmentsMsg),
     Keywords(KeywordsMsg),
+    RelatedLinks(RelatedLinksMsg),
     ExtraFields(ExtraFieldsMsg),
     DateTime(DateTimeMsg),
+    Settings(SettingsMsg),
+    Authors(AuthorsMsg),
+    Onboarding(OnboardingMsg),
+    Templates(TemplatesMsg),
+    /// Template read for "New from template"; applied at once when the form is empty.
+    TemplateLoaded(Result<Box<Template>, String>),
+    /// Replace the current entries with the loaded template.
+    ConfirmTemplate,
+    CancelTemplate,
+    /// The window close button was pressed.
+    CloseRequested,
+    /// Save the archive through the save dialog, then close.
+    SaveAndQuit,
+    /// Close without saving and delete the autosaved draft.
+    DiscardAndQuit,
+    /// Keep the window open.
+    CancelQuit,
 }
 
 /// Commands represent side-effects executed between frames.
 pub enum Command {
-    PickFiles,
+    /// Dialogs start in `start_dir` (a favorite folder) when it still exists.
+    PickFiles {
+        start_dir: Option<PathBuf>,
+    },
+    PickFolder {
+        start_dir: Option<PathBuf>,
+    },
     HashFile {
         path: PathBuf,
+        /// Digests to compute, from the hashing settings at the time the file was picked.
+        algorithms: Vec<HashAlgorithm>,
         _retry: bool,
+        /// Set by the UI shell to abort hashing early.
+        cancel: Arc<AtomicBool>,
+    },
+    /// Abort a running [`Command::HashFile`]; handled by the UI shell, never by a worker.
+    CancelHash {
+        path: PathBuf,
     },
     LoadThumbnail {
         path: PathBuf,
         _retry: bool,
         request_id: u64,
     },
-    PickExtraFieldsFile,
+    /// Load an attachment's contents for the preview dialog.
+    LoadPreview {
+        path: PathBuf,
+    },
+    PickExtraFieldsFile {
+        start_dir: Option<PathBuf>,
+    },
+    /// Pick and parse an eLabFTW entry export for the active entry.
+    PickElabftwEntryFile {
+        start_dir: Option<PathBuf>,
+    },
+    /// Re-read a previously imported metadata file.
+    ReadExtraFieldsFile {
+        path: PathBuf,
+    },
     OpenUrl {
         url: String,
     },
+    /// Show a folder in the file manager.
+    OpenFolder(PathBuf),
+    /// Read one persisted file in safe mode.
+    LoadPersisted {
+        file: PersistedFile,
+        path: PathBuf,
+    },
     SaveArchive(SavePayload),
+    /// Abort a running [`Command::SaveArchive`]; handled by the UI shell, never by a worker.
+    CancelSave,
+    /// Pass the user's decision on a missing attachment to the running save; handled by the
+    /// UI shell like `CancelSave`.
+    ResolveMissingFile(MissingFileAction),
+    OpenArchive(PathBuf),
+    /// Delete the folder an opened archive was extracted to.
+    RemoveExtracted(PathBuf),
+    /// Ask for the folder of entry subfolders and the output folder of a batch.
+    PickBatchFolders {
+        start_dir: Option<PathBuf>,
+    },
+    /// Write the archive of one batch job.
+    RunBatchJob {
+        index: usize,
+        job: BatchJob,
+        options: Arc<BatchOptions>,
+        cancel: Arc<AtomicBool>,
+    },
+    /// Re-hash the attachments of a restored draft before it is applied.
+    CheckDraftAttachments(Box<Draft>),
+    DeleteDraft {
+        path: PathBuf,
+    },
+    SaveSettings {
+        path: PathBuf,
+        settings: Settings,
+    },
+    SaveVocabulary {
+        path: PathBuf,
+        keywords: Vec<String>,
+    },
+    SaveAuthors {
+        path: PathBuf,
+        authors: Vec<Author>,
+    },
+    /// Report which favorite folders exist.
+    CheckFavorites(Vec<PathBuf>),
+    /// Write the clipboard image as PNG into `dir`.
+    PasteImage {
+        dir: PathBuf,
+    },
+    /// Write a serialized event trace to `path`.
+    WriteEventTrace {
+        path: PathBuf,
+        json: String,
+    },
+    SaveTemplate {
+        dir: PathBuf,
+        template: Box<Template>,
+    },
+    LoadTemplate {
+        dir: PathBuf,
+        name: String,
+    },
+    /// Read a template for the template editor, optionally as a copy.
+    LoadTemplateForEdit {
+        dir: PathBuf,
+        name: String,
+        copy: bool,
+    },
+    RenameTemplate {
+        dir: PathBuf,
+        from: String,
+        to: String,
+    },
+    DeleteTemplate {
+        dir: PathBuf,
+        name: String,
+    },
+    /// Import a saved archive into eLabFTW.
+    UploadArchive {
+        path: PathBuf,
+        target: UploadTarget,
+        /// Set by the UI shell to stop sending the archive.
+        cancel: Arc<AtomicBool>,
+    },
+    /// Abort a running [`Command::UploadArchive`]; handled by the UI shell like `CancelSave`.
+    CancelUpload,
+    /// Write the eLabFTW API key; an empty key deletes the file.
+    SaveApiKey {
+        path: PathBuf,
+        key: String,
+    },
+    /// Ask for a file and write the settings export to it.
+    ExportSettings(Box<SettingsExport>),
+    /// Ask where to write a template archive, offering `default_name`.
+    PickTemplateArchive {
+        start_dir: Option<PathBuf>,
+        default_name: String,
+    },
+    /// Ask for a settings export and read it.
+    ImportSettings,
+}
+
+/// Where a [`Command`] runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommandKind {
+    /// Shows a file dialog and blocks until it is answered; runs on the dialog thread.
+    Dialog,
+    /// File IO or computation; runs on the worker pool and counts as pending work.
+    Background,
+}
+
+impl Command {
+    /// Whether this command waits for a file dialog.
+    pub fn kind(&self) -> CommandKind {
+        match self {
+            Command::PickFiles { .. }
+            | Command::PickFolder { .. }
+            | Command::PickExtraFieldsFile { .. }
+            | Command::PickElabftwEntryFile { .. }
+            | Command::ExportSettings(_)
+            | Command::PickTemplateArchive { .. }
+            | Command::PickBatchFolders { .. }
+            | Command::ImportSettings => CommandKind::Dialog,
+            _ => CommandKind::Background,
+        }
+    }
 }
 
 /// Captured, validated data for saving.
 pub struct SavePayload {
     /// Final archive path on disk (with `.eln` extension enforced).
     pub output: PathBuf,
-    /// Entry title.
-    pub title: String,
-    /// Markdown text from the editor.
-    pub body: String,
-    /// Attachment metadata (already sanitized and hashed).
-    pub attachments: Vec<Attachment>,
-    /// Timestamp for when the entry was performed.
-    pub performed_at: time::OffsetDateTime,
-    /// Selected archive genre.
-    pub genre: ArchiveGenre,
-    /// Normalized keywords.
-    pub keywords: Vec<String>,
-    /// Imported extra fields metadata.
-    pub extra_fields: Vec<ExtraField>,
-    pub extra_groups: Vec<ExtraFieldGroup>,
+    /// Validated entries in list order.
+    pub entries: Vec<ArchiveEntry>,
     /// Stored body format (HTML or Markdown).
     pub body_format: crate::logic::eln::BodyFormat,
+    /// Export-time options derived from settings.
+    pub export_options: ExportOptions,
+    /// Private key the written archive is signed with.
+    pub signing_key: Option<PathBuf>,
+    /// Set by the UI shell to abort the save and delete the partial archive.
+    pub cancel: Arc<AtomicBool>,
+    /// Continue from the checkpoint a failed save to `output` left.
+    pub resume: bool,
+    /// What to do with attachments that can no longer be read.
+    pub missing_files: MissingFilePolicy,
+    /// Set by the UI shell to answer the save about a missing attachment under
+    /// [`MissingFilePolicy::Ask`].
+    pub missing_file_answer: Arc<Mutex<Option<MissingFileAction>>>,
 }
 
 /// Update the top-level application state in place and append any produced commands.
@@ -142,38 +723,269 @@ pub struct SavePayload {
 /// let mut model = AppModel::default();
 /// let mut cmds = Vec::new();
 /// update(&mut model, Msg::EntryTitleChanged("New title".into()), &mut cmds);
-/// assert_eq!(model.entry_title, "New title");
+/// assert_eq!(model.entry.title, "New title");
 /// ```
 pub fn update(model: &mut AppModel, msg: Msg, cmds: &mut Vec<Command>) {
+    let edits = edits_entries(&msg);
+    dispatch(model, msg, cmds);
+    if edits {
+        model.dirty = true;
+    }
+    sync_image_attachments(model);
+}
+
+/// Whether `msg` changes the content of an entry, making it unsaved.
+///
+/// Selection, dialog, progress and thumbnail messages leave the content alone.
+fn edits_entries(msg: &Msg) -> bool {
+    match msg {
+        Msg::AddEntry
+        | Msg::DuplicateEntry
+        | Msg::RemoveEntry(_)
+        | Msg::EntryTitleChanged(_)
+        | Msg::SummaryChanged(_)
+        | Msg::SetLanguage(_)
+        | Msg::SetGenre(_)
+        | Msg::SetLicense(_)
+        | Msg::SetBodyFormat(_)
+        | Msg::DraftChecked { .. }
+        | Msg::ImagePasted(Ok(_))
+        | Msg::TemplateLoaded(Ok(_))
+        | Msg::ConfirmTemplate
+        | Msg::DateTime(_) => true,
+        Msg::Markdown(m) => matches!(
+            m,
+            MarkdownMsg::SetText(_)
+                | MarkdownMsg::InsertHeading(_)
+                | MarkdownMsg::ApplyStyle(_)
+                | MarkdownMsg::InsertTable { .. }
+                | MarkdownMsg::InsertAttachmentImage { .. }
+                | MarkdownMsg::Undo
+                | MarkdownMsg::Redo
+        ),
+        Msg::Attachments(m) => matches!(
+            m,
+            AttachmentsMsg::HashComputed { .. }
+                | AttachmentsMsg::Remove(_)
+                | AttachmentsMsg::MoveUp(_)
+                | AttachmentsMsg::MoveDown(_)
+                | AttachmentsMsg::SortByName
+                | AttachmentsMsg::CommitEdit
+                | AttachmentsMsg::AcceptSanitizedName
+                | AttachmentsMsg::CommitAcquired
+                | AttachmentsMsg::SetDescription { .. }
+                | AttachmentsMsg::ConfirmRemove
+                | AttachmentsMsg::SetInFigureList { .. }
+        ),
+        Msg::Keywords(m) => matches!(
+            m,
+            KeywordsMsg::AddFromModal
+                | KeywordsMsg::CommitEdit
+                | KeywordsMsg::Remove(_)
+                | KeywordsMsg::ConfirmRemove
+                | KeywordsMsg::RemoveMany(_)
+                | KeywordsMsg::Clear
+                | KeywordsMsg::SuggestionPicked(_)
+        ),
+        Msg::RelatedLinks(m) => !matches!(m, RelatedLinksMsg::Restore(_)),
+        Msg::ExtraFields(m) => matches!(
+            m,
+            ExtraFieldsMsg::RemoveField(_)
+                | ExtraFieldsMsg::DuplicateField(_)
+                | ExtraFieldsMsg::ImportLoaded { .. }
+                | ExtraFieldsMsg::ApplyReimport(_)
+                | ExtraFieldsMsg::EditValue { .. }
+                | ExtraFieldsMsg::ResetToDefault(_)
+                | ExtraFieldsMsg::ToggleCheckbox { .. }
+                | ExtraFieldsMsg::SelectUnit { .. }
+                | ExtraFieldsMsg::UpdateMulti { .. }
+                | ExtraFieldsMsg::CommitGroupName
+                | ExtraFieldsMsg::RemoveGroup(_)
+                | ExtraFieldsMsg::AddGroup
+                | ExtraFieldsMsg::MoveGroup { .. }
+                | ExtraFieldsMsg::CommitFieldModal
+                | ExtraFieldsMsg::BulkFill { .. }
+                | ExtraFieldsMsg::ShowMainTextToggled(_)
+        ),
+        _ => false,
+    }
+}
+
+/// Offer the active entry's image attachments in the markdown image picker, whichever
+/// message added, renamed, removed or swapped them.
+fn sync_image_attachments(model: &mut AppModel) {
+    let images: Vec<String> = model
+        .entry
+        .attachments
+        .attachments()
+        .iter()
+        .filter(|item| item.mime.starts_with("image/"))
+        .map(|item| archive_path(&item.folder, &item.sanitized_name))
+        .collect();
+    if model.entry.markdown.image_attachments != images {
+        markdown::update(
+            &mut model.entry.markdown,
+            MarkdownMsg::SetImageAttachments(images),
+        );
+    }
+}
+
+/// Apply `msg` to `model`; see [`update`].
+fn dispatch(model: &mut AppModel, msg: Msg, cmds: &mut Vec<Command>) {
     match msg {
-        Msg::EntryTitleChanged(text) => model.entry_title = text,
-        Msg::SetGenre(genre) => model.archive_genre = genre,
-        Msg::SetBodyFormat(format) => model.body_format = format,
-        Msg::DismissError => model.error = None,
+        Msg::AddEntry => {
+            if let Some(reason) = entry_switch_blocked(model) {
+                surface_event(model, reason.into(), Severity::Warning);
+                return;
+            }
+            push_entry(model, EntryModel::default());
+            model.entry.genre = model.settings.settings().defaults.genre;
+            model.entry.license = model.settings.settings().defaults.license.clone();
+        }
+        Msg::DuplicateEntry => {
+            if let Some(reason) = entry_switch_blocked(model) {
+                surface_event(model, reason.into(), Severity::Warning);
+                return;
+            }
+            let mut copy = entry_draft(&model.entry, model.body_format);
+            copy.title = t!("entries.copy_title", title = copy.title.trim());
+            push_entry(model, EntryModel::default());
+            apply_entry_draft(&mut model.entry, copy);
+        }
+        Msg::RemoveEntry(index) => {
+            if entry_count(model) == 1 || index >= entry_count(model) {
+                return;
+            }
+            if let Some(reason) = entry_switch_blocked(model) {
+                surface_event(model, reason.into(), Severity::Warning);
+                return;
+            }
+            remove_entry(model, index);
+        }
+        Msg::SelectEntry(index) => {
+            if index == model.active_entry {
+                return;
+            }
+            if let Some(reason) = entry_switch_blocked(model) {
+                surface_event(model, reason.into(), Severity::Warning);
+                return;
+            }
+            activate_entry(model, index);
+        }
+        Msg::EntryTitleChanged(text) => model.entry.title = text,
+        Msg::SummaryChanged(text) => model.entry.summary = text,
+        Msg::SetLanguage(tag) => model.entry.language = tag,
+        Msg::SetGenre(genre) => {
+            model.entry.genre = genre;
+            update(
+                model,
+                Msg::Settings(SettingsMsg::SetDefaultGenre(genre)),
+                cmds,
+            );
+        }
+        Msg::SetLicense(license) => {
+            model.entry.license = license.clone();
+            if validate_license(&license).is_ok() {
+                update(
+                    model,
+                    Msg::Settings(SettingsMsg::SetDefaultLicense(license)),
+                    cmds,
+                );
+            }
+        }
+        Msg::SetBodyFormat(format) => {
+            model.body_format = format;
+            update(
+                model,
+                Msg::Settings(SettingsMsg::SetDefaultBodyFormat(format)),
+                cmds,
+            );
+        }
+        Msg::DismissError => {
+            model.error = None;
+            model.error_title = None;
+            model.resumable_save = None;
+        }
+        Msg::DraftRestored(draft) => cmds.push(Command::CheckDraftAttachments(draft)),
+        Msg::DraftChecked { draft, missing } => restore_draft(model, *draft, missing),
+        Msg::DismissDraftNotice => model.draft_restored = false,
+        Msg::DiscardDraft => {
+            reset_entry(model);
+            if let Some(path) = &model.draft_path {
+                cmds.push(Command::DeleteDraft { path: path.clone() });
+            }
+            surface_event(model, t!("draft.discarded").into(), Severity::Success);
+        }
+        Msg::DraftDeleted(result) => {
+            if model.quit == QuitState::AwaitingDraftDelete {
+                model.quit = QuitState::Ready;
+            }
+            if let Err(err) = result {
+                surface_event(
+                    model,
+                    t!("draft.delete_failed", err = err),
+                    Severity::Warning,
+                );
+            }
+        }
+        Msg::CloseRequested => {
+            model.quit = if model.dirty {
+                QuitState::Confirming
+            } else if model.uploading {
+                QuitState::AwaitingUpload
+            } else {
+                QuitState::Ready
+            };
+        }
+        Msg::SaveAndQuit => model.quit = QuitState::AwaitingSave,
+        Msg::DiscardAndQuit => match &model.draft_path {
+            Some(path) => {
+                cmds.push(Command::DeleteDraft { path: path.clone() });
+                model.quit = QuitState::AwaitingDraftDelete;
+            }
+            None => model.quit = QuitState::Ready,
+        },
+        Msg::CancelQuit => model.quit = QuitState::Idle,
+        Msg::VocabularySaved(result) => {
+            if let Err(err) = result {
+                surface_event(
+                    model,
+                    t!("keywords.store_failed", err = err),
+                    Severity::Warning,
+                );
+            }
+        }
         Msg::Markdown(m) => {
-            crate::ui::components::markdown::update(&mut model.markdown, m);
+            markdown::update(&mut model.entry.markdown, m);
         }
-        Msg::Attachments(m) => {
-            let mut att_cmds = Vec::new();
-            if let Some(event) = attachments::update(&mut model.attachments, m, &mut att_cmds) {
-                surface_event(model, event.message, event.is_error);
+        Msg::PasteImage => match &model.paste_dir {
+            Some(dir) => cmds.push(Command::PasteImage { dir: dir.clone() }),
+            None => surface_event(model, t!("paste.unavailable").into(), Severity::Warning),
+        },
+        Msg::ImagePasted(result) => match result {
+            Ok((path, size)) => {
+                update_attachments(model, AttachmentsMsg::FilesPicked(vec![path]), cmds);
+                surface_event(
+                    model,
+                    t!("paste.added", size = format_bytes(size)),
+                    Severity::Success,
+                );
             }
-            for c in att_cmds {
-                match c {
-                    AttachmentsCommand::PickFiles => cmds.push(Command::PickFiles),
-                    AttachmentsCommand::HashFile { path } => cmds.push(Command::HashFile {
-                        path,
-                        _retry: false,
-                    }),
-                    AttachmentsCommand::LoadThumbnail { path } => {
-                        cmds.push(Command::LoadThumbnail {
-                            path,
-                            _retry: false,
-                            request_id: 0,
-                        })
+            Err(err) => surface_event(model, err, Severity::Warning),
+        },
+        Msg::Attachments(m) => {
+            match &m {
+                AttachmentsMsg::FilesPicked(files) => {
+                    if let Some(file) = files.first() {
+                        remember_dir(model, PickerKind::Attachments, file, cmds);
                     }
                 }
+                AttachmentsMsg::FolderScanned(scan) => {
+                    remember_dir(model, PickerKind::Attachments, &scan.root, cmds);
+                }
+                _ => {}
             }
+            update_attachments(model, m, cmds);
         }
         Msg::ThumbnailDecoded {
             path,
@@ -184,447 +996,4886 @@ pub fn update(model: &mut AppModel, msg: Msg, cmds: &mut Vec<Command>) {
             // this message should reach `mvu::update`; keep this arm as a no-op.
             let _ = (path, request_id, image);
         }
-        Msg::ThumbnailFailed { path, request_id } => {
+        Msg::ThumbnailFailed {
+            path,
+            request_id,
+            reason,
+        } => {
             // Invariant: the UI runtime validates thumbnail failure request IDs and
-            // forwards only `AttachmentsMsg::ThumbnailFailed { path }`.
-            let _ = (path, request_id);
+            // forwards only `AttachmentsMsg::ThumbnailFailed { path, reason }`.
+            let _ = (path, request_id, reason);
         }
+        Msg::PreviewImageDecoded { path, image } => {
+            // Invariant: the UI runtime turns preview images into textures first.
+            let _ = (path, image);
+        }
+        Msg::Keywords(KeywordsMsg::SuggestionsLoaded(known)) => set_suggestions(model, known),
         Msg::Keywords(m) => {
-            if let Some(event) = keywords::update(&mut model.keywords, m) {
-                surface_event(model, event.message, event.is_error);
+            if let Some(event) = keywords::update(&mut model.entry.keywords, m) {
+                surface_event(model, event.message, Severity::from_error(event.is_error));
             }
         }
-        Msg::ExtraFields(m) => {
+        Msg::RelatedLinks(m) => related_links::update(&mut model.entry.related_links, m),
+        Msg::ExtraFields(mut m) => {
+            let mut tags = Vec::new();
+            if let ExtraFieldsMsg::ImportLoaded {
+                source,
+                tags: imported,
+                ..
+            } = &mut m
+            {
+                remember_dir(model, PickerKind::Metadata, &source.path, cmds);
+                tags = std::mem::take(imported);
+            }
             let mut extra_cmds = Vec::new();
-            if let Some(event) = extra_fields::update(&mut model.extra_fields, m, &mut extra_cmds) {
-                surface_event(model, event.message, event.is_error);
+            if let Some(event) =
+                extra_fields::update(&mut model.entry.extra_fields, m, &mut extra_cmds)
+            {
+                let mut message = event.message;
+                // Tags of the metadata file are merged into the keywords in the same step.
+                if !tags.is_empty()
+                    && let Some(merged) = keywords::update(
+                        &mut model.entry.keywords,
+                        KeywordsMsg::MergeKeywords(tags),
+                    )
+                {
+                    message = t!(
+                        "fields.import.with_tags",
+                        fields = message.trim_end_matches('.'),
+                        tags = merged.message
+                    );
+                }
+                surface_event(model, message, Severity::from_error(event.is_error));
             }
             for c in extra_cmds {
                 match c {
-                    ExtraFieldsCommand::PickMetadataFile => cmds.push(Command::PickExtraFieldsFile),
+                    ExtraFieldsCommand::PickMetadataFile => {
+                        cmds.push(Command::PickExtraFieldsFile {
+                            start_dir: favorite_dir(model, PickerKind::Metadata),
+                        })
+                    }
+                    ExtraFieldsCommand::PickEntryExport => {
+                        cmds.push(Command::PickElabftwEntryFile {
+                            start_dir: favorite_dir(model, PickerKind::Metadata),
+                        })
+                    }
+                    ExtraFieldsCommand::ReadMetadataFile(path) => {
+                        cmds.push(Command::ReadExtraFieldsFile { path })
+                    }
                 }
             }
         }
-        Msg::DateTime(m) => datetime_picker::update(&mut model.datetime, m),
-        Msg::SaveRequested(output_path) => match validate_for_save(model, output_path) {
-            Ok(payload) => cmds.push(Command::SaveArchive(payload)),
-            Err(err) => surface_event(model, err, true),
-        },
-        Msg::SaveCancelled => surface_event(model, "Save cancelled.".to_string(), false),
-        Msg::SaveCompleted(result) => match result {
-            Ok(path) => surface_event(model, format!("Archive saved: {}", path.display()), false),
-            Err(err) => surface_event(model, format!("Failed to save archive:\n\n{err}"), true),
-        },
-        Msg::OpenHelp => {
-            cmds.push(Command::OpenUrl {
-                url: "https://athemis.github.io/ELNPack/".to_string(),
-            });
-            surface_event(
-                model,
-                "Opening ELNPack user guide in your browser…".into(),
-                false,
-            );
+        Msg::DateTime(m) => {
+            datetime_picker::update(&mut model.entry.datetime, m);
+            model.entry.performed_at_confirmed = false;
         }
-        Msg::HelpOpened(result) => match result {
-            Ok(()) => surface_event(model, "Help opened in browser.".into(), false),
-            Err(err) => surface_event(model, format!("Could not open help page: {err}"), true),
-        },
-    }
-}
-
-/// Execute a `Command` and produce the resulting `Msg`.
-///
-/// This function performs the command's blocking side effects (for example: opening file
-/// dialogs, reading files, hashing, generating thumbnails, or writing an archive) and
-/// returns the message that represents the command's outcome.
-///
-/// # Examples
-///
-/// ```rust,ignore
-/// use std::path::PathBuf;
-/// let cmd = crate::mvu::Command::HashFile { path: PathBuf::from("nonexistent"), _retry: false };
-/// match crate::mvu::run_command(cmd) {
-///     crate::mvu::Msg::Attachments(crate::mvu::AttachmentsMsg::HashComputed { sha256, size, .. }) => {
-///         assert_eq!(sha256, "unavailable");
-///         assert_eq!(size, 0);
-///     }
-///     other => panic!("unexpected result: {:?}", other),
-/// }
-/// ```
-pub fn run_command(cmd: Command) -> Msg {
-    match cmd {
-        Command::PickFiles => {
-            let files = rfd::FileDialog::new()
-                .set_title("Select attachments")
-                .pick_files()
-                .unwrap_or_default();
-            Msg::Attachments(AttachmentsMsg::FilesPicked(files))
+        Msg::Settings(m) => {
+            let mut settings_cmds = Vec::new();
+            if let Some(event) = settings_ui::update(&mut model.settings, m, &mut settings_cmds) {
+                surface_event(model, event.message, Severity::from_error(event.is_error));
+            }
+            let policy = &model.settings.settings().attachments;
+            for entry in std::iter::once(&mut model.entry).chain(model.other_entries.iter_mut()) {
+                entry.attachments.set_policy(policy);
+            }
+            for c in settings_cmds {
+                match c {
+                    SettingsCommand::Persist(settings) => {
+                        if let Some(path) = &model.settings_path {
+                            cmds.push(Command::SaveSettings {
+                                path: path.clone(),
+                                settings: *settings,
+                            });
+                        }
+                    }
+                    SettingsCommand::StoreApiKey(key) => {
+                        if let Some(path) = &model.api_key_path {
+                            cmds.push(Command::SaveApiKey {
+                                path: path.clone(),
+                                key,
+                            });
+                        }
+                    }
+                    SettingsCommand::ExportSettings(export) => {
+                        cmds.push(Command::ExportSettings(export))
+                    }
+                    SettingsCommand::PickSettingsImport => cmds.push(Command::ImportSettings),
+                    SettingsCommand::CheckFavorites(paths) => {
+                        cmds.push(Command::CheckFavorites(paths))
+                    }
+                    SettingsCommand::ClearKeywordVocabulary => {
+                        set_suggestions(model, Vec::new());
+                        if let Some(path) = &model.vocabulary_path {
+                            cmds.push(Command::SaveVocabulary {
+                                path: path.clone(),
+                                keywords: Vec::new(),
+                            });
+                        }
+                    }
+                }
+            }
         }
-        Command::PickExtraFieldsFile => {
-            let file = rfd::FileDialog::new()
-                .set_title("Select eLabFTW metadata JSON")
-                .add_filter("JSON", &["json"])
-                .pick_file();
-
-            match file {
-                Some(path) => match std::fs::read_to_string(&path) {
-                    Ok(content) => {
-                        match crate::models::extra_fields::parse_elabftw_extra_fields(&content) {
-                            Ok(import) => Msg::ExtraFields(ExtraFieldsMsg::ImportLoaded {
-                                fields: import.fields,
-                                groups: import.groups,
-                                source: path,
-                            }),
-                            Err(err) => {
-                                Msg::ExtraFields(ExtraFieldsMsg::ImportFailed(err.to_string()))
-                            }
+        Msg::Authors(m) => {
+            let mut author_cmds = Vec::new();
+            if let Some(event) = authors_ui::update(&mut model.authors, m, &mut author_cmds) {
+                surface_event(model, event.message, Severity::from_error(event.is_error));
+            }
+            for c in author_cmds {
+                match c {
+                    AuthorsCommand::Persist(authors) => {
+                        if let Some(path) = &model.authors_path {
+                            cmds.push(Command::SaveAuthors {
+                                path: path.clone(),
+                                authors,
+                            });
                         }
                     }
-                    Err(err) => Msg::ExtraFields(ExtraFieldsMsg::ImportFailed(format!(
-                        "Failed to read metadata file: {err}"
-                    ))),
-                },
-                None => Msg::ExtraFields(ExtraFieldsMsg::ImportCancelled),
+                }
             }
         }
-        Command::HashFile { path, _retry: _ } => {
-            let sha256 = crate::utils::hash_file(&path).unwrap_or_else(|_| "unavailable".into());
-            let size = path.metadata().map(|m| m.len()).unwrap_or(0);
-            let mime = attachments::guess_mime(&path);
-            Msg::Attachments(AttachmentsMsg::HashComputed {
-                path,
-                sha256,
-                size,
-                mime,
-            })
+        Msg::Templates(TemplatesMsg::SaveArchive) | Msg::TemplateArchivePicked(_)
+            if model.saving =>
+        {
+            surface_event(model, t!("save.running").into(), Severity::Warning)
         }
-        Command::LoadThumbnail {
-            path,
-            _retry: _,
-            request_id,
-        } => match attachments::load_image_thumbnail(&path) {
-            Ok(image) => Msg::ThumbnailDecoded {
-                path,
-                request_id,
-                image,
-            },
-            Err(_) => Msg::ThumbnailFailed { path, request_id },
-        },
-        Command::SaveArchive(payload) => {
-            let res = build_and_write_archive(
-                &payload.output,
-                &payload.title,
-                &payload.body,
-                &payload.attachments,
-                &payload.extra_fields,
-                &payload.extra_groups,
-                payload.performed_at,
-                payload.genre,
-                &payload.keywords,
-                payload.body_format,
-            )
-            .map(|_| payload.output.clone());
-            Msg::SaveCompleted(res.map_err(|e| e.to_string()))
+        Msg::Templates(TemplatesMsg::SaveArchive) => {
+            let title = entries(model)
+                .next()
+                .map_or("", |entry| entry.title.as_str());
+            cmds.push(Command::PickTemplateArchive {
+                start_dir: favorite_dir(model, PickerKind::Archive),
+                default_name: suggested_archive_name(&format!("{} template", title.trim())),
+            });
         }
-        Command::OpenUrl { url } => {
-            let res = open::that(url).map(|_| ());
-            Msg::HelpOpened(res.map_err(|e| e.to_string()))
+        Msg::TemplateArchivePicked(None) => {}
+        Msg::TemplateArchivePicked(Some(path)) => {
+            request_save(model, ensure_extension(path, "eln"), true, cmds)
         }
-    }
-}
-
-/// Update status/error fields consistently for user feedback.
-fn surface_event(model: &mut AppModel, message: String, is_error: bool) {
-    if is_error {
-        model.error = Some(message.clone());
-    }
-    model.status = Some(message);
-}
-
-/// Validate model state and build the payload required to save an archive.
-fn validate_for_save(model: &AppModel, output_path: PathBuf) -> Result<SavePayload, String> {
-    let title = model.entry_title.trim().to_string();
-    if title.is_empty() {
-        return Err("Please enter a title.".into());
+        Msg::Templates(m) => {
+            let mut template_cmds = Vec::new();
+            if let Some(event) = templates_ui::update(&mut model.templates, m, &mut template_cmds) {
+                surface_event(model, event.message, Severity::from_error(event.is_error));
+            }
+            let Some(dir) = model.templates_path.clone() else {
+                if !template_cmds.is_empty() {
+                    surface_event(model, t!("templates.unavailable").into(), Severity::Error);
+                }
+                return;
+            };
+            for c in template_cmds {
+                cmds.push(match c {
+                    TemplatesCommand::SaveCurrent(name) => Command::SaveTemplate {
+                        dir: dir.clone(),
+                        template: Box::new(Template::from_draft(
+                            &name,
+                            &entry_draft(&model.entry, model.body_format),
+                        )),
+                    },
+                    TemplatesCommand::Load(name) => Command::LoadTemplate {
+                        dir: dir.clone(),
+                        name,
+                    },
+                    TemplatesCommand::Rename { from, to } => Command::RenameTemplate {
+                        dir: dir.clone(),
+                        from,
+                        to,
+                    },
+                    TemplatesCommand::Delete(name) => Command::DeleteTemplate {
+                        dir: dir.clone(),
+                        name,
+                    },
+                    TemplatesCommand::LoadForEdit { name, copy } => Command::LoadTemplateForEdit {
+                        dir: dir.clone(),
+                        name,
+                        copy,
+                    },
+                    TemplatesCommand::Write(template) => Command::SaveTemplate {
+                        dir: dir.clone(),
+                        template,
+                    },
+                });
+            }
+        }
+        Msg::TemplateLoaded(result) => match result {
+            Ok(template) => {
+                if snapshot_draft(model).is_empty() && !model.entry.extra_fields.is_editing_field()
+                {
+                    apply_template(model, template, cmds);
+                } else {
+                    model.template_confirmation = Some(template);
+                }
+            }
+            Err(err) => surface_event(model, err, Severity::Error),
+        },
+        Msg::ConfirmTemplate => {
+            if model.entry.extra_fields.is_editing_field() {
+                // Keep the confirmation open instead of discarding the field editor's draft.
+                surface_event(model, t!("templates.editor_open").into(), Severity::Warning);
+            } else if let Some(template) = model.template_confirmation.take() {
+                apply_template(model, template, cmds);
+            }
+        }
+        Msg::CancelTemplate => model.template_confirmation = None,
+        Msg::OpenOnboarding => {
+            let settings = model.settings.settings();
+            let first = model.authors.authors().first().cloned().unwrap_or_default();
+            let answers = OnboardingAnswers {
+                given_name: first.given_name,
+                family_name: first.family_name,
+                orcid: first.orcid,
+                elabftw_url: settings.elabftw.url.clone(),
+                defaults: settings.defaults.clone(),
+            };
+            onboarding::update(&mut model.onboarding, OnboardingMsg::Start(answers));
+        }
+        Msg::Onboarding(m) => match onboarding::update(&mut model.onboarding, m) {
+            Some(OnboardingEvent::Finished(result)) => {
+                if let Some(author) = result.author {
+                    // Keep the contact details the setup does not ask for.
+                    let first = model.authors.authors().first().cloned().unwrap_or_default();
+                    let author = Author {
+                        email: first.email,
+                        affiliation: first.affiliation,
+                        ..author
+                    };
+                    update(model, Msg::Authors(AuthorsMsg::SetFirst(author)), cmds);
+                }
+                model.body_format = result.defaults.body_format;
+                model.entry.genre = result.defaults.genre;
+                model.entry.license = result.defaults.license.clone();
+                update(
+                    model,
+                    Msg::Settings(SettingsMsg::FinishOnboarding {
+                        elabftw_url: Some(result.elabftw_url),
+                        defaults: Some(result.defaults),
+                    }),
+                    cmds,
+                );
+                surface_event(model, t!("onboarding.saved").into(), Severity::Success);
+            }
+            Some(OnboardingEvent::Skipped) => {
+                update(
+                    model,
+                    Msg::Settings(SettingsMsg::FinishOnboarding {
+                        elabftw_url: None,
+                        defaults: None,
+                    }),
+                    cmds,
+                );
+                surface_event(model, t!("onboarding.skipped").into(), Severity::Info);
+            }
+            None => {}
+        },
+        Msg::SaveBlocked => {
+            reveal_required_fields(model);
+            let reason = if model.saving {
+                t!("save.running")
+            } else if model.entry.title.trim().is_empty() {
+                t!("save.blocked.title")
+            } else if model.entry.extra_fields.has_invalid_fields() {
+                t!("save.blocked.fields")
+            } else {
+                t!("save.blocked.attachments")
+            };
+            surface_event(model, reason.into(), Severity::Warning);
+        }
+        Msg::SaveRequested(_) if model.saving => {
+            surface_event(model, t!("save.running").into(), Severity::Warning)
+        }
+        Msg::SaveRequested(output_path) => request_save(model, output_path, false, cmds),
+        Msg::ResaveRequested(_) if model.saving => {
+            surface_event(model, t!("save.running").into(), Severity::Warning)
+        }
+        Msg::ResaveRequested(path) => model.overwrite_confirmation = Some(path),
+        Msg::ConfirmOverwrite => {
+            if let Some(path) = model.overwrite_confirmation.take() {
+                update(model, Msg::SaveRequested(path), cmds);
+            }
+        }
+        Msg::CancelOverwrite => {
+            model.overwrite_confirmation = None;
+            surface_event(model, t!("save.cancelled").into(), Severity::Info);
+        }
+        Msg::ConfirmPerformedAt => {
+            if let Some(pending) = model.date_confirmation.take() {
+                if let Some(entry) = entry_mut(model, pending.entry) {
+                    entry.performed_at_confirmed = true;
+                }
+                update(model, save_request(pending.output, pending.template), cmds);
+            }
+        }
+        Msg::CancelPerformedAtConfirmation => {
+            model.date_confirmation = None;
+            abandon_quit_after_save(model);
+            surface_event(model, t!("save.cancelled").into(), Severity::Info);
+        }
+        Msg::ConfirmAttachmentSizes => {
+            if let Some(pending) = model.size_confirmation.take() {
+                model.sizes_confirmed = true;
+                update(model, save_request(pending.output, pending.template), cmds);
+            }
+        }
+        Msg::CancelAttachmentSizeConfirmation => {
+            model.size_confirmation = None;
+            abandon_quit_after_save(model);
+            surface_event(model, t!("save.cancelled").into(), Severity::Info);
+        }
+        Msg::SaveCancelled => {
+            finish_save(model);
+            model.save_warning = None;
+            abandon_quit_after_save(model);
+            surface_event(model, t!("save.cancelled").into(), Severity::Info)
+        }
+        Msg::SaveProgress {
+            current_file,
+            bytes_done,
+            bytes_total,
+        } => {
+            // Progress queued behind the result of a finished save is stale.
+            if model.saving {
+                model.save_progress = Some(WriteProgress {
+                    current_file,
+                    bytes_done,
+                    bytes_total,
+                });
+            }
+        }
+        Msg::CancelSave => {
+            if model.saving {
+                surface_event(model, t!("save.cancelling").into(), Severity::Info);
+                cmds.push(Command::CancelSave);
+            }
+        }
+        Msg::MissingFilePolicyChanged(policy) => model.missing_files = policy,
+        Msg::SaveMissingFile(path) => {
+            if model.saving {
+                model.missing_file_prompt = Some(path);
+            }
+        }
+        Msg::ResolveMissingFile(action) => {
+            if model.missing_file_prompt.take().is_some() {
+                cmds.push(Command::ResolveMissingFile(action));
+            }
+        }
+        Msg::SaveSkippedFiles(paths) => {
+            let names: Vec<String> = paths
+                .iter()
+                .map(|path| {
+                    path.file_name()
+                        .unwrap_or(path.as_os_str())
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect();
+            let skipped = t!(
+                "save.skipped_files",
+                count = names.len(),
+                names = names.join(", ")
+            );
+            model.save_warning = Some(match model.save_warning.take() {
+                Some(warning) => format!("{warning}. {skipped}"),
+                None => skipped,
+            });
+        }
+        Msg::SaveInterrupted { output, error } => {
+            finish_save(model);
+            model.save_warning = None;
+            abandon_quit_after_save(model);
+            model.resumable_save = Some(output);
+            surface_error(
+                model,
+                t!("save.failed"),
+                t!("save.interrupted", error = error),
+            )
+        }
+        Msg::ResumeSave => {
+            if let Some(output) = model.resumable_save.take() {
+                model.error = None;
+                model.error_title = None;
+                if model.saving {
+                    surface_event(model, t!("save.running").into(), Severity::Warning);
+                } else {
+                    start_save(model, output, true, cmds);
+                }
+            }
+        }
+        Msg::SaveWritten { size, elapsed } => model.save_stats = Some((size, elapsed)),
+        Msg::SaveResumed(outcome) => match outcome {
+            ResumeOutcome::Fresh => {}
+            ResumeOutcome::Resumed { attachments } => surface_event(
+                model,
+                t!("save.resumed", count = attachments),
+                Severity::Info,
+            ),
+            ResumeOutcome::Restarted(reason) => surface_event(
+                model,
+                t!("save.restarted", reason = reason),
+                Severity::Warning,
+            ),
+        },
+        Msg::SaveCompleted(result) => match result {
+            Ok(path) => {
+                finish_save(model);
+                archive_saved(model, path, None, cmds)
+            }
+            Err(err) => {
+                finish_save(model);
+                model.save_warning = None;
+                abandon_quit_after_save(model);
+                surface_error(model, t!("save.failed"), t!("save.error", error = err))
+            }
+        },
+        Msg::ArchiveSigned { archive, signature } => {
+            finish_save(model);
+            archive_saved(model, archive, Some(signature), cmds)
+        }
+        Msg::SetUploadOnSave(enabled) => model.skip_upload = !enabled,
+        Msg::SetSaveCompression(method) => {
+            model.compression =
+                (method != model.settings.settings().archive.compression.method).then_some(method);
+        }
+        Msg::UploadCompleted(result) => {
+            model.uploading = false;
+            model.upload_progress = None;
+            match result {
+                Ok(url) => {
+                    if model.quit == QuitState::AwaitingUpload {
+                        model.quit = QuitState::Ready;
+                    }
+                    surface_event(model, t!("upload.done", url = url), Severity::Success);
+                }
+                Err(err) => {
+                    if model.quit == QuitState::AwaitingUpload {
+                        model.quit = QuitState::Idle;
+                    }
+                    surface_error(model, t!("upload.failed"), t!("upload.error", err = err));
+                }
+            }
+        }
+        Msg::UploadProgress(progress) => {
+            // Progress queued behind the result of a finished upload is stale.
+            if model.uploading {
+                model.upload_progress = Some(progress);
+            }
+        }
+        Msg::CancelUpload => {
+            if model.uploading {
+                surface_event(model, t!("upload.cancelling").into(), Severity::Info);
+                cmds.push(Command::CancelUpload);
+            }
+        }
+        Msg::UploadCancelled => {
+            model.uploading = false;
+            model.upload_progress = None;
+            // The archive is saved, so a close waiting for the upload can go ahead.
+            if model.quit == QuitState::AwaitingUpload {
+                model.quit = QuitState::Ready;
+            }
+            surface_event(model, t!("upload.cancelled").into(), Severity::Info);
+        }
+        Msg::AttachmentVerificationFailed { path, unreadable } => {
+            update_all_attachments(model, || AttachmentsMsg::VerificationFailed {
+                path: path.clone(),
+                unreadable,
+            });
+        }
+        Msg::OpenArchiveRequested(path) => {
+            remember_dir(model, PickerKind::Archive, &path, cmds);
+            surface_event(
+                model,
+                t!("open.opening", path = path.display()),
+                Severity::Info,
+            );
+            cmds.push(Command::OpenArchive(path));
+        }
+        Msg::OpenArchiveCancelled => {
+            surface_event(model, t!("open.cancelled").into(), Severity::Info)
+        }
+        Msg::BatchRequested => {
+            if model.batch.as_ref().is_some_and(|batch| !batch.finished()) {
+                surface_event(model, t!("batch.busy").into(), Severity::Warning);
+                return;
+            }
+            cmds.push(Command::PickBatchFolders {
+                start_dir: favorite_dir(model, PickerKind::Archive),
+            });
+        }
+        Msg::BatchPrepared(Ok(None)) => {}
+        Msg::BatchPrepared(Err(err)) => {
+            surface_error(model, t!("batch.error_title"), err);
+        }
+        Msg::BatchPrepared(Ok(Some(jobs))) => start_batch(model, jobs, cmds),
+        Msg::BatchJobUpdate { index, state } => {
+            let Some(batch) = model.batch.as_mut() else {
+                return;
+            };
+            let Some(slot) = batch.states.get_mut(index) else {
+                return;
+            };
+            *slot = state;
+            if batch.finished() {
+                let written = batch.count(|state| matches!(state, BatchJobState::Done(_)));
+                let failed = batch.count(|state| matches!(state, BatchJobState::Failed(_)));
+                let cancelled = batch.count(|state| *state == BatchJobState::Cancelled);
+                let severity = if failed > 0 {
+                    Severity::Warning
+                } else {
+                    Severity::Success
+                };
+                surface_event(
+                    model,
+                    t!(
+                        "batch.done",
+                        written = written,
+                        failed = failed,
+                        cancelled = cancelled
+                    ),
+                    severity,
+                );
+            }
+        }
+        Msg::CancelBatch => {
+            if let Some(batch) = &model.batch {
+                batch.cancel.store(true, Ordering::Relaxed);
+            }
+        }
+        Msg::CloseBatch => {
+            if model.batch.as_ref().is_some_and(BatchRun::finished) {
+                model.batch = None;
+            }
+        }
+        Msg::ElabftwEntryImported { path, result } => match result {
+            Ok(entry) => {
+                remember_dir(model, PickerKind::Metadata, &path, cmds);
+                let summary = entry.summary();
+                apply_elabftw_entry(&mut model.entry, *entry);
+                surface_event(model, format!("{summary}."), Severity::Success);
+            }
+            Err(err) => surface_event(
+                model,
+                t!("import.failed", path = path.display(), err = err),
+                Severity::Error,
+            ),
+        },
+        Msg::ArchiveOpened {
+            path,
+            extract_dir,
+            result,
+        } => match result {
+            Ok(archive) => {
+                let opened = OpenConfirmation {
+                    path,
+                    extract_dir,
+                    archive,
+                };
+                if !model.dirty {
+                    show_opened_archive(model, opened, cmds);
+                } else if let Some(previous) = model.open_confirmation.replace(opened) {
+                    cmds.push(Command::RemoveExtracted(previous.extract_dir));
+                }
+            }
+            Err(err) => surface_error(
+                model,
+                t!("open.failed"),
+                t!("open.error", path = path.display(), err = err),
+            ),
+        },
+        Msg::ConfirmOpen => {
+            if let Some(opened) = model.open_confirmation.take() {
+                show_opened_archive(model, opened, cmds);
+            }
+        }
+        Msg::CancelOpen => {
+            if let Some(opened) = model.open_confirmation.take() {
+                cmds.push(Command::RemoveExtracted(opened.extract_dir));
+            }
+        }
+        Msg::ExtractedRemoved(result) => {
+            if let Err(err) = result {
+                surface_event(
+                    model,
+                    t!("open.cleanup_failed", err = err),
+                    Severity::Warning,
+                );
+            }
+        }
+        Msg::OpenHelp => {
+            cmds.push(Command::OpenUrl {
+                url: "https://athemis.github.io/ELNPack/".to_string(),
+            });
+            surface_event(model, t!("help.opening").into(), Severity::Info);
+        }
+        Msg::ExportEventTrace(path) => {
+            let json = match &model.event_trace {
+                Some(trace) => trace.to_json().map_err(|err| format!("{err:#}")),
+                None => Err(t!("trace.disabled").into()),
+            };
+            match json {
+                Ok(json) => cmds.push(Command::WriteEventTrace { path, json }),
+                Err(err) => surface_event(model, err, Severity::Error),
+            }
+        }
+        Msg::EventTraceExported(result) => match result {
+            Ok(path) => surface_event(
+                model,
+                t!("trace.exported", path = path.display()),
+                Severity::Success,
+            ),
+            Err(err) => surface_event(model, t!("trace.export_failed", err = err), Severity::Error),
+        },
+        Msg::HelpOpened(result) => match result {
+            Ok(()) => surface_event(model, t!("help.opened").into(), Severity::Info),
+            Err(err) => surface_event(model, t!("help.open_failed", err = err), Severity::Error),
+        },
+        Msg::LoadPersisted(file) => {
+            if let Some(path) = model
+                .safe_mode
+                .as_ref()
+                .and_then(|safe_mode| file.path(&safe_mode.paths))
+            {
+                cmds.push(Command::LoadPersisted {
+                    file,
+                    path: path.to_path_buf(),
+                });
+            }
+        }
+        Msg::PersistedLoaded { file, path, result } => {
+            startup::persisted_loaded(model, file, path, result, cmds);
+        }
+        Msg::OpenConfigFolder => {
+            if let Some(dir) = model
+                .safe_mode
+                .as_ref()
+                .and_then(startup::SafeMode::config_dir)
+            {
+                cmds.push(Command::OpenFolder(dir.to_path_buf()));
+            }
+        }
+        Msg::FolderOpened(result) => {
+            if let Err(err) = result {
+                surface_event(model, t!("folder.open_failed", err = err), Severity::Error);
+            }
+        }
+    }
+}
+
+/// Execute a `Command` without progress reporting (synchronous callers such as tests).
+#[cfg(test)]
+pub fn run_command(cmd: Command) -> Msg {
+    run_command_with_progress(cmd, &dialogs::RfdDialogs, &mut |_| {})
+}
+
+/// Execute a `Command` and produce the resulting `Msg`.
+///
+/// This function performs the command's blocking side effects (for example: opening file
+/// dialogs, reading files, hashing, generating thumbnails, or writing an archive) and
+/// returns the message that represents the command's outcome. File dialogs are shown by
+/// `dialogs`. Long-running commands (currently attachment hashing) additionally send
+/// intermediate progress messages to `report`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use std::path::PathBuf;
+/// let cmd = crate::mvu::Command::HashFile {
+///     path: PathBuf::from("nonexistent"),
+///     algorithms: vec![crate::utils::hash::HashAlgorithm::Sha256],
+///     _retry: false,
+///     cancel: Default::default(),
+/// };
+/// let dialogs = crate::mvu::dialogs::RfdDialogs;
+/// match crate::mvu::run_command_with_progress(cmd, &dialogs, &mut |_| {}) {
+///     crate::mvu::Msg::Attachments(crate::mvu::AttachmentsMsg::HashComputed { digests, size, .. }) => {
+///         assert!(digests.is_empty());
+///         assert_eq!(size, 0);
+///     }
+///     other => panic!("unexpected result: {:?}", other),
+/// }
+/// ```
+pub fn run_command_with_progress(
+    cmd: Command,
+    dialogs: &dyn DialogProvider,
+    report: &mut dyn FnMut(Msg),
+) -> Msg {
+    match cmd {
+        Command::PickFiles { start_dir } => {
+            let files = dialogs.pick_files(t!("dialog.attachments"), start_dir.as_deref());
+            Msg::Attachments(AttachmentsMsg::FilesPicked(files))
+        }
+        Command::PickFolder { start_dir } => {
+            let Some(root) =
+                dialogs.pick_folder(t!("dialog.attachment_folder"), start_dir.as_deref())
+            else {
+                return Msg::Attachments(AttachmentsMsg::FilesPicked(Vec::new()));
+            };
+            match scan_folder(&root, MAX_FOLDER_FILES) {
+                Ok(scan) => Msg::Attachments(AttachmentsMsg::FolderScanned(scan)),
+                Err(err) => Msg::Attachments(AttachmentsMsg::FolderScanFailed(format!("{err:#}"))),
+            }
+        }
+        Command::PickExtraFieldsFile { start_dir } => {
+            let file = dialogs.pick_file_with_filter(
+                t!("dialog.metadata"),
+                start_dir.as_deref(),
+                JSON_FILTER,
+            );
+
+            match file {
+                Some(path) => match read_extra_fields_file(path) {
+                    Ok((import, source)) => Msg::ExtraFields(ExtraFieldsMsg::ImportLoaded {
+                        fields: import.fields,
+                        groups: import.groups,
+                        source,
+                        tags: import.tags,
+                        hide_main_text: import.hide_main_text,
+                    }),
+                    Err(err) => Msg::ExtraFields(ExtraFieldsMsg::ImportFailed(err)),
+                },
+                None => Msg::ExtraFields(ExtraFieldsMsg::ImportCancelled),
+            }
+        }
+        Command::PickElabftwEntryFile { start_dir } => {
+            let file = dialogs.pick_file_with_filter(
+                t!("dialog.entry"),
+                start_dir.as_deref(),
+                JSON_FILTER,
+            );
+            let Some(path) = file else {
+                return Msg::ExtraFields(ExtraFieldsMsg::ImportCancelled);
+            };
+            let result = std::fs::read_to_string(&path)
+                .map_err(|err| t!("import.read_failed", err = err))
+                .and_then(|json| {
+                    parse_elabftw_entry(&json)
+                        .map(Box::new)
+                        .map_err(|err| format!("{err:#}"))
+                });
+            Msg::ElabftwEntryImported { path, result }
+        }
+        Command::ReadExtraFieldsFile { path } => match read_extra_fields_file(path) {
+            Ok((import, source)) => Msg::ExtraFields(ExtraFieldsMsg::ReimportLoaded {
+                fields: import.fields,
+                groups: import.groups,
+                source,
+            }),
+            Err(err) => Msg::ExtraFields(ExtraFieldsMsg::ImportFailed(err)),
+        },
+        Command::HashFile {
+            path,
+            algorithms,
+            _retry: _,
+            cancel,
+        } => {
+            let mut last_reported = 0_u64;
+            let hashed =
+                crate::utils::hash_file_with_progress(&path, &algorithms, |bytes_done, total| {
+                    if cancel.load(Ordering::Relaxed) {
+                        return ControlFlow::Break(());
+                    }
+                    // Throttle to roughly one update per percent (at least 1 MiB apart).
+                    let step = (total / 100).max(1024 * 1024);
+                    if bytes_done - last_reported >= step || bytes_done == total {
+                        last_reported = bytes_done;
+                        report(Msg::Attachments(AttachmentsMsg::HashProgress {
+                            path: path.clone(),
+                            bytes_done,
+                            total,
+                        }));
+                    }
+                    ControlFlow::Continue(())
+                });
+            let digests = match hashed {
+                Ok(Some(digests)) => digests,
+                Ok(None) => return Msg::Attachments(AttachmentsMsg::HashCancelled { path }),
+                Err(_) => Digests::new(),
+            };
+            let size = path.metadata().map(|m| m.len()).unwrap_or(0);
+            let mime = attachments::guess_mime(&path);
+            let modified = attachments::file_modified(&path);
+            Msg::Attachments(AttachmentsMsg::HashComputed {
+                path,
+                digests,
+                size,
+                mime,
+                modified,
+            })
+        }
+        Command::CancelHash { path } => {
+            // The shell flips the cancel flag itself; reaching a worker is a no-op.
+            Msg::Attachments(AttachmentsMsg::HashCancelled { path })
+        }
+        Command::CancelSave => {
+            // Like `CancelHash`, the shell flips the flag of the running save itself.
+            Msg::SaveCancelled
+        }
+        Command::ResolveMissingFile(action) => {
+            // The shell hands the answer to the running save itself.
+            Msg::ResolveMissingFile(action)
+        }
+        Command::LoadThumbnail {
+            path,
+            _retry: _,
+            request_id,
+        } => match attachments::load_image_thumbnail(&path) {
+            Ok(image) => Msg::ThumbnailDecoded {
+                path,
+                request_id,
+                image,
+            },
+            Err(reason) => Msg::ThumbnailFailed {
+                path,
+                request_id,
+                reason,
+            },
+        },
+        Command::LoadPreview { path } => {
+            let content = if attachments::is_image(&path) {
+                match attachments::load_image_preview(&path) {
+                    Ok(image) => return Msg::PreviewImageDecoded { path, image },
+                    Err(ThumbnailError::Unsupported(reason) | ThumbnailError::Decode(reason)) => {
+                        Err(reason)
+                    }
+                }
+            } else {
+                file_preview::read_file_preview(&path, &attachments::guess_mime(&path))
+                    .map(PreviewContent::File)
+                    .map_err(|err| format!("{err:#}"))
+            };
+            Msg::Attachments(AttachmentsMsg::PreviewReady { path, content })
+        }
+        Command::SaveArchive(mut payload) => {
+            // Load the key first so its fingerprint is recorded inside the archive.
+            let signing_key = payload
+                .signing_key
+                .as_deref()
+                .map(signing::load_signing_key);
+            if let Some(Ok(key)) = &signing_key {
+                payload.export_options.signing_fingerprint =
+                    Some(signing::fingerprint(key.public_key()));
+            }
+            let started = Instant::now();
+            let mut last_reported = (String::new(), 0_u64);
+            let resumable = Resumable {
+                checkpoint_bytes: CHECKPOINT_BYTES,
+                resume: payload.resume,
+            };
+            let mut skipped = Vec::new();
+            // Progress and missing-file prompts are both reported while writing.
+            let reporter = RefCell::new(&mut *report);
+            let res = build_and_write_archive_resumable(
+                &payload.output,
+                &payload.entries,
+                payload.body_format,
+                &payload.export_options,
+                resumable,
+                |progress| {
+                    if payload.cancel.load(Ordering::Relaxed) {
+                        return ControlFlow::Break(());
+                    }
+                    // Throttle like hashing, but always announce the next attachment.
+                    let step = (progress.bytes_total / 100).max(1024 * 1024);
+                    if progress.current_file != last_reported.0
+                        || progress.bytes_done - last_reported.1 >= step
+                        || progress.bytes_done == progress.bytes_total
+                    {
+                        last_reported = (progress.current_file.clone(), progress.bytes_done);
+                        (reporter.borrow_mut())(Msg::SaveProgress {
+                            current_file: progress.current_file.clone(),
+                            bytes_done: progress.bytes_done,
+                            bytes_total: progress.bytes_total,
+                        });
+                    }
+                    ControlFlow::Continue(())
+                },
+                |path| {
+                    let action = match payload.missing_files {
+                        MissingFilePolicy::Abort => MissingFileAction::Abort,
+                        MissingFilePolicy::Skip => MissingFileAction::Skip,
+                        MissingFilePolicy::Ask => {
+                            (reporter.borrow_mut())(Msg::SaveMissingFile(path.to_path_buf()));
+                            await_missing_file_answer(&payload)
+                        }
+                    };
+                    if action == MissingFileAction::Skip {
+                        skipped.push(path.to_path_buf());
+                    }
+                    action
+                },
+            );
+            let res = res.map(|outcome| {
+                if payload.resume {
+                    report(Msg::SaveResumed(outcome));
+                }
+                report(Msg::SaveWritten {
+                    size: std::fs::metadata(&payload.output).map_or(0, |meta| meta.len()),
+                    elapsed: started.elapsed(),
+                });
+                if !skipped.is_empty() {
+                    report(Msg::SaveSkippedFiles(std::mem::take(&mut skipped)));
+                }
+                payload.output.clone()
+            });
+            // Cancelling while a missing attachment waits for an answer aborts the save.
+            let cancelled = payload.cancel.load(Ordering::Relaxed);
+            if res.is_err() && cancelled && has_resume_state(&payload.output) {
+                let _ = std::fs::remove_file(partial_path(&payload.output));
+                let _ = remove_resume_state(&payload.output);
+            }
+            if res
+                .as_ref()
+                .is_err_and(|err| err.is::<WriteCancelled>() || cancelled)
+            {
+                return Msg::SaveCancelled;
+            }
+            if let Some(failed) = res
+                .as_ref()
+                .err()
+                .and_then(|err| err.downcast_ref::<AttachmentIntegrityError>())
+            {
+                report(Msg::AttachmentVerificationFailed {
+                    path: failed.path.clone(),
+                    unreadable: failed.unreadable,
+                });
+            }
+            if let Err(err) = &res
+                && has_resume_state(&payload.output)
+            {
+                return Msg::SaveInterrupted {
+                    output: payload.output.clone(),
+                    error: err.to_string(),
+                };
+            }
+            match (res, signing_key) {
+                (Ok(archive), Some(key)) => {
+                    let signature = match key {
+                        Ok(key) => signing::sign_archive(&archive, &key),
+                        Err(err) => signing::remove_signature(&archive).and(Err(err)),
+                    }
+                    .map_err(|e| format!("{e:#}"));
+                    Msg::ArchiveSigned { archive, signature }
+                }
+                (res, _) => Msg::SaveCompleted(res.map_err(|e| e.to_string())),
+            }
+        }
+        Command::OpenArchive(path) => {
+            let extract_dir = std::env::temp_dir()
+                .join("elnpack")
+                .join(format!("open-{}", uuid::Uuid::new_v4()));
+            let result = open_archive(&path, &extract_dir)
+                .map(Box::new)
+                .map_err(|e| format!("{e:#}"));
+            if result.is_err() {
+                let _ = std::fs::remove_dir_all(&extract_dir);
+            }
+            Msg::ArchiveOpened {
+                path,
+                extract_dir,
+                result,
+            }
+        }
+        Command::RemoveExtracted(dir) => {
+            Msg::ExtractedRemoved(std::fs::remove_dir_all(&dir).map_err(|e| e.to_string()))
+        }
+        Command::PickBatchFolders { start_dir } => {
+            let Some(parent) = dialogs.pick_folder(t!("dialog.batch_source"), start_dir.as_deref())
+            else {
+                return Msg::BatchPrepared(Ok(None));
+            };
+            let Some(output_dir) = dialogs.pick_folder(t!("dialog.batch_output"), parent.parent())
+            else {
+                return Msg::BatchPrepared(Ok(None));
+            };
+            Msg::BatchPrepared(
+                find_batch_jobs(&parent, &output_dir)
+                    .map(Some)
+                    .map_err(|err| format!("{err:#}")),
+            )
+        }
+        Command::RunBatchJob {
+            index,
+            job,
+            options,
+            cancel,
+        } => {
+            if !cancel.load(Ordering::Relaxed) {
+                report(Msg::BatchJobUpdate {
+                    index,
+                    state: BatchJobState::Running,
+                });
+            }
+            let state = match run_batch_job(&job, &options, &cancel) {
+                Ok(path) => BatchJobState::Done(path),
+                Err(err) if err.is::<WriteCancelled>() => BatchJobState::Cancelled,
+                Err(err) => BatchJobState::Failed(format!("{err:#}")),
+            };
+            Msg::BatchJobUpdate { index, state }
+        }
+        Command::OpenUrl { url } => {
+            let res = open::that(url).map(|_| ());
+            Msg::HelpOpened(res.map_err(|e| e.to_string()))
+        }
+        Command::OpenFolder(dir) => {
+            let res = std::fs::create_dir_all(&dir).and_then(|()| open::that(&dir));
+            Msg::FolderOpened(res.map_err(|e| e.to_string()))
+        }
+        Command::LoadPersisted { file, path } => {
+            let result = startup::load_persisted(file, &path)
+                .map(|msg| msg.map(Box::new))
+                .map_err(|err| format!("{err:#}"));
+            Msg::PersistedLoaded { file, path, result }
+        }
+        Command::CheckDraftAttachments(mut draft) => {
+            let missing = draft::drop_unrestorable_attachments(&mut draft);
+            Msg::DraftChecked { draft, missing }
+        }
+        Command::DeleteDraft { path } => {
+            Msg::DraftDeleted(draft::delete_draft(&path).map_err(|e| e.to_string()))
+        }
+        Command::SaveSettings { path, settings } => Msg::Settings(SettingsMsg::Persisted(
+            settings::save_settings(&path, &settings).map_err(|e| format!("{e:#}")),
+        )),
+        Command::SaveVocabulary { path, keywords } => Msg::VocabularySaved(
+            vocabulary::save_vocabulary(&path, &keywords).map_err(|e| format!("{e:#}")),
+        ),
+        Command::SaveAuthors { path, authors } => Msg::Authors(AuthorsMsg::Persisted(
+            authors::save_authors(&path, &authors).map_err(|e| format!("{e:#}")),
+        )),
+        Command::CheckFavorites(paths) => {
+            let status = paths
+                .into_iter()
+                .map(|path| {
+                    let exists = path.is_dir();
+                    (path, exists)
+                })
+                .collect();
+            Msg::Settings(SettingsMsg::FavoritesChecked(status))
+        }
+        Command::WriteEventTrace { path, json } => Msg::EventTraceExported(
+            crate::utils::storage::write_atomic(&path, json.as_bytes())
+                .map(|()| path)
+                .map_err(|err| format!("{err:#}")),
+        ),
+        Command::PasteImage { dir } => Msg::ImagePasted(
+            crate::utils::pasted_image::paste_clipboard_image(&dir)
+                .map_err(|err| format!("{err:#}")),
+        ),
+        Command::SaveTemplate { dir, template } => {
+            let done = t!("templates.saved", name = template.name);
+            templates_changed(&dir, templates::save_template(&dir, &template), done)
+        }
+        Command::LoadTemplate { dir, name } => {
+            Msg::TemplateLoaded(templates::load_template(&dir, &name).map(Box::new).map_err(
+                |err| {
+                    t!(
+                        "templates.load_failed",
+                        name = name,
+                        err = format!("{err:#}")
+                    )
+                },
+            ))
+        }
+        Command::LoadTemplateForEdit { dir, name, copy } => {
+            Msg::Templates(TemplatesMsg::EditorLoaded {
+                result: templates::load_template(&dir, &name)
+                    .map(Box::new)
+                    .map_err(|err| {
+                        t!(
+                            "templates.load_failed",
+                            name = name,
+                            err = format!("{err:#}")
+                        )
+                    }),
+                copy,
+            })
+        }
+        Command::RenameTemplate { dir, from, to } => {
+            let done = t!("templates.renamed", from = from, to = to);
+            templates_changed(&dir, templates::rename_template(&dir, &from, &to), done)
+        }
+        Command::DeleteTemplate { dir, name } => {
+            let done = t!("templates.deleted", name = name);
+            templates_changed(&dir, templates::delete_template(&dir, &name), done)
+        }
+        Command::UploadArchive {
+            path,
+            target,
+            cancel,
+        } => {
+            let mut last_reported = 0;
+            let result = elabftw::upload_archive(
+                &elabftw::UreqClient::default(),
+                &target,
+                &path,
+                |progress| {
+                    if cancel.load(Ordering::Relaxed) {
+                        return ControlFlow::Break(());
+                    }
+                    // Throttle like saving, but always report the end of the body.
+                    let step = (progress.bytes_total / 100).max(1024 * 1024);
+                    if progress.bytes_sent != last_reported
+                        && (progress.bytes_sent - last_reported >= step
+                            || progress.bytes_sent == progress.bytes_total)
+                    {
+                        last_reported = progress.bytes_sent;
+                        report(Msg::UploadProgress(*progress));
+                    }
+                    ControlFlow::Continue(())
+                },
+            );
+            if result.is_err() && cancel.load(Ordering::Relaxed) {
+                return Msg::UploadCancelled;
+            }
+            Msg::UploadCompleted(result)
+        }
+        Command::CancelUpload => {
+            // Like `CancelSave`, the shell flips the flag of the running upload itself.
+            Msg::UploadCancelled
+        }
+        Command::SaveApiKey { path, key } => Msg::Settings(SettingsMsg::ApiKeyStored(
+            elabftw::save_api_key(&path, &key).map_err(|e| format!("{e:#}")),
+        )),
+        Command::ExportSettings(export) => {
+            let result = match dialogs.save_file(
+                t!("dialog.export_settings"),
+                None,
+                JSON_FILTER,
+                "elnpack-settings.json",
+            ) {
+                Some(path) => settings::write_settings_export(&path, &export)
+                    .map(|()| Some(path))
+                    .map_err(|e| format!("{e:#}")),
+                None => Ok(None),
+            };
+            Msg::Settings(SettingsMsg::Exported(result))
+        }
+        Command::PickTemplateArchive {
+            start_dir,
+            default_name,
+        } => Msg::TemplateArchivePicked(dialogs.save_file(
+            t!("dialog.template_archive"),
+            start_dir.as_deref(),
+            ELN_FILTER,
+            &default_name,
+        )),
+        Command::ImportSettings => {
+            let result =
+                match dialogs.pick_file_with_filter(t!("settings.import.title"), None, JSON_FILTER)
+                {
+                    Some(path) => settings::read_settings_export(&path)
+                        .map(|export| Some(Box::new(export)))
+                        .map_err(|e| format!("{e:#}")),
+                    None => Ok(None),
+                };
+            Msg::Settings(SettingsMsg::ImportLoaded(result))
+        }
+    }
+}
+
+/// Report a finished template change together with the refreshed template list.
+fn templates_changed(dir: &Path, result: anyhow::Result<()>, done: String) -> Msg {
+    let result = result
+        .and_then(|()| templates::list_templates(dir))
+        .map_err(|err| format!("{err:#}"));
+    Msg::Templates(TemplatesMsg::Changed { result, done })
+}
+
+/// Read and parse the eLabFTW metadata file at `path`, recording it as the import source.
+fn read_extra_fields_file(path: PathBuf) -> Result<(ExtraFieldsImport, ExtraFieldsSource), String> {
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(t!("fields.import.source_gone", path = path.display()));
+        }
+        Err(err) => return Err(t!("fields.import.read_failed", err = err)),
+    };
+    let import = parse_elabftw_extra_fields(&content).map_err(|err| err.to_string())?;
+    let source = ExtraFieldsSource::new(path, &content, time::OffsetDateTime::now_utc());
+    Ok((import, source))
+}
+
+/// Route a message to the active entry's attachments and translate their commands.
+fn update_attachments(model: &mut AppModel, msg: AttachmentsMsg, cmds: &mut Vec<Command>) {
+    let mut att_cmds = Vec::new();
+    // Files are checked against the policy in effect when their hash arrives.
+    model
+        .entry
+        .attachments
+        .set_policy(&model.settings.settings().attachments);
+    if let Some(event) = attachments::update(&mut model.entry.attachments, msg, &mut att_cmds) {
+        surface_event(model, event.message, Severity::from_error(event.is_error));
+    }
+    for c in att_cmds {
+        match c {
+            AttachmentsCommand::PickFiles => cmds.push(Command::PickFiles {
+                start_dir: favorite_dir(model, PickerKind::Attachments),
+            }),
+            AttachmentsCommand::PickFolder => cmds.push(Command::PickFolder {
+                start_dir: favorite_dir(model, PickerKind::Attachments),
+            }),
+            AttachmentsCommand::HashFile { path } => cmds.push(Command::HashFile {
+                path,
+                algorithms: model.settings.settings().hashing.algorithms(),
+                _retry: false,
+                cancel: Arc::default(),
+            }),
+            AttachmentsCommand::CancelHash { path } => cmds.push(Command::CancelHash { path }),
+            AttachmentsCommand::LoadThumbnail { path } => cmds.push(Command::LoadThumbnail {
+                path,
+                _retry: false,
+                request_id: 0,
+            }),
+            AttachmentsCommand::LoadPreview { path } => {
+                cmds.push(Command::LoadPreview { path });
+            }
+        }
+    }
+}
+
+/// Folder dialogs of `kind` start in: the selected favorite, else the last folder used.
+pub fn favorite_dir(model: &AppModel, kind: PickerKind) -> Option<PathBuf> {
+    model
+        .settings
+        .settings()
+        .favorites
+        .start_dir(kind)
+        .map(Path::to_path_buf)
+}
+
+/// Remember the folder containing `path` as the start of the next dialog of `kind`.
+fn remember_dir(model: &mut AppModel, kind: PickerKind, path: &Path, cmds: &mut Vec<Command>) {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        let dir = dir.to_path_buf();
+        update(
+            model,
+            Msg::Settings(SettingsMsg::RememberDir { kind, dir }),
+            cmds,
+        );
+    }
+}
+
+/// Start the active entry with the stored default body format, genre, and license.
+fn apply_entry_defaults(model: &mut AppModel) {
+    let defaults = &model.settings.settings().defaults;
+    model.body_format = defaults.body_format;
+    model.entry.genre = defaults.genre;
+    model.entry.license = defaults.license.clone();
+}
+
+/// Number of entries in the archive, including the active one.
+pub fn entry_count(model: &AppModel) -> usize {
+    model.other_entries.len() + 1
+}
+
+/// All entries in list order, with the active one at `active_entry`.
+pub fn entries(model: &AppModel) -> impl Iterator<Item = &EntryModel> {
+    let (before, after) = model.other_entries.split_at(model.active_entry);
+    before
+        .iter()
+        .chain(std::iter::once(&model.entry))
+        .chain(after)
+}
+
+/// Entry at list position `index`.
+fn entry_mut(model: &mut AppModel, index: usize) -> Option<&mut EntryModel> {
+    match index.cmp(&model.active_entry) {
+        std::cmp::Ordering::Equal => Some(&mut model.entry),
+        std::cmp::Ordering::Less => model.other_entries.get_mut(index),
+        std::cmp::Ordering::Greater => model.other_entries.get_mut(index - 1),
+    }
+}
+
+/// Reason the active entry cannot be switched, added, or removed right now.
+///
+/// Results of open dialogs and running commands (picked files, hashes, thumbnails, folder
+/// scans) are routed to the active entry, so it must stay in place until they have arrived.
+fn entry_switch_blocked(model: &AppModel) -> Option<&'static str> {
+    if model.open_dialogs > 0 {
+        return Some(t!("entries.blocked.dialog"));
+    }
+    (model.pending_commands > 0 || model.entry.attachments.has_pending_hashes())
+        .then_some(t!("entries.blocked.pending"))
+}
+
+/// Show the entry at list position `index`, keeping the current one with all its edits.
+fn activate_entry(model: &mut AppModel, index: usize) {
+    if index == model.active_entry || index >= entry_count(model) {
+        return;
+    }
+    let current = std::mem::take(&mut model.entry);
+    model.other_entries.insert(model.active_entry, current);
+    model.entry = model.other_entries.remove(index);
+    model.active_entry = index;
+    model.date_confirmation = None;
+}
+
+/// Append `entry` to the list and show it; it inherits the keyword suggestions.
+fn push_entry(model: &mut AppModel, mut entry: EntryModel) {
+    keywords::update(
+        &mut entry.keywords,
+        KeywordsMsg::SuggestionsLoaded(model.entry.keywords.suggestions().to_vec()),
+    );
+    let index = entry_count(model);
+    model.other_entries.push(entry);
+    activate_entry(model, index);
+}
+
+/// Delete the entry at list position `index`; removing the active one shows its successor,
+/// or its predecessor when it was last.
+fn remove_entry(model: &mut AppModel, index: usize) {
+    let active = model.active_entry;
+    if index == active {
+        let next = active.min(model.other_entries.len() - 1);
+        model.entry = model.other_entries.remove(next);
+        model.active_entry = next;
+    } else if index < active {
+        model.other_entries.remove(index);
+        model.active_entry -= 1;
+    } else {
+        model.other_entries.remove(index - 1);
+    }
+    model.date_confirmation = None;
+}
+
+/// Replace the keyword suggestions of every entry.
+/// Keep the window open when the save started from the close prompt did not happen.
+fn abandon_quit_after_save(model: &mut AppModel) {
+    if model.quit == QuitState::AwaitingSave {
+        model.quit = QuitState::Idle;
+    }
+}
+
+/// Send `msg` to the attachments of every entry, not only the one shown.
+fn update_all_attachments(model: &mut AppModel, msg: impl Fn() -> AttachmentsMsg) {
+    for entry in std::iter::once(&mut model.entry).chain(model.other_entries.iter_mut()) {
+        attachments::update(&mut entry.attachments, msg(), &mut Vec::new());
+    }
+}
+
+/// Report missing required values in every entry, also in fields the user has not edited.
+fn reveal_required_fields(model: &mut AppModel) {
+    for entry in std::iter::once(&mut model.entry).chain(model.other_entries.iter_mut()) {
+        extra_fields::update(
+            &mut entry.extra_fields,
+            ExtraFieldsMsg::SaveAttempted,
+            &mut Vec::new(),
+        );
+    }
+}
+
+fn set_suggestions(model: &mut AppModel, known: Vec<String>) {
+    for entry in std::iter::once(&mut model.entry).chain(model.other_entries.iter_mut()) {
+        keywords::update(
+            &mut entry.keywords,
+            KeywordsMsg::SuggestionsLoaded(known.clone()),
+        );
+    }
+}
+
+/// Capture the persistable parts of all entries as a draft snapshot.
+///
+/// The first entry fills the draft itself; later entries go to [`Draft::more_entries`].
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let model = AppModel::default();
+/// assert!(snapshot_draft(&model).is_empty());
+/// ```
+pub fn snapshot_draft(model: &AppModel) -> Draft {
+    let mut drafts = entries(model).map(|entry| entry_draft(entry, model.body_format));
+    let mut draft = drafts.next().expect("there is always an active entry");
+    draft.more_entries = drafts.collect();
+    draft
+}
+
+/// Capture one entry as a draft without further entries.
+fn entry_draft(entry: &EntryModel, body_format: crate::logic::eln::BodyFormat) -> Draft {
+    Draft {
+        version: DRAFT_SCHEMA_VERSION,
+        title: entry.title.clone(),
+        genre: entry.genre,
+        body_format,
+        body: entry.markdown.text.clone(),
+        keywords: entry.keywords.keywords().to_vec(),
+        extra_fields: entry.extra_fields.fields().to_vec(),
+        extra_groups: entry.extra_fields.groups().to_vec(),
+        extra_fields_source: entry.extra_fields.source().cloned(),
+        performed_date: entry.datetime.date.to_string(),
+        performed_hour: entry.datetime.hour,
+        performed_minute: entry.datetime.minute,
+        attachments: entry
+            .attachments
+            .attachments()
+            .iter()
+            .map(|a| a.to_domain())
+            .collect(),
+        language: entry.language.clone(),
+        related_links: entry.related_links.links().to_vec(),
+        license: entry.license.clone(),
+        summary: entry.summary.clone(),
+        hide_main_text: entry.extra_fields.hide_main_text(),
+        more_entries: Vec::new(),
+    }
+}
+
+/// Apply a restored draft to the model and report the `missing` attachments dropped from it.
+fn restore_draft(model: &mut AppModel, draft: Draft, missing: Vec<Attachment>) {
+    apply_draft(model, draft);
+    model.draft_restored = true;
+
+    let (message, severity) = if missing.is_empty() {
+        (t!("draft.restored.status").to_string(), Severity::Info)
+    } else {
+        let names: Vec<String> = missing
+            .iter()
+            .map(|a| a.path.display().to_string())
+            .collect();
+        let message = t!(
+            "draft.restored.missing",
+            count = missing.len(),
+            names = names.join(", ")
+        );
+        (message, Severity::Warning)
+    };
+    surface_event(model, message, severity);
+}
+
+/// Fill the sections an eLabFTW export provides; the others keep their content.
+fn apply_elabftw_entry(entry: &mut EntryModel, import: ElabftwEntry) {
+    if let Some(title) = import.title {
+        entry.title = title;
+    }
+    if let Some(body) = import.body {
+        markdown::update(&mut entry.markdown, MarkdownMsg::SetText(body));
+    }
+    if !import.keywords.is_empty() {
+        keywords::update(&mut entry.keywords, KeywordsMsg::Restore(import.keywords));
+    }
+    if let Some(date) = import.date
+        && let Ok(civil) = jiff::civil::Date::new(
+            date.year() as i16,
+            u8::from(date.month()) as i8,
+            date.day() as i8,
+        )
+    {
+        entry.datetime.date = civil;
+        if let Some(time) = import.time {
+            entry.datetime.hour = i32::from(time.hour());
+            entry.datetime.minute = i32::from(time.minute());
+        }
+        entry.performed_at_confirmed = false;
+    }
+    if !import.extra_fields.is_empty() {
+        extra_fields::update(
+            &mut entry.extra_fields,
+            ExtraFieldsMsg::Restore {
+                fields: import.extra_fields,
+                groups: import.extra_groups,
+                source: None,
+                hide_main_text: import.hide_main_text,
+            },
+            &mut Vec::new(),
+        );
+    }
+}
+
+/// Replace the entries with an opened archive and delete the files of the one shown before.
+fn show_opened_archive(model: &mut AppModel, opened: OpenConfirmation, cmds: &mut Vec<Command>) {
+    reset_entry(model);
+    if let Some(previous) = model.open_dir.replace(opened.extract_dir) {
+        cmds.push(Command::RemoveExtracted(previous));
+    }
+    apply_opened_archive(model, *opened.archive);
+    surface_event(
+        model,
+        t!("open.done", path = opened.path.display()),
+        Severity::Success,
+    );
+}
+
+/// Load an opened archive into the form; the date/time is kept when the archive has none.
+fn apply_opened_archive(model: &mut AppModel, opened: OpenedArchive) {
+    let draft = opened_draft(opened, &model.entry.datetime);
+    apply_draft(model, draft);
+}
+
+/// Convert an opened archive (and its further entries) into a draft.
+fn opened_draft(opened: OpenedArchive, fallback: &DateTimeModel) -> Draft {
+    let datetime = opened
+        .performed_at
+        .and_then(|at| datetime_picker::from_offset_datetime(at).ok())
+        .unwrap_or_else(|| fallback.clone());
+    Draft {
+        version: DRAFT_SCHEMA_VERSION,
+        title: opened.title,
+        genre: opened.genre,
+        body_format: opened.body_format,
+        body: opened.body,
+        keywords: opened.keywords,
+        extra_fields: opened.extra_fields,
+        extra_groups: opened.extra_groups,
+        extra_fields_source: None,
+        performed_date: datetime.date.to_string(),
+        performed_hour: datetime.hour,
+        performed_minute: datetime.minute,
+        attachments: opened.attachments,
+        language: String::new(),
+        related_links: opened.related_links,
+        license: opened.license,
+        summary: opened.summary,
+        hide_main_text: opened.hide_main_text,
+        more_entries: opened
+            .more_entries
+            .into_iter()
+            .map(|entry| opened_draft(entry, fallback))
+            .collect(),
+    }
+}
+
+/// Replace all entries with the draft's.
+fn apply_draft(model: &mut AppModel, mut draft: Draft) {
+    model.body_format = draft.body_format;
+    model.other_entries.clear();
+    model.active_entry = 0;
+    model.date_confirmation = None;
+
+    let more_entries = std::mem::take(&mut draft.more_entries);
+    apply_entry_draft(&mut model.entry, draft);
+    for draft in more_entries {
+        let mut entry = EntryModel::default();
+        keywords::update(
+            &mut entry.keywords,
+            KeywordsMsg::SuggestionsLoaded(model.entry.keywords.suggestions().to_vec()),
+        );
+        apply_entry_draft(&mut entry, draft);
+        model.other_entries.push(entry);
+    }
+}
+
+/// Copy draft content into one entry's component models.
+fn apply_entry_draft(entry: &mut EntryModel, draft: Draft) {
+    entry.title = draft.title;
+    entry.genre = draft.genre;
+    entry.language = draft.language;
+    entry.license = draft.license;
+    entry.summary = draft.summary;
+    related_links::update(
+        &mut entry.related_links,
+        RelatedLinksMsg::Restore(draft.related_links),
+    );
+    crate::ui::components::markdown::update(&mut entry.markdown, MarkdownMsg::SetText(draft.body));
+    keywords::update(&mut entry.keywords, KeywordsMsg::Restore(draft.keywords));
+    extra_fields::update(
+        &mut entry.extra_fields,
+        ExtraFieldsMsg::Restore {
+            fields: draft.extra_fields,
+            groups: draft.extra_groups,
+            source: draft.extra_fields_source,
+            hide_main_text: draft.hide_main_text,
+        },
+        &mut Vec::new(),
+    );
+    if let Ok(date) = draft.performed_date.parse() {
+        datetime_picker::update(&mut entry.datetime, DateTimeMsg::SetDate(date));
+    }
+    datetime_picker::update(
+        &mut entry.datetime,
+        DateTimeMsg::SetHour(draft.performed_hour),
+    );
+    datetime_picker::update(
+        &mut entry.datetime,
+        DateTimeMsg::SetMinute(draft.performed_minute),
+    );
+
+    attachments::update(
+        &mut entry.attachments,
+        AttachmentsMsg::Restore(draft.attachments),
+        &mut Vec::new(),
+    );
+}
+
+/// Replace all entries with a fresh one filled from `template` and remember it as recent.
+fn apply_template(model: &mut AppModel, template: Box<Template>, cmds: &mut Vec<Command>) {
+    reset_entry(model);
+    let mut draft = entry_draft(&model.entry, model.body_format);
+    template.apply_to(&mut draft);
+    apply_entry_draft(&mut model.entry, draft);
+    let name = template.name.clone();
+    model.entry.template = Some(template);
+    update(
+        model,
+        Msg::Settings(SettingsMsg::TemplateUsed(name.clone())),
+        cmds,
+    );
+    surface_event(
+        model,
+        t!("templates.applied", name = name),
+        Severity::Success,
+    );
+}
+
+/// Name of the template the active entry started from, and whether its field definitions
+/// were changed since.
+pub fn applied_template(model: &AppModel) -> Option<(&str, bool)> {
+    let template = model.entry.template.as_deref()?;
+    let fields = &model.entry.extra_fields;
+    let modified = !template.defines_same_fields(fields.fields(), fields.groups());
+    Some((template.name.as_str(), modified))
+}
+
+/// Clear all entry content while keeping persistence paths and settings; the entry
+/// starts from the stored default body format and genre.
+fn reset_entry(model: &mut AppModel) {
+    let draft_path = model.draft_path.take();
+    let settings_path = model.settings_path.take();
+    let vocabulary_path = model.vocabulary_path.take();
+    let authors_path = model.authors_path.take();
+    let templates_path = model.templates_path.take();
+    let api_key_path = model.api_key_path.take();
+    let uploading = model.uploading;
+    let saving = model.saving;
+    let save_progress = model.save_progress.take();
+    let templates = std::mem::take(&mut model.templates);
+    let paste_dir = model.paste_dir.take();
+    let open_dir = model.open_dir.take();
+    let settings = std::mem::take(&mut model.settings);
+    let authors = std::mem::take(&mut model.authors);
+    let status_history = std::mem::take(&mut model.status_history);
+    let suggestions = model.entry.keywords.suggestions().to_vec();
+    *model = AppModel {
+        draft_path,
+        settings_path,
+        vocabulary_path,
+        authors_path,
+        templates_path,
+        api_key_path,
+        uploading,
+        saving,
+        save_progress,
+        templates,
+        paste_dir,
+        open_dir,
+        settings,
+        authors,
+        status_history,
+        ..Default::default()
+    };
+    keywords::update(
+        &mut model.entry.keywords,
+        KeywordsMsg::SuggestionsLoaded(suggestions),
+    );
+    apply_entry_defaults(model);
+}
+
+/// Add every entry's keywords to the autocomplete vocabulary and persist it when it grew.
+fn remember_keywords(model: &mut AppModel, cmds: &mut Vec<Command>) {
+    let mut known = model.entry.keywords.suggestions().to_vec();
+    let mut grew = false;
+    for entry in entries(model) {
+        grew |= vocabulary::merge_keywords(&mut known, entry.keywords.keywords());
+    }
+    if !grew {
+        return;
+    }
+    set_suggestions(model, known.clone());
+    if let Some(path) = &model.vocabulary_path {
+        cmds.push(Command::SaveVocabulary {
+            path: path.clone(),
+            keywords: known,
+        });
+    }
+}
+
+/// Wait until the UI shell answers `payload` about a missing attachment; a cancelled save
+/// aborts.
+fn await_missing_file_answer(payload: &SavePayload) -> MissingFileAction {
+    loop {
+        if payload.cancel.load(Ordering::Relaxed) {
+            return MissingFileAction::Abort;
+        }
+        if let Some(action) = payload
+            .missing_file_answer
+            .lock()
+            .ok()
+            .and_then(|mut answer| answer.take())
+        {
+            return action;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+}
+
+/// Message requesting the save to `output` again after a confirmation.
+fn save_request(output: PathBuf, template: bool) -> Msg {
+    if template {
+        Msg::TemplateArchivePicked(Some(output))
+    } else {
+        Msg::SaveRequested(output)
+    }
+}
+
+/// Start a save to the archive path the user chose, as a template archive when `template` is
+/// set.
+fn request_save(model: &mut AppModel, output: PathBuf, template: bool, cmds: &mut Vec<Command>) {
+    remember_dir(model, PickerKind::Archive, &output, cmds);
+    model.saving_template = template;
+    start_save(model, output, false, cmds);
+}
+
+/// Validate the entries and start saving them to `output`, continuing the checkpoint of an
+/// interrupted save there when `resume` is set.
+fn start_save(model: &mut AppModel, output: PathBuf, resume: bool, cmds: &mut Vec<Command>) {
+    model.resumable_save = None;
+    let validated = validate_for_save(model, output.clone());
+    // A confirmation covers only the save it was given for.
+    model.sizes_confirmed = false;
+    match validated {
+        Ok(payload) => {
+            let warnings: Vec<String> = [
+                keyword_warning(model),
+                missing_references_warning(model, &payload.entries),
+                hidden_main_text_warning(model, &payload.entries),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            model.save_warning = (!warnings.is_empty()).then(|| warnings.join(". "));
+            model.saving = true;
+            model.save_progress = None;
+            let payload = if model.saving_template {
+                template_archive(payload, &model.settings.settings().keywords)
+            } else {
+                payload
+            };
+            cmds.push(Command::SaveArchive(SavePayload { resume, ..payload }));
+        }
+        Err(SaveRejection::Invalid(err)) => {
+            abandon_quit_after_save(model);
+            reveal_required_fields(model);
+            surface_error(model, t!("save.validation_error"), err)
+        }
+        Err(SaveRejection::ConfirmDate { warning, entry }) => {
+            model.date_confirmation = Some(DateConfirmation {
+                warning,
+                entry,
+                output,
+                template: model.saving_template,
+            });
+        }
+        Err(SaveRejection::ConfirmSizes(message)) => {
+            model.size_confirmation = Some(SizeConfirmation {
+                message,
+                output,
+                template: model.saving_template,
+            });
+        }
+    }
+}
+
+/// Forget the running save once its worker reported back.
+fn finish_save(model: &mut AppModel) {
+    model.saving = false;
+    model.save_progress = None;
+    model.missing_file_prompt = None;
+}
+
+/// Finish a successful save: clear the dirty state, remove the draft, start the upload, and
+/// report the outcome, including the signature when the archive was signed.
+fn archive_saved(
+    model: &mut AppModel,
+    path: PathBuf,
+    signature: Option<Result<PathBuf, String>>,
+    cmds: &mut Vec<Command>,
+) {
+    model.compression = None;
+    let version = model.settings.settings().archive.crate_version.label();
+    let (mut message, upload) = if model.saving_template {
+        // A template archive is an export; the entries are not saved by it.
+        let message = t!(
+            "save.done.template",
+            path = path.display(),
+            version = version
+        );
+        (message, None)
+    } else {
+        let message = t!(
+            "save.done.archive",
+            path = path.display(),
+            version = version
+        );
+        (message, entries_saved(model, &path, cmds))
+    };
+    if let Some((size, elapsed)) = model.save_stats.take() {
+        message.push_str(&t!(
+            "save.done.stats",
+            size = format_bytes(size),
+            seconds = format!("{:.1}", elapsed.as_secs_f64())
+        ));
+    }
+    let mut licenses: Vec<String> = entries(model)
+        .filter_map(|entry| entry.license.summary())
+        .collect();
+    licenses.sort();
+    licenses.dedup();
+    if !licenses.is_empty() {
+        message.push_str(&t!("save.done.license", license = licenses.join(", ")));
+    }
+    let mut severity = Severity::Success;
+    if let Some(warning) = model.save_warning.take() {
+        message.push_str(&t!("save.done.warning", warning = warning));
+        severity = Severity::Warning;
+    }
+    let mut signing_error = None;
+    match signature {
+        Some(Ok(signature)) => {
+            message.push_str(&t!("save.done.signed", path = signature.display()));
+        }
+        Some(Err(err)) => signing_error = Some(err),
+        None => {}
+    }
+    if let Some(target) = upload {
+        message.push_str(t!("save.done.uploading"));
+        model.uploading = true;
+        cmds.push(Command::UploadArchive {
+            path,
+            target,
+            cancel: Arc::new(AtomicBool::new(false)),
+        });
+    }
+    match signing_error {
+        Some(err) => surface_error(
+            model,
+            t!("save.signing_failed"),
+            t!("save.signing_error", message = message, error = err),
+        ),
+        None => surface_event(model, message, severity),
+    }
+}
+
+/// Mark the entries saved to `path`: clear the dirty state, remember the archive, and remove
+/// the draft. Returns where to upload the archive, if anywhere.
+fn entries_saved(
+    model: &mut AppModel,
+    path: &Path,
+    cmds: &mut Vec<Command>,
+) -> Option<UploadTarget> {
+    model.draft_restored = false;
+    model.dirty = false;
+    model.last_saved = Some(path.to_path_buf());
+    update(
+        model,
+        Msg::Settings(SettingsMsg::ArchiveSaved(path.to_path_buf())),
+        cmds,
+    );
+    let upload = model
+        .settings
+        .upload_target()
+        .filter(|_| !std::mem::take(&mut model.skip_upload));
+    if model.quit == QuitState::AwaitingSave {
+        model.quit = if upload.is_some() {
+            QuitState::AwaitingUpload
+        } else {
+            QuitState::Ready
+        };
+    }
+    if let Some(draft_path) = &model.draft_path {
+        cmds.push(Command::DeleteDraft {
+            path: draft_path.clone(),
+        });
+    }
+    remember_keywords(model, cmds);
+    // Every attachment was read and checked against its hash while writing.
+    let now = time::OffsetDateTime::now_utc();
+    update_all_attachments(model, || AttachmentsMsg::Reverified(now));
+    upload
+}
+
+/// Show `message` in the status bar and its history; errors also open the error modal.
+pub fn surface_event(model: &mut AppModel, message: String, severity: Severity) {
+    if severity == Severity::Error {
+        model.error = Some(message.clone());
+        model.error_title = None;
+    }
+    let status = StatusMessage::new(message, severity);
+    model.status_history.push(status.clone());
+    model.status = Some(status);
+}
+
+/// [`surface_event`] for an error whose modal gets `title` instead of the generic one.
+fn surface_error(model: &mut AppModel, title: &'static str, message: String) {
+    surface_event(model, message, Severity::Error);
+    model.error_title = Some(title);
+}
+
+/// Plausibility warning for the active entry's performed-at date, unless the user already
+/// confirmed it.
+pub fn performed_at_warning(model: &AppModel) -> Option<DateWarning> {
+    entry_date_warning(model, &model.entry)
+}
+
+/// Plausibility warning for `entry`'s performed-at date, unless the user already confirmed it.
+fn entry_date_warning(model: &AppModel, entry: &EntryModel) -> Option<DateWarning> {
+    if entry.performed_at_confirmed {
+        return None;
+    }
+    let performed_at = datetime_picker::to_offset_datetime(&entry.datetime).ok()?;
+    check_performed_at(
+        performed_at,
+        time::OffsetDateTime::now_utc(),
+        model.settings.settings().validation.date_horizon_years,
+    )
+}
+
+/// Name of the entry at list position `index` as used in messages, e.g. `Entry 2 (Gel run)`.
+pub fn entry_label(index: usize, entry: &EntryModel) -> String {
+    let title = entry.title.trim();
+    if title.is_empty() {
+        t!("entry.label", number = index + 1)
+    } else {
+        t!("entry.label_titled", number = index + 1, title = title)
+    }
+}
+
+/// Warning listing the keywords that are archived in another form or left out; see
+/// [`Keywords::normalize`].
+fn keyword_warning(model: &AppModel) -> Option<String> {
+    let several = entry_count(model) > 1;
+    let problems: Vec<String> = entries(model)
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            let (_, report) =
+                Keywords::normalize(entry.keywords.keywords().to_vec(), &KeywordRules::default());
+            if report.is_empty() {
+                return None;
+            }
+            Some(if several {
+                format!("{}: {}", entry_label(index, entry), report.summary())
+            } else {
+                report.summary()
+            })
+        })
+        .collect();
+    (!problems.is_empty()).then(|| t!("save.keywords_adjusted", problems = problems.join("; ")))
+}
+
+/// Warning naming attachments the bodies link to as `./<path>` that are not in the archive,
+/// e.g. because they were renamed or removed after being embedded.
+fn missing_references_warning(
+    model: &AppModel,
+    archive_entries: &[ArchiveEntry],
+) -> Option<String> {
+    let several = archive_entries.len() > 1;
+    let problems: Vec<String> = entries(model)
+        .zip(archive_entries)
+        .enumerate()
+        .filter_map(|(index, (entry, archived))| {
+            let missing = missing_attachment_references(&archived.body, &archived.attachments);
+            if missing.is_empty() {
+                return None;
+            }
+            let list = missing.join(", ");
+            Some(if several {
+                format!("{}: {list}", entry_label(index, entry))
+            } else {
+                list
+            })
+        })
+        .collect();
+    (!problems.is_empty()).then(|| t!("save.missing_references", problems = problems.join("; ")))
+}
+
+/// Warning naming entries with a main text that eLabFTW is told not to show.
+fn hidden_main_text_warning(model: &AppModel, archive_entries: &[ArchiveEntry]) -> Option<String> {
+    let hidden: Vec<String> = entries(model)
+        .zip(archive_entries)
+        .enumerate()
+        .filter(|(_, (_, archived))| archived.hide_main_text && !archived.body.trim().is_empty())
+        .map(|(index, (entry, _))| entry_label(index, entry))
+        .collect();
+    match hidden.as_slice() {
+        [] => None,
+        [_] if archive_entries.len() == 1 => Some(t!("save.main_text_hidden").into()),
+        _ => Some(t!("save.main_text_hidden.of", entries = hidden.join("; "))),
+    }
+}
+
+/// Why a save request did not produce a payload.
+enum SaveRejection {
+    /// Hard validation failure shown in the error modal.
+    Invalid(String),
+    /// Plausibility warning the user has to confirm before saving the entry at `entry`.
+    ConfirmDate { warning: DateWarning, entry: usize },
+    /// Attachments above the size warning the user has to accept before saving.
+    ConfirmSizes(String),
+}
+
+impl From<String> for SaveRejection {
+    fn from(message: String) -> Self {
+        SaveRejection::Invalid(message)
+    }
+}
+
+/// Validate every entry and build the payload required to save an archive.
+///
+/// With several entries, error messages start with the offending entry's label.
+fn validate_for_save(model: &AppModel, output_path: PathBuf) -> Result<SavePayload, SaveRejection> {
+    let several = entry_count(model) > 1;
+    let mut archive_entries = Vec::with_capacity(entry_count(model));
+    for (index, entry) in entries(model).enumerate() {
+        let validated =
+            validate_entry(entry, &model.settings.settings().keywords).map_err(|err| {
+                if several {
+                    format!("{}: {err}", entry_label(index, entry))
+                } else {
+                    err
+                }
+            })?;
+        archive_entries.push(validated);
+    }
+
+    let export_options = export_options(model)?;
+
+    for (index, entry) in entries(model).enumerate() {
+        if let Some(warning) = entry_date_warning(model, entry) {
+            return Err(SaveRejection::ConfirmDate {
+                warning,
+                entry: index,
+            });
+        }
+    }
+    // Template archives leave the attachments out.
+    if !model.sizes_confirmed
+        && !model.saving_template
+        && let Some(message) = oversized_attachments_warning(model)
+    {
+        return Err(SaveRejection::ConfirmSizes(message));
+    }
+
+    Ok(SavePayload {
+        output: output_path,
+        entries: archive_entries,
+        body_format: model.body_format,
+        export_options,
+        signing_key: model.settings.settings().archive.signing_key.clone(),
+        cancel: Arc::default(),
+        resume: false,
+        missing_files: model.missing_files,
+        missing_file_answer: Arc::default(),
+    })
+}
+
+/// Export options from the settings and the author list, shared by saves and batches.
+fn export_options(model: &AppModel) -> Result<ExportOptions, String> {
+    let settings = model.settings.settings();
+    let html_policy = HtmlPolicy::from_settings(&settings.html_export)
+        .map_err(|errors| t!("save.invalid_html", errors = errors.join("\n")))?;
+    Ok(ExportOptions {
+        html_policy,
+        include_preview: settings.archive.include_preview,
+        write_zip_comment: settings.archive.write_zip_comment,
+        authors: validate_authors(model.authors.authors())?,
+        crate_version: settings.archive.crate_version,
+        checksums_manifest: settings.archive.checksums_manifest,
+        include_readme: settings.archive.include_readme,
+        license: settings.archive.license.clone(),
+        signing_fingerprint: None,
+        publisher: settings.publisher.clone(),
+        units: settings.units.clone(),
+        verify_written: settings.archive.verify_after_save,
+        include_imported_metadata: settings.archive.include_imported_metadata,
+        compression: CompressionOptions {
+            method: model
+                .compression
+                .unwrap_or(settings.archive.compression.method),
+            ..settings.archive.compression
+        },
+    })
+}
+
+/// Queue one archive per batch job on the worker pool, with the export settings, the
+/// default genre and license, and the body format of the form.
+fn start_batch(model: &mut AppModel, jobs: Vec<BatchJob>, cmds: &mut Vec<Command>) {
+    let export = match export_options(model) {
+        Ok(export) => export,
+        Err(err) => {
+            surface_error(model, t!("batch.error_title"), err);
+            return;
+        }
+    };
+    let defaults = &model.settings.settings().defaults;
+    let options = Arc::new(BatchOptions {
+        genre: defaults.genre,
+        license: defaults.license.clone(),
+        body_format: model.body_format,
+        export,
+    });
+    let cancel = Arc::new(AtomicBool::new(false));
+    for (index, job) in jobs.iter().enumerate() {
+        cmds.push(Command::RunBatchJob {
+            index,
+            job: job.clone(),
+            options: Arc::clone(&options),
+            cancel: Arc::clone(&cancel),
+        });
+    }
+    let count = jobs.len();
+    model.batch = Some(BatchRun {
+        states: vec![BatchJobState::Pending; count],
+        jobs,
+        cancel,
+    });
+    surface_event(model, t!("batch.started", count = count), Severity::Info);
+}
+
+/// `payload` as an eLabFTW template: values of fields flagged to be blanked on duplication
+/// are cleared together with the keywords derived from them, attachments are left out, and
+/// every entry gets the template genre.
+fn template_archive(mut payload: SavePayload, keywords: &KeywordSettings) -> SavePayload {
+    for entry in &mut payload.entries {
+        let blanked: Vec<ExtraField> = entry
+            .extra_fields
+            .iter()
+            .filter(|field| field.blank_value_on_duplicate)
+            .cloned()
+            .collect();
+        let derived = derived_keywords(&blanked, keywords);
+        entry.keywords.retain(|keyword| !derived.contains(keyword));
+        for field in &mut entry.extra_fields {
+            if field.blank_value_on_duplicate {
+                field.value.clear();
+                field.value_multi.clear();
+            }
+        }
+        entry.attachments.clear();
+        entry.genre = ArchiveGenre::Template;
+    }
+    payload
+}
+
+/// Summary of the attachments of all entries above the size warning, e.g.
+/// "2 attachments exceed 1.0 GB (run1.mp4, run2.mp4)"; `None` when there are none.
+fn oversized_attachments_warning(model: &AppModel) -> Option<String> {
+    let policy = &model.settings.settings().attachments;
+    let limit = policy.warn_above_bytes()?;
+    let names: Vec<&str> = entries(model)
+        .flat_map(|entry| entry.attachments.attachments())
+        .filter(|item| policy.is_oversized(item.size))
+        .map(|item| item.sanitized_name.as_str())
+        .collect();
+    match names.as_slice() {
+        [] => None,
+        [name] => Some(t!(
+            "save.oversized.one",
+            name = name,
+            limit = format_bytes(limit)
+        )),
+        _ => Some(t!(
+            "save.oversized.many",
+            count = names.len(),
+            limit = format_bytes(limit),
+            names = names.join(", ")
+        )),
+    }
+}
+
+/// Validate the non-blank authors, keeping their order; ORCID iDs must be unique.
+fn validate_authors(authors: &[Author]) -> Result<Vec<Author>, String> {
+    let mut validated: Vec<Author> = Vec::new();
+    for (index, author) in authors.iter().enumerate() {
+        if author.is_blank() {
+            continue;
+        }
+        let author = validate_author(author)
+            .map_err(|err| t!("save.author_error", number = index + 1, error = err))?;
+        if !author.orcid.is_empty() && validated.iter().any(|a| a.orcid == author.orcid) {
+            return Err(t!(
+                "save.author_orcid_twice",
+                number = index + 1,
+                orcid = author.orcid
+            ));
+        }
+        validated.push(author);
+    }
+    Ok(validated)
+}
+
+/// Validate the non-blank related links, keeping their order.
+fn validate_related_links(links: &[RelatedLink]) -> Result<Vec<RelatedLink>, String> {
+    links
+        .iter()
+        .enumerate()
+        .filter(|(_, link)| !link.is_blank())
+        .map(|(index, link)| {
+            validate_related_link(link)
+                .map_err(|err| t!("save.related_link_invalid", index = index + 1, err = err))
+        })
+        .collect()
+}
+
+/// Validate one entry and capture it for the archive, with the keywords `keyword_settings`
+/// derives from its extra fields appended.
+fn validate_entry(
+    entry: &EntryModel,
+    keyword_settings: &KeywordSettings,
+) -> Result<ArchiveEntry, String> {
+    let title = entry.title.trim().to_string();
+    if title.is_empty() {
+        return Err(t!("save.no_title").into());
+    }
+    if entry.attachments.has_pending_hashes() {
+        return Err(t!("save.pending_hashes").into());
+    }
+
+    let body = entry.markdown.text.trim().to_string();
+
+    let mut keywords = entry.keywords.keywords().to_vec();
+    keywords.extend(derived_keywords(
+        entry.extra_fields.fields(),
+        keyword_settings,
+    ));
+    let keywords = Keywords::new(keywords);
+    let language = normalize_language_tag(&entry.language)
+        .map_err(|err| t!("save.invalid_language", error = err))?;
+    let related_links = validate_related_links(entry.related_links.links())?;
+    let performed_at = datetime_picker::to_offset_datetime(&entry.datetime)
+        .map_err(|err| t!("save.invalid_datetime", error = err))?;
+
+    let attachment_meta: Vec<Attachment> = entry
+        .attachments
+        .attachments()
+        .iter()
+        .map(|a| a.to_domain())
+        .collect();
+
+    let archive_entry = ArchiveEntry {
+        title,
+        body,
+        attachments: attachment_meta,
+        extra_fields: entry.extra_fields.fields().to_vec(),
+        extra_groups: entry.extra_fields.groups().to_vec(),
+        performed_at,
+        genre: entry.genre,
+        keywords: keywords.into_vec(),
+        language,
+        related_links,
+        imported_metadata: entry.extra_fields.source().cloned(),
+        license: validate_license(&entry.license).map_err(|err| err.to_string())?,
+        summary: entry.summary.trim().to_string(),
+        hide_main_text: entry.extra_fields.hide_main_text(),
+    };
+    archive_entry
+        .validate(time::OffsetDateTime::now_utc())
+        .map_err(|err| err.to_string())?;
+    Ok(archive_entry)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::field_reassign_with_default)]
+
+    use super::*;
+    use crate::models::extra_fields::{ExtraField, ExtraFieldKind};
+    use crate::models::related_link::Relation;
+    use crate::mvu::dialogs::{RfdDialogs, ScriptedDialogs};
+    use crate::ui::components::extra_fields::ExtraFieldsMsg;
+    use crate::ui::components::related_links::LinkField;
+    use crate::ui::components::templates::TemplateEditorMsg;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn status_text(model: &AppModel) -> Option<&str> {
+        model.status.as_ref().map(|status| status.text.as_str())
+    }
+
+    #[test]
+    fn status_messages_carry_a_severity_and_only_errors_open_the_modal() {
+        let mut model = AppModel::default();
+        update(&mut model, Msg::AddEntry, &mut Vec::new());
+        update(&mut model, Msg::OpenArchiveCancelled, &mut Vec::new());
+        assert_eq!(model.status.as_ref().unwrap().severity, Severity::Info);
+        assert!(model.error.is_none());
+
+        update(
+            &mut model,
+            Msg::HelpOpened(Err("no browser".into())),
+            &mut Vec::new(),
+        );
+        assert_eq!(model.status.as_ref().unwrap().severity, Severity::Error);
+        assert!(model.error.is_some());
+
+        update(&mut model, Msg::DiscardDraft, &mut Vec::new());
+        assert_eq!(model.status.as_ref().unwrap().severity, Severity::Success);
+        let history: Vec<Severity> = model
+            .status_history
+            .newest_first()
+            .map(|status| status.severity)
+            .collect();
+        assert_eq!(
+            history,
+            [Severity::Success, Severity::Error, Severity::Info]
+        );
+    }
+
+    #[test]
+    fn save_request_enqueues_and_completes() {
+        let tmp = TempDir::new().unwrap();
+        let output = tmp.path().join("test.eln");
+
+        let mut model = AppModel::default();
+        model.entry.title = "Title".into();
+        model.entry.markdown.text = "Body".into();
+
+        let mut cmds = Vec::new();
+        update(&mut model, Msg::SaveRequested(output.clone()), &mut cmds);
+
+        assert_eq!(cmds.len(), 1, "save should enqueue command");
+
+        let msg = run_command(cmds.pop().unwrap());
+        let mut cmds2 = Vec::new();
+        update(&mut model, msg, &mut cmds2);
+
+        assert!(model.error.is_none());
+        assert!(
+            status_text(&model)
+                .map(|s| s.contains("Archive saved"))
+                .unwrap_or(false)
+        );
+        assert!(output.exists());
+    }
+
+    #[test]
+    fn saving_normalizes_keywords_and_warns_about_the_changes() {
+        let tmp = TempDir::new().unwrap();
+        let output = tmp.path().join("test.eln");
+        let mut model = AppModel::default();
+        model.entry.title = "Title".into();
+        keywords::update(
+            &mut model.entry.keywords,
+            KeywordsMsg::Restore(vec!["PCR|gel".into(), "pcr gel".into(), "DNA".into()]),
+        );
+
+        let mut cmds = Vec::new();
+        update(&mut model, Msg::SaveRequested(output.clone()), &mut cmds);
+        let Some(Command::SaveArchive(payload)) = cmds.last() else {
+            panic!("the save goes ahead");
+        };
+        assert_eq!(payload.entries[0].keywords, ["PCR gel", "DNA"]);
+        let msg = run_command(cmds.pop().unwrap());
+        update(&mut model, msg, &mut Vec::new());
+
+        assert!(model.error.is_none(), "{:?}", model.error);
+        let status = model.status.as_ref().unwrap();
+        assert_eq!(status.severity, Severity::Warning);
+        assert!(
+            status.text.contains(
+                "keywords adjusted: stored \"PCR|gel\" as \"PCR gel\"; dropped \"pcr gel\""
+            ),
+            "{}",
+            status.text
+        );
+    }
+
+    #[test]
+    fn resaving_reuses_the_last_path_after_confirmation() {
+        let tmp = TempDir::new().unwrap();
+        let output = tmp.path().join("run.eln");
+        let mut model = AppModel {
+            settings_path: Some(tmp.path().join("settings.json")),
+            ..Default::default()
+        };
+        model.entry.title = "Title".into();
+
+        let mut cmds = Vec::new();
+        update(&mut model, Msg::SaveRequested(output.clone()), &mut cmds);
+        let msg = run_command(cmds.pop().unwrap());
+        update(&mut model, msg, &mut cmds);
+        assert_eq!(model.last_saved.as_deref(), Some(output.as_path()));
+        assert_eq!(
+            model.settings.settings().recent_archives.paths,
+            std::slice::from_ref(&output)
+        );
+        assert!(
+            cmds.iter()
+                .any(|cmd| matches!(cmd, Command::SaveSettings { .. }))
+        );
+
+        cmds.clear();
+        model.entry.title = "Corrected title".into();
+        update(&mut model, Msg::ResaveRequested(output.clone()), &mut cmds);
+        update(&mut model, Msg::CancelOverwrite, &mut cmds);
+        assert!(cmds.is_empty(), "nothing is written without confirmation");
+
+        update(&mut model, Msg::ResaveRequested(output.clone()), &mut cmds);
+        assert_eq!(
+            model.overwrite_confirmation.as_deref(),
+            Some(output.as_path())
+        );
+        update(&mut model, Msg::ConfirmOverwrite, &mut cmds);
+        assert!(model.overwrite_confirmation.is_none());
+        assert!(
+            matches!(cmds.as_slice(), [Command::SaveArchive(payload)] if payload.output == output)
+        );
+
+        // Validation still applies to saves without the dialog.
+        cmds.clear();
+        model.saving = false;
+        model.entry.title.clear();
+        update(&mut model, Msg::ResaveRequested(output), &mut cmds);
+        update(&mut model, Msg::ConfirmOverwrite, &mut cmds);
+        assert!(cmds.is_empty());
+        assert_eq!(model.error_title, Some("Validation error"));
+    }
+
+    #[test]
+    fn running_saves_block_new_ones_and_can_be_cancelled() {
+        let tmp = TempDir::new().unwrap();
+        let output = tmp.path().join("big.eln");
+        let data = tmp.path().join("stack.bin");
+        std::fs::write(&data, vec![0_u8; 2 * 1024 * 1024]).unwrap();
+        let (sha256, size) = crate::utils::copy_and_hash(
+            &mut std::fs::File::open(&data).unwrap(),
+            &mut std::io::sink(),
+        )
+        .unwrap();
+
+        let mut model = AppModel::default();
+        model.entry.title = "Title".into();
+        let mut cmds = Vec::new();
+        update(&mut model, Msg::SaveRequested(output.clone()), &mut cmds);
+        assert!(model.saving);
+        update(&mut model, Msg::SaveRequested(output.clone()), &mut cmds);
+        assert_eq!(cmds.len(), 1, "a second save is refused");
+        assert_eq!(status_text(&model), Some("A save is already running."));
+
+        update(
+            &mut model,
+            Msg::SaveProgress {
+                current_file: "stack.bin".into(),
+                bytes_done: 1024,
+                bytes_total: size,
+            },
+            &mut cmds,
+        );
+        assert_eq!(model.save_progress.as_ref().unwrap().bytes_done, 1024);
+        update(&mut model, Msg::CancelSave, &mut cmds);
+        assert!(matches!(cmds.last(), Some(Command::CancelSave)));
+
+        // The shell flips the payload's flag; the worker then stops at the next chunk.
+        let Some(Command::SaveArchive(mut payload)) = cmds.drain(..).next() else {
+            panic!("save should enqueue the archive");
+        };
+        payload.entries[0].attachments.push(Attachment::new(
+            data,
+            "stack.bin".into(),
+            "application/octet-stream".into(),
+            sha256,
+            size,
+        ));
+        payload.cancel.store(true, Ordering::Relaxed);
+        let msg = run_command(Command::SaveArchive(payload));
+        assert!(matches!(msg, Msg::SaveCancelled));
+        assert!(!output.exists(), "partial archive removed");
+
+        update(&mut model, msg, &mut cmds);
+        assert!(!model.saving);
+        assert!(model.save_progress.is_none());
+        assert_eq!(status_text(&model), Some("Save cancelled."));
+        update(
+            &mut model,
+            Msg::SaveProgress {
+                current_file: "stack.bin".into(),
+                bytes_done: size,
+                bytes_total: size,
+            },
+            &mut cmds,
+        );
+        assert!(model.save_progress.is_none(), "late progress is ignored");
+    }
+
+    #[test]
+    fn saves_ask_about_missing_attachments_and_list_the_skipped_ones() {
+        let tmp = TempDir::new().unwrap();
+        let output = tmp.path().join("share.eln");
+        let data = tmp.path().join("gone.bin");
+        std::fs::write(&data, b"on the network share").unwrap();
+        let (sha256, size) = crate::utils::copy_and_hash(
+            &mut std::fs::File::open(&data).unwrap(),
+            &mut std::io::sink(),
+        )
+        .unwrap();
+
+        let mut model = AppModel::default();
+        model.entry.title = "Title".into();
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            Msg::MissingFilePolicyChanged(MissingFilePolicy::Ask),
+            &mut cmds,
+        );
+        update(&mut model, Msg::SaveRequested(output.clone()), &mut cmds);
+        let Some(Command::SaveArchive(mut payload)) = cmds.drain(..).next() else {
+            panic!("save should enqueue the archive");
+        };
+        assert_eq!(payload.missing_files, MissingFilePolicy::Ask);
+        payload.entries[0].attachments.push(Attachment::new(
+            data.clone(),
+            "gone.bin".into(),
+            "application/octet-stream".into(),
+            sha256,
+            size,
+        ));
+        // The file disappears after validation, before the worker reaches it.
+        std::fs::remove_file(&data).unwrap();
+
+        // The shell writes the user's answer into the payload's slot.
+        let answer = Arc::clone(&payload.missing_file_answer);
+        let mut reported = Vec::new();
+        let msg = run_command_with_progress(
+            Command::SaveArchive(payload),
+            &dialogs::RfdDialogs,
+            &mut |msg| {
+                if matches!(msg, Msg::SaveMissingFile(_)) {
+                    *answer.lock().unwrap() = Some(MissingFileAction::Skip);
+                }
+                reported.push(msg);
+            },
+        );
+        assert!(matches!(&msg, Msg::SaveCompleted(Ok(path)) if *path == output));
+        assert!(crate::logic::eln::verify_archive(&output).unwrap().is_ok());
+
+        for msg in reported {
+            match msg {
+                Msg::SaveMissingFile(path) => {
+                    update(&mut model, Msg::SaveMissingFile(path.clone()), &mut cmds);
+                    assert_eq!(model.missing_file_prompt, Some(path));
+                    update(
+                        &mut model,
+                        Msg::ResolveMissingFile(MissingFileAction::Skip),
+                        &mut cmds,
+                    );
+                    assert!(model.missing_file_prompt.is_none());
+                    assert!(matches!(
+                        cmds.as_slice(),
+                        [Command::ResolveMissingFile(MissingFileAction::Skip)]
+                    ));
+                }
+                msg => update(&mut model, msg, &mut cmds),
+            }
+        }
+        update(&mut model, msg, &mut cmds);
+        assert!(!model.saving);
+        let status = model.status.as_ref().unwrap();
+        assert_eq!(status.severity, Severity::Warning);
+        assert!(
+            status_text(&model)
+                .unwrap()
+                .contains("1 missing attachment(s) were left out (gone.bin)"),
+            "{:?}",
+            status_text(&model)
+        );
+    }
+
+    #[test]
+    fn interrupted_saves_can_be_resumed_from_the_error_modal() {
+        let output = PathBuf::from("/tmp/large.eln");
+        let mut model = AppModel::default();
+        model.entry.title = "Title".into();
+        let mut cmds = Vec::new();
+        update(&mut model, Msg::SaveRequested(output.clone()), &mut cmds);
+        assert!(matches!(cmds.as_slice(), [Command::SaveArchive(payload)] if !payload.resume));
+
+        cmds.clear();
+        update(
+            &mut model,
+            Msg::SaveInterrupted {
+                output: output.clone(),
+                error: "No space left on device".into(),
+            },
+            &mut cmds,
+        );
+        assert!(!model.saving);
+        assert_eq!(model.error_title, Some("Save failed"));
+        assert!(model.error.as_ref().unwrap().contains("Resume the save"));
+        assert_eq!(model.resumable_save.as_deref(), Some(output.as_path()));
+
+        update(&mut model, Msg::ResumeSave, &mut cmds);
+        assert!(model.error.is_none());
+        assert!(model.resumable_save.is_none());
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::SaveArchive(payload)] if payload.resume && payload.output == output
+        ));
+        update(
+            &mut model,
+            Msg::SaveResumed(ResumeOutcome::Restarted("the attachments changed".into())),
+            &mut cmds,
+        );
+        assert_eq!(model.status.as_ref().unwrap().severity, Severity::Warning);
+
+        // Dismissing the error gives up on resuming.
+        model.saving = false;
+        update(
+            &mut model,
+            Msg::SaveInterrupted {
+                output,
+                error: "No space left on device".into(),
+            },
+            &mut cmds,
+        );
+        update(&mut model, Msg::DismissError, &mut cmds);
+        assert!(model.resumable_save.is_none());
+    }
+
+    #[test]
+    fn save_request_with_empty_title_sets_error() {
+        let mut model = AppModel::default();
+        model.entry.title = "   ".into();
+
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            Msg::SaveRequested(PathBuf::from("/tmp/ignored.eln")),
+            &mut cmds,
+        );
+
+        assert!(cmds.is_empty());
+        assert!(model.error.is_some());
+    }
+
+    #[test]
+    fn errors_are_titled_by_their_cause() {
+        let mut model = AppModel::default();
+        model.entry.title = "   ".into();
+        let mut cmds = Vec::new();
+
+        update(
+            &mut model,
+            Msg::SaveRequested(PathBuf::from("/tmp/ignored.eln")),
+            &mut cmds,
+        );
+        assert_eq!(model.error_title, Some("Validation error"));
+
+        update(&mut model, Msg::DismissError, &mut cmds);
+        update(
+            &mut model,
+            Msg::SaveCompleted(Err("disk full".into())),
+            &mut cmds,
+        );
+        assert_eq!(model.error_title, Some("Save failed"));
+    }
+
+    #[test]
+    fn save_cancelled_sets_status() {
+        let mut model = AppModel::default();
+        let mut cmds = Vec::new();
+
+        update(&mut model, Msg::SaveCancelled, &mut cmds);
+
+        assert!(cmds.is_empty());
+        assert_eq!(status_text(&model), Some("Save cancelled."));
+        assert!(model.error.is_none());
+    }
+
+    #[test]
+    fn attachments_load_thumbnail_enqueues_command() {
+        let mut model = AppModel::default();
+        let mut cmds = Vec::new();
+        let path = PathBuf::from("image.png");
+
+        update(
+            &mut model,
+            Msg::Attachments(AttachmentsMsg::LoadThumbnail(path.clone())),
+            &mut cmds,
+        );
+
+        assert_eq!(cmds.len(), 1);
+        match cmds.pop().unwrap() {
+            Command::LoadThumbnail {
+                path: p,
+                _retry,
+                request_id: _,
+            } => {
+                assert_eq!(p, path);
+            }
+            _ => panic!("unexpected command"),
+        }
+    }
+
+    #[test]
+    fn pending_commands_can_track_attachment_flow() {
+        let mut model = AppModel::default();
+        let mut cmds = Vec::new();
+        let path = PathBuf::from("image.png");
+
+        update(
+            &mut model,
+            Msg::Attachments(AttachmentsMsg::LoadThumbnail(path.clone())),
+            &mut cmds,
+        );
+
+        assert_eq!(cmds.len(), 1, "load thumbnail should enqueue command");
+
+        // UI increments when dispatching commands to the worker.
+        model.pending_commands += cmds.len();
+        assert_eq!(model.pending_commands, 1);
+
+        // Simulate worker response and UI decrement.
+        let mut cmds2 = Vec::new();
+        update(
+            &mut model,
+            Msg::Attachments(AttachmentsMsg::ThumbnailFailed {
+                path,
+                reason: ThumbnailError::Decode("truncated".into()),
+            }),
+            &mut cmds2,
+        );
+        model.pending_commands = model.pending_commands.saturating_sub(1);
+
+        assert_eq!(model.pending_commands, 0);
+    }
+
+    #[test]
+    fn thumbnail_failure_still_routes_through_attachment_messages() {
+        let msg = run_command(Command::LoadThumbnail {
+            path: PathBuf::from("missing.png"),
+            _retry: false,
+            request_id: 1,
+        });
+
+        assert!(matches!(
+            msg,
+            Msg::ThumbnailFailed {
+                reason: ThumbnailError::Decode(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn elabftw_entry_imports_fill_only_the_sections_they_have() {
+        let mut model = AppModel::default();
+        model.entry.title = "Draft title".into();
+        model.entry.datetime.hour = 8;
+        model.entry.performed_at_confirmed = true;
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            Msg::ExtraFields(ExtraFieldsMsg::EntryImportRequested),
+            &mut cmds,
+        );
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::PickElabftwEntryFile { .. }]
+        ));
+
+        let entry = parse_elabftw_entry(
+            r#"{"body": "<p>Spin at <em>4 °C</em></p>", "tags": "PCR|gel", "date": "2024-03-14",
+                "metadata": "{\"extra_fields\": {\"Cycles\": {\"type\": \"number\", \"value\": \"30\"}}}"}"#,
+        )
+        .unwrap();
+        update(
+            &mut model,
+            Msg::ElabftwEntryImported {
+                path: PathBuf::from("/exports/run.json"),
+                result: Ok(Box::new(entry)),
+            },
+            &mut cmds,
+        );
+        assert_eq!(model.entry.title, "Draft title", "no title in the export");
+        assert_eq!(model.entry.markdown.text, "Spin at *4 °C*");
+        assert_eq!(model.entry.keywords.keywords(), ["PCR", "gel"]);
+        assert_eq!(model.entry.datetime.date, jiff::civil::date(2024, 3, 14));
+        assert_eq!(
+            model.entry.datetime.hour, 8,
+            "date-only exports keep the time"
+        );
+        assert!(!model.entry.performed_at_confirmed);
+        assert_eq!(model.entry.extra_fields.fields()[0].label, "Cycles");
+        assert_eq!(
+            status_text(&model),
+            Some("Imported body, 2 keywords, date, 1 field.")
+        );
+
+        update(
+            &mut model,
+            Msg::ElabftwEntryImported {
+                path: PathBuf::from("/exports/run.json"),
+                result: Err("not JSON".into()),
+            },
+            &mut cmds,
+        );
+        assert!(model.error.as_deref().unwrap().contains("not JSON"));
+        assert_eq!(model.entry.keywords.keywords(), ["PCR", "gel"]);
+    }
+
+    #[test]
+    fn previews_load_on_the_worker_and_ignore_closed_dialogs() {
+        let tmp = TempDir::new().unwrap();
+        let csv = tmp.path().join("plate.csv");
+        std::fs::write(&csv, "well,od\nA1,0.5\n").unwrap();
+        let mut model = AppModel::default();
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            Msg::Attachments(AttachmentsMsg::OpenPreview(csv.clone())),
+            &mut cmds,
+        );
+        let Some(Command::LoadPreview { path }) = cmds.pop() else {
+            panic!("opening a preview loads it");
+        };
+        let msg = run_command(Command::LoadPreview { path });
+        assert!(matches!(
+            &msg,
+            Msg::Attachments(AttachmentsMsg::PreviewReady {
+                content: Ok(PreviewContent::File(file_preview::FilePreview::Text { text, .. })),
+                ..
+            }) if text == "well,od\nA1,0.5\n"
+        ));
+
+        let png = tmp.path().join("gel.png");
+        image::RgbaImage::new(300, 20).save(&png).unwrap();
+        let msg = run_command(Command::LoadPreview { path: png });
+        assert!(
+            matches!(&msg, Msg::PreviewImageDecoded { image, .. } if image.size == [300, 20]),
+            "images keep their size"
+        );
+
+        update(
+            &mut model,
+            Msg::Attachments(AttachmentsMsg::ClosePreview),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            Msg::Attachments(AttachmentsMsg::PreviewReady {
+                path: csv,
+                content: Err("late".into()),
+            }),
+            &mut cmds,
+        );
+        assert_eq!(model.entry.attachments.preview_path(), None);
+    }
+
+    #[test]
+    fn thumbnail_available_message_clears_loading_state() {
+        let mut model = AppModel::default();
+        let path = PathBuf::from("image.png");
+        let mut cmds = Vec::new();
+
+        update(
+            &mut model,
+            Msg::Attachments(AttachmentsMsg::LoadThumbnail(path.clone())),
+            &mut cmds,
+        );
+        assert!(model.entry.attachments.is_thumbnail_loading(&path));
+
+        update(
+            &mut model,
+            Msg::Attachments(AttachmentsMsg::ThumbnailAvailable { path: path.clone() }),
+            &mut Vec::new(),
+        );
+
+        assert!(!model.entry.attachments.is_thumbnail_loading(&path));
+    }
+
+    #[test]
+    fn validate_rejects_invalid_url_field() {
+        let mut model = AppModel::default();
+        model.entry.title = "Has URL".into();
+        model.entry.markdown.text = "Body".into();
+
+        add_url_field(&mut model, "htp://example");
+
+        match validate_for_save(&model, PathBuf::from("/tmp/out.eln")) {
+            Err(SaveRejection::Invalid(err)) => assert!(err.contains("valid http/https URL")),
+            _ => panic!("validation should fail for invalid URL"),
+        }
+    }
+
+    #[test]
+    fn validate_accepts_valid_url_field() {
+        let mut model = AppModel::default();
+        model.entry.title = "Has URL".into();
+        model.entry.markdown.text = "Body".into();
+
+        add_url_field(&mut model, "https://example.com/path");
+
+        let res = validate_for_save(&model, PathBuf::from("/tmp/out.eln"));
+
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_invalid_number_field() {
+        let mut model = AppModel::default();
+        model.entry.title = "Has number".into();
+        model.entry.markdown.text = "Body".into();
+
+        add_typed_field(&mut model, ExtraFieldKind::Number, "abc");
+
+        match validate_for_save(&model, PathBuf::from("/tmp/out.eln")) {
+            Err(SaveRejection::Invalid(err)) => assert!(err.contains("valid number")),
+            _ => panic!("validation should fail for invalid number"),
+        }
+    }
+
+    #[test]
+    fn validate_accepts_valid_number_field() {
+        let mut model = AppModel::default();
+        model.entry.title = "Has number".into();
+        model.entry.markdown.text = "Body".into();
+
+        add_typed_field(&mut model, ExtraFieldKind::Number, "42.5");
+
+        let res = validate_for_save(&model, PathBuf::from("/tmp/out.eln"));
+
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_invalid_integer_field() {
+        let mut model = AppModel::default();
+        model.entry.title = "Has int".into();
+        model.entry.markdown.text = "Body".into();
+
+        add_typed_field(&mut model, ExtraFieldKind::Items, "12.3");
+
+        match validate_for_save(&model, PathBuf::from("/tmp/out.eln")) {
+            Err(SaveRejection::Invalid(err)) => assert!(err.contains("valid integer ID")),
+            _ => panic!("validation should fail for invalid integer"),
+        }
+    }
+
+    #[test]
+    fn saved_keywords_include_the_configured_field_values() {
+        let mut model = AppModel::default();
+        model.entry.title = "Tagged".into();
+        let mut settings = Settings::default();
+        settings.keywords.from_fields = vec!["field".into()];
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            Msg::Settings(SettingsMsg::Loaded(Box::new(settings))),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            Msg::Keywords(KeywordsMsg::Restore(vec!["krios".into()])),
+            &mut cmds,
+        );
+        add_typed_field(&mut model, ExtraFieldKind::Text, "Krios");
+        add_typed_field(&mut model, ExtraFieldKind::Text, "E. coli");
+
+        let payload = match validate_for_save(&model, PathBuf::from("/tmp/out.eln")) {
+            Ok(payload) => payload,
+            Err(_) => panic!("the entry is valid"),
+        };
+        assert_eq!(payload.entries[0].keywords, ["krios", "E. coli"]);
+        assert_eq!(
+            model.entry.keywords.keywords(),
+            ["krios"],
+            "derived keywords are not stored in the entry"
+        );
+    }
+
+    #[test]
+    fn template_archives_keep_only_the_values_not_flagged_for_blanking() {
+        let mut model = AppModel::default();
+        model.entry.title = "Cryo-EM".into();
+        let mut settings = Settings::default();
+        settings.keywords.from_fields = vec!["field".into(), "grid".into()];
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            Msg::Settings(SettingsMsg::Loaded(Box::new(settings.clone()))),
+            &mut cmds,
+        );
+        add_typed_field(&mut model, ExtraFieldKind::Text, "Krios");
+        let Ok(mut payload) = validate_for_save(&model, PathBuf::from("/tmp/out.eln")) else {
+            panic!("the entry is valid");
+        };
+        let entry = &mut payload.entries[0];
+        let mut grid = entry.extra_fields[0].clone();
+        grid.label = "Grid".into();
+        grid.value = "Quantifoil".into();
+        grid.blank_value_on_duplicate = true;
+        entry.extra_fields.push(grid);
+        entry.keywords.push("Quantifoil".into());
+        entry.attachments.push(Attachment::new(
+            PathBuf::from("/tmp/micrograph.tif"),
+            "micrograph.tif".into(),
+            "image/tiff".into(),
+            "0".repeat(64),
+            3,
+        ));
+
+        let template = template_archive(payload, &settings.keywords);
+        let entry = &template.entries[0];
+        let values: Vec<(&str, &str)> = entry
+            .extra_fields
+            .iter()
+            .map(|field| (field.label.as_str(), field.value.as_str()))
+            .collect();
+        assert_eq!(values, [("Field", "Krios"), ("Grid", "")]);
+        assert_eq!(
+            entry.keywords,
+            ["Krios"],
+            "blanked values leave no keywords"
+        );
+        assert!(entry.attachments.is_empty());
+        assert_eq!(entry.genre, ArchiveGenre::Template);
+        assert_eq!(entry.title, "Cryo-EM");
+    }
+
+    #[test]
+    fn template_archives_are_saved_without_marking_the_entries_saved() {
+        let mut model = AppModel::default();
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            Msg::EntryTitleChanged("Cryo-EM".into()),
+            &mut cmds,
+        );
+        assert!(model.dirty);
+
+        update(
+            &mut model,
+            Msg::Templates(TemplatesMsg::SaveArchive),
+            &mut cmds,
+        );
+        let [Command::PickTemplateArchive { default_name, .. }] = cmds.as_slice() else {
+            panic!("a file dialog should open");
+        };
+        assert!(default_name.ends_with("template.eln"), "{default_name}");
+
+        cmds.clear();
+        let output = PathBuf::from("/tmp/cryo-em-template");
+        update(
+            &mut model,
+            Msg::TemplateArchivePicked(Some(output.clone())),
+            &mut cmds,
+        );
+        let [Command::SaveArchive(payload)] = cmds.as_slice() else {
+            panic!("the template archive should be written");
+        };
+        assert_eq!(payload.output, output.with_extension("eln"));
+        assert_eq!(payload.entries[0].genre, ArchiveGenre::Template);
+
+        update(
+            &mut model,
+            Msg::SaveCompleted(Ok(payload.output.clone())),
+            &mut cmds,
+        );
+        assert!(
+            status_text(&model)
+                .unwrap()
+                .starts_with("Template archive saved")
+        );
+        assert!(model.dirty, "the entries themselves are still unsaved");
+        assert!(model.last_saved.is_none());
+    }
+
+    #[test]
+    fn validate_accepts_valid_integer_field() {
+        let mut model = AppModel::default();
+        model.entry.title = "Has int".into();
+        model.entry.markdown.text = "Body".into();
+
+        add_typed_field(&mut model, ExtraFieldKind::Users, "12345");
+
+        let res = validate_for_save(&model, PathBuf::from("/tmp/out.eln"));
+
+        assert!(res.is_ok());
+    }
+
+    fn model_with_old_date() -> AppModel {
+        let mut model = AppModel::default();
+        model.entry.title = "Backfilled".into();
+        update(
+            &mut model,
+            Msg::DateTime(DateTimeMsg::SetDate(jiff::civil::date(1925, 6, 1))),
+            &mut Vec::new(),
+        );
+        model
+    }
+
+    #[test]
+    fn implausible_date_requires_confirmation_before_saving() {
+        let mut model = model_with_old_date();
+        let output = PathBuf::from("/tmp/old.eln");
+        let mut cmds = Vec::new();
+
+        update(&mut model, Msg::SaveRequested(output.clone()), &mut cmds);
+
+        assert!(cmds.is_empty(), "save must wait for confirmation");
+        assert!(model.error.is_none(), "warning is not a hard error");
+        let pending = model
+            .date_confirmation
+            .as_ref()
+            .expect("confirmation pending");
+        assert_eq!(
+            pending.warning,
+            DateWarning::OlderThan {
+                years: crate::models::performed_at::DEFAULT_DATE_HORIZON_YEARS
+            }
+        );
+
+        update(&mut model, Msg::ConfirmPerformedAt, &mut cmds);
+
+        assert!(model.date_confirmation.is_none());
+        match cmds.as_slice() {
+            [Command::SaveArchive(payload)] => assert_eq!(payload.output, output),
+            _ => panic!("confirmed save should enqueue the archive"),
+        }
+    }
+
+    #[test]
+    fn editing_date_revokes_confirmation() {
+        let mut model = model_with_old_date();
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            Msg::SaveRequested(PathBuf::from("/tmp/old.eln")),
+            &mut cmds,
+        );
+        update(&mut model, Msg::ConfirmPerformedAt, &mut cmds);
+        assert!(performed_at_warning(&model).is_none());
+
+        update(
+            &mut model,
+            Msg::DateTime(DateTimeMsg::SetHour(3)),
+            &mut Vec::new(),
+        );
+
+        assert!(performed_at_warning(&model).is_some());
+    }
+
+    #[test]
+    fn cancelling_date_confirmation_drops_save() {
+        let mut model = model_with_old_date();
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            Msg::SaveRequested(PathBuf::from("/tmp/old.eln")),
+            &mut cmds,
+        );
+
+        update(&mut model, Msg::CancelPerformedAtConfirmation, &mut cmds);
+
+        assert!(model.date_confirmation.is_none());
+        assert!(!model.entry.performed_at_confirmed);
+        assert!(cmds.is_empty());
+    }
+
+    #[test]
+    fn large_attachments_need_confirming_before_saving() {
+        let mut model = AppModel::default();
+        model.entry.title = "Videos".into();
+        let mut settings = Settings::default();
+        settings.attachments.warn_above_mb = 1;
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            Msg::Settings(SettingsMsg::Loaded(Box::new(settings))),
+            &mut cmds,
+        );
+        let file = |name: &str, size: u64| {
+            Attachment::new(
+                PathBuf::from(format!("/data/{name}")),
+                name.into(),
+                "video/mp4".into(),
+                "aa".into(),
+                size,
+            )
+        };
+        update(
+            &mut model,
+            Msg::Attachments(AttachmentsMsg::Restore(vec![
+                file("run1.mp4", 2 << 20),
+                file("notes.txt", 10),
+                file("run2.mp4", 3 << 20),
+            ])),
+            &mut cmds,
+        );
+        let save = |model: &mut AppModel, cmds: &mut Vec<Command>| {
+            cmds.clear();
+            update(
+                model,
+                Msg::SaveRequested(PathBuf::from("/tmp/big.eln")),
+                cmds,
+            );
+        };
+
+        save(&mut model, &mut cmds);
+        assert_eq!(
+            model.size_confirmation.as_ref().map(|c| c.message.as_str()),
+            Some("2 attachments exceed 1.0 MB (run1.mp4, run2.mp4)")
+        );
+        assert!(!cmds.iter().any(|c| matches!(c, Command::SaveArchive(_))));
+        update(&mut model, Msg::CancelAttachmentSizeConfirmation, &mut cmds);
+        assert!(model.size_confirmation.is_none());
+        assert!(!model.saving);
+
+        save(&mut model, &mut cmds);
+        update(&mut model, Msg::ConfirmAttachmentSizes, &mut cmds);
+        assert!(cmds.iter().any(|c| matches!(c, Command::SaveArchive(_))));
+        assert!(!model.sizes_confirmed, "the next save asks again");
+    }
+
+    fn sample_draft(attachments: Vec<Attachment>) -> Draft {
+        Draft {
+            version: DRAFT_SCHEMA_VERSION,
+            title: "Draft title".into(),
+            genre: ArchiveGenre::Resource,
+            body_format: crate::logic::eln::BodyFormat::Markdown,
+            body: "Draft body".into(),
+            keywords: vec!["kw".into()],
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+            extra_fields_source: None,
+            performed_date: "2024-06-15".into(),
+            performed_hour: 14,
+            performed_minute: 30,
+            attachments,
+            language: String::new(),
+            related_links: Vec::new(),
+            license: License::None,
+            summary: String::new(),
+            hide_main_text: false,
+            more_entries: Vec::new(),
+        }
+    }
+
+    /// Restore `draft` the way a draft loaded at startup is: checked by a command first.
+    fn restore(model: &mut AppModel, draft: Draft) {
+        let mut cmds = Vec::new();
+        update(model, Msg::DraftRestored(Box::new(draft)), &mut cmds);
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::CheckDraftAttachments(_)]
+        ));
+        update(model, run_command(cmds.remove(0)), &mut Vec::new());
+    }
+
+    #[test]
+    fn draft_restore_populates_model_and_reports_missing_files() {
+        let tmp = TempDir::new().unwrap();
+        let present = tmp.path().join("present.txt");
+        std::fs::write(&present, b"abc").unwrap();
+        let attachment = |path: PathBuf| {
+            Attachment::new(
+                path,
+                "renamed.txt".into(),
+                "text/plain".into(),
+                "unavailable".into(),
+                3,
+            )
+        };
+
+        let mut model = AppModel::default();
+        restore(
+            &mut model,
+            sample_draft(vec![
+                attachment(present.clone()),
+                attachment(tmp.path().join("gone.txt")),
+            ]),
+        );
+
+        assert_eq!(model.entry.title, "Draft title");
+        assert_eq!(model.entry.markdown.text, "Draft body");
+        assert_eq!(model.entry.keywords.keywords(), ["kw"]);
+        assert_eq!(model.entry.genre, ArchiveGenre::Resource);
+        assert_eq!(model.entry.datetime.hour, 14);
+        assert_eq!(model.entry.attachments.attachments().len(), 1);
+        assert_eq!(
+            model.entry.attachments.attachments()[0].sanitized_name,
+            "renamed.txt"
+        );
+        assert!(model.draft_restored);
+        assert!(model.error.is_none(), "missing files are a warning only");
+        assert!(status_text(&model).unwrap().contains("gone.txt"));
+    }
+
+    #[test]
+    fn restored_drafts_wait_for_the_attachment_check() {
+        let tmp = TempDir::new().unwrap();
+        let data = tmp.path().join("data.csv");
+        std::fs::write(&data, b"1,2").unwrap();
+        let sha256 = crate::utils::hash_file(&data).unwrap();
+        std::fs::write(&data, b"1,3").unwrap();
+
+        let mut model = AppModel::default();
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            Msg::DraftRestored(Box::new(sample_draft(vec![Attachment::new(
+                data,
+                "data.csv".into(),
+                "text/csv".into(),
+                sha256,
+                3,
+            )]))),
+            &mut cmds,
+        );
+        assert!(
+            model.entry.title.is_empty(),
+            "nothing applied before the check"
+        );
+        assert!(!model.dirty);
+
+        let msg = run_command(cmds.pop().expect("check command queued"));
+        update(&mut model, msg, &mut Vec::new());
+        assert_eq!(model.entry.title, "Draft title");
+        assert!(
+            model.entry.attachments.attachments().is_empty(),
+            "same size, different content"
+        );
+        assert!(status_text(&model).unwrap().contains("data.csv"));
+    }
+
+    #[test]
+    fn image_attachments_feed_the_picker_and_dangling_references_warn_on_save() {
+        let tmp = TempDir::new().unwrap();
+        let gel = tmp.path().join("gel.png");
+        std::fs::write(&gel, b"png").unwrap();
+        let output = tmp.path().join("out.eln");
+
+        let mut model = AppModel::default();
+        restore(
+            &mut model,
+            sample_draft(vec![Attachment::new(
+                gel,
+                "gel.png".into(),
+                "image/png".into(),
+                "unavailable".into(),
+                3,
+            )]),
+        );
+        assert_eq!(model.entry.markdown.image_attachments, ["gel.png"]);
+
+        model.entry.markdown.text = "![gel](./gel.png) and ![old](./old.png)".into();
+        let mut cmds = Vec::new();
+        update(&mut model, Msg::SaveRequested(output.clone()), &mut cmds);
+        let msg = run_command(cmds.pop().unwrap());
+        update(&mut model, msg, &mut Vec::new());
+
+        assert!(model.error.is_none(), "{:?}", model.error);
+        let status = status_text(&model).unwrap();
+        assert!(status.contains("Archive saved"), "{status}");
+        assert!(status.contains("(RO-Crate 1.2)"), "{status}");
+        assert!(status.contains("old.png"), "{status}");
+        assert!(!status.contains("gel.png"), "{status}");
+        assert!(output.exists());
+    }
+
+    #[test]
+    fn picked_attachments_are_hashed_and_added_without_a_display() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("plate.csv");
+        std::fs::write(&file, "A1,0.42\n").unwrap();
+        let dialogs = ScriptedDialogs::new([vec![file.clone()], Vec::new()]);
+        let mut model = AppModel::default();
+
+        let mut cmds = Vec::new();
+        for _ in 0..2 {
+            update(
+                &mut model,
+                Msg::Attachments(AttachmentsMsg::RequestPickFiles),
+                &mut cmds,
+            );
+        }
+        while let Some(cmd) = cmds.pop() {
+            let msg = run_command_with_progress(cmd, &dialogs, &mut |_| {});
+            update(&mut model, msg, &mut cmds);
+        }
+
+        assert_eq!(
+            dialogs.asked(),
+            ["Select attachments", "Select attachments"]
+        );
+        let attachments = model.entry.attachments.attachments();
+        assert_eq!(attachments.len(), 1, "the cancelled pick adds nothing");
+        assert_eq!(attachments[0].path, file);
+        assert_eq!(attachments[0].size, 8);
+    }
+
+    #[test]
+    fn batch_writes_one_archive_per_subfolder_and_keeps_going_after_errors() {
+        let tmp = TempDir::new().unwrap();
+        let parent = tmp.path().join("reports");
+        let out = tmp.path().join("out");
+        for sample in ["s1", "s2", "s3"] {
+            std::fs::create_dir_all(parent.join(sample)).unwrap();
+            std::fs::write(parent.join(sample).join("body.md"), sample).unwrap();
+        }
+        std::fs::write(parent.join("s2/metadata.json"), "not json").unwrap();
+        std::fs::create_dir_all(&out).unwrap();
+        let dialogs = ScriptedDialogs::new([vec![parent.clone()], vec![out.clone()]]);
+        let mut model = AppModel::default();
+        let mut cmds = Vec::new();
+
+        update(&mut model, Msg::BatchRequested, &mut cmds);
+        let Some(cmd) = cmds.pop() else {
+            panic!("batch asks for folders");
+        };
+        let msg = run_command_with_progress(cmd, &dialogs, &mut |_| {});
+        update(&mut model, msg, &mut cmds);
+        assert_eq!(cmds.len(), 3);
+        assert!(
+            model
+                .batch
+                .as_ref()
+                .unwrap()
+                .states
+                .iter()
+                .all(|s| *s == BatchJobState::Pending)
+        );
+
+        let mut progress = Vec::new();
+        for cmd in cmds.drain(..) {
+            let msg = run_command_with_progress(cmd, &dialogs, &mut |m| progress.push(m));
+            for update_msg in progress.drain(..) {
+                update(&mut model, update_msg, &mut Vec::new());
+            }
+            update(&mut model, msg, &mut Vec::new());
+        }
+
+        let batch = model.batch.as_ref().unwrap();
+        assert_eq!(batch.states[0], BatchJobState::Done(out.join("s1.eln")));
+        assert!(
+            matches!(&batch.states[1], BatchJobState::Failed(err) if err.contains("metadata.json")),
+            "{:?}",
+            batch.states[1]
+        );
+        assert_eq!(batch.states[2], BatchJobState::Done(out.join("s3.eln")));
+        assert!(out.join("s3.eln").exists());
+        assert_eq!(
+            status_text(&model),
+            Some("Batch finished: 2 archives written, 1 failed, 0 cancelled.")
+        );
+        update(&mut model, Msg::CloseBatch, &mut cmds);
+        assert!(model.batch.is_none());
+    }
+
+    #[test]
+    fn cancelling_a_batch_stops_the_jobs_not_yet_written() {
+        let tmp = TempDir::new().unwrap();
+        let out = tmp.path().join("out");
+        std::fs::create_dir_all(&out).unwrap();
+        let jobs: Vec<BatchJob> = ["s1", "s2"]
+            .into_iter()
+            .map(|name| {
+                let folder = tmp.path().join(name);
+                std::fs::create_dir_all(&folder).unwrap();
+                std::fs::write(folder.join("body.md"), name).unwrap();
+                BatchJob {
+                    folder,
+                    title: name.into(),
+                    output: out.join(format!("{name}.eln")),
+                }
+            })
+            .collect();
+        let mut model = AppModel::default();
+        let mut cmds = Vec::new();
+        update(&mut model, Msg::BatchPrepared(Ok(Some(jobs))), &mut cmds);
+
+        let first = cmds.remove(0);
+        let msg = run_command(first);
+        update(&mut model, msg, &mut Vec::new());
+        update(&mut model, Msg::CancelBatch, &mut Vec::new());
+        update(&mut model, Msg::CloseBatch, &mut Vec::new());
+        assert!(model.batch.is_some(), "an unfinished batch stays open");
+        let msg = run_command(cmds.remove(0));
+        update(&mut model, msg, &mut Vec::new());
+
+        let batch = model.batch.as_ref().unwrap();
+        assert!(matches!(batch.states[0], BatchJobState::Done(_)));
+        assert_eq!(batch.states[1], BatchJobState::Cancelled);
+        assert!(!out.join("s2.eln").exists());
+        assert!(status_text(&model).unwrap().ends_with("1 cancelled."));
+    }
+
+    #[test]
+    fn metadata_import_reads_the_picked_file_and_cancels_without_one() {
+        let tmp = TempDir::new().unwrap();
+        let template = tmp.path().join("template.json");
+        std::fs::write(
+            &template,
+            r#"{"extra_fields": {"Operator": {"type": "text", "value": "ada"}}}"#,
+        )
+        .unwrap();
+        let dialogs = ScriptedDialogs::new([vec![template]]);
+        let mut model = AppModel::default();
+
+        for _ in 0..2 {
+            let mut cmds = Vec::new();
+            update(
+                &mut model,
+                Msg::ExtraFields(ExtraFieldsMsg::ImportRequested),
+                &mut cmds,
+            );
+            assert!(matches!(
+                cmds.as_slice(),
+                [Command::PickExtraFieldsFile { .. }]
+            ));
+            let msg = run_command_with_progress(cmds.remove(0), &dialogs, &mut |_| {});
+            update(&mut model, msg, &mut Vec::new());
+        }
+
+        assert_eq!(dialogs.asked().len(), 2);
+        assert_eq!(model.entry.extra_fields.fields()[0].label, "Operator");
+        assert_eq!(model.entry.extra_fields.fields()[0].value, "ada");
+        assert!(model.error.is_none(), "{:?}", model.error);
+    }
+
+    #[test]
+    fn hidden_main_text_survives_save_and_open_and_is_warned_about() {
+        let tmp = TempDir::new().unwrap();
+        let output = tmp.path().join("fields.eln");
+        let json = r#"{"elabftw":{"display_main_text":false},"extra_fields":{"Notes":{"type":"text","value":"x"}}}"#;
+        let import = parse_elabftw_extra_fields(json).unwrap();
+        assert!(import.hide_main_text);
+        let mut model = AppModel::default();
+        model.entry.title = "Fields only".into();
+        update(
+            &mut model,
+            Msg::ExtraFields(ExtraFieldsMsg::ImportLoaded {
+                fields: import.fields,
+                groups: import.groups,
+                source: ExtraFieldsSource::new(
+                    PathBuf::from("meta.json"),
+                    json,
+                    time::OffsetDateTime::UNIX_EPOCH,
+                ),
+                tags: import.tags,
+                hide_main_text: import.hide_main_text,
+            }),
+            &mut Vec::new(),
+        );
+        assert!(snapshot_draft(&model).hide_main_text);
+        markdown::update(
+            &mut model.entry.markdown,
+            MarkdownMsg::SetText("Not shown in eLabFTW".into()),
+        );
+
+        let mut cmds = Vec::new();
+        update(&mut model, Msg::SaveRequested(output.clone()), &mut cmds);
+        let msg = run_command(cmds.pop().unwrap());
+        update(&mut model, msg, &mut Vec::new());
+        let status = model.status.as_ref().unwrap();
+        assert_eq!(status.severity, Severity::Warning);
+        assert!(
+            status.text.contains("will not show the main text"),
+            "{}",
+            status.text
+        );
+
+        let opened =
+            crate::logic::eln_import::open_archive(&output, &tmp.path().join("opened")).unwrap();
+        assert!(opened.hide_main_text);
+        let mut reopened = AppModel::default();
+        apply_opened_archive(&mut reopened, opened);
+        assert!(reopened.entry.extra_fields.hide_main_text());
+
+        update(
+            &mut reopened,
+            Msg::ExtraFields(ExtraFieldsMsg::ShowMainTextToggled(true)),
+            &mut Vec::new(),
+        );
+        assert!(!snapshot_draft(&reopened).hide_main_text);
+    }
+
+    #[test]
+    fn imported_metadata_tags_are_merged_into_the_keywords() {
+        let mut model = AppModel::default();
+        update(
+            &mut model,
+            Msg::Keywords(KeywordsMsg::Restore(vec!["PCR".into()])),
+            &mut Vec::new(),
+        );
+        let json = r#"{"extra_fields":{"Notes":{"type":"text","value":"x"}},"tags":"pcr|gel"}"#;
+        let import = parse_elabftw_extra_fields(json).unwrap();
+
+        update(
+            &mut model,
+            Msg::ExtraFields(ExtraFieldsMsg::ImportLoaded {
+                fields: import.fields,
+                groups: import.groups,
+                source: ExtraFieldsSource::new(
+                    PathBuf::from("meta.json"),
+                    json,
+                    time::OffsetDateTime::UNIX_EPOCH,
+                ),
+                tags: import.tags,
+                hide_main_text: false,
+            }),
+            &mut Vec::new(),
+        );
+
+        assert_eq!(model.entry.keywords.keywords(), ["PCR", "gel"]);
+        assert_eq!(
+            status_text(&model),
+            Some(
+                "Imported 1 field(s) from meta.json. Added 1 new keyword(s) (1 already present or over the limit)."
+            )
+        );
+    }
+
+    #[test]
+    fn reimport_reads_the_remembered_file_and_reports_when_it_is_gone() {
+        let tmp = TempDir::new().unwrap();
+        let template = tmp.path().join("template_v3.json");
+        std::fs::write(
+            &template,
+            r#"{"extra_fields": {"Operator": {"type": "text", "value": ""}}}"#,
+        )
+        .unwrap();
+
+        let mut model = AppModel::default();
+        let Msg::ExtraFields(ExtraFieldsMsg::ReimportLoaded { fields, source, .. }) =
+            run_command(Command::ReadExtraFieldsFile {
+                path: template.clone(),
+            })
+        else {
+            panic!("expected a parsed file");
+        };
+        assert_eq!(fields.len(), 1);
+        update(
+            &mut model,
+            Msg::ExtraFields(ExtraFieldsMsg::ImportLoaded {
+                fields,
+                groups: Vec::new(),
+                source,
+                tags: Vec::new(),
+                hide_main_text: false,
+            }),
+            &mut Vec::new(),
+        );
+        assert_eq!(
+            snapshot_draft(&model).extra_fields_source.unwrap().path,
+            template
+        );
+
+        std::fs::remove_file(&template).unwrap();
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            Msg::ExtraFields(ExtraFieldsMsg::ReimportRequested),
+            &mut cmds,
+        );
+        let msg = run_command(cmds.pop().expect("re-import reads the file"));
+        update(&mut model, msg, &mut Vec::new());
+        assert!(
+            model.error.as_deref().unwrap().contains("no longer exists"),
+            "{:?}",
+            model.error
+        );
+    }
+
+    #[test]
+    fn saving_reverifies_attachments_and_flags_changed_ones() {
+        use crate::ui::components::attachments::Verification;
+
+        let tmp = TempDir::new().unwrap();
+        let data = tmp.path().join("data.csv");
+        std::fs::write(&data, b"1,2").unwrap();
+        let sha256 = crate::utils::hash_file(&data).unwrap();
+        let output = tmp.path().join("out.eln");
+
+        let mut model = AppModel::default();
+        restore(
+            &mut model,
+            sample_draft(vec![Attachment::new(
+                data.clone(),
+                "data.csv".into(),
+                "text/csv".into(),
+                sha256,
+                3,
+            )]),
+        );
+        let state = |model: &AppModel| model.entry.attachments.attachments()[0].verification;
+        assert_eq!(state(&model), Verification::Unverified);
+
+        let mut cmds = Vec::new();
+        update(&mut model, Msg::SaveRequested(output.clone()), &mut cmds);
+        let msg = run_command(cmds.pop().unwrap());
+        update(&mut model, msg, &mut Vec::new());
+        assert!(matches!(state(&model), Verification::Reverified(_)));
+
+        std::fs::write(&data, b"1,3").unwrap();
+        let mut cmds = Vec::new();
+        update(&mut model, Msg::SaveRequested(output), &mut cmds);
+        let mut reported = Vec::new();
+        let msg =
+            run_command_with_progress(cmds.pop().unwrap(), &RfdDialogs, &mut |m| reported.push(m));
+        for m in reported.into_iter().chain([msg]) {
+            update(&mut model, m, &mut Vec::new());
+        }
+        assert_eq!(state(&model), Verification::Changed);
+        assert!(model.error.is_some());
+    }
+
+    #[test]
+    fn templates_save_and_replace_a_filled_form_only_after_confirmation() {
+        let tmp = TempDir::new().unwrap();
+        let mut model = AppModel {
+            templates_path: Some(tmp.path().to_path_buf()),
+            settings_path: Some(tmp.path().join("settings.json")),
+            ..Default::default()
+        };
+        model.entry.title = "PCR run {date}".into();
+        model.entry.markdown.text = "## Cycles".into();
+
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            Msg::Templates(TemplatesMsg::OpenSave),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            Msg::Templates(TemplatesMsg::SaveNameChanged("PCR".into())),
+            &mut cmds,
+        );
+        update(&mut model, Msg::Templates(TemplatesMsg::Save), &mut cmds);
+        let msg = run_command(cmds.pop().expect("save command"));
+        update(&mut model, msg, &mut cmds);
+        assert_eq!(status_text(&model), Some("Saved template 'PCR'."));
+
+        model.entry.title = "Something else".into();
+        update(
+            &mut model,
+            Msg::Templates(TemplatesMsg::Apply("PCR".into())),
+            &mut cmds,
+        );
+        let msg = run_command(cmds.pop().expect("load command"));
+        update(&mut model, msg, &mut cmds);
+        assert!(
+            model.template_confirmation.is_some(),
+            "filled form asks first"
+        );
+        assert_eq!(model.entry.title, "Something else");
+
+        update(&mut model, Msg::ConfirmTemplate, &mut cmds);
+        assert!(model.template_confirmation.is_none());
+        assert_eq!(
+            model.entry.title,
+            format!("PCR run {}", model.entry.datetime.date)
+        );
+        assert_eq!(model.entry.markdown.text, "## Cycles");
+        assert_eq!(model.templates_path.as_deref(), Some(tmp.path()));
+        assert_eq!(model.settings.settings().templates.recent, ["PCR"]);
+        assert!(
+            cmds.iter()
+                .any(|cmd| matches!(cmd, Command::SaveSettings { .. }))
+        );
+        assert_eq!(applied_template(&model), Some(("PCR", false)));
+
+        add_url_field(&mut model, "https://example.org");
+        assert_eq!(applied_template(&model), Some(("PCR", true)));
+    }
+
+    #[test]
+    fn blocked_saves_reveal_missing_required_values() {
+        let mut model = AppModel::default();
+        let mut cmds = Vec::new();
+        update(&mut model, Msg::EntryTitleChanged("Run".into()), &mut cmds);
+        for msg in [
+            ExtraFieldsMsg::StartAddField { group_id: None },
+            ExtraFieldsMsg::DraftLabelChanged("Operator".into()),
+            ExtraFieldsMsg::DraftRequiredToggled(true),
+            ExtraFieldsMsg::CommitFieldModal,
+        ] {
+            update(&mut model, Msg::ExtraFields(msg), &mut cmds);
+        }
+        let field = model.entry.extra_fields.fields()[0].clone();
+        assert_eq!(model.entry.extra_fields.inline_error(&field), None);
+
+        update(&mut model, Msg::SaveBlocked, &mut cmds);
+        assert_eq!(
+            model.entry.extra_fields.inline_error(&field).as_deref(),
+            Some("This field is required.")
+        );
+        assert!(status_text(&model).unwrap().contains("metadata section"));
+    }
+
+    #[test]
+    fn imports_and_templates_do_not_discard_an_open_field_draft() {
+        let mut model = AppModel::default();
+        let mut cmds = Vec::new();
+        for msg in [
+            ExtraFieldsMsg::StartAddField { group_id: None },
+            ExtraFieldsMsg::DraftLabelChanged("Operator".into()),
+        ] {
+            update(&mut model, Msg::ExtraFields(msg), &mut cmds);
+        }
+
+        let mut template = Template::blank("PCR");
+        template.title = "PCR run".into();
+        update(
+            &mut model,
+            Msg::TemplateLoaded(Ok(Box::new(template))),
+            &mut cmds,
+        );
+        assert!(
+            model.template_confirmation.is_some(),
+            "asks instead of applying"
+        );
+        update(&mut model, Msg::ConfirmTemplate, &mut cmds);
+        assert!(model.template_confirmation.is_some());
+        assert!(status_text(&model).unwrap().contains("field editor"));
+
+        let source = ExtraFieldsSource::new(
+            PathBuf::from("template_v3.json"),
+            "{}",
+            time::OffsetDateTime::UNIX_EPOCH,
+        );
+        update(
+            &mut model,
+            Msg::ExtraFields(ExtraFieldsMsg::ImportLoaded {
+                fields: Vec::new(),
+                groups: Vec::new(),
+                source,
+                tags: Vec::new(),
+                hide_main_text: false,
+            }),
+            &mut cmds,
+        );
+        assert!(
+            model.entry.extra_fields.is_editing_field(),
+            "the draft stays open"
+        );
+        assert!(model.entry.extra_fields.source().is_none());
+        assert!(status_text(&model).unwrap().contains("field editor"));
+
+        update(
+            &mut model,
+            Msg::ExtraFields(ExtraFieldsMsg::CommitFieldModal),
+            &mut cmds,
+        );
+        assert!(!model.entry.extra_fields.is_editing_field());
+        assert_eq!(
+            model.entry.extra_fields.source().unwrap().path,
+            PathBuf::from("template_v3.json"),
+            "the import is applied once the editor closes"
+        );
+        update(&mut model, Msg::ConfirmTemplate, &mut cmds);
+        assert_eq!(model.entry.title, "PCR run");
+    }
+
+    #[test]
+    fn template_editor_leaves_the_entry_untouched() {
+        let tmp = TempDir::new().unwrap();
+        let mut model = AppModel {
+            templates_path: Some(tmp.path().to_path_buf()),
+            ..Default::default()
+        };
+        add_url_field(&mut model, "https://example.org");
+        let fields = model.entry.extra_fields.clone();
+        model.dirty = false;
+
+        let mut cmds = Vec::new();
+        for msg in [
+            TemplatesMsg::New,
+            TemplatesMsg::Editor(TemplateEditorMsg::NameChanged("Cells".into())),
+            TemplatesMsg::Editor(TemplateEditorMsg::Fields(ExtraFieldsMsg::StartAddField {
+                group_id: None,
+            })),
+            TemplatesMsg::Editor(TemplateEditorMsg::Fields(
+                ExtraFieldsMsg::DraftLabelChanged("Count".into()),
+            )),
+            TemplatesMsg::Editor(TemplateEditorMsg::Fields(ExtraFieldsMsg::CommitFieldModal)),
+            TemplatesMsg::Editor(TemplateEditorMsg::Fields(ExtraFieldsMsg::RemoveField(0))),
+            TemplatesMsg::Editor(TemplateEditorMsg::Save),
+        ] {
+            update(&mut model, Msg::Templates(msg), &mut cmds);
+        }
+        let msg = run_command(cmds.pop().expect("write command"));
+        update(&mut model, msg, &mut cmds);
+
+        assert_eq!(model.entry.extra_fields, fields);
+        assert!(!model.dirty, "editing templates does not edit the entry");
+        assert_eq!(status_text(&model), Some("Saved template 'Cells'."));
+        let saved = templates::load_template(tmp.path(), "Cells").unwrap();
+        assert!(
+            saved.extra_fields.is_empty(),
+            "the editor's removal was saved"
+        );
+    }
+
+    #[test]
+    fn draft_round_trips_through_snapshot() {
+        let mut model = AppModel::default();
+        restore(&mut model, sample_draft(Vec::new()));
+
+        assert_eq!(snapshot_draft(&model), sample_draft(Vec::new()));
+    }
+
+    #[test]
+    fn successful_save_deletes_draft() {
+        let mut model = AppModel {
+            draft_path: Some(PathBuf::from("/tmp/draft.json")),
+            draft_restored: true,
+            ..Default::default()
+        };
+        let mut cmds = Vec::new();
+
+        update(
+            &mut model,
+            Msg::SaveCompleted(Ok(PathBuf::from("/tmp/out.eln"))),
+            &mut cmds,
+        );
+
+        assert!(!model.draft_restored);
+        assert!(matches!(cmds.as_slice(), [Command::DeleteDraft { .. }]));
+    }
+
+    #[test]
+    fn edits_mark_entries_dirty_until_saved() {
+        let mut model = AppModel::default();
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            Msg::Markdown(MarkdownMsg::SetCursor(None)),
+            &mut cmds,
+        );
+        update(&mut model, Msg::Keywords(KeywordsMsg::OpenModal), &mut cmds);
+        assert!(!model.dirty, "selection and dialogs change nothing");
+
+        update(&mut model, Msg::EntryTitleChanged("Run".into()), &mut cmds);
+        assert!(model.dirty);
+        update(
+            &mut model,
+            Msg::SaveCompleted(Err("disk full".into())),
+            &mut cmds,
+        );
+        assert!(model.dirty, "failed saves keep the changes unsaved");
+        update(
+            &mut model,
+            Msg::SaveCompleted(Ok(PathBuf::from("/tmp/out.eln"))),
+            &mut cmds,
+        );
+        assert!(!model.dirty);
+
+        update(
+            &mut model,
+            Msg::Markdown(MarkdownMsg::SetText("Body".into())),
+            &mut cmds,
+        );
+        assert!(model.dirty);
+        update(&mut model, Msg::DiscardDraft, &mut cmds);
+        assert!(!model.dirty);
+
+        let mut draft = sample_draft(Vec::new());
+        draft.extra_fields = vec![ExtraField {
+            label: "Buffer".into(),
+            value: "Tris".into(),
+            default_value: Some("PBS".into()),
+            ..Default::default()
+        }];
+        restore(&mut model, draft);
+        model.dirty = false;
+        update(
+            &mut model,
+            Msg::ExtraFields(ExtraFieldsMsg::ResetToDefault(0)),
+            &mut cmds,
+        );
+        assert_eq!(model.entry.extra_fields.fields()[0].value, "PBS");
+        assert!(model.dirty);
+
+        let tmp = TempDir::new().unwrap();
+        let gel = tmp.path().join("gel.png");
+        std::fs::write(&gel, b"png").unwrap();
+        restore(
+            &mut model,
+            sample_draft(vec![Attachment::new(
+                gel,
+                "gel.png".into(),
+                "image/png".into(),
+                "unavailable".into(),
+                3,
+            )]),
+        );
+        model.dirty = false;
+        update(
+            &mut model,
+            Msg::Attachments(AttachmentsMsg::SetInFigureList {
+                index: 0,
+                included: false,
+            }),
+            &mut cmds,
+        );
+        assert!(model.entry.attachments.attachments()[0].exclude_from_figures);
+        assert!(model.dirty);
+    }
+
+    #[test]
+    fn confirmed_table_removals_mark_entries_dirty() {
+        let tmp = TempDir::new().unwrap();
+        let data = tmp.path().join("data.csv");
+        std::fs::write(&data, b"1,2").unwrap();
+        let mut model = AppModel::default();
+        restore(
+            &mut model,
+            sample_draft(vec![Attachment::new(
+                data,
+                "data.csv".into(),
+                "text/csv".into(),
+                "unavailable".into(),
+                3,
+            )]),
+        );
+        model.dirty = false;
+        let mut cmds = Vec::new();
+
+        update(
+            &mut model,
+            Msg::Attachments(AttachmentsMsg::RequestRemove(0)),
+            &mut cmds,
+        );
+        assert!(!model.dirty, "asking changes nothing");
+        update(
+            &mut model,
+            Msg::Attachments(AttachmentsMsg::ConfirmRemove),
+            &mut cmds,
+        );
+        assert!(model.entry.attachments.attachments().is_empty());
+        assert!(model.dirty);
+    }
+
+    #[test]
+    fn signing_failures_report_an_error_but_keep_the_save() {
+        let mut model = AppModel::default();
+        let mut cmds = Vec::new();
+        update(&mut model, Msg::EntryTitleChanged("Run".into()), &mut cmds);
+        update(
+            &mut model,
+            Msg::ArchiveSigned {
+                archive: PathBuf::from("/tmp/out.eln"),
+                signature: Ok(PathBuf::from("/tmp/out.eln.sig")),
+            },
+            &mut cmds,
+        );
+        assert!(!model.dirty);
+        assert!(model.error.is_none());
+        assert!(
+            status_text(&model)
+                .unwrap()
+                .ends_with("Signed: /tmp/out.eln.sig"),
+            "{:?}",
+            model.status
+        );
+
+        update(
+            &mut model,
+            Msg::EntryTitleChanged("Run 2".into()),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            Msg::ArchiveSigned {
+                archive: PathBuf::from("/tmp/out.eln"),
+                signature: Err("Signing key is protected by a passphrase".into()),
+            },
+            &mut cmds,
+        );
+        assert!(!model.dirty, "the archive itself was saved");
+        let error = model.error.as_deref().unwrap();
+        assert!(error.starts_with("Archive saved: /tmp/out.eln"), "{error}");
+        assert!(error.contains("not signed"), "{error}");
+    }
+
+    #[test]
+    fn compression_override_applies_to_one_save_and_its_size_and_time_are_reported() {
+        let mut model = AppModel::default();
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            Msg::SetSaveCompression(Compression::Stored),
+            &mut cmds,
+        );
+        assert_eq!(
+            export_options(&model).unwrap().compression.method,
+            Compression::Stored
+        );
+
+        update(
+            &mut model,
+            Msg::SaveWritten {
+                size: 2048,
+                elapsed: Duration::from_millis(1300),
+            },
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            Msg::SaveCompleted(Ok(PathBuf::from("/tmp/out.eln"))),
+            &mut cmds,
+        );
+        let status = status_text(&model).unwrap();
+        assert!(status.contains("2.0 KB in 1.3 s"), "{status}");
+        assert_eq!(
+            model.compression, None,
+            "the next save uses the settings again"
+        );
+        assert_eq!(
+            export_options(&model).unwrap().compression,
+            CompressionOptions::default()
+        );
+    }
+
+    #[test]
+    fn saved_archives_are_uploaded_unless_skipped_and_closing_waits_for_it() {
+        let mut model = AppModel::default();
+        let mut settings = Settings::default();
+        settings.elabftw.url = "https://elab.example.org".into();
+        settings.elabftw.upload_after_save = true;
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            Msg::Settings(SettingsMsg::Loaded(Box::new(settings))),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            Msg::Settings(SettingsMsg::ApiKeyLoaded("3-secret".into())),
+            &mut cmds,
+        );
+
+        update(&mut model, Msg::SetUploadOnSave(false), &mut cmds);
+        update(
+            &mut model,
+            Msg::SaveCompleted(Ok(PathBuf::from("/tmp/out.eln"))),
+            &mut cmds,
+        );
+        assert!(
+            !cmds
+                .iter()
+                .any(|c| matches!(c, Command::UploadArchive { .. })),
+            "skipped for this save"
+        );
+        assert!(!model.skip_upload, "the next save uploads again");
+
+        update(&mut model, Msg::EntryTitleChanged("Run".into()), &mut cmds);
+        update(&mut model, Msg::CloseRequested, &mut cmds);
+        update(&mut model, Msg::SaveAndQuit, &mut cmds);
+        cmds.clear();
+        update(
+            &mut model,
+            Msg::SaveCompleted(Ok(PathBuf::from("/tmp/out.eln"))),
+            &mut cmds,
+        );
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::UploadArchive { path, target, .. }]
+                if path == Path::new("/tmp/out.eln") && target.api_key == "3-secret"
+        ));
+        assert_eq!(model.quit, QuitState::AwaitingUpload);
+        update(
+            &mut model,
+            Msg::UploadCompleted(Err("offline".into())),
+            &mut cmds,
+        );
+        assert_eq!(model.quit, QuitState::Idle, "failures stay visible");
+        assert!(model.error.as_deref().unwrap().contains("saved"));
+
+        model.uploading = true;
+        update(&mut model, Msg::CloseRequested, &mut cmds);
+        assert_eq!(model.quit, QuitState::AwaitingUpload);
+        update(
+            &mut model,
+            Msg::UploadCompleted(Ok(
+                "https://elab.example.org/experiments.php?mode=view&id=42".into(),
+            )),
+            &mut cmds,
+        );
+        assert_eq!(model.quit, QuitState::Ready);
+        assert!(status_text(&model).unwrap().contains("id=42"));
+        assert!(model.upload_progress.is_none());
+
+        // Cancelling an upload keeps the archive and lets a waiting close go ahead.
+        model.uploading = true;
+        model.quit = QuitState::AwaitingUpload;
+        let progress = UploadProgress {
+            bytes_sent: 1024,
+            bytes_total: 4096,
+        };
+        update(&mut model, Msg::UploadProgress(progress), &mut cmds);
+        assert_eq!(model.upload_progress, Some(progress));
+        cmds.clear();
+        update(&mut model, Msg::CancelUpload, &mut cmds);
+        assert!(matches!(cmds.as_slice(), [Command::CancelUpload]));
+        update(&mut model, Msg::UploadCancelled, &mut cmds);
+        assert!(!model.uploading);
+        assert!(model.upload_progress.is_none());
+        assert_eq!(model.quit, QuitState::Ready);
+        assert_eq!(status_text(&model), Some("Upload cancelled."));
+        update(&mut model, Msg::UploadProgress(progress), &mut cmds);
+        assert!(model.upload_progress.is_none(), "late progress is ignored");
+    }
+
+    #[test]
+    fn closing_with_unsaved_changes_waits_for_the_users_choice() {
+        let mut model = AppModel::default();
+        let mut cmds = Vec::new();
+        update(&mut model, Msg::CloseRequested, &mut cmds);
+        assert_eq!(model.quit, QuitState::Ready, "nothing to lose");
+
+        let mut model = AppModel {
+            draft_path: Some(PathBuf::from("/tmp/draft.json")),
+            ..Default::default()
+        };
+        update(&mut model, Msg::EntryTitleChanged("Run".into()), &mut cmds);
+        update(&mut model, Msg::CloseRequested, &mut cmds);
+        assert_eq!(model.quit, QuitState::Confirming);
+        update(&mut model, Msg::CancelQuit, &mut cmds);
+        assert_eq!(model.quit, QuitState::Idle);
+
+        // Save and quit closes only after the save succeeded.
+        update(&mut model, Msg::CloseRequested, &mut cmds);
+        update(&mut model, Msg::SaveAndQuit, &mut cmds);
+        update(&mut model, Msg::SaveCancelled, &mut cmds);
+        assert_eq!(model.quit, QuitState::Idle);
+        update(&mut model, Msg::SaveAndQuit, &mut cmds);
+        update(
+            &mut model,
+            Msg::SaveCompleted(Err("disk full".into())),
+            &mut cmds,
+        );
+        assert_eq!(model.quit, QuitState::Idle);
+        update(&mut model, Msg::SaveAndQuit, &mut cmds);
+        update(
+            &mut model,
+            Msg::SaveCompleted(Ok(PathBuf::from("/tmp/out.eln"))),
+            &mut cmds,
+        );
+        assert_eq!(model.quit, QuitState::Ready);
+
+        // Discard deletes the draft before closing.
+        model.quit = QuitState::Confirming;
+        cmds.clear();
+        update(&mut model, Msg::DiscardAndQuit, &mut cmds);
+        assert!(matches!(cmds.as_slice(), [Command::DeleteDraft { .. }]));
+        assert_eq!(model.quit, QuitState::AwaitingDraftDelete);
+        update(&mut model, Msg::DraftDeleted(Ok(())), &mut cmds);
+        assert_eq!(model.quit, QuitState::Ready);
     }
 
-    let body = model.markdown.text.trim().to_string();
+    #[test]
+    fn successful_save_adds_keywords_to_vocabulary() {
+        let mut model = AppModel {
+            vocabulary_path: Some(PathBuf::from("/tmp/keywords.json")),
+            ..Default::default()
+        };
+        keywords::update(
+            &mut model.entry.keywords,
+            KeywordsMsg::SuggestionsLoaded(vec!["Alpha".into()]),
+        );
+        keywords::update(
+            &mut model.entry.keywords,
+            KeywordsMsg::Restore(vec!["alpha".into(), "beta".into()]),
+        );
+        let mut cmds = Vec::new();
 
-    let keywords = Keywords::new(model.keywords.keywords().to_vec());
-    let performed_at = datetime_picker::to_offset_datetime(&model.datetime)
-        .map_err(|err| format!("Invalid date/time: {err}"))?;
+        update(
+            &mut model,
+            Msg::SaveCompleted(Ok(PathBuf::from("/tmp/out.eln"))),
+            &mut cmds,
+        );
 
-    let attachment_meta: Vec<Attachment> = model
-        .attachments
-        .attachments()
-        .iter()
-        .map(|a| a.to_domain())
-        .collect();
+        assert_eq!(model.entry.keywords.suggestions(), ["Alpha", "beta"]);
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::SaveVocabulary { keywords, .. }] if keywords == &["Alpha", "beta"]
+        ));
 
-    crate::models::attachment::assert_unique_sanitized_names(&attachment_meta)
-        .map_err(|e| e.to_string())?;
-
-    for field in model.extra_fields.fields() {
-        if let Some(err) = crate::models::extra_fields::validate_field(field) {
-            let msg = match err {
-                "required" => format!("Field '{}' is required.", field.label),
-                "invalid_url" => format!("Field '{}' must be a valid http/https URL.", field.label),
-                "invalid_number" => format!("Field '{}' must be a valid number.", field.label),
-                "invalid_integer" => format!("Field '{}' must be a valid integer ID.", field.label),
-                _ => format!("Field '{}' is invalid.", field.label),
-            };
-            return Err(msg);
-        }
+        cmds.clear();
+        update(
+            &mut model,
+            Msg::Settings(SettingsMsg::ClearKeywordVocabulary),
+            &mut cmds,
+        );
+        assert!(model.entry.keywords.suggestions().is_empty());
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::SaveVocabulary { keywords, .. }] if keywords.is_empty()
+        ));
     }
 
-    Ok(SavePayload {
-        output: output_path,
-        title,
-        body,
-        attachments: attachment_meta,
-        performed_at,
-        genre: model.archive_genre,
-        keywords: keywords.into_vec(),
-        extra_fields: model.extra_fields.fields().to_vec(),
-        extra_groups: model.extra_fields.groups().to_vec(),
-        body_format: model.body_format,
-    })
-}
+    #[test]
+    fn discard_draft_resets_form_and_keeps_draft_path() {
+        let tmp = TempDir::new().unwrap();
+        let draft_path = tmp.path().join("draft.json");
+        draft::save_draft(&draft_path, &sample_draft(Vec::new())).unwrap();
 
-#[cfg(test)]
-mod tests {
-    #![allow(clippy::field_reassign_with_default)]
+        let mut model = AppModel {
+            draft_path: Some(draft_path.clone()),
+            ..Default::default()
+        };
+        restore(&mut model, sample_draft(Vec::new()));
 
-    use super::*;
-    use crate::models::extra_fields::ExtraFieldKind;
-    use crate::ui::components::extra_fields::ExtraFieldsMsg;
-    use std::path::PathBuf;
-    use tempfile::TempDir;
+        let mut cmds = Vec::new();
+        update(&mut model, Msg::DiscardDraft, &mut cmds);
+
+        assert!(model.entry.title.is_empty());
+        assert!(!model.draft_restored);
+        assert_eq!(model.draft_path.as_deref(), Some(draft_path.as_path()));
+        let msg = run_command(cmds.pop().expect("delete command queued"));
+        assert!(matches!(msg, Msg::DraftDeleted(Ok(()))));
+        assert!(!draft_path.exists());
+    }
 
     #[test]
-    fn save_request_enqueues_and_completes() {
+    fn opening_saved_archive_restores_entry() {
         let tmp = TempDir::new().unwrap();
-        let output = tmp.path().join("test.eln");
-
+        let output = tmp.path().join("entry.eln");
         let mut model = AppModel::default();
-        model.entry_title = "Title".into();
-        model.markdown.text = "Body".into();
-
+        model.entry.title = "Reopened".into();
+        model.entry.markdown.text = "Body".into();
+        model.body_format = crate::logic::eln::BodyFormat::Markdown;
+        update(
+            &mut model,
+            Msg::Keywords(KeywordsMsg::Restore(vec!["kw".into()])),
+            &mut Vec::new(),
+        );
         let mut cmds = Vec::new();
         update(&mut model, Msg::SaveRequested(output.clone()), &mut cmds);
+        run_command(cmds.pop().unwrap());
 
-        assert_eq!(cmds.len(), 1, "save should enqueue command");
+        let mut reopened = AppModel::default();
+        let mut cmds = Vec::new();
+        update(&mut reopened, Msg::OpenArchiveRequested(output), &mut cmds);
+        let msg = run_command(cmds.pop().expect("open command queued"));
+        update(&mut reopened, msg, &mut Vec::new());
 
-        let msg = run_command(cmds.pop().unwrap());
-        let mut cmds2 = Vec::new();
-        update(&mut model, msg, &mut cmds2);
+        assert!(reopened.error.is_none(), "{:?}", reopened.error);
+        assert_eq!(reopened.entry.title, "Reopened");
+        assert_eq!(reopened.entry.markdown.text, "Body");
+        assert_eq!(reopened.entry.keywords.keywords(), ["kw".to_string()]);
+        assert_eq!(reopened.entry.datetime, model.entry.datetime);
+    }
 
-        assert!(model.error.is_none());
-        assert!(
-            model
-                .status
-                .as_deref()
-                .map(|s| s.contains("Archive saved"))
-                .unwrap_or(false)
-        );
-        assert!(output.exists());
+    /// Folder a queued [`Command::RemoveExtracted`] deletes.
+    fn removed_dir(cmds: &[Command]) -> Option<PathBuf> {
+        cmds.iter().find_map(|cmd| match cmd {
+            Command::RemoveExtracted(dir) => Some(dir.clone()),
+            _ => None,
+        })
     }
 
     #[test]
-    fn save_request_with_empty_title_sets_error() {
+    fn opening_over_unsaved_changes_asks_and_deletes_extracted_files() {
+        let tmp = TempDir::new().unwrap();
+        let output = tmp.path().join("entry.eln");
+        let mut saved = AppModel::default();
+        saved.entry.title = "Saved".into();
+        let mut cmds = Vec::new();
+        update(&mut saved, Msg::SaveRequested(output.clone()), &mut cmds);
+        run_command(cmds.pop().unwrap());
+
         let mut model = AppModel::default();
-        model.entry_title = "   ".into();
+        let open = |model: &mut AppModel, cmds: &mut Vec<Command>| {
+            cmds.clear();
+            update(model, Msg::OpenArchiveRequested(output.clone()), cmds);
+            let msg = run_command(cmds.pop().expect("open command queued"));
+            update(model, msg, cmds);
+        };
+        update(
+            &mut model,
+            Msg::EntryTitleChanged("Unsaved".into()),
+            &mut cmds,
+        );
+        open(&mut model, &mut cmds);
+        assert_eq!(model.entry.title, "Unsaved", "kept until the user agrees");
+        let pending = model.open_confirmation.as_ref().expect("asks first");
+        let cancelled_dir = pending.extract_dir.clone();
+        update(&mut model, Msg::CancelOpen, &mut cmds);
+        assert_eq!(removed_dir(&cmds).as_ref(), Some(&cancelled_dir));
+        let msg = run_command(Command::RemoveExtracted(cancelled_dir.clone()));
+        assert!(matches!(msg, Msg::ExtractedRemoved(Ok(()))));
+        assert!(!cancelled_dir.exists());
+
+        open(&mut model, &mut cmds);
+        update(&mut model, Msg::ConfirmOpen, &mut cmds);
+        assert_eq!(model.entry.title, "Saved");
+        assert!(!model.dirty);
+        let first_dir = model.open_dir.clone().expect("extracted files are tracked");
+
+        open(&mut model, &mut cmds);
+        assert!(model.open_confirmation.is_none(), "nothing to lose");
+        assert_eq!(removed_dir(&cmds).as_ref(), Some(&first_dir));
+        assert_ne!(model.open_dir.as_ref(), Some(&first_dir));
+        run_command(Command::RemoveExtracted(first_dir));
+        run_command(Command::RemoveExtracted(model.open_dir.take().unwrap()));
+    }
 
+    #[test]
+    fn switching_entries_preserves_unsaved_edits() {
+        let mut model = AppModel::default();
         let mut cmds = Vec::new();
         update(
             &mut model,
-            Msg::SaveRequested(PathBuf::from("/tmp/ignored.eln")),
+            Msg::EntryTitleChanged("First".into()),
+            &mut cmds,
+        );
+        update(&mut model, Msg::AddEntry, &mut cmds);
+        update(
+            &mut model,
+            Msg::EntryTitleChanged("Second".into()),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            Msg::Keywords(KeywordsMsg::Restore(vec!["kw".into()])),
             &mut cmds,
         );
 
+        update(&mut model, Msg::SelectEntry(0), &mut cmds);
+        assert_eq!(model.entry.title, "First");
+        assert!(model.entry.keywords.keywords().is_empty());
+
+        update(&mut model, Msg::SelectEntry(1), &mut cmds);
+        assert_eq!(model.entry.title, "Second");
+        assert_eq!(model.entry.keywords.keywords(), ["kw"]);
+
+        update(&mut model, Msg::DuplicateEntry, &mut cmds);
+        let copy_title = t!("entries.copy_title", title = "Second");
+        assert_eq!(entry_count(&model), 3);
+        assert_eq!(model.active_entry, 2);
+        assert_eq!(model.entry.title, copy_title);
+        assert_eq!(model.entry.keywords.keywords(), ["kw"]);
+
+        update(&mut model, Msg::RemoveEntry(1), &mut cmds);
+        let titles: Vec<&str> = entries(&model).map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, ["First", copy_title.as_str()]);
+        assert_eq!(model.active_entry, 1);
+
+        update(&mut model, Msg::RemoveEntry(1), &mut cmds);
+        assert_eq!(model.entry.title, "First");
+        update(&mut model, Msg::RemoveEntry(0), &mut cmds);
+        assert_eq!(entry_count(&model), 1, "the last entry stays");
         assert!(cmds.is_empty());
-        assert!(model.error.is_some());
     }
 
     #[test]
-    fn save_cancelled_sets_status() {
+    fn entries_cannot_switch_while_commands_are_running() {
         let mut model = AppModel::default();
-        let mut cmds = Vec::new();
+        update(&mut model, Msg::AddEntry, &mut Vec::new());
+        model.pending_commands = 1;
 
-        update(&mut model, Msg::SaveCancelled, &mut cmds);
+        update(&mut model, Msg::SelectEntry(0), &mut Vec::new());
 
-        assert!(cmds.is_empty());
-        assert_eq!(model.status.as_deref(), Some("Save cancelled."));
-        assert!(model.error.is_none());
+        assert_eq!(model.active_entry, 1);
+        assert!(model.status.is_some());
     }
 
     #[test]
-    fn attachments_load_thumbnail_enqueues_command() {
+    fn save_validates_every_entry_and_names_the_offending_one() {
         let mut model = AppModel::default();
-        let mut cmds = Vec::new();
-        let path = PathBuf::from("image.png");
+        model.entry.title = "First".into();
+        update(&mut model, Msg::AddEntry, &mut Vec::new());
+        model.entry.title = "Second".into();
+        add_typed_field(&mut model, ExtraFieldKind::Number, "abc");
+        update(&mut model, Msg::SelectEntry(0), &mut Vec::new());
+
+        match validate_for_save(&model, PathBuf::from("/tmp/out.eln")) {
+            Err(SaveRejection::Invalid(err)) => assert_eq!(
+                err,
+                "Entry 2 (Second): Field 'Field' must be a valid number."
+            ),
+            _ => panic!("validation should fail for the second entry"),
+        }
 
+        update(&mut model, Msg::SelectEntry(1), &mut Vec::new());
         update(
             &mut model,
-            Msg::Attachments(AttachmentsMsg::LoadThumbnail(path.clone())),
-            &mut cmds,
+            Msg::ExtraFields(ExtraFieldsMsg::EditValue {
+                index: 0,
+                value: "4".into(),
+            }),
+            &mut Vec::new(),
         );
-
-        assert_eq!(cmds.len(), 1);
-        match cmds.pop().unwrap() {
-            Command::LoadThumbnail {
-                path: p,
-                _retry,
-                request_id: _,
-            } => {
-                assert_eq!(p, path);
-            }
-            _ => panic!("unexpected command"),
-        }
+        let payload = match validate_for_save(&model, PathBuf::from("/tmp/out.eln")) {
+            Ok(payload) => payload,
+            Err(_) => panic!("both entries are valid"),
+        };
+        let titles: Vec<&str> = payload.entries.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, ["First", "Second"]);
     }
 
     #[test]
-    fn pending_commands_can_track_attachment_flow() {
+    fn save_checks_authors_and_skips_blank_rows() {
         let mut model = AppModel::default();
-        let mut cmds = Vec::new();
-        let path = PathBuf::from("image.png");
+        model.entry.title = "Credited".into();
+        let author = |orcid: &str| Author {
+            family_name: "Carberry".into(),
+            orcid: orcid.into(),
+            ..Default::default()
+        };
+        update(
+            &mut model,
+            Msg::Authors(AuthorsMsg::Loaded(vec![
+                Author::default(),
+                author("0000-0002-1825-0098"),
+            ])),
+            &mut Vec::new(),
+        );
+
+        match validate_for_save(&model, PathBuf::from("/tmp/out.eln")) {
+            Err(SaveRejection::Invalid(err)) => {
+                assert!(err.starts_with("Author 2: ORCID iD"), "{err}")
+            }
+            _ => panic!("invalid ORCID must block saving"),
+        }
 
         update(
             &mut model,
-            Msg::Attachments(AttachmentsMsg::LoadThumbnail(path.clone())),
-            &mut cmds,
+            Msg::Authors(AuthorsMsg::Loaded(vec![
+                Author::default(),
+                author("https://orcid.org/0000-0002-1825-0097"),
+            ])),
+            &mut Vec::new(),
         );
+        let payload = match validate_for_save(&model, PathBuf::from("/tmp/out.eln")) {
+            Ok(payload) => payload,
+            Err(_) => panic!("authors are valid"),
+        };
+        assert_eq!(
+            payload.export_options.authors,
+            vec![author("0000-0002-1825-0097")]
+        );
+    }
 
-        assert_eq!(cmds.len(), 1, "load thumbnail should enqueue command");
+    #[test]
+    fn save_checks_related_links_and_skips_blank_rows() {
+        let mut model = AppModel::default();
+        model.entry.title = "Linked".into();
+        for msg in [
+            RelatedLinksMsg::Add,
+            RelatedLinksMsg::Add,
+            RelatedLinksMsg::Edit {
+                index: 1,
+                field: LinkField::Url,
+                value: "elab.example.org/experiments.php?id=6".into(),
+            },
+            RelatedLinksMsg::SetRelation {
+                index: 1,
+                relation: Relation::DerivedFrom,
+            },
+        ] {
+            update(&mut model, Msg::RelatedLinks(msg), &mut Vec::new());
+        }
+        assert!(model.dirty);
 
-        // UI increments when dispatching commands to the worker.
-        model.pending_commands += cmds.len();
-        assert_eq!(model.pending_commands, 1);
+        match validate_for_save(&model, PathBuf::from("/tmp/out.eln")) {
+            Err(SaveRejection::Invalid(err)) => {
+                assert!(err.starts_with("Related link 2: "), "{err}");
+                assert!(err.contains("http/https"), "{err}");
+            }
+            _ => panic!("invalid link URL must block saving"),
+        }
 
-        // Simulate worker response and UI decrement.
-        let mut cmds2 = Vec::new();
         update(
             &mut model,
-            Msg::Attachments(AttachmentsMsg::ThumbnailFailed { path }),
-            &mut cmds2,
+            Msg::RelatedLinks(RelatedLinksMsg::Edit {
+                index: 1,
+                field: LinkField::Url,
+                value: " https://elab.example.org/experiments.php?id=6 ".into(),
+            }),
+            &mut Vec::new(),
+        );
+        let payload = match validate_for_save(&model, PathBuf::from("/tmp/out.eln")) {
+            Ok(payload) => payload,
+            Err(_) => panic!("links are valid"),
+        };
+        assert_eq!(
+            payload.entries[0].related_links,
+            vec![RelatedLink {
+                label: String::new(),
+                url: "https://elab.example.org/experiments.php?id=6".into(),
+                relation: Relation::DerivedFrom,
+            }]
+        );
+        assert_eq!(
+            snapshot_draft(&model).related_links,
+            model.entry.related_links.links()
         );
-        model.pending_commands = model.pending_commands.saturating_sub(1);
-
-        assert_eq!(model.pending_commands, 0);
     }
 
     #[test]
-    fn thumbnail_failure_still_routes_through_attachment_messages() {
-        let msg = run_command(Command::LoadThumbnail {
-            path: PathBuf::from("missing.png"),
-            _retry: false,
-            request_id: 1,
-        });
+    fn chosen_license_is_checked_reported_and_becomes_default() {
+        let mut model = AppModel {
+            settings_path: Some(PathBuf::from("/tmp/settings.json")),
+            ..Default::default()
+        };
+        model.entry.title = "Licensed".into();
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            Msg::SetLicense(License::Url(String::new())),
+            &mut cmds,
+        );
+        assert!(cmds.is_empty(), "an empty URL is not remembered");
+        match validate_for_save(&model, PathBuf::from("/tmp/out.eln")) {
+            Err(SaveRejection::Invalid(err)) => assert!(err.contains("URL"), "{err}"),
+            _ => panic!("a custom license needs a URL"),
+        }
+
+        update(&mut model, Msg::SetLicense(License::Cc0), &mut cmds);
+        assert_eq!(model.settings.settings().defaults.license, License::Cc0);
+        let payload = match validate_for_save(&model, PathBuf::from("/tmp/out.eln")) {
+            Ok(payload) => payload,
+            Err(_) => panic!("preset licenses are valid"),
+        };
+        assert_eq!(payload.entries[0].license, License::Cc0);
+        assert_eq!(snapshot_draft(&model).license, License::Cc0);
 
-        assert!(matches!(msg, Msg::ThumbnailFailed { .. }));
+        update(
+            &mut model,
+            Msg::SaveCompleted(Ok(PathBuf::from("/tmp/out.eln"))),
+            &mut cmds,
+        );
+        assert!(
+            status_text(&model).unwrap().ends_with(". License: CC0-1.0"),
+            "{:?}",
+            model.status
+        );
+        update(&mut model, Msg::AddEntry, &mut cmds);
+        assert_eq!(model.entry.license, License::Cc0);
     }
 
     #[test]
-    fn thumbnail_available_message_clears_loading_state() {
+    fn summary_is_trimmed_limited_and_kept_in_the_draft() {
         let mut model = AppModel::default();
-        let path = PathBuf::from("image.png");
+        model.entry.title = "Run".into();
         let mut cmds = Vec::new();
-
         update(
             &mut model,
-            Msg::Attachments(AttachmentsMsg::LoadThumbnail(path.clone())),
+            Msg::SummaryChanged(format!("  {}  ", "a".repeat(501))),
             &mut cmds,
         );
-        assert!(model.attachments.is_thumbnail_loading(&path));
+        match validate_for_save(&model, PathBuf::from("/tmp/out.eln")) {
+            Err(SaveRejection::Invalid(err)) => assert!(err.contains("summary"), "{err}"),
+            _ => panic!("an over-long summary is rejected"),
+        }
 
         update(
             &mut model,
-            Msg::Attachments(AttachmentsMsg::ThumbnailAvailable { path: path.clone() }),
-            &mut Vec::new(),
+            Msg::SummaryChanged("  Viability after 72 h.\n".into()),
+            &mut cmds,
         );
+        let payload = match validate_for_save(&model, PathBuf::from("/tmp/out.eln")) {
+            Ok(payload) => payload,
+            Err(_) => panic!("a short summary is valid"),
+        };
+        assert_eq!(payload.entries[0].summary, "Viability after 72 h.");
 
-        assert!(!model.attachments.is_thumbnail_loading(&path));
+        let draft = snapshot_draft(&model);
+        let mut restored = AppModel::default();
+        restore(&mut restored, draft);
+        assert_eq!(restored.entry.summary, model.entry.summary);
     }
 
     #[test]
-    fn validate_rejects_invalid_url_field() {
+    fn draft_keeps_every_entry() {
         let mut model = AppModel::default();
-        model.entry_title = "Has URL".into();
-        model.markdown.text = "Body".into();
+        restore(&mut model, sample_draft(Vec::new()));
+        update(&mut model, Msg::AddEntry, &mut Vec::new());
+        model.entry.title = "Second".into();
 
-        add_url_field(&mut model, "htp://example");
+        let draft = snapshot_draft(&model);
+        assert_eq!(draft.title, "Draft title");
+        assert_eq!(draft.more_entries.len(), 1);
+        assert_eq!(draft.more_entries[0].title, "Second");
 
-        match validate_for_save(&model, PathBuf::from("/tmp/out.eln")) {
-            Err(err) => assert!(err.contains("valid http/https URL")),
-            Ok(_) => panic!("validation should fail for invalid URL"),
-        }
+        let mut restored = AppModel::default();
+        restore(&mut restored, draft.clone());
+        assert_eq!(entry_count(&restored), 2);
+        assert_eq!(restored.active_entry, 0);
+        assert_eq!(snapshot_draft(&restored), draft);
     }
 
     #[test]
-    fn validate_accepts_valid_url_field() {
+    fn opening_invalid_archive_shows_error() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("broken.eln");
+        std::fs::write(&path, b"not a zip").unwrap();
         let mut model = AppModel::default();
-        model.entry_title = "Has URL".into();
-        model.markdown.text = "Body".into();
-
-        add_url_field(&mut model, "https://example.com/path");
+        model.entry.title = "Keep me".into();
 
-        let res = validate_for_save(&model, PathBuf::from("/tmp/out.eln"));
+        let msg = run_command(Command::OpenArchive(path));
+        update(&mut model, msg, &mut Vec::new());
 
-        assert!(res.is_ok());
+        assert!(
+            model
+                .error
+                .as_deref()
+                .unwrap()
+                .contains("Failed to open archive")
+        );
+        assert_eq!(model.entry.title, "Keep me");
     }
 
     #[test]
-    fn validate_rejects_invalid_number_field() {
-        let mut model = AppModel::default();
-        model.entry_title = "Has number".into();
-        model.markdown.text = "Body".into();
+    fn dialogs_reopen_in_last_folder_and_choices_become_defaults() {
+        let mut model = AppModel {
+            settings_path: Some(PathBuf::from("/tmp/settings.json")),
+            ..Default::default()
+        };
+        let mut cmds = Vec::new();
 
-        add_typed_field(&mut model, ExtraFieldKind::Number, "abc");
+        update(
+            &mut model,
+            Msg::Attachments(AttachmentsMsg::FilesPicked(vec![PathBuf::from(
+                "/data/run7/scan.tif",
+            )])),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            Msg::SetBodyFormat(crate::logic::eln::BodyFormat::Markdown),
+            &mut cmds,
+        );
+        update(&mut model, Msg::SetGenre(ArchiveGenre::Resource), &mut cmds);
+        let saves = cmds
+            .iter()
+            .filter(|cmd| matches!(cmd, Command::SaveSettings { .. }))
+            .count();
+        assert_eq!(saves, 3);
+        assert_eq!(
+            favorite_dir(&model, PickerKind::Attachments),
+            Some(PathBuf::from("/data/run7"))
+        );
+        assert_eq!(favorite_dir(&model, PickerKind::Archive), None);
 
-        match validate_for_save(&model, PathBuf::from("/tmp/out.eln")) {
-            Err(err) => assert!(err.contains("valid number")),
-            Ok(_) => panic!("validation should fail for invalid number"),
-        }
+        update(&mut model, Msg::DiscardDraft, &mut Vec::new());
+        assert_eq!(model.body_format, crate::logic::eln::BodyFormat::Markdown);
+        assert_eq!(model.entry.genre, ArchiveGenre::Resource);
+        update(&mut model, Msg::AddEntry, &mut Vec::new());
+        assert_eq!(model.entry.genre, ArchiveGenre::Resource);
     }
 
     #[test]
-    fn validate_accepts_valid_number_field() {
-        let mut model = AppModel::default();
-        model.entry_title = "Has number".into();
-        model.markdown.text = "Body".into();
+    fn onboarding_rerun_updates_first_author_and_skip_still_persists() {
+        let mut model = AppModel {
+            settings_path: Some(PathBuf::from("/tmp/settings.json")),
+            authors_path: Some(PathBuf::from("/tmp/authors.json")),
+            ..Default::default()
+        };
+        let mut cmds = Vec::new();
+        let existing = Author {
+            given_name: "Lise".into(),
+            family_name: "Meitner".into(),
+            email: "lise@example.org".into(),
+            ..Default::default()
+        };
+        update(
+            &mut model,
+            Msg::Authors(AuthorsMsg::Loaded(vec![existing])),
+            &mut cmds,
+        );
 
-        add_typed_field(&mut model, ExtraFieldKind::Number, "42.5");
+        update(&mut model, Msg::OpenOnboarding, &mut cmds);
+        assert!(model.onboarding.is_open());
+        assert_eq!(model.onboarding.answers().family_name, "Meitner");
+        update(
+            &mut model,
+            Msg::Onboarding(OnboardingMsg::Edit {
+                field: onboarding::OnboardingField::Orcid,
+                value: "0000-0002-1825-0097".into(),
+            }),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            Msg::Onboarding(OnboardingMsg::SetBodyFormat(
+                crate::logic::eln::BodyFormat::Markdown,
+            )),
+            &mut cmds,
+        );
+        for _ in 0..3 {
+            update(&mut model, Msg::Onboarding(OnboardingMsg::Next), &mut cmds);
+        }
 
-        let res = validate_for_save(&model, PathBuf::from("/tmp/out.eln"));
+        let first = &model.authors.authors()[0];
+        assert_eq!(first.orcid, "0000-0002-1825-0097");
+        assert_eq!(first.email, "lise@example.org");
+        assert_eq!(model.body_format, crate::logic::eln::BodyFormat::Markdown);
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::SaveAuthors { .. }, Command::SaveSettings { settings, .. }]
+                if settings.defaults.body_format == crate::logic::eln::BodyFormat::Markdown
+        ));
 
-        assert!(res.is_ok());
+        cmds.clear();
+        update(&mut model, Msg::OpenOnboarding, &mut cmds);
+        update(&mut model, Msg::Onboarding(OnboardingMsg::Skip), &mut cmds);
+        assert!(matches!(cmds.as_slice(), [Command::SaveSettings { .. }]));
+        assert!(!model.onboarding.is_open());
     }
 
     #[test]
-    fn validate_rejects_invalid_integer_field() {
+    fn pasted_image_is_attached_without_moving_the_dialog_folder() {
         let mut model = AppModel::default();
-        model.entry_title = "Has int".into();
-        model.markdown.text = "Body".into();
+        let mut cmds = Vec::new();
+        update(&mut model, Msg::PasteImage, &mut cmds);
+        assert!(cmds.is_empty());
 
-        add_typed_field(&mut model, ExtraFieldKind::Items, "12.3");
+        model.paste_dir = Some(PathBuf::from("/tmp/elnpack-pasted"));
+        update(&mut model, Msg::PasteImage, &mut cmds);
+        assert!(matches!(cmds.as_slice(), [Command::PasteImage { .. }]));
 
-        match validate_for_save(&model, PathBuf::from("/tmp/out.eln")) {
-            Err(err) => assert!(err.contains("valid integer ID")),
-            Ok(_) => panic!("validation should fail for invalid integer"),
-        }
+        cmds.clear();
+        let path = PathBuf::from("/tmp/elnpack-pasted/pasted-20250314-101500.png");
+        update(
+            &mut model,
+            Msg::ImagePasted(Ok((path.clone(), 145_408))),
+            &mut cmds,
+        );
+        assert_eq!(status_text(&model), Some("Pasted image added (142.0 KB)"));
+        assert!(matches!(cmds.as_slice(), [Command::HashFile { path: p, .. }] if *p == path));
+        assert_eq!(favorite_dir(&model, PickerKind::Attachments), None);
     }
 
     #[test]
-    fn validate_accepts_valid_integer_field() {
-        let mut model = AppModel::default();
-        model.entry_title = "Has int".into();
-        model.markdown.text = "Body".into();
-
-        add_typed_field(&mut model, ExtraFieldKind::Users, "12345");
+    fn hash_command_reports_progress_and_honours_cancel() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("stack.bin");
+        std::fs::write(&path, vec![0_u8; 3 * 1024 * 1024]).unwrap();
 
-        let res = validate_for_save(&model, PathBuf::from("/tmp/out.eln"));
+        let mut progress = Vec::new();
+        let msg = run_command_with_progress(
+            Command::HashFile {
+                path: path.clone(),
+                algorithms: vec![HashAlgorithm::Sha256, HashAlgorithm::Blake3],
+                _retry: false,
+                cancel: Arc::default(),
+            },
+            &RfdDialogs,
+            &mut |m| progress.push(m),
+        );
+        assert!(matches!(
+            msg,
+            Msg::Attachments(AttachmentsMsg::HashComputed { ref digests, .. })
+                if digests.keys().eq(&[HashAlgorithm::Sha256, HashAlgorithm::Blake3])
+        ));
+        assert!(matches!(
+            progress.last(),
+            Some(Msg::Attachments(AttachmentsMsg::HashProgress { bytes_done, total, .. }))
+                if bytes_done == total
+        ));
 
-        assert!(res.is_ok());
+        let cancel = Arc::new(AtomicBool::new(true));
+        let msg = run_command(Command::HashFile {
+            path,
+            algorithms: vec![HashAlgorithm::Sha256],
+            _retry: false,
+            cancel,
+        });
+        assert!(matches!(
+            msg,
+            Msg::Attachments(AttachmentsMsg::HashCancelled { .. })
+        ));
     }
 
     fn add_url_field(model: &mut AppModel, value: &str) {
@@ -676,8 +5927,8 @@ mod tests {
     /// ```rust,ignore
     /// let mut model = AppModel::default();
     /// add_typed_field(&mut model, ExtraFieldKind::Number, "42");
-    /// assert_eq!(model.extra_fields.fields.len(), 1);
-    /// assert_eq!(model.extra_fields.fields[0].value.as_deref(), Some("42"));
+    /// assert_eq!(model.entry.extra_fields.fields.len(), 1);
+    /// assert_eq!(model.entry.extra_fields.fields[0].value.as_deref(), Some("42"));
     /// ```
     fn add_typed_field(model: &mut AppModel, kind: ExtraFieldKind, value: &str) {
         let mut cmds = Vec::new();
@@ -0,0 +1,314 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Loading the files ELNPack persists between sessions, all of them at startup or one at a
+//! time in safe mode.
+//!
+//! Safe mode (`--safe-mode`, or Shift held while the window opens) starts with factory
+//! defaults and leaves every persisted file alone, so a file that breaks the app can be
+//! found by loading the files one by one from the safe mode banner.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use super::status::Severity;
+use super::{AppModel, Msg, surface_event};
+use crate::i18n::t;
+use crate::ui::components::authors::AuthorsMsg;
+use crate::ui::components::keywords::KeywordsMsg;
+use crate::ui::components::settings::SettingsMsg;
+use crate::ui::components::templates::TemplatesMsg;
+use crate::utils::storage::AppPaths;
+
+/// File ELNPack loads at startup.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PersistedFile {
+    Settings,
+    Vocabulary,
+    Authors,
+    Templates,
+    ApiKey,
+    Draft,
+}
+
+impl PersistedFile {
+    /// Every persisted file, in loading order; the draft comes last so it wins over the
+    /// defaults from the settings.
+    pub const ALL: [Self; 6] = [
+        Self::Settings,
+        Self::Vocabulary,
+        Self::Authors,
+        Self::Templates,
+        Self::ApiKey,
+        Self::Draft,
+    ];
+
+    /// Name shown in the safe mode banner.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Settings => t!("persisted.settings"),
+            Self::Vocabulary => t!("persisted.vocabulary"),
+            Self::Authors => t!("authors.title"),
+            Self::Templates => t!("persisted.templates"),
+            Self::ApiKey => t!("persisted.api_key"),
+            Self::Draft => t!("persisted.draft"),
+        }
+    }
+
+    /// Start of the status message when the file cannot be loaded.
+    fn failure(self) -> &'static str {
+        match self {
+            Self::Settings => t!("persisted.settings.failed"),
+            Self::Vocabulary => t!("persisted.vocabulary.failed"),
+            Self::Authors => t!("persisted.authors.failed"),
+            Self::Templates => t!("persisted.templates.failed"),
+            Self::ApiKey => t!("persisted.api_key.failed"),
+            Self::Draft => t!("persisted.draft.failed"),
+        }
+    }
+
+    /// Location of the file in `paths`; `None` when it is not persisted.
+    pub fn path(self, paths: &AppPaths) -> Option<&Path> {
+        match self {
+            Self::Settings => paths.settings.as_deref(),
+            Self::Vocabulary => paths.vocabulary.as_deref(),
+            Self::Authors => paths.authors.as_deref(),
+            Self::Templates => paths.templates.as_deref(),
+            Self::ApiKey => paths.api_key.as_deref(),
+            Self::Draft => paths.draft.as_deref(),
+        }
+    }
+
+    /// Where `model` writes the file; `None` keeps it in memory only.
+    fn model_path(self, model: &mut AppModel) -> &mut Option<PathBuf> {
+        match self {
+            Self::Settings => &mut model.settings_path,
+            Self::Vocabulary => &mut model.vocabulary_path,
+            Self::Authors => &mut model.authors_path,
+            Self::Templates => &mut model.templates_path,
+            Self::ApiKey => &mut model.api_key_path,
+            Self::Draft => &mut model.draft_path,
+        }
+    }
+}
+
+/// Persisted files left alone in safe mode, and how loading them individually went.
+#[derive(Debug, Default)]
+pub struct SafeMode {
+    /// Locations the files would have been loaded from.
+    pub paths: AppPaths,
+    /// Files the user tried to load, with the error when that failed.
+    pub loaded: BTreeMap<PersistedFile, Result<(), String>>,
+}
+
+impl SafeMode {
+    /// Folder holding the settings, for the banner's "Open config folder" button.
+    pub fn config_dir(&self) -> Option<&Path> {
+        self.paths.settings.as_deref().and_then(Path::parent)
+    }
+}
+
+/// Read `file` from `path` and return the message that applies it, or `None` when there is
+/// nothing to apply, i.e. no draft was saved.
+///
+/// # Errors
+///
+/// Returns an error when the file exists but cannot be read or parsed.
+pub fn load_persisted(file: PersistedFile, path: &Path) -> Result<Option<Msg>> {
+    use crate::logic;
+
+    let msg = match file {
+        PersistedFile::Settings => Msg::Settings(SettingsMsg::Loaded(Box::new(
+            logic::settings::load_settings(path)?,
+        ))),
+        PersistedFile::Vocabulary => Msg::Keywords(KeywordsMsg::SuggestionsLoaded(
+            logic::vocabulary::load_vocabulary(path)?,
+        )),
+        PersistedFile::Authors => {
+            Msg::Authors(AuthorsMsg::Loaded(logic::authors::load_authors(path)?))
+        }
+        PersistedFile::Templates => Msg::Templates(TemplatesMsg::Loaded(
+            logic::templates::list_templates(path)?,
+        )),
+        PersistedFile::ApiKey => Msg::Settings(SettingsMsg::ApiKeyLoaded(
+            logic::elabftw::load_api_key(path)?,
+        )),
+        PersistedFile::Draft => match logic::draft::load_draft(path)? {
+            Some(draft) => Msg::DraftRestored(Box::new(draft)),
+            None => return Ok(None),
+        },
+    };
+    Ok(Some(msg))
+}
+
+/// Load every file at `paths` for `model`, queueing the messages that apply them in `inbox`.
+///
+/// Files that cannot be loaded are reported in the status bar and replaced by defaults; the
+/// next save of that kind overwrites them.
+pub fn load_all(model: &mut AppModel, paths: &AppPaths, inbox: &mut Vec<Msg>) {
+    for file in PersistedFile::ALL {
+        let Some(path) = file.path(paths) else {
+            continue;
+        };
+        match load_persisted(file, path) {
+            Ok(Some(msg)) => {
+                if let Msg::Settings(SettingsMsg::Loaded(loaded)) = &msg {
+                    // Applied here rather than via the inbox so a restored draft wins.
+                    model.body_format = loaded.defaults.body_format;
+                    model.entry.genre = loaded.defaults.genre;
+                    model.entry.license = loaded.defaults.license.clone();
+                }
+                inbox.push(msg);
+            }
+            Ok(None) => {}
+            Err(err) => surface_event(
+                model,
+                format!("{}: {err:#}", file.failure()),
+                Severity::Warning,
+            ),
+        }
+        *file.model_path(model) = Some(path.to_path_buf());
+    }
+}
+
+/// Start `model` in safe mode: nothing is loaded from `paths` and nothing written there until
+/// the user loads a file from the banner.
+pub fn start_safe(model: &mut AppModel, paths: AppPaths) {
+    for file in PersistedFile::ALL {
+        *file.model_path(model) = None;
+    }
+    model.safe_mode = Some(SafeMode {
+        paths,
+        loaded: BTreeMap::new(),
+    });
+    surface_event(model, t!("safe_mode.started").into(), Severity::Warning);
+}
+
+/// Apply the result of loading `file` from `path` in safe mode. Once loaded, the file is
+/// written again like outside safe mode.
+pub(super) fn persisted_loaded(
+    model: &mut AppModel,
+    file: PersistedFile,
+    path: PathBuf,
+    result: Result<Option<Box<Msg>>, String>,
+    cmds: &mut Vec<super::Command>,
+) {
+    let outcome = match result {
+        Ok(msg) => {
+            *file.model_path(model) = Some(path);
+            if let Some(msg) = msg {
+                super::update(model, *msg, cmds);
+            }
+            surface_event(
+                model,
+                t!("safe_mode.loaded_file", file = file.label()),
+                Severity::Success,
+            );
+            Ok(())
+        }
+        Err(err) => {
+            surface_event(
+                model,
+                format!("{}: {err}", file.failure()),
+                Severity::Warning,
+            );
+            Err(err)
+        }
+    };
+    if let Some(safe_mode) = &mut model.safe_mode {
+        safe_mode.loaded.insert(file, outcome);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mvu::{Command, run_command, update};
+    use tempfile::TempDir;
+
+    /// Paths of every persisted file in `dir`, each filled with `content`.
+    fn corrupted_paths(dir: &Path, content: &str) -> AppPaths {
+        let paths = AppPaths {
+            draft: Some(dir.join("draft.json")),
+            settings: Some(dir.join("settings.json")),
+            vocabulary: Some(dir.join("keywords.json")),
+            authors: Some(dir.join("authors.json")),
+            templates: Some(dir.join("templates")),
+            api_key: Some(dir.join("elabftw-api-key")),
+        };
+        for file in PersistedFile::ALL {
+            std::fs::write(file.path(&paths).unwrap(), content).unwrap();
+        }
+        paths
+    }
+
+    #[test]
+    fn corrupted_files_fall_back_to_defaults() {
+        let tmp = TempDir::new().unwrap();
+        let paths = corrupted_paths(tmp.path(), "{\"version\": [truncated");
+
+        let mut model = AppModel::default();
+        let mut inbox = Vec::new();
+        load_all(&mut model, &paths, &mut inbox);
+        let mut cmds = Vec::new();
+        for msg in inbox {
+            update(&mut model, msg, &mut cmds);
+        }
+
+        assert_eq!(model.settings.settings(), &Default::default());
+        assert!(model.authors.authors().is_empty());
+        assert_eq!(
+            model.draft_path, paths.draft,
+            "the next autosave replaces it"
+        );
+        let warnings: Vec<&str> = model
+            .status_history
+            .newest_first()
+            .filter(|status| status.severity == Severity::Warning)
+            .map(|status| status.text.as_str())
+            .collect();
+        // The API key file is plain text, so any content is a key.
+        assert_eq!(
+            warnings.len(),
+            PersistedFile::ALL.len() - 1,
+            "{warnings:#?}"
+        );
+    }
+
+    #[test]
+    fn safe_mode_loads_files_one_by_one() {
+        let tmp = TempDir::new().unwrap();
+        let paths = corrupted_paths(tmp.path(), "not json");
+        let curie = crate::models::author::Author {
+            family_name: "Curie".into(),
+            ..Default::default()
+        };
+        crate::logic::authors::save_authors(paths.authors.as_ref().unwrap(), &[curie]).unwrap();
+
+        let mut model = AppModel::default();
+        start_safe(&mut model, paths.clone());
+        assert_eq!(model.settings_path, None, "nothing is written in safe mode");
+        assert_eq!(model.draft_path, None);
+
+        let mut load = |file: PersistedFile| {
+            let mut cmds = Vec::new();
+            update(&mut model, Msg::LoadPersisted(file), &mut cmds);
+            let Some(command @ Command::LoadPersisted { .. }) = cmds.pop() else {
+                panic!("no load command");
+            };
+            assert!(cmds.is_empty());
+            update(&mut model, run_command(command), &mut Vec::new());
+        };
+        load(PersistedFile::Settings);
+        load(PersistedFile::Authors);
+
+        let safe_mode = model.safe_mode.as_ref().unwrap();
+        assert!(safe_mode.loaded[&PersistedFile::Settings].is_err());
+        assert_eq!(safe_mode.loaded[&PersistedFile::Authors], Ok(()));
+        assert_eq!(model.settings_path, None);
+        assert_eq!(model.authors_path, paths.authors);
+        assert_eq!(model.authors.authors()[0].family_name, "Curie");
+    }
+}
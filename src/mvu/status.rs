@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Status bar messages with a severity, and the history of recent ones.
+//!
+//! Informational and success messages fade from the status bar after
+//! [`STATUS_AUTO_CLEAR`]; warnings and errors stay until the next message. Every message is
+//! also kept in a [`StatusHistory`] of the last [`STATUS_HISTORY_LEN`].
+
+use std::collections::VecDeque;
+
+use time::{Duration, OffsetDateTime};
+
+/// Messages kept in the history; older ones are dropped first.
+pub const STATUS_HISTORY_LEN: usize = 50;
+
+/// How long informational and success messages stay in the status bar.
+pub const STATUS_AUTO_CLEAR: Duration = Duration::seconds(8);
+
+/// How important a status message is; only errors open the error modal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// Severity of a component event, which only tells errors from other messages.
+    pub fn from_error(is_error: bool) -> Self {
+        if is_error { Self::Error } else { Self::Info }
+    }
+
+    /// Whether messages of this severity leave the status bar on their own.
+    pub fn auto_clears(self) -> bool {
+        matches!(self, Self::Info | Self::Success)
+    }
+}
+
+/// One message shown in the status bar.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatusMessage {
+    pub text: String,
+    pub severity: Severity,
+    /// When the message was raised.
+    pub timestamp: OffsetDateTime,
+}
+
+impl StatusMessage {
+    /// Message raised now.
+    pub fn new(text: String, severity: Severity) -> Self {
+        Self {
+            text,
+            severity,
+            timestamp: OffsetDateTime::now_utc(),
+        }
+    }
+
+    /// Time left before the message leaves the status bar at `now`; `None` when it stays
+    /// until replaced.
+    pub fn remaining(&self, now: OffsetDateTime) -> Option<Duration> {
+        self.severity
+            .auto_clears()
+            .then(|| (self.timestamp + STATUS_AUTO_CLEAR - now).max(Duration::ZERO))
+    }
+
+    /// Whether the status bar still shows the message at `now`.
+    pub fn is_visible_at(&self, now: OffsetDateTime) -> bool {
+        self.remaining(now).is_none_or(Duration::is_positive)
+    }
+}
+
+/// The last [`STATUS_HISTORY_LEN`] messages.
+#[derive(Debug, Default)]
+pub struct StatusHistory {
+    messages: VecDeque<StatusMessage>,
+}
+
+impl StatusHistory {
+    /// Record `message`, dropping the oldest one when full.
+    pub fn push(&mut self, message: StatusMessage) {
+        if self.messages.len() == STATUS_HISTORY_LEN {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(message);
+    }
+
+    /// Recorded messages, newest first.
+    pub fn newest_first(&self) -> impl Iterator<Item = &StatusMessage> {
+        self.messages.iter().rev()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn message(severity: Severity) -> StatusMessage {
+        StatusMessage {
+            text: "Saved".into(),
+            severity,
+            timestamp: datetime!(2025-01-01 12:00 UTC),
+        }
+    }
+
+    #[test]
+    fn only_info_and_success_messages_clear_themselves() {
+        let at = datetime!(2025-01-01 12:00:05 UTC);
+        let later = datetime!(2025-01-01 12:00:09 UTC);
+        let success = message(Severity::Success);
+        assert_eq!(success.remaining(at), Some(Duration::seconds(3)));
+        assert!(success.is_visible_at(at));
+        assert!(!success.is_visible_at(later));
+        assert_eq!(success.remaining(later), Some(Duration::ZERO));
+
+        for severity in [Severity::Warning, Severity::Error] {
+            assert_eq!(message(severity).remaining(later), None);
+            assert!(message(severity).is_visible_at(later));
+        }
+    }
+
+    #[test]
+    fn history_keeps_the_newest_messages() {
+        let mut history = StatusHistory::default();
+        for i in 0..STATUS_HISTORY_LEN + 3 {
+            history.push(StatusMessage::new(format!("#{i}"), Severity::Info));
+        }
+        let texts: Vec<&str> = history.newest_first().map(|m| m.text.as_str()).collect();
+        assert_eq!(texts.len(), STATUS_HISTORY_LEN);
+        assert_eq!(texts[0], "#52");
+        assert_eq!(texts[STATUS_HISTORY_LEN - 1], "#3");
+    }
+}
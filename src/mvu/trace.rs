@@ -0,0 +1,241 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Ring buffer of recent messages for bug reports, enabled with `--debug-events`.
+//!
+//! Messages are recorded through their `Debug` representation with every string literal
+//! (titles, bodies, keywords, paths, error texts) replaced by its length, so a trace shows
+//! what happened without revealing what was typed. Thumbnail pixels are never recorded.
+
+use std::collections::VecDeque;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use super::Msg;
+use crate::ui::components::attachments::AttachmentsMsg;
+
+/// Number of messages kept; older ones are dropped first.
+pub const EVENT_TRACE_CAPACITY: usize = 1000;
+
+/// Longest recorded message description; longer ones are cut off.
+const MAX_EVENT_CHARS: usize = 2000;
+
+/// Recent messages, oldest first.
+#[derive(Debug, Default)]
+pub struct EventTrace {
+    events: VecDeque<TraceEvent>,
+    /// Messages dropped because the buffer was full.
+    dropped: usize,
+}
+
+/// One recorded message.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TraceEvent {
+    /// When the message was processed, as RFC 3339 in UTC.
+    pub at: String,
+    /// Redacted description of the message.
+    pub msg: String,
+}
+
+/// On-disk form of an exported trace.
+#[derive(Serialize)]
+struct TraceFile<'a> {
+    app_version: &'static str,
+    dropped: usize,
+    events: &'a VecDeque<TraceEvent>,
+}
+
+impl EventTrace {
+    /// Record `msg`; hashing progress ticks are skipped so they do not flush the buffer.
+    pub fn record(&mut self, msg: &Msg) {
+        self.record_at(msg, OffsetDateTime::now_utc());
+    }
+
+    fn record_at(&mut self, msg: &Msg, at: OffsetDateTime) {
+        if matches!(msg, Msg::Attachments(AttachmentsMsg::HashProgress { .. })) {
+            return;
+        }
+        if self.events.len() == EVENT_TRACE_CAPACITY {
+            self.events.pop_front();
+            self.dropped += 1;
+        }
+        self.events.push_back(TraceEvent {
+            at: at.format(&Rfc3339).unwrap_or_default(),
+            msg: redact(msg),
+        });
+    }
+
+    /// Serialize the trace as pretty-printed JSON for attaching to a bug report.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when serialization fails.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(&TraceFile {
+            app_version: env!("CARGO_PKG_VERSION"),
+            dropped: self.dropped,
+            events: &self.events,
+        })
+        .context("Failed to serialize event trace")
+    }
+}
+
+/// Describe `msg` with all text contents reduced to their length.
+pub fn redact(msg: &Msg) -> String {
+    let debug = match msg {
+        Msg::ThumbnailDecoded {
+            path,
+            request_id,
+            image,
+        } => format!(
+            "ThumbnailDecoded {{ path: {path:?}, request_id: {request_id}, image: {}x{} }}",
+            image.size[0], image.size[1]
+        ),
+        Msg::PreviewImageDecoded { path, image } => format!(
+            "PreviewImageDecoded {{ path: {path:?}, image: {}x{} }}",
+            image.size[0], image.size[1]
+        ),
+        other => format!("{other:?}"),
+    };
+    let mut redacted = redact_literals(&debug);
+    if let Some((cut, _)) = redacted.char_indices().nth(MAX_EVENT_CHARS) {
+        redacted.truncate(cut);
+        redacted.push('…');
+    }
+    redacted
+}
+
+/// Replace string literals in `Debug` output with `<N chars>` and char literals with `<char>`.
+fn redact_literals(debug: &str) -> String {
+    let mut out = String::with_capacity(debug.len());
+    let mut chars = debug.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                let mut len = 0;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => {
+                            // `\u{…}` and `\n`-style escapes stand for one character.
+                            if chars.next() == Some('u') {
+                                chars.by_ref().find(|c| *c == '}');
+                            }
+                            len += 1;
+                        }
+                        _ => len += 1,
+                    }
+                }
+                out.push_str(&format!("<{len} chars>"));
+            }
+            '\'' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\'' => break,
+                        '\\' => {
+                            chars.next();
+                        }
+                        _ => {}
+                    }
+                }
+                out.push_str("<char>");
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::logic::draft::{DRAFT_SCHEMA_VERSION, Draft};
+    use crate::models::license::License;
+    use crate::ui::components::keywords::KeywordsMsg;
+
+    #[test]
+    fn text_paths_and_pixels_are_redacted_to_lengths() {
+        let title = redact(&Msg::EntryTitleChanged("Secret \"compound\" X-17".into()));
+        assert_eq!(title, "EntryTitleChanged(<22 chars>)");
+
+        let pasted = redact(&Msg::ImagePasted(Ok((
+            PathBuf::from("/home/alice/pasted.png"),
+            2048,
+        ))));
+        assert!(!pasted.contains("alice"), "{pasted}");
+        assert!(pasted.contains("2048"), "sizes are kept: {pasted}");
+
+        let keyword = redact(&Msg::Keywords(KeywordsMsg::SuggestionPicked("tox".into())));
+        assert_eq!(keyword, "Keywords(SuggestionPicked(<3 chars>))");
+
+        let thumbnail = redact(&Msg::ThumbnailDecoded {
+            path: PathBuf::from("/data/gel.png"),
+            request_id: 4,
+            image: eframe::egui::ColorImage::from_rgba_unmultiplied([2, 1], &[9; 8]),
+        });
+        assert_eq!(
+            thumbnail,
+            "ThumbnailDecoded { path: <13 chars>, request_id: 4, image: 2x1 }"
+        );
+    }
+
+    #[test]
+    fn restored_drafts_record_no_body_or_title() {
+        let draft = Draft {
+            version: DRAFT_SCHEMA_VERSION,
+            title: "Unpublished result".into(),
+            genre: Default::default(),
+            body_format: Default::default(),
+            body: "The patient cohort showed…".into(),
+            keywords: vec!["confidential".into()],
+            extra_fields: Vec::new(),
+            extra_groups: Vec::new(),
+            extra_fields_source: None,
+            performed_date: "2025-03-14".into(),
+            performed_hour: 9,
+            performed_minute: 30,
+            attachments: Vec::new(),
+            language: String::new(),
+            related_links: Vec::new(),
+            license: License::None,
+            summary: String::new(),
+            hide_main_text: false,
+            more_entries: Vec::new(),
+        };
+        let redacted = redact(&Msg::DraftRestored(Box::new(draft)));
+        assert!(redacted.starts_with("DraftRestored("), "{redacted}");
+        for secret in ["Unpublished", "patient", "confidential"] {
+            assert!(!redacted.contains(secret), "{secret} leaked: {redacted}");
+        }
+    }
+
+    #[test]
+    fn buffer_keeps_the_newest_messages_and_skips_progress() {
+        let mut trace = EventTrace::default();
+        let at = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        for _ in 0..EVENT_TRACE_CAPACITY {
+            trace.record_at(&Msg::DismissError, at);
+        }
+        trace.record_at(&Msg::AddEntry, at);
+        trace.record_at(
+            &Msg::Attachments(AttachmentsMsg::HashProgress {
+                path: PathBuf::from("/data/big.bin"),
+                bytes_done: 1,
+                total: 2,
+            }),
+            at,
+        );
+
+        assert_eq!(trace.events.len(), EVENT_TRACE_CAPACITY);
+        assert_eq!(trace.events.back().unwrap().msg, "AddEntry");
+
+        let json: serde_json::Value = serde_json::from_str(&trace.to_json().unwrap()).unwrap();
+        assert_eq!(json["dropped"], 1);
+        assert_eq!(json["events"][0]["at"], "2023-11-14T22:13:20Z");
+    }
+}
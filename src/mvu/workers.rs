@@ -0,0 +1,163 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Threads that run commands for the UI shell.
+//!
+//! File dialogs block the thread that shows them until the user answers, so they run on a
+//! thread of their own. Everything else (hashing, thumbnails, saving) shares a pool that an
+//! open dialog can never occupy.
+
+use std::sync::Arc;
+
+use crossbeam_channel::{Receiver, Sender};
+
+use super::dialogs::DialogProvider;
+use super::{Command, CommandKind, Msg, run_command_with_progress};
+
+/// Channels to the dialog thread and the background pool.
+pub struct Workers {
+    background_tx: Sender<Command>,
+    dialog_tx: Sender<Command>,
+    /// Results of background commands; each one completes a pending command.
+    pub results: Receiver<Msg>,
+    /// Results of dialog commands, which are not counted as pending work.
+    pub dialog_results: Receiver<Msg>,
+    /// Intermediate progress messages; these do not complete a command.
+    pub progress: Receiver<Msg>,
+}
+
+impl Workers {
+    /// Start `threads` background workers and one dialog thread showing `dialogs`.
+    pub fn spawn(threads: usize, dialogs: Arc<dyn DialogProvider>) -> Self {
+        let (background_tx, background_rx) = crossbeam_channel::unbounded::<Command>();
+        let (dialog_tx, dialog_rx) = crossbeam_channel::unbounded::<Command>();
+        let (result_tx, results) = crossbeam_channel::unbounded::<Msg>();
+        let (dialog_result_tx, dialog_results) = crossbeam_channel::unbounded::<Msg>();
+        let (progress_tx, progress) = crossbeam_channel::unbounded::<Msg>();
+
+        for _ in 0..threads.max(1) {
+            spawn_worker(
+                background_rx.clone(),
+                result_tx.clone(),
+                progress_tx.clone(),
+                Arc::clone(&dialogs),
+            );
+        }
+        spawn_worker(dialog_rx, dialog_result_tx, progress_tx, dialogs);
+
+        Self {
+            background_tx,
+            dialog_tx,
+            results,
+            dialog_results,
+            progress,
+        }
+    }
+
+    /// Queue `cmd` on the thread matching its kind.
+    ///
+    /// Returns the kind it was queued as, or `None` when the workers are gone.
+    pub fn send(&self, cmd: Command) -> Option<CommandKind> {
+        let kind = cmd.kind();
+        let tx = match kind {
+            CommandKind::Dialog => &self.dialog_tx,
+            CommandKind::Background => &self.background_tx,
+        };
+        tx.send(cmd).ok().map(|()| kind)
+    }
+}
+
+fn spawn_worker(
+    commands: Receiver<Command>,
+    results: Sender<Msg>,
+    progress: Sender<Msg>,
+    dialogs: Arc<dyn DialogProvider>,
+) {
+    std::thread::spawn(move || {
+        for cmd in commands.iter() {
+            let msg = run_command_with_progress(cmd, &*dialogs, &mut |update| {
+                let _ = progress.send(update);
+            });
+            let _ = results.send(msg);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::mvu::AttachmentsMsg;
+    use crate::mvu::dialogs::FileFilter;
+    use crate::utils::hash::HashAlgorithm;
+
+    /// Dialogs that stay open until the test answers them.
+    struct HeldDialogs {
+        answers: Receiver<Vec<PathBuf>>,
+    }
+
+    impl DialogProvider for HeldDialogs {
+        fn pick_files(&self, _: &str, _: Option<&Path>) -> Vec<PathBuf> {
+            self.answers.recv().unwrap_or_default()
+        }
+
+        fn pick_folder(&self, _: &str, _: Option<&Path>) -> Option<PathBuf> {
+            self.answers.recv().ok()?.into_iter().next()
+        }
+
+        fn pick_file_with_filter(
+            &self,
+            _: &str,
+            _: Option<&Path>,
+            _: FileFilter,
+        ) -> Option<PathBuf> {
+            self.answers.recv().ok()?.into_iter().next()
+        }
+
+        fn save_file(&self, _: &str, _: Option<&Path>, _: FileFilter, _: &str) -> Option<PathBuf> {
+            self.answers.recv().ok()?.into_iter().next()
+        }
+    }
+
+    #[test]
+    fn hashing_proceeds_while_a_dialog_is_open() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("gel.tif");
+        std::fs::write(&file, b"lane 4").unwrap();
+        let (answer_tx, answers) = crossbeam_channel::unbounded();
+        // A single background worker: it would be taken by the dialog if they shared it.
+        let workers = Workers::spawn(1, Arc::new(HeldDialogs { answers }));
+
+        let kind = workers.send(Command::PickFiles { start_dir: None });
+        assert_eq!(kind, Some(CommandKind::Dialog));
+        let kind = workers.send(Command::HashFile {
+            path: file.clone(),
+            algorithms: vec![HashAlgorithm::Sha256],
+            _retry: false,
+            cancel: Default::default(),
+        });
+        assert_eq!(kind, Some(CommandKind::Background));
+
+        let msg = workers.results.recv_timeout(Duration::from_secs(10));
+        assert!(
+            matches!(
+                msg,
+                Ok(Msg::Attachments(AttachmentsMsg::HashComputed { ref path, size: 6, .. }))
+                    if *path == file
+            ),
+            "hash should finish while the dialog is open: {msg:?}"
+        );
+        assert!(workers.dialog_results.try_recv().is_err());
+
+        answer_tx.send(vec![file.clone()]).unwrap();
+        let msg = workers.dialog_results.recv_timeout(Duration::from_secs(10));
+        assert!(
+            matches!(msg, Ok(Msg::Attachments(AttachmentsMsg::FilesPicked(ref files))) if *files == [file]),
+            "{msg:?}"
+        );
+    }
+}
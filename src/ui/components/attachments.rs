@@ -3,16 +3,45 @@
 
 //! Attachments panel refactored for MVU-style updates.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 
 use eframe::egui;
-use egui_extras::image::load_svg_bytes_with_size;
-use resvg::usvg::Options;
-
-use crate::models::attachment::Attachment;
+use egui_extras::{Column, TableBuilder};
+
+use time::OffsetDateTime;
+
+use super::datetime_picker::{self, DateTimeModel, DateTimeMsg};
+use super::{IconLabel, SectionTitle};
+use crate::i18n::t;
+use crate::models::attachment::{
+    Attachment, AttachmentPolicy, archive_path, is_reserved_archive_name, unreserved_name,
+};
+use crate::utils::file_preview::{self, FilePreview};
+use crate::utils::folder_scan::FolderScan;
+use crate::utils::hash::{Digests, HashAlgorithm};
+use crate::utils::sanitize_component::{
+    DiffSegment, SanitizeExplanation, explain_sanitization, sanitize_folder,
+};
+use crate::utils::svg::{MAX_SVG_BYTES, SvgError, render_svg};
 use crate::utils::{icon_for, sanitize_component};
 
+pub use crate::utils::folder_scan::MAX_FOLDER_FILES;
+
+/// Folders with more files than this ask for confirmation before hashing starts.
+const FOLDER_CONFIRM_THRESHOLD: usize = 200;
+
+/// Longest side of a raster image in the preview dialog; larger images are scaled down to
+/// stay within common GPU texture limits.
+const PREVIEW_MAX_SIDE: u32 = 4096;
+
+/// Side of the box SVG images are rendered into for the preview dialog.
+const PREVIEW_SVG_SIDE: u32 = 1024;
+
+/// Most thumbnails decoded at once; further requests wait in a queue so that adding a large
+/// folder does not decode hundreds of full-size images in parallel.
+pub const MAX_THUMBNAIL_LOADS: usize = 4;
+
 /// User-selected attachment with original path and sanitized display name.
 pub struct AttachmentItem {
     /// Original filesystem path to the attachment.
@@ -23,11 +52,104 @@ pub struct AttachmentItem {
     pub mime: String,
     /// SHA-256 digest of the file contents or `"unavailable"` on failure.
     pub sha256: String,
+    /// Digests besides SHA-256 chosen in the settings when the file was added.
+    pub digests: Digests,
     /// File size in bytes.
     pub size: u64,
+    /// Sanitized subdirectory inside the archive (empty for top-level files).
+    pub folder: String,
+    /// When the data was acquired; defaults to the file modification time.
+    pub acquired_at: Option<OffsetDateTime>,
+    /// Caption for the archive; `None` when blank.
+    pub description: Option<String>,
+    /// Whether an image is left out of the preview's figure list.
+    pub exclude_from_figures: bool,
+    /// Whether the file on disk still matches its recorded hash, as far as known.
+    pub verification: Verification,
+}
+
+/// What is known about an attachment's content relative to its recorded SHA-256.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Verification {
+    /// Restored from a draft or archive; not read in this session.
+    #[default]
+    Unverified,
+    /// Hashed when it was added.
+    VerifiedAtAdd(OffsetDateTime),
+    /// Read again (e.g. while saving) and still matching.
+    Reverified(OffsetDateTime),
+    /// Content no longer matches the recorded hash.
+    Changed,
+    /// The file could not be read.
+    Unreadable,
+}
+
+impl Verification {
+    /// Short badge text.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Unverified => t!("attachments.state.unverified"),
+            Self::VerifiedAtAdd(_) => t!("attachments.state.verified"),
+            Self::Reverified(_) => t!("attachments.state.reverified"),
+            Self::Changed => t!("attachments.state.changed"),
+            Self::Unreadable => t!("attachments.state.unreadable"),
+        }
+    }
+
+    fn color(self) -> egui::Color32 {
+        match self {
+            Self::Unverified => egui::Color32::from_gray(120),
+            Self::VerifiedAtAdd(_) | Self::Reverified(_) => egui::Color32::from_rgb(47, 158, 68),
+            Self::Changed => egui::Color32::from_rgb(224, 49, 49),
+            Self::Unreadable => egui::Color32::from_rgb(232, 89, 12),
+        }
+    }
+
+    /// Hover text explaining the state.
+    fn details(self) -> String {
+        let when = |at: OffsetDateTime| {
+            datetime_picker::from_offset_datetime(at)
+                .map(|local| datetime_picker::summary(&local))
+                .unwrap_or_default()
+        };
+        match self {
+            Self::Unverified => t!("attachments.state.unverified.details").into(),
+            Self::VerifiedAtAdd(at) => t!("attachments.state.verified.details", when = when(at)),
+            Self::Reverified(at) => t!("attachments.state.reverified.details", when = when(at)),
+            Self::Changed => t!("attachments.state.changed.details").into(),
+            Self::Unreadable => t!("attachments.state.unreadable.details").into(),
+        }
+    }
 }
 
 impl AttachmentItem {
+    /// Record the outcome of hashing at add time; a missing SHA-256 means the read failed.
+    pub fn hashed_at_add(&mut self, at: OffsetDateTime) {
+        self.verification = if self.sha256 == "unavailable" {
+            Verification::Unreadable
+        } else {
+            Verification::VerifiedAtAdd(at)
+        };
+    }
+
+    /// Record that the file was read again and matched its recorded hash.
+    ///
+    /// Files without a recorded hash and files already known to differ keep their state.
+    pub fn reverified(&mut self, at: OffsetDateTime) {
+        if self.sha256 != "unavailable" && self.verification != Verification::Changed {
+            self.verification = Verification::Reverified(at);
+        }
+    }
+
+    /// Record that reading the file failed, or that its content differs from the hash.
+    pub fn verification_failed(&mut self, unreadable: bool) {
+        self.verification = if unreadable {
+            Verification::Unreadable
+        } else {
+            Verification::Changed
+        };
+    }
+
     /// Convert into the domain attachment model used by archive logic.
     pub fn to_domain(&self) -> Attachment {
         Attachment::new(
@@ -37,30 +159,150 @@ impl AttachmentItem {
             self.sha256.clone(),
             self.size,
         )
+        .in_folder(self.folder.clone())
+        .with_acquired_at(self.acquired_at)
+        .with_description(self.description.clone())
+        .with_digests(self.digests.clone())
+        .excluded_from_figures(self.exclude_from_figures)
     }
+
+    /// Digest shown for `algorithm`, falling back to SHA-256 for files added before it was
+    /// chosen; returns the algorithm actually shown.
+    fn shown_digest(&self, algorithm: HashAlgorithm) -> (HashAlgorithm, &str) {
+        match self.digests.get(&algorithm) {
+            Some(digest) if algorithm != HashAlgorithm::Sha256 => (algorithm, digest),
+            _ => (HashAlgorithm::Sha256, &self.sha256),
+        }
+    }
+}
+
+/// File whose hash is still being computed on a worker thread.
+struct PendingHash {
+    path: PathBuf,
+    /// Sanitized archive subdirectory the file is added to.
+    folder: String,
+    bytes_done: u64,
+    total: u64,
 }
 
 /// MVU state for the attachments picker and thumbnail loading status.
 #[derive(Default)]
 pub struct AttachmentsModel {
     attachments: Vec<AttachmentItem>,
-    thumbnail_failures: HashSet<PathBuf>,
+    /// Picked files awaiting their hash, in pick order.
+    pending: Vec<PendingHash>,
+    /// Images whose thumbnail could not be shown, with the reason.
+    thumbnail_failures: HashMap<PathBuf, ThumbnailError>,
+    /// Thumbnails being decoded, at most [`MAX_THUMBNAIL_LOADS`].
     thumbnail_loading: HashSet<PathBuf>,
+    /// Thumbnails waiting for a free decode slot, in request order.
+    thumbnail_queue: VecDeque<PathBuf>,
     hashes: HashSet<String>,
     editing_index: Option<usize>,
     editing_buffer: String,
+    /// How sanitizing would change the edited name, shown until the user accepts it.
+    rename_review: Option<SanitizeExplanation>,
+    /// Large scanned folder waiting for the user to confirm adding it.
+    pending_folder: Option<FolderScan>,
+    /// Rows ticked for bulk actions; reset whenever the list changes.
+    selected: BTreeSet<usize>,
+    /// Last row toggled, used as the start of shift-click ranges.
+    selection_anchor: Option<usize>,
+    /// Open acquisition time editor, if any.
+    acquired_edit: Option<AcquiredEdit>,
+    /// Attachment shown in the preview dialog.
+    preview: Option<Preview>,
+    /// Blocked types and size warning applied to files as they are added.
+    policy: AttachmentPolicy,
+    /// Whether the panel shows cards or the table.
+    layout: AttachmentsLayout,
+    /// Column the table is sorted by; the archive order is unaffected.
+    table_sort: Option<TableSort>,
+    /// Table row highlighted for keyboard navigation.
+    focused: Option<usize>,
+    /// Row waiting for the user to confirm its removal.
+    pending_removal: Option<usize>,
+}
+
+/// How the attachments panel lays out its rows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AttachmentsLayout {
+    /// One card per file with every detail and control.
+    #[default]
+    Cards,
+    /// Compact rows with sortable, resizable columns.
+    Table,
+}
+
+/// Sortable column of the attachments table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableColumn {
+    Name,
+    Type,
+    Size,
+    Sha256,
+}
+
+/// Table order chosen by clicking a column header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TableSort {
+    pub column: TableColumn,
+    pub ascending: bool,
+}
+
+/// Attachment shown in the preview dialog; `content` is `None` while it loads.
+struct Preview {
+    path: PathBuf,
+    content: Option<Result<PreviewContent, String>>,
+}
+
+/// What the preview dialog shows for an attachment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PreviewContent {
+    /// Full-size image; the runtime shell holds its texture.
+    Image,
+    File(FilePreview),
+}
+
+/// Acquisition time being edited for one or more attachments.
+struct AcquiredEdit {
+    indices: Vec<usize>,
+    datetime: DateTimeModel,
 }
 
 /// Messages emitted by the attachments view.
+#[derive(Debug)]
 pub enum AttachmentsMsg {
     RequestPickFiles,
     FilesPicked(Vec<PathBuf>),
+    RequestPickFolder,
+    /// Files found below a picked folder.
+    FolderScanned(FolderScan),
+    FolderScanFailed(String),
+    /// Add a large folder after confirmation.
+    ConfirmFolder,
+    CancelFolder,
     LoadThumbnail(PathBuf),
     HashComputed {
         path: PathBuf,
-        sha256: String,
+        /// Digests of the requested algorithms; without SHA-256 when hashing failed.
+        digests: Digests,
         size: u64,
         mime: String,
+        /// File modification time, used as the default acquisition time.
+        modified: Option<OffsetDateTime>,
+    },
+    /// Streaming progress for a file that is still being hashed.
+    HashProgress {
+        path: PathBuf,
+        bytes_done: u64,
+        total: u64,
+    },
+    /// User aborted hashing a pending file.
+    CancelHash(PathBuf),
+    /// Worker confirmed that hashing stopped after a cancel request.
+    HashCancelled {
+        path: PathBuf,
     },
     ThumbnailAvailable {
         path: PathBuf,
@@ -68,19 +310,93 @@ pub enum AttachmentsMsg {
     /// Thumbnail load failed after runtime-shell request validation.
     ThumbnailFailed {
         path: PathBuf,
+        reason: ThumbnailError,
     },
+    /// Load a thumbnail again after a decode error.
+    RetryThumbnail(PathBuf),
+    /// Open the preview dialog for the attachment at this path.
+    OpenPreview(PathBuf),
+    PreviewReady {
+        path: PathBuf,
+        content: Result<PreviewContent, String>,
+    },
+    ClosePreview,
     Remove(usize),
+    /// Move the attachment one position up (earlier in the archive).
+    MoveUp(usize),
+    /// Move the attachment one position down.
+    MoveDown(usize),
+    /// Order attachments by folder, then file name, ignoring case.
+    SortByName,
     StartEdit(usize),
     EditInputChanged(String),
     CommitEdit,
+    /// Apply the edited name as sanitized after reviewing the changes.
+    AcceptSanitizedName,
+    /// Close the sanitizing review and keep editing the name.
+    ReviseName,
     CancelEdit,
+    /// Re-add attachments recorded in a draft without rehashing them.
+    Restore(Vec<Attachment>),
+    /// Saving read every attachment again and all matched their recorded hashes.
+    Reverified(OffsetDateTime),
+    /// Saving found the file at `path` unreadable or changed.
+    VerificationFailed {
+        path: PathBuf,
+        unreadable: bool,
+    },
+    /// Tick or untick a row for bulk actions.
+    ToggleSelected(usize),
+    /// Select the range from the selection anchor to this row (shift-click).
+    SelectRange(usize),
+    /// Open the acquisition time editor for these rows.
+    EditAcquired(Vec<usize>),
+    AcquiredEdited(DateTimeMsg),
+    CommitAcquired,
+    CancelAcquired,
+    /// Replace the caption of one attachment; blank text removes it.
+    SetDescription {
+        index: usize,
+        text: String,
+    },
+    /// Include an image in the preview's figure list, or leave it out.
+    SetInFigureList {
+        index: usize,
+        included: bool,
+    },
+    /// Switch between the card view and the table.
+    SetLayout(AttachmentsLayout),
+    /// Sort the table by this column, reversing the order if it already is.
+    SortTable(TableColumn),
+    /// Highlight a table row.
+    FocusRow(usize),
+    /// Move the table highlight to the next or previous row in table order.
+    MoveFocus {
+        down: bool,
+    },
+    /// Ask before removing this row.
+    RequestRemove(usize),
+    ConfirmRemove,
+    CancelRemove,
 }
 
 /// Side-effectful commands that can be run off the UI path.
 pub enum AttachmentsCommand {
     PickFiles,
-    HashFile { path: PathBuf },
-    LoadThumbnail { path: PathBuf },
+    PickFolder,
+    HashFile {
+        path: PathBuf,
+    },
+    CancelHash {
+        path: PathBuf,
+    },
+    LoadThumbnail {
+        path: PathBuf,
+    },
+    /// Read the start of a file, or decode an image, for the preview dialog.
+    LoadPreview {
+        path: PathBuf,
+    },
 }
 
 /// User-facing events for status/error surfaces.
@@ -93,19 +409,47 @@ pub struct AttachmentsEvent {
 }
 
 impl AttachmentsModel {
-    /// Current list of attachments in selection order.
+    /// Current list of attachments in archive order.
     pub fn attachments(&self) -> &[AttachmentItem] {
         &self.attachments
     }
 
+    /// Whether any picked file is still being hashed.
+    pub fn has_pending_hashes(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Indices of rows selected for bulk actions, in ascending order.
+    pub fn selected(&self) -> Vec<usize> {
+        self.selected.iter().copied().collect()
+    }
+
+    /// Apply `policy` to files added from now on; attachments already listed are kept.
+    pub fn set_policy(&mut self, policy: &AttachmentPolicy) {
+        if self.policy != *policy {
+            self.policy = policy.clone();
+        }
+    }
+
+    /// Path of the attachment shown in the preview dialog, if it is open.
+    pub fn preview_path(&self) -> Option<&Path> {
+        self.preview.as_ref().map(|preview| preview.path.as_path())
+    }
+
     /// Convenience helper for tests to add a path directly.
     #[cfg(test)]
     pub fn add_path(&mut self, path: PathBuf) -> bool {
         // Simulate hashing step to reuse validation path.
-        let sha256 = crate::utils::hash_file(&path).unwrap_or_else(|_| "unavailable".into());
+        let digests = crate::utils::hash_file(&path)
+            .map(|sha256| Digests::from([(HashAlgorithm::Sha256, sha256)]))
+            .unwrap_or_default();
         let size = path.metadata().map(|m| m.len()).unwrap_or(0);
         let mime = guess_mime(&path);
-        add_attachment_with_meta(self, path, sha256, size, mime)
+        let modified = file_modified(&path);
+        !matches!(
+            add_attachment_with_meta(self, path, String::new(), digests, size, mime, modified),
+            AddOutcome::Skipped
+        )
     }
 
     /// Convenience helper for tests to inspect thumbnail loading state.
@@ -113,6 +457,11 @@ impl AttachmentsModel {
     pub fn is_thumbnail_loading(&self, path: &Path) -> bool {
         self.thumbnail_loading.contains(path)
     }
+
+    /// Whether a thumbnail for `path` is being decoded or waits for a decode slot.
+    fn is_thumbnail_requested(&self, path: &Path) -> bool {
+        self.thumbnail_loading.contains(path) || self.thumbnail_queue.iter().any(|p| p == path)
+    }
 }
 
 /// Apply a message to the attachments model. Returns a user-facing event when relevant.
@@ -131,56 +480,213 @@ pub fn update(
                 return None;
             }
             for path in paths {
-                cmds.push(AttachmentsCommand::HashFile { path });
+                queue_hash(model, path, String::new(), cmds);
             }
             Some(AttachmentsEvent {
-                message: "Processing attachments...".into(),
+                message: t!("attachments.processing").into(),
                 is_error: false,
             })
         }
+        AttachmentsMsg::RequestPickFolder => {
+            cmds.push(AttachmentsCommand::PickFolder);
+            None
+        }
+        AttachmentsMsg::FolderScanned(scan) => {
+            if scan.truncated {
+                return Some(AttachmentsEvent {
+                    message: t!(
+                        "attachments.folder.too_large",
+                        folder = scan.root.display(),
+                        max = MAX_FOLDER_FILES
+                    ),
+                    is_error: true,
+                });
+            }
+            if scan.files.is_empty() {
+                return Some(AttachmentsEvent {
+                    message: t!("attachments.folder.empty", folder = scan.root.display()),
+                    is_error: false,
+                });
+            }
+            if scan.files.len() > FOLDER_CONFIRM_THRESHOLD {
+                model.pending_folder = Some(scan);
+                return None;
+            }
+            Some(queue_folder(model, scan, cmds))
+        }
+        AttachmentsMsg::FolderScanFailed(err) => Some(AttachmentsEvent {
+            message: t!("attachments.folder.failed", err = err),
+            is_error: true,
+        }),
+        AttachmentsMsg::ConfirmFolder => {
+            let scan = model.pending_folder.take()?;
+            Some(queue_folder(model, scan, cmds))
+        }
+        AttachmentsMsg::CancelFolder => {
+            model.pending_folder = None;
+            None
+        }
         AttachmentsMsg::LoadThumbnail(path) => {
             // Avoid queuing duplicate thumbnail loads.
-            if model.thumbnail_loading.insert(path.clone()) {
-                cmds.push(AttachmentsCommand::LoadThumbnail { path });
+            if !model.is_thumbnail_requested(&path) {
+                model.thumbnail_queue.push_back(path);
+                start_thumbnail_loads(model, cmds);
             }
             None
         }
         AttachmentsMsg::HashComputed {
             path,
-            sha256,
+            digests,
             size,
             mime,
+            modified,
         } => {
-            let added = add_attachment_with_meta(model, path, sha256, size, mime);
-            Some(AttachmentsEvent {
-                message: if added {
-                    "Attachment added".to_string()
-                } else {
-                    "Attachment skipped (duplicate or invalid)".to_string()
+            // Results for cancelled (no longer pending) files are dropped.
+            let index = model.pending.iter().position(|p| p.path == path)?;
+            let folder = model.pending.remove(index).folder;
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            if let Some(pattern) = model.policy.blocked_by(&name, &mime) {
+                return Some(AttachmentsEvent {
+                    message: t!("attachments.blocked", name = name, pattern = pattern),
+                    is_error: true,
+                });
+            }
+            let oversized = model.policy.is_oversized(size);
+            let outcome =
+                add_attachment_with_meta(model, path, folder, digests, size, mime, modified);
+            let mut event = match outcome {
+                AddOutcome::Added => AttachmentsEvent {
+                    message: t!("attachments.added").into(),
+                    is_error: false,
+                },
+                AddOutcome::Renamed { from, to } => AttachmentsEvent {
+                    message: t!("attachments.added_renamed", to = to, from = from),
+                    is_error: false,
+                },
+                AddOutcome::Skipped => AttachmentsEvent {
+                    message: t!("attachments.skipped").into(),
+                    is_error: true,
                 },
-                is_error: !added,
+            };
+            if oversized && !event.is_error {
+                if !event.message.ends_with('.') {
+                    event.message.push('.');
+                }
+                event.message.push(' ');
+                event.message.push_str(&t!(
+                    "attachments.oversized",
+                    name = name,
+                    size = format_bytes(size),
+                    limit = format_bytes(model.policy.warn_above_bytes().unwrap_or_default())
+                ));
+            }
+            Some(event)
+        }
+        AttachmentsMsg::HashProgress {
+            path,
+            bytes_done,
+            total,
+        } => {
+            if let Some(pending) = model.pending.iter_mut().find(|p| p.path == path) {
+                pending.bytes_done = bytes_done;
+                pending.total = total;
+            }
+            None
+        }
+        AttachmentsMsg::CancelHash(path) => {
+            let index = model.pending.iter().position(|p| p.path == path)?;
+            model.pending.remove(index);
+            cmds.push(AttachmentsCommand::CancelHash { path });
+            Some(AttachmentsEvent {
+                message: t!("attachments.cancelled").into(),
+                is_error: false,
             })
         }
+        AttachmentsMsg::HashCancelled { path } => {
+            let _ = path;
+            None
+        }
         AttachmentsMsg::ThumbnailAvailable { path } => {
             model.thumbnail_failures.remove(&path);
             model.thumbnail_loading.remove(&path);
+            start_thumbnail_loads(model, cmds);
             None
         }
         // Request validation happens in the UI runtime shell before this reducer runs.
-        AttachmentsMsg::ThumbnailFailed { path } => {
-            model.thumbnail_failures.insert(path.clone());
+        AttachmentsMsg::ThumbnailFailed { path, reason } => {
             model.thumbnail_loading.remove(&path);
+            model.thumbnail_failures.insert(path, reason);
+            start_thumbnail_loads(model, cmds);
             None
         }
-        AttachmentsMsg::Remove(index) => {
-            remove_attachment(model, index);
-            Some(AttachmentsEvent {
-                message: "Attachment removed".to_string(),
-                is_error: false,
-            })
+        AttachmentsMsg::RetryThumbnail(path) => {
+            // Unsupported formats fail the same way again.
+            if model
+                .thumbnail_failures
+                .get(&path)
+                .is_some_and(ThumbnailError::is_retryable)
+            {
+                model.thumbnail_failures.remove(&path);
+                update(model, AttachmentsMsg::LoadThumbnail(path), cmds);
+            }
+            None
+        }
+        AttachmentsMsg::OpenPreview(path) => {
+            if model.preview_path() != Some(path.as_path()) {
+                model.preview = Some(Preview {
+                    path: path.clone(),
+                    content: None,
+                });
+                cmds.push(AttachmentsCommand::LoadPreview { path });
+            }
+            None
+        }
+        AttachmentsMsg::PreviewReady { path, content } => {
+            // Results for a dialog that was closed or switched to another file are dropped.
+            if let Some(preview) = model.preview.as_mut().filter(|p| p.path == path) {
+                preview.content = Some(content);
+            }
+            None
+        }
+        AttachmentsMsg::ClosePreview => {
+            model.preview = None;
+            None
+        }
+        AttachmentsMsg::Remove(index) => remove_row(model, index, cmds),
+        AttachmentsMsg::MoveUp(index) => {
+            if index > 0 && index < model.attachments.len() {
+                let mut order: Vec<usize> = (0..model.attachments.len()).collect();
+                order.swap(index - 1, index);
+                reorder(model, &order);
+            }
+            None
+        }
+        AttachmentsMsg::MoveDown(index) => {
+            if index + 1 < model.attachments.len() {
+                let mut order: Vec<usize> = (0..model.attachments.len()).collect();
+                order.swap(index, index + 1);
+                reorder(model, &order);
+            }
+            None
+        }
+        AttachmentsMsg::SortByName => {
+            let mut order: Vec<usize> = (0..model.attachments.len()).collect();
+            order.sort_by_cached_key(|&index| {
+                let item = &model.attachments[index];
+                (
+                    item.folder.to_lowercase(),
+                    item.sanitized_name.to_lowercase(),
+                )
+            });
+            reorder(model, &order);
+            None
         }
         AttachmentsMsg::StartEdit(index) => {
             model.editing_index = Some(index);
+            model.rename_review = None;
             model.editing_buffer = model
                 .attachments
                 .get(index)
@@ -190,32 +696,289 @@ pub fn update(
         }
         AttachmentsMsg::EditInputChanged(text) => {
             model.editing_buffer = text;
+            model.rename_review = None;
+            None
+        }
+        AttachmentsMsg::CommitEdit => commit_filename_edit(model, false),
+        AttachmentsMsg::AcceptSanitizedName => commit_filename_edit(model, true),
+        AttachmentsMsg::ReviseName => {
+            model.rename_review = None;
             None
         }
-        AttachmentsMsg::CommitEdit => commit_filename_edit(model),
         AttachmentsMsg::CancelEdit => {
             model.editing_index = None;
             model.editing_buffer.clear();
+            model.rename_review = None;
+            None
+        }
+        AttachmentsMsg::Restore(items) => {
+            *model = AttachmentsModel::default();
+            for att in items {
+                if att.sha256 != "unavailable" {
+                    model.hashes.insert(att.sha256.clone());
+                }
+                model.attachments.push(AttachmentItem {
+                    path: att.path,
+                    sanitized_name: att.sanitized_name,
+                    mime: att.mime,
+                    sha256: att.sha256,
+                    digests: att.digests,
+                    size: att.size,
+                    folder: att.folder,
+                    acquired_at: att.acquired_at,
+                    description: att.description,
+                    exclude_from_figures: att.exclude_from_figures,
+                    verification: Verification::Unverified,
+                });
+            }
+            None
+        }
+        AttachmentsMsg::Reverified(at) => {
+            for item in &mut model.attachments {
+                item.reverified(at);
+            }
+            None
+        }
+        AttachmentsMsg::VerificationFailed { path, unreadable } => {
+            for item in model
+                .attachments
+                .iter_mut()
+                .filter(|item| item.path == path)
+            {
+                item.verification_failed(unreadable);
+            }
+            None
+        }
+        AttachmentsMsg::ToggleSelected(index) => {
+            if index < model.attachments.len() && !model.selected.remove(&index) {
+                model.selected.insert(index);
+            }
+            model.selection_anchor = Some(index);
+            None
+        }
+        AttachmentsMsg::SelectRange(index) => {
+            if index < model.attachments.len() {
+                let anchor = model.selection_anchor.unwrap_or(index);
+                model.selected.extend(anchor.min(index)..=anchor.max(index));
+                model.selection_anchor = Some(anchor);
+            }
+            None
+        }
+        AttachmentsMsg::EditAcquired(indices) => {
+            let indices: Vec<usize> = indices
+                .into_iter()
+                .filter(|&i| i < model.attachments.len())
+                .collect();
+            let first = *indices.first()?;
+            let datetime = model.attachments[first]
+                .acquired_at
+                .and_then(|at| datetime_picker::from_offset_datetime(at).ok())
+                .unwrap_or_default();
+            model.acquired_edit = Some(AcquiredEdit { indices, datetime });
+            None
+        }
+        AttachmentsMsg::AcquiredEdited(msg) => {
+            if let Some(edit) = model.acquired_edit.as_mut() {
+                datetime_picker::update(&mut edit.datetime, msg);
+            }
+            None
+        }
+        AttachmentsMsg::CommitAcquired => commit_acquired_edit(model),
+        AttachmentsMsg::CancelAcquired => {
+            model.acquired_edit = None;
+            None
+        }
+        AttachmentsMsg::SetDescription { index, text } => {
+            if let Some(item) = model.attachments.get_mut(index) {
+                item.description = (!text.trim().is_empty()).then_some(text);
+            }
+            None
+        }
+        AttachmentsMsg::SetLayout(layout) => {
+            model.layout = layout;
+            None
+        }
+        AttachmentsMsg::SortTable(column) => {
+            model.table_sort = Some(match model.table_sort {
+                Some(sort) if sort.column == column => TableSort {
+                    column,
+                    ascending: !sort.ascending,
+                },
+                _ => TableSort {
+                    column,
+                    ascending: true,
+                },
+            });
+            None
+        }
+        AttachmentsMsg::FocusRow(index) => {
+            model.focused = (index < model.attachments.len()).then_some(index);
+            None
+        }
+        AttachmentsMsg::MoveFocus { down } => {
+            let order = table_order(model);
+            let position = model
+                .focused
+                .and_then(|focused| order.iter().position(|&index| index == focused));
+            model.focused = match (position, down) {
+                (None, true) => order.first(),
+                (None, false) => order.last(),
+                (Some(position), true) => order.get(position + 1).or(order.get(position)),
+                (Some(position), false) => order.get(position.saturating_sub(1)),
+            }
+            .copied();
+            None
+        }
+        AttachmentsMsg::RequestRemove(index) => {
+            model.pending_removal = (index < model.attachments.len()).then_some(index);
+            None
+        }
+        AttachmentsMsg::ConfirmRemove => {
+            let index = model.pending_removal.take()?;
+            // Keep the highlight in place so that the next Delete removes the following row.
+            let order = table_order(model);
+            let next = order
+                .iter()
+                .position(|&row| row == index)
+                .and_then(|position| {
+                    order
+                        .get(position + 1)
+                        .or_else(|| order.get(position.checked_sub(1)?))
+                })
+                .copied();
+            let event = remove_row(model, index, cmds);
+            model.focused = next.map(|row| if row > index { row - 1 } else { row });
+            event
+        }
+        AttachmentsMsg::CancelRemove => {
+            model.pending_removal = None;
+            None
+        }
+        AttachmentsMsg::SetInFigureList { index, included } => {
+            if let Some(item) = model.attachments.get_mut(index) {
+                item.exclude_from_figures = !included;
+            }
             None
         }
     }
 }
 
+/// Title of the attachments section, e.g. `Attachments (12, 3.2 GB, 1 unreadable or changed)`.
+pub fn section_title(model: &AttachmentsModel) -> SectionTitle {
+    let items = model.attachments();
+    let mut counts = Vec::new();
+    if !items.is_empty() {
+        counts.push(items.len().to_string());
+        counts.push(format_bytes(items.iter().map(|item| item.size).sum()));
+    }
+    let broken = items
+        .iter()
+        .filter(|item| {
+            matches!(
+                item.verification,
+                Verification::Changed | Verification::Unreadable
+            )
+        })
+        .count();
+    SectionTitle {
+        name: t!("attachments.title"),
+        counts,
+        problems: (broken > 0).then(|| t!("attachments.broken", count = broken)),
+        blocker: model
+            .has_pending_hashes()
+            .then_some(t!("attachments.blocker")),
+    }
+}
+
 /// Render the attachments panel and return any messages triggered by user interaction.
+///
+/// `extra_controls` draws shell-owned widgets (the favorite-folder menu) next to the add buttons;
+/// each row shows its `primary_digest`.
 pub fn view(
     ui: &mut egui::Ui,
     model: &AttachmentsModel,
     textures: &HashMap<PathBuf, egui::TextureHandle>,
+    primary_digest: HashAlgorithm,
+    extra_controls: impl FnOnce(&mut egui::Ui),
 ) -> Vec<AttachmentsMsg> {
     let mut msgs = Vec::new();
 
-    let add_resp = ui.add(egui::Button::new(format!(
-        "{} Add files",
-        egui_phosphor::regular::PLUS
-    )));
-    let add_resp = add_resp.on_hover_text("Add files");
-    if add_resp.clicked() {
-        msgs.push(AttachmentsMsg::RequestPickFiles);
+    ui.horizontal(|ui| {
+        let add_resp = ui.add(egui::Button::new(format!(
+            "{} {}",
+            egui_phosphor::regular::PLUS,
+            t!("attachments.add_files")
+        )));
+        let add_resp = add_resp.on_hover_text(t!("attachments.add_files"));
+        if add_resp.clicked() {
+            msgs.push(AttachmentsMsg::RequestPickFiles);
+        }
+        if ui
+            .button(format!(
+                "{} {}",
+                egui_phosphor::regular::FOLDER_PLUS,
+                t!("attachments.add_folder")
+            ))
+            .on_hover_text(t!("attachments.add_folder.hover"))
+            .clicked()
+        {
+            msgs.push(AttachmentsMsg::RequestPickFolder);
+        }
+        extra_controls(ui);
+        let (icon, label, next) = match model.layout {
+            AttachmentsLayout::Cards => (
+                egui_phosphor::regular::TABLE,
+                t!("attachments.layout.table"),
+                AttachmentsLayout::Table,
+            ),
+            AttachmentsLayout::Table => (
+                egui_phosphor::regular::CARDS,
+                t!("attachments.layout.cards"),
+                AttachmentsLayout::Cards,
+            ),
+        };
+        if ui
+            .button(format!("{icon} {label}"))
+            .on_hover_text(t!("attachments.layout.hover"))
+            .clicked()
+        {
+            msgs.push(AttachmentsMsg::SetLayout(next));
+        }
+        if model.attachments.len() > 1
+            && ui
+                .button(format!(
+                    "{} {}",
+                    egui_phosphor::regular::SORT_ASCENDING,
+                    t!("attachments.sort")
+                ))
+                .on_hover_text(t!("attachments.sort.hover"))
+                .clicked()
+        {
+            msgs.push(AttachmentsMsg::SortByName);
+        }
+    });
+    render_folder_confirmation(ui.ctx(), model, &mut msgs);
+    render_acquired_editor(ui.ctx(), model, &mut msgs);
+    render_removal_confirmation(ui.ctx(), model, &mut msgs);
+
+    if !model.selected.is_empty() {
+        ui.horizontal(|ui| {
+            let count = model.selected.len();
+            if ui
+                .button(format!(
+                    "{} {}",
+                    egui_phosphor::regular::CLOCK,
+                    if count == 1 {
+                        t!("attachments.set_acquired.one").to_string()
+                    } else {
+                        t!("attachments.set_acquired.many", count = count)
+                    }
+                ))
+                .clicked()
+            {
+                msgs.push(AttachmentsMsg::EditAcquired(model.selected()));
+            }
+        });
     }
 
     ui.add_space(6.0);
@@ -226,12 +989,24 @@ pub fn view(
         .stroke(visuals.window_stroke())
         .inner_margin(8.0)
         .show(ui, |ui| {
-            if model.attachments.is_empty() {
+            if model.attachments.is_empty() && model.pending.is_empty() {
                 ui.label(
-                    egui::RichText::new("No attachments").color(egui::Color32::from_gray(150)),
+                    egui::RichText::new(t!("attachments.none"))
+                        .color(egui::Color32::from_gray(150)),
                 );
             } else {
-                render_attachment_list(ui, model, textures, &mut msgs);
+                match model.layout {
+                    AttachmentsLayout::Cards => {
+                        render_attachment_list(ui, model, textures, primary_digest, &mut msgs)
+                    }
+                    AttachmentsLayout::Table => {
+                        render_attachment_table(ui, model, textures, &mut msgs)
+                    }
+                }
+                if !model.attachments.is_empty() && !model.pending.is_empty() {
+                    ui.separator();
+                }
+                render_pending_list(ui, model, &mut msgs);
             }
         });
 
@@ -243,10 +1018,23 @@ fn render_attachment_list(
     ui: &mut egui::Ui,
     model: &AttachmentsModel,
     textures: &HashMap<PathBuf, egui::TextureHandle>,
+    primary_digest: HashAlgorithm,
     msgs: &mut Vec<AttachmentsMsg>,
 ) {
     for index in 0..model.attachments.len() {
-        let (sanitized_name, original_name, path, mime, sha, size) = {
+        let (
+            sanitized_name,
+            original_name,
+            path,
+            mime,
+            (algorithm, digest),
+            size,
+            folder,
+            acquired_at,
+            description,
+            in_figure_list,
+            verification,
+        ) = {
             let item = &model.attachments[index];
             let original_name = item
                 .path
@@ -258,12 +1046,30 @@ fn render_attachment_list(
                 original_name,
                 item.path.clone(),
                 item.mime.clone(),
-                item.sha256.clone(),
+                item.shown_digest(primary_digest),
                 item.size,
+                item.folder.clone(),
+                item.acquired_at,
+                item.description.clone().unwrap_or_default(),
+                !item.exclude_from_figures,
+                item.verification,
             )
         };
 
         ui.horizontal(|ui| {
+            let mut checked = model.selected.contains(&index);
+            if ui
+                .checkbox(&mut checked, "")
+                .on_hover_text(t!("attachments.select.hover"))
+                .clicked()
+            {
+                if ui.input(|i| i.modifiers.shift) {
+                    msgs.push(AttachmentsMsg::SelectRange(index));
+                } else {
+                    msgs.push(AttachmentsMsg::ToggleSelected(index));
+                }
+            }
+
             let icon_for_mime = icon_for(&mime, &path);
 
             if let Some(texture) = textures.get(&path) {
@@ -274,13 +1080,14 @@ fn render_attachment_list(
             } else {
                 let thumb_rect = ui.allocate_space(egui::vec2(96.0, 72.0)).1;
 
-                if is_image(&path) {
-                    if !model.thumbnail_failures.contains(&path)
-                        && !model.thumbnail_loading.contains(&path)
-                    {
+                if let Some(error) = model.thumbnail_failures.get(&path) {
+                    render_thumbnail_failure(ui, thumb_rect, &path, error, msgs);
+                } else if is_image(&path) {
+                    // Rows scrolled out of view request their thumbnail once they are shown.
+                    if ui.is_rect_visible(thumb_rect) && !model.is_thumbnail_requested(&path) {
                         msgs.push(AttachmentsMsg::LoadThumbnail(path.clone()));
                     }
-                    // Always show a placeholder while the image is loading or failed.
+                    // Show a placeholder while the image is loading.
                     render_placeholder_icon(ui, thumb_rect, icon_for_mime);
                 } else {
                     render_placeholder_icon(ui, thumb_rect, icon_for_mime);
@@ -299,52 +1106,161 @@ fn render_attachment_list(
                             )
                             .on_hover_cursor(egui::CursorIcon::Help)
                             .on_hover_text(format!(
-                                "Filename sanitized:\n{} {} {}",
+                                "{}\n{} {} {}",
+                                t!("attachments.sanitized"),
                                 original_name,
                                 egui_phosphor::regular::ARROW_RIGHT,
                                 sanitized_name
                             ));
                         }
 
-                        ui.label(sanitized_name.clone());
+                        if !folder.is_empty() {
+                            ui.label(
+                                egui::RichText::new(format!("{folder}/"))
+                                    .color(egui::Color32::from_gray(120)),
+                            );
+                        }
+                        let name = ui
+                            .add(egui::Button::new(sanitized_name.as_str()).frame(false))
+                            .on_hover_text(t!("attachments.name.hover"));
+                        if name.clicked() {
+                            msgs.push(AttachmentsMsg::StartEdit(index));
+                        } else if name.has_focus() {
+                            row_keys(&name, index, msgs);
+                        }
 
                         if ui
                             .button(
                                 egui::RichText::new(egui_phosphor::regular::PENCIL_SIMPLE)
                                     .color(egui::Color32::from_gray(140)),
                             )
-                            .on_hover_text("Edit filename")
+                            .icon_label(t!("attachments.edit_name"))
                             .clicked()
                         {
                             msgs.push(AttachmentsMsg::StartEdit(index));
                         }
                     }
                 });
+                if model.editing_index == Some(index)
+                    && let Some(review) = &model.rename_review
+                {
+                    render_rename_review(ui, review, msgs);
+                }
                 ui.label(
                     egui::RichText::new(path.to_string_lossy())
                         .small()
                         .color(egui::Color32::from_gray(102)),
                 );
                 ui.label(
-                    egui::RichText::new(format!("{} | sha256 {}", mime, sha))
-                        .small()
-                        .color(egui::Color32::from_gray(90)),
-                );
-                ui.label(
-                    egui::RichText::new(format_bytes(size))
+                    egui::RichText::new(format!("{} | {} {}", mime, algorithm.key(), digest))
                         .small()
                         .color(egui::Color32::from_gray(90)),
                 );
+                ui.horizontal(|ui| {
+                    ui.label(
+                        egui::RichText::new(format_bytes(size))
+                            .small()
+                            .color(egui::Color32::from_gray(90)),
+                    );
+                    if let Some(limit) = model
+                        .policy
+                        .warn_above_bytes()
+                        .filter(|limit| size > *limit)
+                    {
+                        ui.label(
+                            egui::RichText::new(egui_phosphor::regular::WARNING)
+                                .small()
+                                .color(egui::Color32::from_rgb(232, 89, 12)),
+                        )
+                        .on_hover_cursor(egui::CursorIcon::Help)
+                        .on_hover_text(t!(
+                            "attachments.oversized.hover",
+                            limit = format_bytes(limit)
+                        ));
+                    }
+                    ui.label(
+                        egui::RichText::new(verification.label())
+                            .small()
+                            .color(egui::Color32::WHITE)
+                            .background_color(verification.color()),
+                    )
+                    .on_hover_cursor(egui::CursorIcon::Help)
+                    .on_hover_text(verification.details());
+                });
+                ui.horizontal(|ui| {
+                    let acquired = acquired_at
+                        .and_then(|at| datetime_picker::from_offset_datetime(at).ok())
+                        .map(|local| {
+                            t!(
+                                "attachments.acquired",
+                                when = datetime_picker::summary(&local)
+                            )
+                        })
+                        .unwrap_or_else(|| t!("attachments.acquired.unknown").into());
+                    ui.label(
+                        egui::RichText::new(acquired)
+                            .small()
+                            .color(egui::Color32::from_gray(90)),
+                    );
+                    if ui
+                        .small_button(egui_phosphor::regular::CLOCK)
+                        .icon_label(t!("attachments.acquired.set"))
+                        .clicked()
+                    {
+                        msgs.push(AttachmentsMsg::EditAcquired(vec![index]));
+                    }
+                });
+                render_description(ui, index, &path, &description, msgs);
+                if mime.starts_with("image/") {
+                    let mut included = in_figure_list;
+                    if ui
+                        .checkbox(
+                            &mut included,
+                            egui::RichText::new(t!("attachments.figure")).small(),
+                        )
+                        .on_hover_text(t!("attachments.figure.hover"))
+                        .changed()
+                    {
+                        msgs.push(AttachmentsMsg::SetInFigureList { index, included });
+                    }
+                }
             });
 
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if ui
                     .button(egui::RichText::new(egui_phosphor::regular::TRASH_SIMPLE))
-                    .on_hover_text("Remove attached file")
+                    .icon_label(t!("attachments.remove.hover"))
                     .clicked()
                 {
                     msgs.push(AttachmentsMsg::Remove(index));
                 }
+                if ui
+                    .add_enabled(
+                        index + 1 < model.attachments.len(),
+                        egui::Button::new(egui_phosphor::regular::ARROW_DOWN).small(),
+                    )
+                    .icon_label(t!("attachments.move_down"))
+                    .clicked()
+                {
+                    msgs.push(AttachmentsMsg::MoveDown(index));
+                }
+                if ui
+                    .add_enabled(
+                        index > 0,
+                        egui::Button::new(egui_phosphor::regular::ARROW_UP).small(),
+                    )
+                    .icon_label(t!("attachments.move_up"))
+                    .clicked()
+                {
+                    msgs.push(AttachmentsMsg::MoveUp(index));
+                }
+                if ui
+                    .button(egui_phosphor::regular::MAGNIFYING_GLASS)
+                    .icon_label(t!("attachments.preview"))
+                    .clicked()
+                {
+                    msgs.push(AttachmentsMsg::OpenPreview(path.clone()));
+                }
             });
         });
 
@@ -354,8 +1270,557 @@ fn render_attachment_list(
     }
 }
 
-/// Inline filename edit UI with save/cancel controls.
-fn render_editing_filename(
+/// Render the attachments as a table in [`table_order`], with one row action menu per row.
+///
+/// Up and down move the highlight and Delete asks to remove the highlighted row, unless a text
+/// field has keyboard focus.
+fn render_attachment_table(
+    ui: &mut egui::Ui,
+    model: &AttachmentsModel,
+    textures: &HashMap<PathBuf, egui::TextureHandle>,
+    msgs: &mut Vec<AttachmentsMsg>,
+) {
+    const ROW_HEIGHT: f32 = 28.0;
+
+    let order = table_order(model);
+    let keyboard_free = model.editing_index.is_none()
+        && model.pending_removal.is_none()
+        && ui.memory(|memory| memory.focused().is_none());
+    if keyboard_free && let Some(focused) = model.focused {
+        ui.input(|input| {
+            if input.key_pressed(egui::Key::ArrowDown) {
+                msgs.push(AttachmentsMsg::MoveFocus { down: true });
+            }
+            if input.key_pressed(egui::Key::ArrowUp) {
+                msgs.push(AttachmentsMsg::MoveFocus { down: false });
+            }
+            if input.key_pressed(egui::Key::Delete) {
+                msgs.push(AttachmentsMsg::RequestRemove(focused));
+            }
+        });
+    }
+
+    if let Some(review) = &model.rename_review
+        && let Some(item) = model
+            .editing_index
+            .and_then(|index| model.attachments.get(index))
+    {
+        ui.label(t!("attachments.renaming", name = item.sanitized_name));
+        render_rename_review(ui, review, msgs);
+    }
+
+    let mut table = TableBuilder::new(ui)
+        .id_salt("attachments-table")
+        .striped(true)
+        .resizable(true)
+        .sense(egui::Sense::click())
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .max_scroll_height(480.0)
+        .column(Column::exact(ROW_HEIGHT))
+        .column(Column::initial(240.0).at_least(80.0).clip(true))
+        .column(Column::initial(140.0).at_least(60.0).clip(true))
+        .column(Column::initial(80.0).at_least(60.0))
+        .column(Column::initial(110.0).at_least(60.0).clip(true))
+        .column(Column::remainder().at_least(90.0));
+    if let Some(position) = model
+        .focused
+        .and_then(|focused| order.iter().position(|&index| index == focused))
+    {
+        table = table.scroll_to_row(position, None);
+    }
+
+    table
+        .header(22.0, |mut header| {
+            header.col(|_| {});
+            for (column, title) in [
+                (TableColumn::Name, t!("attachments.column.name")),
+                (TableColumn::Type, t!("attachments.column.type")),
+                (TableColumn::Size, t!("attachments.column.size")),
+                (TableColumn::Sha256, "SHA-256"),
+            ] {
+                header.col(|ui| {
+                    let arrow = match model.table_sort {
+                        Some(sort) if sort.column == column && sort.ascending => {
+                            egui_phosphor::regular::CARET_UP
+                        }
+                        Some(sort) if sort.column == column => egui_phosphor::regular::CARET_DOWN,
+                        _ => "",
+                    };
+                    if ui
+                        .add(
+                            egui::Button::new(
+                                egui::RichText::new(format!("{title} {arrow}")).strong(),
+                            )
+                            .frame(false),
+                        )
+                        .on_hover_text(t!("attachments.column.hover"))
+                        .clicked()
+                    {
+                        msgs.push(AttachmentsMsg::SortTable(column));
+                    }
+                });
+            }
+            header.col(|_| {});
+        })
+        .body(|body| {
+            body.rows(ROW_HEIGHT, order.len(), |mut row| {
+                let index = order[row.index()];
+                let item = &model.attachments[index];
+                row.set_selected(model.focused == Some(index));
+
+                row.col(|ui| {
+                    if let Some(texture) = textures.get(&item.path) {
+                        let size = texture.size_vec2();
+                        let scale = (ROW_HEIGHT - 4.0) / size.x.max(size.y);
+                        ui.add(egui::Image::new((texture.id(), size * scale.min(1.0))));
+                    } else {
+                        if is_image(&item.path)
+                            && !model.thumbnail_failures.contains_key(&item.path)
+                            && !model.is_thumbnail_requested(&item.path)
+                        {
+                            msgs.push(AttachmentsMsg::LoadThumbnail(item.path.clone()));
+                        }
+                        ui.label(icon_for(&item.mime, &item.path));
+                    }
+                });
+                row.col(|ui| {
+                    if model.editing_index == Some(index) {
+                        render_editing_filename(ui, model, msgs);
+                    } else {
+                        let name = if item.folder.is_empty() {
+                            item.sanitized_name.clone()
+                        } else {
+                            format!("{}/{}", item.folder, item.sanitized_name)
+                        };
+                        ui.add(egui::Label::new(name).truncate().selectable(false))
+                            .on_hover_text(item.path.to_string_lossy());
+                    }
+                });
+                row.col(|ui| {
+                    ui.add(egui::Label::new(&item.mime).truncate().selectable(false));
+                });
+                row.col(|ui| {
+                    ui.add(egui::Label::new(format_bytes(item.size)).selectable(false));
+                });
+                row.col(|ui| {
+                    let prefix: String = item.sha256.chars().take(12).collect();
+                    ui.add(
+                        egui::Label::new(egui::RichText::new(prefix).monospace()).selectable(false),
+                    )
+                    .on_hover_text(format!("SHA-256 {}", item.sha256));
+                });
+                row.col(|ui| {
+                    if ui
+                        .small_button(egui_phosphor::regular::MAGNIFYING_GLASS)
+                        .icon_label(t!("attachments.preview"))
+                        .clicked()
+                    {
+                        msgs.push(AttachmentsMsg::OpenPreview(item.path.clone()));
+                    }
+                    if ui
+                        .small_button(egui_phosphor::regular::PENCIL_SIMPLE)
+                        .icon_label(t!("attachments.edit_name"))
+                        .clicked()
+                    {
+                        msgs.push(AttachmentsMsg::StartEdit(index));
+                    }
+                    if ui
+                        .small_button(egui_phosphor::regular::TRASH_SIMPLE)
+                        .icon_label(t!("attachments.remove.hover"))
+                        .clicked()
+                    {
+                        msgs.push(AttachmentsMsg::RequestRemove(index));
+                    }
+                });
+
+                let response = row.response();
+                response.widget_info(|| {
+                    egui::WidgetInfo::selected(
+                        egui::WidgetType::Other,
+                        true,
+                        model.focused == Some(index),
+                        &item.sanitized_name,
+                    )
+                });
+                if response.clicked() || response.gained_focus() {
+                    msgs.push(AttachmentsMsg::FocusRow(index));
+                }
+                // Rows reached with Tab: Enter renames and Delete asks to remove.
+                if response.has_focus() {
+                    row_keys(&response, index, msgs);
+                }
+                response.context_menu(|ui| {
+                    if ui.button(t!("attachments.preview")).clicked() {
+                        msgs.push(AttachmentsMsg::OpenPreview(item.path.clone()));
+                        ui.close();
+                    }
+                    if ui.button(t!("attachments.rename")).clicked() {
+                        msgs.push(AttachmentsMsg::StartEdit(index));
+                        ui.close();
+                    }
+                    if ui.button(t!("attachments.remove_ellipsis")).clicked() {
+                        msgs.push(AttachmentsMsg::RequestRemove(index));
+                        ui.close();
+                    }
+                });
+            });
+        });
+}
+
+/// Keys on a focused attachment row: Enter starts renaming it, Delete asks to remove it.
+fn row_keys(response: &egui::Response, index: usize, msgs: &mut Vec<AttachmentsMsg>) {
+    response.ctx.input(|input| {
+        if input.key_pressed(egui::Key::Enter) {
+            msgs.push(AttachmentsMsg::StartEdit(index));
+        }
+        if input.key_pressed(egui::Key::Delete) {
+            msgs.push(AttachmentsMsg::RequestRemove(index));
+        }
+    });
+}
+
+/// Render the preview dialog when open; `texture` is the shell's full-size image, if any.
+pub fn preview_window(
+    ctx: &egui::Context,
+    model: &AttachmentsModel,
+    texture: Option<&egui::TextureHandle>,
+) -> Vec<AttachmentsMsg> {
+    let mut msgs = Vec::new();
+    let Some(preview) = &model.preview else {
+        return msgs;
+    };
+    let name = preview
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut open = true;
+    egui::Window::new(t!("attachments.preview.title", name = name))
+        .id(egui::Id::new("attachment_preview"))
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_size(egui::vec2(640.0, 480.0))
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(preview.path.to_string_lossy())
+                        .small()
+                        .color(egui::Color32::from_gray(102)),
+                );
+                if ui
+                    .small_button(egui_phosphor::regular::COPY)
+                    .icon_label(t!("attachments.copy_path"))
+                    .clicked()
+                {
+                    ui.ctx()
+                        .copy_text(preview.path.to_string_lossy().into_owned());
+                }
+            });
+            ui.separator();
+            match &preview.content {
+                None => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(t!("attachments.preview.loading"));
+                    });
+                }
+                Some(Err(err)) => {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(192, 57, 43),
+                        t!("attachments.preview.failed", err = err),
+                    );
+                }
+                Some(Ok(content)) => render_preview_content(ui, content, texture),
+            }
+        });
+    if !open || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        msgs.push(AttachmentsMsg::ClosePreview);
+    }
+    msgs
+}
+
+fn render_preview_content(
+    ui: &mut egui::Ui,
+    content: &PreviewContent,
+    texture: Option<&egui::TextureHandle>,
+) {
+    let note = |ui: &mut egui::Ui, text: String| {
+        ui.label(
+            egui::RichText::new(text)
+                .small()
+                .color(egui::Color32::from_gray(110)),
+        );
+    };
+    match content {
+        PreviewContent::Image => {
+            if let Some(texture) = texture {
+                let [width, height] = texture.size();
+                note(ui, format!("{width} × {height} px"));
+            }
+        }
+        PreviewContent::File(FilePreview::Text {
+            lossy, truncated, ..
+        }) => {
+            if *lossy {
+                note(ui, t!("attachments.preview.lossy").into());
+            }
+            if *truncated {
+                note(
+                    ui,
+                    t!(
+                        "attachments.preview.truncated",
+                        lines = file_preview::PREVIEW_MAX_LINES
+                    ),
+                );
+            }
+        }
+        PreviewContent::File(FilePreview::Binary { size, head }) => {
+            note(
+                ui,
+                t!(
+                    "attachments.preview.binary",
+                    shown = format_bytes(head.len() as u64),
+                    size = format_bytes(*size)
+                ),
+            );
+        }
+    }
+    egui::ScrollArea::both()
+        .auto_shrink([false, false])
+        .show(ui, |ui| match content {
+            PreviewContent::Image => match texture {
+                Some(texture) => {
+                    ui.image((texture.id(), texture.size_vec2()));
+                }
+                None => {
+                    ui.spinner();
+                }
+            },
+            PreviewContent::File(FilePreview::Text { text, .. }) => {
+                ui.add(
+                    egui::Label::new(egui::RichText::new(text).monospace())
+                        .extend()
+                        .selectable(true),
+                );
+            }
+            PreviewContent::File(FilePreview::Binary { head, .. }) => {
+                ui.add(
+                    egui::Label::new(egui::RichText::new(file_preview::hex_dump(head)).monospace())
+                        .extend()
+                        .selectable(true),
+                );
+            }
+        });
+}
+
+/// Collapsible caption editor below an attachment row, with a character count.
+fn render_description(
+    ui: &mut egui::Ui,
+    index: usize,
+    path: &Path,
+    description: &str,
+    msgs: &mut Vec<AttachmentsMsg>,
+) {
+    let heading = if description.is_empty() {
+        t!("attachments.description").to_string()
+    } else {
+        t!(
+            "attachments.description.count",
+            count = description.chars().count()
+        )
+    };
+    egui::CollapsingHeader::new(egui::RichText::new(heading).small())
+        .id_salt(("attachment_description", path))
+        .show(ui, |ui| {
+            let mut edited = description.to_string();
+            let response = ui.add(
+                egui::TextEdit::multiline(&mut edited)
+                    .hint_text(t!("attachments.description.hint"))
+                    .desired_rows(2)
+                    .desired_width(f32::INFINITY),
+            );
+            if response.changed() {
+                msgs.push(AttachmentsMsg::SetDescription {
+                    index,
+                    text: edited.clone(),
+                });
+            }
+            ui.label(
+                egui::RichText::new(t!(
+                    "attachments.description.characters",
+                    count = edited.trim().chars().count()
+                ))
+                .small()
+                .color(egui::Color32::from_gray(110)),
+            );
+        });
+}
+
+/// Ask before hashing a scanned folder with many files.
+fn render_folder_confirmation(
+    ctx: &egui::Context,
+    model: &AttachmentsModel,
+    msgs: &mut Vec<AttachmentsMsg>,
+) {
+    let Some(scan) = &model.pending_folder else {
+        return;
+    };
+    egui::Window::new(t!("attachments.folder.confirm"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(t!(
+                "attachments.folder.confirm.text",
+                folder = scan.root.display(),
+                count = scan.files.len()
+            ));
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button(t!("attachments.add_files")).clicked() {
+                    msgs.push(AttachmentsMsg::ConfirmFolder);
+                }
+                if ui.button(t!("common.cancel")).clicked() {
+                    msgs.push(AttachmentsMsg::CancelFolder);
+                }
+            });
+        });
+}
+
+/// Ask before removing the row picked in the table.
+fn render_removal_confirmation(
+    ctx: &egui::Context,
+    model: &AttachmentsModel,
+    msgs: &mut Vec<AttachmentsMsg>,
+) {
+    let Some(item) = model
+        .pending_removal
+        .and_then(|index| model.attachments.get(index))
+    else {
+        return;
+    };
+    egui::Window::new(t!("attachments.remove.confirm"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(t!(
+                "attachments.remove.confirm.text",
+                name = item.sanitized_name
+            ));
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button(t!("common.remove")).clicked()
+                    || ui.input(|input| input.key_pressed(egui::Key::Enter))
+                {
+                    msgs.push(AttachmentsMsg::ConfirmRemove);
+                }
+                if ui.button(t!("common.cancel")).clicked()
+                    || ui.input(|input| input.key_pressed(egui::Key::Escape))
+                {
+                    msgs.push(AttachmentsMsg::CancelRemove);
+                }
+            });
+        });
+}
+
+/// Small date/time editor for the acquisition time of one or more attachments.
+fn render_acquired_editor(
+    ctx: &egui::Context,
+    model: &AttachmentsModel,
+    msgs: &mut Vec<AttachmentsMsg>,
+) {
+    let Some(edit) = &model.acquired_edit else {
+        return;
+    };
+    let title = match edit.indices.as_slice() {
+        [index] => model
+            .attachments
+            .get(*index)
+            .map(|item| t!("attachments.acquired.title", name = item.sanitized_name))
+            .unwrap_or_else(|| t!("attachments.acquired.title.bare").into()),
+        indices => t!("attachments.acquired.title.many", count = indices.len()),
+    };
+    egui::Window::new(title)
+        .id(egui::Id::new("attachment_acquired_editor"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            msgs.extend(
+                datetime_picker::view(&edit.datetime, None, ui)
+                    .into_iter()
+                    .map(AttachmentsMsg::AcquiredEdited),
+            );
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button(t!("common.apply")).clicked() {
+                    msgs.push(AttachmentsMsg::CommitAcquired);
+                }
+                if ui.button(t!("common.cancel")).clicked() {
+                    msgs.push(AttachmentsMsg::CancelAcquired);
+                }
+            });
+        });
+}
+
+/// Render files that are still being hashed with a progress bar and cancel button.
+fn render_pending_list(
+    ui: &mut egui::Ui,
+    model: &AttachmentsModel,
+    msgs: &mut Vec<AttachmentsMsg>,
+) {
+    for (index, pending) in model.pending.iter().enumerate() {
+        let name = pending
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| pending.path.to_string_lossy().into_owned());
+
+        ui.horizontal(|ui| {
+            ui.vertical(|ui| {
+                ui.label(name);
+                let fraction = if pending.total > 0 {
+                    pending.bytes_done as f32 / pending.total as f32
+                } else {
+                    0.0
+                };
+                ui.add(
+                    egui::ProgressBar::new(fraction)
+                        .desired_width(240.0)
+                        .text(format!(
+                            "{} / {}",
+                            format_bytes(pending.bytes_done),
+                            format_bytes(pending.total)
+                        )),
+                );
+                ui.label(
+                    egui::RichText::new(t!("attachments.hashing"))
+                        .small()
+                        .color(egui::Color32::from_gray(90)),
+                );
+            });
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui
+                    .button(egui::RichText::new(egui_phosphor::regular::X))
+                    .icon_label(t!("attachments.hashing.cancel"))
+                    .clicked()
+                {
+                    msgs.push(AttachmentsMsg::CancelHash(pending.path.clone()));
+                }
+            });
+        });
+
+        if index < model.pending.len() - 1 {
+            ui.separator();
+        }
+    }
+}
+
+/// Inline filename edit UI with save/cancel controls.
+fn render_editing_filename(
     ui: &mut egui::Ui,
     model: &AttachmentsModel,
     msgs: &mut Vec<AttachmentsMsg>,
@@ -363,7 +1828,7 @@ fn render_editing_filename(
     let mut buffer = model.editing_buffer.clone();
     let response = ui.add(
         egui::TextEdit::singleline(&mut buffer)
-            .hint_text("Edit filename")
+            .hint_text(t!("attachments.edit_name"))
             .desired_width(180.0),
     );
 
@@ -381,7 +1846,7 @@ fn render_editing_filename(
 
     if ui
         .button(egui_phosphor::regular::CHECK)
-        .on_hover_text("Save")
+        .icon_label(t!("common.save"))
         .clicked()
     {
         msgs.push(AttachmentsMsg::CommitEdit);
@@ -389,57 +1854,220 @@ fn render_editing_filename(
 
     if ui
         .button(egui_phosphor::regular::X)
-        .on_hover_text("Cancel")
+        .icon_label(t!("common.cancel"))
         .clicked()
     {
         msgs.push(AttachmentsMsg::CancelEdit);
     }
 }
 
-/// Insert a new attachment if it does not collide by sanitized name or hash.
+/// Show how sanitizing changes the edited name, with the replaced characters struck through.
+fn render_rename_review(
+    ui: &mut egui::Ui,
+    review: &SanitizeExplanation,
+    msgs: &mut Vec<AttachmentsMsg>,
+) {
+    let removed = egui::Color32::from_rgb(224, 49, 49);
+    let added = egui::Color32::from_rgb(47, 158, 68);
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let mut job = egui::text::LayoutJob::default();
+    for segment in &review.segments {
+        let (text, format) = match segment {
+            DiffSegment::Kept(text) => (
+                text.clone(),
+                egui::TextFormat::simple(font_id.clone(), ui.visuals().text_color()),
+            ),
+            // Spaces are made visible so that trimmed ones can be seen.
+            DiffSegment::Removed(text) => (
+                text.replace(' ', "␣"),
+                egui::TextFormat {
+                    font_id: font_id.clone(),
+                    color: removed,
+                    background: removed.gamma_multiply(0.15),
+                    strikethrough: egui::Stroke::new(1.0, removed),
+                    ..Default::default()
+                },
+            ),
+            DiffSegment::Added(text) => (
+                text.clone(),
+                egui::TextFormat {
+                    font_id: font_id.clone(),
+                    color: added,
+                    underline: egui::Stroke::new(1.0, added),
+                    ..Default::default()
+                },
+            ),
+        };
+        job.append(&text, 0.0, format);
+    }
+
+    egui::Frame::group(ui.style()).show(ui, |ui| {
+        ui.label(egui::RichText::new(t!("attachments.review")).small());
+        ui.label(job);
+        for reason in &review.reasons {
+            ui.label(egui::RichText::new(format!("• {reason}")).small());
+        }
+        ui.horizontal(|ui| {
+            if ui.button(t!("attachments.review.accept")).clicked() {
+                msgs.push(AttachmentsMsg::AcceptSanitizedName);
+            }
+            if ui.button(t!("attachments.review.revise")).clicked() {
+                msgs.push(AttachmentsMsg::ReviseName);
+            }
+        });
+    });
+}
+
+/// Mark `path` as pending and request its hash, unless it is already queued.
+fn queue_hash(
+    model: &mut AttachmentsModel,
+    path: PathBuf,
+    folder: String,
+    cmds: &mut Vec<AttachmentsCommand>,
+) {
+    if model.pending.iter().any(|p| p.path == path) {
+        return;
+    }
+    model.pending.push(PendingHash {
+        path: path.clone(),
+        folder,
+        bytes_done: 0,
+        total: 0,
+    });
+    cmds.push(AttachmentsCommand::HashFile { path });
+}
+
+/// Queue every file of a scanned folder under its sanitized relative directory.
+fn queue_folder(
+    model: &mut AttachmentsModel,
+    scan: FolderScan,
+    cmds: &mut Vec<AttachmentsCommand>,
+) -> AttachmentsEvent {
+    let count = scan.files.len();
+    for file in scan.files {
+        let folder = sanitize_folder(&file.relative_dir);
+        queue_hash(model, file.path, folder, cmds);
+    }
+    AttachmentsEvent {
+        message: t!(
+            "attachments.folder.processing",
+            count = count,
+            folder = scan.root.display()
+        ),
+        is_error: false,
+    }
+}
+
+/// Result of inserting a hashed file into the list.
+enum AddOutcome {
+    Added,
+    /// The sanitized name was reserved by the archive writer and has been changed.
+    Renamed {
+        from: String,
+        to: String,
+    },
+    /// The file collides with an existing attachment by archive path or hash.
+    Skipped,
+}
+
+/// Insert a new attachment if it does not collide by archive path or hash, renaming it when
+/// its name is reserved for the archive's own files.
 fn add_attachment_with_meta(
     model: &mut AttachmentsModel,
     path: PathBuf,
-    sha256: String,
+    folder: String,
+    mut digests: Digests,
     size: u64,
     mime: String,
-) -> bool {
+    acquired_at: Option<OffsetDateTime>,
+) -> AddOutcome {
+    let sha256 = digests
+        .remove(&HashAlgorithm::Sha256)
+        .unwrap_or_else(|| "unavailable".into());
     let original_name = path
         .file_name()
         .map(|n| n.to_string_lossy().into_owned())
         .unwrap_or_else(|| format!("attachment-{}", model.attachments.len() + 1));
-    let sanitized_name = sanitize_component(&original_name);
+    let reserved_name = sanitize_component(&original_name);
+    let sanitized_name = unreserved_name(&reserved_name);
 
+    let target = archive_path(&folder, &sanitized_name);
     if model
         .attachments
         .iter()
-        .any(|item| item.sanitized_name == sanitized_name)
+        .any(|item| archive_path(&item.folder, &item.sanitized_name) == target)
     {
-        return false;
+        return AddOutcome::Skipped;
     }
 
     if sha256 != "unavailable" && model.hashes.contains(&sha256) {
-        return false;
+        return AddOutcome::Skipped;
     }
 
     if sha256 != "unavailable" {
         model.hashes.insert(sha256.clone());
     }
-    model.attachments.push(AttachmentItem {
+    let outcome = if reserved_name == sanitized_name {
+        AddOutcome::Added
+    } else {
+        AddOutcome::Renamed {
+            from: reserved_name,
+            to: sanitized_name.clone(),
+        }
+    };
+    let mut item = AttachmentItem {
         path,
         sanitized_name,
         mime,
         sha256,
+        digests,
         size,
-    });
-    true
+        folder,
+        acquired_at,
+        description: None,
+        exclude_from_figures: false,
+        verification: Verification::Unverified,
+    };
+    item.hashed_at_add(OffsetDateTime::now_utc());
+    model.attachments.push(item);
+    outcome
+}
+
+/// Send queued thumbnail requests while fewer than [`MAX_THUMBNAIL_LOADS`] are decoding.
+///
+/// Requests for attachments removed while they waited are dropped.
+fn start_thumbnail_loads(model: &mut AttachmentsModel, cmds: &mut Vec<AttachmentsCommand>) {
+    while model.thumbnail_loading.len() < MAX_THUMBNAIL_LOADS {
+        let Some(path) = model.thumbnail_queue.pop_front() else {
+            break;
+        };
+        if model.thumbnail_loading.insert(path.clone()) {
+            cmds.push(AttachmentsCommand::LoadThumbnail { path });
+        }
+    }
 }
 
 /// Remove an attachment and associated thumbnail state safely.
+/// Remove row `index` and report it; the selection is dropped because indices shift.
+fn remove_row(
+    model: &mut AttachmentsModel,
+    index: usize,
+    cmds: &mut Vec<AttachmentsCommand>,
+) -> Option<AttachmentsEvent> {
+    remove_attachment(model, index);
+    start_thumbnail_loads(model, cmds);
+    clear_selection(model);
+    Some(AttachmentsEvent {
+        message: t!("attachments.removed").into(),
+        is_error: false,
+    })
+}
+
 fn remove_attachment(model: &mut AttachmentsModel, index: usize) {
     if let Some(removed) = model.attachments.get(index) {
         model.thumbnail_failures.remove(&removed.path);
         model.thumbnail_loading.remove(&removed.path);
+        model.thumbnail_queue.retain(|path| *path != removed.path);
         if removed.sha256 != "unavailable" {
             model.hashes.remove(&removed.sha256);
         }
@@ -447,55 +2075,176 @@ fn remove_attachment(model: &mut AttachmentsModel, index: usize) {
     if index < model.attachments.len() {
         model.attachments.remove(index);
     }
+    model.focused = model.focused.and_then(|focused| match focused.cmp(&index) {
+        std::cmp::Ordering::Less => Some(focused),
+        std::cmp::Ordering::Equal => None,
+        std::cmp::Ordering::Greater => Some(focused - 1),
+    });
+    model.pending_removal = None;
 }
 
-/// Validate and commit a sanitized filename edit, returning a feedback event.
-fn commit_filename_edit(model: &mut AttachmentsModel) -> Option<AttachmentsEvent> {
-    let index = model.editing_index?;
+/// Row indices in the order the table shows them; ties keep the archive order.
+fn table_order(model: &AttachmentsModel) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..model.attachments.len()).collect();
+    let Some(sort) = model.table_sort else {
+        return order;
+    };
+    let items = &model.attachments;
+    order.sort_by(|&a, &b| {
+        let (a, b) = (&items[a], &items[b]);
+        let ordering = match sort.column {
+            TableColumn::Name => (a.folder.to_lowercase(), a.sanitized_name.to_lowercase())
+                .cmp(&(b.folder.to_lowercase(), b.sanitized_name.to_lowercase())),
+            TableColumn::Type => a.mime.cmp(&b.mime),
+            TableColumn::Size => a.size.cmp(&b.size),
+            TableColumn::Sha256 => a.sha256.cmp(&b.sha256),
+        };
+        if sort.ascending {
+            ordering
+        } else {
+            ordering.reverse()
+        }
+    });
+    order
+}
 
-    let raw = model.editing_buffer.trim();
-    if raw.is_empty() {
-        return Some(AttachmentsEvent {
-            message: "Filename cannot be empty.".into(),
-            is_error: true,
-        });
+/// Rearrange rows so that row `i` shows the former row `order[i]`.
+///
+/// The open rename editor, the bulk selection, the table highlight, a pending removal and the
+/// acquisition editor follow their rows.
+/// Thumbnail state is keyed by path and needs no adjustment.
+fn reorder(model: &mut AttachmentsModel, order: &[usize]) {
+    let mut new_index = vec![0; order.len()];
+    for (new, &old) in order.iter().enumerate() {
+        new_index[old] = new;
     }
+    let remap = |old: usize| new_index.get(old).copied().unwrap_or(old);
 
-    let sanitized = sanitize_component(raw);
-    if sanitized.is_empty() {
+    let mut rows: Vec<Option<AttachmentItem>> = std::mem::take(&mut model.attachments)
+        .into_iter()
+        .map(Some)
+        .collect();
+    model.attachments = order
+        .iter()
+        .filter_map(|&old| rows.get_mut(old).and_then(Option::take))
+        .collect();
+    model.editing_index = model.editing_index.map(remap);
+    model.selected = model.selected.iter().map(|&index| remap(index)).collect();
+    model.selection_anchor = model.selection_anchor.map(remap);
+    model.focused = model.focused.map(remap);
+    model.pending_removal = model.pending_removal.map(remap);
+    if let Some(edit) = model.acquired_edit.as_mut() {
+        for index in &mut edit.indices {
+            *index = remap(*index);
+        }
+    }
+}
+
+/// Drop the bulk selection and any open acquisition editor after the list changed.
+fn clear_selection(model: &mut AttachmentsModel) {
+    model.selected.clear();
+    model.selection_anchor = None;
+    model.acquired_edit = None;
+}
+
+/// Apply the edited acquisition time to every targeted row; future times are rejected.
+fn commit_acquired_edit(model: &mut AttachmentsModel) -> Option<AttachmentsEvent> {
+    let edit = model.acquired_edit.as_ref()?;
+    let acquired_at = match datetime_picker::to_offset_datetime(&edit.datetime) {
+        Ok(at) => at,
+        Err(err) => {
+            return Some(AttachmentsEvent {
+                message: err,
+                is_error: true,
+            });
+        }
+    };
+    if acquired_at > OffsetDateTime::now_utc() {
         return Some(AttachmentsEvent {
-            message: "Filename is invalid after sanitization.".into(),
+            message: t!("attachments.acquired.future").into(),
             is_error: true,
         });
     }
 
-    let duplicate = model
-        .attachments
-        .iter()
-        .enumerate()
-        .any(|(i, item)| i != index && item.sanitized_name == sanitized);
+    let edit = model.acquired_edit.take()?;
+    for &index in &edit.indices {
+        if let Some(item) = model.attachments.get_mut(index) {
+            item.acquired_at = Some(acquired_at);
+        }
+    }
+    Some(AttachmentsEvent {
+        message: t!("attachments.acquired.done", count = edit.indices.len()),
+        is_error: false,
+    })
+}
+
+/// Validate and commit a sanitized filename edit, returning a feedback event.
+///
+/// Unless `reviewed`, a name that sanitizing changes in more than case is not applied; the
+/// changes are shown for review instead.
+fn commit_filename_edit(model: &mut AttachmentsModel, reviewed: bool) -> Option<AttachmentsEvent> {
+    let index = model.editing_index?;
+
+    let raw = model.editing_buffer.trim();
+    if raw.is_empty() {
+        return Some(AttachmentsEvent {
+            message: t!("attachments.name.empty").into(),
+            is_error: true,
+        });
+    }
+
+    let sanitized = sanitize_component(raw);
+    if sanitized.is_empty() {
+        return Some(AttachmentsEvent {
+            message: t!("attachments.name.invalid").into(),
+            is_error: true,
+        });
+    }
+
+    if is_reserved_archive_name(&sanitized) {
+        return Some(AttachmentsEvent {
+            message: t!("attachments.name.reserved", name = sanitized),
+            is_error: true,
+        });
+    }
+
+    let folder = model
+        .attachments
+        .get(index)
+        .map(|item| item.folder.as_str())
+        .unwrap_or_default();
+    let duplicate =
+        model.attachments.iter().enumerate().any(|(i, item)| {
+            i != index && item.folder == folder && item.sanitized_name == sanitized
+        });
     if duplicate {
         return Some(AttachmentsEvent {
-            message: "Another attachment already uses this filename in the archive.".into(),
+            message: t!("attachments.name.duplicate").into(),
             is_error: true,
         });
     }
 
+    if !reviewed && let Some(review) = explain_sanitization(&model.editing_buffer, &sanitized) {
+        model.rename_review = Some(review);
+        return None;
+    }
+
     if let Some(item) = model.attachments.get_mut(index) {
         item.sanitized_name = sanitized;
     }
 
     model.editing_index = None;
     model.editing_buffer.clear();
+    model.rename_review = None;
 
     Some(AttachmentsEvent {
-        message: "Attachment filename updated.".into(),
+        message: t!("attachments.name.updated").into(),
         is_error: false,
     })
 }
 
 /// Return true when the path extension is a supported raster or SVG image.
-fn is_image(path: &Path) -> bool {
+pub(crate) fn is_image(path: &Path) -> bool {
     path.extension()
         .and_then(|e| e.to_str())
         .is_some_and(|ext| {
@@ -520,8 +2269,16 @@ pub(crate) fn guess_mime(path: &Path) -> String {
         .to_string()
 }
 
+/// File modification time, used as the default acquisition time of new attachments.
+pub(crate) fn file_modified(path: &Path) -> Option<OffsetDateTime> {
+    path.metadata()
+        .and_then(|m| m.modified())
+        .ok()
+        .map(OffsetDateTime::from)
+}
+
 /// Human-readable formatting for byte sizes with binary units.
-fn format_bytes(bytes: u64) -> String {
+pub(crate) fn format_bytes(bytes: u64) -> String {
     const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
     let mut value = bytes as f64;
     let mut unit = 0;
@@ -558,28 +2315,130 @@ fn render_placeholder_icon(ui: &mut egui::Ui, rect: egui::Rect, icon: &'static s
     );
 }
 
+/// Show a broken-image icon in the thumbnail slot, explaining the failure on hover, with a
+/// retry button for decode errors.
+fn render_thumbnail_failure(
+    ui: &mut egui::Ui,
+    rect: egui::Rect,
+    path: &Path,
+    error: &ThumbnailError,
+    msgs: &mut Vec<AttachmentsMsg>,
+) {
+    render_placeholder_icon(ui, rect, egui_phosphor::regular::IMAGE_BROKEN);
+    let hint = match error {
+        ThumbnailError::Unsupported(reason) => {
+            t!("attachments.thumbnail.unsupported", reason = reason)
+        }
+        ThumbnailError::Decode(reason) => t!("attachments.thumbnail.decode", reason = reason),
+    };
+    ui.interact(
+        rect,
+        ui.id().with(("thumbnail_failure", path)),
+        egui::Sense::hover(),
+    )
+    .on_hover_text(hint);
+    if error.is_retryable() {
+        let button_rect = egui::Rect::from_min_size(
+            rect.right_bottom() - egui::vec2(24.0, 24.0),
+            egui::vec2(22.0, 22.0),
+        );
+        if ui
+            .put(
+                button_rect,
+                egui::Button::new(egui_phosphor::regular::ARROW_CLOCKWISE).small(),
+            )
+            .icon_label(t!("attachments.thumbnail.retry"))
+            .clicked()
+        {
+            msgs.push(AttachmentsMsg::RetryThumbnail(path.to_path_buf()));
+        }
+    }
+}
+
+/// Why no thumbnail could be made for an image attachment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ThumbnailError {
+    /// The format, or a variant of it, cannot be decoded; loading again fails the same way.
+    Unsupported(String),
+    /// The file could not be read or is damaged, e.g. while it was still being written.
+    Decode(String),
+}
+
+impl ThumbnailError {
+    /// Whether loading the thumbnail again may succeed.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Decode(_))
+    }
+}
+
+impl From<image::ImageError> for ThumbnailError {
+    fn from(err: image::ImageError) -> Self {
+        match err {
+            image::ImageError::Unsupported(_) | image::ImageError::Limits(_) => {
+                Self::Unsupported(err.to_string())
+            }
+            _ => Self::Decode(err.to_string()),
+        }
+    }
+}
+
+/// Render the SVG at `path` within `max_side` pixels; oversized or hostile files are refused
+/// without a retry.
+fn load_svg(path: &Path, max_side: u32) -> Result<egui::ColorImage, ThumbnailError> {
+    let size = std::fs::metadata(path)
+        .map_err(|e| ThumbnailError::Decode(e.to_string()))?
+        .len();
+    if size > MAX_SVG_BYTES {
+        return Err(ThumbnailError::Unsupported(
+            SvgError::TooLarge(size).to_string(),
+        ));
+    }
+    let bytes = std::fs::read(path).map_err(|e| ThumbnailError::Decode(e.to_string()))?;
+    render_svg(bytes, max_side).map_err(|err| {
+        if err.is_refused() {
+            ThumbnailError::Unsupported(err.to_string())
+        } else {
+            ThumbnailError::Decode(err.to_string())
+        }
+    })
+}
+
 /// Load and resize an image to a thumbnail-friendly `ColorImage`.
-pub(crate) fn load_image_thumbnail(path: &Path) -> Result<egui::ColorImage, String> {
+pub(crate) fn load_image_thumbnail(path: &Path) -> Result<egui::ColorImage, ThumbnailError> {
     const MAX: u32 = 256;
 
     if is_svg(path) {
-        let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
-        let hint = egui::SizeHint::Size {
-            width: MAX,
-            height: MAX,
-            maintain_aspect_ratio: true,
-        };
-        let options = Options::default();
-        return load_svg_bytes_with_size(&bytes, hint, &options).map_err(|e| e.to_string());
+        return load_svg(path, MAX);
     }
 
-    let dyn_img = image::open(path).map_err(|e| e.to_string())?;
+    // The JPEG decoder of `image` cannot decode at a reduced DCT scale, so every format is
+    // decoded at full size; `MAX_THUMBNAIL_LOADS` bounds how many are held at once.
+    let dyn_img = image::open(path)?;
     let resized = dyn_img.thumbnail(MAX, MAX).to_rgba8();
     let size = [resized.width() as usize, resized.height() as usize];
     let pixels = resized.into_raw();
     Ok(egui::ColorImage::from_rgba_unmultiplied(size, &pixels))
 }
 
+/// Load an image at full size for the preview dialog, scaled down only beyond
+/// [`PREVIEW_MAX_SIDE`] pixels per side.
+pub(crate) fn load_image_preview(path: &Path) -> Result<egui::ColorImage, ThumbnailError> {
+    if is_svg(path) {
+        return load_svg(path, PREVIEW_SVG_SIDE);
+    }
+
+    let mut dyn_img = image::open(path)?;
+    if dyn_img.width() > PREVIEW_MAX_SIDE || dyn_img.height() > PREVIEW_MAX_SIDE {
+        dyn_img = dyn_img.thumbnail(PREVIEW_MAX_SIDE, PREVIEW_MAX_SIDE);
+    }
+    let rgba = dyn_img.to_rgba8();
+    let size = [rgba.width() as usize, rgba.height() as usize];
+    Ok(egui::ColorImage::from_rgba_unmultiplied(
+        size,
+        rgba.as_raw(),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -590,7 +2449,16 @@ mod tests {
     use image::{ImageBuffer, Rgba};
     use tempfile::TempDir;
 
-    use super::{AttachmentsModel, AttachmentsMsg, is_image, load_image_thumbnail, view};
+    use time::OffsetDateTime;
+
+    use super::{
+        AttachmentsCommand, AttachmentsLayout, AttachmentsModel, AttachmentsMsg,
+        FOLDER_CONFIRM_THRESHOLD, MAX_THUMBNAIL_LOADS, TableColumn, ThumbnailError, Verification,
+        is_image, load_image_thumbnail, section_title, table_order, update, view,
+    };
+    use crate::models::attachment::{Attachment, AttachmentPolicy};
+    use crate::utils::folder_scan::{FolderScan, ScannedFile};
+    use crate::utils::hash::{Digests, HashAlgorithm};
 
     // Ensures extension filtering matches documented formats and rejects others.
     #[test]
@@ -643,6 +2511,220 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // Damaged files may load after a retry; formats the decoder lacks never will.
+    #[test]
+    fn thumbnail_errors_tell_decode_failures_from_unsupported_formats() {
+        let tmp = TempDir::new().unwrap();
+        let damaged = tmp.path().join("damaged.png");
+        fs::write(&damaged, b"\x89PNG\r\n\x1a\ntruncated").unwrap();
+        let unsupported = tmp.path().join("scan.heic");
+        fs::write(&unsupported, b"ftypheic").unwrap();
+
+        let decode = load_image_thumbnail(&damaged).unwrap_err();
+        assert!(matches!(decode, ThumbnailError::Decode(_)), "{decode:?}");
+        let format = load_image_thumbnail(&unsupported).unwrap_err();
+        assert!(
+            matches!(format, ThumbnailError::Unsupported(_)),
+            "{format:?}"
+        );
+
+        let mut model = AttachmentsModel::default();
+        let mut cmds = Vec::new();
+        for (path, reason) in [(&damaged, decode), (&unsupported, format)] {
+            update(
+                &mut model,
+                AttachmentsMsg::ThumbnailFailed {
+                    path: path.clone(),
+                    reason,
+                },
+                &mut cmds,
+            );
+            update(
+                &mut model,
+                AttachmentsMsg::RetryThumbnail(path.clone()),
+                &mut cmds,
+            );
+        }
+        assert!(
+            matches!(cmds.as_slice(), [AttachmentsCommand::LoadThumbnail { path }] if *path == damaged),
+            "only the decode error is retried"
+        );
+        assert!(!model.thumbnail_failures.contains_key(&damaged));
+        assert!(model.thumbnail_failures.contains_key(&unsupported));
+    }
+
+    #[test]
+    fn picked_files_stay_pending_until_hashed() {
+        let tmp = TempDir::new().unwrap();
+        let path1 = tmp.path().join("a.bin");
+        let path2 = tmp.path().join("b.bin");
+        fs::write(&path1, b"same").unwrap();
+        fs::write(&path2, b"same").unwrap();
+        let sha = crate::utils::hash_file(&path1).unwrap();
+        let mut model = AttachmentsModel::default();
+        let mut cmds = Vec::new();
+
+        update(
+            &mut model,
+            AttachmentsMsg::FilesPicked(vec![path1.clone(), path2.clone()]),
+            &mut cmds,
+        );
+        assert_eq!(cmds.len(), 2);
+        assert!(model.has_pending_hashes());
+
+        update(
+            &mut model,
+            AttachmentsMsg::HashProgress {
+                path: path1.clone(),
+                bytes_done: 2,
+                total: 4,
+            },
+            &mut cmds,
+        );
+        assert_eq!(model.pending[0].bytes_done, 2);
+
+        for path in [path1, path2] {
+            update(
+                &mut model,
+                AttachmentsMsg::HashComputed {
+                    path,
+                    digests: Digests::from([(HashAlgorithm::Sha256, sha.clone())]),
+                    size: 4,
+                    mime: "application/octet-stream".into(),
+                    modified: None,
+                },
+                &mut cmds,
+            );
+        }
+
+        assert!(!model.has_pending_hashes());
+        assert_eq!(
+            model.attachments.len(),
+            1,
+            "duplicate detection still applies"
+        );
+    }
+
+    #[test]
+    fn scanned_folder_keeps_relative_directories() {
+        let file = |name: &str, dir: &str| ScannedFile {
+            path: PathBuf::from(format!("/data/{dir}/{name}")),
+            relative_dir: PathBuf::from(dir),
+        };
+        let mut model = AttachmentsModel::default();
+        let mut cmds = Vec::new();
+
+        update(
+            &mut model,
+            AttachmentsMsg::FolderScanned(FolderScan {
+                root: PathBuf::from("/data/run 42"),
+                files: vec![file("a.csv", "run 42/raw"), file("a.csv", "run 42")],
+                truncated: false,
+            }),
+            &mut cmds,
+        );
+        assert_eq!(cmds.len(), 2);
+
+        for (sha, dir) in [("1", "run 42/raw"), ("2", "run 42")] {
+            update(
+                &mut model,
+                AttachmentsMsg::HashComputed {
+                    path: PathBuf::from(format!("/data/{dir}/a.csv")),
+                    digests: Digests::from([(HashAlgorithm::Sha256, sha.into())]),
+                    size: 1,
+                    mime: "text/csv".into(),
+                    modified: None,
+                },
+                &mut cmds,
+            );
+        }
+        let paths: Vec<String> = model
+            .attachments()
+            .iter()
+            .map(|item| item.to_domain().archive_path())
+            .collect();
+        assert_eq!(paths, vec!["run_42/raw/a.csv", "run_42/a.csv"]);
+    }
+
+    #[test]
+    fn large_folders_need_confirmation_and_oversized_ones_are_rejected() {
+        let files: Vec<ScannedFile> = (0..=FOLDER_CONFIRM_THRESHOLD)
+            .map(|i| ScannedFile {
+                path: PathBuf::from(format!("/data/big/{i}.bin")),
+                relative_dir: PathBuf::from("big"),
+            })
+            .collect();
+        let scan = FolderScan {
+            root: PathBuf::from("/data/big"),
+            files,
+            truncated: false,
+        };
+        let mut model = AttachmentsModel::default();
+        let mut cmds = Vec::new();
+
+        update(
+            &mut model,
+            AttachmentsMsg::FolderScanned(scan.clone()),
+            &mut cmds,
+        );
+        assert!(cmds.is_empty());
+        assert!(model.pending_folder.is_some());
+
+        update(&mut model, AttachmentsMsg::ConfirmFolder, &mut cmds);
+        assert_eq!(cmds.len(), FOLDER_CONFIRM_THRESHOLD + 1);
+        assert!(model.pending_folder.is_none());
+
+        let mut cmds = Vec::new();
+        let event = update(
+            &mut AttachmentsModel::default(),
+            AttachmentsMsg::FolderScanned(FolderScan {
+                truncated: true,
+                ..scan
+            }),
+            &mut cmds,
+        )
+        .unwrap();
+        assert!(event.is_error);
+        assert!(cmds.is_empty());
+    }
+
+    #[test]
+    fn cancel_hash_removes_row_and_ignores_late_result() {
+        let path = PathBuf::from("/tmp/huge.tif");
+        let mut model = AttachmentsModel::default();
+        update(
+            &mut model,
+            AttachmentsMsg::FilesPicked(vec![path.clone()]),
+            &mut Vec::new(),
+        );
+
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            AttachmentsMsg::CancelHash(path.clone()),
+            &mut cmds,
+        );
+
+        assert!(!model.has_pending_hashes());
+        assert!(
+            matches!(cmds.as_slice(), [AttachmentsCommand::CancelHash { path: p }] if p == &path)
+        );
+
+        let event = update(
+            &mut model,
+            AttachmentsMsg::HashComputed {
+                path,
+                digests: Digests::from([(HashAlgorithm::Sha256, "abc".into())]),
+                size: 1,
+                mime: "image/tiff".into(),
+                modified: None,
+            },
+            &mut Vec::new(),
+        );
+        assert!(event.is_none());
+        assert!(model.attachments.is_empty());
+    }
+
     #[test]
     fn add_via_dialog_skips_duplicates_by_hash() {
         let tmp = TempDir::new().unwrap();
@@ -703,6 +2785,607 @@ mod tests {
         assert_eq!(model.attachments[2].sanitized_name, "normal-file_123.txt");
     }
 
+    fn restored(names: &[&str]) -> AttachmentsModel {
+        let mut model = AttachmentsModel::default();
+        let items = names
+            .iter()
+            .map(|name| {
+                Attachment::new(
+                    PathBuf::from(format!("/data/{name}")),
+                    name.to_string(),
+                    "text/csv".into(),
+                    format!("sha-{name}"),
+                    1,
+                )
+            })
+            .collect();
+        update(&mut model, AttachmentsMsg::Restore(items), &mut Vec::new());
+        model
+    }
+
+    fn names(model: &AttachmentsModel) -> Vec<&str> {
+        model
+            .attachments()
+            .iter()
+            .map(|item| item.sanitized_name.as_str())
+            .collect()
+    }
+
+    // Moving past either end is a no-op; the thumbnail state follows the path.
+    #[test]
+    fn moving_first_and_last_rows_keeps_bounds_and_thumbnail_state() {
+        let mut model = restored(&["a.csv", "b.csv", "c.csv"]);
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            AttachmentsMsg::LoadThumbnail(PathBuf::from("/data/a.csv")),
+            &mut cmds,
+        );
+
+        update(&mut model, AttachmentsMsg::MoveUp(0), &mut cmds);
+        update(&mut model, AttachmentsMsg::MoveDown(2), &mut cmds);
+        update(&mut model, AttachmentsMsg::MoveDown(7), &mut cmds);
+        assert_eq!(names(&model), vec!["a.csv", "b.csv", "c.csv"]);
+
+        update(&mut model, AttachmentsMsg::MoveDown(0), &mut cmds);
+        update(&mut model, AttachmentsMsg::MoveUp(2), &mut cmds);
+        assert_eq!(names(&model), vec!["b.csv", "c.csv", "a.csv"]);
+        assert!(model.is_thumbnail_loading(Path::new("/data/a.csv")));
+        assert_eq!(cmds.len(), 1, "reordering queues no work");
+    }
+
+    // An open rename and the bulk selection stay on their rows while rows move.
+    #[test]
+    fn rename_editor_and_selection_follow_moved_rows() {
+        let mut model = restored(&["b.csv", "C.csv", "a.csv"]);
+        let mut cmds = Vec::new();
+        update(&mut model, AttachmentsMsg::StartEdit(2), &mut cmds);
+        update(&mut model, AttachmentsMsg::ToggleSelected(0), &mut cmds);
+
+        update(&mut model, AttachmentsMsg::MoveUp(2), &mut cmds);
+        assert_eq!(model.editing_index, Some(1));
+        update(&mut model, AttachmentsMsg::MoveUp(1), &mut cmds);
+        assert_eq!(model.editing_index, Some(0));
+        assert_eq!(model.selected(), vec![1]);
+
+        update(&mut model, AttachmentsMsg::SortByName, &mut cmds);
+        assert_eq!(names(&model), vec!["a.csv", "b.csv", "C.csv"]);
+        assert_eq!(model.editing_index, Some(0));
+        assert_eq!(model.selected(), vec![1]);
+
+        update(
+            &mut model,
+            AttachmentsMsg::EditInputChanged("first.csv".into()),
+            &mut cmds,
+        );
+        update(&mut model, AttachmentsMsg::CommitEdit, &mut cmds);
+        assert_eq!(names(&model), vec!["first.csv", "b.csv", "C.csv"]);
+    }
+
+    // Sorting the table leaves the archive order alone; keyboard removal asks first and then
+    // highlights the row that followed in the table.
+    #[test]
+    fn table_sorts_without_reordering_and_removes_rows_from_the_keyboard() {
+        let mut model = restored(&["a.csv", "b.csv", "c.csv", "d.csv"]);
+        for (item, size) in model.attachments.iter_mut().zip([20, 5_000, 7, 300]) {
+            item.size = size;
+        }
+        let mut cmds = Vec::new();
+
+        update(
+            &mut model,
+            AttachmentsMsg::SortTable(TableColumn::Size),
+            &mut cmds,
+        );
+        assert_eq!(table_order(&model), vec![2, 0, 3, 1]);
+        update(
+            &mut model,
+            AttachmentsMsg::SortTable(TableColumn::Size),
+            &mut cmds,
+        );
+        assert_eq!(table_order(&model), vec![1, 3, 0, 2]);
+        assert_eq!(names(&model), vec!["a.csv", "b.csv", "c.csv", "d.csv"]);
+
+        update(
+            &mut model,
+            AttachmentsMsg::MoveFocus { down: true },
+            &mut cmds,
+        );
+        assert_eq!(model.focused, Some(1), "largest file first");
+        update(
+            &mut model,
+            AttachmentsMsg::MoveFocus { down: false },
+            &mut cmds,
+        );
+        assert_eq!(model.focused, Some(1), "stays on the first row");
+
+        update(&mut model, AttachmentsMsg::RequestRemove(1), &mut cmds);
+        update(&mut model, AttachmentsMsg::CancelRemove, &mut cmds);
+        assert_eq!(model.attachments().len(), 4);
+
+        update(&mut model, AttachmentsMsg::RequestRemove(1), &mut cmds);
+        update(&mut model, AttachmentsMsg::ConfirmRemove, &mut cmds);
+        assert_eq!(names(&model), vec!["a.csv", "c.csv", "d.csv"]);
+        assert_eq!(
+            model.focused.map(|index| names(&model)[index]),
+            Some("d.csv"),
+            "next largest"
+        );
+
+        update(&mut model, AttachmentsMsg::FocusRow(1), &mut cmds);
+        update(&mut model, AttachmentsMsg::RequestRemove(1), &mut cmds);
+        update(&mut model, AttachmentsMsg::ConfirmRemove, &mut cmds);
+        assert_eq!(names(&model), vec!["a.csv", "d.csv"]);
+        assert_eq!(
+            model.focused.map(|index| names(&model)[index]),
+            Some("a.csv"),
+            "the last row falls back to the one above"
+        );
+        update(
+            &mut model,
+            AttachmentsMsg::SetLayout(AttachmentsLayout::Table),
+            &mut cmds,
+        );
+        assert_eq!(model.layout, AttachmentsLayout::Table);
+    }
+
+    // Renames that sanitizing would change wait for the user to accept the changes.
+    #[test]
+    fn sanitized_renames_are_reviewed_before_they_apply() {
+        let mut model = restored(&["a.csv"]);
+        let mut cmds = Vec::new();
+        update(&mut model, AttachmentsMsg::StartEdit(0), &mut cmds);
+        update(
+            &mut model,
+            AttachmentsMsg::EditInputChanged("runs\\plate 1.csv ".into()),
+            &mut cmds,
+        );
+
+        assert!(update(&mut model, AttachmentsMsg::CommitEdit, &mut cmds).is_none());
+        assert_eq!(names(&model), vec!["a.csv"]);
+        let review = model.rename_review.as_ref().expect("changes shown");
+        assert_eq!(review.reasons.len(), 3, "{:?}", review.reasons);
+
+        update(&mut model, AttachmentsMsg::ReviseName, &mut cmds);
+        assert!(model.rename_review.is_none());
+        assert_eq!(model.editing_index, Some(0), "still editing");
+
+        update(&mut model, AttachmentsMsg::CommitEdit, &mut cmds);
+        let event = update(&mut model, AttachmentsMsg::AcceptSanitizedName, &mut cmds).unwrap();
+        assert!(!event.is_error);
+        assert_eq!(names(&model), vec!["runs_plate_1.csv"]);
+        assert!(model.rename_review.is_none());
+        assert_eq!(model.editing_index, None);
+
+        // Names that sanitizing leaves alone apply at once.
+        update(&mut model, AttachmentsMsg::StartEdit(0), &mut cmds);
+        update(
+            &mut model,
+            AttachmentsMsg::EditInputChanged("Plate_1.CSV".into()),
+            &mut cmds,
+        );
+        update(&mut model, AttachmentsMsg::CommitEdit, &mut cmds);
+        assert_eq!(names(&model), vec!["Plate_1.CSV"]);
+    }
+
+    // Extra digests travel with the attachment; the list falls back to SHA-256 when the
+    // primary algorithm was not computed for a file.
+    #[test]
+    fn extra_digests_are_kept_and_shown_with_sha256_fallback() {
+        let mut model = AttachmentsModel::default();
+        let mut cmds = Vec::new();
+        let path = PathBuf::from("/data/gel.tif");
+        update(
+            &mut model,
+            AttachmentsMsg::FilesPicked(vec![path.clone()]),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            AttachmentsMsg::HashComputed {
+                path,
+                digests: Digests::from([
+                    (HashAlgorithm::Sha256, "aa".into()),
+                    (HashAlgorithm::Blake3, "bb".into()),
+                ]),
+                size: 1,
+                mime: "image/tiff".into(),
+                modified: None,
+            },
+            &mut cmds,
+        );
+
+        let item = &model.attachments()[0];
+        assert_eq!(item.sha256, "aa");
+        assert_eq!(
+            item.shown_digest(HashAlgorithm::Blake3),
+            (HashAlgorithm::Blake3, "bb")
+        );
+        assert_eq!(
+            item.shown_digest(HashAlgorithm::Sha512),
+            (HashAlgorithm::Sha256, "aa")
+        );
+        let domain = item.to_domain();
+        assert_eq!(domain.digest(HashAlgorithm::Blake3), Some("bb"));
+        assert_eq!(domain.digest(HashAlgorithm::Sha256), Some("aa"));
+
+        update(&mut model, AttachmentsMsg::Restore(vec![domain]), &mut cmds);
+        assert_eq!(
+            model.attachments()[0].digests,
+            Digests::from([(HashAlgorithm::Blake3, "bb".into())])
+        );
+    }
+
+    // Add-time hashing verifies the file, or marks it unreadable when SHA-256 is missing.
+    #[test]
+    fn hashing_at_add_marks_files_verified_or_unreadable() {
+        let mut model = AttachmentsModel::default();
+        let mut cmds = Vec::new();
+        let ok = PathBuf::from("/data/ok.csv");
+        let locked = PathBuf::from("/data/locked.csv");
+        update(
+            &mut model,
+            AttachmentsMsg::FilesPicked(vec![ok.clone(), locked.clone()]),
+            &mut cmds,
+        );
+        for (path, digests) in [
+            (ok, Digests::from([(HashAlgorithm::Sha256, "aa".into())])),
+            (locked, Digests::new()),
+        ] {
+            update(
+                &mut model,
+                AttachmentsMsg::HashComputed {
+                    path,
+                    digests,
+                    size: 1,
+                    mime: "text/csv".into(),
+                    modified: None,
+                },
+                &mut cmds,
+            );
+        }
+
+        let states: Vec<Verification> = model
+            .attachments()
+            .iter()
+            .map(|item| item.verification)
+            .collect();
+        assert!(matches!(states[0], Verification::VerifiedAtAdd(_)));
+        assert_eq!(states[1], Verification::Unreadable);
+    }
+
+    // The policy refuses blocked types and flags large files once their hash arrives.
+    #[test]
+    fn policy_refuses_blocked_files_and_flags_large_ones() {
+        let mut model = AttachmentsModel::default();
+        model.set_policy(&AttachmentPolicy {
+            blocked: vec!["exe".into()],
+            warn_above_mb: 1,
+        });
+        let mut cmds = Vec::new();
+        let setup = PathBuf::from("/data/setup.exe");
+        let video = PathBuf::from("/data/run.mp4");
+        update(
+            &mut model,
+            AttachmentsMsg::FilesPicked(vec![setup.clone(), video.clone()]),
+            &mut cmds,
+        );
+        let mut hashed = |path: PathBuf, size: u64| {
+            update(
+                &mut model,
+                AttachmentsMsg::HashComputed {
+                    path,
+                    digests: Digests::from([(HashAlgorithm::Sha256, "aa".into())]),
+                    size,
+                    mime: "application/octet-stream".into(),
+                    modified: None,
+                },
+                &mut Vec::new(),
+            )
+            .unwrap()
+        };
+
+        let refused = hashed(setup, 10);
+        assert!(refused.is_error);
+        assert!(
+            refused.message.contains("exe files are blocked"),
+            "{}",
+            refused.message
+        );
+        let flagged = hashed(video, 3 * 1024 * 1024);
+        assert!(!flagged.is_error);
+        assert!(
+            flagged
+                .message
+                .contains("run.mp4 is 3.0 MB, larger than the 1.0 MB"),
+            "{}",
+            flagged.message
+        );
+
+        assert_eq!(model.attachments().len(), 1);
+        assert!(!model.has_pending_hashes());
+    }
+
+    // Restored rows start unverified; a save re-verifies all rows that have a hash.
+    #[test]
+    fn restored_files_are_unverified_until_a_save_reverifies_them() {
+        let mut model = restored(&["a.csv", "b.csv"]);
+        model.attachments[1].sha256 = "unavailable".into();
+        assert!(
+            model
+                .attachments()
+                .iter()
+                .all(|item| item.verification == Verification::Unverified)
+        );
+
+        let at = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        update(&mut model, AttachmentsMsg::Reverified(at), &mut Vec::new());
+        assert_eq!(
+            model.attachments[0].verification,
+            Verification::Reverified(at)
+        );
+        assert_eq!(
+            model.attachments[1].verification,
+            Verification::Unverified,
+            "nothing to compare without a recorded hash"
+        );
+
+        let later = at + time::Duration::hours(1);
+        model.attachments[0].hashed_at_add(later);
+        assert_eq!(
+            model.attachments[0].verification,
+            Verification::VerifiedAtAdd(later)
+        );
+    }
+
+    // Failures mark only the failing row; a changed file stays changed, an unreadable one
+    // recovers once it can be read and matches again.
+    #[test]
+    fn failed_verification_marks_the_failing_file() {
+        let mut model = restored(&["a.csv", "b.csv"]);
+        let at = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        update(
+            &mut model,
+            AttachmentsMsg::VerificationFailed {
+                path: PathBuf::from("/data/a.csv"),
+                unreadable: false,
+            },
+            &mut Vec::new(),
+        );
+        update(&mut model, AttachmentsMsg::Reverified(at), &mut Vec::new());
+        assert_eq!(model.attachments[0].verification, Verification::Changed);
+        assert_eq!(
+            model.attachments[1].verification,
+            Verification::Reverified(at)
+        );
+
+        update(
+            &mut model,
+            AttachmentsMsg::VerificationFailed {
+                path: PathBuf::from("/data/b.csv"),
+                unreadable: true,
+            },
+            &mut Vec::new(),
+        );
+        assert_eq!(model.attachments[1].verification, Verification::Unreadable);
+        update(&mut model, AttachmentsMsg::Reverified(at), &mut Vec::new());
+        assert_eq!(
+            model.attachments[1].verification,
+            Verification::Reverified(at)
+        );
+    }
+
+    #[test]
+    fn section_title_counts_files_size_and_problems() {
+        let empty = section_title(&AttachmentsModel::default());
+        assert_eq!(empty.to_string(), "Attachments");
+        assert!(empty.blocker.is_none());
+
+        let mut model = restored(&["a.csv", "b.csv"]);
+        model.attachments[0].size = 3 * 1024 * 1024 * 1024;
+        model.attachments[1].size = 200 * 1024 * 1024;
+        assert_eq!(section_title(&model).to_string(), "Attachments (2, 3.2 GB)");
+
+        update(
+            &mut model,
+            AttachmentsMsg::VerificationFailed {
+                path: PathBuf::from("/data/a.csv"),
+                unreadable: true,
+            },
+            &mut Vec::new(),
+        );
+        update(
+            &mut model,
+            AttachmentsMsg::FilesPicked(vec![PathBuf::from("/data/c.csv")]),
+            &mut Vec::new(),
+        );
+        let title = section_title(&model);
+        assert_eq!(
+            title.to_string(),
+            "Attachments (2, 3.2 GB, 1 unreadable or changed)"
+        );
+        assert!(title.blocker.is_some(), "pending hashes block saving");
+    }
+
+    // Reserved names are renamed on add and refused on rename, whatever their case.
+    #[test]
+    fn reserved_archive_names_are_renamed_on_add_and_refused_on_rename() {
+        let mut model = AttachmentsModel::default();
+        let mut cmds = Vec::new();
+        let picked: Vec<PathBuf> = ["INDEX.HTML", "ro-crate-metadata.json", "Experiment"]
+            .iter()
+            .map(|name| PathBuf::from(format!("/data/{name}")))
+            .collect();
+        update(
+            &mut model,
+            AttachmentsMsg::FilesPicked(picked.clone()),
+            &mut cmds,
+        );
+        let mut messages = Vec::new();
+        for (i, path) in picked.into_iter().enumerate() {
+            let event = update(
+                &mut model,
+                AttachmentsMsg::HashComputed {
+                    path,
+                    digests: Digests::from([(HashAlgorithm::Sha256, format!("sha{i}"))]),
+                    size: 1,
+                    mime: "text/plain".into(),
+                    modified: None,
+                },
+                &mut cmds,
+            )
+            .unwrap();
+            assert!(!event.is_error);
+            messages.push(event.message);
+        }
+        assert_eq!(
+            names(&model),
+            vec!["INDEX_1.HTML", "ro-crate-metadata_1.json", "Experiment_1"]
+        );
+        assert!(
+            messages[0].contains("INDEX.HTML is reserved"),
+            "{}",
+            messages[0]
+        );
+
+        for reserved in [
+            "Index.html",
+            "RO-CRATE-METADATA.JSON",
+            "checksums.SHA512",
+            "experiment-3",
+        ] {
+            update(&mut model, AttachmentsMsg::StartEdit(0), &mut cmds);
+            update(
+                &mut model,
+                AttachmentsMsg::EditInputChanged(reserved.into()),
+                &mut cmds,
+            );
+            let event = update(&mut model, AttachmentsMsg::CommitEdit, &mut cmds).unwrap();
+            assert!(event.is_error, "{reserved}");
+            assert_eq!(model.editing_index, Some(0));
+        }
+        assert_eq!(names(&model)[0], "INDEX_1.HTML");
+    }
+
+    // Bulk editing stamps every selected row and refuses times in the future.
+    #[test]
+    fn acquisition_time_is_set_for_selected_rows_but_not_in_the_future() {
+        let mut model = AttachmentsModel::default();
+        let mut cmds = Vec::new();
+        let restored = (0..4)
+            .map(|i| {
+                Attachment::new(
+                    PathBuf::from(format!("/data/{i}.csv")),
+                    format!("{i}.csv"),
+                    "text/csv".into(),
+                    format!("sha{i}"),
+                    1,
+                )
+            })
+            .collect();
+        update(&mut model, AttachmentsMsg::Restore(restored), &mut cmds);
+
+        update(&mut model, AttachmentsMsg::ToggleSelected(1), &mut cmds);
+        update(&mut model, AttachmentsMsg::SelectRange(3), &mut cmds);
+        assert_eq!(model.selected(), vec![1, 2, 3]);
+
+        let selected = model.selected();
+        update(
+            &mut model,
+            AttachmentsMsg::EditAcquired(selected),
+            &mut cmds,
+        );
+        let next_year = time::OffsetDateTime::now_utc().year() + 1;
+        let future = jiff::civil::date(next_year as i16, 1, 1);
+        update(
+            &mut model,
+            AttachmentsMsg::AcquiredEdited(super::DateTimeMsg::SetDate(future)),
+            &mut cmds,
+        );
+        let rejected = update(&mut model, AttachmentsMsg::CommitAcquired, &mut cmds)
+            .expect("future time rejected");
+        assert!(rejected.is_error);
+        assert!(model.attachments().iter().all(|a| a.acquired_at.is_none()));
+
+        update(
+            &mut model,
+            AttachmentsMsg::AcquiredEdited(super::DateTimeMsg::SetDate(jiff::civil::date(
+                2024, 5, 1,
+            ))),
+            &mut cmds,
+        );
+        let event = update(&mut model, AttachmentsMsg::CommitAcquired, &mut cmds)
+            .expect("acquisition time set");
+        assert_eq!(event.message, "Acquisition time set for 3 file(s).");
+        let acquired: Vec<bool> = model
+            .attachments()
+            .iter()
+            .map(|a| a.acquired_at.is_some())
+            .collect();
+        assert_eq!(acquired, vec![false, true, true, true]);
+        assert_eq!(
+            model.attachments()[1].to_domain().acquired_at,
+            model.attachments()[1].acquired_at
+        );
+
+        update(&mut model, AttachmentsMsg::Remove(0), &mut cmds);
+        assert!(model.selected().is_empty());
+    }
+
+    // Captions follow renames, blank ones are dropped, and a re-added file starts without one.
+    #[test]
+    fn descriptions_survive_renames_and_are_cleared_on_removal() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("gel.png");
+        fs::write(&path, b"gel").unwrap();
+        let mut model = AttachmentsModel::default();
+        let mut cmds = Vec::new();
+        assert!(model.add_path(path.clone()));
+
+        update(
+            &mut model,
+            AttachmentsMsg::SetDescription {
+                index: 0,
+                text: "Lane 3: ladder ".into(),
+            },
+            &mut cmds,
+        );
+        update(&mut model, AttachmentsMsg::StartEdit(0), &mut cmds);
+        update(
+            &mut model,
+            AttachmentsMsg::EditInputChanged("gel-final.png".into()),
+            &mut cmds,
+        );
+        update(&mut model, AttachmentsMsg::CommitEdit, &mut cmds);
+        assert_eq!(names(&model), ["gel-final.png"]);
+        assert_eq!(
+            model.attachments()[0].to_domain().description.as_deref(),
+            Some("Lane 3: ladder")
+        );
+
+        update(
+            &mut model,
+            AttachmentsMsg::SetDescription {
+                index: 0,
+                text: " \n\t".into(),
+            },
+            &mut cmds,
+        );
+        assert_eq!(model.attachments()[0].description, None);
+
+        update(
+            &mut model,
+            AttachmentsMsg::SetDescription {
+                index: 0,
+                text: "ladder".into(),
+            },
+            &mut cmds,
+        );
+        update(&mut model, AttachmentsMsg::Remove(0), &mut cmds);
+        assert!(model.add_path(path));
+        assert_eq!(model.attachments()[0].description, None);
+    }
+
     #[test]
     fn thumbnail_available_clears_loading_without_storing_texture_state() {
         let path = PathBuf::from("/tmp/example.png");
@@ -724,7 +3407,118 @@ mod tests {
 
         assert!(event.is_none());
         assert!(!model.thumbnail_loading.contains(&path));
-        assert!(!model.thumbnail_failures.contains(&path));
+        assert!(!model.thumbnail_failures.contains_key(&path));
+    }
+
+    #[test]
+    fn thumbnail_loads_are_capped_coalesced_and_limited_to_visible_rows() {
+        let mut model = AttachmentsModel::default();
+        let paths: Vec<PathBuf> = (0..300)
+            .map(|i| PathBuf::from(format!("/data/scan-{i:03}.png")))
+            .collect();
+        update(
+            &mut model,
+            AttachmentsMsg::Restore(
+                paths
+                    .iter()
+                    .enumerate()
+                    .map(|(i, path)| {
+                        Attachment::new(
+                            path.clone(),
+                            path.file_name().unwrap().to_string_lossy().into_owned(),
+                            "image/png".into(),
+                            format!("{i:064x}"),
+                            1,
+                        )
+                    })
+                    .collect(),
+            ),
+            &mut Vec::new(),
+        );
+
+        // Only rows inside the viewport ask for a thumbnail.
+        let ctx = egui::Context::default();
+        let mut requested = Vec::new();
+        let input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(
+                egui::Pos2::ZERO,
+                egui::vec2(1024.0, 768.0),
+            )),
+            ..Default::default()
+        };
+        let _ = ctx.run_ui(input, |ui| {
+            egui::CentralPanel::default().show_inside(ui, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    requested = view(ui, &model, &HashMap::new(), HashAlgorithm::Sha256, |_| {});
+                });
+            });
+        });
+        let requested = requested
+            .iter()
+            .filter(|msg| matches!(msg, AttachmentsMsg::LoadThumbnail(_)))
+            .count();
+        assert!(
+            requested > 0 && requested < 50,
+            "{requested} rows requested"
+        );
+
+        // However many are requested, and however often, only a few decode at once.
+        let mut cmds = Vec::new();
+        for _ in 0..3 {
+            for path in &paths {
+                update(
+                    &mut model,
+                    AttachmentsMsg::LoadThumbnail(path.clone()),
+                    &mut cmds,
+                );
+            }
+        }
+        let started = |cmds: &[AttachmentsCommand]| {
+            cmds.iter()
+                .filter(|cmd| matches!(cmd, AttachmentsCommand::LoadThumbnail { .. }))
+                .count()
+        };
+        assert_eq!(started(&cmds), MAX_THUMBNAIL_LOADS);
+
+        // Each finished or failed load starts the next queued one, in request order.
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            AttachmentsMsg::ThumbnailAvailable {
+                path: paths[0].clone(),
+            },
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            AttachmentsMsg::ThumbnailFailed {
+                path: paths[1].clone(),
+                reason: ThumbnailError::Decode("truncated".into()),
+            },
+            &mut cmds,
+        );
+        // Removing a decoding row frees its slot; removed rows waiting in the queue are skipped.
+        update(&mut model, AttachmentsMsg::Remove(8), &mut cmds);
+        update(&mut model, AttachmentsMsg::Remove(5), &mut cmds);
+        update(&mut model, AttachmentsMsg::Remove(2), &mut cmds);
+        update(
+            &mut model,
+            AttachmentsMsg::ThumbnailAvailable {
+                path: paths[3].clone(),
+            },
+            &mut cmds,
+        );
+        let next: Vec<&Path> = cmds
+            .iter()
+            .filter_map(|cmd| match cmd {
+                AttachmentsCommand::LoadThumbnail { path } => Some(path.as_path()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            next,
+            [&paths[4], &paths[5], &paths[6], &paths[7], &paths[9]]
+        );
     }
 
     #[test]
@@ -756,7 +3550,7 @@ mod tests {
         let mut out = Vec::new();
         let _ = ctx.run_ui(Default::default(), |ui| {
             egui::CentralPanel::default().show_inside(ui, |ui| {
-                out = view(ui, &model, &HashMap::new());
+                out = view(ui, &model, &HashMap::new(), HashAlgorithm::Sha256, |_| {});
             });
         });
 
@@ -777,7 +3571,7 @@ mod tests {
 
         let _ = ctx.run_ui(Default::default(), |ui| {
             egui::CentralPanel::default().show_inside(ui, |ui| {
-                out = view(ui, &model, &HashMap::new());
+                out = view(ui, &model, &HashMap::new(), HashAlgorithm::Sha256, |_| {});
             });
         });
 
@@ -807,7 +3601,7 @@ mod tests {
 
         let _ = ctx.run_ui(Default::default(), |ui| {
             egui::CentralPanel::default().show_inside(ui, |ui| {
-                out = view(ui, &model, &textures);
+                out = view(ui, &model, &textures, HashAlgorithm::Sha256, |_| {});
             });
         });
 
@@ -0,0 +1,373 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Author list credited in saved archives, persisted across sessions.
+
+use eframe::egui;
+
+use crate::i18n::t;
+use crate::models::author::{Author, normalize_orcid};
+use crate::ui::components::IconLabel;
+
+/// Editable author list in credit order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuthorsModel {
+    authors: Vec<Author>,
+    /// Edits not yet written to disk; flushed when a field loses focus.
+    dirty: bool,
+}
+
+/// Editable author fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthorField {
+    GivenName,
+    FamilyName,
+    Orcid,
+    Email,
+    Affiliation,
+}
+
+/// Messages emitted by the authors section.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuthorsMsg {
+    /// Authors read from disk at startup.
+    Loaded(Vec<Author>),
+    Add,
+    /// Replace the first author, or add one to an empty list; used by the first-run setup.
+    SetFirst(Author),
+    Remove(usize),
+    /// Move the author one position up (earlier in the credit order).
+    MoveUp(usize),
+    /// Move the author one position down.
+    MoveDown(usize),
+    Edit {
+        index: usize,
+        field: AuthorField,
+        value: String,
+    },
+    /// A field lost focus; persist pending edits.
+    Commit,
+    Persisted(Result<(), String>),
+}
+
+/// Side effects requested by the authors section.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuthorsCommand {
+    Persist(Vec<Author>),
+}
+
+/// Feedback surfaced to the status bar/modal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuthorsEvent {
+    pub message: String,
+    pub is_error: bool,
+}
+
+impl AuthorsModel {
+    /// Authors in credit order, including blank rows still being filled in.
+    pub fn authors(&self) -> &[Author] {
+        &self.authors
+    }
+}
+
+/// Apply a message to the authors model.
+pub fn update(
+    model: &mut AuthorsModel,
+    msg: AuthorsMsg,
+    cmds: &mut Vec<AuthorsCommand>,
+) -> Option<AuthorsEvent> {
+    match msg {
+        AuthorsMsg::Loaded(authors) => {
+            model.authors = authors;
+            model.dirty = false;
+            None
+        }
+        AuthorsMsg::Add => {
+            model.authors.push(Author::default());
+            persist(model, cmds);
+            None
+        }
+        AuthorsMsg::SetFirst(author) => {
+            match model.authors.first_mut() {
+                Some(first) if *first == author => return None,
+                Some(first) => *first = author,
+                None => model.authors.push(author),
+            }
+            persist(model, cmds);
+            None
+        }
+        AuthorsMsg::Remove(index) => {
+            if index < model.authors.len() {
+                model.authors.remove(index);
+                persist(model, cmds);
+            }
+            None
+        }
+        AuthorsMsg::MoveUp(index) => {
+            if index > 0 && index < model.authors.len() {
+                model.authors.swap(index - 1, index);
+                persist(model, cmds);
+            }
+            None
+        }
+        AuthorsMsg::MoveDown(index) => {
+            if index + 1 < model.authors.len() {
+                model.authors.swap(index, index + 1);
+                persist(model, cmds);
+            }
+            None
+        }
+        AuthorsMsg::Edit {
+            index,
+            field,
+            value,
+        } => {
+            if let Some(author) = model.authors.get_mut(index) {
+                *field_mut(author, field) = value;
+                model.dirty = true;
+            }
+            None
+        }
+        AuthorsMsg::Commit => {
+            if model.dirty {
+                persist(model, cmds);
+            }
+            None
+        }
+        AuthorsMsg::Persisted(result) => result.err().map(|err| AuthorsEvent {
+            message: t!("authors.save.failed", err = err),
+            is_error: true,
+        }),
+    }
+}
+
+/// Request writing the current list and mark it clean.
+fn persist(model: &mut AuthorsModel, cmds: &mut Vec<AuthorsCommand>) {
+    model.dirty = false;
+    cmds.push(AuthorsCommand::Persist(model.authors.clone()));
+}
+
+fn field_mut(author: &mut Author, field: AuthorField) -> &mut String {
+    match field {
+        AuthorField::GivenName => &mut author.given_name,
+        AuthorField::FamilyName => &mut author.family_name,
+        AuthorField::Orcid => &mut author.orcid,
+        AuthorField::Email => &mut author.email,
+        AuthorField::Affiliation => &mut author.affiliation,
+    }
+}
+
+/// Render the collapsible authors section and return triggered messages.
+pub fn view(ui: &mut egui::Ui, model: &AuthorsModel) -> Vec<AuthorsMsg> {
+    let mut msgs = Vec::new();
+    let header = match model.authors.len() {
+        0 => t!("authors.title").to_string(),
+        count => t!("authors.title.count", count = count),
+    };
+
+    egui::CollapsingHeader::new(header)
+        .id_salt("authors_section")
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.label(
+                egui::RichText::new(t!("authors.text"))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
+            );
+            ui.add_space(4.0);
+
+            let count = model.authors.len();
+            for (index, author) in model.authors.iter().enumerate() {
+                render_author(ui, index, count, author, &mut msgs);
+                ui.separator();
+            }
+
+            if ui
+                .button(format!(
+                    "{} {}",
+                    egui_phosphor::regular::PLUS,
+                    t!("authors.add")
+                ))
+                .clicked()
+            {
+                msgs.push(AuthorsMsg::Add);
+            }
+        });
+
+    msgs
+}
+
+/// Render one author's fields with ordering and remove controls.
+fn render_author(
+    ui: &mut egui::Ui,
+    index: usize,
+    count: usize,
+    author: &Author,
+    msgs: &mut Vec<AuthorsMsg>,
+) {
+    ui.horizontal(|ui| {
+        ui.strong(format!("{}.", index + 1));
+        if ui
+            .add_enabled(
+                index > 0,
+                egui::Button::new(egui_phosphor::regular::ARROW_UP).small(),
+            )
+            .icon_label(t!("attachments.move_up"))
+            .clicked()
+        {
+            msgs.push(AuthorsMsg::MoveUp(index));
+        }
+        if ui
+            .add_enabled(
+                index + 1 < count,
+                egui::Button::new(egui_phosphor::regular::ARROW_DOWN).small(),
+            )
+            .icon_label(t!("attachments.move_down"))
+            .clicked()
+        {
+            msgs.push(AuthorsMsg::MoveDown(index));
+        }
+        if ui
+            .small_button(egui_phosphor::regular::TRASH_SIMPLE)
+            .icon_label(t!("authors.remove"))
+            .clicked()
+        {
+            msgs.push(AuthorsMsg::Remove(index));
+        }
+    });
+
+    egui::Grid::new(("author_grid", index))
+        .num_columns(2)
+        .spacing(egui::vec2(8.0, 4.0))
+        .show(ui, |ui| {
+            let fields = [
+                (
+                    t!("onboarding.author.given"),
+                    AuthorField::GivenName,
+                    &author.given_name,
+                    "",
+                ),
+                (
+                    t!("onboarding.author.family"),
+                    AuthorField::FamilyName,
+                    &author.family_name,
+                    "",
+                ),
+                (
+                    "ORCID",
+                    AuthorField::Orcid,
+                    &author.orcid,
+                    "0000-0000-0000-0000",
+                ),
+                (
+                    t!("fields.kind.email"),
+                    AuthorField::Email,
+                    &author.email,
+                    "",
+                ),
+                (
+                    t!("authors.affiliation"),
+                    AuthorField::Affiliation,
+                    &author.affiliation,
+                    "",
+                ),
+            ];
+            for (label, field, value, hint) in fields {
+                ui.label(label);
+                ui.horizontal(|ui| {
+                    let mut text = value.clone();
+                    let resp = ui.add(
+                        egui::TextEdit::singleline(&mut text)
+                            .hint_text(hint)
+                            .desired_width(240.0),
+                    );
+                    if resp.changed() {
+                        msgs.push(AuthorsMsg::Edit {
+                            index,
+                            field,
+                            value: text,
+                        });
+                    }
+                    if resp.lost_focus() {
+                        msgs.push(AuthorsMsg::Commit);
+                    }
+                    if field == AuthorField::Orcid
+                        && !value.trim().is_empty()
+                        && let Err(err) = normalize_orcid(value)
+                    {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(200, 140, 40),
+                            egui_phosphor::regular::WARNING,
+                        )
+                        .on_hover_text(err.to_string());
+                    }
+                });
+                ui.end_row();
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(family_name: &str) -> Author {
+        Author {
+            family_name: family_name.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reordering_and_removal_persist_immediately() {
+        let mut model = AuthorsModel::default();
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            AuthorsMsg::Loaded(vec![named("A"), named("B"), named("C")]),
+            &mut cmds,
+        );
+        assert!(cmds.is_empty());
+
+        update(&mut model, AuthorsMsg::MoveUp(2), &mut cmds);
+        update(&mut model, AuthorsMsg::MoveDown(2), &mut cmds);
+        update(&mut model, AuthorsMsg::MoveUp(0), &mut cmds);
+        let order: Vec<&str> = model
+            .authors()
+            .iter()
+            .map(|a| a.family_name.as_str())
+            .collect();
+        assert_eq!(order, vec!["A", "C", "B"]);
+        assert_eq!(cmds.len(), 1, "no-op moves do not persist");
+
+        update(&mut model, AuthorsMsg::Remove(0), &mut cmds);
+        assert_eq!(
+            cmds.last(),
+            Some(&AuthorsCommand::Persist(vec![named("C"), named("B")]))
+        );
+    }
+
+    #[test]
+    fn field_edits_persist_when_focus_leaves() {
+        let mut model = AuthorsModel::default();
+        let mut cmds = Vec::new();
+        update(&mut model, AuthorsMsg::Add, &mut cmds);
+        cmds.clear();
+
+        update(
+            &mut model,
+            AuthorsMsg::Edit {
+                index: 0,
+                field: AuthorField::Orcid,
+                value: "0000-0002-1825-0097".into(),
+            },
+            &mut cmds,
+        );
+        assert!(cmds.is_empty());
+
+        update(&mut model, AuthorsMsg::Commit, &mut cmds);
+        update(&mut model, AuthorsMsg::Commit, &mut cmds);
+        assert_eq!(cmds.len(), 1);
+        assert_eq!(model.authors()[0].orcid, "0000-0002-1825-0097");
+    }
+}
@@ -8,6 +8,9 @@ use egui_extras::DatePickerButton;
 use jiff::{Zoned, civil::Date as CivilDate, tz::TimeZone};
 use time::OffsetDateTime;
 
+use crate::i18n::t;
+use crate::models::performed_at::DateWarning;
+
 /// Format an integer as a two-digit string (00-99).
 fn format_two(n: i32) -> String {
     format!("{:02}", n.clamp(0, 99))
@@ -58,77 +61,102 @@ pub fn update(model: &mut DateTimeModel, msg: DateTimeMsg) {
     }
 }
 
-/// Render the picker controls and return any triggered messages.
-pub fn view(model: &DateTimeModel, ui: &mut egui::Ui) -> Vec<DateTimeMsg> {
+/// Render the picker controls, plus `warning` below them, and return any triggered messages.
+pub fn view(
+    model: &DateTimeModel,
+    warning: Option<DateWarning>,
+    ui: &mut egui::Ui,
+) -> Vec<DateTimeMsg> {
     let mut msgs = Vec::new();
 
-    ui.horizontal(|ui| {
-        let mut date = model.date;
-        if ui
-            .add(DatePickerButton::new(&mut date).show_icon(true))
-            .changed()
-        {
-            msgs.push(DateTimeMsg::SetDate(date));
-        }
-        ui.add_space(8.0);
-
-        let mut hour = model.hour;
-        if ui
-            .add(
-                egui::DragValue::new(&mut hour)
-                    .range(0..=23)
-                    .speed(0.1)
-                    .clamp_existing_to_range(true)
-                    .custom_formatter(|v, _| format_two(v as i32)),
-            )
-            .changed()
-        {
-            msgs.push(DateTimeMsg::SetHour(hour));
-        }
-        ui.label(":");
-        let mut minute = model.minute;
-        if ui
-            .add(
-                egui::DragValue::new(&mut minute)
-                    .range(0..=59)
-                    .speed(0.1)
-                    .clamp_existing_to_range(true)
-                    .custom_formatter(|v, _| format_two(v as i32)),
-            )
-            .changed()
-        {
-            msgs.push(DateTimeMsg::SetMinute(minute));
-        }
+    ui.vertical(|ui| {
+        ui.horizontal(|ui| {
+            let mut date = model.date;
+            if ui
+                .add(DatePickerButton::new(&mut date).show_icon(true))
+                .changed()
+            {
+                msgs.push(DateTimeMsg::SetDate(date));
+            }
+            ui.add_space(8.0);
 
-        ui.add_space(8.0);
-        if ui
-            .button(egui::RichText::new(format!(
-                "{} Now",
-                egui_phosphor::regular::CLOCK
-            )))
-            .on_hover_text("Set date/time to your current local time (stored as UTC)")
-            .clicked()
-        {
-            msgs.push(DateTimeMsg::SetNow);
+            let mut hour = model.hour;
+            if ui
+                .add(
+                    egui::DragValue::new(&mut hour)
+                        .range(0..=23)
+                        .speed(0.1)
+                        .clamp_existing_to_range(true)
+                        .custom_formatter(|v, _| format_two(v as i32)),
+                )
+                .changed()
+            {
+                msgs.push(DateTimeMsg::SetHour(hour));
+            }
+            ui.label(":");
+            let mut minute = model.minute;
+            if ui
+                .add(
+                    egui::DragValue::new(&mut minute)
+                        .range(0..=59)
+                        .speed(0.1)
+                        .clamp_existing_to_range(true)
+                        .custom_formatter(|v, _| format_two(v as i32)),
+                )
+                .changed()
+            {
+                msgs.push(DateTimeMsg::SetMinute(minute));
+            }
+
+            ui.add_space(8.0);
+            if ui
+                .button(egui::RichText::new(format!(
+                    "{} {}",
+                    egui_phosphor::regular::CLOCK,
+                    t!("datetime.now")
+                )))
+                .on_hover_text(t!("datetime.now.hover"))
+                .clicked()
+            {
+                msgs.push(DateTimeMsg::SetNow);
+            }
+        });
+
+        if let Some(warning) = warning {
+            ui.colored_label(
+                egui::Color32::from_rgb(200, 140, 40),
+                format!("{} {}", egui_phosphor::regular::WARNING, warning.message()),
+            )
+            .on_hover_text(t!("datetime.warning.hover"));
         }
     });
 
     msgs
 }
 
+/// Short `YYYY-MM-DD HH:MM` label of the selected local date and time.
+pub fn summary(model: &DateTimeModel) -> String {
+    format!(
+        "{} {}:{}",
+        model.date,
+        format_two(model.hour),
+        format_two(model.minute)
+    )
+}
+
 /// Convert the selected date and time to an `OffsetDateTime` in UTC.
 pub fn to_offset_datetime(model: &DateTimeModel) -> Result<OffsetDateTime, String> {
     if !(0..=23).contains(&model.hour) {
-        return Err("Hour must be 0-23".into());
+        return Err(t!("datetime.hour.invalid").into());
     }
     if !(0..=59).contains(&model.minute) {
-        return Err("Minute must be 0-59".into());
+        return Err(t!("datetime.minute.invalid").into());
     }
 
     let datetime = model.date.at(model.hour as i8, model.minute as i8, 0, 0);
     let zoned = datetime
         .to_zoned(TimeZone::system())
-        .map_err(|_| "Invalid local date/time (likely skipped by offset)".to_string())?;
+        .map_err(|_| t!("datetime.skipped").to_string())?;
     let timestamp = zoned.timestamp();
     let unix_nanos = i128::from(timestamp.as_second()) * 1_000_000_000
         + i128::from(timestamp.subsec_nanosecond());
@@ -138,6 +166,20 @@ pub fn to_offset_datetime(model: &DateTimeModel) -> Result<OffsetDateTime, Strin
     Ok(utc_dt)
 }
 
+/// Build a picker model showing `datetime` in the system's local time zone.
+///
+/// Seconds are dropped, mirroring the minute resolution of the picker.
+pub fn from_offset_datetime(datetime: OffsetDateTime) -> Result<DateTimeModel, String> {
+    let timestamp = jiff::Timestamp::from_nanosecond(datetime.unix_timestamp_nanos())
+        .map_err(|e| format!("Timestamp out of range: {e}"))?;
+    let local = timestamp.to_zoned(TimeZone::system());
+    Ok(DateTimeModel {
+        date: local.date(),
+        hour: i32::from(local.hour()),
+        minute: i32::from(local.minute()),
+    })
+}
+
 /// Update the model fields to the current local date and time.
 fn set_to_now(model: &mut DateTimeModel) {
     let now = Zoned::now();
@@ -213,4 +255,16 @@ mod tests {
         let result = super::to_offset_datetime(&picker);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn from_offset_datetime_round_trips_through_local_time() {
+        let picker = DateTimeModel {
+            date: CivilDate::new(2024, 6, 15).unwrap(),
+            hour: 14,
+            minute: 30,
+        };
+        let utc = super::to_offset_datetime(&picker).unwrap();
+
+        assert_eq!(super::from_offset_datetime(utc).unwrap(), picker);
+    }
 }
@@ -3,9 +3,19 @@
 
 //! UI component for importing and editing eLabFTW extra fields metadata.
 
+use std::collections::BTreeSet;
+
 use eframe::egui;
+use egui_extras::DatePickerButton;
+use time::OffsetDateTime;
 
-use crate::models::extra_fields::{ExtraField, ExtraFieldGroup, ExtraFieldKind, validate_field};
+use crate::i18n::t;
+use crate::logic::units::UnitSettings;
+use crate::models::extra_fields::{
+    ExtraField, ExtraFieldGroup, ExtraFieldKind, ExtraFieldsSource, field_inline_error,
+    join_date_range, split_date_range, validate_field,
+};
+use crate::ui::components::{IconLabel, SectionTitle};
 
 /// UI state for imported extra fields.
 #[derive(Clone, Default, Debug, PartialEq, Eq)]
@@ -17,6 +27,65 @@ pub struct ExtraFieldsModel {
     editing_field: Option<usize>,
     modal_open: bool,
     modal_draft: Option<FieldDraft>,
+    /// Open "Fill matching fields" dialog, if any.
+    bulk_fill: Option<BulkFillDraft>,
+    /// File the fields were last imported from.
+    source: Option<ExtraFieldsSource>,
+    /// Re-read import file waiting for the user to choose how to apply it.
+    reimport: Option<PendingReimport>,
+    /// Import or restore that arrived while the field editor was open; applied once it
+    /// closes so the draft is not lost.
+    deferred: Option<Box<ExtraFieldsMsg>>,
+    /// Labels of fields whose value the user changed; only these report a missing value.
+    touched: BTreeSet<String>,
+    /// A save was attempted, so every missing required value is reported.
+    show_required: bool,
+    /// Only fields whose label, description or group name contain this text are shown.
+    filter_text: String,
+    /// Ids of the groups the user collapsed; all others are shown expanded.
+    collapsed_groups: BTreeSet<i32>,
+    /// eLabFTW shows only the extra fields, not the main text, for this entry.
+    hide_main_text: bool,
+}
+
+/// Content of a re-imported file, shown in the re-import dialog.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct PendingReimport {
+    fields: Vec<ExtraField>,
+    groups: Vec<ExtraFieldGroup>,
+    source: ExtraFieldsSource,
+    /// The file content differs from the last import.
+    changed: bool,
+}
+
+/// How a re-imported file is applied to the current fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReimportMode {
+    /// Take definitions from the file but keep values entered for fields it still has.
+    KeepValues,
+    /// Discard all edits and use the file as is.
+    Replace,
+}
+
+/// Inputs of the "Fill matching fields" dialog.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct BulkFillDraft {
+    label: String,
+    value: String,
+    only_empty: bool,
+}
+
+/// Outcome of filling every field with a given label, computed before applying it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BulkFillPreview {
+    /// Fields whose value will change.
+    pub changed: usize,
+    /// Matching fields left alone because they are read-only.
+    pub readonly: usize,
+    /// Matching fields left alone because they already have a value (only-empty mode).
+    pub filled: usize,
+    /// Matching fields whose kind does not accept the value.
+    pub invalid: usize,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -31,6 +100,8 @@ struct FieldDraft {
     unit: String,
     kind: ExtraFieldKind,
     group_id: Option<i32>,
+    /// Default value as typed; multi-value defaults are separated by commas.
+    default_value: String,
 }
 
 impl Default for FieldDraft {
@@ -66,6 +137,7 @@ impl Default for FieldDraft {
             unit: String::new(),
             kind: ExtraFieldKind::Text,
             group_id: None,
+            default_value: String::new(),
         }
     }
 }
@@ -86,6 +158,25 @@ impl ExtraFieldsModel {
         &self.fields
     }
 
+    /// Fields of `group` that pass the filter, with their index in [`Self::fields`].
+    pub fn visible_fields(&self, group: &ExtraFieldGroup) -> Vec<(usize, &ExtraField)> {
+        let filter = self.filter_text.trim();
+        let group_matches = contains_text(&group.name, filter);
+        self.fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.group_id == Some(group.id))
+            .filter(|(_, field)| {
+                group_matches
+                    || contains_text(&field.label, filter)
+                    || field
+                        .description
+                        .as_deref()
+                        .is_some_and(|desc| contains_text(desc, filter))
+            })
+            .collect()
+    }
+
     /// Returns a slice of all field groups in the model.
     ///
     /// The returned slice borrows the model's internal group storage and can be used for read-only iteration.
@@ -103,6 +194,21 @@ impl ExtraFieldsModel {
         &self.groups
     }
 
+    /// File the fields were last imported from, if any.
+    pub fn source(&self) -> Option<&ExtraFieldsSource> {
+        self.source.as_ref()
+    }
+
+    /// Whether eLabFTW should hide the entry's main text and show only its extra fields.
+    pub fn hide_main_text(&self) -> bool {
+        self.hide_main_text
+    }
+
+    /// Whether the field editor is open; replacing the fields now would discard its draft.
+    pub fn is_editing_field(&self) -> bool {
+        self.modal_open
+    }
+
     /// Returns whether any extra field in the model is invalid.
     ///
     /// # Returns
@@ -127,6 +233,15 @@ impl ExtraFieldsModel {
         self.fields.iter().any(field_invalid)
     }
 
+    /// Message shown under `field`; a missing required value waits until the field was
+    /// edited or a save was attempted.
+    pub fn inline_error(&self, field: &ExtraField) -> Option<String> {
+        field_inline_error(
+            field,
+            self.show_required || self.touched.contains(&field.label),
+        )
+    }
+
     /// Ensure a group named "Default" exists in the model and return its id.
     ///
     /// If a "Default" group already exists, returns its id. Otherwise a new group named
@@ -212,14 +327,41 @@ impl ExtraFieldsModel {
 pub enum ExtraFieldsMsg {
     DraftKindChanged(ExtraFieldKind),
     RemoveField(usize),
+    /// Insert a copy of the field after it and open the copy for editing.
+    DuplicateField(usize),
     ImportRequested,
+    /// Fill the whole entry from an eLabFTW JSON export; the shell applies the result.
+    EntryImportRequested,
     ImportCancelled,
     ImportLoaded {
         fields: Vec<ExtraField>,
         groups: Vec<ExtraFieldGroup>,
-        source: std::path::PathBuf,
+        source: ExtraFieldsSource,
+        /// Tags found next to the fields; the shell merges them into the keywords.
+        tags: Vec<String>,
+        /// The file turned off eLabFTW's `display_main_text`.
+        hide_main_text: bool,
     },
     ImportFailed(String),
+    /// Re-read the file the fields were last imported from.
+    ReimportRequested,
+    /// The re-read file parsed; ask how to apply it.
+    ReimportLoaded {
+        fields: Vec<ExtraField>,
+        groups: Vec<ExtraFieldGroup>,
+        source: ExtraFieldsSource,
+    },
+    ApplyReimport(ReimportMode),
+    CloseReimport,
+    /// Replace fields and groups without an import notice, e.g. when restoring a draft.
+    Restore {
+        fields: Vec<ExtraField>,
+        groups: Vec<ExtraFieldGroup>,
+        source: Option<ExtraFieldsSource>,
+        hide_main_text: bool,
+    },
+    /// Show or hide the main text next to the extra fields in eLabFTW.
+    ShowMainTextToggled(bool),
     EditValue {
         index: usize,
         value: String,
@@ -236,12 +378,19 @@ pub enum ExtraFieldsMsg {
         index: usize,
         values: Vec<String>,
     },
+    /// Put the field's default value back.
+    ResetToDefault(usize),
     StartEditGroup(usize),
     EditGroupName(String),
     CommitGroupName,
     CancelGroupEdit,
     RemoveGroup(usize),
     AddGroup,
+    /// Move the group at list position `from` to position `to`; fields keep their group.
+    MoveGroup {
+        from: usize,
+        to: usize,
+    },
     StartAddField {
         group_id: Option<i32>,
     },
@@ -249,6 +398,7 @@ pub enum ExtraFieldsMsg {
     CloseFieldModal,
     DraftLabelChanged(String),
     DraftDescChanged(String),
+    DraftDefaultValueChanged(String),
     DraftRequiredToggled(bool),
     DraftReadonlyToggled(bool),
     DraftAllowMultiToggled(bool),
@@ -267,12 +417,39 @@ pub enum ExtraFieldsMsg {
     DraftDefaultUnitChanged(String),
     DraftGroupChanged(Option<i32>),
     CommitFieldModal,
+    OpenBulkFill,
+    CloseBulkFill,
+    BulkFillLabelChanged(String),
+    BulkFillValueChanged(String),
+    BulkFillOnlyEmptyToggled(bool),
+    /// Set `value` on every writable field labelled `label` (case-insensitive) that accepts
+    /// it for its kind; with `only_empty`, fields that already have a value are kept.
+    BulkFill {
+        label: String,
+        value: String,
+        only_empty: bool,
+    },
+    /// A save was attempted; report every missing required value from now on.
+    SaveAttempted,
+    FilterChanged(String),
+    ClearFilter,
+    /// Expand or collapse the group with `group_id`.
+    SetGroupOpen {
+        group_id: i32,
+        open: bool,
+    },
+    /// Expand or collapse every group.
+    SetAllGroupsOpen(bool),
 }
 
 /// Commands that require side effects.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ExtraFieldsCommand {
     PickMetadataFile,
+    /// Pick an eLabFTW entry export to fill the whole form from.
+    PickEntryExport,
+    /// Read and parse a metadata file without asking, for re-imports.
+    ReadMetadataFile(std::path::PathBuf),
 }
 
 /// Feedback surfaced to the status bar/modal.
@@ -288,13 +465,38 @@ pub fn update(
     msg: ExtraFieldsMsg,
     cmds: &mut Vec<ExtraFieldsCommand>,
 ) -> Option<ExtraFieldsEvent> {
+    if model.modal_open {
+        match msg {
+            ExtraFieldsMsg::ImportLoaded { .. }
+            | ExtraFieldsMsg::ApplyReimport(_)
+            | ExtraFieldsMsg::Restore { .. } => {
+                // A later replacement supersedes an earlier one.
+                model.deferred = Some(Box::new(msg));
+                return Some(ExtraFieldsEvent {
+                    message: t!("fields.editor_open.deferred").into(),
+                    is_error: false,
+                });
+            }
+            ExtraFieldsMsg::RemoveGroup(_) => {
+                return Some(ExtraFieldsEvent {
+                    message: t!("fields.editor_open").into(),
+                    is_error: true,
+                });
+            }
+            _ => {}
+        }
+    }
     match msg {
         ExtraFieldsMsg::ImportRequested => {
             cmds.push(ExtraFieldsCommand::PickMetadataFile);
             None
         }
+        ExtraFieldsMsg::EntryImportRequested => {
+            cmds.push(ExtraFieldsCommand::PickEntryExport);
+            None
+        }
         ExtraFieldsMsg::ImportCancelled => Some(ExtraFieldsEvent {
-            message: "Metadata import cancelled.".to_string(),
+            message: t!("fields.import.cancelled").to_string(),
             is_error: false,
         }),
         ExtraFieldsMsg::ImportFailed(err) => Some(ExtraFieldsEvent {
@@ -302,29 +504,104 @@ pub fn update(
             is_error: true,
         }),
         ExtraFieldsMsg::ImportLoaded {
-            mut fields,
+            fields,
             groups,
             source,
+            tags: _,
+            hide_main_text,
         } => {
-            fields.sort_by(|a, b| a.cmp_key().cmp(&b.cmp_key()));
-            model.fields = fields;
-            model.groups = groups;
-            model.editing_group = None;
-            model.editing_group_buffer.clear();
-            model.modal_open = false;
-            model.modal_draft = None;
-            model.editing_field = None;
+            let mut message = t!(
+                "fields.imported",
+                count = fields.len(),
+                path = source.path.display()
+            );
+            message.push_str(&replace_fields(model, fields, groups, source));
+            model.hide_main_text = hide_main_text;
             Some(ExtraFieldsEvent {
-                message: format!(
-                    "Imported {} field(s) from {}",
-                    model.fields.len(),
-                    source.display()
-                ),
+                message,
+                is_error: false,
+            })
+        }
+        ExtraFieldsMsg::ReimportRequested => {
+            if let Some(source) = &model.source {
+                cmds.push(ExtraFieldsCommand::ReadMetadataFile(source.path.clone()));
+            }
+            None
+        }
+        ExtraFieldsMsg::ReimportLoaded {
+            fields,
+            groups,
+            source,
+        } => {
+            let changed = model
+                .source
+                .as_ref()
+                .is_none_or(|previous| previous.sha256 != source.sha256);
+            model.reimport = Some(PendingReimport {
+                fields,
+                groups,
+                source,
+                changed,
+            });
+            None
+        }
+        ExtraFieldsMsg::ApplyReimport(mode) => {
+            let pending = model.reimport.take()?;
+            let name = pending.source.file_name();
+            let (fields, groups, mut message) = match mode {
+                ReimportMode::KeepValues => {
+                    let (fields, groups) = merge_reimport(model, pending.fields, pending.groups);
+                    let message = t!("fields.reimported.kept", count = fields.len(), name = name);
+                    (fields, groups, message)
+                }
+                ReimportMode::Replace => {
+                    let message = t!(
+                        "fields.reimported.replaced",
+                        count = pending.fields.len(),
+                        name = name
+                    );
+                    (pending.fields, pending.groups, message)
+                }
+            };
+            message.push_str(&replace_fields(model, fields, groups, pending.source));
+            Some(ExtraFieldsEvent {
+                message,
                 is_error: false,
             })
         }
+        ExtraFieldsMsg::CloseReimport => {
+            model.reimport = None;
+            None
+        }
+        ExtraFieldsMsg::Restore {
+            fields,
+            groups,
+            source,
+            hide_main_text,
+        } => {
+            *model = ExtraFieldsModel {
+                fields,
+                groups,
+                source,
+                hide_main_text,
+                ..Default::default()
+            };
+            None
+        }
+        ExtraFieldsMsg::ShowMainTextToggled(show) => {
+            model.hide_main_text = !show;
+            None
+        }
+        ExtraFieldsMsg::ResetToDefault(index) => {
+            if let Some(field) = model.fields.get_mut(index).filter(|field| !field.readonly) {
+                model.touched.insert(field.label.clone());
+                field.reset_to_default();
+            }
+            None
+        }
         ExtraFieldsMsg::EditValue { index, value } => {
             if let Some(field) = model.fields.get_mut(index) {
+                model.touched.insert(field.label.clone());
                 field.value = value;
                 // keep multi list in sync for multi selects
                 if field.allow_multi_values {
@@ -333,20 +610,113 @@ pub fn update(
             }
             None
         }
+        ExtraFieldsMsg::OpenBulkFill => {
+            let label = model
+                .fields
+                .first()
+                .map(|f| f.label.clone())
+                .unwrap_or_default();
+            model.bulk_fill = Some(BulkFillDraft {
+                label,
+                ..Default::default()
+            });
+            None
+        }
+        ExtraFieldsMsg::CloseBulkFill => {
+            model.bulk_fill = None;
+            None
+        }
+        ExtraFieldsMsg::BulkFillLabelChanged(label) => {
+            if let Some(draft) = model.bulk_fill.as_mut() {
+                draft.label = label;
+            }
+            None
+        }
+        ExtraFieldsMsg::BulkFillValueChanged(value) => {
+            if let Some(draft) = model.bulk_fill.as_mut() {
+                draft.value = value;
+            }
+            None
+        }
+        ExtraFieldsMsg::BulkFillOnlyEmptyToggled(only_empty) => {
+            if let Some(draft) = model.bulk_fill.as_mut() {
+                draft.only_empty = only_empty;
+            }
+            None
+        }
+        ExtraFieldsMsg::BulkFill {
+            label,
+            value,
+            only_empty,
+        } => {
+            let (preview, updates) = plan_bulk_fill(model, &label, &value, only_empty);
+            for (index, field) in updates {
+                model.fields[index] = field;
+            }
+            model.bulk_fill = None;
+            let mut message = t!(
+                "fields.filled",
+                count = preview.changed,
+                label = label.trim()
+            );
+            if preview.readonly + preview.invalid > 0 {
+                message.push(' ');
+                message.push_str(&t!(
+                    "fields.filled.skipped",
+                    readonly = preview.readonly,
+                    invalid = preview.invalid
+                ));
+            }
+            Some(ExtraFieldsEvent {
+                message,
+                is_error: false,
+            })
+        }
+        ExtraFieldsMsg::SaveAttempted => {
+            model.show_required = true;
+            None
+        }
+        ExtraFieldsMsg::FilterChanged(text) => {
+            model.filter_text = text;
+            None
+        }
+        ExtraFieldsMsg::ClearFilter => {
+            model.filter_text.clear();
+            None
+        }
+        ExtraFieldsMsg::SetGroupOpen { group_id, open } => {
+            if open {
+                model.collapsed_groups.remove(&group_id);
+            } else {
+                model.collapsed_groups.insert(group_id);
+            }
+            None
+        }
+        ExtraFieldsMsg::SetAllGroupsOpen(open) => {
+            model.collapsed_groups = if open {
+                BTreeSet::new()
+            } else {
+                model.groups.iter().map(|group| group.id).collect()
+            };
+            None
+        }
         ExtraFieldsMsg::ToggleCheckbox { index, checked } => {
             if let Some(field) = model.fields.get_mut(index) {
+                model.touched.insert(field.label.clone());
                 field.value = if checked { "on".into() } else { String::new() };
             }
             None
         }
         ExtraFieldsMsg::SelectUnit { index, unit } => {
             if let Some(field) = model.fields.get_mut(index) {
+                model.touched.insert(field.label.clone());
                 field.unit = Some(unit);
             }
             None
         }
         ExtraFieldsMsg::UpdateMulti { index, values } => {
             if let Some(field) = model.fields.get_mut(index) {
+                model.touched.insert(field.label.clone());
                 field.value_multi = values.clone();
                 field.value = values.join(", ");
             }
@@ -367,6 +737,7 @@ pub fn update(
                     unit: f.unit.clone().unwrap_or_default(),
                     kind: f.kind.clone(),
                     group_id: f.group_id,
+                    default_value: f.default_value.clone().unwrap_or_default(),
                 });
             }
             None
@@ -375,7 +746,7 @@ pub fn update(
             model.modal_open = false;
             model.modal_draft = None;
             model.editing_field = None;
-            None
+            apply_deferred(model, cmds)
         }
         ExtraFieldsMsg::DraftLabelChanged(text) => {
             if let Some(d) = model.modal_draft.as_mut() {
@@ -389,6 +760,12 @@ pub fn update(
             }
             None
         }
+        ExtraFieldsMsg::DraftDefaultValueChanged(text) => {
+            if let Some(d) = model.modal_draft.as_mut() {
+                d.default_value = text;
+            }
+            None
+        }
         ExtraFieldsMsg::DraftRequiredToggled(val) => {
             if let Some(d) = model.modal_draft.as_mut() {
                 d.required = val;
@@ -490,13 +867,26 @@ pub fn update(
             }
             None
         }
+        ExtraFieldsMsg::DuplicateField(index) => {
+            let mut copy = model.fields.get(index)?.clone();
+            copy.label = copy_label(model, &copy.label);
+            if copy.blank_value_on_duplicate {
+                copy.value.clear();
+                copy.value_multi.clear();
+            }
+            model.fields.insert(index + 1, copy);
+            for (position, field) in model.fields.iter_mut().enumerate() {
+                field.position = Some(position as i32);
+            }
+            update(model, ExtraFieldsMsg::OpenFieldModal(index + 1), cmds)
+        }
         ExtraFieldsMsg::CommitFieldModal => {
             if let Some(draft) = model.modal_draft.take() {
                 if name_conflict(model, &draft.label, model.editing_field) {
                     // keep modal open; restore draft
                     model.modal_draft = Some(draft);
                     return Some(ExtraFieldsEvent {
-                        message: "Field name must be unique".into(),
+                        message: t!("fields.name.duplicate").into(),
                         is_error: true,
                     });
                 }
@@ -526,15 +916,18 @@ pub fn update(
                             blank_value_on_duplicate: false,
                             group_id: draft.group_id,
                             readonly: false,
+                            default_value: None,
+                            default_multi: Vec::new(),
                         };
                         apply_draft_to_field(&draft, &mut new_field);
+                        new_field.reset_to_default();
                         model.fields.push(new_field);
                     }
                 }
             }
             model.modal_open = false;
             model.editing_field = None;
-            None
+            apply_deferred(model, cmds)
         }
         ExtraFieldsMsg::StartEditGroup(idx) => {
             if let Some(g) = model.groups.get(idx) {
@@ -545,11 +938,24 @@ pub fn update(
         }
         ExtraFieldsMsg::AddGroup => {
             let next_id = model.groups.iter().map(|g| g.id).max().unwrap_or(0) + 1;
+            let wanted = format!("Group {}", next_id);
+            let name = unique_group_name(&model.groups, &wanted, None);
             model.groups.push(ExtraFieldGroup {
                 id: next_id,
-                name: format!("Group {}", next_id),
+                name: name.clone(),
                 position: model.groups.len() as i32,
             });
+            renamed_notice(&wanted, &name)
+        }
+        ExtraFieldsMsg::MoveGroup { from, to } => {
+            if from < model.groups.len() {
+                let editing = model.editing_group.map(|idx| model.groups[idx].id);
+                let group = model.groups.remove(from);
+                model.groups.insert(to.min(model.groups.len()), group);
+                renumber_groups(&mut model.groups);
+                model.editing_group =
+                    editing.and_then(|id| model.groups.iter().position(|g| g.id == id));
+            }
             None
         }
         ExtraFieldsMsg::EditGroupName(name) => {
@@ -557,14 +963,18 @@ pub fn update(
             None
         }
         ExtraFieldsMsg::CommitGroupName => {
+            let mut event = None;
             if let Some(idx) = model.editing_group
-                && let Some(group) = model.groups.get_mut(idx)
+                && idx < model.groups.len()
             {
-                group.name = model.editing_group_buffer.trim().to_string();
+                let wanted = model.editing_group_buffer.trim();
+                let name = unique_group_name(&model.groups, wanted, Some(idx));
+                event = renamed_notice(wanted, &name);
+                model.groups[idx].name = name;
             }
             model.editing_group = None;
             model.editing_group_buffer.clear();
-            None
+            event
         }
         ExtraFieldsMsg::CancelGroupEdit => {
             model.editing_group = None;
@@ -601,7 +1011,8 @@ pub fn update(
 ///
 /// This draws the collapsible "Metadata" section, action buttons, the grouped field list,
 /// and any open field-edit modal, returning a list of messages for actions the user took
-/// during this render pass.
+/// during this render pass. `extra_controls` draws shell-owned widgets (the favorite-folder
+/// menu) next to "Import JSON".
 ///
 /// # Returns
 ///
@@ -614,19 +1025,207 @@ pub fn update(
 /// let ctx = Context::default();
 /// let mut model = crate::ui::components::extra_fields::ExtraFieldsModel::default();
 /// let mut ui = ctx.begin_frame(Default::default());
-/// let msgs = crate::ui::components::extra_fields::view(&mut ui, &model);
+/// let msgs = crate::ui::components::extra_fields::view(&mut ui, &model, &UnitSettings::default(), |_| {});
 /// ```
-pub fn view(ui: &mut egui::Ui, model: &ExtraFieldsModel) -> Vec<ExtraFieldsMsg> {
+/// Apply the import or restore deferred while the field editor was open, if any.
+fn apply_deferred(
+    model: &mut ExtraFieldsModel,
+    cmds: &mut Vec<ExtraFieldsCommand>,
+) -> Option<ExtraFieldsEvent> {
+    let msg = model.deferred.take()?;
+    update(model, *msg, cmds)
+}
+
+/// Replace all fields and groups with imported ones and close any editing state.
+///
+/// Groups sharing a name are merged first; returns a sentence naming them for the import
+/// notice, or an empty string.
+fn replace_fields(
+    model: &mut ExtraFieldsModel,
+    mut fields: Vec<ExtraField>,
+    mut groups: Vec<ExtraFieldGroup>,
+    source: ExtraFieldsSource,
+) -> String {
+    let merged = merge_duplicate_groups(&mut fields, &mut groups);
+    fields.sort_by(|a, b| a.cmp_key().cmp(&b.cmp_key()));
+    *model = ExtraFieldsModel {
+        fields,
+        groups,
+        source: Some(source),
+        hide_main_text: model.hide_main_text,
+        ..Default::default()
+    };
+    if merged.is_empty() {
+        String::new()
+    } else {
+        format!(
+            ". {}",
+            t!("fields.groups.merged", names = merged.join(", "))
+        )
+    }
+}
+
+/// Fold groups whose names match case-insensitively into the first of them, moving their
+/// fields along. Returns the names of the groups that absorbed others.
+fn merge_duplicate_groups(
+    fields: &mut [ExtraField],
+    groups: &mut Vec<ExtraFieldGroup>,
+) -> Vec<String> {
+    let mut merged: Vec<String> = Vec::new();
+    let mut kept: Vec<ExtraFieldGroup> = Vec::with_capacity(groups.len());
+    for group in groups.drain(..) {
+        let key = group.name.trim().to_lowercase();
+        match kept.iter().find(|k| k.name.trim().to_lowercase() == key) {
+            Some(target) => {
+                for field in fields.iter_mut().filter(|f| f.group_id == Some(group.id)) {
+                    field.group_id = Some(target.id);
+                }
+                if !merged.contains(&target.name) {
+                    merged.push(target.name.clone());
+                }
+            }
+            None => kept.push(group),
+        }
+    }
+    *groups = kept;
+    merged
+}
+
+/// Return `name`, or `name (2)`, `name (3)`, … when another group (all but `except`)
+/// already uses it, compared case-insensitively. Empty names are left alone.
+fn unique_group_name(groups: &[ExtraFieldGroup], name: &str, except: Option<usize>) -> String {
+    let taken = |candidate: &str| {
+        groups
+            .iter()
+            .enumerate()
+            .any(|(i, g)| Some(i) != except && g.name.trim().eq_ignore_ascii_case(candidate))
+    };
+    if name.is_empty() || !taken(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| format!("{name} ({n})"))
+        .find(|candidate| !taken(candidate))
+        .expect("unbounded suffixes")
+}
+
+/// Set each group's `position` to its place in the list, as eLabFTW orders groups by it.
+fn renumber_groups(groups: &mut [ExtraFieldGroup]) {
+    for (position, group) in groups.iter_mut().enumerate() {
+        group.position = position as i32;
+    }
+}
+
+/// Drag payload carrying the list position of a group header being dragged.
+struct GroupDrag(usize);
+
+/// Status notice for a group name that had to be changed to stay unique.
+fn renamed_notice(wanted: &str, name: &str) -> Option<ExtraFieldsEvent> {
+    (wanted != name).then(|| ExtraFieldsEvent {
+        message: t!("fields.group.renamed", wanted = wanted, name = name),
+        is_error: false,
+    })
+}
+
+/// Combine re-imported definitions with the current fields.
+///
+/// A field from the file takes the value and unit of a current field with the same label
+/// (case-insensitive) in a group of the same name. Current fields the file no longer has are
+/// kept, together with their group.
+fn merge_reimport(
+    model: &ExtraFieldsModel,
+    mut fields: Vec<ExtraField>,
+    mut groups: Vec<ExtraFieldGroup>,
+) -> (Vec<ExtraField>, Vec<ExtraFieldGroup>) {
+    let group_name = |groups: &[ExtraFieldGroup], id: Option<i32>| {
+        id.and_then(|id| groups.iter().find(|g| g.id == id))
+            .map(|g| g.name.trim().to_lowercase())
+    };
+    let key = |field: &ExtraField, groups: &[ExtraFieldGroup]| {
+        (
+            field.label.trim().to_lowercase(),
+            group_name(groups, field.group_id),
+        )
+    };
+
+    let mut kept = vec![false; model.fields.len()];
+    for field in &mut fields {
+        let wanted = key(field, &groups);
+        let Some(index) = (0..model.fields.len())
+            .find(|&i| !kept[i] && key(&model.fields[i], &model.groups) == wanted)
+        else {
+            continue;
+        };
+        kept[index] = true;
+        let current = &model.fields[index];
+        field.value = current.value.clone();
+        field.value_multi = current.value_multi.clone();
+        if current
+            .unit
+            .as_ref()
+            .is_some_and(|unit| field.units.contains(unit))
+        {
+            field.unit = current.unit.clone();
+        }
+    }
+
+    for (current, _) in model.fields.iter().zip(kept).filter(|(_, kept)| !kept) {
+        let mut field = current.clone();
+        field.group_id = current
+            .group_id
+            .and_then(|id| model.groups.iter().find(|g| g.id == id))
+            .map(|group| {
+                match groups
+                    .iter()
+                    .find(|g| g.name.trim().eq_ignore_ascii_case(group.name.trim()))
+                {
+                    Some(existing) => existing.id,
+                    None => {
+                        let id = groups.iter().map(|g| g.id).max().unwrap_or(0) + 1;
+                        groups.push(ExtraFieldGroup {
+                            id,
+                            name: group.name.clone(),
+                            position: groups.len() as i32,
+                        });
+                        id
+                    }
+                }
+            });
+        fields.push(field);
+    }
+    (fields, groups)
+}
+
+/// Describe how long before `now` the Unix timestamp `then` lies, e.g. `2 h ago`.
+fn time_ago(then: i64, now: i64) -> String {
+    let secs = (now - then).max(0);
+    match secs {
+        0..60 => t!("fields.ago.now").to_string(),
+        60..3600 => t!("fields.ago.minutes", count = secs / 60),
+        3600..86400 => t!("fields.ago.hours", count = secs / 3600),
+        _ => t!("fields.ago.days", count = secs / 86400),
+    }
+}
+
+pub fn view(
+    ui: &mut egui::Ui,
+    model: &ExtraFieldsModel,
+    units: &UnitSettings,
+    extra_controls: impl FnOnce(&mut egui::Ui),
+) -> Vec<ExtraFieldsMsg> {
     let mut msgs = Vec::new();
 
-    egui::CollapsingHeader::new("Metadata")
+    let title = section_title(model);
+    let section = egui::CollapsingHeader::new(title.widget_text(ui))
+        .id_salt("metadata_section")
         .default_open(false)
         .show(ui, |ui| {
             ui.horizontal(|ui| {
                 if ui
                     .add(egui::Button::new(format!(
-                        "{} Add group",
-                        egui_phosphor::regular::FOLDER_PLUS
+                        "{} {}",
+                        egui_phosphor::regular::FOLDER_PLUS,
+                        t!("fields.add_group")
                     )))
                     .clicked()
                 {
@@ -634,8 +1233,9 @@ pub fn view(ui: &mut egui::Ui, model: &ExtraFieldsModel) -> Vec<ExtraFieldsMsg>
                 }
                 if ui
                     .add(egui::Button::new(format!(
-                        "{} Add field",
-                        egui_phosphor::regular::PLUS
+                        "{} {}",
+                        egui_phosphor::regular::PLUS,
+                        t!("fields.add_field")
                     )))
                     .clicked()
                 {
@@ -643,34 +1243,179 @@ pub fn view(ui: &mut egui::Ui, model: &ExtraFieldsModel) -> Vec<ExtraFieldsMsg>
                 }
                 if ui
                     .add(egui::Button::new(format!(
-                        "{} Import JSON",
-                        egui_phosphor::regular::FILE_ARROW_DOWN
+                        "{} {}",
+                        egui_phosphor::regular::FILE_ARROW_DOWN,
+                        t!("fields.import")
                     )))
                     .clicked()
                 {
                     msgs.push(ExtraFieldsMsg::ImportRequested);
                 }
+                if ui
+                    .add(egui::Button::new(format!(
+                        "{} {}",
+                        egui_phosphor::regular::FILE_TEXT,
+                        t!("fields.import_entry")
+                    )))
+                    .on_hover_text(t!("fields.import_entry.hover"))
+                    .clicked()
+                {
+                    msgs.push(ExtraFieldsMsg::EntryImportRequested);
+                }
+                if ui
+                    .add_enabled(
+                        !model.fields.is_empty(),
+                        egui::Button::new(format!(
+                            "{} {}",
+                            egui_phosphor::regular::PAINT_BUCKET,
+                            t!("fields.bulk_fill.button")
+                        )),
+                    )
+                    .on_hover_text(t!("fields.bulk_fill.hover"))
+                    .clicked()
+                {
+                    msgs.push(ExtraFieldsMsg::OpenBulkFill);
+                }
+                extra_controls(ui);
             });
 
+            if let Some(source) = &model.source {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        egui::RichText::new(t!(
+                            "fields.source",
+                            name = source.file_name(),
+                            ago = time_ago(
+                                source.imported_at,
+                                OffsetDateTime::now_utc().unix_timestamp()
+                            )
+                        ))
+                        .small(),
+                    )
+                    .on_hover_text(source.path.display().to_string());
+                    if ui
+                        .small_button(format!(
+                            "{} {}",
+                            egui_phosphor::regular::ARROWS_CLOCKWISE,
+                            t!("fields.reimport")
+                        ))
+                        .on_hover_text(t!("fields.reimport.hover"))
+                        .clicked()
+                    {
+                        msgs.push(ExtraFieldsMsg::ReimportRequested);
+                    }
+                });
+            }
+
+            if !model.groups.is_empty() {
+                render_filter_bar(ui, model, &mut msgs);
+            }
+
             ui.add_space(6.0);
 
             ui.label(
-                egui::RichText::new(
-                    "Imports eLabFTW-style extra_fields JSON. Field options are imported; you can adjust values before saving."
-                )
-                .small()
-                .color(egui::Color32::from_gray(110)),
+                egui::RichText::new(t!("fields.import.text"))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
             );
 
             ui.add_space(10.0);
-            render_fields(ui, model, &mut msgs);
+            render_fields(ui, model, units, &mut msgs);
         });
+    if let Some(blocker) = title.blocker {
+        section.header_response.on_hover_text(blocker);
+    }
 
     render_field_modal(ui.ctx(), model, &mut msgs);
+    render_bulk_fill_window(ui.ctx(), model, &mut msgs);
+    render_reimport_window(ui.ctx(), model, &mut msgs);
 
     msgs
 }
 
+/// Render the filter box and the buttons expanding or collapsing all groups.
+fn render_filter_bar(ui: &mut egui::Ui, model: &ExtraFieldsModel, msgs: &mut Vec<ExtraFieldsMsg>) {
+    let filtering = !model.filter_text.trim().is_empty();
+    ui.horizontal(|ui| {
+        ui.label(egui_phosphor::regular::MAGNIFYING_GLASS);
+        let mut text = model.filter_text.clone();
+        if ui
+            .add(
+                egui::TextEdit::singleline(&mut text)
+                    .hint_text(t!("fields.filter.hint"))
+                    .desired_width(260.0),
+            )
+            .changed()
+        {
+            msgs.push(ExtraFieldsMsg::FilterChanged(text));
+        }
+        if !model.filter_text.is_empty()
+            && ui
+                .small_button(egui_phosphor::regular::X)
+                .icon_label(t!("fields.filter.clear"))
+                .clicked()
+        {
+            msgs.push(ExtraFieldsMsg::ClearFilter);
+        }
+        // Matching groups are always expanded while filtering.
+        if ui
+            .add_enabled(
+                !filtering,
+                egui::Button::new(format!(
+                    "{} {}",
+                    egui_phosphor::regular::CARET_DOWN,
+                    t!("fields.expand_all")
+                )),
+            )
+            .clicked()
+        {
+            msgs.push(ExtraFieldsMsg::SetAllGroupsOpen(true));
+        }
+        if ui
+            .add_enabled(
+                !filtering,
+                egui::Button::new(format!(
+                    "{} {}",
+                    egui_phosphor::regular::CARET_UP,
+                    t!("fields.collapse_all")
+                )),
+            )
+            .clicked()
+        {
+            msgs.push(ExtraFieldsMsg::SetAllGroupsOpen(false));
+        }
+    });
+}
+
+/// Checkbox choosing whether eLabFTW shows the entry's main text next to its fields.
+pub fn main_text_toggle(ui: &mut egui::Ui, model: &ExtraFieldsModel) -> Option<ExtraFieldsMsg> {
+    let mut show_main_text = !model.hide_main_text;
+    ui.checkbox(&mut show_main_text, t!("fields.main_text"))
+        .on_hover_text(t!("fields.main_text.hover"))
+        .changed()
+        .then_some(ExtraFieldsMsg::ShowMainTextToggled(show_main_text))
+}
+
+/// Title of the metadata section, e.g. `Metadata (8 fields, 2 invalid)`.
+pub fn section_title(model: &ExtraFieldsModel) -> SectionTitle {
+    let counts = match model.fields.len() {
+        0 => Vec::new(),
+        1 => vec![t!("fields.count.one").to_string()],
+        count => vec![t!("fields.count.many", count = count)],
+    };
+    let invalid = model
+        .fields
+        .iter()
+        .filter(|field| field_invalid(field))
+        .count();
+    SectionTitle {
+        name: t!("fields.title"),
+        counts,
+        problems: (invalid > 0).then(|| t!("fields.invalid", count = invalid)),
+        blocker: (invalid > 0).then_some(t!("fields.blocker")),
+    }
+}
+
 /// Render the list of extra fields grouped into collapsible group panels and collect any emitted UI messages.
 ///
 /// Renders each group in `model.groups` as a collapsible header containing its fields; when there are
@@ -686,54 +1431,65 @@ pub fn view(ui: &mut egui::Ui, model: &ExtraFieldsModel) -> Vec<ExtraFieldsMsg>
 /// let mut msgs = Vec::new();
 ///
 /// egui::CentralPanel::default().show(&ctx, |ui| {
-///     render_fields(ui, &model, &mut msgs);
+///     render_fields(ui, &model, &UnitSettings::default(), &mut msgs);
 /// });
 ///
 /// assert!(msgs.is_empty());
 /// ```
-fn render_fields(ui: &mut egui::Ui, model: &ExtraFieldsModel, msgs: &mut Vec<ExtraFieldsMsg>) {
+fn render_fields(
+    ui: &mut egui::Ui,
+    model: &ExtraFieldsModel,
+    units: &UnitSettings,
+    msgs: &mut Vec<ExtraFieldsMsg>,
+) {
     if model.fields.is_empty() && model.groups.is_empty() {
         ui.label(
-            egui::RichText::new("No metadata yet. Add a group or import JSON to begin.")
+            egui::RichText::new(t!("fields.none"))
                 .italics()
                 .color(egui::Color32::from_gray(110)),
         );
     }
 
+    let filter = model.filter_text.trim();
+    let filtering = !filter.is_empty();
+    let mut shown = 0;
+
     // Render grouped fields in group order, collapsible.
-    for group in model.groups.iter() {
-        let group_fields: Vec<(usize, &ExtraField)> = model
-            .fields
-            .iter()
-            .enumerate()
-            .filter(|(_, f)| f.group_id == Some(group.id))
-            .collect();
+    for (group_idx, group) in model.groups.iter().enumerate() {
+        // Indices stay those of `model.fields`, so messages address the right field.
+        let group_fields = model.visible_fields(group);
+        if filtering && group_fields.is_empty() {
+            continue;
+        }
+        shown += group_fields.len();
 
-        egui::CollapsingHeader::new(group.name.clone())
+        let open = filtering || !model.collapsed_groups.contains(&group.id);
+        let response = egui::CollapsingHeader::new(highlighted(ui, &group.name, filter))
             .id_salt(format!("extra-group-{}", group.id))
-            .default_open(true)
+            .open(Some(open))
             .show(ui, |ui| {
                 // Header controls inside the collapsible header area.
-                render_group_header(ui, group, msgs, model);
+                render_group_header(ui, group, group_idx, msgs, model);
                 ui.add_space(4.0);
                 if group_fields.is_empty() {
                     ui.label(
-                        egui::RichText::new("No fields in this group yet.")
+                        egui::RichText::new(t!("fields.group.empty"))
                             .italics()
                             .color(egui::Color32::from_gray(120)),
                     );
                 } else {
                     for (idx, field) in group_fields {
-                        render_field(ui, field, idx, msgs);
+                        let error = model.inline_error(field);
+                        render_field(ui, field, idx, error, filter, units, msgs);
                         ui.add_space(6.0);
                     }
                 }
 
                 if ui
                     .add(egui::Button::new(format!(
-                        "{} Add field to {}",
+                        "{} {}",
                         egui_phosphor::regular::PLUS,
-                        group.name
+                        t!("fields.add_field_to", group = group.name)
                     )))
                     .clicked()
                 {
@@ -743,20 +1499,118 @@ fn render_fields(ui: &mut egui::Ui, model: &ExtraFieldsModel, msgs: &mut Vec<Ext
                 }
             });
 
+        // Group headers are drop targets for dragged groups.
+        let header = &response.header_response;
+        if !filtering && header.clicked() {
+            msgs.push(ExtraFieldsMsg::SetGroupOpen {
+                group_id: group.id,
+                open: !open,
+            });
+        }
+        if header.dnd_hover_payload::<GroupDrag>().is_some() {
+            ui.painter().hline(
+                header.rect.x_range(),
+                header.rect.top(),
+                ui.visuals().selection.stroke,
+            );
+        }
+        if let Some(dragged) = header.dnd_release_payload::<GroupDrag>() {
+            msgs.push(ExtraFieldsMsg::MoveGroup {
+                from: dragged.0,
+                to: group_idx,
+            });
+        }
+
         ui.add_space(10.0);
     }
+
+    if filtering && shown == 0 {
+        ui.label(
+            egui::RichText::new(t!("fields.filter.none", filter = filter))
+                .italics()
+                .color(egui::Color32::from_gray(110)),
+        );
+    }
 }
 
-/// Render the header controls for a single extra-field group (rename, remove, or edit).
-///
-/// Displays either an inline editing row (cancel and commit buttons plus a single-line
-/// text edit bound to the model's editing buffer) when that group is currently being
-/// edited, or action buttons to rename or remove the group when not editing. User
-/// interactions append corresponding `ExtraFieldsMsg` entries to the provided `msgs`
+/// Whether `text` contains `needle`, ignoring case; an empty `needle` is always contained.
+fn contains_text(text: &str, needle: &str) -> bool {
+    needle.is_empty() || !match_ranges(text, needle).is_empty()
+}
+
+/// Byte ranges of the non-overlapping occurrences of `needle` in `text`, ignoring case.
+fn match_ranges(text: &str, needle: &str) -> Vec<std::ops::Range<usize>> {
+    let needle: Vec<char> = needle.chars().flat_map(char::to_lowercase).collect();
+    let mut ranges = Vec::new();
+    if needle.is_empty() {
+        return ranges;
+    }
+    let mut from = 0;
+    for (start, _) in text.char_indices() {
+        if start < from {
+            continue;
+        }
+        let mut wanted = needle.iter();
+        let mut end = None;
+        'chars: for (offset, c) in text[start..].char_indices() {
+            for lower in c.to_lowercase() {
+                match wanted.next() {
+                    Some(&w) if w == lower => {}
+                    Some(_) => break 'chars,
+                    None => break,
+                }
+            }
+            if wanted.as_slice().is_empty() {
+                end = Some(start + offset + c.len_utf8());
+                break;
+            }
+        }
+        if let Some(end) = end {
+            ranges.push(start..end);
+            from = end;
+        }
+    }
+    ranges
+}
+
+/// Format of regular label text in `ui`.
+fn plain_format(ui: &egui::Ui) -> egui::TextFormat {
+    egui::TextFormat::simple(
+        egui::TextStyle::Body.resolve(ui.style()),
+        ui.visuals().text_color(),
+    )
+}
+
+/// Lay out `text` with the occurrences of `needle` highlighted.
+fn highlighted(ui: &egui::Ui, text: &str, needle: &str) -> egui::text::LayoutJob {
+    let plain = plain_format(ui);
+    let marked = egui::TextFormat {
+        background: ui.visuals().selection.bg_fill,
+        color: ui.visuals().strong_text_color(),
+        ..plain.clone()
+    };
+    let mut job = egui::text::LayoutJob::default();
+    let mut last = 0;
+    for range in match_ranges(text, needle) {
+        job.append(&text[last..range.start], 0.0, plain.clone());
+        job.append(&text[range.clone()], 0.0, marked.clone());
+        last = range.end;
+    }
+    job.append(&text[last..], 0.0, plain);
+    job
+}
+
+/// Render the header controls for a single extra-field group (rename, remove, or edit).
+///
+/// Displays either an inline editing row (cancel and commit buttons plus a single-line
+/// text edit bound to the model's editing buffer) when that group is currently being
+/// edited, or action buttons to rename or remove the group when not editing. User
+/// interactions append corresponding `ExtraFieldsMsg` entries to the provided `msgs`
 /// vector:
 /// - CancelGroupEdit, CommitGroupName, EditGroupName when editing;
-/// - RemoveGroup(idx) or StartEditGroup(idx) when not editing (removal only shown if
-///   more than one group exists).
+/// - RemoveGroup(idx), StartEditGroup(idx), or MoveGroup when not editing (removal and
+///   moving only shown if more than one group exists). The grip handle starts dragging
+///   the group onto another group header.
 ///
 /// The function reads `model.editing_group` and `model.editing_group_buffer` and uses
 /// the group's `id` to find the group's index when emitting messages that require it.
@@ -775,6 +1629,7 @@ fn render_fields(ui: &mut egui::Ui, model: &ExtraFieldsModel, msgs: &mut Vec<Ext
 fn render_group_header(
     ui: &mut egui::Ui,
     group: &ExtraFieldGroup,
+    idx: usize,
     msgs: &mut Vec<ExtraFieldsMsg>,
     model: &ExtraFieldsModel,
 ) {
@@ -791,34 +1646,73 @@ fn render_group_header(
 
     ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
         if is_editing {
-            if ui.button(egui_phosphor::regular::X).clicked() {
+            if ui
+                .button(egui_phosphor::regular::X)
+                .icon_label(t!("common.cancel"))
+                .clicked()
+            {
                 msgs.push(ExtraFieldsMsg::CancelGroupEdit);
             }
-            if ui.button(egui_phosphor::regular::CHECK).clicked() {
+            if ui
+                .button(egui_phosphor::regular::CHECK)
+                .icon_label(t!("fields.group.save"))
+                .clicked()
+            {
                 msgs.push(ExtraFieldsMsg::CommitGroupName);
             }
             let mut text = model.editing_group_buffer.clone();
             if ui
-                .add(egui::TextEdit::singleline(&mut text).hint_text("Group name"))
+                .add(egui::TextEdit::singleline(&mut text).hint_text(t!("fields.group.name")))
                 .changed()
             {
                 msgs.push(ExtraFieldsMsg::EditGroupName(text));
             }
         } else {
-            if model.groups.len() > 1
+            let count = model.groups.len();
+            if count > 1 {
+                ui.add(
+                    egui::Label::new(egui_phosphor::regular::DOTS_SIX_VERTICAL)
+                        .sense(egui::Sense::drag()),
+                )
+                .on_hover_cursor(egui::CursorIcon::Grab)
+                .on_hover_text(t!("fields.group.drag"))
+                .dnd_set_drag_payload(GroupDrag(idx));
+                if ui
+                    .add_enabled(idx > 0, egui::Button::new(egui_phosphor::regular::ARROW_UP))
+                    .icon_label(t!("fields.group.up"))
+                    .clicked()
+                {
+                    msgs.push(ExtraFieldsMsg::MoveGroup {
+                        from: idx,
+                        to: idx - 1,
+                    });
+                }
+                if ui
+                    .add_enabled(
+                        idx + 1 < count,
+                        egui::Button::new(egui_phosphor::regular::ARROW_DOWN),
+                    )
+                    .icon_label(t!("fields.group.down"))
+                    .clicked()
+                {
+                    msgs.push(ExtraFieldsMsg::MoveGroup {
+                        from: idx,
+                        to: idx + 1,
+                    });
+                }
+            }
+            if count > 1
                 && ui
                     .button(egui_phosphor::regular::TRASH)
-                    .on_hover_text("Remove group")
+                    .icon_label(t!("fields.group.remove"))
                     .clicked()
-                && let Some(idx) = model.groups.iter().position(|g| g.id == group.id)
             {
                 msgs.push(ExtraFieldsMsg::RemoveGroup(idx));
             }
             if ui
                 .button(egui_phosphor::regular::PENCIL_SIMPLE)
-                .on_hover_text("Rename group")
+                .icon_label(t!("fields.group.rename"))
                 .clicked()
-                && let Some(idx) = model.groups.iter().position(|g| g.id == group.id)
             {
                 msgs.push(ExtraFieldsMsg::StartEditGroup(idx));
             }
@@ -829,17 +1723,28 @@ fn render_group_header(
 /// Render a single extra-field card including its label, description, controls (edit/remove)
 /// and the appropriate value editor for the field's kind.
 ///
-/// The card is visually highlighted when the field is invalid. Clicking the trash or pencil
-/// buttons pushes `ExtraFieldsMsg::RemoveField` or `ExtraFieldsMsg::OpenFieldModal` (with
-/// the provided `idx`) onto the supplied `msgs` vector; other interactions push their
-/// corresponding messages as handled by the value renderer.
+/// The card is visually highlighted when the field is invalid, and `error` is shown in red
+/// under the value editor. Occurrences of `filter` in the label are highlighted. Clicking the trash, copy, or
+/// pencil buttons pushes `ExtraFieldsMsg::RemoveField`, `ExtraFieldsMsg::DuplicateField`, or
+/// `ExtraFieldsMsg::OpenFieldModal` (with the provided `idx`) onto the supplied `msgs`
+/// vector, and the reset button, shown while the value differs from the field's default,
+/// pushes `ExtraFieldsMsg::ResetToDefault`; other interactions push their corresponding messages as handled by the value
+/// renderer.
 ///
 /// # Examples
 ///
 /// ```rust,ignore
 /// use egui::{CtxRef, CentralPanel};
 /// ```
-fn render_field(ui: &mut egui::Ui, field: &ExtraField, idx: usize, msgs: &mut Vec<ExtraFieldsMsg>) {
+fn render_field(
+    ui: &mut egui::Ui,
+    field: &ExtraField,
+    idx: usize,
+    error: Option<String>,
+    filter: &str,
+    units: &UnitSettings,
+    msgs: &mut Vec<ExtraFieldsMsg>,
+) {
     let invalid = field_invalid(field);
     let mut frame = egui::Frame::group(ui.style()).stroke(if invalid {
         egui::Stroke::new(1.0, egui::Color32::from_rgb(200, 80, 80))
@@ -864,26 +1769,47 @@ fn render_field(ui: &mut egui::Ui, field: &ExtraField, idx: usize, msgs: &mut Ve
 
     frame.show(ui, |ui| {
         ui.horizontal(|ui| {
-            let mut label = field.label.clone();
+            let mut label = highlighted(ui, &field.label, filter);
             if field.required {
-                label.push_str(" *");
+                label.append(" *", 0.0, plain_format(ui));
             }
             ui.label(label);
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if ui
                     .button(egui_phosphor::regular::TRASH)
-                    .on_hover_text("Remove field")
+                    .icon_label(t!("fields.remove"))
                     .clicked()
                 {
                     msgs.push(ExtraFieldsMsg::RemoveField(idx));
                 }
+                if ui
+                    .button(egui_phosphor::regular::COPY)
+                    .icon_label(t!("fields.duplicate"))
+                    .clicked()
+                {
+                    msgs.push(ExtraFieldsMsg::DuplicateField(idx));
+                }
                 if ui
                     .button(egui_phosphor::regular::PENCIL_SIMPLE)
-                    .on_hover_text("Edit field")
+                    .icon_label(t!("fields.edit"))
                     .clicked()
                 {
                     msgs.push(ExtraFieldsMsg::OpenFieldModal(idx));
                 }
+                if let Some(default) = &field.default_value
+                    && !field.readonly
+                    && field.differs_from_default()
+                    && ui
+                        .button(egui_phosphor::regular::ARROW_COUNTER_CLOCKWISE)
+                        .icon_label(&if default.is_empty() {
+                            t!("fields.reset.empty").to_string()
+                        } else {
+                            t!("fields.reset", default = default)
+                        })
+                        .clicked()
+                {
+                    msgs.push(ExtraFieldsMsg::ResetToDefault(idx));
+                }
             });
         });
 
@@ -896,7 +1822,14 @@ fn render_field(ui: &mut egui::Ui, field: &ExtraField, idx: usize, msgs: &mut Ve
         }
 
         ui.add_space(4.0);
-        render_field_value(ui, field, idx, msgs);
+        render_field_value(ui, field, idx, units, msgs);
+        if let Some(error) = error {
+            ui.label(
+                egui::RichText::new(error)
+                    .small()
+                    .color(egui::Color32::from_rgb(200, 80, 80)),
+            );
+        }
         ui.add_space(6.0);
     });
 }
@@ -907,6 +1840,8 @@ fn render_field(ui: &mut egui::Ui, field: &ExtraField, idx: usize, msgs: &mut Ve
 /// - `Checkbox` renders a checkbox control.
 /// - `Select` and `Radio` render option controls.
 /// - `Number` renders a numeric input (and unit selector when applicable).
+/// - `Date` and `DateTimeLocal` render a text input with a date picker button.
+/// - `DateRange` renders one such input for the start and one for the end.
 /// - All other kinds render a text input.
 ///
 /// The function emits user interactions as `ExtraFieldsMsg` entries pushed into `msgs`.
@@ -923,16 +1858,114 @@ fn render_field_value(
     ui: &mut egui::Ui,
     field: &ExtraField,
     idx: usize,
+    units: &UnitSettings,
     msgs: &mut Vec<ExtraFieldsMsg>,
 ) {
     ui.group(|ui| match field.kind {
         ExtraFieldKind::Checkbox => render_checkbox(ui, field, idx, msgs),
         ExtraFieldKind::Select | ExtraFieldKind::Radio => render_options(ui, field, idx, msgs),
-        ExtraFieldKind::Number => render_number(ui, field, idx, msgs),
+        ExtraFieldKind::Number => render_number(ui, field, idx, units, msgs),
+        ExtraFieldKind::Date | ExtraFieldKind::DateTimeLocal => {
+            ui.horizontal(|ui| {
+                render_text_input(ui, field, idx, msgs);
+                render_date_picker(ui, field, idx, msgs);
+            });
+        }
+        ExtraFieldKind::DateRange => render_date_range(ui, field, idx, msgs),
         _ => render_text_input(ui, field, idx, msgs),
     });
 }
 
+/// Render a date picker button that replaces the date part of a date or date/time value.
+///
+/// The picker starts at the field's current date (today if it cannot be parsed); a
+/// date/time value keeps its time part, or gets `00:00` if it had none.
+fn render_date_picker(
+    ui: &mut egui::Ui,
+    field: &ExtraField,
+    idx: usize,
+    msgs: &mut Vec<ExtraFieldsMsg>,
+) {
+    let value = field.value.trim();
+    let (date_part, time_part) = value.split_once('T').unwrap_or((value, ""));
+    let salt = format!("extra_field_date_{idx}");
+    if let Some(date) = pick_date(ui, date_part, &salt, !field.readonly) {
+        let value = match field.kind {
+            ExtraFieldKind::DateTimeLocal if time_part.is_empty() => format!("{date}T00:00"),
+            ExtraFieldKind::DateTimeLocal => format!("{date}T{time_part}"),
+            _ => date.to_string(),
+        };
+        msgs.push(ExtraFieldsMsg::EditValue { index: idx, value });
+    }
+}
+
+/// Render a date picker button starting at `current`, or today if it is no date, and return
+/// the date picked.
+fn pick_date(
+    ui: &mut egui::Ui,
+    current: &str,
+    salt: &str,
+    enabled: bool,
+) -> Option<jiff::civil::Date> {
+    let mut date = current
+        .parse::<jiff::civil::Date>()
+        .unwrap_or_else(|_| jiff::Zoned::now().date());
+    let changed = ui
+        .add_enabled_ui(enabled, |ui| {
+            ui.add(
+                DatePickerButton::new(&mut date)
+                    .show_icon(true)
+                    .id_salt(salt),
+            )
+            .changed()
+        })
+        .inner;
+    changed.then_some(date)
+}
+
+/// Render the start and end of a date range, each as a text input with a date picker.
+///
+/// Editing either part writes the whole `start/end` value, so a range with one date missing
+/// stays flagged until it is complete.
+fn render_date_range(
+    ui: &mut egui::Ui,
+    field: &ExtraField,
+    idx: usize,
+    msgs: &mut Vec<ExtraFieldsMsg>,
+) {
+    let (start, end) = split_date_range(&field.value);
+    let mut parts = [start.to_string(), end.to_string()];
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        for (part, (label, name)) in parts.iter_mut().zip([
+            (t!("fields.range.from"), "start"),
+            (t!("fields.range.to"), "end"),
+        ]) {
+            let label = ui.label(label);
+            changed |= ui
+                .add_enabled(
+                    !field.readonly,
+                    egui::TextEdit::singleline(part)
+                        .hint_text("YYYY-MM-DD")
+                        .desired_width(96.0),
+                )
+                .labelled_by(label.id)
+                .changed();
+            let salt = format!("extra_field_date_{name}_{idx}");
+            if let Some(date) = pick_date(ui, part, &salt, !field.readonly) {
+                *part = date.to_string();
+                changed = true;
+            }
+        }
+    });
+    if changed {
+        msgs.push(ExtraFieldsMsg::EditValue {
+            index: idx,
+            value: join_date_range(&parts[0], &parts[1]),
+        });
+    }
+}
+
 /// Renders a checkbox for a boolean extra field and queues a ToggleCheckbox message when its state changes.
 ///
 /// The checkbox reflects the field's `value` being `"on"` and, when toggled by the user, appends
@@ -960,7 +1993,7 @@ fn render_checkbox(
 ) {
     let mut checked = field.value == "on";
     ui.add_enabled_ui(!field.readonly, |ui| {
-        if ui.checkbox(&mut checked, "Checked").changed() {
+        if ui.checkbox(&mut checked, t!("fields.checked")).changed() {
             msgs.push(ExtraFieldsMsg::ToggleCheckbox {
                 index: idx,
                 checked,
@@ -1030,7 +2063,8 @@ fn render_options(
 /// Renders a numeric text input for an extra field and, if present, a unit selector.
 ///
 /// The input is disabled when the field is read-only. User edits emit `ExtraFieldsMsg::EditValue`,
-/// and selecting a unit emits `ExtraFieldsMsg::SelectUnit`.
+/// and selecting a unit emits `ExtraFieldsMsg::SelectUnit`. Units with a UCUM code show it
+/// as a hint, so misspelled units stand out.
 ///
 /// # Examples
 ///
@@ -1045,6 +2079,7 @@ fn render_number(
     ui: &mut egui::Ui,
     field: &ExtraField,
     idx: usize,
+    units: &UnitSettings,
     msgs: &mut Vec<ExtraFieldsMsg>,
 ) {
     ui.horizontal(|ui| {
@@ -1068,14 +2103,26 @@ fn render_number(
                 egui::ComboBox::from_id_salt(format!("extra-unit-{}", idx))
                     .width(90.0)
                     .selected_text(if current_unit.is_empty() {
-                        "Unit"
+                        t!("fields.unit")
                     } else {
                         &current_unit
                     })
                     .show_ui(ui, |ui| {
                         for unit in &field.units {
+                            let mut text = egui::text::LayoutJob::default();
+                            text.append(unit, 0.0, egui::TextFormat::default());
+                            if let Some(code) = units.ucum_code(unit) {
+                                text.append(
+                                    code,
+                                    8.0,
+                                    egui::TextFormat {
+                                        color: egui::Color32::from_gray(120),
+                                        ..Default::default()
+                                    },
+                                );
+                            }
                             if ui
-                                .selectable_value(&mut current_unit, unit.clone(), unit)
+                                .selectable_value(&mut current_unit, unit.clone(), text)
                                 .clicked()
                             {
                                 msgs.push(ExtraFieldsMsg::SelectUnit {
@@ -1086,6 +2133,24 @@ fn render_number(
                         }
                     });
             });
+            if let Some(unit) = field.unit.as_deref().filter(|unit| !unit.is_empty()) {
+                match units.ucum_code(unit) {
+                    Some(code) => ui
+                        .label(
+                            egui::RichText::new(code)
+                                .small()
+                                .color(egui::Color32::from_gray(120)),
+                        )
+                        .on_hover_text(t!("fields.unit.code", code = code)),
+                    None => ui
+                        .label(
+                            egui::RichText::new(egui_phosphor::regular::QUESTION)
+                                .small()
+                                .color(egui::Color32::from_gray(120)),
+                        )
+                        .on_hover_text(t!("fields.unit.unknown")),
+                };
+            }
         }
     });
 }
@@ -1139,12 +2204,15 @@ fn render_text_input(
 fn field_hint(kind: &ExtraFieldKind) -> &'static str {
     match kind {
         ExtraFieldKind::Date => "YYYY-MM-DD",
+        ExtraFieldKind::DateRange => "YYYY-MM-DD/YYYY-MM-DD",
         ExtraFieldKind::DateTimeLocal => "YYYY-MM-DDTHH:MM",
         ExtraFieldKind::Time => "HH:MM",
         ExtraFieldKind::Url => "https://example.com",
         ExtraFieldKind::Email => "name@example.com",
-        ExtraFieldKind::Number => "Number",
-        ExtraFieldKind::Items | ExtraFieldKind::Experiments | ExtraFieldKind::Users => "Numeric ID",
+        ExtraFieldKind::Number => t!("fields.kind.number"),
+        ExtraFieldKind::Items | ExtraFieldKind::Experiments | ExtraFieldKind::Users => {
+            t!("fields.hint.id")
+        }
         _ => "",
     }
 }
@@ -1162,20 +2230,21 @@ fn field_hint(kind: &ExtraFieldKind) -> &'static str {
 /// ```
 fn kind_label(kind: &ExtraFieldKind) -> &'static str {
     match kind {
-        ExtraFieldKind::Text => "Text",
-        ExtraFieldKind::Number => "Number",
-        ExtraFieldKind::Select => "Select",
-        ExtraFieldKind::Checkbox => "Checkbox",
-        ExtraFieldKind::Date => "Date",
-        ExtraFieldKind::DateTimeLocal => "Date/time",
-        ExtraFieldKind::Time => "Time",
-        ExtraFieldKind::Url => "URL",
-        ExtraFieldKind::Email => "Email",
-        ExtraFieldKind::Radio => "Radio",
-        ExtraFieldKind::Items => "Items",
-        ExtraFieldKind::Experiments => "Experiments",
-        ExtraFieldKind::Users => "Users",
-        ExtraFieldKind::Unknown(_) => "Unknown",
+        ExtraFieldKind::Text => t!("fields.kind.text"),
+        ExtraFieldKind::Number => t!("fields.kind.number"),
+        ExtraFieldKind::Select => t!("fields.kind.select"),
+        ExtraFieldKind::Checkbox => t!("fields.kind.checkbox"),
+        ExtraFieldKind::Date => t!("fields.kind.date"),
+        ExtraFieldKind::DateRange => t!("fields.kind.date_range"),
+        ExtraFieldKind::DateTimeLocal => t!("fields.kind.datetime"),
+        ExtraFieldKind::Time => t!("fields.kind.time"),
+        ExtraFieldKind::Url => t!("fields.kind.url"),
+        ExtraFieldKind::Email => t!("fields.kind.email"),
+        ExtraFieldKind::Radio => t!("fields.kind.radio"),
+        ExtraFieldKind::Items => t!("fields.kind.items"),
+        ExtraFieldKind::Experiments => t!("fields.kind.experiments"),
+        ExtraFieldKind::Users => t!("fields.kind.users"),
+        ExtraFieldKind::Unknown(_) => t!("fields.kind.unknown"),
     }
 }
 
@@ -1198,6 +2267,7 @@ fn all_kinds() -> Vec<ExtraFieldKind> {
         ExtraFieldKind::Select,
         ExtraFieldKind::Checkbox,
         ExtraFieldKind::Date,
+        ExtraFieldKind::DateRange,
         ExtraFieldKind::DateTimeLocal,
         ExtraFieldKind::Time,
         ExtraFieldKind::Url,
@@ -1252,6 +2322,117 @@ fn name_conflict(model: &ExtraFieldsModel, label: &str, editing: Option<usize>)
     })
 }
 
+/// Label for a copy of the field labelled `label`: `label (copy)`, then `label (copy 2)`,
+/// `label (copy 3)`, … until no field uses it.
+fn copy_label(model: &ExtraFieldsModel, label: &str) -> String {
+    let base = format!("{} (copy)", label.trim());
+    if !name_conflict(model, &base, None) {
+        return base;
+    }
+    (2..)
+        .map(|n| format!("{} (copy {n})", label.trim()))
+        .find(|candidate| !name_conflict(model, candidate, None))
+        .expect("unbounded suffixes")
+}
+
+/// Count how many fields a bulk fill of `value` into fields labelled `label` would change.
+pub fn bulk_fill_preview(
+    model: &ExtraFieldsModel,
+    label: &str,
+    value: &str,
+    only_empty: bool,
+) -> BulkFillPreview {
+    plan_bulk_fill(model, label, value, only_empty).0
+}
+
+/// Compute the updated fields of a bulk fill together with its preview counts.
+fn plan_bulk_fill(
+    model: &ExtraFieldsModel,
+    label: &str,
+    value: &str,
+    only_empty: bool,
+) -> (BulkFillPreview, Vec<(usize, ExtraField)>) {
+    let key = label.trim();
+    let mut preview = BulkFillPreview::default();
+    let mut updates = Vec::new();
+    if key.is_empty() {
+        return (preview, updates);
+    }
+    for (index, field) in model.fields.iter().enumerate() {
+        if !field.label.trim().eq_ignore_ascii_case(key) {
+            continue;
+        }
+        if field.readonly {
+            preview.readonly += 1;
+            continue;
+        }
+        if only_empty && !field.value.trim().is_empty() {
+            preview.filled += 1;
+            continue;
+        }
+        match filled_field(field, value) {
+            Some(filled) if filled != *field => {
+                preview.changed += 1;
+                updates.push((index, filled));
+            }
+            Some(_) => {}
+            None => preview.invalid += 1,
+        }
+    }
+    (preview, updates)
+}
+
+/// Copy of `field` holding `value` converted for its kind, or `None` when the kind rejects it.
+///
+/// Checkboxes accept yes/no style words, selects and radios only their options, and all
+/// other kinds go through [`validate_field`].
+fn filled_field(field: &ExtraField, value: &str) -> Option<ExtraField> {
+    let value = value.trim();
+    let mut filled = field.clone();
+    match field.kind {
+        ExtraFieldKind::Checkbox => {
+            filled.value = match value.to_ascii_lowercase().as_str() {
+                "on" | "true" | "yes" | "1" | "checked" => "on".into(),
+                "" | "off" | "false" | "no" | "0" => String::new(),
+                _ => return None,
+            };
+        }
+        ExtraFieldKind::Select | ExtraFieldKind::Radio if !field.options.is_empty() => {
+            let wanted = if field.allow_multi_values {
+                split_multi(value)
+            } else if value.is_empty() {
+                Vec::new()
+            } else {
+                vec![value.to_string()]
+            };
+            let chosen = wanted
+                .iter()
+                .map(|w| {
+                    field
+                        .options
+                        .iter()
+                        .find(|opt| opt.eq_ignore_ascii_case(w))
+                        .cloned()
+                })
+                .collect::<Option<Vec<_>>>()?;
+            filled.value = chosen.join(", ");
+            if field.allow_multi_values {
+                filled.value_multi = chosen;
+            }
+        }
+        _ => {
+            filled.value = value.to_string();
+            if field.allow_multi_values {
+                filled.value_multi = split_multi(value);
+            }
+        }
+    }
+    if validate_field(&filled).is_some() {
+        return None;
+    }
+    Some(filled)
+}
+
 /// Determines whether a metadata field fails validation.
 ///
 /// # Returns
@@ -1341,6 +2522,11 @@ fn apply_draft_to_field(draft: &FieldDraft, field: &mut ExtraField) {
     field.readonly = draft.readonly;
     field.allow_multi_values = draft.allow_multi_values;
     field.group_id = draft.group_id;
+    field.default_value = trimmed_or_none(&draft.default_value);
+    field.default_multi = match &field.default_value {
+        Some(default) if field.allow_multi_values => split_multi(default),
+        _ => Vec::new(),
+    };
 
     if matches!(field.kind, ExtraFieldKind::Select | ExtraFieldKind::Radio) {
         field.options = draft.options.clone();
@@ -1383,7 +2569,7 @@ fn render_field_modal(
         .map(|d| name_conflict(model, &d.label, model.editing_field))
         .unwrap_or(false);
 
-    egui::Window::new("Edit field")
+    egui::Window::new(t!("fields.edit"))
         .collapsible(false)
         .resizable(true)
         .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
@@ -1392,7 +2578,7 @@ fn render_field_modal(
 
             ui.set_width(ui.available_width().max(420.0));
 
-            ui.label("Title");
+            ui.label(t!("fields.modal.title"));
             let mut title = draft.label.clone();
             let title_resp = ui.text_edit_singleline(&mut title);
             if title_resp.changed() {
@@ -1413,7 +2599,7 @@ fn render_field_modal(
             } else {
                 egui::Color32::from_rgba_unmultiplied(0, 0, 0, 0)
             };
-            let text = egui::RichText::new("Field name must be unique").color(color);
+            let text = egui::RichText::new(t!("fields.name.duplicate")).color(color);
             ui.scope(|ui| {
                 ui.spacing_mut().item_spacing.y = 0.0;
                 ui.label(text);
@@ -1421,7 +2607,7 @@ fn render_field_modal(
 
             if model.editing_field.is_none() {
                 ui.add_space(8.0);
-                ui.label("Field type");
+                ui.label(t!("fields.modal.kind"));
                 let mut kind = draft.kind.clone();
                 egui::ComboBox::from_id_salt("extra-field-kind")
                     .selected_text(kind_label(&kind))
@@ -1438,7 +2624,7 @@ fn render_field_modal(
             }
 
             ui.add_space(8.0);
-            ui.label("Description");
+            ui.label(t!("fields.modal.description"));
             let mut desc = draft.description.clone();
             if ui
                 .add(egui::TextEdit::multiline(&mut desc).desired_rows(3))
@@ -1447,14 +2633,36 @@ fn render_field_modal(
                 msgs.push(ExtraFieldsMsg::DraftDescChanged(desc));
             }
 
+            ui.add_space(8.0);
+            ui.label(t!("fields.modal.default"));
+            let mut default = draft.default_value.clone();
+            let hint = if draft.allow_multi_values {
+                t!("fields.modal.default.multi")
+            } else {
+                field_hint(&draft.kind)
+            };
+            if ui
+                .add(egui::TextEdit::singleline(&mut default).hint_text(hint))
+                .on_hover_text(t!("fields.modal.default.hover"))
+                .changed()
+            {
+                msgs.push(ExtraFieldsMsg::DraftDefaultValueChanged(default));
+            }
+
             ui.add_space(8.0);
             let mut required = draft.required;
-            if ui.checkbox(&mut required, "Required").changed() {
+            if ui
+                .checkbox(&mut required, t!("fields.modal.required"))
+                .changed()
+            {
                 msgs.push(ExtraFieldsMsg::DraftRequiredToggled(required));
             }
             ui.add_space(4.0);
             let mut readonly = draft.readonly;
-            if ui.checkbox(&mut readonly, "Read-only").changed() {
+            if ui
+                .checkbox(&mut readonly, t!("fields.modal.readonly"))
+                .changed()
+            {
                 msgs.push(ExtraFieldsMsg::DraftReadonlyToggled(readonly));
             }
 
@@ -1462,11 +2670,14 @@ fn render_field_modal(
             match draft.kind {
                 ExtraFieldKind::Select | ExtraFieldKind::Radio => {
                     let mut allow_multi = draft.allow_multi_values;
-                    if ui.checkbox(&mut allow_multi, "Allow multiple").changed() {
+                    if ui
+                        .checkbox(&mut allow_multi, t!("fields.modal.multi"))
+                        .changed()
+                    {
                         msgs.push(ExtraFieldsMsg::DraftAllowMultiToggled(allow_multi));
                     }
                     ui.add_space(6.0);
-                    ui.label("Options");
+                    ui.label(t!("fields.modal.options"));
                     for (i, opt) in draft.options.iter().enumerate() {
                         ui.horizontal(|ui| {
                             let mut v = opt.clone();
@@ -1476,33 +2687,49 @@ fn render_field_modal(
                                     value: v,
                                 });
                             }
-                            if ui.button(egui_phosphor::regular::TRASH).clicked() {
+                            if ui
+                                .button(egui_phosphor::regular::TRASH)
+                                .icon_label(t!("fields.modal.option.remove"))
+                                .clicked()
+                            {
                                 msgs.push(ExtraFieldsMsg::DraftRemoveOption(i));
                             }
                         });
                     }
-                    if ui.button(egui_phosphor::regular::PLUS).clicked() {
+                    if ui
+                        .button(egui_phosphor::regular::PLUS)
+                        .icon_label(t!("fields.modal.option.add"))
+                        .clicked()
+                    {
                         msgs.push(ExtraFieldsMsg::DraftAddOption);
                     }
                 }
                 ExtraFieldKind::Number => {
-                    ui.label("Units");
+                    ui.label(t!("fields.modal.units"));
                     for (i, unit) in draft.units.iter().enumerate() {
                         ui.horizontal(|ui| {
                             let mut v = unit.clone();
                             if ui.text_edit_singleline(&mut v).changed() {
                                 msgs.push(ExtraFieldsMsg::DraftUnitChanged { index: i, value: v });
                             }
-                            if ui.button(egui_phosphor::regular::TRASH).clicked() {
+                            if ui
+                                .button(egui_phosphor::regular::TRASH)
+                                .icon_label(t!("fields.modal.unit.remove"))
+                                .clicked()
+                            {
                                 msgs.push(ExtraFieldsMsg::DraftRemoveUnit(i));
                             }
                         });
                     }
-                    if ui.button(egui_phosphor::regular::PLUS).clicked() {
+                    if ui
+                        .button(egui_phosphor::regular::PLUS)
+                        .icon_label(t!("fields.modal.unit.add"))
+                        .clicked()
+                    {
                         msgs.push(ExtraFieldsMsg::DraftAddUnit);
                     }
                     ui.add_space(6.0);
-                    ui.label("Default unit");
+                    ui.label(t!("fields.modal.unit.default"));
                     let mut unit = draft.unit.clone();
                     if ui.text_edit_singleline(&mut unit).changed() {
                         msgs.push(ExtraFieldsMsg::DraftDefaultUnitChanged(unit));
@@ -1512,11 +2739,11 @@ fn render_field_modal(
             }
 
             ui.add_space(8.0);
-            ui.label("Group assignment");
+            ui.label(t!("fields.modal.group"));
             let mut current = draft.group_id.unwrap_or(-1);
             let display_name = model.display_group_name(draft.group_id);
 
-            egui::ComboBox::from_label("Group")
+            egui::ComboBox::from_label(t!("fields.modal.group.label"))
                 .selected_text(display_name)
                 .show_ui(ui, |ui| {
                     for g in &model.groups {
@@ -1528,52 +2755,197 @@ fn render_field_modal(
 
             ui.add_space(10.0);
             ui.horizontal(|ui| {
-                let save_btn = ui.add_enabled(can_save, egui::Button::new("Save"));
+                let save_btn = ui.add_enabled(can_save, egui::Button::new(t!("common.save")));
                 if save_btn.clicked() && can_save {
                     msgs.push(ExtraFieldsMsg::CommitFieldModal);
                 }
-                if ui.button("Cancel").clicked() {
+                if ui.button(t!("common.cancel")).clicked() {
                     msgs.push(ExtraFieldsMsg::CloseFieldModal);
                 }
             });
         });
 }
 
+/// Dialog for filling one value into every field with a chosen label.
+fn render_bulk_fill_window(
+    ctx: &egui::Context,
+    model: &ExtraFieldsModel,
+    msgs: &mut Vec<ExtraFieldsMsg>,
+) {
+    let Some(draft) = &model.bulk_fill else {
+        return;
+    };
+
+    // Distinct labels (case-insensitive) with how often they occur, in field order.
+    let mut labels: Vec<(String, usize)> = Vec::new();
+    for field in &model.fields {
+        let label = field.label.trim();
+        match labels
+            .iter_mut()
+            .find(|(known, _)| known.eq_ignore_ascii_case(label))
+        {
+            Some((_, count)) => *count += 1,
+            None => labels.push((label.to_string(), 1)),
+        }
+    }
+    let preview = bulk_fill_preview(model, &draft.label, &draft.value, draft.only_empty);
+
+    egui::Window::new(t!("fields.bulk_fill"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            egui::Grid::new("bulk_fill_grid")
+                .num_columns(2)
+                .spacing(egui::vec2(8.0, 6.0))
+                .show(ui, |ui| {
+                    ui.label(t!("fields.bulk_fill.field"));
+                    egui::ComboBox::from_id_salt("bulk_fill_label")
+                        .selected_text(draft.label.clone())
+                        .show_ui(ui, |ui| {
+                            for (label, count) in &labels {
+                                if ui
+                                    .selectable_label(
+                                        label.eq_ignore_ascii_case(draft.label.trim()),
+                                        format!("{label} ({count})"),
+                                    )
+                                    .clicked()
+                                {
+                                    msgs.push(ExtraFieldsMsg::BulkFillLabelChanged(label.clone()));
+                                }
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(t!("fields.bulk_fill.value"));
+                    let mut value = draft.value.clone();
+                    if ui
+                        .add(egui::TextEdit::singleline(&mut value).desired_width(240.0))
+                        .changed()
+                    {
+                        msgs.push(ExtraFieldsMsg::BulkFillValueChanged(value));
+                    }
+                    ui.end_row();
+                });
+
+            let mut only_empty = draft.only_empty;
+            if ui
+                .checkbox(&mut only_empty, t!("fields.bulk_fill.only_empty"))
+                .changed()
+            {
+                msgs.push(ExtraFieldsMsg::BulkFillOnlyEmptyToggled(only_empty));
+            }
+
+            ui.add_space(6.0);
+            ui.label(t!("fields.bulk_fill.changes", count = preview.changed));
+            let skipped = [
+                (preview.readonly, t!("fields.bulk_fill.readonly")),
+                (preview.filled, t!("fields.bulk_fill.filled")),
+                (preview.invalid, t!("fields.bulk_fill.invalid")),
+            ];
+            for (count, reason) in skipped.into_iter().filter(|(count, _)| *count > 0) {
+                ui.label(
+                    egui::RichText::new(t!(
+                        "fields.bulk_fill.skipped",
+                        count = count,
+                        reason = reason
+                    ))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
+                );
+            }
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(preview.changed > 0, egui::Button::new(t!("common.apply")))
+                    .clicked()
+                {
+                    msgs.push(ExtraFieldsMsg::BulkFill {
+                        label: draft.label.clone(),
+                        value: draft.value.clone(),
+                        only_empty: draft.only_empty,
+                    });
+                }
+                if ui.button(t!("common.cancel")).clicked() {
+                    msgs.push(ExtraFieldsMsg::CloseBulkFill);
+                }
+            });
+        });
+}
+
+/// Ask how to apply a re-imported file, if one is pending.
+fn render_reimport_window(
+    ctx: &egui::Context,
+    model: &ExtraFieldsModel,
+    msgs: &mut Vec<ExtraFieldsMsg>,
+) {
+    let Some(pending) = &model.reimport else {
+        return;
+    };
+
+    egui::Window::new(t!("fields.reimport.title"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            let name = pending.source.file_name();
+            ui.label(if pending.changed {
+                t!("fields.reimport.changed", name = name)
+            } else {
+                t!("fields.reimport.unchanged", name = name)
+            });
+            ui.label(t!(
+                "fields.reimport.counts",
+                count = pending.fields.len(),
+                current = model.fields.len()
+            ));
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui
+                    .button(t!("fields.reimport.keep"))
+                    .on_hover_text(t!("fields.reimport.keep.hover"))
+                    .clicked()
+                {
+                    msgs.push(ExtraFieldsMsg::ApplyReimport(ReimportMode::KeepValues));
+                }
+                if ui
+                    .button(t!("fields.reimport.replace"))
+                    .on_hover_text(t!("fields.reimport.replace.hover"))
+                    .clicked()
+                {
+                    msgs.push(ExtraFieldsMsg::ApplyReimport(ReimportMode::Replace));
+                }
+                if ui.button(t!("common.cancel")).clicked() {
+                    msgs.push(ExtraFieldsMsg::CloseReimport);
+                }
+            });
+        });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::path::PathBuf;
 
-    /// Creates a new `ExtraField` with the given label and kind, initialized with empty/default values.
-    ///
-    /// The returned field has no value, options, units, or group assigned and is not required or readonly.
+    /// Creates an `ExtraField` with the given label, kind and value and every other property
+    /// at its default.
     ///
     /// # Examples
     ///
     /// ```rust,ignore
-    /// let f = make_field("Temperature", ExtraFieldKind::Number);
-    /// assert_eq!(f.label, "Temperature");
-    /// assert_eq!(f.kind, ExtraFieldKind::Number);
-    /// assert!(f.value.is_empty());
+    /// let f = field("Temperature", ExtraFieldKind::Number, "37");
+    /// assert_eq!(f.value, "37");
     /// assert!(f.options.is_empty());
     /// assert!(!f.required);
     /// ```
-    fn make_field(label: &str, kind: ExtraFieldKind) -> ExtraField {
+    fn field(label: &str, kind: ExtraFieldKind, value: &str) -> ExtraField {
         ExtraField {
             label: label.into(),
             kind,
-            value: String::new(),
-            value_multi: Vec::new(),
-            options: vec![],
-            unit: None,
-            units: vec![],
-            position: None,
-            required: false,
-            description: None,
-            allow_multi_values: false,
-            blank_value_on_duplicate: false,
-            group_id: None,
-            readonly: false,
+            value: value.into(),
+            ..ExtraField::default()
         }
     }
 
@@ -1595,29 +2967,113 @@ mod tests {
         }
     }
 
+    #[test]
+    fn missing_values_are_reported_once_touched_or_saved() {
+        let mut name = field("Operator", ExtraFieldKind::Text, "");
+        name.required = true;
+        let amount = field("Amount", ExtraFieldKind::Number, "lots");
+        let mut model = ExtraFieldsModel {
+            fields: vec![name, amount],
+            ..Default::default()
+        };
+        let mut cmds = Vec::new();
+        assert_eq!(
+            model.inline_error(&model.fields[0]),
+            None,
+            "not yet touched"
+        );
+        assert_eq!(
+            model.inline_error(&model.fields[1]).as_deref(),
+            Some("This field must be a valid number.")
+        );
+
+        for value in ["A", ""] {
+            let msg = ExtraFieldsMsg::EditValue {
+                index: 0,
+                value: value.into(),
+            };
+            let _ = update(&mut model, msg, &mut cmds);
+        }
+        assert_eq!(
+            model.inline_error(&model.fields[0]).as_deref(),
+            Some("This field is required.")
+        );
+
+        let mut other = field("Sample", ExtraFieldKind::Text, "");
+        other.required = true;
+        model.fields.push(other);
+        assert_eq!(model.inline_error(&model.fields[2]), None);
+        let _ = update(&mut model, ExtraFieldsMsg::SaveAttempted, &mut cmds);
+        assert_eq!(
+            model.inline_error(&model.fields[2]).as_deref(),
+            Some("This field is required.")
+        );
+    }
+
+    #[test]
+    fn imports_wait_for_the_field_editor_to_close() {
+        let mut model = ExtraFieldsModel {
+            fields: vec![field("Old", ExtraFieldKind::Text, "")],
+            groups: vec![make_group(1, "General"), make_group(2, "Extra")],
+            ..Default::default()
+        };
+        let mut cmds = Vec::new();
+        update(&mut model, ExtraFieldsMsg::OpenFieldModal(0), &mut cmds);
+        update(
+            &mut model,
+            ExtraFieldsMsg::DraftLabelChanged("Operator".into()),
+            &mut cmds,
+        );
+        let import = |label: &str| ExtraFieldsMsg::ImportLoaded {
+            fields: vec![field(label, ExtraFieldKind::Text, "")],
+            groups: Vec::new(),
+            source: ExtraFieldsSource::new(
+                PathBuf::from("sample.json"),
+                label,
+                OffsetDateTime::UNIX_EPOCH,
+            ),
+            tags: Vec::new(),
+            hide_main_text: false,
+        };
+
+        let event = update(&mut model, import("First"), &mut cmds).unwrap();
+        assert!(event.message.contains("field editor"), "{}", event.message);
+        update(&mut model, import("Second"), &mut cmds);
+        let event = update(&mut model, ExtraFieldsMsg::RemoveGroup(1), &mut cmds).unwrap();
+        assert!(event.is_error);
+        assert_eq!(model.groups.len(), 2, "groups stay while editing");
+        assert_eq!(model.fields[0].label, "Old");
+        assert_eq!(model.modal_draft.as_ref().unwrap().label, "Operator");
+
+        let event = update(&mut model, ExtraFieldsMsg::CommitFieldModal, &mut cmds).unwrap();
+        assert!(
+            event.message.contains("Imported 1 field"),
+            "{}",
+            event.message
+        );
+        assert_eq!(model.fields.len(), 1);
+        assert_eq!(model.fields[0].label, "Second", "the latest import wins");
+        assert!(model.deferred.is_none());
+    }
+
     #[test]
     fn import_loaded_populates_model() {
         let mut model = ExtraFieldsModel::default();
         let mut cmds = Vec::new();
         let msg = ExtraFieldsMsg::ImportLoaded {
             fields: vec![ExtraField {
-                label: "Example".into(),
-                kind: ExtraFieldKind::Text,
-                value: "value".into(),
-                value_multi: Vec::new(),
-                options: vec![],
-                unit: None,
-                units: vec![],
                 position: Some(2),
                 required: true,
-                description: None,
-                allow_multi_values: false,
-                blank_value_on_duplicate: false,
-                group_id: None,
-                readonly: false,
+                ..field("Example", ExtraFieldKind::Text, "value")
             }],
             groups: vec![],
-            source: PathBuf::from("sample.json"),
+            source: ExtraFieldsSource::new(
+                PathBuf::from("sample.json"),
+                "{}",
+                OffsetDateTime::UNIX_EPOCH,
+            ),
+            tags: Vec::new(),
+            hide_main_text: false,
         };
 
         let event = update(&mut model, msg, &mut cmds).unwrap();
@@ -1628,20 +3084,256 @@ mod tests {
     }
 
     #[test]
-    fn required_empty_marks_invalid() {
+    fn edited_values_can_be_reset_to_the_template_default() {
+        let json = r#"{"extra_fields":{"Buffer":{"type":"text","default_value":"PBS"}}}"#;
+        let import = crate::models::extra_fields::parse_elabftw_extra_fields(json).unwrap();
         let mut model = ExtraFieldsModel::default();
-        let mut f = make_field("Req", ExtraFieldKind::Text);
-        f.required = true;
-        model.fields.push(f);
-
-        assert!(model.has_invalid_fields());
-    }
-
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            ExtraFieldsMsg::ImportLoaded {
+                fields: import.fields,
+                groups: import.groups,
+                source: ExtraFieldsSource::new(
+                    PathBuf::from("buffer.json"),
+                    json,
+                    OffsetDateTime::UNIX_EPOCH,
+                ),
+                tags: Vec::new(),
+                hide_main_text: false,
+            },
+            &mut cmds,
+        );
+        assert_eq!(model.fields[0].value, "PBS");
+        assert!(!model.fields[0].differs_from_default());
+
+        update(
+            &mut model,
+            ExtraFieldsMsg::EditValue {
+                index: 0,
+                value: "Tris".into(),
+            },
+            &mut cmds,
+        );
+        assert!(model.fields[0].differs_from_default());
+        update(&mut model, ExtraFieldsMsg::ResetToDefault(0), &mut cmds);
+        assert_eq!(model.fields[0].value, "PBS");
+
+        // Fields created by hand take their default from the editor and start with it.
+        update(
+            &mut model,
+            ExtraFieldsMsg::StartAddField { group_id: None },
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            ExtraFieldsMsg::DraftLabelChanged("Volume".into()),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            ExtraFieldsMsg::DraftDefaultValueChanged(" 50 ".into()),
+            &mut cmds,
+        );
+        update(&mut model, ExtraFieldsMsg::CommitFieldModal, &mut cmds);
+        let volume = model.fields.iter().find(|f| f.label == "Volume").unwrap();
+        assert_eq!(volume.default_value.as_deref(), Some("50"));
+        assert_eq!(volume.value, "50");
+    }
+
+    #[test]
+    fn reimport_keeps_values_of_matching_fields_and_local_additions() {
+        let mut model = ExtraFieldsModel::default();
+        let source = |content: &str| {
+            ExtraFieldsSource::new(
+                PathBuf::from("/templates/template_v3.json"),
+                content,
+                OffsetDateTime::UNIX_EPOCH,
+            )
+        };
+        let mut temperature = field("Temperature", ExtraFieldKind::Number, "");
+        temperature.group_id = Some(1);
+        temperature.units = vec!["K".into(), "°C".into()];
+        temperature.unit = Some("K".into());
+        update(
+            &mut model,
+            ExtraFieldsMsg::ImportLoaded {
+                fields: vec![
+                    temperature.clone(),
+                    field("Operator", ExtraFieldKind::Text, ""),
+                ],
+                groups: vec![make_group(1, "Conditions")],
+                source: source("v3"),
+                tags: Vec::new(),
+                hide_main_text: false,
+            },
+            &mut Vec::new(),
+        );
+        for (index, value) in [(0, "Ada"), (1, "21")] {
+            update(
+                &mut model,
+                ExtraFieldsMsg::EditValue {
+                    index,
+                    value: value.into(),
+                },
+                &mut Vec::new(),
+            );
+        }
+        update(
+            &mut model,
+            ExtraFieldsMsg::SelectUnit {
+                index: 1,
+                unit: "°C".into(),
+            },
+            &mut Vec::new(),
+        );
+        model.fields.push(field("Notes", ExtraFieldKind::Text, ""));
+
+        let mut cmds = Vec::new();
+        update(&mut model, ExtraFieldsMsg::ReimportRequested, &mut cmds);
+        assert_eq!(
+            cmds,
+            [ExtraFieldsCommand::ReadMetadataFile(PathBuf::from(
+                "/templates/template_v3.json"
+            ))]
+        );
+
+        let mut newer = temperature;
+        newer.group_id = Some(7);
+        newer.description = Some("Bath temperature".into());
+        let mut sample = field("Sample", ExtraFieldKind::Text, "");
+        sample.position = Some(0);
+        update(
+            &mut model,
+            ExtraFieldsMsg::ReimportLoaded {
+                fields: vec![sample, newer],
+                groups: vec![make_group(7, "conditions")],
+                source: source("v4"),
+            },
+            &mut Vec::new(),
+        );
+        assert!(model.reimport.as_ref().unwrap().changed);
+
+        let event = update(
+            &mut model,
+            ExtraFieldsMsg::ApplyReimport(ReimportMode::KeepValues),
+            &mut Vec::new(),
+        )
+        .unwrap();
+        assert!(
+            event.message.contains("template_v3.json"),
+            "{}",
+            event.message
+        );
+        assert!(model.reimport.is_none());
+
+        let summary: Vec<(&str, &str, Option<&str>)> = model
+            .fields()
+            .iter()
+            .map(|f| (f.label.as_str(), f.value.as_str(), f.unit.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("Sample", "", None),
+                ("Notes", "", None),
+                ("Operator", "Ada", None),
+                ("Temperature", "21", Some("°C")),
+            ]
+        );
+        assert_eq!(
+            model.fields()[3].description.as_deref(),
+            Some("Bath temperature")
+        );
+        assert_eq!(model.source().unwrap(), &source("v4"));
+    }
+
+    #[test]
+    fn reimport_replace_discards_edits_and_unchanged_files_are_noticed() {
+        let mut model = ExtraFieldsModel::default();
+        let source =
+            ExtraFieldsSource::new(PathBuf::from("t.json"), "same", OffsetDateTime::UNIX_EPOCH);
+        let field = field("Operator", ExtraFieldKind::Text, "");
+        update(
+            &mut model,
+            ExtraFieldsMsg::ImportLoaded {
+                fields: vec![field.clone()],
+                groups: Vec::new(),
+                source: source.clone(),
+                tags: Vec::new(),
+                hide_main_text: false,
+            },
+            &mut Vec::new(),
+        );
+        update(
+            &mut model,
+            ExtraFieldsMsg::EditValue {
+                index: 0,
+                value: "Ada".into(),
+            },
+            &mut Vec::new(),
+        );
+        update(
+            &mut model,
+            ExtraFieldsMsg::ReimportLoaded {
+                fields: vec![field],
+                groups: Vec::new(),
+                source,
+            },
+            &mut Vec::new(),
+        );
+        assert!(!model.reimport.as_ref().unwrap().changed);
+
+        update(
+            &mut model,
+            ExtraFieldsMsg::ApplyReimport(ReimportMode::Replace),
+            &mut Vec::new(),
+        );
+        assert_eq!(model.fields()[0].value, "");
+    }
+
+    #[test]
+    fn import_age_is_rounded_down_to_the_largest_unit() {
+        assert_eq!(time_ago(100, 130), "just now");
+        assert_eq!(time_ago(0, 125), "2 min ago");
+        assert_eq!(time_ago(0, 2 * 3600 + 59), "2 h ago");
+        assert_eq!(time_ago(0, 3 * 86400), "3 d ago");
+        assert_eq!(time_ago(500, 0), "just now");
+    }
+
+    #[test]
+    fn section_title_counts_fields_and_invalid_ones() {
+        let mut model = ExtraFieldsModel::default();
+        assert_eq!(section_title(&model).to_string(), "Metadata");
+
+        model.fields.push(field("Note", ExtraFieldKind::Text, ""));
+        assert_eq!(section_title(&model).to_string(), "Metadata (1 field)");
+
+        for label in ["Count", "Volume"] {
+            model
+                .fields
+                .push(field(label, ExtraFieldKind::Number, "many"));
+        }
+        let title = section_title(&model);
+        assert_eq!(title.to_string(), "Metadata (3 fields, 2 invalid)");
+        assert_eq!(title.problems.as_deref(), Some("2 invalid"));
+        assert!(title.blocker.is_some(), "invalid fields block saving");
+    }
+
+    #[test]
+    fn required_empty_marks_invalid() {
+        let mut model = ExtraFieldsModel::default();
+        let mut f = field("Req", ExtraFieldKind::Text, "");
+        f.required = true;
+        model.fields.push(f);
+
+        assert!(model.has_invalid_fields());
+    }
+
     #[test]
     fn invalid_number_marks_invalid() {
         let mut model = ExtraFieldsModel::default();
-        let mut f = make_field("Num", ExtraFieldKind::Number);
-        f.value = "abc".into();
+        let f = field("Num", ExtraFieldKind::Number, "abc");
         model.fields.push(f);
 
         assert!(model.has_invalid_fields());
@@ -1650,8 +3342,7 @@ mod tests {
     #[test]
     fn valid_integer_id_is_accepted() {
         let mut model = ExtraFieldsModel::default();
-        let mut f = make_field("ID", ExtraFieldKind::Users);
-        f.value = "123".into();
+        let f = field("ID", ExtraFieldKind::Users, "123");
         model.fields.push(f);
 
         assert!(!model.has_invalid_fields());
@@ -1692,22 +3383,7 @@ mod tests {
     #[test]
     fn remove_field_drops_entry() {
         let mut model = ExtraFieldsModel::default();
-        model.fields.push(ExtraField {
-            label: "One".into(),
-            kind: ExtraFieldKind::Text,
-            value: "a".into(),
-            value_multi: Vec::new(),
-            options: vec![],
-            unit: None,
-            units: vec![],
-            position: None,
-            required: false,
-            description: None,
-            allow_multi_values: false,
-            blank_value_on_duplicate: false,
-            group_id: None,
-            readonly: false,
-        });
+        model.fields.push(field("One", ExtraFieldKind::Text, "a"));
 
         let _ = update(&mut model, ExtraFieldsMsg::RemoveField(0), &mut Vec::new());
         assert!(model.fields.is_empty());
@@ -1719,20 +3395,10 @@ mod tests {
     fn modal_save_updates_field() {
         let mut model = ExtraFieldsModel::default();
         model.fields.push(ExtraField {
-            label: "Old".into(),
-            kind: ExtraFieldKind::Select,
-            value: "A".into(),
             value_multi: vec!["A".into()],
             options: vec!["A".into(), "B".into()],
-            unit: None,
-            units: vec![],
-            position: None,
-            required: false,
             description: Some("desc".into()),
-            allow_multi_values: false,
-            blank_value_on_duplicate: false,
-            group_id: None,
-            readonly: false,
+            ..field("Old", ExtraFieldKind::Select, "A")
         });
         let mut cmds = Vec::new();
         let _ = update(&mut model, ExtraFieldsMsg::OpenFieldModal(0), &mut cmds);
@@ -1818,20 +3484,9 @@ mod tests {
     fn modal_cancel_keeps_field() {
         let mut model = ExtraFieldsModel::default();
         model.fields.push(ExtraField {
-            label: "Old".into(),
-            kind: ExtraFieldKind::Number,
-            value: "1".into(),
-            value_multi: Vec::new(),
-            options: vec![],
             unit: Some("m".into()),
             units: vec!["m".into()],
-            position: None,
-            required: false,
-            description: None,
-            allow_multi_values: false,
-            blank_value_on_duplicate: false,
-            group_id: None,
-            readonly: false,
+            ..field("Old", ExtraFieldKind::Number, "1")
         });
         let mut cmds = Vec::new();
         let _ = update(&mut model, ExtraFieldsMsg::OpenFieldModal(0), &mut cmds);
@@ -1848,22 +3503,7 @@ mod tests {
     #[test]
     fn duplicate_name_blocks_new_field() {
         let mut model = ExtraFieldsModel::default();
-        model.fields.push(ExtraField {
-            label: "Name".into(),
-            kind: ExtraFieldKind::Text,
-            value: "".into(),
-            value_multi: Vec::new(),
-            options: vec![],
-            unit: None,
-            units: vec![],
-            position: None,
-            required: false,
-            description: None,
-            allow_multi_values: false,
-            blank_value_on_duplicate: false,
-            group_id: None,
-            readonly: false,
-        });
+        model.fields.push(field("Name", ExtraFieldKind::Text, ""));
         let mut cmds = Vec::new();
         let _ = update(
             &mut model,
@@ -1894,38 +3534,8 @@ mod tests {
     #[test]
     fn duplicate_name_blocks_edit() {
         let mut model = ExtraFieldsModel::default();
-        model.fields.push(ExtraField {
-            label: "First".into(),
-            kind: ExtraFieldKind::Text,
-            value: "".into(),
-            value_multi: Vec::new(),
-            options: vec![],
-            unit: None,
-            units: vec![],
-            position: None,
-            required: false,
-            description: None,
-            allow_multi_values: false,
-            blank_value_on_duplicate: false,
-            group_id: None,
-            readonly: false,
-        });
-        model.fields.push(ExtraField {
-            label: "Second".into(),
-            kind: ExtraFieldKind::Text,
-            value: "".into(),
-            value_multi: Vec::new(),
-            options: vec![],
-            unit: None,
-            units: vec![],
-            position: None,
-            required: false,
-            description: None,
-            allow_multi_values: false,
-            blank_value_on_duplicate: false,
-            group_id: None,
-            readonly: false,
-        });
+        model.fields.push(field("First", ExtraFieldKind::Text, ""));
+        model.fields.push(field("Second", ExtraFieldKind::Text, ""));
 
         let mut cmds = Vec::new();
         let _ = update(&mut model, ExtraFieldsMsg::OpenFieldModal(1), &mut cmds);
@@ -1950,20 +3560,8 @@ mod tests {
             position: 0,
         });
         model.fields.push(ExtraField {
-            label: "F".into(),
-            kind: ExtraFieldKind::Text,
-            value: "v".into(),
-            value_multi: Vec::new(),
-            options: vec![],
-            unit: None,
-            units: vec![],
-            position: None,
-            required: false,
-            description: None,
-            allow_multi_values: false,
-            blank_value_on_duplicate: false,
             group_id: Some(1),
-            readonly: false,
+            ..field("F", ExtraFieldKind::Text, "v")
         });
 
         let mut cmds = Vec::new();
@@ -1983,20 +3581,8 @@ mod tests {
             position: 0,
         });
         model.fields.push(ExtraField {
-            label: "F".into(),
-            kind: ExtraFieldKind::Text,
-            value: "v".into(),
-            value_multi: Vec::new(),
-            options: vec![],
-            unit: None,
-            units: vec![],
-            position: None,
-            required: false,
-            description: None,
-            allow_multi_values: false,
-            blank_value_on_duplicate: false,
             group_id: Some(7),
-            readonly: false,
+            ..field("F", ExtraFieldKind::Text, "v")
         });
 
         let mut cmds = Vec::new();
@@ -2021,20 +3607,8 @@ mod tests {
             position: 1,
         });
         model.fields.push(ExtraField {
-            label: "F".into(),
-            kind: ExtraFieldKind::Text,
-            value: "v".into(),
-            value_multi: Vec::new(),
-            options: vec![],
-            unit: None,
-            units: vec![],
-            position: None,
-            required: false,
-            description: None,
-            allow_multi_values: false,
-            blank_value_on_duplicate: false,
             group_id: Some(2),
-            readonly: false,
+            ..field("F", ExtraFieldKind::Text, "v")
         });
 
         let mut cmds = Vec::new();
@@ -2049,4 +3623,427 @@ mod tests {
             .id;
         assert_eq!(model.fields[0].group_id, Some(default_id));
     }
+
+    #[test]
+    fn group_names_stay_unique_when_renaming_or_adding() {
+        let mut model = ExtraFieldsModel {
+            groups: vec![make_group(1, "General"), make_group(2, "Group 3")],
+            ..Default::default()
+        };
+        let mut cmds = Vec::new();
+
+        update(&mut model, ExtraFieldsMsg::StartEditGroup(1), &mut cmds);
+        update(
+            &mut model,
+            ExtraFieldsMsg::EditGroupName(" general ".into()),
+            &mut cmds,
+        );
+        let event = update(&mut model, ExtraFieldsMsg::CommitGroupName, &mut cmds).unwrap();
+        assert_eq!(model.groups[1].name, "general (2)");
+        assert!(event.message.contains("'general (2)'"), "{}", event.message);
+        assert!(!event.is_error);
+
+        update(&mut model, ExtraFieldsMsg::StartEditGroup(0), &mut cmds);
+        let event = update(&mut model, ExtraFieldsMsg::CommitGroupName, &mut cmds);
+        assert_eq!(event, None, "keeping its own name is no conflict");
+        assert_eq!(model.groups[0].name, "General");
+
+        model.groups[1].name = "Group 3".into();
+        let event = update(&mut model, ExtraFieldsMsg::AddGroup, &mut cmds).unwrap();
+        assert_eq!(model.groups[2].name, "Group 3 (2)");
+        assert!(event.message.contains("'Group 3'"), "{}", event.message);
+    }
+
+    #[test]
+    fn import_merges_groups_with_the_same_name() {
+        let mut model = ExtraFieldsModel::default();
+        let mut first = field("Temperature", ExtraFieldKind::Number, "");
+        first.group_id = Some(1);
+        let mut second = field("Operator", ExtraFieldKind::Text, "");
+        second.group_id = Some(2);
+        let mut other = field("Lot", ExtraFieldKind::Text, "");
+        other.group_id = Some(3);
+        let mut cmds = Vec::new();
+
+        let event = update(
+            &mut model,
+            ExtraFieldsMsg::ImportLoaded {
+                fields: vec![first, second, other],
+                groups: vec![
+                    make_group(1, "General"),
+                    make_group(2, "general "),
+                    make_group(3, "Reagents"),
+                ],
+                source: ExtraFieldsSource {
+                    path: PathBuf::from("/tmp/meta.json"),
+                    imported_at: 0,
+                    sha256: String::new(),
+                },
+                tags: Vec::new(),
+                hide_main_text: false,
+            },
+            &mut cmds,
+        )
+        .unwrap();
+
+        let names: Vec<&str> = model.groups.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, ["General", "Reagents"]);
+        let group_of = |label: &str| {
+            model
+                .fields
+                .iter()
+                .find(|f| f.label == label)
+                .unwrap()
+                .group_id
+        };
+        assert_eq!(group_of("Temperature"), Some(1));
+        assert_eq!(group_of("Operator"), Some(1));
+        assert_eq!(group_of("Lot"), Some(3));
+        assert!(
+            event
+                .message
+                .ends_with("Merged groups with the same name: General."),
+            "{}",
+            event.message
+        );
+    }
+
+    #[test]
+    fn duplicated_fields_get_unique_labels_after_the_original() {
+        let mut first = field("Absorbance", ExtraFieldKind::Number, "0.42");
+        first.unit = Some("AU".into());
+        first.group_id = Some(3);
+        let mut model = ExtraFieldsModel {
+            fields: vec![first, field("Operator", ExtraFieldKind::Text, "")],
+            ..Default::default()
+        };
+        let mut cmds = Vec::new();
+
+        update(&mut model, ExtraFieldsMsg::DuplicateField(0), &mut cmds);
+        update(&mut model, ExtraFieldsMsg::CloseFieldModal, &mut cmds);
+        update(&mut model, ExtraFieldsMsg::DuplicateField(0), &mut cmds);
+
+        let labels: Vec<&str> = model.fields.iter().map(|f| f.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            [
+                "Absorbance",
+                "Absorbance (copy 2)",
+                "Absorbance (copy)",
+                "Operator"
+            ]
+        );
+        let positions: Vec<Option<i32>> = model.fields.iter().map(|f| f.position).collect();
+        assert_eq!(positions, [Some(0), Some(1), Some(2), Some(3)]);
+        let copy = &model.fields[1];
+        assert_eq!(copy.value, "0.42");
+        assert_eq!(copy.unit.as_deref(), Some("AU"));
+        assert_eq!(copy.group_id, Some(3));
+        assert!(model.modal_open);
+        assert_eq!(model.editing_field, Some(1), "the copy opens for editing");
+    }
+
+    #[test]
+    fn duplicating_blanks_values_when_flagged() {
+        let mut sample = field("Sample", ExtraFieldKind::Select, "S-17");
+        sample.value_multi = vec!["S-17".into(), "S-18".into()];
+        sample.blank_value_on_duplicate = true;
+        let mut model = ExtraFieldsModel {
+            fields: vec![sample],
+            ..Default::default()
+        };
+
+        update(
+            &mut model,
+            ExtraFieldsMsg::DuplicateField(0),
+            &mut Vec::new(),
+        );
+
+        assert_eq!(
+            model.fields[0].value, "S-17",
+            "the original keeps its value"
+        );
+        assert_eq!(model.fields[1].label, "Sample (copy)");
+        assert!(model.fields[1].value.is_empty());
+        assert!(model.fields[1].value_multi.is_empty());
+    }
+
+    #[test]
+    fn moving_groups_renumbers_positions_and_keeps_fields() {
+        let mut field = field("Operator", ExtraFieldKind::Text, "");
+        field.group_id = Some(1);
+        let mut model = ExtraFieldsModel {
+            fields: vec![field],
+            groups: vec![
+                make_group(1, "General"),
+                make_group(2, "Sample"),
+                make_group(3, "Results"),
+            ],
+            ..Default::default()
+        };
+        let mut cmds = Vec::new();
+
+        update(
+            &mut model,
+            ExtraFieldsMsg::MoveGroup { from: 0, to: 2 },
+            &mut cmds,
+        );
+        let order: Vec<(i32, i32)> = model.groups.iter().map(|g| (g.id, g.position)).collect();
+        assert_eq!(order, [(2, 0), (3, 1), (1, 2)]);
+        assert_eq!(model.fields[0].group_id, Some(1));
+
+        update(
+            &mut model,
+            ExtraFieldsMsg::MoveGroup { from: 2, to: 1 },
+            &mut cmds,
+        );
+        let ids: Vec<i32> = model.groups.iter().map(|g| g.id).collect();
+        assert_eq!(ids, [2, 1, 3]);
+    }
+
+    #[test]
+    fn moving_the_only_group_or_out_of_range_changes_nothing() {
+        let mut model = ExtraFieldsModel {
+            groups: vec![ExtraFieldGroup {
+                id: 4,
+                name: "Solo".into(),
+                position: 7,
+            }],
+            ..Default::default()
+        };
+        let mut cmds = Vec::new();
+
+        update(
+            &mut model,
+            ExtraFieldsMsg::MoveGroup { from: 0, to: 3 },
+            &mut cmds,
+        );
+        assert_eq!(model.groups.len(), 1);
+        assert_eq!((model.groups[0].id, model.groups[0].position), (4, 0));
+
+        update(
+            &mut model,
+            ExtraFieldsMsg::MoveGroup { from: 5, to: 0 },
+            &mut cmds,
+        );
+        assert_eq!(model.groups[0].id, 4);
+    }
+
+    #[test]
+    fn bulk_fill_respects_kinds_readonly_and_only_empty() {
+        let mut model = ExtraFieldsModel::default();
+        let text = field("Operator", ExtraFieldKind::Text, "");
+        let filled_text = field("operator", ExtraFieldKind::Text, "Someone");
+        let readonly = ExtraField {
+            readonly: true,
+            ..field("Operator", ExtraFieldKind::Text, "")
+        };
+        let select = ExtraField {
+            options: vec!["Ada".into(), "Grace".into()],
+            ..field("Operator", ExtraFieldKind::Select, "")
+        };
+        let number = field("Operator", ExtraFieldKind::Number, "");
+        let email = field("Operator", ExtraFieldKind::Email, "");
+        let other = field("Sample", ExtraFieldKind::Text, "");
+        update(
+            &mut model,
+            ExtraFieldsMsg::Restore {
+                fields: vec![text, filled_text, readonly, select, number, email, other],
+                groups: Vec::new(),
+                source: None,
+                hide_main_text: false,
+            },
+            &mut Vec::new(),
+        );
+
+        assert_eq!(
+            bulk_fill_preview(&model, "OPERATOR", "grace", true),
+            BulkFillPreview {
+                changed: 2,
+                readonly: 1,
+                filled: 1,
+                invalid: 2,
+            }
+        );
+
+        let event = update(
+            &mut model,
+            ExtraFieldsMsg::BulkFill {
+                label: "Operator".into(),
+                value: "grace".into(),
+                only_empty: false,
+            },
+            &mut Vec::new(),
+        )
+        .expect("bulk fill reports");
+        assert!(
+            event.message.starts_with("Filled 3 field(s)"),
+            "{}",
+            event.message
+        );
+
+        let values: Vec<&str> = model.fields().iter().map(|f| f.value.as_str()).collect();
+        assert_eq!(values, ["grace", "grace", "", "Grace", "", "", ""]);
+    }
+
+    #[test]
+    fn bulk_fill_converts_checkbox_and_multi_select_values() {
+        let mut model = ExtraFieldsModel::default();
+        let multi = ExtraField {
+            options: vec!["a".into(), "b".into(), "c".into()],
+            allow_multi_values: true,
+            ..field("Flags", ExtraFieldKind::Select, "")
+        };
+        let checkbox = field("Flags", ExtraFieldKind::Checkbox, "");
+        update(
+            &mut model,
+            ExtraFieldsMsg::Restore {
+                fields: vec![multi, checkbox],
+                groups: Vec::new(),
+                source: None,
+                hide_main_text: false,
+            },
+            &mut Vec::new(),
+        );
+
+        assert_eq!(bulk_fill_preview(&model, "Flags", "a, C", false).changed, 1);
+        update(
+            &mut model,
+            ExtraFieldsMsg::BulkFill {
+                label: "Flags".into(),
+                value: "yes".into(),
+                only_empty: false,
+            },
+            &mut Vec::new(),
+        );
+        assert_eq!(model.fields()[1].value, "on");
+
+        update(
+            &mut model,
+            ExtraFieldsMsg::BulkFill {
+                label: "Flags".into(),
+                value: "a, C".into(),
+                only_empty: false,
+            },
+            &mut Vec::new(),
+        );
+        assert_eq!(model.fields()[0].value_multi, vec!["a", "c"]);
+        assert_eq!(model.fields()[1].value, "on");
+    }
+
+    #[test]
+    fn filter_matches_label_description_and_group_keeping_indices() {
+        let mut power = field("Laser power", ExtraFieldKind::Number, "");
+        power.group_id = Some(1);
+        let mut wavelength = field("Wavelength", ExtraFieldKind::Number, "");
+        wavelength.group_id = Some(1);
+        wavelength.description = Some("Of the excitation LASER".into());
+        let mut operator = field("Operator", ExtraFieldKind::Text, "");
+        operator.group_id = Some(2);
+        let mut model = ExtraFieldsModel {
+            fields: vec![operator, power, wavelength],
+            groups: vec![make_group(1, "Optics"), make_group(2, "Sample")],
+            ..Default::default()
+        };
+        let mut cmds = Vec::new();
+        let visible = |model: &ExtraFieldsModel, group: usize| -> Vec<usize> {
+            let group = &model.groups[group];
+            model
+                .visible_fields(group)
+                .iter()
+                .map(|(idx, _)| *idx)
+                .collect()
+        };
+
+        update(
+            &mut model,
+            ExtraFieldsMsg::FilterChanged(" laser ".into()),
+            &mut cmds,
+        );
+        assert_eq!(visible(&model, 0), [1, 2]);
+        assert!(visible(&model, 1).is_empty());
+
+        update(
+            &mut model,
+            ExtraFieldsMsg::FilterChanged("SAMP".into()),
+            &mut cmds,
+        );
+        assert!(visible(&model, 0).is_empty());
+        assert_eq!(
+            visible(&model, 1),
+            [0],
+            "group names match all their fields"
+        );
+
+        update(&mut model, ExtraFieldsMsg::ClearFilter, &mut cmds);
+        assert_eq!(visible(&model, 0), [1, 2]);
+        assert_eq!(visible(&model, 1), [0]);
+    }
+
+    #[test]
+    fn match_ranges_ignore_case_and_do_not_overlap() {
+        let ranges = |text: &str, needle: &str| -> Vec<(usize, usize)> {
+            match_ranges(text, needle)
+                .into_iter()
+                .map(|range| (range.start, range.end))
+                .collect()
+        };
+        assert_eq!(ranges("Laser power", "POWER"), [(6, 11)]);
+        assert_eq!(ranges("aaaa", "aa"), [(0, 2), (2, 4)]);
+        assert!(ranges("Größe", "SSE").is_empty());
+        assert_eq!(ranges("ÄRGER", "är"), [(0, 3)]);
+        assert!(ranges("anything", "").is_empty());
+    }
+
+    #[test]
+    fn groups_expand_and_collapse_individually_or_all_at_once() {
+        let mut model = ExtraFieldsModel {
+            groups: vec![make_group(1, "Optics"), make_group(2, "Sample")],
+            ..Default::default()
+        };
+        let mut cmds = Vec::new();
+
+        update(
+            &mut model,
+            ExtraFieldsMsg::SetGroupOpen {
+                group_id: 2,
+                open: false,
+            },
+            &mut cmds,
+        );
+        assert_eq!(model.collapsed_groups, BTreeSet::from([2]));
+
+        update(
+            &mut model,
+            ExtraFieldsMsg::SetAllGroupsOpen(false),
+            &mut cmds,
+        );
+        assert_eq!(model.collapsed_groups, BTreeSet::from([1, 2]));
+
+        update(
+            &mut model,
+            ExtraFieldsMsg::MoveGroup { from: 0, to: 1 },
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            ExtraFieldsMsg::SetGroupOpen {
+                group_id: 1,
+                open: true,
+            },
+            &mut cmds,
+        );
+        assert_eq!(
+            model.collapsed_groups,
+            BTreeSet::from([2]),
+            "state follows the group"
+        );
+
+        update(
+            &mut model,
+            ExtraFieldsMsg::SetAllGroupsOpen(true),
+            &mut cmds,
+        );
+        assert!(model.collapsed_groups.is_empty());
+    }
 }
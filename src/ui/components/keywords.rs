@@ -3,8 +3,18 @@
 
 //! Keywords editor refactored to an MVU-friendly shape.
 
+use std::collections::BTreeSet;
+
 use eframe::egui;
 
+use crate::i18n::t;
+use crate::models::keywords::{KeywordRules, normalize_keyword, same_keyword};
+use crate::ui::components::IconLabel;
+use crate::utils::text::ellipsize;
+
+/// Most suggestions shown below a keyword field at once.
+const MAX_SUGGESTIONS: usize = 8;
+
 /// UI model for keywords, kept free of side effects.
 #[derive(Clone, Default, Debug, PartialEq, Eq)]
 pub struct KeywordsModel {
@@ -13,6 +23,17 @@ pub struct KeywordsModel {
     modal_input: String,
     editing_index: Option<usize>,
     editing_buffer: String,
+    /// Indices of chips selected via ctrl/shift-click; reset whenever the list changes.
+    selected: BTreeSet<usize>,
+    /// Last chip toggled with ctrl-click, used as the start of shift-click ranges.
+    selection_anchor: Option<usize>,
+    confirm_clear: bool,
+    /// Keyword whose removal waits for confirmation, after Delete on its focused chip.
+    pending_removal: Option<usize>,
+    /// Known keywords from earlier archives offered as autocomplete suggestions.
+    suggestions: Vec<String>,
+    /// Suggestion selected with the arrow keys, as an index into [`matching_suggestions`].
+    highlighted: Option<usize>,
 }
 
 /// Messages emitted by the keywords view.
@@ -27,6 +48,34 @@ pub enum KeywordsMsg {
     CommitEdit,
     CancelEdit,
     Remove(usize),
+    /// Ask before removing this keyword.
+    RequestRemove(usize),
+    ConfirmRemove,
+    CancelRemove,
+    /// Remove several keywords at once; indices refer to the current list.
+    RemoveMany(Vec<usize>),
+    /// Toggle one chip in the selection (ctrl/cmd-click).
+    ToggleSelected(usize),
+    /// Select the range from the selection anchor to this chip (shift-click).
+    SelectRange(usize),
+    ClearSelection,
+    /// Ask for confirmation before removing all keywords.
+    RequestClear,
+    CancelClear,
+    /// Remove all keywords.
+    Clear,
+    /// Replace all keywords, e.g. when restoring a draft.
+    Restore(Vec<String>),
+    /// Add imported keywords, skipping ones already present in any letter case.
+    MergeKeywords(Vec<String>),
+    /// Replace the vocabulary offered as autocomplete suggestions.
+    SuggestionsLoaded(Vec<String>),
+    /// Move the suggestion highlight down (arrow down), wrapping around.
+    HighlightNext,
+    /// Move the suggestion highlight up (arrow up), wrapping around.
+    HighlightPrevious,
+    /// Complete the active input with a suggestion.
+    SuggestionPicked(String),
 }
 
 /// User-facing feedback surfaced to the status bar or error modal.
@@ -43,6 +92,33 @@ impl KeywordsModel {
     pub fn keywords(&self) -> &[String] {
         &self.keywords
     }
+
+    /// Indices of the currently selected keywords in ascending order.
+    pub fn selected(&self) -> Vec<usize> {
+        self.selected.iter().copied().collect()
+    }
+
+    /// Vocabulary used for autocomplete suggestions.
+    pub fn suggestions(&self) -> &[String] {
+        &self.suggestions
+    }
+
+    /// Text field the suggestions currently complete: the add dialog, else the inline editor.
+    fn active_input(&self) -> Option<&str> {
+        if self.modal_open {
+            Some(current_segment(&self.modal_input))
+        } else if self.editing_index.is_some() {
+            Some(self.editing_buffer.trim())
+        } else {
+            None
+        }
+    }
+
+    /// Drop selection state after the list changed shape.
+    fn reset_selection(&mut self) {
+        self.selected.clear();
+        self.selection_anchor = None;
+    }
 }
 
 /// Apply a message to the model. Returns a feedback event when relevant.
@@ -51,20 +127,24 @@ pub fn update(model: &mut KeywordsModel, msg: KeywordsMsg) -> Option<KeywordsEve
         KeywordsMsg::OpenModal => {
             model.modal_open = true;
             model.modal_input.clear();
+            model.highlighted = None;
             None
         }
         KeywordsMsg::CloseModal => {
             model.modal_open = false;
             model.modal_input.clear();
+            model.highlighted = None;
             None
         }
         KeywordsMsg::ModalInputChanged(text) => {
             model.modal_input = text;
+            model.highlighted = None;
             None
         }
         KeywordsMsg::AddFromModal => {
             let (message, added_any) = process_modal_input(model);
             if added_any {
+                model.reset_selection();
                 model.modal_open = false;
                 model.modal_input.clear();
             }
@@ -76,10 +156,12 @@ pub fn update(model: &mut KeywordsModel, msg: KeywordsMsg) -> Option<KeywordsEve
         KeywordsMsg::StartEdit(index) => {
             model.editing_index = Some(index);
             model.editing_buffer = model.keywords.get(index).cloned().unwrap_or_default();
+            model.highlighted = None;
             None
         }
         KeywordsMsg::EditInputChanged(text) => {
             model.editing_buffer = text;
+            model.highlighted = None;
             None
         }
         KeywordsMsg::CommitEdit => commit_edit(model),
@@ -91,31 +173,199 @@ pub fn update(model: &mut KeywordsModel, msg: KeywordsMsg) -> Option<KeywordsEve
         KeywordsMsg::Remove(index) => {
             if index < model.keywords.len() {
                 model.keywords.remove(index);
+                model.reset_selection();
+                model.pending_removal = None;
                 if model.editing_index == Some(index) {
                     model.editing_index = None;
                     model.editing_buffer.clear();
                 }
                 return Some(KeywordsEvent {
-                    message: "Keyword removed".to_string(),
+                    message: t!("keywords.removed").to_string(),
                     is_error: false,
                 });
             }
             None
         }
+        KeywordsMsg::RequestRemove(index) => {
+            model.pending_removal = (index < model.keywords.len()).then_some(index);
+            None
+        }
+        KeywordsMsg::ConfirmRemove => {
+            let index = model.pending_removal.take()?;
+            update(model, KeywordsMsg::Remove(index))
+        }
+        KeywordsMsg::CancelRemove => {
+            model.pending_removal = None;
+            None
+        }
+        KeywordsMsg::RemoveMany(indices) => remove_many(model, indices),
+        KeywordsMsg::ToggleSelected(index) => {
+            if index < model.keywords.len() && !model.selected.remove(&index) {
+                model.selected.insert(index);
+            }
+            model.selection_anchor = Some(index);
+            None
+        }
+        KeywordsMsg::SelectRange(index) => {
+            if index < model.keywords.len() {
+                let anchor = model.selection_anchor.unwrap_or(index);
+                model.selected.extend(anchor.min(index)..=anchor.max(index));
+                model.selection_anchor = Some(anchor);
+            }
+            None
+        }
+        KeywordsMsg::ClearSelection => {
+            model.reset_selection();
+            None
+        }
+        KeywordsMsg::RequestClear => {
+            model.confirm_clear = !model.keywords.is_empty();
+            None
+        }
+        KeywordsMsg::CancelClear => {
+            model.confirm_clear = false;
+            None
+        }
+        KeywordsMsg::Clear => {
+            let count = model.keywords.len();
+            model.keywords.clear();
+            model.reset_selection();
+            model.confirm_clear = false;
+            model.pending_removal = None;
+            model.editing_index = None;
+            model.editing_buffer.clear();
+            (count > 0).then(|| KeywordsEvent {
+                message: t!("keywords.cleared", count = count),
+                is_error: false,
+            })
+        }
+        KeywordsMsg::Restore(keywords) => {
+            *model = KeywordsModel {
+                keywords,
+                suggestions: std::mem::take(&mut model.suggestions),
+                ..Default::default()
+            };
+            None
+        }
+        KeywordsMsg::MergeKeywords(incoming) => {
+            let (added, skipped) = merge_keywords(model, incoming);
+            if added > 0 {
+                model.reset_selection();
+            }
+            let mut message = t!("keywords.merged", count = added);
+            if skipped > 0 {
+                message.push(' ');
+                message.push_str(&t!("keywords.merged.skipped", count = skipped));
+            }
+            Some(KeywordsEvent {
+                message,
+                is_error: false,
+            })
+        }
+        KeywordsMsg::SuggestionsLoaded(suggestions) => {
+            model.suggestions = suggestions;
+            model.highlighted = None;
+            None
+        }
+        KeywordsMsg::HighlightNext => {
+            let count = matching_suggestions(model).len();
+            model.highlighted = match (count, model.highlighted) {
+                (0, _) => None,
+                (_, None) => Some(0),
+                (n, Some(i)) => Some((i + 1) % n),
+            };
+            None
+        }
+        KeywordsMsg::HighlightPrevious => {
+            let count = matching_suggestions(model).len();
+            model.highlighted = match (count, model.highlighted) {
+                (0, _) => None,
+                (n, None) => Some(n - 1),
+                (n, Some(i)) => Some((i + n - 1) % n),
+            };
+            None
+        }
+        KeywordsMsg::SuggestionPicked(keyword) => {
+            model.highlighted = None;
+            if model.modal_open {
+                let head = model
+                    .modal_input
+                    .rfind(',')
+                    .map_or("", |i| &model.modal_input[..=i]);
+                // Replace only the keyword being typed; earlier comma-separated ones stay.
+                model.modal_input = if head.is_empty() {
+                    keyword
+                } else {
+                    format!("{head} {keyword}")
+                };
+            } else if model.editing_index.is_some() {
+                model.editing_buffer = keyword;
+            }
+            None
+        }
     }
 }
 
+/// Vocabulary entries starting with the active input (case-insensitive).
+///
+/// Keywords already on the entry, or already typed in the add dialog, are never suggested.
+pub fn matching_suggestions(model: &KeywordsModel) -> Vec<&str> {
+    let Some(prefix) = model.active_input().map(str::to_lowercase) else {
+        return Vec::new();
+    };
+    if prefix.is_empty() {
+        return Vec::new();
+    }
+    let typed: Vec<String> = if model.modal_open {
+        model
+            .modal_input
+            .split(',')
+            .map(|part| part.trim().to_lowercase())
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    model
+        .suggestions
+        .iter()
+        .map(String::as_str)
+        .filter(|candidate| {
+            let lower = candidate.to_lowercase();
+            lower.starts_with(&prefix)
+                && lower != prefix
+                && !typed.contains(&lower)
+                && !model.keywords.iter().any(|kw| kw.to_lowercase() == lower)
+        })
+        .take(MAX_SUGGESTIONS)
+        .collect()
+}
+
+/// Text after the last comma of the add-dialog input, i.e. the keyword being typed.
+fn current_segment(input: &str) -> &str {
+    input.rsplit(',').next().unwrap_or(input).trim()
+}
+
 /// Render the keywords UI and return any messages triggered by user interaction.
-pub fn view(ui: &mut egui::Ui, ctx: &egui::Context, model: &KeywordsModel) -> Vec<KeywordsMsg> {
+///
+/// `derived` lists the keywords saving adds from extra-field values; they are shown as
+/// read-only chips after the entered ones.
+pub fn view(
+    ui: &mut egui::Ui,
+    ctx: &egui::Context,
+    model: &KeywordsModel,
+    derived: &[String],
+) -> Vec<KeywordsMsg> {
     let mut msgs = Vec::new();
 
-    egui::CollapsingHeader::new("Keywords")
+    egui::CollapsingHeader::new(t!("settings.keywords"))
         .default_open(true)
         .show(ui, |ui| {
             if ui
                 .add(egui::Button::new(format!(
-                    "{} Add keyword(s)",
-                    egui_phosphor::regular::PLUS
+                    "{} {}",
+                    egui_phosphor::regular::PLUS,
+                    t!("keywords.add")
                 )))
                 .clicked()
             {
@@ -124,24 +374,134 @@ pub fn view(ui: &mut egui::Ui, ctx: &egui::Context, model: &KeywordsModel) -> Ve
 
             ui.add_space(6.0);
             ui.label(
-                egui::RichText::new(
-                    "Tip: Paste comma-separated keywords in the dialog; they will be split safely.",
-                )
-                .small()
-                .color(egui::Color32::from_gray(110)),
+                egui::RichText::new(t!("keywords.tip"))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
             );
 
             ui.add_space(8.0);
+            render_bulk_actions(ui, model, &mut msgs);
             render_keywords_grid(ui, model, &mut msgs);
+            render_derived_keywords(ui, derived);
         });
 
     if model.modal_open {
         render_modal(ctx, model, &mut msgs);
     }
+    if model.confirm_clear {
+        render_clear_confirmation(ctx, model, &mut msgs);
+    }
+    render_removal_confirmation(ctx, model, &mut msgs);
 
     msgs
 }
 
+/// Render "Remove selected" (only with a selection) and "Clear all" actions above the grid.
+fn render_bulk_actions(ui: &mut egui::Ui, model: &KeywordsModel, msgs: &mut Vec<KeywordsMsg>) {
+    if model.keywords.is_empty() {
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        if !model.selected.is_empty() {
+            if ui
+                .button(format!(
+                    "{} {}",
+                    egui_phosphor::regular::TRASH,
+                    t!("keywords.remove_selected", count = model.selected.len())
+                ))
+                .clicked()
+            {
+                msgs.push(KeywordsMsg::RemoveMany(model.selected()));
+            }
+            if ui.button(t!("keywords.deselect")).clicked() {
+                msgs.push(KeywordsMsg::ClearSelection);
+            }
+            ui.separator();
+        }
+
+        if ui
+            .button(format!(
+                "{} {}",
+                egui_phosphor::regular::BROOM,
+                t!("keywords.clear")
+            ))
+            .on_hover_text(t!("keywords.clear.hover"))
+            .clicked()
+        {
+            msgs.push(KeywordsMsg::RequestClear);
+        }
+
+        ui.label(
+            egui::RichText::new(t!("keywords.select.hint"))
+                .small()
+                .color(egui::Color32::from_gray(110)),
+        );
+    });
+    ui.add_space(4.0);
+}
+
+/// Ask the user to confirm removing all keywords.
+fn render_clear_confirmation(
+    ctx: &egui::Context,
+    model: &KeywordsModel,
+    msgs: &mut Vec<KeywordsMsg>,
+) {
+    egui::Window::new(t!("keywords.clear.title"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(t!("keywords.clear.text", count = model.keywords.len()));
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button(t!("keywords.clear.confirm")).clicked() {
+                    msgs.push(KeywordsMsg::Clear);
+                }
+                if ui.button(t!("common.cancel")).clicked() {
+                    msgs.push(KeywordsMsg::CancelClear);
+                }
+            });
+        });
+}
+
+/// Ask before removing the keyword whose chip had focus when Delete was pressed.
+fn render_removal_confirmation(
+    ctx: &egui::Context,
+    model: &KeywordsModel,
+    msgs: &mut Vec<KeywordsMsg>,
+) {
+    let Some(keyword) = model
+        .pending_removal
+        .and_then(|index| model.keywords.get(index))
+    else {
+        return;
+    };
+    egui::Window::new(t!("keywords.remove.title"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(t!("keywords.remove.text", keyword = keyword));
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button(t!("common.remove")).clicked()
+                    || ui.input(|input| input.key_pressed(egui::Key::Enter))
+                {
+                    msgs.push(KeywordsMsg::ConfirmRemove);
+                }
+                if ui.button(t!("common.cancel")).clicked()
+                    || ui.input(|input| input.key_pressed(egui::Key::Escape))
+                {
+                    msgs.push(KeywordsMsg::CancelRemove);
+                }
+            });
+        });
+}
+
+/// Longest keyword shown in full on a chip; longer ones are cut and shown on hover.
+const KEYWORD_CHIP_MAX_CHARS: usize = 32;
+
 /// Display keywords in a responsive grid, wiring chip actions into messages.
 fn render_keywords_grid(ui: &mut egui::Ui, model: &KeywordsModel, msgs: &mut Vec<KeywordsMsg>) {
     let available = ui.available_width();
@@ -155,7 +515,7 @@ fn render_keywords_grid(ui: &mut egui::Ui, model: &KeywordsModel, msgs: &mut Vec
         .show(ui, |ui| {
             if model.keywords.is_empty() {
                 ui.label(
-                    egui::RichText::new("No keywords added yet.")
+                    egui::RichText::new(t!("keywords.none"))
                         .italics()
                         .color(egui::Color32::from_gray(110)),
                 );
@@ -172,7 +532,7 @@ fn render_keywords_grid(ui: &mut egui::Ui, model: &KeywordsModel, msgs: &mut Vec
                         if model.editing_index == Some(i) {
                             render_editing_keyword(ui, model, msgs);
                         } else {
-                            render_keyword_chip(ui, i, kw, msgs);
+                            render_keyword_chip(ui, i, kw, model.selected.contains(&i), msgs);
                         }
                     });
                 });
@@ -188,20 +548,61 @@ fn render_keywords_grid(ui: &mut egui::Ui, model: &KeywordsModel, msgs: &mut Vec
         });
 }
 
+/// Show the keywords added from extra-field values as greyed-out chips that cannot be edited.
+fn render_derived_keywords(ui: &mut egui::Ui, derived: &[String]) {
+    if derived.is_empty() {
+        return;
+    }
+    ui.add_space(6.0);
+    ui.label(
+        egui::RichText::new(t!("keywords.derived"))
+            .small()
+            .color(egui::Color32::from_gray(110)),
+    );
+    ui.horizontal_wrapped(|ui| {
+        for keyword in derived {
+            let label = ellipsize(keyword, KEYWORD_CHIP_MAX_CHARS);
+            ui.add_enabled(
+                false,
+                egui::Button::new(egui::RichText::new(label).italics()).wrap(),
+            )
+            .on_disabled_hover_text(t!("keywords.derived.hover", keyword = keyword));
+        }
+    });
+}
+
 /// Render a single keyword chip with inline delete/edit affordances.
 fn render_keyword_chip(
     ui: &mut egui::Ui,
     index: usize,
     keyword: &str,
+    selected: bool,
     msgs: &mut Vec<KeywordsMsg>,
 ) {
-    let chip_resp = ui.add(
-        egui::Button::new(keyword)
-            .selected(false)
+    let label = ellipsize(keyword, KEYWORD_CHIP_MAX_CHARS);
+    let mut chip_resp = ui.add(
+        egui::Button::new(label.as_str())
+            .selected(selected)
             .wrap()
             .min_size(egui::vec2(0.0, 0.0)),
     );
-    if chip_resp.clicked() {
+    // Screen readers get the whole keyword even when the chip shows it cut.
+    chip_resp.widget_info(|| {
+        egui::WidgetInfo::selected(egui::WidgetType::Button, true, selected, keyword)
+    });
+    if label != keyword {
+        chip_resp = chip_resp.on_hover_text(keyword);
+    }
+    // Enter on a focused chip clicks it and so starts editing; Delete asks to remove it.
+    if chip_resp.has_focus() && ui.input(|inp| inp.key_pressed(egui::Key::Delete)) {
+        msgs.push(KeywordsMsg::RequestRemove(index));
+    }
+    let modifiers = ui.input(|inp| inp.modifiers);
+    if chip_resp.clicked() && modifiers.shift {
+        msgs.push(KeywordsMsg::SelectRange(index));
+    } else if chip_resp.clicked() && modifiers.command {
+        msgs.push(KeywordsMsg::ToggleSelected(index));
+    } else if chip_resp.clicked() {
         // Signal caller to start editing this keyword.
         ui.memory_mut(|mem| mem.request_focus(chip_resp.id));
         msgs.push(KeywordsMsg::StartEdit(index));
@@ -212,7 +613,7 @@ fn render_keyword_chip(
             egui::RichText::new(egui_phosphor::regular::TRASH_SIMPLE)
                 .color(egui::Color32::from_gray(140)),
         )
-        .on_hover_text("Remove keyword")
+        .icon_label(t!("keywords.remove"))
         .clicked()
     {
         msgs.push(KeywordsMsg::Remove(index));
@@ -221,10 +622,15 @@ fn render_keyword_chip(
 
 /// Render the inline editing UI for a keyword row.
 fn render_editing_keyword(ui: &mut egui::Ui, model: &KeywordsModel, msgs: &mut Vec<KeywordsMsg>) {
+    let id = egui::Id::new("keyword_edit_input");
+    let suggestions = matching_suggestions(model);
+    handle_suggestion_keys(ui, id, &suggestions, model.highlighted, msgs);
+
     let mut buffer = model.editing_buffer.clone();
     let response = ui.add(
         egui::TextEdit::singleline(&mut buffer)
-            .hint_text("Edit keyword")
+            .id(id)
+            .hint_text(t!("keywords.edit"))
             .desired_width(140.0),
     );
 
@@ -232,6 +638,17 @@ fn render_editing_keyword(ui: &mut egui::Ui, model: &KeywordsModel, msgs: &mut V
         msgs.push(KeywordsMsg::EditInputChanged(buffer.clone()));
     }
 
+    if !suggestions.is_empty() {
+        egui::Area::new(id.with("suggestions"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(response.rect.left_bottom())
+            .show(ui.ctx(), |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    render_suggestions(ui, &suggestions, model.highlighted, msgs);
+                });
+            });
+    }
+
     let enter = response.lost_focus()
         && ui.input(|inp| inp.key_pressed(egui::Key::Enter) || inp.key_pressed(egui::Key::Tab));
     if enter {
@@ -241,7 +658,7 @@ fn render_editing_keyword(ui: &mut egui::Ui, model: &KeywordsModel, msgs: &mut V
 
     if ui
         .button(egui_phosphor::regular::CHECK)
-        .on_hover_text("Save")
+        .icon_label(t!("common.save"))
         .clicked()
     {
         msgs.push(KeywordsMsg::CommitEdit);
@@ -249,7 +666,7 @@ fn render_editing_keyword(ui: &mut egui::Ui, model: &KeywordsModel, msgs: &mut V
 
     if ui
         .button(egui_phosphor::regular::X)
-        .on_hover_text("Cancel")
+        .icon_label(t!("common.cancel"))
         .clicked()
     {
         msgs.push(KeywordsMsg::CancelEdit);
@@ -260,15 +677,20 @@ fn render_editing_keyword(ui: &mut egui::Ui, model: &KeywordsModel, msgs: &mut V
 fn render_modal(ctx: &egui::Context, model: &KeywordsModel, msgs: &mut Vec<KeywordsMsg>) {
     let mut input = model.modal_input.clone();
 
-    egui::Window::new("Add keyword(s)")
+    egui::Window::new(t!("keywords.add"))
         .collapsible(false)
         .resizable(false)
         .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
         .show(ctx, |ui| {
-            ui.label("Keyword(s)");
+            ui.label(t!("keywords.modal.label"));
+            let id = egui::Id::new("keywords_modal_input");
+            let suggestions = matching_suggestions(model);
+            handle_suggestion_keys(ui, id, &suggestions, model.highlighted, msgs);
+
             let resp = ui.add(
                 egui::TextEdit::singleline(&mut input)
-                    .hint_text("e.g., microscopy or microscopy, dataset"),
+                    .id(id)
+                    .hint_text(t!("keywords.modal.hint")),
             );
 
             if resp.changed() {
@@ -280,28 +702,107 @@ fn render_modal(ctx: &egui::Context, model: &KeywordsModel, msgs: &mut Vec<Keywo
                 msgs.push(KeywordsMsg::AddFromModal);
             }
 
+            if !suggestions.is_empty() {
+                egui::Frame::group(ui.style()).show(ui, |ui| {
+                    render_suggestions(ui, &suggestions, model.highlighted, msgs);
+                });
+            }
+
             ui.add_space(8.0);
             ui.horizontal(|ui| {
-                if ui.button("Add").clicked() {
+                if ui.button(t!("keywords.modal.add")).clicked() {
                     msgs.push(KeywordsMsg::AddFromModal);
                 }
 
-                if ui.button("Cancel").clicked() {
+                if ui.button(t!("common.cancel")).clicked() {
                     msgs.push(KeywordsMsg::CloseModal);
                 }
             });
         });
 }
 
-/// Split modal input on commas, add unique keywords, and return a status message plus added flag.
+/// Translate arrow keys and Enter into suggestion messages while the field `id` has focus.
+///
+/// Runs before the field is drawn so Enter picks the highlighted suggestion instead of
+/// submitting the field.
+fn handle_suggestion_keys(
+    ui: &mut egui::Ui,
+    id: egui::Id,
+    suggestions: &[&str],
+    highlighted: Option<usize>,
+    msgs: &mut Vec<KeywordsMsg>,
+) {
+    if suggestions.is_empty() || !ui.memory(|mem| mem.has_focus(id)) {
+        return;
+    }
+    ui.input_mut(|inp| {
+        if inp.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown) {
+            msgs.push(KeywordsMsg::HighlightNext);
+        }
+        if inp.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp) {
+            msgs.push(KeywordsMsg::HighlightPrevious);
+        }
+        if let Some(keyword) = highlighted.and_then(|i| suggestions.get(i))
+            && inp.consume_key(egui::Modifiers::NONE, egui::Key::Enter)
+        {
+            msgs.push(KeywordsMsg::SuggestionPicked(keyword.to_string()));
+        }
+    });
+}
+
+/// Render the suggestion list; clicking an entry picks it.
+fn render_suggestions(
+    ui: &mut egui::Ui,
+    suggestions: &[&str],
+    highlighted: Option<usize>,
+    msgs: &mut Vec<KeywordsMsg>,
+) {
+    for (i, suggestion) in suggestions.iter().enumerate() {
+        if ui
+            .selectable_label(highlighted == Some(i), *suggestion)
+            .clicked()
+        {
+            msgs.push(KeywordsMsg::SuggestionPicked(suggestion.to_string()));
+        }
+    }
+}
+
+/// Split modal input on commas, add unique keywords in their stored form, and return a status
+/// message plus added flag.
+/// Append normalized `incoming` keywords that are new, returning how many were added and
+/// skipped; blanks are dropped without counting.
+fn merge_keywords(model: &mut KeywordsModel, incoming: Vec<String>) -> (usize, usize) {
+    let rules = KeywordRules::default();
+    let (mut added, mut skipped) = (0, 0);
+    for raw in incoming {
+        let keyword = normalize_keyword(&raw, &rules);
+        if keyword.is_empty() {
+            continue;
+        }
+        let exists = model
+            .keywords
+            .iter()
+            .any(|existing| same_keyword(existing, &keyword));
+        if exists || model.keywords.len() >= rules.max_count {
+            skipped += 1;
+        } else {
+            model.keywords.push(keyword);
+            added += 1;
+        }
+    }
+    (added, skipped)
+}
+
 fn process_modal_input(model: &mut KeywordsModel) -> (String, bool) {
+    let rules = KeywordRules::default();
     let mut added_count = 0usize;
     let mut dup_count = 0usize;
     let mut empty_count = 0usize;
+    let mut over_limit_count = 0usize;
 
     for part in model.modal_input.split(',') {
-        let trimmed = part.trim();
-        if trimmed.is_empty() {
+        let keyword = normalize_keyword(part, &rules);
+        if keyword.is_empty() {
             empty_count += 1;
             continue;
         }
@@ -309,45 +810,83 @@ fn process_modal_input(model: &mut KeywordsModel) -> (String, bool) {
         let exists = model
             .keywords
             .iter()
-            .any(|existing| existing.eq_ignore_ascii_case(trimmed));
+            .any(|existing| same_keyword(existing, &keyword));
         if exists {
             dup_count += 1;
             continue;
         }
+        if model.keywords.len() >= rules.max_count {
+            over_limit_count += 1;
+            continue;
+        }
 
-        model.keywords.push(trimmed.to_string());
+        model.keywords.push(keyword);
         added_count += 1;
     }
 
     let mut skipped_parts = Vec::new();
     if dup_count > 0 {
-        skipped_parts.push(format!("{dup_count} duplicate(s)"));
+        skipped_parts.push(t!("keywords.skipped.duplicates", count = dup_count));
     }
     if empty_count > 0 {
-        skipped_parts.push(format!("{empty_count} empty entry/entries"));
+        skipped_parts.push(t!("keywords.skipped.empty", count = empty_count));
+    }
+    if over_limit_count > 0 {
+        skipped_parts.push(t!(
+            "keywords.skipped.limit",
+            count = over_limit_count,
+            max = rules.max_count
+        ));
     }
 
     let message = match (added_count, skipped_parts.is_empty()) {
         (a, false) if a > 0 => {
-            format!(
-                "Added {a} keyword(s); skipped {}.",
-                skipped_parts.join(" and ")
+            t!(
+                "keywords.added.skipped",
+                count = a,
+                skipped = skipped_parts.join(&format!(" {} ", t!("keywords.and")))
             )
         }
-        (a, true) if a > 0 => format!("Added {a} keyword(s)."),
-        (_, _) => "No keywords added; skipped duplicates or empty entries.".to_string(),
+        (a, true) if a > 0 => t!("keywords.added", count = a),
+        (_, _) => t!("keywords.added.none").to_string(),
     };
 
     (message, added_count > 0)
 }
 
-/// Validate and commit an inline keyword edit, returning a feedback event on error.
+/// Remove the keywords at `indices` (deduplicated, out-of-range ignored) in descending order.
+fn remove_many(model: &mut KeywordsModel, indices: Vec<usize>) -> Option<KeywordsEvent> {
+    let targets: BTreeSet<usize> = indices
+        .into_iter()
+        .filter(|i| *i < model.keywords.len())
+        .collect();
+    if targets.is_empty() {
+        return None;
+    }
+
+    // Descending order keeps the remaining target indices valid while removing.
+    for &index in targets.iter().rev() {
+        model.keywords.remove(index);
+    }
+    model.reset_selection();
+    model.pending_removal = None;
+    model.editing_index = None;
+    model.editing_buffer.clear();
+
+    Some(KeywordsEvent {
+        message: t!("keywords.removed_many", count = targets.len()),
+        is_error: false,
+    })
+}
+
+/// Validate and commit an inline keyword edit in its stored form, returning a feedback event
+/// on error.
 fn commit_edit(model: &mut KeywordsModel) -> Option<KeywordsEvent> {
     let index = model.editing_index?;
-    let new_kw = model.editing_buffer.trim();
+    let new_kw = normalize_keyword(&model.editing_buffer, &KeywordRules::default());
     if new_kw.is_empty() {
         return Some(KeywordsEvent {
-            message: "Keyword cannot be empty.".into(),
+            message: t!("keywords.edit.empty").into(),
             is_error: true,
         });
     }
@@ -356,16 +895,16 @@ fn commit_edit(model: &mut KeywordsModel) -> Option<KeywordsEvent> {
         .keywords
         .iter()
         .enumerate()
-        .any(|(i, existing)| i != index && existing.eq_ignore_ascii_case(new_kw));
+        .any(|(i, existing)| i != index && same_keyword(existing, &new_kw));
     if duplicate {
         return Some(KeywordsEvent {
-            message: "Keyword already exists.".into(),
+            message: t!("keywords.edit.duplicate").into(),
             is_error: true,
         });
     }
 
     if let Some(slot) = model.keywords.get_mut(index) {
-        *slot = new_kw.to_string();
+        *slot = new_kw;
     }
     model.editing_index = None;
     model.editing_buffer.clear();
@@ -399,6 +938,7 @@ mod tests {
             modal_input: String::new(),
             editing_index: Some(0),
             editing_buffer: "two".into(),
+            ..Default::default()
         };
 
         let event = commit_edit(&mut model).expect("should return error event");
@@ -408,6 +948,23 @@ mod tests {
         assert_eq!(model.keywords, vec!["one", "two"]);
     }
 
+    #[test]
+    fn committed_keywords_are_stored_normalized() {
+        let mut model = KeywordsModel {
+            keywords: vec!["one".into()],
+            modal_open: true,
+            modal_input: "  cell   culture , PCR|gel, ONE".into(),
+            ..Default::default()
+        };
+        update(&mut model, KeywordsMsg::AddFromModal);
+        assert_eq!(model.keywords, vec!["one", "cell culture", "PCR gel"]);
+
+        model.editing_index = Some(1);
+        model.editing_buffer = " tissue |  culture ".into();
+        assert_eq!(commit_edit(&mut model), None);
+        assert_eq!(model.keywords[1], "tissue culture");
+    }
+
     #[test]
     fn remove_keyword_updates_model() {
         let mut model = KeywordsModel {
@@ -420,4 +977,162 @@ mod tests {
         assert_eq!(model.keywords, vec!["two"]);
         assert_eq!(event.message, "Keyword removed");
     }
+
+    #[test]
+    fn remove_many_removes_in_descending_order() {
+        let mut model = KeywordsModel {
+            keywords: vec!["a".into(), "b".into(), "c".into(), "d".into()],
+            ..Default::default()
+        };
+
+        let event =
+            update(&mut model, KeywordsMsg::RemoveMany(vec![0, 2, 2, 9])).expect("event expected");
+
+        assert_eq!(model.keywords, vec!["b", "d"]);
+        assert_eq!(event.message, "Removed 2 keyword(s).");
+    }
+
+    #[test]
+    fn merged_keywords_skip_existing_ones_in_any_case() {
+        let mut model = KeywordsModel::default();
+        update(&mut model, KeywordsMsg::Restore(vec!["PCR".into()]));
+
+        let event = update(
+            &mut model,
+            KeywordsMsg::MergeKeywords(vec![
+                "pcr".into(),
+                " Gel  Imaging ".into(),
+                "gel imaging".into(),
+                " ".into(),
+                "buffer".into(),
+            ]),
+        )
+        .expect("event expected");
+
+        assert_eq!(model.keywords(), ["PCR", "Gel Imaging", "buffer"]);
+        assert_eq!(
+            event.message,
+            "2 new keyword(s) (2 already present or over the limit)"
+        );
+    }
+
+    #[test]
+    fn shift_click_selects_range_from_anchor() {
+        let mut model = KeywordsModel {
+            keywords: (0..5).map(|i| i.to_string()).collect(),
+            ..Default::default()
+        };
+
+        update(&mut model, KeywordsMsg::ToggleSelected(1));
+        update(&mut model, KeywordsMsg::SelectRange(3));
+
+        assert_eq!(model.selected(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn selection_is_cleared_when_list_changes() {
+        let mut model = KeywordsModel {
+            keywords: vec!["a".into(), "b".into()],
+            ..Default::default()
+        };
+        update(&mut model, KeywordsMsg::ToggleSelected(1));
+
+        update(&mut model, KeywordsMsg::Restore(vec!["x".into()]));
+        assert!(model.selected().is_empty());
+
+        update(&mut model, KeywordsMsg::ToggleSelected(0));
+        update(&mut model, KeywordsMsg::Remove(0));
+        assert!(model.selected().is_empty());
+    }
+
+    #[test]
+    fn suggestions_match_prefix_and_skip_present_keywords() {
+        let mut model = KeywordsModel {
+            keywords: vec!["Microscopy".into()],
+            suggestions: vec![
+                "microscopy".into(),
+                "Mass spec".into(),
+                "methods".into(),
+                "dataset".into(),
+            ],
+            ..Default::default()
+        };
+        update(&mut model, KeywordsMsg::OpenModal);
+        update(
+            &mut model,
+            KeywordsMsg::ModalInputChanged("methods, M".into()),
+        );
+
+        assert_eq!(matching_suggestions(&model), vec!["Mass spec"]);
+
+        update(&mut model, KeywordsMsg::ModalInputChanged("".into()));
+        assert!(matching_suggestions(&model).is_empty());
+    }
+
+    #[test]
+    fn arrow_navigation_wraps_and_enter_completes_current_segment() {
+        let mut model = KeywordsModel {
+            suggestions: vec!["alpha".into(), "alpine".into()],
+            ..Default::default()
+        };
+        update(&mut model, KeywordsMsg::OpenModal);
+        update(
+            &mut model,
+            KeywordsMsg::ModalInputChanged("beta, al".into()),
+        );
+
+        update(&mut model, KeywordsMsg::HighlightPrevious);
+        assert_eq!(model.highlighted, Some(1));
+        update(&mut model, KeywordsMsg::HighlightNext);
+        assert_eq!(model.highlighted, Some(0));
+
+        update(&mut model, KeywordsMsg::SuggestionPicked("alpha".into()));
+        assert_eq!(model.modal_input, "beta, alpha");
+        assert_eq!(model.highlighted, None);
+
+        update(&mut model, KeywordsMsg::AddFromModal);
+        assert_eq!(model.keywords, vec!["beta", "alpha"]);
+        assert_eq!(model.suggestions(), ["alpha", "alpine"]);
+    }
+
+    #[test]
+    fn clear_requires_confirmation_flow() {
+        let mut model = KeywordsModel {
+            keywords: vec!["a".into(), "b".into()],
+            ..Default::default()
+        };
+
+        update(&mut model, KeywordsMsg::RequestClear);
+        assert!(model.confirm_clear);
+        update(&mut model, KeywordsMsg::CancelClear);
+        assert_eq!(model.keywords.len(), 2);
+
+        update(&mut model, KeywordsMsg::RequestClear);
+        let event = update(&mut model, KeywordsMsg::Clear).expect("event expected");
+
+        assert!(model.keywords.is_empty());
+        assert!(!model.confirm_clear);
+        assert_eq!(event.message, "Removed all 2 keyword(s).");
+    }
+
+    #[test]
+    fn removing_a_focused_chip_asks_first() {
+        let mut model = KeywordsModel {
+            keywords: vec!["a".into(), "b".into()],
+            ..Default::default()
+        };
+
+        update(&mut model, KeywordsMsg::RequestRemove(5));
+        assert_eq!(model.pending_removal, None, "out of range");
+        update(&mut model, KeywordsMsg::RequestRemove(1));
+        update(&mut model, KeywordsMsg::CancelRemove);
+        assert_eq!(model.keywords, ["a", "b"]);
+        assert!(update(&mut model, KeywordsMsg::ConfirmRemove).is_none());
+
+        update(&mut model, KeywordsMsg::RequestRemove(1));
+        let event = update(&mut model, KeywordsMsg::ConfirmRemove).expect("event expected");
+        assert_eq!(model.keywords, ["a"]);
+        assert_eq!(model.pending_removal, None);
+        assert_eq!(event.message, "Keyword removed");
+    }
 }
@@ -8,6 +8,10 @@ use egui::text::{CCursor, CCursorRange};
 use egui::text_edit::TextEditState;
 use egui_phosphor::regular;
 
+use crate::i18n::t;
+use crate::ui::components::IconLabel;
+use crate::ui::layout::LayoutParams;
+
 /// Code insertion style preference.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CodeChoice {
@@ -29,6 +33,42 @@ pub enum MathChoice {
     Display,
 }
 
+/// Maximum number of undo steps kept per editor.
+const HISTORY_LIMIT: usize = 100;
+
+/// Typing edits merged into one undo step before a new step starts.
+const TYPING_UNIT_EDITS: usize = 20;
+
+/// Text and cursor captured before an edit.
+#[derive(Clone, Debug)]
+struct EditSnapshot {
+    text: String,
+    cursor: Option<CCursorRange>,
+}
+
+/// Undo/redo stacks for the markdown editor.
+///
+/// Toolbar insertions always create their own step; consecutive typing is coalesced into
+/// steps of up to [`TYPING_UNIT_EDITS`] edits, split at line breaks.
+#[derive(Clone, Debug, Default)]
+pub struct EditHistory {
+    undo: Vec<EditSnapshot>,
+    redo: Vec<EditSnapshot>,
+    /// Edits merged into the current typing step; `None` when the last edit was not typing.
+    typing_edits: Option<usize>,
+}
+
+impl EditHistory {
+    /// Record the state before a new edit and drop the redo branch.
+    fn checkpoint(&mut self, before: EditSnapshot) {
+        if self.undo.len() == HISTORY_LIMIT {
+            self.undo.remove(0);
+        }
+        self.undo.push(before);
+        self.redo.clear();
+    }
+}
+
 /// Editor state for the markdown component, including cursor selection metadata.
 #[derive(Clone, Debug)]
 pub struct MarkdownModel {
@@ -50,6 +90,23 @@ pub struct MarkdownModel {
     pub table_rows: u8,
     /// Columns to use when inserting a table.
     pub table_cols: u8,
+    /// Undo/redo history of text edits.
+    pub history: EditHistory,
+    /// Archive paths of the entry's image attachments offered by the image picker; kept in
+    /// sync by `mvu::update`.
+    pub image_attachments: Vec<String>,
+}
+
+impl MarkdownModel {
+    /// Whether there is an edit to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.history.undo.is_empty()
+    }
+
+    /// Whether there is an undone edit to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.history.redo.is_empty()
+    }
 }
 
 impl Default for MarkdownModel {
@@ -64,6 +121,8 @@ impl Default for MarkdownModel {
             math_choice: MathChoice::Inline,
             table_rows: 2,
             table_cols: 2,
+            history: EditHistory::default(),
+            image_attachments: Vec::new(),
         }
     }
 }
@@ -82,7 +141,7 @@ pub enum StyleKind {
     Link,
     /// Prefix selection with a quote block.
     Quote,
-    /// Insert an image template.
+    /// Insert an image template for an external URL.
     Image,
     /// Insert a horizontal rule.
     Rule,
@@ -112,35 +171,162 @@ pub enum MarkdownMsg {
     SetListChoice(ListChoice),
     SetMathChoice(MathChoice),
     ApplyStyle(StyleKind),
-    InsertTable { rows: u8, cols: u8 },
+    InsertTable {
+        rows: u8,
+        cols: u8,
+    },
     SetTableRows(u8),
     SetTableCols(u8),
+    /// Embed the image attachment at this archive path (relative to the entry folder).
+    InsertAttachmentImage {
+        sanitized_name: String,
+    },
+    /// Image attachments of the entry changed.
+    SetImageAttachments(Vec<String>),
+    Undo,
+    Redo,
 }
 
 /// Update the markdown model in response to a message.
+///
+/// The cursor is kept inside the text afterwards, whatever the message changed.
 pub fn update(model: &mut MarkdownModel, msg: MarkdownMsg) {
+    apply_msg(model, msg);
+    normalize_cursor(model);
+}
+
+/// Clamp the cursor and its pending override to the current text.
+///
+/// Call this after changing `text` outside [`update`], e.g. when rewriting references.
+pub fn normalize_cursor(model: &mut MarkdownModel) {
+    let len = model.text.chars().count();
+    let clamp = |mut range: CCursorRange| {
+        range.primary.index = usize::from(range.primary.index).min(len).into();
+        range.secondary.index = usize::from(range.secondary.index).min(len).into();
+        range
+    };
+    model.cursor = model.cursor.map(clamp);
+    model.cursor_override = model.cursor_override.map(clamp);
+}
+
+fn apply_msg(model: &mut MarkdownModel, msg: MarkdownMsg) {
     match msg {
-        MarkdownMsg::SetText(text) => model.text = text,
+        MarkdownMsg::SetText(text) => set_text(model, text),
         MarkdownMsg::SetCursor(cursor) => model.cursor = cursor,
         MarkdownMsg::ClearCursorOverride => model.cursor_override = None,
         MarkdownMsg::SetHeadingLevel(level) => model.heading_level = level.clamp(1, 6),
-        MarkdownMsg::InsertHeading(level) => insert_heading(model, level),
+        MarkdownMsg::InsertHeading(level) => {
+            record_edit(model);
+            insert_heading(model, level);
+        }
         MarkdownMsg::SetCodeChoice(choice) => model.code_choice = choice,
         MarkdownMsg::SetListChoice(choice) => model.list_choice = choice,
         MarkdownMsg::SetMathChoice(choice) => model.math_choice = choice,
-        MarkdownMsg::ApplyStyle(kind) => apply_style_kind(model, kind),
-        MarkdownMsg::InsertTable { rows, cols } => insert_table_at_cursor(model, rows, cols),
+        MarkdownMsg::ApplyStyle(kind) => {
+            record_edit(model);
+            apply_style_kind(model, kind);
+        }
+        MarkdownMsg::InsertTable { rows, cols } => {
+            record_edit(model);
+            insert_table_at_cursor(model, rows, cols);
+        }
         MarkdownMsg::SetTableRows(rows) => model.table_rows = rows.clamp(1, 100),
         MarkdownMsg::SetTableCols(cols) => model.table_cols = cols.clamp(1, 20),
+        MarkdownMsg::InsertAttachmentImage { sanitized_name } => {
+            record_edit(model);
+            insert_attachment_image(model, &sanitized_name);
+        }
+        MarkdownMsg::SetImageAttachments(paths) => model.image_attachments = paths,
+        MarkdownMsg::Undo => {
+            if let Some(previous) = model.history.undo.pop() {
+                let current = restore_snapshot(model, previous);
+                model.history.redo.push(current);
+            }
+        }
+        MarkdownMsg::Redo => {
+            if let Some(next) = model.history.redo.pop() {
+                let current = restore_snapshot(model, next);
+                model.history.undo.push(current);
+            }
+        }
     }
 }
 
+/// Current text and cursor.
+fn snapshot(model: &MarkdownModel) -> EditSnapshot {
+    EditSnapshot {
+        text: model.text.clone(),
+        cursor: model.cursor,
+    }
+}
+
+/// Checkpoint the current state as its own undo step.
+fn record_edit(model: &mut MarkdownModel) {
+    let before = snapshot(model);
+    model.history.checkpoint(before);
+    model.history.typing_edits = None;
+}
+
+/// Replace the text from the editor, coalescing consecutive typing into one undo step.
+fn set_text(model: &mut MarkdownModel, text: String) {
+    if text == model.text {
+        return;
+    }
+    let line_break = text.matches('\n').count() > model.text.matches('\n').count();
+    match model.history.typing_edits {
+        Some(edits) if edits < TYPING_UNIT_EDITS && !line_break => {
+            model.history.typing_edits = Some(edits + 1);
+        }
+        _ => {
+            let before = snapshot(model);
+            model.history.checkpoint(before);
+            model.history.typing_edits = Some(1);
+        }
+    }
+    model.text = text;
+}
+
+/// Swap in `target`, placing the cursor where it was, and return the replaced state.
+fn restore_snapshot(model: &mut MarkdownModel, target: EditSnapshot) -> EditSnapshot {
+    let current = snapshot(model);
+    model.text = target.text;
+    model.cursor = target.cursor;
+    model.cursor_override = target.cursor;
+    model.history.typing_edits = None;
+    current
+}
+
 /// Render the toolbar and text area, emitting messages instead of mutating state directly.
-pub fn view(model: &MarkdownModel, ui: &mut egui::Ui) -> Vec<MarkdownMsg> {
+///
+/// The editor height bounds come from `layout`.
+pub fn view(model: &MarkdownModel, layout: &LayoutParams, ui: &mut egui::Ui) -> Vec<MarkdownMsg> {
     let mut msgs = Vec::new();
 
     ui.vertical(|ui| {
         ui.horizontal(|ui| {
+            // History
+            if ui
+                .add_enabled(
+                    model.can_undo(),
+                    egui::Button::new(regular::ARROW_COUNTER_CLOCKWISE),
+                )
+                .icon_label(t!("markdown.undo"))
+                .clicked()
+            {
+                msgs.push(MarkdownMsg::Undo);
+            }
+            if ui
+                .add_enabled(
+                    model.can_redo(),
+                    egui::Button::new(regular::ARROW_CLOCKWISE),
+                )
+                .icon_label(t!("markdown.redo"))
+                .clicked()
+            {
+                msgs.push(MarkdownMsg::Redo);
+            }
+            ui.separator();
+
             // Headings
             let heading_resp = egui::ComboBox::from_id_salt("heading_picker")
                 .width(40.0)
@@ -157,34 +343,34 @@ pub fn view(model: &MarkdownModel, ui: &mut egui::Ui) -> Vec<MarkdownMsg> {
                         }
                     }
                 });
-            heading_resp.response.on_hover_text("Heading");
+            heading_resp.response.picker_label(t!("markdown.heading"));
             ui.separator();
 
             // Inline styles
             if ui
                 .button(egui_phosphor::regular::TEXT_BOLDER)
-                .on_hover_text("Bold")
+                .icon_label(t!("markdown.bold"))
                 .clicked()
             {
                 msgs.push(MarkdownMsg::ApplyStyle(StyleKind::Bold));
             }
             if ui
                 .button(egui_phosphor::regular::TEXT_ITALIC)
-                .on_hover_text("Italic")
+                .icon_label(t!("markdown.italic"))
                 .clicked()
             {
                 msgs.push(MarkdownMsg::ApplyStyle(StyleKind::Italic));
             }
             if ui
                 .button(egui_phosphor::regular::TEXT_STRIKETHROUGH)
-                .on_hover_text("Strikethrough")
+                .icon_label(t!("markdown.strikethrough"))
                 .clicked()
             {
                 msgs.push(MarkdownMsg::ApplyStyle(StyleKind::Strikethrough));
             }
             if ui
                 .button(egui_phosphor::regular::TEXT_UNDERLINE)
-                .on_hover_text("Underline")
+                .icon_label(t!("markdown.underline"))
                 .clicked()
             {
                 msgs.push(MarkdownMsg::ApplyStyle(StyleKind::Underline));
@@ -203,7 +389,7 @@ pub fn view(model: &MarkdownModel, ui: &mut egui::Ui) -> Vec<MarkdownMsg> {
                             matches!(model.code_choice, CodeChoice::Inline),
                             egui_phosphor::regular::CODE_SIMPLE,
                         )
-                        .on_hover_text("Inline code")
+                        .on_hover_text(t!("markdown.code.inline"))
                         .clicked()
                     {
                         msgs.push(MarkdownMsg::SetCodeChoice(CodeChoice::Inline));
@@ -214,14 +400,14 @@ pub fn view(model: &MarkdownModel, ui: &mut egui::Ui) -> Vec<MarkdownMsg> {
                             matches!(model.code_choice, CodeChoice::Block),
                             egui_phosphor::regular::CODE_BLOCK,
                         )
-                        .on_hover_text("Code block")
+                        .on_hover_text(t!("markdown.code.block"))
                         .clicked()
                     {
                         msgs.push(MarkdownMsg::SetCodeChoice(CodeChoice::Block));
                         msgs.push(MarkdownMsg::ApplyStyle(StyleKind::CodeBlock));
                     }
                 });
-            code_resp.response.on_hover_text("Code");
+            code_resp.response.picker_label(t!("markdown.code"));
 
             // Lists
             let list_resp = egui::ComboBox::from_id_salt("list_picker")
@@ -236,7 +422,7 @@ pub fn view(model: &MarkdownModel, ui: &mut egui::Ui) -> Vec<MarkdownMsg> {
                             matches!(model.list_choice, ListChoice::Unordered),
                             egui_phosphor::regular::LIST_DASHES,
                         )
-                        .on_hover_text("Bulleted list")
+                        .on_hover_text(t!("markdown.list.bulleted"))
                         .clicked()
                     {
                         msgs.push(MarkdownMsg::SetListChoice(ListChoice::Unordered));
@@ -247,37 +433,35 @@ pub fn view(model: &MarkdownModel, ui: &mut egui::Ui) -> Vec<MarkdownMsg> {
                             matches!(model.list_choice, ListChoice::Ordered),
                             egui_phosphor::regular::LIST_NUMBERS,
                         )
-                        .on_hover_text("Numbered list")
+                        .on_hover_text(t!("markdown.list.numbered"))
                         .clicked()
                     {
                         msgs.push(MarkdownMsg::SetListChoice(ListChoice::Ordered));
                         msgs.push(MarkdownMsg::ApplyStyle(StyleKind::ListOrdered));
                     }
                 });
-            list_resp.response.on_hover_text("List");
+            list_resp.response.picker_label(t!("markdown.list"));
 
             // Other inserts
             if ui
                 .button(egui_phosphor::regular::LINK_SIMPLE)
-                .on_hover_text("Link")
+                .icon_label(t!("markdown.link"))
                 .clicked()
             {
                 msgs.push(MarkdownMsg::ApplyStyle(StyleKind::Link));
             }
             if ui
                 .button(egui_phosphor::regular::QUOTES)
-                .on_hover_text("Quote")
+                .icon_label(t!("markdown.quote"))
                 .clicked()
             {
                 msgs.push(MarkdownMsg::ApplyStyle(StyleKind::Quote));
             }
-            if ui
-                .button(egui_phosphor::regular::IMAGE_SQUARE)
-                .on_hover_text("Image")
-                .clicked()
-            {
-                msgs.push(MarkdownMsg::ApplyStyle(StyleKind::Image));
-            }
+            ui.menu_button(egui_phosphor::regular::IMAGE_SQUARE, |ui| {
+                image_picker(ui, model, msgs.as_mut());
+            })
+            .response
+            .icon_label(t!("markdown.image"));
             let table_resp = egui::ComboBox::from_id_salt("table_picker")
                 .width(80.0)
                 .selected_text(format!(
@@ -289,13 +473,11 @@ pub fn view(model: &MarkdownModel, ui: &mut egui::Ui) -> Vec<MarkdownMsg> {
                 .show_ui(ui, |ui| {
                     table_size_picker(ui, model, msgs.as_mut());
                 });
-            table_resp
-                .response
-                .on_hover_text("Insert table (choose size)");
+            table_resp.response.picker_label(t!("markdown.table"));
 
             if ui
                 .button(egui_phosphor::regular::RULER)
-                .on_hover_text("Rule")
+                .icon_label(t!("markdown.rule"))
                 .clicked()
             {
                 msgs.push(MarkdownMsg::ApplyStyle(StyleKind::Rule));
@@ -313,7 +495,7 @@ pub fn view(model: &MarkdownModel, ui: &mut egui::Ui) -> Vec<MarkdownMsg> {
                             matches!(model.math_choice, MathChoice::Inline),
                             format!("{} $", egui_phosphor::regular::FUNCTION),
                         )
-                        .on_hover_text("Inline math")
+                        .on_hover_text(t!("markdown.math.inline"))
                         .clicked()
                     {
                         msgs.push(MarkdownMsg::SetMathChoice(MathChoice::Inline));
@@ -324,23 +506,37 @@ pub fn view(model: &MarkdownModel, ui: &mut egui::Ui) -> Vec<MarkdownMsg> {
                             matches!(model.math_choice, MathChoice::Display),
                             format!("{} $$", egui_phosphor::regular::FUNCTION),
                         )
-                        .on_hover_text("Display math")
+                        .on_hover_text(t!("markdown.math.display"))
                         .clicked()
                     {
                         msgs.push(MarkdownMsg::SetMathChoice(MathChoice::Display));
                         msgs.push(MarkdownMsg::ApplyStyle(StyleKind::MathDisplay));
                     }
                 });
-            math_resp.response.on_hover_text("Math");
+            math_resp.response.picker_label(t!("markdown.math"));
         });
 
         ui.add_space(4.0);
 
+        // The resize corner takes keyboard focus like any draggable widget, which would stop
+        // Ctrl+Tab out of the body on an invisible target; hand the focus on past it.
+        let resize_id = ui.make_persistent_id("markdown_editor_resize");
+        let corner_id = resize_id.with("__resize_corner");
+        if ui.memory(|mem| mem.has_focus(corner_id)) {
+            let from_body = ui.data_mut(|data| data.remove_temp::<bool>(corner_id));
+            let direction = if from_body.unwrap_or(false) {
+                egui::FocusDirection::Next
+            } else {
+                egui::FocusDirection::Previous
+            };
+            ui.memory_mut(|mem| mem.move_focus(direction));
+        }
+
         egui::Resize::default()
-            .id_salt("markdown_editor_resize")
+            .id(resize_id)
             .resizable([false, true])
-            .default_size([ui.available_width(), 200.0])
-            .min_size([ui.available_width(), 100.0])
+            .default_size([ui.available_width(), layout.editor_default_height])
+            .min_size([ui.available_width(), layout.editor_min_height])
             .max_size([ui.available_width(), f32::INFINITY])
             .show(ui, |ui| {
                 let body_id = ui.id().with("body_text_edit");
@@ -350,6 +546,49 @@ pub fn view(model: &MarkdownModel, ui: &mut egui::Ui) -> Vec<MarkdownMsg> {
                     msgs.push(MarkdownMsg::SetCursor(state.cursor.char_range()));
                 }
 
+                // Handle history and formatting shortcuts before the text edit sees them,
+                // e.g. its own undo or Ctrl+K deleting to the end of the line.
+                if ui.memory(|mem| mem.has_focus(body_id)) {
+                    let redo = egui::KeyboardShortcut::new(
+                        egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                        egui::Key::Z,
+                    );
+                    let undo = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z);
+                    // Tab indents inside the editor, so Ctrl+Tab and Ctrl+Shift+Tab move the
+                    // focus on to the neighbouring widgets instead.
+                    let leave = ui.input_mut(|input| {
+                        // Checked first: a pattern without Shift also matches with it held.
+                        if input.consume_key(
+                            egui::Modifiers::CTRL | egui::Modifiers::SHIFT,
+                            egui::Key::Tab,
+                        ) {
+                            Some(egui::FocusDirection::Previous)
+                        } else if input.consume_key(egui::Modifiers::CTRL, egui::Key::Tab) {
+                            Some(egui::FocusDirection::Next)
+                        } else {
+                            None
+                        }
+                    });
+                    if let Some(direction) = leave {
+                        if direction == egui::FocusDirection::Next {
+                            ui.data_mut(|data| data.insert_temp(corner_id, true));
+                        }
+                        ui.memory_mut(|mem| mem.move_focus(direction));
+                    }
+                    ui.input_mut(|input| {
+                        if input.consume_shortcut(&redo) {
+                            msgs.push(MarkdownMsg::Redo);
+                        } else if input.consume_shortcut(&undo) {
+                            msgs.push(MarkdownMsg::Undo);
+                        }
+                        for (shortcut, shortcut_msgs) in format_shortcuts() {
+                            if input.consume_shortcut(&shortcut) {
+                                msgs.extend(shortcut_msgs);
+                            }
+                        }
+                    });
+                }
+
                 // Calculate desired rows based on available height
                 let line_height = ui.text_style_height(&egui::TextStyle::Monospace);
                 let desired_rows = (ui.available_height() / line_height).max(1.0) as usize;
@@ -357,7 +596,7 @@ pub fn view(model: &MarkdownModel, ui: &mut egui::Ui) -> Vec<MarkdownMsg> {
                 let mut buffer = model.text.clone();
                 let mut output = egui::TextEdit::multiline(&mut buffer)
                     .code_editor()
-                    .id_source(body_id)
+                    .id(body_id)
                     .desired_width(f32::INFINITY)
                     .desired_rows(desired_rows)
                     .show(ui);
@@ -385,6 +624,56 @@ pub fn view(model: &MarkdownModel, ui: &mut egui::Ui) -> Vec<MarkdownMsg> {
     msgs
 }
 
+/// Formatting shortcuts of the focused editor with the messages of the matching toolbar
+/// controls, so a shortcut wraps the selection exactly like a click.
+///
+/// Cut, copy, paste, select all, and undo keep their text edit bindings.
+fn format_shortcuts() -> Vec<(egui::KeyboardShortcut, Vec<MarkdownMsg>)> {
+    let command = |key| egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, key);
+    let mut shortcuts = vec![
+        (
+            command(egui::Key::B),
+            vec![MarkdownMsg::ApplyStyle(StyleKind::Bold)],
+        ),
+        (
+            command(egui::Key::I),
+            vec![MarkdownMsg::ApplyStyle(StyleKind::Italic)],
+        ),
+        (
+            command(egui::Key::K),
+            vec![MarkdownMsg::ApplyStyle(StyleKind::Link)],
+        ),
+        (
+            egui::KeyboardShortcut::new(
+                egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                egui::Key::C,
+            ),
+            vec![
+                MarkdownMsg::SetCodeChoice(CodeChoice::Inline),
+                MarkdownMsg::ApplyStyle(StyleKind::CodeInline),
+            ],
+        ),
+    ];
+    let digits = [
+        egui::Key::Num1,
+        egui::Key::Num2,
+        egui::Key::Num3,
+        egui::Key::Num4,
+        egui::Key::Num5,
+        egui::Key::Num6,
+    ];
+    shortcuts.extend((1..=6).zip(digits).map(|(level, key)| {
+        (
+            command(key),
+            vec![
+                MarkdownMsg::SetHeadingLevel(level),
+                MarkdownMsg::InsertHeading(level),
+            ],
+        )
+    }));
+    shortcuts
+}
+
 /// Map a heading level to its phosphor icon glyph.
 fn heading_icon(level: u8) -> &'static str {
     match level {
@@ -427,7 +716,13 @@ fn apply_style_kind(model: &mut MarkdownModel, kind: StyleKind) {
         StyleKind::Underline => apply_style(model, "<u>", "</u>", "text", false),
         StyleKind::Link => apply_style(model, "[", "](https://example.com)", "text", false),
         StyleKind::Quote => apply_style(model, "\n> ", "", "quote", true),
-        StyleKind::Image => apply_style(model, "![", "](path/to/image.png)", "alt text", false),
+        StyleKind::Image => apply_style(
+            model,
+            "![",
+            "](https://example.com/image.png)",
+            "alt text",
+            false,
+        ),
         StyleKind::Rule => apply_style(model, "\n---\n", "", "", true),
         StyleKind::CodeInline => apply_style(model, "`", "`", "code", false),
         StyleKind::CodeBlock => apply_style(model, "```\n", "\n```", "code", true),
@@ -438,6 +733,38 @@ fn apply_style_kind(model: &mut MarkdownModel, kind: StyleKind) {
     }
 }
 
+/// Embed an attachment as `![alt](./<path>)`, using the selection or the file stem as alt
+/// text; the link is rewritten to the attachment's crate path when the archive is written.
+fn insert_attachment_image(model: &mut MarkdownModel, path: &str) {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let alt = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+    apply_style(model, "![", &format!("](./{path})"), alt, false);
+}
+
+/// Menu listing the entry's image attachments, plus a template for external images.
+fn image_picker(ui: &mut egui::Ui, model: &MarkdownModel, msgs: &mut Vec<MarkdownMsg>) {
+    if model.image_attachments.is_empty() {
+        ui.label(egui::RichText::new(t!("markdown.image.none")).weak());
+    }
+    for path in &model.image_attachments {
+        if ui
+            .button(path)
+            .on_hover_text(t!("markdown.image.embed"))
+            .clicked()
+        {
+            msgs.push(MarkdownMsg::InsertAttachmentImage {
+                sanitized_name: path.clone(),
+            });
+            ui.close();
+        }
+    }
+    ui.separator();
+    if ui.button(t!("markdown.image.url")).clicked() {
+        msgs.push(MarkdownMsg::ApplyStyle(StyleKind::Image));
+        ui.close();
+    }
+}
+
 /// UI control for choosing table dimensions before insertion.
 fn table_size_picker(ui: &mut egui::Ui, model: &MarkdownModel, msgs: &mut Vec<MarkdownMsg>) {
     const MAX_ROWS: u8 = 100;
@@ -449,7 +776,7 @@ fn table_size_picker(ui: &mut egui::Ui, model: &MarkdownModel, msgs: &mut Vec<Ma
         .num_columns(2)
         .spacing(egui::vec2(8.0, 4.0))
         .show(ui, |ui| {
-            ui.label("Rows");
+            ui.label(t!("markdown.table.rows"));
             if ui
                 .add(
                     egui::DragValue::new(&mut rows)
@@ -462,7 +789,7 @@ fn table_size_picker(ui: &mut egui::Ui, model: &MarkdownModel, msgs: &mut Vec<Ma
             }
             ui.end_row();
 
-            ui.label("Columns");
+            ui.label(t!("markdown.table.columns"));
             if ui
                 .add(
                     egui::DragValue::new(&mut cols)
@@ -477,7 +804,11 @@ fn table_size_picker(ui: &mut egui::Ui, model: &MarkdownModel, msgs: &mut Vec<Ma
         });
 
     if ui
-        .button(format!("{} Insert table", egui_phosphor::regular::PLUS))
+        .button(format!(
+            "{} {}",
+            egui_phosphor::regular::PLUS,
+            t!("markdown.table.insert")
+        ))
         .clicked()
     {
         let rows = rows.clamp(1, MAX_ROWS);
@@ -569,12 +900,17 @@ fn insert_table_at_cursor(model: &mut MarkdownModel, rows: u8, cols: u8) {
 }
 
 /// Return (start, end, selected text) for the current cursor range.
+///
+/// Indices past the end of the text are clamped, so `start <= end <= len` always holds.
 fn selection(model: &MarkdownModel) -> (usize, usize, String) {
+    let len = model.text.chars().count();
     let (start_char, end_char) = if let Some(range) = &model.cursor {
-        let (a, b) = (range.primary.index, range.secondary.index);
+        let (a, b) = (
+            usize::from(range.primary.index).min(len),
+            usize::from(range.secondary.index).min(len),
+        );
         (a.min(b), a.max(b))
     } else {
-        let len = model.text.chars().count();
         (len, len)
     };
 
@@ -639,7 +975,6 @@ fn apply_style(
     model.cursor_override = model.cursor;
 }
 
-/// Convert a character index to a byte index, clamping to the end when out of bounds.
 /// Convert a character index to a byte index, clamping to the string end.
 fn char_to_byte(text: &str, char_idx: usize) -> usize {
     if char_idx == text.chars().count() {
@@ -650,3 +985,218 @@ fn char_to_byte(text: &str, char_idx: usize) -> usize {
         .map(|(i, _)| i)
         .unwrap_or_else(|| text.len())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_shortcuts_emit_the_toolbar_messages() {
+        let shortcuts = format_shortcuts();
+        let msgs_for = |modifiers: egui::Modifiers, key: egui::Key| {
+            shortcuts
+                .iter()
+                .find(|(shortcut, _)| {
+                    shortcut.modifiers == modifiers && shortcut.logical_key == key
+                })
+                .map(|(_, msgs)| msgs.clone())
+                .unwrap_or_default()
+        };
+        let command = egui::Modifiers::COMMAND;
+
+        assert!(matches!(
+            msgs_for(command, egui::Key::I).as_slice(),
+            [MarkdownMsg::ApplyStyle(StyleKind::Italic)]
+        ));
+        assert!(matches!(
+            msgs_for(command, egui::Key::K).as_slice(),
+            [MarkdownMsg::ApplyStyle(StyleKind::Link)]
+        ));
+        assert!(matches!(
+            msgs_for(command | egui::Modifiers::SHIFT, egui::Key::C).as_slice(),
+            [
+                MarkdownMsg::SetCodeChoice(CodeChoice::Inline),
+                MarkdownMsg::ApplyStyle(StyleKind::CodeInline)
+            ]
+        ));
+        assert!(matches!(
+            msgs_for(command, egui::Key::Num3).as_slice(),
+            [
+                MarkdownMsg::SetHeadingLevel(3),
+                MarkdownMsg::InsertHeading(3)
+            ]
+        ));
+        for key in [
+            egui::Key::A,
+            egui::Key::C,
+            egui::Key::V,
+            egui::Key::X,
+            egui::Key::Y,
+            egui::Key::Z,
+        ] {
+            assert!(
+                msgs_for(command, key).is_empty(),
+                "{key:?} stays with the text edit"
+            );
+        }
+
+        // A shortcut wraps the selection like the toolbar button.
+        let mut model = model_with("see results");
+        model.cursor = Some(CCursorRange::two(CCursor::new(4), CCursor::new(11)));
+        for msg in msgs_for(command, egui::Key::B) {
+            update(&mut model, msg);
+        }
+        assert_eq!(model.text, "see **results**");
+    }
+
+    fn model_with(text: &str) -> MarkdownModel {
+        MarkdownModel {
+            text: text.into(),
+            cursor: Some(CCursorRange::one(CCursor::new(text.chars().count()))),
+            ..Default::default()
+        }
+    }
+
+    /// Deterministic xorshift generator, so failures reproduce without a property-test crate.
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, n: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % n as u64) as usize
+        }
+    }
+
+    fn assert_cursor_in_bounds(model: &MarkdownModel, step: &str) {
+        let len = model.text.chars().count();
+        for range in [model.cursor, model.cursor_override].into_iter().flatten() {
+            assert!(
+                usize::from(range.primary.index) <= len
+                    && usize::from(range.secondary.index) <= len,
+                "{step}: cursor {range:?} beyond {len} chars of {:?}",
+                model.text
+            );
+        }
+    }
+
+    #[test]
+    fn stale_inverted_cursor_is_clamped_before_styling() {
+        let mut model = model_with("héllo wörld");
+        model.cursor = Some(CCursorRange::two(CCursor::new(40), CCursor::new(6)));
+        model.text = "hé".into();
+        normalize_cursor(&mut model);
+        assert_eq!(
+            model.cursor,
+            Some(CCursorRange::two(CCursor::new(2), CCursor::new(2)))
+        );
+
+        model.cursor = Some(CCursorRange::two(CCursor::new(40), CCursor::new(1)));
+        update(&mut model, MarkdownMsg::ApplyStyle(StyleKind::Bold));
+        assert_eq!(model.text, "h**é**");
+        assert_cursor_in_bounds(&model, "bold");
+    }
+
+    #[test]
+    fn random_edits_and_cursors_never_panic_or_escape_the_text() {
+        const SAMPLES: [&str; 5] = ["", "a", "héllo\nwörld", "# Title\n\n- 🧪 item\n", "x y z"];
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        let mut model = MarkdownModel::default();
+        for step in 0..2000 {
+            let len = model.text.chars().count();
+            let cursor = |rng: &mut Rng| CCursor::new(rng.below(len + 8));
+            let msg = match rng.below(8) {
+                0 => MarkdownMsg::SetText(SAMPLES[rng.below(SAMPLES.len())].into()),
+                1 => {
+                    // Text replaced behind the editor's back, keeping the stale cursor.
+                    model.text = SAMPLES[rng.below(SAMPLES.len())].into();
+                    MarkdownMsg::ClearCursorOverride
+                }
+                2 => MarkdownMsg::SetCursor(Some(CCursorRange::two(
+                    cursor(&mut rng),
+                    cursor(&mut rng),
+                ))),
+                3 => MarkdownMsg::ApplyStyle(
+                    [
+                        StyleKind::Bold,
+                        StyleKind::Quote,
+                        StyleKind::CodeBlock,
+                        StyleKind::Link,
+                    ][rng.below(4)],
+                ),
+                4 => MarkdownMsg::InsertHeading(rng.below(8) as u8),
+                5 => MarkdownMsg::InsertTable { rows: 2, cols: 2 },
+                6 => MarkdownMsg::Undo,
+                _ => MarkdownMsg::Redo,
+            };
+            let described = format!("step {step}: {msg:?}");
+            update(&mut model, msg);
+            assert_cursor_in_bounds(&model, &described);
+        }
+    }
+
+    #[test]
+    fn undo_and_redo_table_insertion() {
+        let mut model = model_with("Intro");
+        let cursor_before = model.cursor;
+
+        update(&mut model, MarkdownMsg::InsertTable { rows: 2, cols: 2 });
+        let with_table = model.text.clone();
+        assert!(with_table.contains("Column 2"));
+
+        update(&mut model, MarkdownMsg::Undo);
+        assert_eq!(model.text, "Intro");
+        assert_eq!(model.cursor_override, cursor_before);
+        assert!(model.can_redo());
+
+        update(&mut model, MarkdownMsg::Redo);
+        assert_eq!(model.text, with_table);
+        assert!(!model.can_redo());
+    }
+
+    #[test]
+    fn typing_is_coalesced_into_undo_units() {
+        let mut model = model_with("");
+        for text in ["a", "ab", "abc", "abc\n", "abc\nd"] {
+            update(&mut model, MarkdownMsg::SetText(text.into()));
+        }
+
+        update(&mut model, MarkdownMsg::Undo);
+        assert_eq!(model.text, "abc");
+        update(&mut model, MarkdownMsg::Undo);
+        assert_eq!(model.text, "");
+        assert!(!model.can_undo());
+    }
+
+    #[test]
+    fn new_edit_discards_redo_branch() {
+        let mut model = model_with("x");
+        update(&mut model, MarkdownMsg::ApplyStyle(StyleKind::Bold));
+        update(&mut model, MarkdownMsg::Undo);
+
+        update(&mut model, MarkdownMsg::ApplyStyle(StyleKind::Italic));
+
+        assert!(!model.can_redo());
+    }
+
+    #[test]
+    fn attachment_images_are_embedded_relative_to_the_entry_folder() {
+        let mut model = model_with("See ");
+        update(
+            &mut model,
+            MarkdownMsg::SetImageAttachments(vec!["raw/gel_1.png".into()]),
+        );
+
+        update(
+            &mut model,
+            MarkdownMsg::InsertAttachmentImage {
+                sanitized_name: "raw/gel_1.png".into(),
+            },
+        );
+
+        assert_eq!(model.text, "See ![gel_1](./raw/gel_1.png)");
+        update(&mut model, MarkdownMsg::Undo);
+        assert_eq!(model.text, "See ");
+    }
+}
@@ -4,7 +4,104 @@
 //! Reusable egui components structured for MVU-style updates.
 
 pub mod attachments;
+pub mod authors;
 pub mod datetime_picker;
 pub mod extra_fields;
 pub mod keywords;
 pub mod markdown;
+pub mod onboarding;
+pub mod related_links;
+pub mod settings;
+pub mod templates;
+
+use std::fmt;
+
+use eframe::egui;
+
+/// Title of a collapsible section summarizing its content, so it is informative collapsed,
+/// e.g. `Metadata (8 fields, 2 invalid)`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SectionTitle {
+    pub name: &'static str,
+    /// Counts in parentheses after the name, e.g. `8 fields`.
+    pub counts: Vec<String>,
+    /// Problem count shown in red after the counts, e.g. `2 invalid`.
+    pub problems: Option<String>,
+    /// Why something in the section prevents saving, if it does.
+    pub blocker: Option<&'static str>,
+}
+
+impl fmt::Display for SectionTitle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)?;
+        let details: Vec<&str> = self
+            .counts
+            .iter()
+            .chain(&self.problems)
+            .map(String::as_str)
+            .collect();
+        if !details.is_empty() {
+            write!(f, " ({})", details.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl SectionTitle {
+    /// Header text with the problem count in red and a warning glyph for blockers.
+    pub fn widget_text(&self, ui: &egui::Ui) -> egui::WidgetText {
+        let visuals = &ui.style().visuals;
+        let format = |color| egui::TextFormat {
+            font_id: egui::TextStyle::Body.resolve(ui.style()),
+            color,
+            ..Default::default()
+        };
+        let plain = format(visuals.text_color());
+        let mut job = egui::text::LayoutJob::default();
+        if self.blocker.is_some() {
+            job.append(
+                egui_phosphor::regular::WARNING,
+                0.0,
+                format(visuals.warn_fg_color),
+            );
+            job.append(" ", 0.0, plain.clone());
+        }
+        job.append(self.name, 0.0, plain.clone());
+        if self.counts.is_empty() && self.problems.is_none() {
+            return job.into();
+        }
+        job.append(&format!(" ({}", self.counts.join(", ")), 0.0, plain.clone());
+        if let Some(problems) = &self.problems {
+            let separator = if self.counts.is_empty() { "" } else { ", " };
+            job.append(separator, 0.0, plain.clone());
+            job.append(problems, 0.0, format(visuals.error_fg_color));
+        }
+        job.append(")", 0.0, plain);
+        job.into()
+    }
+}
+
+/// Accessible names for buttons that show only an icon glyph.
+pub trait IconLabel {
+    /// Name the button `label` for screen readers (AccessKit) and show it on hover.
+    fn icon_label(self, label: &str) -> Self;
+
+    /// Like [`Self::icon_label`], for a combo box whose closed state shows only an icon.
+    fn picker_label(self, label: &str) -> Self;
+}
+
+impl IconLabel for egui::Response {
+    fn icon_label(self, label: &str) -> Self {
+        self.widget_info(|| {
+            egui::WidgetInfo::labeled(egui::WidgetType::Button, self.enabled(), label)
+        });
+        self.on_hover_text(label)
+    }
+
+    fn picker_label(self, label: &str) -> Self {
+        self.widget_info(|| {
+            egui::WidgetInfo::labeled(egui::WidgetType::ComboBox, self.enabled(), label)
+        });
+        self.on_hover_text(label)
+    }
+}
@@ -0,0 +1,539 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! First-run setup collecting the user's name, eLabFTW instance and entry defaults.
+//!
+//! The step sequence and per-step validation live in [`update`]; [`view`] only renders the
+//! current step. Results are written into the same settings and author list the Settings
+//! window and the Authors section edit.
+
+use eframe::egui;
+
+use crate::i18n::t;
+use crate::logic::eln::{ArchiveGenre, BodyFormat};
+use crate::logic::settings::{EntryDefaults, normalize_instance_url};
+use crate::models::author::{Author, validate_author};
+
+/// Steps of the setup, in order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OnboardingStep {
+    #[default]
+    Author,
+    Instance,
+    Defaults,
+}
+
+impl OnboardingStep {
+    /// All steps in the order they are shown.
+    pub const ALL: [OnboardingStep; 3] = [
+        OnboardingStep::Author,
+        OnboardingStep::Instance,
+        OnboardingStep::Defaults,
+    ];
+
+    /// Heading shown above the step.
+    pub fn title(self) -> &'static str {
+        match self {
+            OnboardingStep::Author => t!("onboarding.author"),
+            OnboardingStep::Instance => t!("onboarding.instance"),
+            OnboardingStep::Defaults => t!("onboarding.defaults"),
+        }
+    }
+
+    /// Position of the step, starting at 0.
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|step| *step == self).unwrap_or(0)
+    }
+
+    fn next(self) -> Option<Self> {
+        Self::ALL.get(self.index() + 1).copied()
+    }
+
+    fn previous(self) -> Option<Self> {
+        self.index().checked_sub(1).map(|index| Self::ALL[index])
+    }
+}
+
+/// Values entered so far; prefilled from the current settings when the setup starts.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OnboardingAnswers {
+    pub given_name: String,
+    pub family_name: String,
+    pub orcid: String,
+    pub elabftw_url: String,
+    pub defaults: EntryDefaults,
+}
+
+/// State of the setup dialog.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OnboardingModel {
+    open: bool,
+    step: OnboardingStep,
+    answers: OnboardingAnswers,
+    /// Why the current step cannot be left yet.
+    error: Option<String>,
+}
+
+/// Editable text fields of the setup.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OnboardingField {
+    GivenName,
+    FamilyName,
+    Orcid,
+    ElabftwUrl,
+}
+
+/// Messages emitted by the setup dialog.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OnboardingMsg {
+    /// Show the dialog at the first step with `answers` prefilled.
+    Start(OnboardingAnswers),
+    Edit {
+        field: OnboardingField,
+        value: String,
+    },
+    SetBodyFormat(BodyFormat),
+    SetGenre(ArchiveGenre),
+    /// Validate the current step and move on; finishes on the last step.
+    Next,
+    Back,
+    /// Close without changing anything.
+    Skip,
+}
+
+/// Outcome of the setup, applied by the caller.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OnboardingEvent {
+    Finished(OnboardingResult),
+    Skipped,
+}
+
+/// Validated answers of a completed setup.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OnboardingResult {
+    /// Name and ORCID iD of the first author; `None` when left blank.
+    pub author: Option<Author>,
+    /// Normalized eLabFTW address; empty when not configured.
+    pub elabftw_url: String,
+    pub defaults: EntryDefaults,
+}
+
+impl OnboardingModel {
+    /// Whether the setup dialog is shown.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Step currently shown.
+    pub fn step(&self) -> OnboardingStep {
+        self.step
+    }
+
+    /// Values entered so far.
+    pub fn answers(&self) -> &OnboardingAnswers {
+        &self.answers
+    }
+
+    /// Why the current step cannot be left yet.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+/// Apply a message to the setup model; returns the outcome once the dialog closes.
+pub fn update(model: &mut OnboardingModel, msg: OnboardingMsg) -> Option<OnboardingEvent> {
+    match msg {
+        OnboardingMsg::Start(answers) => {
+            *model = OnboardingModel {
+                open: true,
+                answers,
+                ..Default::default()
+            };
+            None
+        }
+        OnboardingMsg::Edit { field, value } => {
+            let answers = &mut model.answers;
+            let slot = match field {
+                OnboardingField::GivenName => &mut answers.given_name,
+                OnboardingField::FamilyName => &mut answers.family_name,
+                OnboardingField::Orcid => &mut answers.orcid,
+                OnboardingField::ElabftwUrl => &mut answers.elabftw_url,
+            };
+            *slot = value;
+            model.error = None;
+            None
+        }
+        OnboardingMsg::SetBodyFormat(format) => {
+            model.answers.defaults.body_format = format;
+            None
+        }
+        OnboardingMsg::SetGenre(genre) => {
+            model.answers.defaults.genre = genre;
+            None
+        }
+        OnboardingMsg::Next => {
+            if let Err(err) = validate_step(&model.answers, model.step) {
+                model.error = Some(err);
+                return None;
+            }
+            model.error = None;
+            if let Some(next) = model.step.next() {
+                model.step = next;
+                return None;
+            }
+            // Every step passed validation, so the answers convert without errors.
+            let result = OnboardingResult {
+                author: author_answer(&model.answers).ok().flatten(),
+                elabftw_url: normalize_instance_url(&model.answers.elabftw_url).unwrap_or_default(),
+                defaults: model.answers.defaults.clone(),
+            };
+            model.open = false;
+            Some(OnboardingEvent::Finished(result))
+        }
+        OnboardingMsg::Back => {
+            if let Some(previous) = model.step.previous() {
+                model.step = previous;
+                model.error = None;
+            }
+            None
+        }
+        OnboardingMsg::Skip => {
+            model.open = false;
+            model.error = None;
+            Some(OnboardingEvent::Skipped)
+        }
+    }
+}
+
+/// Check the answers belonging to `step`.
+fn validate_step(answers: &OnboardingAnswers, step: OnboardingStep) -> Result<(), String> {
+    match step {
+        OnboardingStep::Author => author_answer(answers).map(|_| ()),
+        OnboardingStep::Instance => normalize_instance_url(&answers.elabftw_url)
+            .map(|_| ())
+            .map_err(|err| err.to_string()),
+        OnboardingStep::Defaults => Ok(()),
+    }
+}
+
+/// The entered author, or `None` when all author fields are blank.
+fn author_answer(answers: &OnboardingAnswers) -> Result<Option<Author>, String> {
+    let author = Author {
+        given_name: answers.given_name.clone(),
+        family_name: answers.family_name.clone(),
+        orcid: answers.orcid.clone(),
+        ..Default::default()
+    };
+    if author.is_blank() {
+        return Ok(None);
+    }
+    validate_author(&author)
+        .map(Some)
+        .map_err(|err| err.to_string())
+}
+
+/// Render the setup dialog when open and return triggered messages.
+pub fn view(ctx: &egui::Context, model: &OnboardingModel) -> Vec<OnboardingMsg> {
+    let mut msgs = Vec::new();
+    if !model.is_open() {
+        return msgs;
+    }
+
+    let step = model.step();
+    egui::Window::new(t!("onboarding.title"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(t!(
+                    "onboarding.step",
+                    number = step.index() + 1,
+                    total = OnboardingStep::ALL.len()
+                ))
+                .small()
+                .color(egui::Color32::from_gray(110)),
+            );
+            ui.heading(step.title());
+            ui.add_space(4.0);
+
+            match step {
+                OnboardingStep::Author => render_author_step(ui, model.answers(), &mut msgs),
+                OnboardingStep::Instance => render_instance_step(ui, model.answers(), &mut msgs),
+                OnboardingStep::Defaults => render_defaults_step(ui, model.answers(), &mut msgs),
+            }
+
+            if let Some(err) = model.error() {
+                ui.colored_label(
+                    egui::Color32::from_rgb(200, 80, 80),
+                    format!("{} {err}", egui_phosphor::regular::WARNING),
+                );
+            }
+
+            ui.add_space(8.0);
+            ui.label(
+                egui::RichText::new(t!("onboarding.later"))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
+            );
+            ui.horizontal(|ui| {
+                if ui.button(t!("onboarding.skip")).clicked() {
+                    msgs.push(OnboardingMsg::Skip);
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let next = if step.next().is_some() {
+                        t!("onboarding.next")
+                    } else {
+                        t!("onboarding.finish")
+                    };
+                    if ui.button(next).clicked() {
+                        msgs.push(OnboardingMsg::Next);
+                    }
+                    if ui
+                        .add_enabled(
+                            step.previous().is_some(),
+                            egui::Button::new(t!("onboarding.back")),
+                        )
+                        .clicked()
+                    {
+                        msgs.push(OnboardingMsg::Back);
+                    }
+                });
+            });
+        });
+
+    msgs
+}
+
+fn render_author_step(
+    ui: &mut egui::Ui,
+    answers: &OnboardingAnswers,
+    msgs: &mut Vec<OnboardingMsg>,
+) {
+    ui.label(t!("onboarding.author.text"));
+    ui.add_space(4.0);
+    egui::Grid::new("onboarding_author_grid")
+        .num_columns(2)
+        .spacing(egui::vec2(8.0, 4.0))
+        .show(ui, |ui| {
+            let fields = [
+                (
+                    t!("onboarding.author.given"),
+                    OnboardingField::GivenName,
+                    &answers.given_name,
+                    "",
+                ),
+                (
+                    t!("onboarding.author.family"),
+                    OnboardingField::FamilyName,
+                    &answers.family_name,
+                    "",
+                ),
+                (
+                    "ORCID",
+                    OnboardingField::Orcid,
+                    &answers.orcid,
+                    "0000-0000-0000-0000",
+                ),
+            ];
+            for (label, field, value, hint) in fields {
+                ui.label(label);
+                text_input(ui, field, value, hint, msgs);
+                ui.end_row();
+            }
+        });
+}
+
+fn render_instance_step(
+    ui: &mut egui::Ui,
+    answers: &OnboardingAnswers,
+    msgs: &mut Vec<OnboardingMsg>,
+) {
+    ui.label(t!("onboarding.instance.text"));
+    ui.add_space(4.0);
+    ui.horizontal(|ui| {
+        ui.label(t!("onboarding.instance.address"));
+        text_input(
+            ui,
+            OnboardingField::ElabftwUrl,
+            &answers.elabftw_url,
+            t!("settings.elabftw.url.hint"),
+            msgs,
+        );
+    });
+}
+
+fn render_defaults_step(
+    ui: &mut egui::Ui,
+    answers: &OnboardingAnswers,
+    msgs: &mut Vec<OnboardingMsg>,
+) {
+    ui.label(t!("onboarding.defaults.text"));
+    ui.add_space(4.0);
+    let defaults = &answers.defaults;
+    egui::Grid::new("onboarding_defaults_grid")
+        .num_columns(2)
+        .spacing(egui::vec2(8.0, 4.0))
+        .show(ui, |ui| {
+            ui.label(t!("onboarding.defaults.format"));
+            ui.horizontal(|ui| {
+                let mut format = defaults.body_format;
+                ui.radio_value(&mut format, BodyFormat::Html, "HTML")
+                    .on_hover_text(t!("format.html.hover"));
+                ui.radio_value(&mut format, BodyFormat::Markdown, "Markdown")
+                    .on_hover_text(t!("format.markdown.hover"));
+                ui.radio_value(&mut format, BodyFormat::Both, t!("format.both"))
+                    .on_hover_text(t!("format.both.hover"));
+                if format != defaults.body_format {
+                    msgs.push(OnboardingMsg::SetBodyFormat(format));
+                }
+            });
+            ui.end_row();
+
+            ui.label(t!("meta.entry_type"));
+            ui.horizontal(|ui| {
+                let mut genre = defaults.genre;
+                ui.radio_value(&mut genre, ArchiveGenre::Experiment, t!("genre.experiment"));
+                ui.radio_value(&mut genre, ArchiveGenre::Resource, t!("genre.resource"));
+                if genre != defaults.genre {
+                    msgs.push(OnboardingMsg::SetGenre(genre));
+                }
+            });
+            ui.end_row();
+        });
+}
+
+fn text_input(
+    ui: &mut egui::Ui,
+    field: OnboardingField,
+    value: &str,
+    hint: &str,
+    msgs: &mut Vec<OnboardingMsg>,
+) {
+    let mut text = value.to_string();
+    if ui
+        .add(
+            egui::TextEdit::singleline(&mut text)
+                .hint_text(hint)
+                .desired_width(240.0),
+        )
+        .changed()
+    {
+        msgs.push(OnboardingMsg::Edit { field, value: text });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::license::License;
+
+    fn started(answers: OnboardingAnswers) -> OnboardingModel {
+        let mut model = OnboardingModel::default();
+        update(&mut model, OnboardingMsg::Start(answers));
+        model
+    }
+
+    fn edit(model: &mut OnboardingModel, field: OnboardingField, value: &str) {
+        update(
+            model,
+            OnboardingMsg::Edit {
+                field,
+                value: value.into(),
+            },
+        );
+    }
+
+    #[test]
+    fn steps_advance_only_when_valid_and_finish_with_normalized_answers() {
+        let mut model = started(OnboardingAnswers::default());
+        assert!(model.is_open());
+        assert_eq!(model.step(), OnboardingStep::Author);
+
+        edit(&mut model, OnboardingField::Orcid, "0000-0002-1825-0097");
+        assert_eq!(update(&mut model, OnboardingMsg::Next), None);
+        assert_eq!(model.step(), OnboardingStep::Author, "ORCID without a name");
+        assert!(model.error().is_some());
+
+        edit(&mut model, OnboardingField::FamilyName, " Meitner ");
+        assert_eq!(model.error(), None, "editing clears the error");
+        update(&mut model, OnboardingMsg::Next);
+        assert_eq!(model.step(), OnboardingStep::Instance);
+
+        edit(&mut model, OnboardingField::ElabftwUrl, "ftp://elab");
+        update(&mut model, OnboardingMsg::Next);
+        assert_eq!(model.step(), OnboardingStep::Instance);
+        edit(&mut model, OnboardingField::ElabftwUrl, "elab.example.org/");
+        update(&mut model, OnboardingMsg::Next);
+        assert_eq!(model.step(), OnboardingStep::Defaults);
+
+        update(&mut model, OnboardingMsg::Back);
+        assert_eq!(model.step(), OnboardingStep::Instance);
+        update(&mut model, OnboardingMsg::Next);
+        update(&mut model, OnboardingMsg::SetGenre(ArchiveGenre::Resource));
+        update(
+            &mut model,
+            OnboardingMsg::SetBodyFormat(BodyFormat::Markdown),
+        );
+
+        let event = update(&mut model, OnboardingMsg::Next);
+        assert!(!model.is_open());
+        assert_eq!(
+            event,
+            Some(OnboardingEvent::Finished(OnboardingResult {
+                author: Some(Author {
+                    family_name: "Meitner".into(),
+                    orcid: "0000-0002-1825-0097".into(),
+                    ..Default::default()
+                }),
+                elabftw_url: "https://elab.example.org".into(),
+                defaults: EntryDefaults {
+                    body_format: BodyFormat::Markdown,
+                    genre: ArchiveGenre::Resource,
+                    license: License::None,
+                },
+            }))
+        );
+    }
+
+    #[test]
+    fn blank_answers_finish_without_author_and_skip_closes_without_result() {
+        let mut model = started(OnboardingAnswers::default());
+        update(&mut model, OnboardingMsg::Back);
+        assert_eq!(
+            model.step(),
+            OnboardingStep::Author,
+            "no step before the first"
+        );
+        for _ in 0..2 {
+            update(&mut model, OnboardingMsg::Next);
+        }
+        assert!(matches!(
+            update(&mut model, OnboardingMsg::Next),
+            Some(OnboardingEvent::Finished(OnboardingResult { author: None, ref elabftw_url, .. }))
+                if elabftw_url.is_empty()
+        ));
+
+        let mut model = started(OnboardingAnswers {
+            given_name: "Lise".into(),
+            ..Default::default()
+        });
+        update(&mut model, OnboardingMsg::Next);
+        assert_eq!(
+            update(&mut model, OnboardingMsg::Skip),
+            Some(OnboardingEvent::Skipped)
+        );
+        assert!(!model.is_open());
+
+        update(
+            &mut model,
+            OnboardingMsg::Start(OnboardingAnswers::default()),
+        );
+        assert_eq!(
+            model.step(),
+            OnboardingStep::Author,
+            "restarting begins anew"
+        );
+        assert_eq!(model.answers(), &OnboardingAnswers::default());
+    }
+}
@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Typed links from the entry to related experiments and resources.
+
+use eframe::egui;
+
+use crate::i18n::t;
+use crate::models::related_link::{RelatedLink, Relation, validate_related_link};
+use crate::ui::components::IconLabel;
+
+/// Editable link list of one entry.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RelatedLinksModel {
+    links: Vec<RelatedLink>,
+}
+
+/// Editable link fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkField {
+    Label,
+    Url,
+}
+
+/// Messages emitted by the related links section.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RelatedLinksMsg {
+    /// Replace the list, e.g. from a draft or an opened archive.
+    Restore(Vec<RelatedLink>),
+    Add,
+    Remove(usize),
+    Edit {
+        index: usize,
+        field: LinkField,
+        value: String,
+    },
+    SetRelation {
+        index: usize,
+        relation: Relation,
+    },
+}
+
+impl RelatedLinksModel {
+    /// Links in the order they were added, including blank rows still being filled in.
+    pub fn links(&self) -> &[RelatedLink] {
+        &self.links
+    }
+}
+
+/// Apply a message to the related links model.
+pub fn update(model: &mut RelatedLinksModel, msg: RelatedLinksMsg) {
+    match msg {
+        RelatedLinksMsg::Restore(links) => model.links = links,
+        RelatedLinksMsg::Add => model.links.push(RelatedLink::default()),
+        RelatedLinksMsg::Remove(index) => {
+            if index < model.links.len() {
+                model.links.remove(index);
+            }
+        }
+        RelatedLinksMsg::Edit {
+            index,
+            field,
+            value,
+        } => {
+            if let Some(link) = model.links.get_mut(index) {
+                match field {
+                    LinkField::Label => link.label = value,
+                    LinkField::Url => link.url = value,
+                }
+            }
+        }
+        RelatedLinksMsg::SetRelation { index, relation } => {
+            if let Some(link) = model.links.get_mut(index) {
+                link.relation = relation;
+            }
+        }
+    }
+}
+
+/// Render the collapsible related links section and return triggered messages.
+pub fn view(ui: &mut egui::Ui, model: &RelatedLinksModel) -> Vec<RelatedLinksMsg> {
+    let mut msgs = Vec::new();
+    let header = match model.links.len() {
+        0 => t!("links.title").to_string(),
+        count => t!("links.title.count", count = count),
+    };
+
+    egui::CollapsingHeader::new(header)
+        .id_salt("related_links_section")
+        .default_open(!model.links.is_empty())
+        .show(ui, |ui| {
+            ui.label(
+                egui::RichText::new(t!("links.text"))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
+            );
+            ui.add_space(4.0);
+
+            for (index, link) in model.links.iter().enumerate() {
+                render_link(ui, index, link, &mut msgs);
+            }
+
+            if ui
+                .button(format!(
+                    "{} {}",
+                    egui_phosphor::regular::PLUS,
+                    t!("links.add")
+                ))
+                .clicked()
+            {
+                msgs.push(RelatedLinksMsg::Add);
+            }
+        });
+
+    msgs
+}
+
+/// Render one link as a row of relation, label, and URL with a remove button.
+fn render_link(
+    ui: &mut egui::Ui,
+    index: usize,
+    link: &RelatedLink,
+    msgs: &mut Vec<RelatedLinksMsg>,
+) {
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_salt(("related_link_relation", index))
+            .selected_text(link.relation.label())
+            .show_ui(ui, |ui| {
+                for relation in Relation::ALL {
+                    if ui
+                        .selectable_label(link.relation == relation, relation.label())
+                        .clicked()
+                    {
+                        msgs.push(RelatedLinksMsg::SetRelation { index, relation });
+                    }
+                }
+            });
+
+        for (field, value, hint, width) in [
+            (LinkField::Label, &link.label, t!("links.label"), 160.0),
+            (LinkField::Url, &link.url, "https://…", 260.0),
+        ] {
+            let mut text = value.clone();
+            if ui
+                .add(
+                    egui::TextEdit::singleline(&mut text)
+                        .hint_text(hint)
+                        .desired_width(width),
+                )
+                .changed()
+            {
+                msgs.push(RelatedLinksMsg::Edit {
+                    index,
+                    field,
+                    value: text,
+                });
+            }
+        }
+
+        if !link.is_blank()
+            && let Err(err) = validate_related_link(link)
+        {
+            ui.colored_label(
+                egui::Color32::from_rgb(200, 140, 40),
+                egui_phosphor::regular::WARNING,
+            )
+            .on_hover_text(err.to_string());
+        }
+        if ui
+            .small_button(egui_phosphor::regular::TRASH_SIMPLE)
+            .icon_label(t!("links.remove"))
+            .clicked()
+        {
+            msgs.push(RelatedLinksMsg::Remove(index));
+        }
+    });
+}
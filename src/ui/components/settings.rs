@@ -0,0 +1,1737 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Settings window for persistent user preferences.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+
+use crate::i18n::t;
+use crate::logic::elabftw::UploadTarget;
+use crate::logic::eln::{
+    ArchiveGenre, BodyFormat, Compression, CompressionOptions, DEFLATE_LEVELS, PublisherInfo,
+    RoCrateVersion,
+};
+use crate::logic::favorites::PickerKind;
+use crate::logic::html_export::{HtmlExportSettings, HtmlPolicy};
+use crate::logic::settings::{
+    EntryDefaults, HashingSettings, Language, Settings, SettingsExport, ThemeSetting,
+    WindowSettings, normalize_instance_url, normalize_ror, normalize_web_address,
+};
+use crate::logic::units::{format_unit_codes, parse_unit_codes};
+use crate::models::attachment::{AttachmentPolicy, parse_blocked_types};
+use crate::models::keywords::same_keyword;
+use crate::models::license::License;
+use crate::utils::hash::HashAlgorithm;
+
+/// UI state for the settings window plus the currently applied settings.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SettingsModel {
+    settings: Settings,
+    open: bool,
+    tags_input: String,
+    classes_input: String,
+    horizon_input: u32,
+    zip_comment_input: bool,
+    readme_input: bool,
+    license_input: String,
+    /// Path of the signing key, empty for unsigned archives.
+    signing_key_input: String,
+    /// Edited eLabFTW address, validated on save.
+    elabftw_input: String,
+    /// Edited publisher; URL and ROR identifier are validated on save.
+    publisher_input: PublisherInfo,
+    /// eLabFTW API key; kept out of `settings` because it is stored in its own file.
+    api_key: String,
+    api_key_input: String,
+    upload_input: bool,
+    /// Include the API key in the next settings export.
+    export_key_input: bool,
+    /// Imported settings waiting for the user to confirm what they overwrite.
+    pending_import: Option<Box<SettingsExport>>,
+    /// Favorite folders, one per line.
+    favorites_input: String,
+    /// UCUM codes as `unit = code`, one per line.
+    units_input: String,
+    /// Labels of the fields mirrored as keywords, one per line.
+    keyword_fields_input: String,
+    /// Blocked attachment extensions and MIME patterns, comma separated.
+    blocked_types_input: String,
+    /// Attachment size warning in MB; 0 turns it off.
+    size_warning_input: u64,
+    /// Whether each favorite folder existed at the last background check.
+    favorite_status: HashMap<PathBuf, bool>,
+    errors: Vec<String>,
+    /// Confirm the next successful write; only saves from the window are announced.
+    announce_save: bool,
+}
+
+/// Messages emitted by the settings window.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SettingsMsg {
+    Open,
+    Close,
+    /// Settings read from disk at startup.
+    Loaded(Box<Settings>),
+    TagsInputChanged(String),
+    ClassesInputChanged(String),
+    /// Edited age limit for performed-at dates, applied on save.
+    HorizonChanged(u32),
+    /// Edited zip comment toggle, applied on save.
+    ZipCommentChanged(bool),
+    /// Edited README toggle, applied on save.
+    ReadmeChanged(bool),
+    /// Edited README license, applied on save.
+    LicenseChanged(String),
+    /// Edited signing key path, applied on save.
+    SigningKeyChanged(String),
+    /// Edited eLabFTW address, applied on save.
+    ElabftwUrlChanged(String),
+    /// Edited publisher name, applied on save.
+    PublisherNameChanged(String),
+    /// Edited publisher web address, applied on save.
+    PublisherUrlChanged(String),
+    /// Edited publisher ROR identifier, applied on save.
+    PublisherRorChanged(String),
+    /// Stored API key read at startup.
+    ApiKeyLoaded(String),
+    /// Edited API key, applied on save.
+    ApiKeyChanged(String),
+    /// Edited upload-after-save toggle, applied on save.
+    UploadAfterSaveChanged(bool),
+    /// Result of writing the API key file.
+    ApiKeyStored(Result<(), String>),
+    ExportKeyToggled(bool),
+    /// Write the applied settings to a file chosen by the user.
+    ExportRequested,
+    /// Export finished; `None` when the dialog was cancelled.
+    Exported(Result<Option<PathBuf>, String>),
+    /// Read settings from a file chosen by the user.
+    ImportRequested,
+    /// Export file read; `None` when the dialog was cancelled.
+    ImportLoaded(Result<Option<Box<SettingsExport>>, String>),
+    /// Apply the imported settings listed in the confirmation dialog.
+    ConfirmImport,
+    CancelImport,
+    /// Toggle the archive preview; applied and persisted immediately.
+    SetIncludePreview(bool),
+    /// Toggle compact mode; applied and persisted immediately.
+    SetCompactMode(bool),
+    /// Choose the RO-Crate version of saved archives; applied and persisted immediately.
+    SetCrateVersion(RoCrateVersion),
+    /// Choose the digests computed for new attachments; applied and persisted immediately.
+    SetHashing(HashingSettings),
+    /// Toggle the `checksums.sha512` manifest; applied and persisted immediately.
+    SetChecksumsManifest(bool),
+    SetVerifyAfterSave(bool),
+    /// Toggle copying imported metadata files into archives; applied and persisted immediately.
+    SetIncludeImportedMetadata(bool),
+    /// Choose how saved archives are compressed; applied and persisted immediately.
+    SetCompression(CompressionOptions),
+    /// Theme chosen in the top bar; applied and persisted immediately.
+    SetTheme(ThemeSetting),
+    /// Interface language chosen in the top bar; applied and persisted immediately.
+    SetLanguage(Language),
+    /// Toggle the font coverage panel; applied and persisted immediately.
+    SetShowFontCoverage(bool),
+    /// Window resized or (un)maximized; persisted immediately.
+    SetWindow(WindowSettings),
+    /// Body format chosen for an entry; new entries start with it.
+    SetDefaultBodyFormat(BodyFormat),
+    /// Genre chosen for an entry; new entries start with it.
+    SetDefaultGenre(ArchiveGenre),
+    /// License chosen for an entry; new entries start with it.
+    SetDefaultLicense(License),
+    /// A dialog of `kind` was confirmed in `dir`; the next one starts there.
+    RememberDir {
+        kind: PickerKind,
+        dir: PathBuf,
+    },
+    /// A template was applied; it moves to the front of the quick-switch list.
+    TemplateUsed(String),
+    /// An archive was saved; it moves to the front of the recent archives.
+    ArchiveSaved(PathBuf),
+    /// Drop these paths from the recent archives, e.g. because they no longer exist.
+    ForgetRecentArchives(Vec<PathBuf>),
+    /// First-run setup closed; `None` keeps the current value. Always persisted, so the
+    /// setup is not offered again at the next start.
+    FinishOnboarding {
+        elabftw_url: Option<String>,
+        defaults: Option<EntryDefaults>,
+    },
+    /// Edited favorite folders (one per line), applied on save.
+    FavoritesInputChanged(String),
+    /// Edited unit codes (`unit = code` per line), applied on save.
+    UnitCodesInputChanged(String),
+    /// Edited labels of the fields mirrored as keywords (one per line), applied on save.
+    KeywordFieldsInputChanged(String),
+    /// Edited blocked attachment types, applied on save.
+    BlockedTypesInputChanged(String),
+    /// Edited attachment size warning in MB, applied on save.
+    SizeWarningChanged(u64),
+    /// Choose the favorite the next dialog of `kind` opens in (`None` = system default);
+    /// applied and persisted immediately.
+    SelectFavorite {
+        kind: PickerKind,
+        path: Option<PathBuf>,
+    },
+    /// Result of checking which favorite folders exist.
+    FavoritesChecked(Vec<(PathBuf, bool)>),
+    Apply,
+    Persisted(Result<(), String>),
+    /// Forget all keywords collected for autocompletion.
+    ClearKeywordVocabulary,
+}
+
+/// Side effects requested by the settings component.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SettingsCommand {
+    Persist(Box<Settings>),
+    /// Write the API key to its own file; an empty key deletes it.
+    StoreApiKey(String),
+    /// Ask for a file and write the export to it.
+    ExportSettings(Box<SettingsExport>),
+    /// Ask for an export file and read it.
+    PickSettingsImport,
+    ClearKeywordVocabulary,
+    /// Check off the UI thread which favorite folders exist (network shares may be slow).
+    CheckFavorites(Vec<PathBuf>),
+}
+
+/// Feedback surfaced to the status bar/modal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SettingsEvent {
+    pub message: String,
+    pub is_error: bool,
+}
+
+impl SettingsModel {
+    /// Currently applied settings.
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Whether the settings window is shown.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Instance and key saved archives are uploaded with, when uploading is enabled.
+    pub fn upload_target(&self) -> Option<UploadTarget> {
+        let elabftw = &self.settings.elabftw;
+        (elabftw.upload_after_save && !elabftw.url.is_empty() && !self.api_key.is_empty()).then(
+            || UploadTarget {
+                url: elabftw.url.clone(),
+                api_key: self.api_key.clone(),
+            },
+        )
+    }
+
+    /// Whether `path` existed at the last check; unchecked folders count as available.
+    pub fn favorite_available(&self, path: &Path) -> bool {
+        self.favorite_status.get(path).copied().unwrap_or(true)
+    }
+
+    /// Imported settings awaiting confirmation.
+    pub fn pending_import(&self) -> Option<&SettingsExport> {
+        self.pending_import.as_deref()
+    }
+
+    /// Fill the window's inputs from the applied settings.
+    fn load_inputs(&mut self) {
+        self.tags_input = self.settings.html_export.extra_tags.join(", ");
+        self.classes_input = self.settings.html_export.extra_classes.join(", ");
+        self.horizon_input = self.settings.validation.date_horizon_years;
+        self.zip_comment_input = self.settings.archive.write_zip_comment;
+        self.readme_input = self.settings.archive.include_readme;
+        self.license_input = self.settings.archive.license.clone();
+        self.signing_key_input = self
+            .settings
+            .archive
+            .signing_key
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        self.elabftw_input = self.settings.elabftw.url.clone();
+        self.publisher_input = self.settings.publisher.clone();
+        self.api_key_input = self.api_key.clone();
+        self.upload_input = self.settings.elabftw.upload_after_save;
+        self.favorites_input = self
+            .settings
+            .favorites
+            .locations
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.units_input = format_unit_codes(&self.settings.units.codes);
+        self.keyword_fields_input = self.settings.keywords.from_fields.join("\n");
+        self.blocked_types_input = self.settings.attachments.blocked.join(", ");
+        self.size_warning_input = self.settings.attachments.warn_above_mb;
+    }
+
+    /// Request an existence check for all favorite folders, if there are any.
+    fn check_favorites(&self, cmds: &mut Vec<SettingsCommand>) {
+        let locations = &self.settings.favorites.locations;
+        if !locations.is_empty() {
+            cmds.push(SettingsCommand::CheckFavorites(locations.clone()));
+        }
+    }
+}
+
+/// Apply a message to the settings model.
+pub fn update(
+    model: &mut SettingsModel,
+    msg: SettingsMsg,
+    cmds: &mut Vec<SettingsCommand>,
+) -> Option<SettingsEvent> {
+    match msg {
+        SettingsMsg::Open => {
+            model.open = true;
+            model.load_inputs();
+            model.errors.clear();
+            model.check_favorites(cmds);
+            None
+        }
+        SettingsMsg::Close => {
+            model.open = false;
+            model.errors.clear();
+            None
+        }
+        SettingsMsg::Loaded(settings) => {
+            model.settings = *settings;
+            model.check_favorites(cmds);
+            None
+        }
+        SettingsMsg::TagsInputChanged(text) => {
+            model.tags_input = text;
+            None
+        }
+        SettingsMsg::ClassesInputChanged(text) => {
+            model.classes_input = text;
+            None
+        }
+        SettingsMsg::HorizonChanged(years) => {
+            model.horizon_input = years;
+            None
+        }
+        SettingsMsg::ZipCommentChanged(enabled) => {
+            model.zip_comment_input = enabled;
+            None
+        }
+        SettingsMsg::ReadmeChanged(enabled) => {
+            model.readme_input = enabled;
+            None
+        }
+        SettingsMsg::LicenseChanged(text) => {
+            model.license_input = text;
+            None
+        }
+        SettingsMsg::SigningKeyChanged(text) => {
+            model.signing_key_input = text;
+            None
+        }
+        SettingsMsg::ElabftwUrlChanged(text) => {
+            model.elabftw_input = text;
+            None
+        }
+        SettingsMsg::PublisherNameChanged(text) => {
+            model.publisher_input.name = text;
+            None
+        }
+        SettingsMsg::PublisherUrlChanged(text) => {
+            model.publisher_input.url = text;
+            None
+        }
+        SettingsMsg::PublisherRorChanged(text) => {
+            model.publisher_input.ror = text;
+            None
+        }
+        SettingsMsg::ApiKeyLoaded(key) => {
+            model.api_key = key;
+            None
+        }
+        SettingsMsg::ApiKeyChanged(text) => {
+            model.api_key_input = text;
+            None
+        }
+        SettingsMsg::UploadAfterSaveChanged(enabled) => {
+            model.upload_input = enabled;
+            None
+        }
+        SettingsMsg::ApiKeyStored(result) => result.err().map(|err| SettingsEvent {
+            message: t!("settings.api_key.failed", err = err),
+            is_error: true,
+        }),
+        SettingsMsg::ExportKeyToggled(enabled) => {
+            model.export_key_input = enabled;
+            None
+        }
+        SettingsMsg::ExportRequested => {
+            let api_key = (model.export_key_input && !model.api_key.is_empty())
+                .then_some(model.api_key.as_str());
+            cmds.push(SettingsCommand::ExportSettings(Box::new(
+                SettingsExport::new(&model.settings, api_key),
+            )));
+            None
+        }
+        SettingsMsg::Exported(result) => match result {
+            Ok(Some(path)) => Some(SettingsEvent {
+                message: t!("settings.exported", path = path.display()),
+                is_error: false,
+            }),
+            Ok(None) => None,
+            Err(err) => Some(SettingsEvent {
+                message: t!("settings.export.failed", err = err),
+                is_error: true,
+            }),
+        },
+        SettingsMsg::ImportRequested => {
+            cmds.push(SettingsCommand::PickSettingsImport);
+            None
+        }
+        SettingsMsg::ImportLoaded(result) => match result {
+            Ok(export) => {
+                model.pending_import = export;
+                None
+            }
+            Err(err) => Some(SettingsEvent {
+                message: t!("settings.import.failed", err = err),
+                is_error: true,
+            }),
+        },
+        SettingsMsg::ConfirmImport => {
+            let export = model.pending_import.take()?;
+            model.settings = export.apply_to(&model.settings);
+            cmds.push(SettingsCommand::Persist(Box::new(model.settings.clone())));
+            if let Some(key) = export.elabftw_api_key.as_deref().map(str::trim)
+                && key != model.api_key
+            {
+                model.api_key = key.to_string();
+                cmds.push(SettingsCommand::StoreApiKey(model.api_key.clone()));
+            }
+            if model.open {
+                model.load_inputs();
+                model.errors.clear();
+            }
+            model.check_favorites(cmds);
+            Some(SettingsEvent {
+                message: t!("settings.imported").into(),
+                is_error: false,
+            })
+        }
+        SettingsMsg::CancelImport => {
+            model.pending_import = None;
+            None
+        }
+        SettingsMsg::SetIncludePreview(enabled) => {
+            if model.settings.archive.include_preview != enabled {
+                model.settings.archive.include_preview = enabled;
+                cmds.push(SettingsCommand::Persist(Box::new(model.settings.clone())));
+            }
+            None
+        }
+        SettingsMsg::SetCompactMode(enabled) => {
+            if model.settings.appearance.compact_mode != enabled {
+                model.settings.appearance.compact_mode = enabled;
+                cmds.push(SettingsCommand::Persist(Box::new(model.settings.clone())));
+            }
+            None
+        }
+        SettingsMsg::SetCrateVersion(version) => {
+            if model.settings.archive.crate_version != version {
+                model.settings.archive.crate_version = version;
+                cmds.push(SettingsCommand::Persist(Box::new(model.settings.clone())));
+            }
+            None
+        }
+        SettingsMsg::SetHashing(hashing) => {
+            persist_if_changed(model, cmds, |s| &mut s.hashing, hashing);
+            None
+        }
+        SettingsMsg::SetChecksumsManifest(enabled) => {
+            persist_if_changed(model, cmds, |s| &mut s.archive.checksums_manifest, enabled);
+            None
+        }
+        SettingsMsg::SetVerifyAfterSave(enabled) => {
+            persist_if_changed(model, cmds, |s| &mut s.archive.verify_after_save, enabled);
+            None
+        }
+        SettingsMsg::SetCompression(compression) => {
+            persist_if_changed(model, cmds, |s| &mut s.archive.compression, compression);
+            None
+        }
+        SettingsMsg::SetShowFontCoverage(enabled) => {
+            persist_if_changed(
+                model,
+                cmds,
+                |s| &mut s.appearance.show_font_coverage,
+                enabled,
+            );
+            None
+        }
+        SettingsMsg::SetIncludeImportedMetadata(enabled) => {
+            persist_if_changed(
+                model,
+                cmds,
+                |s| &mut s.archive.include_imported_metadata,
+                enabled,
+            );
+            None
+        }
+        SettingsMsg::SetLanguage(language) => {
+            persist_if_changed(model, cmds, |s| &mut s.appearance.language, language);
+            None
+        }
+        SettingsMsg::SetTheme(theme) => {
+            persist_if_changed(model, cmds, |s| &mut s.appearance.theme, theme);
+            None
+        }
+        SettingsMsg::SetWindow(window) => {
+            persist_if_changed(model, cmds, |s| &mut s.window, window);
+            None
+        }
+        SettingsMsg::SetDefaultBodyFormat(format) => {
+            persist_if_changed(model, cmds, |s| &mut s.defaults.body_format, format);
+            None
+        }
+        SettingsMsg::SetDefaultGenre(genre) => {
+            persist_if_changed(model, cmds, |s| &mut s.defaults.genre, genre);
+            None
+        }
+        SettingsMsg::SetDefaultLicense(license) => {
+            persist_if_changed(model, cmds, |s| &mut s.defaults.license, license);
+            None
+        }
+        SettingsMsg::RememberDir { kind, dir } => {
+            let last_dirs = &mut model.settings.favorites.last_dirs;
+            if last_dirs.get(&kind) != Some(&dir) {
+                last_dirs.insert(kind, dir);
+                cmds.push(SettingsCommand::Persist(Box::new(model.settings.clone())));
+            }
+            None
+        }
+        SettingsMsg::TemplateUsed(name) => {
+            if model.settings.templates.record_use(&name) {
+                cmds.push(SettingsCommand::Persist(Box::new(model.settings.clone())));
+            }
+            None
+        }
+        SettingsMsg::ArchiveSaved(path) => {
+            if model.settings.recent_archives.record(&path) {
+                cmds.push(SettingsCommand::Persist(Box::new(model.settings.clone())));
+            }
+            None
+        }
+        SettingsMsg::ForgetRecentArchives(paths) => {
+            if model.settings.recent_archives.forget(&paths) {
+                cmds.push(SettingsCommand::Persist(Box::new(model.settings.clone())));
+            }
+            None
+        }
+        SettingsMsg::FinishOnboarding {
+            elabftw_url,
+            defaults,
+        } => {
+            if let Some(url) = elabftw_url {
+                model.settings.elabftw.url = url;
+            }
+            if let Some(defaults) = defaults {
+                model.settings.defaults = defaults;
+            }
+            cmds.push(SettingsCommand::Persist(Box::new(model.settings.clone())));
+            None
+        }
+        SettingsMsg::FavoritesInputChanged(text) => {
+            model.favorites_input = text;
+            None
+        }
+        SettingsMsg::UnitCodesInputChanged(text) => {
+            model.units_input = text;
+            None
+        }
+        SettingsMsg::KeywordFieldsInputChanged(text) => {
+            model.keyword_fields_input = text;
+            None
+        }
+        SettingsMsg::BlockedTypesInputChanged(text) => {
+            model.blocked_types_input = text;
+            None
+        }
+        SettingsMsg::SizeWarningChanged(megabytes) => {
+            model.size_warning_input = megabytes;
+            None
+        }
+        SettingsMsg::SelectFavorite { kind, path } => {
+            let favorites = &mut model.settings.favorites;
+            let changed = match path {
+                Some(path) => favorites.last_used.insert(kind, path.clone()) != Some(path),
+                None => favorites.last_used.remove(&kind).is_some(),
+            };
+            if changed {
+                cmds.push(SettingsCommand::Persist(Box::new(model.settings.clone())));
+            }
+            None
+        }
+        SettingsMsg::FavoritesChecked(status) => {
+            model.favorite_status = status.into_iter().collect();
+            None
+        }
+        SettingsMsg::Apply => {
+            let html_export = HtmlExportSettings {
+                extra_tags: split_list(&model.tags_input),
+                extra_classes: split_list(&model.classes_input),
+            };
+            let mut errors = HtmlPolicy::from_settings(&html_export)
+                .err()
+                .unwrap_or_default();
+            let elabftw_url = normalize_instance_url(&model.elabftw_input).unwrap_or_else(|err| {
+                errors.push(err.to_string());
+                String::new()
+            });
+            let publisher = PublisherInfo {
+                name: model.publisher_input.name.trim().to_string(),
+                url: normalize_web_address(
+                    &model.publisher_input.url,
+                    t!("settings.publisher.address"),
+                )
+                .unwrap_or_else(|err| {
+                    errors.push(err.to_string());
+                    String::new()
+                }),
+                ror: normalize_ror(&model.publisher_input.ror).unwrap_or_else(|err| {
+                    errors.push(err.to_string());
+                    String::new()
+                }),
+            };
+            let unit_codes = parse_unit_codes(&model.units_input).unwrap_or_else(|err| {
+                errors.push(err.to_string());
+                Default::default()
+            });
+            let blocked_types =
+                parse_blocked_types(&model.blocked_types_input).unwrap_or_else(|err| {
+                    errors.push(err.to_string());
+                    Vec::new()
+                });
+            let api_key = model.api_key_input.trim().to_string();
+            if model.upload_input && (model.elabftw_input.trim().is_empty() || api_key.is_empty()) {
+                errors.push(t!("settings.upload.incomplete").into());
+            }
+            if !errors.is_empty() {
+                model.errors = errors;
+                return None;
+            }
+            model.errors.clear();
+            model.settings.html_export = html_export;
+            model.settings.elabftw.url = elabftw_url;
+            model.settings.elabftw.upload_after_save = model.upload_input;
+            model.settings.publisher = publisher;
+            model.settings.units.codes = unit_codes;
+            let mut from_fields: Vec<String> = Vec::new();
+            for label in model.keyword_fields_input.lines().map(str::trim) {
+                if !label.is_empty() && !from_fields.iter().any(|l| same_keyword(l, label)) {
+                    from_fields.push(label.to_string());
+                }
+            }
+            model.settings.keywords.from_fields = from_fields;
+            model.settings.attachments = AttachmentPolicy {
+                blocked: blocked_types,
+                warn_above_mb: model.size_warning_input,
+            };
+            if api_key != model.api_key {
+                model.api_key = api_key.clone();
+                cmds.push(SettingsCommand::StoreApiKey(api_key));
+            }
+            model.settings.validation.date_horizon_years = model.horizon_input;
+            model.settings.archive.write_zip_comment = model.zip_comment_input;
+            model.settings.archive.include_readme = model.readme_input;
+            model.settings.archive.license = model.license_input.trim().to_string();
+            let signing_key = model.signing_key_input.trim();
+            model.settings.archive.signing_key =
+                (!signing_key.is_empty()).then(|| PathBuf::from(signing_key));
+            let mut locations: Vec<PathBuf> = Vec::new();
+            for line in model.favorites_input.lines().map(str::trim) {
+                let path = PathBuf::from(line);
+                if !line.is_empty() && !locations.contains(&path) {
+                    locations.push(path);
+                }
+            }
+            let favorites = &mut model.settings.favorites;
+            favorites
+                .last_used
+                .retain(|_, path| locations.contains(path));
+            favorites.locations = locations;
+            model.open = false;
+            model.announce_save = true;
+            cmds.push(SettingsCommand::Persist(Box::new(model.settings.clone())));
+            model.check_favorites(cmds);
+            None
+        }
+        SettingsMsg::ClearKeywordVocabulary => {
+            cmds.push(SettingsCommand::ClearKeywordVocabulary);
+            Some(SettingsEvent {
+                message: t!("settings.keywords.cleared").into(),
+                is_error: false,
+            })
+        }
+        SettingsMsg::Persisted(result) => {
+            let announce = std::mem::take(&mut model.announce_save);
+            match result {
+                Ok(()) => announce.then(|| SettingsEvent {
+                    message: t!("settings.saved").into(),
+                    is_error: false,
+                }),
+                Err(err) => Some(SettingsEvent {
+                    message: t!("settings.save.failed", err = err),
+                    is_error: true,
+                }),
+            }
+        }
+    }
+}
+
+/// Store `value` in the field selected by `field` and persist when it changed.
+fn persist_if_changed<T: PartialEq>(
+    model: &mut SettingsModel,
+    cmds: &mut Vec<SettingsCommand>,
+    field: impl FnOnce(&mut Settings) -> &mut T,
+    value: T,
+) {
+    let slot = field(&mut model.settings);
+    if *slot != value {
+        *slot = value;
+        cmds.push(SettingsCommand::Persist(Box::new(model.settings.clone())));
+    }
+}
+
+/// Ask before imported settings overwrite the current ones, listing what changes.
+fn render_import_confirmation(
+    ctx: &egui::Context,
+    model: &SettingsModel,
+    msgs: &mut Vec<SettingsMsg>,
+) {
+    let Some(export) = model.pending_import() else {
+        return;
+    };
+    egui::Window::new(t!("settings.import.title"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .order(egui::Order::Foreground)
+        .show(ctx, |ui| {
+            let changes = export.changes(model.settings());
+            if changes.is_empty() {
+                ui.label(t!("settings.import.same"));
+            } else {
+                ui.label(t!("settings.import.changes"));
+                for section in &changes {
+                    ui.label(format!("• {section}"));
+                }
+            }
+            if export.elabftw_api_key.is_some() {
+                ui.label(t!("settings.import.key"));
+            }
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button(t!("settings.import.confirm")).clicked() {
+                    msgs.push(SettingsMsg::ConfirmImport);
+                }
+                if ui.button(t!("common.cancel")).clicked() {
+                    msgs.push(SettingsMsg::CancelImport);
+                }
+            });
+        });
+}
+
+/// Render the compression method and deflate level; changes apply immediately.
+fn compression_controls(ui: &mut egui::Ui, settings: &Settings) -> Vec<SettingsMsg> {
+    let mut msgs = Vec::new();
+    let current = settings.archive.compression;
+    let mut next = current;
+    ui.horizontal(|ui| {
+        ui.label(t!("settings.compression.method"));
+        egui::ComboBox::from_id_salt("compression_method")
+            .selected_text(current.method.label())
+            .show_ui(ui, |ui| {
+                for method in Compression::ALL {
+                    ui.selectable_value(&mut next.method, method, method.label());
+                }
+            })
+            .response
+            .on_hover_text(t!("settings.compression.method.hover"));
+    });
+    ui.horizontal(|ui| {
+        ui.label(t!("settings.compression.level"));
+        ui.add_enabled(
+            current.method != Compression::Stored,
+            egui::Slider::new(&mut next.level, DEFLATE_LEVELS),
+        )
+        .on_hover_text(t!("settings.compression.level.hover"));
+    });
+    if next != current {
+        msgs.push(SettingsMsg::SetCompression(next));
+    }
+    msgs
+}
+
+/// Render the digest choices and the checksum manifest toggle; changes apply immediately.
+fn hashing_controls(ui: &mut egui::Ui, settings: &Settings) -> Vec<SettingsMsg> {
+    let mut msgs = Vec::new();
+    let hashing = &settings.hashing;
+    ui.horizontal(|ui| {
+        ui.label(t!("settings.hashing.compute"));
+        for algorithm in HashAlgorithm::ALL {
+            let required = algorithm == HashAlgorithm::Sha256 || algorithm == hashing.primary;
+            let mut enabled = required || hashing.extra.contains(&algorithm);
+            let response = ui
+                .add_enabled(
+                    !required,
+                    egui::Checkbox::new(&mut enabled, algorithm.label()),
+                )
+                .on_disabled_hover_text(if algorithm == HashAlgorithm::Sha256 {
+                    t!("settings.hashing.sha256")
+                } else {
+                    t!("settings.hashing.primary_required")
+                });
+            if response.changed() {
+                let mut next = hashing.clone();
+                if enabled {
+                    next.extra.insert(algorithm);
+                } else {
+                    next.extra.remove(&algorithm);
+                }
+                msgs.push(SettingsMsg::SetHashing(next));
+            }
+        }
+    })
+    .response
+    .on_hover_text(t!("settings.hashing.hover"));
+
+    ui.horizontal(|ui| {
+        ui.label(t!("settings.hashing.primary"));
+        let mut primary = hashing.primary;
+        egui::ComboBox::from_id_salt("primary_digest")
+            .selected_text(primary.label())
+            .show_ui(ui, |ui| {
+                for algorithm in HashAlgorithm::ALL {
+                    ui.selectable_value(&mut primary, algorithm, algorithm.label());
+                }
+            });
+        if primary != hashing.primary {
+            msgs.push(SettingsMsg::SetHashing(HashingSettings {
+                primary,
+                ..hashing.clone()
+            }));
+        }
+    });
+
+    let mut manifest = settings.archive.checksums_manifest;
+    if ui
+        .checkbox(&mut manifest, t!("settings.hashing.manifest"))
+        .on_hover_text(t!("settings.hashing.manifest.hover"))
+        .changed()
+    {
+        msgs.push(SettingsMsg::SetChecksumsManifest(manifest));
+    }
+
+    let mut verify = settings.archive.verify_after_save;
+    if ui
+        .checkbox(&mut verify, t!("settings.verify"))
+        .on_hover_text(t!("settings.verify.hover"))
+        .changed()
+    {
+        msgs.push(SettingsMsg::SetVerifyAfterSave(verify));
+    }
+
+    let mut imported = settings.archive.include_imported_metadata;
+    if ui
+        .checkbox(&mut imported, t!("settings.imported_metadata"))
+        .on_hover_text(t!("settings.imported_metadata.hover"))
+        .changed()
+    {
+        msgs.push(SettingsMsg::SetIncludeImportedMetadata(imported));
+    }
+    msgs
+}
+
+/// Render the settings window when open and return triggered messages.
+pub fn view(ctx: &egui::Context, model: &SettingsModel) -> Vec<SettingsMsg> {
+    let mut msgs = Vec::new();
+    render_import_confirmation(ctx, model, &mut msgs);
+    if !model.is_open() {
+        return msgs;
+    }
+
+    egui::Window::new(t!("settings.title"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.heading(t!("settings.advanced"));
+            ui.label(
+                egui::RichText::new(t!("settings.advanced.text"))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
+            );
+            ui.add_space(6.0);
+
+            egui::Grid::new("settings_export_grid")
+                .num_columns(2)
+                .spacing(egui::vec2(8.0, 8.0))
+                .show(ui, |ui| {
+                    ui.label(t!("settings.advanced.tags"));
+                    let mut tags = model.tags_input.clone();
+                    if ui
+                        .add(
+                            egui::TextEdit::singleline(&mut tags)
+                                .hint_text(t!("settings.advanced.tags.hint"))
+                                .desired_width(260.0),
+                        )
+                        .changed()
+                    {
+                        msgs.push(SettingsMsg::TagsInputChanged(tags));
+                    }
+                    ui.end_row();
+
+                    ui.label(t!("settings.advanced.classes"));
+                    let mut classes = model.classes_input.clone();
+                    if ui
+                        .add(
+                            egui::TextEdit::singleline(&mut classes)
+                                .hint_text(t!("settings.advanced.classes.hint"))
+                                .desired_width(260.0),
+                        )
+                        .changed()
+                    {
+                        msgs.push(SettingsMsg::ClassesInputChanged(classes));
+                    }
+                    ui.end_row();
+                });
+
+            ui.label(
+                egui::RichText::new(t!("settings.advanced.tip"))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
+            );
+
+            ui.add_space(8.0);
+            ui.heading(t!("settings.archive"));
+            let mut zip_comment = model.zip_comment_input;
+            if ui
+                .checkbox(&mut zip_comment, t!("settings.zip_comment"))
+                .on_hover_text(t!("settings.zip_comment.hover"))
+                .changed()
+            {
+                msgs.push(SettingsMsg::ZipCommentChanged(zip_comment));
+            }
+
+            let mut readme = model.readme_input;
+            if ui
+                .checkbox(&mut readme, t!("settings.readme"))
+                .on_hover_text(t!("settings.readme.hover"))
+                .changed()
+            {
+                msgs.push(SettingsMsg::ReadmeChanged(readme));
+            }
+            ui.horizontal(|ui| {
+                ui.label(t!("settings.readme.license"));
+                let mut license = model.license_input.clone();
+                if ui
+                    .add_enabled(
+                        model.readme_input,
+                        egui::TextEdit::singleline(&mut license)
+                            .hint_text(t!("settings.readme.license.hint"))
+                            .desired_width(200.0),
+                    )
+                    .on_hover_text(t!("settings.readme.license.hover"))
+                    .changed()
+                {
+                    msgs.push(SettingsMsg::LicenseChanged(license));
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("settings.signing_key"));
+                let mut key = model.signing_key_input.clone();
+                if ui
+                    .add(
+                        egui::TextEdit::singleline(&mut key)
+                            .hint_text(t!("settings.signing_key.hint"))
+                            .desired_width(260.0),
+                    )
+                    .on_hover_text(t!("settings.signing_key.hover"))
+                    .changed()
+                {
+                    msgs.push(SettingsMsg::SigningKeyChanged(key));
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(t!("settings.elabftw.url"));
+                let mut url = model.elabftw_input.clone();
+                if ui
+                    .add(
+                        egui::TextEdit::singleline(&mut url)
+                            .hint_text(t!("settings.elabftw.url.hint"))
+                            .desired_width(260.0),
+                    )
+                    .on_hover_text(t!("settings.elabftw.url.hover"))
+                    .changed()
+                {
+                    msgs.push(SettingsMsg::ElabftwUrlChanged(url));
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("settings.elabftw.key"));
+                let mut key = model.api_key_input.clone();
+                if ui
+                    .add(
+                        egui::TextEdit::singleline(&mut key)
+                            .password(true)
+                            .desired_width(260.0),
+                    )
+                    .on_hover_text(t!("settings.elabftw.key.hover"))
+                    .changed()
+                {
+                    msgs.push(SettingsMsg::ApiKeyChanged(key));
+                }
+            });
+            let mut upload = model.upload_input;
+            if ui
+                .checkbox(&mut upload, t!("settings.elabftw.upload"))
+                .on_hover_text(t!("settings.elabftw.upload.hover"))
+                .changed()
+            {
+                msgs.push(SettingsMsg::UploadAfterSaveChanged(upload));
+            }
+
+            ui.add_space(8.0);
+            ui.heading(t!("settings.publisher"));
+            ui.label(
+                egui::RichText::new(t!("settings.publisher.text"))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
+            );
+            egui::Grid::new("settings_publisher_grid")
+                .num_columns(2)
+                .spacing(egui::vec2(8.0, 8.0))
+                .show(ui, |ui| {
+                    ui.label(t!("settings.publisher.name"));
+                    let mut name = model.publisher_input.name.clone();
+                    if ui
+                        .add(
+                            egui::TextEdit::singleline(&mut name)
+                                .hint_text(t!("settings.publisher.name.hint"))
+                                .desired_width(260.0),
+                        )
+                        .changed()
+                    {
+                        msgs.push(SettingsMsg::PublisherNameChanged(name));
+                    }
+                    ui.end_row();
+
+                    ui.label(t!("settings.publisher.url"));
+                    let mut url = model.publisher_input.url.clone();
+                    if ui
+                        .add(
+                            egui::TextEdit::singleline(&mut url)
+                                .hint_text(t!("settings.publisher.url.hint"))
+                                .desired_width(260.0),
+                        )
+                        .on_hover_text(t!("settings.publisher.url.hover"))
+                        .changed()
+                    {
+                        msgs.push(SettingsMsg::PublisherUrlChanged(url));
+                    }
+                    ui.end_row();
+
+                    ui.label(t!("settings.publisher.ror"));
+                    let mut ror = model.publisher_input.ror.clone();
+                    if ui
+                        .add(
+                            egui::TextEdit::singleline(&mut ror)
+                                .hint_text(t!("settings.publisher.ror.hint"))
+                                .desired_width(260.0),
+                        )
+                        .on_hover_text(t!("settings.publisher.ror.hover"))
+                        .changed()
+                    {
+                        msgs.push(SettingsMsg::PublisherRorChanged(ror));
+                    }
+                    ui.end_row();
+                });
+
+            ui.add_space(8.0);
+            ui.heading(t!("settings.compression"));
+            msgs.extend(compression_controls(ui, model.settings()));
+
+            ui.add_space(8.0);
+            ui.heading(t!("settings.checksums"));
+            msgs.extend(hashing_controls(ui, model.settings()));
+
+            ui.add_space(8.0);
+            ui.heading(t!("settings.validation"));
+            ui.horizontal(|ui| {
+                ui.label(t!("settings.horizon"));
+                let mut years = model.horizon_input;
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut years)
+                            .range(0..=200)
+                            .suffix(format!(" {}", t!("settings.horizon.years"))),
+                    )
+                    .on_hover_text(t!("settings.horizon.hover"))
+                    .changed()
+                {
+                    msgs.push(SettingsMsg::HorizonChanged(years));
+                }
+            });
+
+            ui.add_space(8.0);
+            ui.heading(t!("settings.units"));
+            ui.label(
+                egui::RichText::new(t!("settings.units.text"))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
+            );
+            let mut units = model.units_input.clone();
+            if ui
+                .add(
+                    egui::TextEdit::multiline(&mut units)
+                        .hint_text(t!("settings.units.hint"))
+                        .desired_rows(2)
+                        .desired_width(360.0),
+                )
+                .changed()
+            {
+                msgs.push(SettingsMsg::UnitCodesInputChanged(units));
+            }
+
+            ui.add_space(8.0);
+            ui.heading(t!("settings.attachment_policy"));
+            ui.label(
+                egui::RichText::new(t!("settings.attachment_policy.text"))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
+            );
+            let mut blocked = model.blocked_types_input.clone();
+            if ui
+                .add(
+                    egui::TextEdit::singleline(&mut blocked)
+                        .hint_text(t!("settings.attachment_policy.hint"))
+                        .desired_width(360.0),
+                )
+                .changed()
+            {
+                msgs.push(SettingsMsg::BlockedTypesInputChanged(blocked));
+            }
+            ui.horizontal(|ui| {
+                ui.label(t!("settings.attachment_policy.size"));
+                let mut megabytes = model.size_warning_input;
+                if ui
+                    .add(egui::DragValue::new(&mut megabytes).suffix(" MB"))
+                    .on_hover_text(t!("settings.attachment_policy.size.hover"))
+                    .changed()
+                {
+                    msgs.push(SettingsMsg::SizeWarningChanged(megabytes));
+                }
+            });
+
+            ui.add_space(8.0);
+            ui.heading(t!("settings.keyword_fields"));
+            ui.label(
+                egui::RichText::new(t!("settings.keyword_fields.text"))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
+            );
+            let mut keyword_fields = model.keyword_fields_input.clone();
+            if ui
+                .add(
+                    egui::TextEdit::multiline(&mut keyword_fields)
+                        .hint_text(t!("settings.keyword_fields.hint"))
+                        .desired_rows(2)
+                        .desired_width(360.0),
+                )
+                .changed()
+            {
+                msgs.push(SettingsMsg::KeywordFieldsInputChanged(keyword_fields));
+            }
+
+            ui.add_space(8.0);
+            ui.heading(t!("settings.favorites"));
+            ui.label(
+                egui::RichText::new(t!("settings.favorites.text"))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
+            );
+            let mut favorites = model.favorites_input.clone();
+            if ui
+                .add(
+                    egui::TextEdit::multiline(&mut favorites)
+                        .hint_text(t!("settings.favorites.hint"))
+                        .desired_rows(3)
+                        .desired_width(360.0),
+                )
+                .changed()
+            {
+                msgs.push(SettingsMsg::FavoritesInputChanged(favorites));
+            }
+            for path in &model.settings.favorites.locations {
+                if !model.favorite_available(path) {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(200, 140, 40),
+                        format!(
+                            "{} {}",
+                            egui_phosphor::regular::WARNING,
+                            t!("settings.favorites.missing", path = path.display())
+                        ),
+                    );
+                }
+            }
+
+            ui.add_space(8.0);
+            ui.heading(t!("settings.share"));
+            ui.label(
+                egui::RichText::new(t!("settings.share.text"))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
+            );
+            let mut include_key = model.export_key_input;
+            if ui
+                .checkbox(&mut include_key, t!("settings.share.key"))
+                .changed()
+            {
+                msgs.push(SettingsMsg::ExportKeyToggled(include_key));
+            }
+            if model.export_key_input {
+                ui.colored_label(
+                    egui::Color32::from_rgb(200, 140, 40),
+                    format!(
+                        "{} {}",
+                        egui_phosphor::regular::WARNING,
+                        t!("settings.share.key_warning")
+                    ),
+                );
+            }
+            ui.horizontal(|ui| {
+                if ui
+                    .button(format!(
+                        "{} {}",
+                        egui_phosphor::regular::EXPORT,
+                        t!("settings.share.export")
+                    ))
+                    .on_hover_text(t!("settings.share.export.hover"))
+                    .clicked()
+                {
+                    msgs.push(SettingsMsg::ExportRequested);
+                }
+                if ui
+                    .button(format!(
+                        "{} {}",
+                        egui_phosphor::regular::DOWNLOAD_SIMPLE,
+                        t!("settings.share.import")
+                    ))
+                    .on_hover_text(t!("settings.share.import.hover"))
+                    .clicked()
+                {
+                    msgs.push(SettingsMsg::ImportRequested);
+                }
+            });
+
+            ui.add_space(8.0);
+            ui.heading(t!("settings.keywords"));
+            if ui
+                .button(format!(
+                    "{} {}",
+                    egui_phosphor::regular::BROOM,
+                    t!("settings.keywords.clear")
+                ))
+                .on_hover_text(t!("settings.keywords.clear.hover"))
+                .clicked()
+            {
+                msgs.push(SettingsMsg::ClearKeywordVocabulary);
+            }
+
+            ui.add_space(8.0);
+            ui.heading(t!("settings.diagnostics"));
+            let mut font_coverage = model.settings.appearance.show_font_coverage;
+            if ui
+                .checkbox(&mut font_coverage, t!("settings.font_coverage"))
+                .on_hover_text(t!("settings.font_coverage.hover"))
+                .changed()
+            {
+                msgs.push(SettingsMsg::SetShowFontCoverage(font_coverage));
+            }
+
+            for err in &model.errors {
+                ui.colored_label(
+                    egui::Color32::from_rgb(200, 80, 80),
+                    format!("{} {err}", egui_phosphor::regular::WARNING),
+                );
+            }
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button(t!("common.save")).clicked() {
+                    msgs.push(SettingsMsg::Apply);
+                }
+                if ui.button(t!("common.cancel")).clicked() {
+                    msgs.push(SettingsMsg::Close);
+                }
+            });
+        });
+
+    msgs
+}
+
+/// Render the star menu choosing which favorite the next dialog of `kind` starts in.
+///
+/// Favorites that were missing at the last check are shown disabled with a warning.
+pub fn favorite_menu(
+    ui: &mut egui::Ui,
+    model: &SettingsModel,
+    kind: PickerKind,
+) -> Vec<SettingsMsg> {
+    let mut msgs = Vec::new();
+    let favorites = &model.settings.favorites;
+    let selected = favorites.selected(kind);
+    let hover = match selected {
+        Some(path) => t!("settings.favorite.current", path = path.display()),
+        None => t!("settings.favorite.hover").to_string(),
+    };
+
+    ui.menu_button(egui_phosphor::regular::STAR, |ui| {
+        if ui
+            .selectable_label(selected.is_none(), t!("settings.favorite.default"))
+            .clicked()
+        {
+            msgs.push(SettingsMsg::SelectFavorite { kind, path: None });
+            ui.close();
+        }
+        if favorites.locations.is_empty() {
+            ui.label(
+                egui::RichText::new(t!("settings.favorite.none"))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
+            );
+        }
+        for path in &favorites.locations {
+            let available = model.favorite_available(path);
+            let label = if available {
+                path.display().to_string()
+            } else {
+                format!("{} {}", egui_phosphor::regular::WARNING, path.display())
+            };
+            let response = ui
+                .add_enabled(
+                    available,
+                    egui::Button::selectable(selected == Some(path.as_path()), label),
+                )
+                .on_disabled_hover_text(t!("settings.favorite.missing"));
+            if response.clicked() {
+                msgs.push(SettingsMsg::SelectFavorite {
+                    kind,
+                    path: Some(path.clone()),
+                });
+                ui.close();
+            }
+        }
+    })
+    .response
+    .on_hover_text(hover);
+
+    msgs
+}
+
+/// Split a comma-separated list into trimmed, non-empty entries.
+fn split_list(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_rejects_unsafe_tags_and_keeps_window_open() {
+        let mut model = SettingsModel::default();
+        let mut cmds = Vec::new();
+        update(&mut model, SettingsMsg::Open, &mut cmds);
+        update(
+            &mut model,
+            SettingsMsg::TagsInputChanged("mark, script".into()),
+            &mut cmds,
+        );
+
+        update(&mut model, SettingsMsg::Apply, &mut cmds);
+
+        assert!(model.is_open());
+        assert_eq!(model.errors.len(), 1);
+        assert!(cmds.is_empty());
+        assert!(model.settings().html_export.extra_tags.is_empty());
+    }
+
+    #[test]
+    fn apply_valid_settings_persists_them() {
+        let mut model = SettingsModel::default();
+        let mut cmds = Vec::new();
+        update(&mut model, SettingsMsg::Open, &mut cmds);
+        update(
+            &mut model,
+            SettingsMsg::ClassesInputChanged(" boxed , highlight ,".into()),
+            &mut cmds,
+        );
+        update(&mut model, SettingsMsg::HorizonChanged(25), &mut cmds);
+        update(&mut model, SettingsMsg::ZipCommentChanged(true), &mut cmds);
+        update(&mut model, SettingsMsg::ReadmeChanged(true), &mut cmds);
+        update(
+            &mut model,
+            SettingsMsg::LicenseChanged(" CC-BY-4.0 ".into()),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            SettingsMsg::UnitCodesInputChanged("OD600 = {OD600}\n".into()),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            SettingsMsg::KeywordFieldsInputChanged(" Organism \n\norganism\nInstrument".into()),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            SettingsMsg::BlockedTypesInputChanged(".exe, video/*".into()),
+            &mut cmds,
+        );
+        update(&mut model, SettingsMsg::SizeWarningChanged(1024), &mut cmds);
+
+        update(&mut model, SettingsMsg::Apply, &mut cmds);
+
+        assert!(!model.is_open());
+        assert_eq!(
+            model.settings().html_export.extra_classes,
+            vec!["boxed", "highlight"]
+        );
+        assert_eq!(model.settings().validation.date_horizon_years, 25);
+        assert!(model.settings().archive.write_zip_comment);
+        assert!(model.settings().archive.include_readme);
+        assert_eq!(model.settings().archive.license, "CC-BY-4.0");
+        assert_eq!(model.settings().units.ucum_code("od600"), Some("{OD600}"));
+        assert_eq!(
+            model.settings().keywords.from_fields,
+            ["Organism", "Instrument"]
+        );
+        assert_eq!(model.settings().attachments.blocked, ["exe", "video/*"]);
+        assert!(model.settings().attachments.is_oversized(2 << 30));
+        assert!(matches!(cmds.as_slice(), [SettingsCommand::Persist(_)]));
+    }
+
+    #[test]
+    fn favorites_are_applied_and_stale_selections_dropped() {
+        let mut model = SettingsModel::default();
+        let mut cmds = Vec::new();
+        update(&mut model, SettingsMsg::Open, &mut cmds);
+        update(
+            &mut model,
+            SettingsMsg::FavoritesInputChanged(
+                "/data/beamline\n\n /srv/share \n/data/beamline".into(),
+            ),
+            &mut cmds,
+        );
+        update(&mut model, SettingsMsg::Apply, &mut cmds);
+
+        let favorites = &model.settings().favorites;
+        assert_eq!(
+            favorites.locations,
+            vec![PathBuf::from("/data/beamline"), PathBuf::from("/srv/share")]
+        );
+        assert!(matches!(
+            cmds.as_slice(),
+            [SettingsCommand::Persist(_), SettingsCommand::CheckFavorites(paths)] if paths.len() == 2
+        ));
+
+        cmds.clear();
+        update(
+            &mut model,
+            SettingsMsg::SelectFavorite {
+                kind: PickerKind::Archive,
+                path: Some(PathBuf::from("/srv/share")),
+            },
+            &mut cmds,
+        );
+        assert!(matches!(cmds.as_slice(), [SettingsCommand::Persist(_)]));
+        update(
+            &mut model,
+            SettingsMsg::FavoritesChecked(vec![(PathBuf::from("/srv/share"), false)]),
+            &mut cmds,
+        );
+        assert!(!model.favorite_available(Path::new("/srv/share")));
+        assert!(model.favorite_available(Path::new("/data/beamline")));
+
+        update(&mut model, SettingsMsg::Open, &mut cmds);
+        update(
+            &mut model,
+            SettingsMsg::FavoritesInputChanged("/data/beamline".into()),
+            &mut cmds,
+        );
+        update(&mut model, SettingsMsg::Apply, &mut cmds);
+        assert_eq!(
+            model.settings().favorites.selected(PickerKind::Archive),
+            None
+        );
+        assert!(model.settings().favorites.last_used.is_empty());
+    }
+
+    #[test]
+    fn elabftw_address_is_validated_and_normalized_on_apply() {
+        let mut model = SettingsModel::default();
+        let mut cmds = Vec::new();
+        update(&mut model, SettingsMsg::Open, &mut cmds);
+        update(
+            &mut model,
+            SettingsMsg::ElabftwUrlChanged("ftp://elab.example.org".into()),
+            &mut cmds,
+        );
+        update(&mut model, SettingsMsg::Apply, &mut cmds);
+        assert!(model.is_open());
+        assert_eq!(model.errors.len(), 1);
+
+        update(
+            &mut model,
+            SettingsMsg::ElabftwUrlChanged("elab.example.org/".into()),
+            &mut cmds,
+        );
+        update(&mut model, SettingsMsg::Apply, &mut cmds);
+        assert!(!model.is_open());
+        assert_eq!(model.settings().elabftw.url, "https://elab.example.org");
+    }
+
+    #[test]
+    fn publisher_is_validated_and_normalized_on_apply() {
+        let mut model = SettingsModel::default();
+        let mut cmds = Vec::new();
+        update(&mut model, SettingsMsg::Open, &mut cmds);
+        assert_eq!(model.publisher_input, PublisherInfo::default());
+        update(
+            &mut model,
+            SettingsMsg::PublisherNameChanged(" ETH Zurich ".into()),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            SettingsMsg::PublisherRorChanged("05a28rw59".into()),
+            &mut cmds,
+        );
+        update(&mut model, SettingsMsg::Apply, &mut cmds);
+        assert!(model.is_open());
+        assert_eq!(model.errors.len(), 1, "{:?}", model.errors);
+
+        update(
+            &mut model,
+            SettingsMsg::PublisherRorChanged("ror.org/05a28rw58".into()),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            SettingsMsg::PublisherUrlChanged("  ".into()),
+            &mut cmds,
+        );
+        update(&mut model, SettingsMsg::Apply, &mut cmds);
+        assert!(!model.is_open());
+        assert_eq!(
+            model.settings().publisher,
+            PublisherInfo {
+                name: "ETH Zurich".into(),
+                url: String::new(),
+                ror: "https://ror.org/05a28rw58".into(),
+            }
+        );
+        assert!(matches!(cmds.last(), Some(SettingsCommand::Persist(_))));
+    }
+
+    #[test]
+    fn upload_needs_address_and_key_which_is_stored_separately() {
+        let mut model = SettingsModel::default();
+        let mut cmds = Vec::new();
+        update(&mut model, SettingsMsg::Open, &mut cmds);
+        update(
+            &mut model,
+            SettingsMsg::UploadAfterSaveChanged(true),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            SettingsMsg::ElabftwUrlChanged("elab.example.org".into()),
+            &mut cmds,
+        );
+        update(&mut model, SettingsMsg::Apply, &mut cmds);
+        assert!(model.is_open(), "a key is required");
+        assert!(cmds.is_empty());
+
+        update(
+            &mut model,
+            SettingsMsg::ApiKeyChanged(" 3-secret ".into()),
+            &mut cmds,
+        );
+        update(&mut model, SettingsMsg::Apply, &mut cmds);
+        assert!(!model.is_open());
+        assert!(matches!(
+            cmds.as_slice(),
+            [SettingsCommand::StoreApiKey(key), SettingsCommand::Persist(s)]
+                if s.elabftw.upload_after_save && key == "3-secret"
+        ));
+        let target = model.upload_target().unwrap();
+        assert_eq!(target.url, "https://elab.example.org");
+        assert_eq!(target.api_key, "3-secret");
+
+        cmds.clear();
+        update(&mut model, SettingsMsg::Open, &mut cmds);
+        update(&mut model, SettingsMsg::Apply, &mut cmds);
+        assert!(
+            !cmds
+                .iter()
+                .any(|c| matches!(c, SettingsCommand::StoreApiKey(_))),
+            "an unchanged key is not rewritten"
+        );
+    }
+
+    #[test]
+    fn imports_apply_only_after_confirmation() {
+        let mut model = SettingsModel::default();
+        let mut cmds = Vec::new();
+        update(
+            &mut model,
+            SettingsMsg::ApiKeyLoaded("3-secret".into()),
+            &mut cmds,
+        );
+        update(&mut model, SettingsMsg::ExportRequested, &mut cmds);
+        assert!(matches!(
+            cmds.as_slice(),
+            [SettingsCommand::ExportSettings(export)] if export.elabftw_api_key.is_none()
+        ));
+
+        let mut lab = Settings::default();
+        lab.elabftw.url = "https://elab.example.org".into();
+        let export = SettingsExport::new(&lab, Some("4-lab"));
+        cmds.clear();
+        update(
+            &mut model,
+            SettingsMsg::ImportLoaded(Ok(Some(Box::new(export)))),
+            &mut cmds,
+        );
+        assert!(model.pending_import().is_some());
+        assert_eq!(model.settings().elabftw.url, "", "nothing applied yet");
+
+        let event = update(&mut model, SettingsMsg::ConfirmImport, &mut cmds).unwrap();
+        assert!(!event.is_error);
+        assert!(model.pending_import().is_none());
+        assert_eq!(model.settings().elabftw.url, "https://elab.example.org");
+        assert!(matches!(
+            cmds.as_slice(),
+            [SettingsCommand::Persist(_), SettingsCommand::StoreApiKey(key)] if key == "4-lab"
+        ));
+    }
+
+    #[test]
+    fn toggling_preview_persists_immediately() {
+        let mut model = SettingsModel::default();
+        let mut cmds = Vec::new();
+
+        update(&mut model, SettingsMsg::SetIncludePreview(true), &mut cmds);
+        update(&mut model, SettingsMsg::SetIncludePreview(true), &mut cmds);
+
+        assert!(model.settings().archive.include_preview);
+        assert_eq!(cmds.len(), 1, "unchanged values are not re-persisted");
+    }
+
+    #[test]
+    fn choosing_crate_version_persists_immediately() {
+        let mut model = SettingsModel::default();
+        let mut cmds = Vec::new();
+        assert_eq!(model.settings().archive.crate_version, RoCrateVersion::V1_2);
+
+        update(
+            &mut model,
+            SettingsMsg::SetCrateVersion(RoCrateVersion::V1_1),
+            &mut cmds,
+        );
+
+        assert_eq!(model.settings().archive.crate_version, RoCrateVersion::V1_1);
+        assert!(matches!(
+            cmds.as_slice(),
+            [SettingsCommand::Persist(s)] if s.archive.crate_version == RoCrateVersion::V1_1
+        ));
+    }
+
+    #[test]
+    fn hashing_choices_persist_immediately() {
+        let mut model = SettingsModel::default();
+        let mut cmds = Vec::new();
+        let hashing = HashingSettings {
+            extra: [HashAlgorithm::Blake3].into(),
+            primary: HashAlgorithm::Sha512,
+        };
+
+        update(
+            &mut model,
+            SettingsMsg::SetHashing(hashing.clone()),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            SettingsMsg::SetHashing(hashing.clone()),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            SettingsMsg::SetChecksumsManifest(true),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            SettingsMsg::SetIncludeImportedMetadata(true),
+            &mut cmds,
+        );
+
+        assert_eq!(model.settings().hashing, hashing);
+        assert!(model.settings().archive.checksums_manifest);
+        assert!(model.settings().archive.include_imported_metadata);
+        assert_eq!(cmds.len(), 3, "unchanged values are not re-persisted");
+    }
+
+    #[test]
+    fn remembered_folders_persist_quietly() {
+        let mut model = SettingsModel::default();
+        let mut cmds = Vec::new();
+        let remember = SettingsMsg::RememberDir {
+            kind: PickerKind::Attachments,
+            dir: PathBuf::from("/data/run7"),
+        };
+
+        update(&mut model, remember.clone(), &mut cmds);
+        update(&mut model, remember, &mut cmds);
+        assert_eq!(cmds.len(), 1, "the same folder is not re-persisted");
+        assert_eq!(
+            model
+                .settings()
+                .favorites
+                .start_dir(PickerKind::Attachments),
+            Some(Path::new("/data/run7"))
+        );
+        assert_eq!(
+            update(&mut model, SettingsMsg::Persisted(Ok(())), &mut cmds),
+            None
+        );
+
+        update(&mut model, SettingsMsg::Open, &mut cmds);
+        update(&mut model, SettingsMsg::Apply, &mut cmds);
+        let event = update(&mut model, SettingsMsg::Persisted(Ok(())), &mut cmds);
+        assert_eq!(event.map(|e| e.message), Some("Settings saved.".into()));
+    }
+}
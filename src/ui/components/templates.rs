@@ -0,0 +1,903 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Template menu and dialogs for saving, applying, renaming, and deleting entry templates,
+//! and an editor for the metadata fields of a template.
+//!
+//! The editor works on a scratch copy of the extra fields, so editing a template never
+//! touches the entry being edited.
+
+use eframe::egui;
+
+use crate::i18n::t;
+use crate::logic::templates::Template;
+use crate::logic::units::UnitSettings;
+use crate::ui::components::IconLabel;
+use crate::ui::components::extra_fields::{self, ExtraFieldsModel, ExtraFieldsMsg};
+
+/// Known template names plus the state of the save and manage dialogs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TemplatesModel {
+    /// Template names, sorted case-insensitively.
+    names: Vec<String>,
+    /// Name typed into the open "Save as template" dialog.
+    save_name: Option<String>,
+    manage_open: bool,
+    /// Template being renamed and its new name.
+    rename: Option<(String, String)>,
+    /// Template open in the editor.
+    editor: Option<TemplateEditor>,
+}
+
+/// Template being edited, with its fields in a model of their own.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct TemplateEditor {
+    /// Name of the template on disk; `None` for new templates and copies.
+    original: Option<String>,
+    /// Title, body, keywords, and genre, kept as loaded.
+    template: Template,
+    name: String,
+    /// Scratch fields; never the active entry's.
+    fields: ExtraFieldsModel,
+    /// A write was requested; the editor closes once it succeeds.
+    saving: bool,
+}
+
+impl TemplateEditor {
+    fn new(template: Template, original: Option<String>, name: String) -> Self {
+        let mut fields = ExtraFieldsModel::default();
+        extra_fields::update(
+            &mut fields,
+            ExtraFieldsMsg::Restore {
+                fields: template.extra_fields.clone(),
+                groups: template.extra_groups.clone(),
+                source: None,
+                hide_main_text: false,
+            },
+            &mut Vec::new(),
+        );
+        Self {
+            original,
+            template,
+            name,
+            fields,
+            saving: false,
+        }
+    }
+}
+
+/// Messages emitted by the template menu and dialogs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TemplatesMsg {
+    /// Template names read from disk at startup.
+    Loaded(Vec<String>),
+    OpenSave,
+    SaveNameChanged(String),
+    CloseSave,
+    /// Store the current entry under the typed name.
+    Save,
+    /// Write the entries as an eLabFTW template archive; handled by the app.
+    SaveArchive,
+    /// Start a new entry from the named template.
+    Apply(String),
+    OpenManage,
+    CloseManage,
+    StartRename(String),
+    RenameChanged(String),
+    CommitRename,
+    CancelRename,
+    Delete(String),
+    /// A save, rename, or delete finished; `done` describes it for the status bar.
+    Changed {
+        result: Result<Vec<String>, String>,
+        done: String,
+    },
+    /// Open the editor with an empty template.
+    New,
+    /// Open the editor on the named template.
+    Edit(String),
+    /// Open the editor on a copy of the named template.
+    Duplicate(String),
+    /// A template requested by [`TemplatesMsg::Edit`] or [`TemplatesMsg::Duplicate`] was read.
+    EditorLoaded {
+        result: Result<Box<Template>, String>,
+        copy: bool,
+    },
+    Editor(TemplateEditorMsg),
+}
+
+/// Messages of the template editor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TemplateEditorMsg {
+    NameChanged(String),
+    TitleChanged(String),
+    Fields(ExtraFieldsMsg),
+    /// Validate the template and write it.
+    Save,
+    Close,
+}
+
+/// Side effects requested by the template dialogs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TemplatesCommand {
+    /// Store the current entry as a template with this name.
+    SaveCurrent(String),
+    Load(String),
+    Rename {
+        from: String,
+        to: String,
+    },
+    Delete(String),
+    /// Read the named template for the editor.
+    LoadForEdit {
+        name: String,
+        copy: bool,
+    },
+    /// Write a template from the editor, replacing one of the same name.
+    Write(Box<Template>),
+}
+
+/// Feedback surfaced to the status bar/modal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TemplatesEvent {
+    pub message: String,
+    pub is_error: bool,
+}
+
+/// Apply a message to the templates model.
+pub fn update(
+    model: &mut TemplatesModel,
+    msg: TemplatesMsg,
+    cmds: &mut Vec<TemplatesCommand>,
+) -> Option<TemplatesEvent> {
+    match msg {
+        TemplatesMsg::Loaded(names) => model.names = names,
+        TemplatesMsg::OpenSave => model.save_name = Some(String::new()),
+        TemplatesMsg::SaveNameChanged(name) => {
+            if let Some(current) = model.save_name.as_mut() {
+                *current = name;
+            }
+        }
+        TemplatesMsg::CloseSave => model.save_name = None,
+        TemplatesMsg::Save => {
+            let name = model.save_name.take()?;
+            let name = name.trim();
+            if name.is_empty() {
+                return Some(TemplatesEvent {
+                    message: t!("templates.name.empty").into(),
+                    is_error: true,
+                });
+            }
+            cmds.push(TemplatesCommand::SaveCurrent(name.to_string()));
+        }
+        // Saving archives is the app's business; it never forwards this message.
+        TemplatesMsg::SaveArchive => {}
+        TemplatesMsg::Apply(name) => cmds.push(TemplatesCommand::Load(name)),
+        TemplatesMsg::OpenManage => model.manage_open = true,
+        TemplatesMsg::CloseManage => {
+            model.manage_open = false;
+            model.rename = None;
+        }
+        TemplatesMsg::StartRename(name) => model.rename = Some((name.clone(), name)),
+        TemplatesMsg::RenameChanged(to) => {
+            if let Some((_, current)) = model.rename.as_mut() {
+                *current = to;
+            }
+        }
+        TemplatesMsg::CommitRename => {
+            let (from, to) = model.rename.take()?;
+            if to.trim() != from {
+                cmds.push(TemplatesCommand::Rename {
+                    from,
+                    to: to.trim().to_string(),
+                });
+            }
+        }
+        TemplatesMsg::CancelRename => model.rename = None,
+        TemplatesMsg::Delete(name) => cmds.push(TemplatesCommand::Delete(name)),
+        TemplatesMsg::Changed { result, done } => {
+            let saving = model.editor.as_ref().is_some_and(|editor| editor.saving);
+            return Some(match result {
+                Ok(names) => {
+                    model.names = names;
+                    if saving {
+                        model.editor = None;
+                    }
+                    TemplatesEvent {
+                        message: done,
+                        is_error: false,
+                    }
+                }
+                Err(err) => {
+                    // Keep the edits so the save can be retried.
+                    if let Some(editor) = model.editor.as_mut() {
+                        editor.saving = false;
+                    }
+                    TemplatesEvent {
+                        message: err,
+                        is_error: true,
+                    }
+                }
+            });
+        }
+        TemplatesMsg::New => {
+            model.editor = Some(TemplateEditor::new(
+                Template::blank(""),
+                None,
+                String::new(),
+            ));
+        }
+        TemplatesMsg::Edit(name) => cmds.push(TemplatesCommand::LoadForEdit { name, copy: false }),
+        TemplatesMsg::Duplicate(name) => {
+            cmds.push(TemplatesCommand::LoadForEdit { name, copy: true })
+        }
+        TemplatesMsg::EditorLoaded { result, copy } => {
+            let template = match result {
+                Ok(template) => *template,
+                Err(message) => {
+                    return Some(TemplatesEvent {
+                        message,
+                        is_error: true,
+                    });
+                }
+            };
+            model.editor = Some(if copy {
+                let name = copy_name(&model.names, &template.name);
+                TemplateEditor::new(template, None, name)
+            } else {
+                let name = template.name.clone();
+                TemplateEditor::new(template, Some(name.clone()), name)
+            });
+        }
+        TemplatesMsg::Editor(msg) => return update_editor(model, msg, cmds),
+    }
+    None
+}
+
+/// Apply a message to the open template editor.
+fn update_editor(
+    model: &mut TemplatesModel,
+    msg: TemplateEditorMsg,
+    cmds: &mut Vec<TemplatesCommand>,
+) -> Option<TemplatesEvent> {
+    let editor = model.editor.as_mut()?;
+    match msg {
+        TemplateEditorMsg::NameChanged(name) => {
+            // Existing templates are renamed from the list instead.
+            if editor.original.is_none() {
+                editor.name = name;
+            }
+        }
+        TemplateEditorMsg::TitleChanged(title) => editor.template.title = title,
+        TemplateEditorMsg::Fields(
+            ExtraFieldsMsg::ImportRequested
+            | ExtraFieldsMsg::EntryImportRequested
+            | ExtraFieldsMsg::ReimportRequested,
+        ) => {
+            return Some(TemplatesEvent {
+                message: t!("templates.import_hint").into(),
+                is_error: false,
+            });
+        }
+        TemplateEditorMsg::Fields(msg) => {
+            // The scratch model has no file of its own to pick or re-read.
+            let event = extra_fields::update(&mut editor.fields, msg, &mut Vec::new())?;
+            return Some(TemplatesEvent {
+                message: event.message,
+                is_error: event.is_error,
+            });
+        }
+        TemplateEditorMsg::Save => {
+            let name = editor.name.trim().to_string();
+            let taken = editor.original.is_none()
+                && model
+                    .names
+                    .iter()
+                    .any(|known| known.eq_ignore_ascii_case(&name));
+            if taken {
+                return Some(TemplatesEvent {
+                    message: t!("templates.exists", name = name),
+                    is_error: true,
+                });
+            }
+            let template = Template {
+                name,
+                extra_fields: editor.fields.fields().to_vec(),
+                extra_groups: editor.fields.groups().to_vec(),
+                ..editor.template.clone()
+            };
+            if let Err(err) = template.validate() {
+                return Some(TemplatesEvent {
+                    message: err.to_string(),
+                    is_error: true,
+                });
+            }
+            editor.saving = true;
+            cmds.push(TemplatesCommand::Write(Box::new(template)));
+        }
+        TemplateEditorMsg::Close => model.editor = None,
+    }
+    None
+}
+
+/// Name for a copy of `name` that no template in `names` uses yet.
+fn copy_name(names: &[String], name: &str) -> String {
+    let taken = |candidate: &str| names.iter().any(|n| n.eq_ignore_ascii_case(candidate));
+    let mut candidate = format!("{name} (copy)");
+    let mut number = 2;
+    while taken(&candidate) {
+        candidate = format!("{name} (copy {number})");
+        number += 1;
+    }
+    candidate
+}
+
+/// Render the toolbar menu listing templates to start from, plus save and manage actions.
+pub fn menu(ui: &mut egui::Ui, model: &TemplatesModel, label: String) -> Vec<TemplatesMsg> {
+    let mut msgs = Vec::new();
+    ui.menu_button(label, |ui| {
+        if model.names.is_empty() {
+            ui.label(egui::RichText::new(t!("templates.none")).weak());
+        }
+        for name in &model.names {
+            if ui
+                .button(name)
+                .on_hover_text(t!("templates.apply.hover"))
+                .clicked()
+            {
+                msgs.push(TemplatesMsg::Apply(name.clone()));
+                ui.close();
+            }
+        }
+        ui.separator();
+        if ui
+            .button(format!(
+                "{} {}",
+                egui_phosphor::regular::FLOPPY_DISK_BACK,
+                t!("templates.save")
+            ))
+            .on_hover_text(t!("templates.save.hover"))
+            .clicked()
+        {
+            msgs.push(TemplatesMsg::OpenSave);
+            ui.close();
+        }
+        if ui
+            .button(format!(
+                "{} {}",
+                egui_phosphor::regular::FILE_ARCHIVE,
+                t!("templates.archive")
+            ))
+            .on_hover_text(t!("templates.archive.hover"))
+            .clicked()
+        {
+            msgs.push(TemplatesMsg::SaveArchive);
+            ui.close();
+        }
+        if ui
+            .button(format!(
+                "{} {}",
+                egui_phosphor::regular::PENCIL_SIMPLE,
+                t!("templates.manage.button")
+            ))
+            .on_hover_text(t!("templates.manage.hover"))
+            .clicked()
+        {
+            msgs.push(TemplatesMsg::OpenManage);
+            ui.close();
+        }
+    })
+    .response
+    .on_hover_text(t!("templates.menu.hover"));
+    msgs
+}
+
+/// Render the compact switcher listing the `recent` templates that still exist, labelled
+/// with the `applied` template's name and whether its fields were modified.
+pub fn quick_switch(
+    ui: &mut egui::Ui,
+    model: &TemplatesModel,
+    recent: &[String],
+    applied: Option<(&str, bool)>,
+) -> Vec<TemplatesMsg> {
+    let mut msgs = Vec::new();
+    let recent: Vec<&String> = recent
+        .iter()
+        .filter(|name| model.names.iter().any(|known| known == *name))
+        .collect();
+    if recent.is_empty() && applied.is_none() {
+        return msgs;
+    }
+    let label = match applied {
+        Some((name, true)) => t!("templates.modified", name = name),
+        Some((name, false)) => name.to_string(),
+        None => t!("templates.none_applied").to_string(),
+    };
+    egui::ComboBox::from_id_salt("template_quick_switch")
+        .selected_text(format!("{} {label}", egui_phosphor::regular::STACK))
+        .show_ui(ui, |ui| {
+            if recent.is_empty() {
+                ui.label(egui::RichText::new(t!("templates.recent.none")).weak());
+            }
+            for name in recent {
+                let current = applied.is_some_and(|(applied, _)| applied == name);
+                if ui.selectable_label(current, name).clicked() {
+                    msgs.push(TemplatesMsg::Apply(name.clone()));
+                }
+            }
+        })
+        .response
+        .on_hover_text(match applied {
+            Some((_, true)) => t!("templates.modified.hover"),
+            _ => t!("templates.recent.hover"),
+        });
+    msgs
+}
+
+/// Render the save and manage dialogs when open.
+pub fn view(
+    ctx: &egui::Context,
+    model: &TemplatesModel,
+    units: &UnitSettings,
+) -> Vec<TemplatesMsg> {
+    let mut msgs = Vec::new();
+    render_save_window(ctx, model, &mut msgs);
+    render_manage_window(ctx, model, &mut msgs);
+    render_editor_window(ctx, model, units, &mut msgs);
+    msgs
+}
+
+fn render_save_window(ctx: &egui::Context, model: &TemplatesModel, msgs: &mut Vec<TemplatesMsg>) {
+    let Some(name) = &model.save_name else {
+        return;
+    };
+    egui::Window::new(t!("templates.save.title"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            let mut edited = name.clone();
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut edited)
+                    .hint_text(t!("templates.name.hint"))
+                    .desired_width(240.0),
+            );
+            if response.changed() {
+                msgs.push(TemplatesMsg::SaveNameChanged(edited));
+            }
+            ui.label(
+                egui::RichText::new(t!("templates.save.text"))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
+            );
+            let exists = model
+                .names
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(name.trim()));
+            if exists {
+                ui.label(t!("templates.replaced", name = name.trim()));
+            }
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                let label = if exists {
+                    t!("templates.replace")
+                } else {
+                    t!("common.save")
+                };
+                if ui
+                    .add_enabled(!name.trim().is_empty(), egui::Button::new(label))
+                    .clicked()
+                {
+                    msgs.push(TemplatesMsg::Save);
+                }
+                if ui.button(t!("common.cancel")).clicked() {
+                    msgs.push(TemplatesMsg::CloseSave);
+                }
+            });
+        });
+}
+
+fn render_manage_window(ctx: &egui::Context, model: &TemplatesModel, msgs: &mut Vec<TemplatesMsg>) {
+    if !model.manage_open {
+        return;
+    }
+    egui::Window::new(t!("templates.manage"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            egui::Grid::new("templates_grid")
+                .num_columns(2)
+                .spacing(egui::vec2(8.0, 6.0))
+                .show(ui, |ui| {
+                    for name in &model.names {
+                        match &model.rename {
+                            Some((from, to)) if from == name => {
+                                let mut edited = to.clone();
+                                let response = ui.add(
+                                    egui::TextEdit::singleline(&mut edited).desired_width(200.0),
+                                );
+                                if response.changed() {
+                                    msgs.push(TemplatesMsg::RenameChanged(edited));
+                                }
+                                ui.horizontal(|ui| {
+                                    if ui
+                                        .small_button(egui_phosphor::regular::CHECK)
+                                        .icon_label(t!("attachments.rename"))
+                                        .clicked()
+                                    {
+                                        msgs.push(TemplatesMsg::CommitRename);
+                                    }
+                                    if ui
+                                        .small_button(egui_phosphor::regular::X)
+                                        .icon_label(t!("common.cancel"))
+                                        .clicked()
+                                    {
+                                        msgs.push(TemplatesMsg::CancelRename);
+                                    }
+                                });
+                            }
+                            _ => {
+                                ui.label(name);
+                                ui.horizontal(|ui| {
+                                    if ui
+                                        .small_button(egui_phosphor::regular::NOTE_PENCIL)
+                                        .icon_label(t!("templates.edit.hover"))
+                                        .clicked()
+                                    {
+                                        msgs.push(TemplatesMsg::Edit(name.clone()));
+                                    }
+                                    if ui
+                                        .small_button(egui_phosphor::regular::COPY)
+                                        .icon_label(t!("templates.duplicate"))
+                                        .clicked()
+                                    {
+                                        msgs.push(TemplatesMsg::Duplicate(name.clone()));
+                                    }
+                                    if ui
+                                        .small_button(egui_phosphor::regular::PENCIL_SIMPLE)
+                                        .icon_label(t!("attachments.rename"))
+                                        .clicked()
+                                    {
+                                        msgs.push(TemplatesMsg::StartRename(name.clone()));
+                                    }
+                                    if ui
+                                        .small_button(egui_phosphor::regular::TRASH)
+                                        .icon_label(t!("templates.delete"))
+                                        .clicked()
+                                    {
+                                        msgs.push(TemplatesMsg::Delete(name.clone()));
+                                    }
+                                });
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+            if model.names.is_empty() {
+                ui.label(egui::RichText::new(t!("templates.none")).weak());
+            }
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui
+                    .button(format!(
+                        "{} {}",
+                        egui_phosphor::regular::PLUS,
+                        t!("templates.new")
+                    ))
+                    .clicked()
+                {
+                    msgs.push(TemplatesMsg::New);
+                }
+                if ui.button(t!("common.close")).clicked() {
+                    msgs.push(TemplatesMsg::CloseManage);
+                }
+            });
+        });
+}
+
+fn render_editor_window(
+    ctx: &egui::Context,
+    model: &TemplatesModel,
+    units: &UnitSettings,
+    msgs: &mut Vec<TemplatesMsg>,
+) {
+    let Some(editor) = &model.editor else {
+        return;
+    };
+    let heading = match &editor.original {
+        Some(name) => t!("templates.edit", name = name),
+        None => t!("templates.new").to_string(),
+    };
+    egui::Window::new(heading)
+        .id(egui::Id::new("template_editor"))
+        .collapsible(false)
+        .default_width(520.0)
+        .show(ctx, |ui| {
+            egui::Grid::new("template_editor_grid")
+                .num_columns(2)
+                .spacing(egui::vec2(8.0, 6.0))
+                .show(ui, |ui| {
+                    ui.label(t!("settings.publisher.name"));
+                    if editor.original.is_some() {
+                        ui.label(&editor.name);
+                    } else {
+                        let mut name = editor.name.clone();
+                        if ui
+                            .add(
+                                egui::TextEdit::singleline(&mut name)
+                                    .hint_text(t!("templates.name.hint"))
+                                    .desired_width(240.0),
+                            )
+                            .changed()
+                        {
+                            msgs.push(TemplatesMsg::Editor(TemplateEditorMsg::NameChanged(name)));
+                        }
+                    }
+                    ui.end_row();
+
+                    ui.label(t!("fields.modal.title"));
+                    let mut title = editor.template.title.clone();
+                    if ui
+                        .add(
+                            egui::TextEdit::singleline(&mut title)
+                                .hint_text(t!("templates.title.hint"))
+                                .desired_width(240.0),
+                        )
+                        .changed()
+                    {
+                        msgs.push(TemplatesMsg::Editor(TemplateEditorMsg::TitleChanged(title)));
+                    }
+                    ui.end_row();
+                });
+            ui.label(
+                egui::RichText::new(t!("templates.editor.text"))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
+            );
+            ui.add_space(4.0);
+            egui::ScrollArea::vertical()
+                .max_height(420.0)
+                .show(ui, |ui| {
+                    let field_msgs = extra_fields::view(ui, &editor.fields, units, |_| {});
+                    msgs.extend(
+                        field_msgs
+                            .into_iter()
+                            .map(|m| TemplatesMsg::Editor(TemplateEditorMsg::Fields(m))),
+                    );
+                });
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        !editor.saving,
+                        egui::Button::new(t!("templates.editor.save")),
+                    )
+                    .clicked()
+                {
+                    msgs.push(TemplatesMsg::Editor(TemplateEditorMsg::Save));
+                }
+                if ui.button(t!("common.cancel")).clicked() {
+                    msgs.push(TemplatesMsg::Editor(TemplateEditorMsg::Close));
+                }
+            });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_and_rename_request_commands_with_trimmed_names() {
+        let mut model = TemplatesModel::default();
+        let mut cmds = Vec::new();
+
+        update(&mut model, TemplatesMsg::OpenSave, &mut cmds);
+        update(&mut model, TemplatesMsg::Save, &mut cmds);
+        assert!(cmds.is_empty(), "empty names are refused");
+
+        update(&mut model, TemplatesMsg::OpenSave, &mut cmds);
+        update(
+            &mut model,
+            TemplatesMsg::SaveNameChanged(" PCR run ".into()),
+            &mut cmds,
+        );
+        update(&mut model, TemplatesMsg::Save, &mut cmds);
+        assert_eq!(cmds, [TemplatesCommand::SaveCurrent("PCR run".into())]);
+        assert!(model.save_name.is_none());
+
+        let event = update(
+            &mut model,
+            TemplatesMsg::Changed {
+                result: Ok(vec!["PCR run".into()]),
+                done: "Saved template 'PCR run'.".into(),
+            },
+            &mut cmds,
+        )
+        .unwrap();
+        assert!(!event.is_error);
+        assert_eq!(model.names, ["PCR run"]);
+
+        cmds.clear();
+        update(
+            &mut model,
+            TemplatesMsg::StartRename("PCR run".into()),
+            &mut cmds,
+        );
+        update(&mut model, TemplatesMsg::CommitRename, &mut cmds);
+        assert!(cmds.is_empty(), "unchanged names are not renamed");
+        update(
+            &mut model,
+            TemplatesMsg::StartRename("PCR run".into()),
+            &mut cmds,
+        );
+        update(
+            &mut model,
+            TemplatesMsg::RenameChanged("qPCR ".into()),
+            &mut cmds,
+        );
+        update(&mut model, TemplatesMsg::CommitRename, &mut cmds);
+        assert_eq!(
+            cmds,
+            [TemplatesCommand::Rename {
+                from: "PCR run".into(),
+                to: "qPCR".into(),
+            }]
+        );
+    }
+
+    fn edit(model: &mut TemplatesModel, msg: TemplateEditorMsg) -> Option<TemplatesEvent> {
+        update(model, TemplatesMsg::Editor(msg), &mut Vec::new())
+    }
+
+    fn add_number_field(model: &mut TemplatesModel, label: &str, value: &str) {
+        for msg in [
+            ExtraFieldsMsg::StartAddField { group_id: None },
+            ExtraFieldsMsg::DraftLabelChanged(label.into()),
+            ExtraFieldsMsg::DraftKindChanged(crate::models::extra_fields::ExtraFieldKind::Number),
+            ExtraFieldsMsg::DraftRequiredToggled(true),
+            ExtraFieldsMsg::CommitFieldModal,
+        ] {
+            edit(model, TemplateEditorMsg::Fields(msg));
+        }
+        let index = model.editor.as_ref().unwrap().fields.fields().len() - 1;
+        edit(
+            model,
+            TemplateEditorMsg::Fields(ExtraFieldsMsg::EditValue {
+                index,
+                value: value.into(),
+            }),
+        );
+    }
+
+    #[test]
+    fn editor_validates_before_writing_and_closes_once_saved() {
+        let mut model = TemplatesModel {
+            names: vec!["PCR".into()],
+            ..Default::default()
+        };
+        let mut cmds = Vec::new();
+        update(&mut model, TemplatesMsg::New, &mut cmds);
+
+        edit(&mut model, TemplateEditorMsg::NameChanged("pcr".into()));
+        let event = edit(&mut model, TemplateEditorMsg::Save).unwrap();
+        assert!(event.is_error, "names of other templates are refused");
+
+        edit(
+            &mut model,
+            TemplateEditorMsg::NameChanged("Cell count".into()),
+        );
+        edit(
+            &mut model,
+            TemplateEditorMsg::TitleChanged("Count {date}".into()),
+        );
+        add_number_field(&mut model, "Cells", "many");
+        let event = edit(&mut model, TemplateEditorMsg::Save).unwrap();
+        assert!(
+            event.is_error,
+            "invalid defaults are refused: {}",
+            event.message
+        );
+
+        let event = edit(
+            &mut model,
+            TemplateEditorMsg::Fields(ExtraFieldsMsg::ImportRequested),
+        )
+        .unwrap();
+        assert!(!event.is_error);
+
+        let index = model.editor.as_ref().unwrap().fields.fields().len() - 1;
+        edit(
+            &mut model,
+            TemplateEditorMsg::Fields(ExtraFieldsMsg::EditValue {
+                index,
+                value: String::new(),
+            }),
+        );
+        update(
+            &mut model,
+            TemplatesMsg::Editor(TemplateEditorMsg::Save),
+            &mut cmds,
+        );
+        let [TemplatesCommand::Write(template)] = cmds.as_slice() else {
+            panic!("expected a write, got {cmds:?}");
+        };
+        assert_eq!(template.name, "Cell count");
+        assert_eq!(template.title, "Count {date}");
+        assert_eq!(template.extra_fields.len(), 1);
+        assert!(template.extra_fields[0].required);
+
+        update(
+            &mut model,
+            TemplatesMsg::Changed {
+                result: Err("disk full".into()),
+                done: String::new(),
+            },
+            &mut Vec::new(),
+        );
+        assert!(model.editor.is_some(), "failed writes keep the edits");
+        update(
+            &mut model,
+            TemplatesMsg::Editor(TemplateEditorMsg::Save),
+            &mut Vec::new(),
+        );
+        update(
+            &mut model,
+            TemplatesMsg::Changed {
+                result: Ok(vec!["Cell count".into(), "PCR".into()]),
+                done: "Saved template 'Cell count'.".into(),
+            },
+            &mut Vec::new(),
+        );
+        assert!(model.editor.is_none());
+    }
+
+    #[test]
+    fn duplicates_get_a_free_name_and_edits_keep_theirs() {
+        let mut model = TemplatesModel {
+            names: vec!["PCR".into(), "PCR (copy)".into()],
+            ..Default::default()
+        };
+        let mut cmds = Vec::new();
+        update(&mut model, TemplatesMsg::Duplicate("PCR".into()), &mut cmds);
+        assert_eq!(
+            cmds,
+            [TemplatesCommand::LoadForEdit {
+                name: "PCR".into(),
+                copy: true,
+            }]
+        );
+
+        let loaded = Box::new(Template::blank("PCR"));
+        update(
+            &mut model,
+            TemplatesMsg::EditorLoaded {
+                result: Ok(loaded.clone()),
+                copy: true,
+            },
+            &mut cmds,
+        );
+        let editor = model.editor.as_ref().unwrap();
+        assert_eq!(editor.name, "PCR (copy 2)");
+        assert_eq!(editor.original, None);
+
+        update(
+            &mut model,
+            TemplatesMsg::EditorLoaded {
+                result: Ok(loaded),
+                copy: false,
+            },
+            &mut cmds,
+        );
+        edit(&mut model, TemplateEditorMsg::NameChanged("Other".into()));
+        let editor = model.editor.as_ref().unwrap();
+        assert_eq!(
+            editor.name, "PCR",
+            "existing templates are renamed from the list"
+        );
+        assert_eq!(editor.original.as_deref(), Some("PCR"));
+    }
+}
@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Fonts registered with egui and a glyph coverage check for diagnosing missing characters.
+//!
+//! egui's default fonts lack many symbols used in lab notes, such as `✕` or `≥`, and render
+//! them as boxes. With the `fallback-font` feature (on by default), a subset of DejaVu Sans
+//! with Latin, Greek and the symbol blocks is bundled and tried after the default and icon
+//! fonts; `scripts/subset-fallback-font.py` builds it. The fonts are registered from static
+//! data, so they are neither copied nor decoded before a glyph is first looked up in them.
+//!
+//! Emoji come from egui's own emoji fonts, which end at Unicode 8. The feature also bundles
+//! the laboratory emoji added later (`🧪`, `🧫`, `🧬`), drawn by
+//! `scripts/build-lab-emoji-font.py`; other newer emoji still render as boxes.
+
+use std::collections::BTreeMap;
+
+use eframe::egui::{self, FontDefinitions, FontFamily};
+use egui_phosphor::Variant;
+use ttf_parser::Face;
+
+use crate::i18n::t;
+
+/// Name of the bundled fallback font in [`FontDefinitions::font_data`].
+#[cfg(feature = "fallback-font")]
+pub const FALLBACK_FONT: &str = "DejaVuSans";
+
+/// Name of the bundled laboratory emoji font in [`FontDefinitions::font_data`].
+#[cfg(feature = "fallback-font")]
+pub const LAB_EMOJI_FONT: &str = "ELNPackLabEmoji";
+
+/// Text checked by the font coverage panel until the user enters another.
+pub const DEFAULT_PROBE: &str = "✕ ± µ ≥ ‰ α β 🧪";
+
+/// Default fonts with the phosphor icons and, if bundled, the fallback font.
+pub fn definitions() -> FontDefinitions {
+    let mut fonts = FontDefinitions::default();
+    egui_phosphor::add_to_fonts(&mut fonts, Variant::Regular);
+
+    #[cfg(feature = "fallback-font")]
+    {
+        let bundled: [(&str, &'static [u8]); 2] = [
+            (
+                FALLBACK_FONT,
+                include_bytes!("../../assets/fonts/DejaVuSans-Symbols.ttf"),
+            ),
+            (
+                LAB_EMOJI_FONT,
+                include_bytes!("../../assets/fonts/ELNPack-LabEmoji.ttf"),
+            ),
+        ];
+        for (name, data) in bundled {
+            fonts.font_data.insert(
+                name.to_owned(),
+                std::sync::Arc::new(egui::FontData::from_static(data)),
+            );
+            for family in [FontFamily::Proportional, FontFamily::Monospace] {
+                fonts
+                    .families
+                    .entry(family)
+                    .or_default()
+                    .push(name.to_owned());
+            }
+        }
+    }
+    fonts
+}
+
+/// Glyph coverage of `probe` in one configured font.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FontCoverage {
+    /// Font name, or the family name for the families used to render text.
+    pub name: String,
+    /// Characters of the probe the font has no glyph for, whitespace excluded.
+    pub missing: Vec<char>,
+}
+
+/// Check which characters of `probe` each configured font, and the text families as a whole,
+/// can render.
+///
+/// Coverage is read from the fonts' character maps: egui reports a glyph as missing whenever
+/// it comes from the font that also supplies the replacement character.
+///
+/// Only valid once `ctx` ran a pass with fonts from [`definitions`].
+pub fn coverage(ctx: &egui::Context, probe: &str) -> Vec<FontCoverage> {
+    let chars: Vec<char> = probe.chars().filter(|c| !c.is_whitespace()).collect();
+    ctx.fonts_mut(|fonts| {
+        let definitions = fonts.definitions();
+        let faces: BTreeMap<&str, Face<'_>> = definitions
+            .font_data
+            .iter()
+            .filter_map(|(name, data)| {
+                Some((name.as_str(), Face::parse(&data.font, data.index).ok()?))
+            })
+            .collect();
+        let covers = |name: &str, c: char| {
+            faces
+                .get(name)
+                .and_then(|face| face.glyph_index(c))
+                .is_some_and(|glyph| glyph.0 != 0)
+        };
+        let missing = |names: &[String]| -> Vec<char> {
+            chars
+                .iter()
+                .copied()
+                .filter(|&c| !names.iter().any(|name| covers(name, c)))
+                .collect()
+        };
+
+        let mut report: Vec<FontCoverage> = [
+            (t!("fonts.proportional"), FontFamily::Proportional),
+            (t!("fonts.monospace"), FontFamily::Monospace),
+        ]
+        .into_iter()
+        .map(|(name, family)| FontCoverage {
+            name: name.to_owned(),
+            missing: missing(definitions.families.get(&family).map_or(&[], Vec::as_slice)),
+        })
+        .collect();
+        report.extend(definitions.font_data.keys().map(|name| FontCoverage {
+            name: name.clone(),
+            missing: missing(std::slice::from_ref(name)),
+        }));
+        report
+    })
+}
+
+#[cfg(all(test, feature = "fallback-font"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fallback_font_covers_scientific_symbols() {
+        let ctx = egui::Context::default();
+        ctx.set_fonts(definitions());
+        let _ = ctx.run_ui(Default::default(), |_| {});
+
+        let report = coverage(&ctx, "✕ ± µ ≥ ‰ α β 🧪 🧫 🧬");
+        let missing = |name: &str| {
+            report
+                .iter()
+                .find(|font| font.name.starts_with(name))
+                .map(|font| font.missing.clone())
+        };
+        assert_eq!(missing("Proportional"), Some(Vec::new()));
+        assert_eq!(missing("Monospace"), Some(Vec::new()));
+        assert_eq!(missing(FALLBACK_FONT), Some(vec!['🧪', '🧫', '🧬']));
+        assert_eq!(
+            missing(LAB_EMOJI_FONT),
+            Some(vec!['✕', '±', 'µ', '≥', '‰', 'α', 'β'])
+        );
+        assert!(
+            report.iter().any(|font| font.missing.contains(&'✕')),
+            "the default fonts lack it"
+        );
+    }
+}
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Layout parameters for the regular and compact window modes.
+//!
+//! Render functions read sizes and toggles from [`LayoutParams`] instead of branching on the
+//! mode themselves, so both layouts are defined side by side in one place.
+
+use eframe::egui;
+
+/// Spacing, sizes, and presentation toggles consulted by the render functions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayoutParams {
+    /// Global spacing between widgets.
+    pub item_spacing: egui::Vec2,
+    /// Vertical gap between form sections.
+    pub section_gap: f32,
+    /// Show top-bar buttons as icons with tooltips only.
+    pub icon_only_toolbar: bool,
+    /// Put entry type and performed-at on a single row with popovers.
+    pub inline_meta: bool,
+    /// Initial height of the body editor.
+    pub editor_default_height: f32,
+    /// Smallest height the body editor can be resized to.
+    pub editor_min_height: f32,
+}
+
+impl LayoutParams {
+    /// Derive the layout for the given mode.
+    pub fn for_mode(compact: bool) -> Self {
+        if compact {
+            Self {
+                item_spacing: egui::vec2(4.0, 3.0),
+                section_gap: 6.0,
+                icon_only_toolbar: true,
+                inline_meta: true,
+                editor_default_height: 160.0,
+                editor_min_height: 60.0,
+            }
+        } else {
+            Self {
+                item_spacing: egui::vec2(6.0, 6.0),
+                section_gap: 12.0,
+                icon_only_toolbar: false,
+                inline_meta: false,
+                editor_default_height: 200.0,
+                editor_min_height: 100.0,
+            }
+        }
+    }
+
+    /// Caption for a top-bar button: the icon alone in compact mode, icon and label otherwise.
+    pub fn toolbar_text(&self, icon: &str, label: &str) -> String {
+        if self.icon_only_toolbar {
+            icon.to_string()
+        } else {
+            format!("{icon} {label}")
+        }
+    }
+}
+
+impl Default for LayoutParams {
+    fn default() -> Self {
+        Self::for_mode(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compact_mode_shrinks_every_dimension() {
+        let regular = LayoutParams::for_mode(false);
+        let compact = LayoutParams::for_mode(true);
+
+        assert!(compact.item_spacing.x < regular.item_spacing.x);
+        assert!(compact.item_spacing.y < regular.item_spacing.y);
+        assert!(compact.section_gap < regular.section_gap);
+        assert!(compact.editor_min_height < regular.editor_min_height);
+        assert!(compact.editor_min_height <= compact.editor_default_height);
+        assert!(compact.icon_only_toolbar && compact.inline_meta);
+        assert_eq!(regular, LayoutParams::default());
+    }
+
+    #[test]
+    fn toolbar_text_drops_label_in_compact_mode() {
+        assert_eq!(
+            LayoutParams::for_mode(false).toolbar_text("*", "Save"),
+            "* Save"
+        );
+        assert_eq!(LayoutParams::for_mode(true).toolbar_text("*", "Save"), "*");
+    }
+}
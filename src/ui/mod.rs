@@ -5,60 +5,210 @@
 //! Handles layout, form controls, and wiring to archive creation.
 
 pub mod components;
+pub mod fonts;
+pub mod layout;
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use eframe::egui;
 
-use crate::logic::eln::{ArchiveGenre, ensure_extension, suggested_archive_name};
-use crate::mvu::{self, AppModel, Command, Msg};
-use crate::ui::components::{attachments, datetime_picker, extra_fields, keywords, markdown};
+use crate::i18n::{self, t};
+use crate::logic::eln::{
+    ArchiveGenre, Compression, MissingFileAction, MissingFilePolicy, RoCrateVersion,
+    SUMMARY_MAX_CHARS, ensure_extension, suggested_archive_name, title_length_warning,
+};
+use crate::logic::favorites::PickerKind;
+use crate::logic::settings::{Language, ThemeSetting};
+use crate::logic::summary::{format_span, summarize_entry};
+use crate::models::keywords::{added_keywords, derived_keywords};
+use crate::models::license::{License, validate_license};
+use crate::mvu::dialogs::{DialogProvider, ELN_FILTER, JSON_FILTER, RfdDialogs};
+use crate::mvu::startup::PersistedFile;
+use crate::mvu::status::{Severity, StatusMessage};
+use crate::mvu::workers::Workers;
+use crate::mvu::{self, AppModel, BatchJobState, Command, CommandKind, Msg, QuitState};
+use crate::ui::components::settings::SettingsMsg;
+use crate::ui::components::{
+    IconLabel, attachments, authors, datetime_picker, extra_fields, keywords, markdown, onboarding,
+    related_links, settings, templates,
+};
+use crate::ui::layout::LayoutParams;
+use crate::utils::storage::AppPaths;
+use crate::utils::text::ellipsize;
 
 /// Stateful egui application for building and exporting ELN entries.
 pub struct ElnPackApp {
     model: AppModel,
     inbox: Vec<Msg>,
-    cmd_tx: crossbeam_channel::Sender<Command>,
-    msg_rx: crossbeam_channel::Receiver<Msg>,
+    /// Dialog thread and background pool that run commands.
+    workers: Workers,
+    /// File dialogs, shared with the dialog thread.
+    dialogs: Arc<dyn DialogProvider>,
     thumbnail_textures: HashMap<PathBuf, egui::TextureHandle>,
     pending_thumbnail_images: Vec<(PathBuf, u64, egui::ColorImage)>,
     active_thumbnail_requests: HashMap<PathBuf, u64>,
     next_thumbnail_request_id: u64,
+    /// Full-size image of the preview dialog, waiting for its texture.
+    pending_preview_image: Option<(PathBuf, egui::ColorImage)>,
+    preview_texture: Option<(PathBuf, egui::TextureHandle)>,
+    /// Cancel flags of running hash commands, keyed by attachment path.
+    active_hash_cancels: HashMap<PathBuf, Arc<AtomicBool>>,
+    /// Cancel flag of the running archive save.
+    active_save_cancel: Option<Arc<AtomicBool>>,
+    /// Where the running archive save waits for answers about missing attachments.
+    active_save_answer: Option<Arc<Mutex<Option<MissingFileAction>>>>,
+    /// Cancel flag of the running eLabFTW upload.
+    active_upload_cancel: Option<Arc<AtomicBool>>,
+    /// Serialized draft last written (or deliberately skipped) by autosave.
+    last_draft_snapshot: Option<String>,
+    /// Time of the last autosave check, used to throttle disk writes.
+    last_draft_check: Instant,
+    /// Whether the stored theme was handed to egui after settings loaded.
+    theme_applied: bool,
+    /// Time of the last window geometry check, used to throttle settings writes.
+    last_window_check: Instant,
+    /// Locations of the persisted files, kept to restart in safe mode; empty in safe mode.
+    paths: AppPaths,
+    /// Until when holding Shift restarts the app in safe mode; `None` once that is decided.
+    safe_mode_shift_deadline: Option<Instant>,
+    /// Text checked in the font coverage panel.
+    font_probe: String,
 }
 
+/// Maximum number of thumbnail textures uploaded per frame; the rest wait for later frames.
+const THUMBNAIL_UPLOADS_PER_FRAME: usize = 4;
+
+/// Longest entry label shown in full in the entries list; longer ones show on hover.
+const ENTRY_LABEL_MAX_CHARS: usize = 40;
+
+/// Hover text of save buttons that are disabled.
+/// How long after startup holding Shift still restarts the app in safe mode.
+const SAFE_MODE_SHIFT_WINDOW: Duration = Duration::from_secs(2);
+
+/// Minimum delay between two draft autosave checks.
+const DRAFT_AUTOSAVE_INTERVAL: Duration = Duration::from_secs(2);
+
 impl Default for ElnPackApp {
     fn default() -> Self {
-        let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded::<Command>();
-        let (msg_tx, msg_rx) = crossbeam_channel::unbounded::<Msg>();
-
+        let dialogs: Arc<dyn DialogProvider> = Arc::new(RfdDialogs);
         let threads = std::thread::available_parallelism()
             .map(|n| n.get().max(2))
             .unwrap_or(2);
-        for _ in 0..threads {
-            let cmd_rx = cmd_rx.clone();
-            let msg_tx = msg_tx.clone();
-            std::thread::spawn(move || {
-                for cmd in cmd_rx.iter() {
-                    let msg = mvu::run_command(cmd);
-                    let _ = msg_tx.send(msg);
-                }
-            });
-        }
+        let workers = Workers::spawn(threads, Arc::clone(&dialogs));
 
         Self {
             model: AppModel {
-                archive_genre: ArchiveGenre::Experiment,
+                entry: mvu::EntryModel {
+                    genre: ArchiveGenre::Experiment,
+                    ..Default::default()
+                },
                 body_format: crate::logic::eln::BodyFormat::Html,
                 ..Default::default()
             },
             inbox: Vec::new(),
-            cmd_tx,
-            msg_rx,
+            workers,
+            dialogs,
             thumbnail_textures: HashMap::new(),
             pending_thumbnail_images: Vec::new(),
             active_thumbnail_requests: HashMap::new(),
             next_thumbnail_request_id: 1,
+            pending_preview_image: None,
+            preview_texture: None,
+            active_hash_cancels: HashMap::new(),
+            active_save_cancel: None,
+            active_save_answer: None,
+            active_upload_cancel: None,
+            last_draft_snapshot: None,
+            last_draft_check: Instant::now(),
+            theme_applied: false,
+            last_window_check: Instant::now(),
+            paths: AppPaths::default(),
+            safe_mode_shift_deadline: None,
+            font_probe: fonts::DEFAULT_PROBE.to_owned(),
+        }
+    }
+}
+
+impl ElnPackApp {
+    /// Create the app with persistence at `paths`, loading settings and any saved draft.
+    ///
+    /// A draft that cannot be read is reported in the status bar and left untouched until
+    /// the next autosave replaces it; unreadable settings fall back to defaults. In
+    /// `safe_mode` nothing is loaded; see [`mvu::startup`].
+    pub fn new(paths: AppPaths, safe_mode: bool) -> Self {
+        let mut app = Self::default();
+        if safe_mode {
+            mvu::startup::start_safe(&mut app.model, paths);
+        } else {
+            let first_run = paths.settings.as_deref().is_some_and(|path| !path.exists());
+            mvu::startup::load_all(&mut app.model, &paths, &mut app.inbox);
+            // The form as loaded counts as persisted, so autosave does not delete a draft
+            // that is still being restored as empty.
+            app.last_draft_snapshot = serde_json::to_string(&mvu::snapshot_draft(&app.model)).ok();
+            if first_run {
+                // Processed last, so the setup is prefilled from the loaded authors.
+                app.inbox.insert(0, Msg::OpenOnboarding);
+            }
+            app.paths = paths;
+            app.safe_mode_shift_deadline = Some(Instant::now() + SAFE_MODE_SHIFT_WINDOW);
+        }
+        app.model.paste_dir =
+            Some(std::env::temp_dir().join(format!("elnpack-pasted-{}", std::process::id())));
+        app
+    }
+}
+
+impl ElnPackApp {
+    /// Record recent messages so they can be exported for a bug report.
+    pub fn enable_event_trace(&mut self) {
+        self.model.event_trace = Some(mvu::trace::EventTrace::default());
+    }
+
+    /// Start over in safe mode when Shift is held while the window opens.
+    ///
+    /// Loaded files are only applied by the first update, and nothing is written back that
+    /// early, so restarting leaves them as they were.
+    fn enter_safe_mode_on_shift(&mut self, ctx: &egui::Context) {
+        let Some(deadline) = self.safe_mode_shift_deadline else {
+            return;
+        };
+        if Instant::now() > deadline {
+            self.safe_mode_shift_deadline = None;
+            return;
+        }
+        if !ctx.input(|input| input.modifiers.shift) {
+            return;
+        }
+        let trace = self.model.event_trace.is_some();
+        *self = Self::new(std::mem::take(&mut self.paths), true);
+        if trace {
+            self.enable_event_trace();
+        }
+    }
+}
+
+impl Drop for ElnPackApp {
+    /// Delete pasted images and the files of opened archives; they are only needed until
+    /// the archive is written.
+    fn drop(&mut self) {
+        let pending = self
+            .model
+            .open_confirmation
+            .as_ref()
+            .map(|opened| &opened.extract_dir);
+        for dir in [
+            self.model.paste_dir.as_ref(),
+            self.model.open_dir.as_ref(),
+            pending,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            let _ = std::fs::remove_dir_all(dir);
         }
     }
 }
@@ -77,32 +227,111 @@ impl eframe::App for ElnPackApp {
     ///
     fn logic(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         self.ensure_spacing(ctx);
+        self.enter_safe_mode_on_shift(ctx);
         self.process_runtime_messages();
+        i18n::set_language(self.model.settings.settings().appearance.language);
+        self.sync_theme(ctx);
+        self.remember_window(ctx);
+        self.detect_image_paste(ctx);
+        self.autosave_draft();
+        self.handle_close_request(ctx);
+        if self.model.entry.attachments.has_pending_hashes()
+            || self
+                .model
+                .batch
+                .as_ref()
+                .is_some_and(|batch| !batch.finished())
+        {
+            // Keep progress moving while workers report hashing and batch progress.
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
     }
 
     /// Main application UI pass for the root viewport.
     fn ui(&mut self, ui: &mut egui::Ui, _frame: &mut eframe::Frame) {
         self.realize_pending_thumbnail_textures(ui.ctx());
+        self.realize_preview_texture(ui.ctx());
         self.process_runtime_messages();
+        let layout = self.layout();
 
         egui::Panel::top("top_bar").show_inside(ui, |ui| {
             ui.add_space(6.0);
             ui.horizontal(|ui| {
-                ui.heading("ELN Entry");
+                ui.heading(t!("app.heading"));
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     self.render_theme_controls(ui);
+                    self.render_compact_toggle(ui, &layout);
+                    self.render_language_menu(ui);
                     ui.separator();
-                    self.render_help_button(ui);
+                    self.render_help_menu(ui, &layout);
+                    self.render_settings_button(ui, &layout);
                     ui.separator();
-                    self.render_save_button(ui);
+                    self.render_crate_version_menu(ui);
+                    self.render_preview_toggle(ui, &layout);
+                    self.render_upload_toggle(ui, &layout);
+                    self.render_compression_menu(ui);
+                    self.render_recent_archives_menu(ui);
+                    self.render_save_button(ui, &layout);
+                    self.render_batch_button(ui, &layout);
+                    self.render_open_button(ui, &layout);
+                    self.render_templates_menu(ui, &layout);
+                    self.render_archive_favorites(ui);
                     ui.separator();
-                    self.render_body_format_toggle(ui);
+                    self.render_body_format_toggle(ui, &layout);
                 });
             });
             ui.add_space(4.0);
         });
 
+        if self.model.safe_mode.is_some() {
+            egui::Panel::top("safe_mode_notice").show_inside(ui, |ui| {
+                self.render_safe_mode_notice(ui);
+            });
+        }
+        if self.model.draft_restored {
+            egui::Panel::top("draft_notice").show_inside(ui, |ui| {
+                self.render_draft_notice(ui);
+            });
+        }
+
         self.render_error_modal(ui.ctx());
+        self.render_date_confirmation(ui.ctx());
+        self.render_size_confirmation(ui.ctx());
+        self.render_missing_file_prompt(ui.ctx());
+        self.render_template_confirmation(ui.ctx());
+        self.render_open_confirmation(ui.ctx());
+        self.render_overwrite_confirmation(ui.ctx());
+        self.render_batch_window(ui.ctx());
+        self.render_close_confirmation(ui.ctx());
+        self.render_font_coverage(ui.ctx());
+        let preview = self.model.entry.attachments.preview_path();
+        if self
+            .preview_texture
+            .as_ref()
+            .is_some_and(|(path, _)| Some(path.as_path()) != preview)
+        {
+            self.preview_texture = None;
+        }
+        let preview_msgs = attachments::preview_window(
+            ui.ctx(),
+            &self.model.entry.attachments,
+            self.preview_texture.as_ref().map(|(_, texture)| texture),
+        );
+        self.inbox
+            .extend(preview_msgs.into_iter().map(Msg::Attachments));
+        let template_msgs = templates::view(
+            ui.ctx(),
+            &self.model.templates,
+            &self.model.settings.settings().units,
+        );
+        self.inbox
+            .extend(template_msgs.into_iter().map(Msg::Templates));
+        let settings_msgs = settings::view(ui.ctx(), &self.model.settings);
+        self.inbox
+            .extend(settings_msgs.into_iter().map(Msg::Settings));
+        let onboarding_msgs = onboarding::view(ui.ctx(), &self.model.onboarding);
+        self.inbox
+            .extend(onboarding_msgs.into_iter().map(Msg::Onboarding));
 
         egui::Panel::bottom("status_panel")
             .resizable(false)
@@ -110,49 +339,103 @@ impl eframe::App for ElnPackApp {
                 self.render_status(ui);
             });
 
+        egui::Panel::left("entries_panel")
+            .resizable(true)
+            .show_inside(ui, |ui| {
+                self.render_entries_panel(ui);
+            });
+
         egui::CentralPanel::default().show_inside(ui, |ui| {
             ui.add_space(8.0);
+            egui::ScrollArea::vertical().show(ui, |ui| self.render_entry_form(ui, &layout));
+        });
+    }
+}
 
-            egui::ScrollArea::vertical().show(ui, |ui| {
-                self.render_title_input(ui);
-                ui.add_space(12.0);
+impl ElnPackApp {
+    /// Render the form of the active entry.
+    ///
+    /// Tab follows the order of rendering: title, summary, entry type, date/time, language,
+    /// license, the body's toolbar and editor, keywords, related links, metadata,
+    /// attachments, and the authors. Keep new widgets in this reading order.
+    fn render_entry_form(&mut self, ui: &mut egui::Ui, layout: &LayoutParams) {
+        self.render_title_input(ui);
+        ui.add_space(layout.section_gap);
+
+        if layout.inline_meta {
+            self.render_meta_row(ui);
+        } else {
+            self.render_meta_group(ui);
+        }
+        ui.add_space(layout.section_gap);
+
+        self.render_description_input(ui, layout);
+        ui.add_space(layout.section_gap);
+
+        let ctx = ui.ctx().clone();
+        let derived = added_keywords(
+            self.model.entry.keywords.keywords(),
+            &derived_keywords(
+                self.model.entry.extra_fields.fields(),
+                &self.model.settings.settings().keywords,
+            ),
+        );
+        let kw_msgs = keywords::view(ui, &ctx, &self.model.entry.keywords, &derived);
+        self.inbox.extend(kw_msgs.into_iter().map(Msg::Keywords));
+        ui.add_space(layout.section_gap);
 
-                self.render_meta_group(ui);
-                ui.add_space(12.0);
+        let link_msgs = related_links::view(ui, &self.model.entry.related_links);
+        self.inbox
+            .extend(link_msgs.into_iter().map(Msg::RelatedLinks));
+        ui.add_space(layout.section_gap);
 
-                self.render_description_input(ui);
-                ui.add_space(12.0);
+        self.render_extra_fields_section(ui);
+        ui.add_space(layout.section_gap);
 
-                let ctx = ui.ctx().clone();
-                let kw_msgs = keywords::view(ui, &ctx, &self.model.keywords);
-                self.inbox.extend(kw_msgs.into_iter().map(Msg::Keywords));
-                ui.add_space(12.0);
+        self.render_attachments_section(ui);
+        ui.add_space(layout.section_gap);
 
-                self.render_extra_fields_section(ui);
-                ui.add_space(12.0);
+        self.render_summary_section(ui);
+        ui.add_space(layout.section_gap);
 
-                self.render_attachments_section(ui);
-                ui.add_space(8.0);
-            });
-        });
+        let author_msgs = authors::view(ui, &self.model.authors);
+        self.inbox.extend(author_msgs.into_iter().map(Msg::Authors));
+        ui.add_space(8.0);
+    }
+
+    /// Layout parameters for the persisted regular/compact mode.
+    fn layout(&self) -> LayoutParams {
+        LayoutParams::for_mode(self.model.settings.settings().appearance.compact_mode)
     }
-}
 
-impl ElnPackApp {
     fn ensure_spacing(&self, ctx: &egui::Context) {
+        let item_spacing = self.layout().item_spacing;
         ctx.global_style_mut(|style| {
-            style.spacing.item_spacing = egui::vec2(6.0, 6.0);
+            style.spacing.item_spacing = item_spacing;
         });
     }
 
     fn process_runtime_messages(&mut self) {
-        while let Ok(msg) = self.msg_rx.try_recv() {
+        while let Ok(msg) = self.workers.results.try_recv() {
             self.model.pending_commands = self.model.pending_commands.saturating_sub(1);
+            if let Msg::Attachments(attachments::AttachmentsMsg::HashComputed { path, .. }) = &msg {
+                self.active_hash_cancels.remove(path);
+            }
+            self.inbox.push(msg);
+        }
+        while let Ok(msg) = self.workers.dialog_results.try_recv() {
+            self.model.open_dialogs = self.model.open_dialogs.saturating_sub(1);
+            self.inbox.push(msg);
+        }
+        while let Ok(msg) = self.workers.progress.try_recv() {
             self.inbox.push(msg);
         }
 
         let mut msgs = std::mem::take(&mut self.inbox);
         while let Some(msg) = msgs.pop() {
+            if let Some(trace) = self.model.event_trace.as_mut() {
+                trace.record(&msg);
+            }
             match msg {
                 mvu::Msg::ThumbnailDecoded {
                     path,
@@ -162,7 +445,14 @@ impl ElnPackApp {
                     self.pending_thumbnail_images
                         .push((path, request_id, image));
                 }
-                mvu::Msg::ThumbnailFailed { path, request_id } => {
+                mvu::Msg::PreviewImageDecoded { path, image } => {
+                    self.pending_preview_image = Some((path, image));
+                }
+                mvu::Msg::ThumbnailFailed {
+                    path,
+                    request_id,
+                    reason,
+                } => {
                     if self.active_thumbnail_requests.get(&path).copied() != Some(request_id) {
                         continue;
                     }
@@ -170,7 +460,10 @@ impl ElnPackApp {
                     let mut commands = Vec::new();
                     mvu::update(
                         &mut self.model,
-                        Msg::Attachments(attachments::AttachmentsMsg::ThumbnailFailed { path }),
+                        Msg::Attachments(attachments::AttachmentsMsg::ThumbnailFailed {
+                            path,
+                            reason,
+                        }),
                         &mut commands,
                     );
                     self.dispatch_commands(commands);
@@ -178,6 +471,7 @@ impl ElnPackApp {
                 Msg::Attachments(attachments::AttachmentsMsg::LoadThumbnail(path)) => {
                     if !self
                         .model
+                        .entry
                         .attachments
                         .attachments()
                         .iter()
@@ -198,6 +492,7 @@ impl ElnPackApp {
                     if let Msg::Attachments(attachments::AttachmentsMsg::Remove(index)) = &other
                         && let Some(path) = self
                             .model
+                            .entry
                             .attachments
                             .attachments()
                             .get(*index)
@@ -215,9 +510,17 @@ impl ElnPackApp {
     }
 
     fn realize_pending_thumbnail_textures(&mut self, ctx: &egui::Context) {
-        for (path, request_id, image) in std::mem::take(&mut self.pending_thumbnail_images) {
+        let mut queue = std::mem::take(&mut self.pending_thumbnail_images).into_iter();
+        let mut uploaded = 0;
+        for (path, request_id, image) in queue.by_ref() {
+            if uploaded == THUMBNAIL_UPLOADS_PER_FRAME {
+                self.pending_thumbnail_images
+                    .push((path, request_id, image));
+                break;
+            }
             if !self
                 .model
+                .entry
                 .attachments
                 .attachments()
                 .iter()
@@ -239,12 +542,42 @@ impl ElnPackApp {
             self.inbox.push(Msg::Attachments(
                 attachments::AttachmentsMsg::ThumbnailAvailable { path },
             ));
+            uploaded += 1;
+        }
+
+        // Stale entries are dropped only when reached; the budget counts uploads, not checks.
+        self.pending_thumbnail_images.extend(queue);
+        if !self.pending_thumbnail_images.is_empty() {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Upload the staged preview image if its dialog is still open for the same file.
+    fn realize_preview_texture(&mut self, ctx: &egui::Context) {
+        let Some((path, image)) = self.pending_preview_image.take() else {
+            return;
+        };
+        if self.model.entry.attachments.preview_path() != Some(path.as_path()) {
+            return;
         }
+        let texture = ctx.load_texture(
+            format!("preview-{}", path.display()),
+            image,
+            egui::TextureOptions::default(),
+        );
+        self.preview_texture = Some((path.clone(), texture));
+        self.inbox.push(Msg::Attachments(
+            attachments::AttachmentsMsg::PreviewReady {
+                path,
+                content: Ok(attachments::PreviewContent::Image),
+            },
+        ));
     }
 
     fn prune_thumbnail_textures(&mut self) {
         let paths: std::collections::HashSet<PathBuf> = self
             .model
+            .entry
             .attachments
             .attachments()
             .iter()
@@ -265,8 +598,48 @@ impl ElnPackApp {
             .retain(|(pending_path, _, _)| pending_path != path);
     }
 
+    /// Persist the current entry as a draft when it changed since the last write.
+    ///
+    /// Checks are throttled to [`DRAFT_AUTOSAVE_INTERVAL`]; empty entries are never written.
+    fn autosave_draft(&mut self) {
+        if self.last_draft_check.elapsed() < DRAFT_AUTOSAVE_INTERVAL {
+            return;
+        }
+        self.last_draft_check = Instant::now();
+        self.write_draft_if_changed();
+    }
+
+    fn write_draft_if_changed(&mut self) {
+        let Some(path) = self.model.draft_path.clone() else {
+            return;
+        };
+        let draft = mvu::snapshot_draft(&self.model);
+        let Ok(serialized) = serde_json::to_string(&draft) else {
+            return;
+        };
+        if self.last_draft_snapshot.as_deref() == Some(serialized.as_str()) {
+            return;
+        }
+        if draft.is_empty() {
+            // The form was cleared; the old draft would come back on the next start.
+            self.dispatch_commands(vec![Command::DeleteDraft { path }]);
+        } else if let Err(err) = crate::logic::draft::save_draft(&path, &draft) {
+            mvu::surface_event(
+                &mut self.model,
+                t!("draft.autosave.failed", err = format!("{err:#}")),
+                Severity::Warning,
+            );
+        }
+        self.last_draft_snapshot = Some(serialized);
+    }
+
     fn dispatch_commands(&mut self, commands: Vec<Command>) {
         for cmd in commands {
+            if matches!(cmd, Command::DeleteDraft { .. }) {
+                // Treat the current state as persisted so autosave does not resurrect the draft.
+                self.last_draft_snapshot =
+                    serde_json::to_string(&mvu::snapshot_draft(&self.model)).ok();
+            }
             match cmd {
                 Command::LoadThumbnail {
                     path,
@@ -280,85 +653,646 @@ impl ElnPackApp {
                         _retry,
                         request_id,
                     };
-                    if self.cmd_tx.send(cmd).is_ok() {
+                    if self.workers.send(cmd).is_some() {
                         self.next_thumbnail_request_id += 1;
                         self.active_thumbnail_requests
                             .insert(tracked_path, request_id);
                         self.model.pending_commands += 1;
                     }
                 }
+                Command::CancelHash { path } => {
+                    if let Some(cancel) = self.active_hash_cancels.remove(&path) {
+                        cancel.store(true, Ordering::Relaxed);
+                    }
+                }
+                Command::CancelSave => {
+                    if let Some(cancel) = self.active_save_cancel.take() {
+                        cancel.store(true, Ordering::Relaxed);
+                    }
+                }
+                Command::CancelUpload => {
+                    if let Some(cancel) = self.active_upload_cancel.take() {
+                        cancel.store(true, Ordering::Relaxed);
+                    }
+                }
+                Command::ResolveMissingFile(action) => {
+                    if let Some(mut answer) = self
+                        .active_save_answer
+                        .as_ref()
+                        .and_then(|answer| answer.lock().ok())
+                    {
+                        *answer = Some(action);
+                    }
+                }
                 other => {
-                    if self.cmd_tx.send(other).is_ok() {
-                        self.model.pending_commands += 1;
+                    let hash_cancel = match &other {
+                        Command::HashFile { path, cancel, .. } => {
+                            Some((path.clone(), Arc::clone(cancel)))
+                        }
+                        _ => None,
+                    };
+                    let save_slots = match &other {
+                        Command::SaveArchive(payload) => Some((
+                            Arc::clone(&payload.cancel),
+                            Arc::clone(&payload.missing_file_answer),
+                        )),
+                        _ => None,
+                    };
+                    let upload_cancel = match &other {
+                        Command::UploadArchive { cancel, .. } => Some(Arc::clone(cancel)),
+                        _ => None,
+                    };
+                    match self.workers.send(other) {
+                        Some(CommandKind::Dialog) => self.model.open_dialogs += 1,
+                        Some(CommandKind::Background) => {
+                            self.model.pending_commands += 1;
+                            if let Some((path, cancel)) = hash_cancel {
+                                self.active_hash_cancels.insert(path, cancel);
+                            }
+                            if let Some((cancel, answer)) = save_slots {
+                                self.active_save_cancel = Some(cancel);
+                                self.active_save_answer = Some(answer);
+                            }
+                            if let Some(cancel) = upload_cancel {
+                                self.active_upload_cancel = Some(cancel);
+                            }
+                        }
+                        None => {}
                     }
                 }
             }
         }
     }
 
+    /// Attach the clipboard image on Ctrl+V (Cmd+V) while no text field has focus.
+    ///
+    /// egui only forwards pasted text, so the release of V is the one event an image paste
+    /// leaves behind. Text fields such as the Markdown editor keep handling their own pastes.
+    fn detect_image_paste(&mut self, ctx: &egui::Context) {
+        let pasted = ctx.input(|i| {
+            i.events.iter().any(|event| {
+                matches!(
+                    event,
+                    egui::Event::Key {
+                        key: egui::Key::V,
+                        pressed: false,
+                        modifiers,
+                        ..
+                    } if modifiers.command
+                )
+            })
+        });
+        if pasted && ctx.memory(|mem| mem.focused().is_none()) {
+            self.inbox.push(Msg::PasteImage);
+        }
+    }
+
     /// Render the global theme preference control with a small vertical gap.
     ///
     /// Adds a 2.0-point vertical spacer, then inserts egui's built-in global theme
     /// preference switch into the provided UI.
-    ///
     fn render_theme_controls(&mut self, ui: &mut egui::Ui) {
         ui.add_space(2.0);
         egui::widgets::global_theme_preference_switch(ui);
     }
 
-    /// Render a compact help button that opens the hosted user guide in a browser tab.
-    fn render_help_button(&mut self, ui: &mut egui::Ui) {
+    /// Apply the stored theme once, then persist whenever the theme switch changes it.
+    fn sync_theme(&mut self, ctx: &egui::Context) {
+        let stored = theme_preference(self.model.settings.settings().appearance.theme);
+        if !self.theme_applied {
+            self.theme_applied = true;
+            ctx.set_theme(stored);
+            return;
+        }
+        let current = ctx.options(|o| o.theme_preference);
+        if current != stored {
+            self.inbox
+                .push(Msg::Settings(SettingsMsg::SetTheme(theme_setting(current))));
+        }
+    }
+
+    /// Persist the window size and maximized state when they changed.
+    ///
+    /// Checks are throttled to [`DRAFT_AUTOSAVE_INTERVAL`] so dragging a window edge does
+    /// not write settings on every frame. The size is kept while maximized so restoring
+    /// the window later reopens it at its previous size.
+    fn remember_window(&mut self, ctx: &egui::Context) {
+        if self.last_window_check.elapsed() < DRAFT_AUTOSAVE_INTERVAL {
+            return;
+        }
+        self.last_window_check = Instant::now();
+
+        let (inner_rect, maximized, minimized) = ctx.input(|i| {
+            let viewport = i.viewport();
+            (viewport.inner_rect, viewport.maximized, viewport.minimized)
+        });
+        if minimized == Some(true) {
+            return;
+        }
+        let stored = &self.model.settings.settings().window;
+        let mut window = stored.clone();
+        window.maximized = maximized.unwrap_or(false);
+        if !window.maximized
+            && let Some(rect) = inner_rect
+        {
+            window.width = rect.width().round() as u32;
+            window.height = rect.height().round() as u32;
+        }
+        if window != *stored {
+            self.inbox
+                .push(Msg::Settings(SettingsMsg::SetWindow(window)));
+        }
+    }
+
+    /// Render the toggle that switches between the regular and compact layout.
+    fn render_compact_toggle(&mut self, ui: &mut egui::Ui, layout: &LayoutParams) {
+        let compact = layout.icon_only_toolbar;
+        let button =
+            egui::Button::new(egui_phosphor::regular::ARROWS_IN_LINE_VERTICAL).selected(compact);
+        let response = ui.add(button);
+        response.widget_info(|| {
+            egui::WidgetInfo::selected(
+                egui::WidgetType::Button,
+                response.enabled(),
+                compact,
+                t!("top.compact.hover"),
+            )
+        });
+        if response.on_hover_text(t!("top.compact.hover")).clicked() {
+            self.inbox
+                .push(Msg::Settings(SettingsMsg::SetCompactMode(!compact)));
+        }
+    }
+
+    /// Render the selector for the language of the user interface.
+    fn render_language_menu(&mut self, ui: &mut egui::Ui) {
+        let current = self.model.settings.settings().appearance.language;
+        let mut selected = current;
+        egui::ComboBox::from_id_salt("ui_language")
+            .selected_text(current.native_name())
+            .show_ui(ui, |ui| {
+                for language in Language::ALL {
+                    ui.selectable_value(&mut selected, language, language.native_name());
+                }
+            })
+            .response
+            .on_hover_text(t!("top.language.hover"));
+        if selected != current {
+            self.inbox
+                .push(Msg::Settings(SettingsMsg::SetLanguage(selected)));
+        }
+    }
+
+    /// Render the help menu with the hosted user guide and the first-run setup.
+    fn render_help_menu(&mut self, ui: &mut egui::Ui, layout: &LayoutParams) {
         ui.add_space(2.0);
-        let button = egui::Button::new(format!("{} Help", egui_phosphor::regular::QUESTION));
+        ui.menu_button(
+            layout.toolbar_text(egui_phosphor::regular::QUESTION, t!("help.menu")),
+            |ui| {
+                if ui
+                    .button(format!(
+                        "{} {}",
+                        egui_phosphor::regular::BOOK_OPEN,
+                        t!("help.guide")
+                    ))
+                    .on_hover_text(t!("help.guide.hover"))
+                    .clicked()
+                {
+                    self.inbox.push(Msg::OpenHelp);
+                    ui.close();
+                }
+                if ui
+                    .button(format!(
+                        "{} {}",
+                        egui_phosphor::regular::HAND_WAVING,
+                        t!("help.setup")
+                    ))
+                    .on_hover_text(t!("help.setup.hover"))
+                    .clicked()
+                {
+                    self.inbox.push(Msg::OpenOnboarding);
+                    ui.close();
+                }
+                if self.model.event_trace.is_some()
+                    && ui
+                        .button(format!(
+                            "{} {}",
+                            egui_phosphor::regular::BUG,
+                            t!("help.trace")
+                        ))
+                        .on_hover_text(t!("help.trace.hover"))
+                        .clicked()
+                {
+                    ui.close();
+                    if let Some(path) = self.dialogs.save_file(
+                        t!("help.trace.dialog"),
+                        None,
+                        JSON_FILTER,
+                        "elnpack-events.json",
+                    ) {
+                        self.inbox.push(Msg::ExportEventTrace(path));
+                    }
+                }
+            },
+        )
+        .response
+        .on_hover_text(t!("help.menu.hover"));
+    }
+
+    /// Render a compact button that opens the settings window.
+    fn render_settings_button(&mut self, ui: &mut egui::Ui, layout: &LayoutParams) {
+        let button = egui::Button::new(
+            layout.toolbar_text(egui_phosphor::regular::GEAR, t!("settings.button")),
+        );
         if ui
             .add(button)
-            .on_hover_text("Open the ELNPack user guide")
+            .on_hover_text(t!("settings.button.hover"))
             .clicked()
         {
-            self.inbox.push(Msg::OpenHelp);
+            self.inbox.push(Msg::Settings(SettingsMsg::Open));
         }
     }
 
-    /// Renders the "Save ELN archive" button and, when activated, opens a file-save dialog to request saving the current entry.
-    ///
-    /// The button is enabled only when the entry title is not empty and there are no invalid extra fields. When the user selects a file the chosen path is normalized to have the `.eln` extension and a `Msg::SaveRequested(path)` is queued; if the dialog is cancelled a `Msg::SaveCancelled` is queued.
-    ///
-    fn render_save_button(&mut self, ui: &mut egui::Ui) {
-        let save_enabled = !self.model.entry_title.trim().is_empty()
-            && !self.model.extra_fields.has_invalid_fields();
-        let button = egui::Button::new(format!(
-            "{} Save ELN archive",
-            egui_phosphor::regular::FLOPPY_DISK
-        ));
+    /// Render the checkbox that adds an HTML preview to saved archives.
+    fn render_preview_toggle(&mut self, ui: &mut egui::Ui, layout: &LayoutParams) {
+        let mut include = self.model.settings.settings().archive.include_preview;
+        if ui
+            .checkbox(
+                &mut include,
+                layout.toolbar_text(egui_phosphor::regular::BROWSER, t!("top.preview")),
+            )
+            .on_hover_text(t!("top.preview.hover"))
+            .changed()
+        {
+            self.inbox
+                .push(Msg::Settings(SettingsMsg::SetIncludePreview(include)));
+        }
+    }
+
+    /// Render the per-save upload toggle when uploading to eLabFTW is set up.
+    fn render_upload_toggle(&mut self, ui: &mut egui::Ui, layout: &LayoutParams) {
+        if self.model.settings.upload_target().is_none() {
+            return;
+        }
+        let mut upload = !self.model.skip_upload;
+        if ui
+            .checkbox(
+                &mut upload,
+                layout.toolbar_text(egui_phosphor::regular::CLOUD_ARROW_UP, t!("top.upload")),
+            )
+            .on_hover_text(t!("top.upload.hover"))
+            .changed()
+        {
+            self.inbox.push(Msg::SetUploadOnSave(upload));
+        }
+    }
+
+    /// Render the compression selector of the next save, which starts at the settings' method.
+    fn render_compression_menu(&mut self, ui: &mut egui::Ui) {
+        let current = self
+            .model
+            .compression
+            .unwrap_or(self.model.settings.settings().archive.compression.method);
+        let mut selected = current;
+        egui::ComboBox::from_id_salt("save_compression")
+            .selected_text(current.label())
+            .show_ui(ui, |ui| {
+                for method in Compression::ALL {
+                    ui.selectable_value(&mut selected, method, method.label());
+                }
+            })
+            .response
+            .on_hover_text(t!("top.compression.hover"));
+        if selected != current {
+            self.inbox.push(Msg::SetSaveCompression(selected));
+        }
+    }
+
+    /// Render the RO-Crate version selector applied to the next save.
+    fn render_crate_version_menu(&mut self, ui: &mut egui::Ui) {
+        let current = self.model.settings.settings().archive.crate_version;
+        let mut selected = current;
+        egui::ComboBox::from_id_salt("crate_version")
+            .selected_text(current.label())
+            .show_ui(ui, |ui| {
+                for version in RoCrateVersion::ALL {
+                    ui.selectable_value(&mut selected, version, version.compatibility());
+                }
+            })
+            .response
+            .on_hover_text(t!(
+                "top.crate_version.hover",
+                version = current.compatibility()
+            ));
+        if selected != current {
+            self.inbox
+                .push(Msg::Settings(SettingsMsg::SetCrateVersion(selected)));
+        }
+    }
+
+    /// Render the favorite-folder menu shared by the save and open dialogs.
+    fn render_archive_favorites(&mut self, ui: &mut egui::Ui) {
+        let msgs = settings::favorite_menu(ui, &self.model.settings, PickerKind::Archive);
+        self.inbox.extend(msgs.into_iter().map(Msg::Settings));
+    }
 
+    /// Render the template menu; hidden when templates cannot be stored.
+    fn render_templates_menu(&mut self, ui: &mut egui::Ui, layout: &LayoutParams) {
+        if self.model.templates_path.is_none() {
+            return;
+        }
+        let msgs = templates::menu(
+            ui,
+            &self.model.templates,
+            layout.toolbar_text(egui_phosphor::regular::STACK, t!("top.templates")),
+        );
+        self.inbox.extend(msgs.into_iter().map(Msg::Templates));
+    }
+
+    /// Render the button that packages a folder of entry subfolders into one archive each.
+    fn render_batch_button(&mut self, ui: &mut egui::Ui, layout: &LayoutParams) {
+        let button = egui::Button::new(
+            layout.toolbar_text(egui_phosphor::regular::FOLDERS, t!("top.batch")),
+        );
         if ui
-            .add_enabled(save_enabled, button)
-            .on_disabled_hover_text("Please enter a title and fix required/invalid fields")
+            .add(button)
+            .on_hover_text(t!("top.batch.hover"))
             .clicked()
         {
-            let default_name = suggested_archive_name(&self.model.entry_title);
-            let dialog = rfd::FileDialog::new()
-                .set_title("Save ELN archive")
-                .add_filter("ELN archive", &["eln"])
-                .set_file_name(&default_name);
-
-            if let Some(path) = dialog.save_file() {
-                let output_path = ensure_extension(path, "eln");
-                self.inbox.push(Msg::SaveRequested(output_path));
+            self.inbox.push(Msg::BatchRequested);
+        }
+    }
+
+    /// Render the "Open archive" button; a picked file is queued as `Msg::OpenArchiveRequested`.
+    fn render_open_button(&mut self, ui: &mut egui::Ui, layout: &LayoutParams) {
+        let button = egui::Button::new(
+            layout.toolbar_text(egui_phosphor::regular::FOLDER_OPEN, t!("top.open")),
+        );
+        if ui.add(button).on_hover_text(t!("top.open.hover")).clicked() {
+            let start_dir = mvu::favorite_dir(&self.model, PickerKind::Archive);
+            let picked = self.dialogs.pick_file_with_filter(
+                t!("top.open.dialog"),
+                start_dir.as_deref(),
+                ELN_FILTER,
+            );
+
+            if let Some(path) = picked {
+                self.inbox.push(Msg::OpenArchiveRequested(path));
             } else {
-                self.inbox.push(Msg::SaveCancelled);
+                self.inbox.push(Msg::OpenArchiveCancelled);
+            }
+        }
+    }
+
+    /// Renders the "Save ELN archive" button and, when activated, opens a file-save dialog to request saving the current entry.
+    ///
+    /// The button is enabled only when the entry title is not empty and there are no invalid extra fields. When the user selects a file the chosen path is normalized to have the `.eln` extension and a `Msg::SaveRequested(path)` is queued; if the dialog is cancelled a `Msg::SaveCancelled` is queued. While a save runs, the button cancels it instead.
+    ///
+    /// After a save, the button splits into "Save", which queues `Msg::ResaveRequested` for the
+    /// same path, and "Save As…", which opens the dialog.
+    ///
+    fn render_save_button(&mut self, ui: &mut egui::Ui, layout: &LayoutParams) {
+        if self.model.saving {
+            let button = egui::Button::new(
+                layout.toolbar_text(egui_phosphor::regular::X, t!("save.cancel")),
+            );
+            if ui
+                .add(button)
+                .on_hover_text(t!("save.cancel.hover"))
+                .clicked()
+            {
+                self.inbox.push(Msg::CancelSave);
+            }
+            return;
+        }
+        let Some(last_saved) = self.model.last_saved.clone() else {
+            let button = egui::Button::new(
+                layout.toolbar_text(egui_phosphor::regular::FLOPPY_DISK, t!("save.button")),
+            );
+            let response = ui
+                .add_enabled(self.save_enabled(), button)
+                .on_hover_text(t!("save.button.hover"))
+                .on_disabled_hover_text(t!("save.disabled_hint"));
+            if response.clicked() {
+                self.request_save();
+            } else if clicked_while_disabled(ui, &response) {
+                self.inbox.push(Msg::SaveBlocked);
+            }
+            return;
+        };
+
+        // Laid out right to left: "Save As…" ends up to the right of "Save".
+        let save_as = egui::Button::new(
+            layout.toolbar_text(egui_phosphor::regular::FLOPPY_DISK_BACK, t!("save.save_as")),
+        );
+        let response = ui
+            .add_enabled(self.save_enabled(), save_as)
+            .on_hover_text(t!("save.save_as.hover"))
+            .on_disabled_hover_text(t!("save.disabled_hint"));
+        if response.clicked() {
+            self.request_save();
+        } else if clicked_while_disabled(ui, &response) {
+            self.inbox.push(Msg::SaveBlocked);
+        }
+        let save = egui::Button::new(
+            layout.toolbar_text(egui_phosphor::regular::FLOPPY_DISK, t!("save.save")),
+        );
+        let response = ui
+            .add_enabled(self.save_enabled(), save)
+            .on_hover_text(t!("save.save.hover", path = last_saved.display()))
+            .on_disabled_hover_text(t!("save.disabled_hint"));
+        if response.clicked() {
+            self.inbox.push(Msg::ResaveRequested(last_saved));
+        } else if clicked_while_disabled(ui, &response) {
+            self.inbox.push(Msg::SaveBlocked);
+        }
+    }
+
+    /// Render the menu of recently saved archives; picking one offers to overwrite it.
+    ///
+    /// Archives that no longer exist are greyed out and can be removed from the list.
+    fn render_recent_archives_menu(&mut self, ui: &mut egui::Ui) {
+        let recent = &self.model.settings.settings().recent_archives.paths;
+        if recent.is_empty() {
+            return;
+        }
+        let save_enabled = self.save_enabled();
+        let mut msgs = Vec::new();
+        ui.menu_button(egui_phosphor::regular::CLOCK_COUNTER_CLOCKWISE, |ui| {
+            ui.label(egui::RichText::new(t!("recent.heading")).weak());
+            let mut missing = Vec::new();
+            for path in recent {
+                let exists = path.exists();
+                if !exists {
+                    missing.push(path.clone());
+                }
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.display().to_string());
+                let response = ui
+                    .add_enabled(exists && save_enabled, egui::Button::new(name))
+                    .on_hover_text(path.display().to_string())
+                    .on_disabled_hover_text(if exists {
+                        format!("{}\n\n{}", path.display(), t!("save.disabled_hint"))
+                    } else {
+                        format!("{}\n\n{}", path.display(), t!("recent.missing"))
+                    });
+                if response.clicked() {
+                    msgs.push(Msg::ResaveRequested(path.clone()));
+                    ui.close();
+                }
+            }
+            if !missing.is_empty() {
+                ui.separator();
+                if ui
+                    .button(format!(
+                        "{} {}",
+                        egui_phosphor::regular::BROOM,
+                        t!("recent.remove_missing")
+                    ))
+                    .clicked()
+                {
+                    msgs.push(Msg::Settings(SettingsMsg::ForgetRecentArchives(missing)));
+                    ui.close();
+                }
+            }
+        })
+        .response
+        .on_hover_text(t!("recent.hover"));
+        self.inbox.extend(msgs);
+    }
+
+    /// Whether the shown entry can be saved: it has a title, valid fields, no attachment
+    /// is still being hashed, and no other save is running.
+    fn save_enabled(&self) -> bool {
+        !self.model.saving
+            && !self.model.entry.title.trim().is_empty()
+            && !self.model.entry.extra_fields.has_invalid_fields()
+            && !self.model.entry.attachments.has_pending_hashes()
+    }
+
+    /// Ask for the archive path and queue the save, or report that the dialog was cancelled.
+    fn request_save(&mut self) {
+        let first_title = mvu::entries(&self.model)
+            .next()
+            .map_or("", |entry| entry.title.as_str());
+        let default_name = suggested_archive_name(first_title);
+        let start_dir = mvu::favorite_dir(&self.model, PickerKind::Archive);
+        let picked = self.dialogs.save_file(
+            t!("save.button"),
+            start_dir.as_deref(),
+            ELN_FILTER,
+            &default_name,
+        );
+
+        if let Some(path) = picked {
+            let output_path = ensure_extension(path, "eln");
+            self.inbox.push(Msg::SaveRequested(output_path));
+        } else {
+            self.inbox.push(Msg::SaveCancelled);
+        }
+    }
+
+    /// Keep the window open while there are unsaved changes or an upload is running, and
+    /// close it once the user decided in the close prompt or the upload finished.
+    fn handle_close_request(&mut self, ctx: &egui::Context) {
+        match self.model.quit {
+            QuitState::Ready => {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                return;
+            }
+            QuitState::AwaitingSave
+            | QuitState::AwaitingDraftDelete
+            | QuitState::AwaitingUpload => {
+                // Poll for the worker result that allows closing.
+                ctx.request_repaint_after(Duration::from_millis(100));
+            }
+            QuitState::Idle | QuitState::Confirming => {}
+        }
+        if ctx.input(|i| i.viewport().close_requested())
+            && (self.model.dirty || self.model.uploading)
+        {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            if self.model.quit == QuitState::Idle {
+                self.inbox.push(Msg::CloseRequested);
             }
         }
     }
 
+    /// Render the list of the archive's entries with add, duplicate, and remove controls.
+    ///
+    /// Switching is disabled while a file dialog is open or background work for the shown
+    /// entry is still running.
+    fn render_entries_panel(&mut self, ui: &mut egui::Ui) {
+        ui.add_space(8.0);
+        ui.strong(t!("entries.heading"));
+        ui.add_space(4.0);
+        let idle = self.model.pending_commands == 0
+            && self.model.open_dialogs == 0
+            && !self.model.entry.attachments.has_pending_hashes();
+        let count = mvu::entry_count(&self.model);
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(idle, egui::Button::new(egui_phosphor::regular::PLUS))
+                .icon_label(t!("entries.add.hover"))
+                .clicked()
+            {
+                self.inbox.push(Msg::AddEntry);
+            }
+            if ui
+                .add_enabled(idle, egui::Button::new(egui_phosphor::regular::COPY))
+                .icon_label(t!("entries.duplicate.hover"))
+                .clicked()
+            {
+                self.inbox.push(Msg::DuplicateEntry);
+            }
+            if ui
+                .add_enabled(
+                    idle && count > 1,
+                    egui::Button::new(egui_phosphor::regular::TRASH),
+                )
+                .icon_label(t!("entries.remove.hover"))
+                .clicked()
+            {
+                self.inbox.push(Msg::RemoveEntry(self.model.active_entry));
+            }
+        });
+        ui.separator();
+
+        let labels: Vec<String> = mvu::entries(&self.model)
+            .enumerate()
+            .map(|(index, entry)| mvu::entry_label(index, entry))
+            .collect();
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (index, label) in labels.into_iter().enumerate() {
+                let selected = index == self.model.active_entry;
+                let short = ellipsize(&label, ENTRY_LABEL_MAX_CHARS);
+                let mut response = ui
+                    .add_enabled(idle || selected, egui::Button::selectable(selected, &short))
+                    .on_disabled_hover_text(t!("entries.busy"));
+                if short != label {
+                    response = response.on_hover_text(label);
+                }
+                if response.clicked() && !selected {
+                    self.inbox.push(Msg::SelectEntry(index));
+                }
+            }
+        });
+    }
+
     /// Render the entry title field.
     fn render_title_input(&mut self, ui: &mut egui::Ui) {
-        ui.label("Title");
+        let label = ui.label(t!("title.label"));
         ui.add_space(4.0);
-        let mut title = self.model.entry_title.clone();
-        let title_response = ui.add(
-            egui::TextEdit::singleline(&mut title).hint_text("e.g., Cell viability assay day 3"),
-        );
+        let mut title = self.model.entry.title.clone();
+        let title_response = ui
+            .add(
+                egui::TextEdit::singleline(&mut title)
+                    .id(egui::Id::new("entry_title"))
+                    .hint_text(t!("title.hint")),
+            )
+            .labelled_by(label.id);
 
         if title_response.changed()
             || (title_response.lost_focus()
@@ -368,42 +1302,187 @@ impl ElnPackApp {
         {
             self.inbox.push(Msg::EntryTitleChanged(title));
         }
+        if let Some(warning) = title_length_warning(&self.model.entry.title) {
+            ui.colored_label(
+                egui::Color32::from_rgb(200, 140, 40),
+                format!("{} {warning}", egui_phosphor::regular::WARNING),
+            );
+        }
+
+        ui.add_space(6.0);
+        let label = ui.label(t!("summary.label"));
+        ui.add_space(4.0);
+        let mut summary = self.model.entry.summary.clone();
+        if ui
+            .add(
+                egui::TextEdit::multiline(&mut summary)
+                    .id(egui::Id::new("entry_summary"))
+                    .desired_rows(2)
+                    .hint_text(t!("summary.hint")),
+            )
+            .labelled_by(label.id)
+            .changed()
+        {
+            self.inbox.push(Msg::SummaryChanged(summary));
+        }
+        let count = self.model.entry.summary.trim().chars().count();
+        let counter = t!("summary.counter", count = count, max = SUMMARY_MAX_CHARS);
+        if count > SUMMARY_MAX_CHARS {
+            ui.colored_label(egui::Color32::from_rgb(200, 80, 80), counter);
+        } else {
+            ui.label(
+                egui::RichText::new(counter)
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
+            );
+        }
     }
 
     /// Render the markdown editor field and toolbar.
-    fn render_description_input(&mut self, ui: &mut egui::Ui) {
-        ui.label("Main Text");
+    fn render_description_input(&mut self, ui: &mut egui::Ui, layout: &LayoutParams) {
+        ui.label(t!("body.label"));
         ui.label(
-            egui::RichText::new("Use Markdown to format text.")
+            egui::RichText::new(t!("body.hint"))
                 .small()
                 .color(egui::Color32::from_gray(110)),
         );
         ui.add_space(4.0);
-        let md_msgs = markdown::view(&self.model.markdown, ui);
+        let md_msgs = markdown::view(&self.model.entry.markdown, layout, ui);
         self.inbox.extend(md_msgs.into_iter().map(Msg::Markdown));
     }
-    fn render_body_format_toggle(&mut self, ui: &mut egui::Ui) {
+    fn render_body_format_toggle(&mut self, ui: &mut egui::Ui, layout: &LayoutParams) {
         let mut choice = self.model.body_format;
         ui.horizontal(|ui| {
-            let md_label = format!("{} Markdown", egui_phosphor::regular::MARKDOWN_LOGO);
+            let md_label = layout.toolbar_text(egui_phosphor::regular::MARKDOWN_LOGO, "Markdown");
             ui.selectable_value(
                 &mut choice,
                 crate::logic::eln::BodyFormat::Markdown,
                 md_label,
             )
-            .on_hover_text("Store the raw markdown in the archive metadata");
-            let html_label = format!("{} HTML", egui_phosphor::regular::FILE_HTML);
+            .on_hover_text(t!("format.markdown.hover"));
+            let html_label = layout.toolbar_text(egui_phosphor::regular::FILE_HTML, "HTML");
             ui.selectable_value(&mut choice, crate::logic::eln::BodyFormat::Html, html_label)
-                .on_hover_text("Convert markdown to HTML in the archive metadata");
-            ui.label("Export as");
+                .on_hover_text(t!("format.html.hover"));
+            let both_label = layout.toolbar_text(egui_phosphor::regular::FILES, t!("format.both"));
+            ui.selectable_value(&mut choice, crate::logic::eln::BodyFormat::Both, both_label)
+                .on_hover_text(t!("format.both.hover"));
+            if !layout.icon_only_toolbar {
+                ui.label(t!("format.label"));
+            }
         });
         if choice != self.model.body_format {
             self.inbox.push(Msg::SetBodyFormat(choice));
         }
     }
 
-    /// Grouped metadata block with entry type and performed-at controls.
-    fn render_meta_group(&mut self, ui: &mut egui::Ui) {
+    /// Single-row metadata for compact mode; entry type and date/time open as popovers.
+    fn render_meta_row(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let genre = match self.model.entry.genre {
+                ArchiveGenre::Experiment => t!("genre.experiment"),
+                ArchiveGenre::Resource => t!("genre.resource"),
+                ArchiveGenre::Template => t!("genre.template"),
+            };
+            ui.menu_button(format!("{} {genre}", egui_phosphor::regular::FLASK), |ui| {
+                self.render_entry_type(ui);
+            })
+            .response
+            .on_hover_text(t!("meta.entry_type"));
+
+            let warning = mvu::performed_at_warning(&self.model);
+            let date_label = format!(
+                "{} {}",
+                egui_phosphor::regular::CALENDAR_BLANK,
+                datetime_picker::summary(&self.model.entry.datetime)
+            );
+            let config = egui::containers::menu::MenuConfig::new()
+                .close_behavior(egui::PopupCloseBehavior::CloseOnClickOutside);
+            let (response, _) = egui::containers::menu::MenuButton::new(date_label)
+                .config(config)
+                .ui(ui, |ui| {
+                    let dt_msgs = datetime_picker::view(&self.model.entry.datetime, warning, ui);
+                    self.inbox.extend(dt_msgs.into_iter().map(Msg::DateTime));
+                });
+            response.on_hover_text(t!("meta.performed_at.hover"));
+
+            if let Some(warning) = warning {
+                ui.colored_label(
+                    egui::Color32::from_rgb(200, 140, 40),
+                    egui_phosphor::regular::WARNING,
+                )
+                .on_hover_text(warning.message());
+            }
+
+            self.render_language(ui);
+            self.render_license(ui);
+        });
+    }
+
+    /// Text field overriding the body's language; left empty, it is detected when saving.
+    fn render_language(&mut self, ui: &mut egui::Ui) {
+        let mut tag = self.model.entry.language.clone();
+        let response = ui
+            .add(
+                egui::TextEdit::singleline(&mut tag)
+                    .hint_text(t!("meta.language.hint"))
+                    .desired_width(64.0),
+            )
+            .on_hover_text(t!("meta.language.hover"));
+        if response.changed() {
+            self.inbox.push(Msg::SetLanguage(tag));
+        }
+    }
+
+    /// License picker; "Other (URL)" adds a field for the address of the license text.
+    fn render_license(&mut self, ui: &mut egui::Ui) {
+        let current = &self.model.entry.license;
+        let mut selected = current.clone();
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("entry_license")
+                .selected_text(license_label(current))
+                .show_ui(ui, |ui| {
+                    for preset in License::PRESETS {
+                        let label = license_label(&preset);
+                        ui.selectable_value(&mut selected, preset, label);
+                    }
+                    let custom = matches!(current, License::Url(_));
+                    if ui.selectable_label(custom, t!("license.other")).clicked() && !custom {
+                        selected = License::Url(String::new());
+                    }
+                })
+                .response
+                .on_hover_text(t!("meta.license.hover"));
+            let License::Url(url) = current else {
+                return;
+            };
+            let mut url = url.clone();
+            let response = ui
+                .add(
+                    egui::TextEdit::singleline(&mut url)
+                        .hint_text("https://…")
+                        .desired_width(220.0),
+                )
+                .on_hover_text(t!("license.url.hover"));
+            if response.changed() {
+                selected = License::Url(url.clone());
+            }
+            if !url.trim().is_empty()
+                && let Err(err) = validate_license(current)
+            {
+                ui.colored_label(
+                    egui::Color32::from_rgb(200, 140, 40),
+                    egui_phosphor::regular::WARNING,
+                )
+                .on_hover_text(err.to_string());
+            }
+        });
+        if selected != *current {
+            self.inbox.push(Msg::SetLicense(selected));
+        }
+    }
+
+    /// Grouped metadata block with entry type and performed-at controls.
+    fn render_meta_group(&mut self, ui: &mut egui::Ui) {
         egui::Frame::group(ui.style()).show(ui, |ui| {
             ui.set_width(ui.available_width());
             egui::Grid::new("meta_grid")
@@ -411,23 +1490,33 @@ impl ElnPackApp {
                 .spacing(egui::vec2(8.0, 10.0))
                 .min_col_width(140.0)
                 .show(ui, |ui| {
-                    ui.label("Entry type");
+                    ui.label(t!("meta.entry_type"));
                     self.render_entry_type(ui);
                     ui.end_row();
 
-                    ui.label("Performed at");
-                    let dt_msgs = datetime_picker::view(&self.model.datetime, ui);
+                    ui.label(t!("meta.performed_at"));
+                    let dt_msgs = datetime_picker::view(
+                        &self.model.entry.datetime,
+                        mvu::performed_at_warning(&self.model),
+                        ui,
+                    );
                     self.inbox.extend(dt_msgs.into_iter().map(Msg::DateTime));
                     ui.end_row();
+
+                    ui.label(t!("meta.language"));
+                    self.render_language(ui);
+                    ui.end_row();
+
+                    ui.label(t!("meta.license"));
+                    self.render_license(ui);
+                    ui.end_row();
                 });
 
             ui.add_space(6.0);
             ui.label(
-                egui::RichText::new(
-                    "Times are shown in your local time zone and stored as UTC in the archive.",
-                )
-                .small()
-                .color(egui::Color32::from_gray(110)),
+                egui::RichText::new(t!("meta.utc_note"))
+                    .small()
+                    .color(egui::Color32::from_gray(110)),
             );
         });
     }
@@ -435,13 +1524,174 @@ impl ElnPackApp {
     /// Render attachments as a collapsible section in the main column.
     fn render_attachments_section(&mut self, ui: &mut egui::Ui) {
         self.prune_thumbnail_textures();
-        egui::CollapsingHeader::new("Attachments")
+        let title = attachments::section_title(&self.model.entry.attachments);
+        let section = egui::CollapsingHeader::new(title.widget_text(ui))
+            .id_salt("attachments_section")
             .default_open(true)
             .show(ui, |ui| {
-                let att_msgs =
-                    attachments::view(ui, &self.model.attachments, &self.thumbnail_textures);
+                let mut favorite_msgs = Vec::new();
+                let att_msgs = attachments::view(
+                    ui,
+                    &self.model.entry.attachments,
+                    &self.thumbnail_textures,
+                    self.model.settings.settings().hashing.primary,
+                    |ui| {
+                        favorite_msgs = settings::favorite_menu(
+                            ui,
+                            &self.model.settings,
+                            PickerKind::Attachments,
+                        );
+                    },
+                );
                 self.inbox
                     .extend(att_msgs.into_iter().map(Msg::Attachments));
+                self.inbox
+                    .extend(favorite_msgs.into_iter().map(Msg::Settings));
+            });
+        if let Some(blocker) = title.blocker {
+            section.header_response.on_hover_text(blocker);
+        }
+    }
+
+    /// Render the active entry's composition overview; computed only while expanded.
+    fn render_summary_section(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(t!("summary.heading"))
+            .default_open(false)
+            .show(ui, |ui| {
+                let entry = &self.model.entry;
+                let attachments: Vec<_> = entry
+                    .attachments
+                    .attachments()
+                    .iter()
+                    .map(|item| item.to_domain())
+                    .collect();
+                let summary = summarize_entry(
+                    &entry.markdown.text,
+                    &attachments,
+                    entry.extra_fields.fields(),
+                );
+                let span = summary
+                    .acquisition_span
+                    .map(|(first, last)| {
+                        let local = |at| {
+                            datetime_picker::from_offset_datetime(at)
+                                .map(|dt| datetime_picker::summary(&dt))
+                                .unwrap_or_default()
+                        };
+                        if first == last {
+                            local(first)
+                        } else {
+                            format!(
+                                "{} – {} ({})",
+                                local(first),
+                                local(last),
+                                format_span(last - first)
+                            )
+                        }
+                    })
+                    .unwrap_or_else(|| t!("summary.unknown").into());
+                let rows = [
+                    (t!("summary.words"), summary.words.to_string()),
+                    (
+                        t!("summary.figures"),
+                        format!(
+                            "{} / {}",
+                            summary.figures_referenced, summary.figures_attached
+                        ),
+                    ),
+                    (
+                        t!("summary.required"),
+                        t!(
+                            "summary.required_value",
+                            filled = summary.required_filled,
+                            total = summary.required_total
+                        ),
+                    ),
+                    (
+                        t!("summary.data_volume"),
+                        t!(
+                            "summary.data_value",
+                            size = attachments::format_bytes(summary.data_bytes),
+                            count = attachments.len()
+                        ),
+                    ),
+                    (t!("summary.acquired"), span),
+                ];
+                egui::Grid::new("entry_summary")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (label, value) in rows {
+                            ui.label(label);
+                            ui.label(value);
+                            ui.end_row();
+                        }
+                    });
+
+                let warn = egui::Color32::from_rgb(200, 140, 40);
+                if !summary.has_inconsistencies() {
+                    ui.label(format!(
+                        "{} {}",
+                        egui_phosphor::regular::CHECK,
+                        t!("summary.consistent")
+                    ));
+                }
+                if !summary.missing_attachments.is_empty() {
+                    ui.colored_label(
+                        warn,
+                        format!(
+                            "{} {}",
+                            egui_phosphor::regular::WARNING,
+                            t!(
+                                "summary.missing_attachments",
+                                files = summary.missing_attachments.join(", ")
+                            )
+                        ),
+                    );
+                }
+                if !summary.unmentioned_attachments.is_empty() {
+                    ui.colored_label(
+                        warn,
+                        format!(
+                            "{} {}",
+                            egui_phosphor::regular::WARNING,
+                            t!(
+                                "summary.unmentioned",
+                                files = summary.unmentioned_attachments.join(", ")
+                            )
+                        ),
+                    );
+                }
+                if summary.required_filled < summary.required_total {
+                    ui.colored_label(
+                        warn,
+                        format!(
+                            "{} {}",
+                            egui_phosphor::regular::WARNING,
+                            t!(
+                                "summary.required_empty",
+                                count = summary.required_total - summary.required_filled
+                            )
+                        ),
+                    );
+                }
+
+                ui.add_space(4.0);
+                let current = self.model.missing_files;
+                let mut selected = current;
+                ui.horizontal(|ui| {
+                    ui.label(t!("summary.missing_policy"));
+                    egui::ComboBox::from_id_salt("missing_files")
+                        .selected_text(current.label())
+                        .show_ui(ui, |ui| {
+                            for policy in MissingFilePolicy::ALL {
+                                ui.selectable_value(&mut selected, policy, policy.label());
+                            }
+                        });
+                });
+                if selected != current {
+                    self.inbox.push(Msg::MissingFilePolicyChanged(selected));
+                }
             });
     }
 
@@ -450,8 +1700,26 @@ impl ElnPackApp {
     /// The view is produced by `extra_fields::view` and each returned message is wrapped and appended to `self.inbox`.
     ///
     fn render_extra_fields_section(&mut self, ui: &mut egui::Ui) {
-        let msgs = extra_fields::view(ui, &self.model.extra_fields);
-        self.inbox.extend(msgs.into_iter().map(Msg::ExtraFields));
+        let mut favorite_msgs = Vec::new();
+        let mut template_msgs = Vec::new();
+        let units = &self.model.settings.settings().units;
+        let mut toggle_msg = None;
+        let msgs = extra_fields::view(ui, &self.model.entry.extra_fields, units, |ui| {
+            toggle_msg = extra_fields::main_text_toggle(ui, &self.model.entry.extra_fields);
+            favorite_msgs = settings::favorite_menu(ui, &self.model.settings, PickerKind::Metadata);
+            template_msgs = templates::quick_switch(
+                ui,
+                &self.model.templates,
+                &self.model.settings.settings().templates.recent,
+                mvu::applied_template(&self.model),
+            );
+        });
+        self.inbox
+            .extend(msgs.into_iter().chain(toggle_msg).map(Msg::ExtraFields));
+        self.inbox
+            .extend(favorite_msgs.into_iter().map(Msg::Settings));
+        self.inbox
+            .extend(template_msgs.into_iter().map(Msg::Templates));
     }
 
     /// Renders a two-button segmented control for selecting the entry's archive genre.
@@ -460,62 +1728,690 @@ impl ElnPackApp {
     ///
     fn render_entry_type(&mut self, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
-            let exp = egui::Button::new("Experiment")
-                .selected(matches!(self.model.archive_genre, ArchiveGenre::Experiment));
+            let exp = egui::Button::new(t!("genre.experiment"))
+                .selected(matches!(self.model.entry.genre, ArchiveGenre::Experiment));
             if ui.add(exp).clicked() {
                 self.inbox.push(Msg::SetGenre(ArchiveGenre::Experiment));
             }
-            let res = egui::Button::new("Resource")
-                .selected(matches!(self.model.archive_genre, ArchiveGenre::Resource));
+            let res = egui::Button::new(t!("genre.resource"))
+                .selected(matches!(self.model.entry.genre, ArchiveGenre::Resource));
             if ui.add(res).clicked() {
                 self.inbox.push(Msg::SetGenre(ArchiveGenre::Resource));
             }
         });
     }
 
-    /// Render a simple modal window for error messages.
+    /// Render the notice shown after a draft from a previous session was restored.
+    fn render_draft_notice(&mut self, ui: &mut egui::Ui) {
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            ui.label(egui_phosphor::regular::CLOCK_COUNTER_CLOCKWISE);
+            ui.label(t!("draft.restored"));
+            if ui
+                .button(format!(
+                    "{} {}",
+                    egui_phosphor::regular::TRASH,
+                    t!("draft.discard")
+                ))
+                .on_hover_text(t!("draft.discard.hover"))
+                .clicked()
+            {
+                self.inbox.push(Msg::DiscardDraft);
+            }
+            if ui.button(t!("draft.keep")).clicked() {
+                self.inbox.push(Msg::DismissDraftNotice);
+            }
+        });
+        ui.add_space(4.0);
+    }
+
+    /// Render the safe mode banner with a button to load each persisted file on its own.
+    fn render_safe_mode_notice(&mut self, ui: &mut egui::Ui) {
+        let Some(safe_mode) = &self.model.safe_mode else {
+            return;
+        };
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            ui.label(egui_phosphor::regular::LIFEBUOY);
+            ui.label(t!("safe_mode.notice"));
+            if safe_mode.config_dir().is_some()
+                && ui
+                    .button(format!(
+                        "{} {}",
+                        egui_phosphor::regular::FOLDER_OPEN,
+                        t!("safe_mode.config")
+                    ))
+                    .on_hover_text(t!("safe_mode.config.hover"))
+                    .clicked()
+            {
+                self.inbox.push(Msg::OpenConfigFolder);
+            }
+        });
+        for file in PersistedFile::ALL {
+            let Some(path) = file.path(&safe_mode.paths) else {
+                continue;
+            };
+            ui.horizontal(|ui| {
+                let loaded = safe_mode.loaded.get(&file);
+                let button = ui
+                    .add_enabled(
+                        !matches!(loaded, Some(Ok(()))),
+                        egui::Button::new(t!("safe_mode.load", file = file.label())),
+                    )
+                    .on_hover_text(t!("safe_mode.load.hover", path = path.display()));
+                if button.clicked() {
+                    self.inbox.push(Msg::LoadPersisted(file));
+                }
+                match loaded {
+                    Some(Ok(())) => {
+                        ui.label(format!(
+                            "{} {}",
+                            egui_phosphor::regular::CHECK_CIRCLE,
+                            t!("safe_mode.loaded")
+                        ));
+                    }
+                    Some(Err(err)) => {
+                        ui.colored_label(
+                            ui.visuals().error_fg_color,
+                            format!("{} {err}", egui_phosphor::regular::WARNING),
+                        );
+                    }
+                    None => {}
+                }
+            });
+        }
+        ui.add_space(4.0);
+    }
+
+    /// Render the font coverage panel when enabled in the settings.
+    fn render_font_coverage(&mut self, ctx: &egui::Context) {
+        if !self.model.settings.settings().appearance.show_font_coverage {
+            return;
+        }
+        let mut open = true;
+        egui::Window::new(t!("fonts.coverage"))
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.label(self.font_probe.as_str());
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.font_probe)
+                        .hint_text(t!("fonts.coverage.hint")),
+                );
+                ui.add_space(6.0);
+                egui::Grid::new("font_coverage")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for font in fonts::coverage(ctx, &self.font_probe) {
+                            ui.label(font.name);
+                            if font.missing.is_empty() {
+                                ui.label(format!(
+                                    "{} {}",
+                                    egui_phosphor::regular::CHECK_CIRCLE,
+                                    t!("fonts.coverage.complete")
+                                ));
+                            } else {
+                                let missing: Vec<String> = font
+                                    .missing
+                                    .iter()
+                                    .map(|&c| format!("{c} (U+{:04X})", u32::from(c)))
+                                    .collect();
+                                ui.label(t!("fonts.coverage.missing", chars = missing.join(", ")));
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+        if !open {
+            self.inbox
+                .push(Msg::Settings(SettingsMsg::SetShowFontCoverage(false)));
+        }
+    }
+
+    /// Render the modal window for error messages.
+    ///
+    /// Long messages show their first paragraph with the full text in a collapsible,
+    /// scrollable details section. Enter or Escape dismisses the window. The window is modal:
+    /// it takes the keyboard focus when it opens and Tab stays within it.
     fn render_error_modal(&mut self, ctx: &egui::Context) {
-        if let Some(message) = self.model.error.clone() {
-            egui::Window::new("Validation error")
-                .collapsible(false)
-                .resizable(false)
-                .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
-                .show(ctx, |ui| {
-                    ui.label(message);
-                    ui.add_space(8.0);
-                    if ui.button("OK").clicked() {
-                        self.inbox.push(Msg::DismissError);
+        let focus_taken = egui::Id::new("error_modal_focus");
+        let Some(message) = self.model.error.clone() else {
+            ctx.data_mut(|data| data.remove::<bool>(focus_taken));
+            return;
+        };
+        let mut dismiss = ctx.input_mut(|inp| {
+            inp.consume_key(egui::Modifiers::NONE, egui::Key::Enter)
+                || inp.consume_key(egui::Modifiers::NONE, egui::Key::Escape)
+        });
+        egui::Modal::new(egui::Id::new("error_modal")).show(ctx, |ui| {
+            ui.set_min_width(420.0);
+            ui.set_max_width(560.0);
+            ui.heading(self.model.error_title.unwrap_or(t!("error.title")));
+            ui.add_space(4.0);
+            match error_summary(&message) {
+                Some(summary) => {
+                    ui.label(summary);
+                    egui::CollapsingHeader::new(t!("error.details"))
+                        .id_salt("error_details")
+                        .show(ui, |ui| render_error_text(ui, &message));
+                }
+                None => render_error_text(ui, &message),
+            }
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if self.model.resumable_save.is_some()
+                    && ui
+                        .button(format!(
+                            "{} {}",
+                            egui_phosphor::regular::PLAY,
+                            t!("error.resume")
+                        ))
+                        .on_hover_text(t!("error.resume.hover"))
+                        .clicked()
+                {
+                    self.inbox.push(Msg::ResumeSave);
+                }
+                let ok = ui.button(t!("common.ok"));
+                if !ctx.data(|data| data.get_temp::<bool>(focus_taken).unwrap_or(false)) {
+                    ok.request_focus();
+                    ctx.data_mut(|data| data.insert_temp(focus_taken, true));
+                }
+                dismiss |= ok.clicked();
+                if ui
+                    .button(format!(
+                        "{} {}",
+                        egui_phosphor::regular::COPY,
+                        t!("error.copy")
+                    ))
+                    .on_hover_text(t!("error.copy.hover"))
+                    .clicked()
+                {
+                    ui.ctx().copy_text(message.clone());
+                }
+            });
+        });
+        if dismiss {
+            self.inbox.push(Msg::DismissError);
+        }
+    }
+
+    /// Ask the user to confirm an implausible performed-at date before saving.
+    fn render_date_confirmation(&mut self, ctx: &egui::Context) {
+        let Some(pending) = &self.model.date_confirmation else {
+            return;
+        };
+        let message = match mvu::entries(&self.model).nth(pending.entry) {
+            Some(entry) if mvu::entry_count(&self.model) > 1 => {
+                format!(
+                    "{}: {}",
+                    mvu::entry_label(pending.entry, entry),
+                    pending.warning.message()
+                )
+            }
+            _ => pending.warning.message(),
+        };
+        egui::Window::new(t!("date_confirm.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(message);
+                ui.label(t!("date_confirm.text"));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button(t!("date_confirm.yes")).clicked() {
+                        self.inbox.push(Msg::ConfirmPerformedAt);
+                    }
+                    if ui.button(t!("common.cancel")).clicked() {
+                        self.inbox.push(Msg::CancelPerformedAtConfirmation);
+                    }
+                });
+            });
+    }
+
+    /// Ask the user to accept attachments above the size warning before saving.
+    fn render_size_confirmation(&mut self, ctx: &egui::Context) {
+        let Some(pending) = &self.model.size_confirmation else {
+            return;
+        };
+        egui::Window::new(t!("size_confirm.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(format!("{}.", pending.message));
+                ui.label(t!("size_confirm.text"));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button(t!("size_confirm.yes")).clicked() {
+                        self.inbox.push(Msg::ConfirmAttachmentSizes);
+                    }
+                    if ui.button(t!("common.cancel")).clicked() {
+                        self.inbox.push(Msg::CancelAttachmentSizeConfirmation);
+                    }
+                });
+            });
+    }
+
+    /// Ask what the running save does with an attachment it can no longer read.
+    fn render_missing_file_prompt(&mut self, ctx: &egui::Context) {
+        let Some(path) = &self.model.missing_file_prompt else {
+            return;
+        };
+        egui::Window::new(t!("missing_file.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(t!("missing_file.text", path = path.display()));
+                ui.label(t!("missing_file.hint"));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button(t!("missing_file.retry")).clicked() {
+                        self.inbox
+                            .push(Msg::ResolveMissingFile(MissingFileAction::Retry));
+                    }
+                    if ui
+                        .button(t!("missing_file.skip"))
+                        .on_hover_text(t!("missing_file.skip.hover"))
+                        .clicked()
+                    {
+                        self.inbox
+                            .push(Msg::ResolveMissingFile(MissingFileAction::Skip));
+                    }
+                    if ui.button(t!("missing_files.abort")).clicked() {
+                        self.inbox
+                            .push(Msg::ResolveMissingFile(MissingFileAction::Abort));
                     }
                 });
+            });
+    }
+
+    /// Ask before a template replaces entries that contain content.
+    fn render_template_confirmation(&mut self, ctx: &egui::Context) {
+        let Some(template) = &self.model.template_confirmation else {
+            return;
+        };
+        egui::Window::new(t!("template_confirm.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(t!("template_confirm.text", name = template.name));
+                ui.label(t!("template_confirm.hint"));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button(t!("template_confirm.yes")).clicked() {
+                        self.inbox.push(Msg::ConfirmTemplate);
+                    }
+                    if ui.button(t!("common.cancel")).clicked() {
+                        self.inbox.push(Msg::CancelTemplate);
+                    }
+                });
+            });
+    }
+
+    /// Ask before an opened archive replaces entries with unsaved changes.
+    fn render_open_confirmation(&mut self, ctx: &egui::Context) {
+        let Some(opened) = &self.model.open_confirmation else {
+            return;
+        };
+        egui::Window::new(t!("quit.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(t!("quit.text"));
+                ui.label(t!("open_confirm.text", path = opened.path.display()));
+                ui.label(t!("template_confirm.hint"));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button(t!("open_confirm.yes")).clicked() {
+                        self.inbox.push(Msg::ConfirmOpen);
+                    }
+                    if ui.button(t!("common.cancel")).clicked() {
+                        self.inbox.push(Msg::CancelOpen);
+                    }
+                });
+            });
+    }
+
+    /// Ask before saving over an existing archive without the file dialog.
+    fn render_overwrite_confirmation(&mut self, ctx: &egui::Context) {
+        let Some(path) = &self.model.overwrite_confirmation else {
+            return;
+        };
+        egui::Window::new(t!("overwrite.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(t!("overwrite.text", path = path.display()));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button(t!("overwrite.yes")).clicked() {
+                        self.inbox.push(Msg::ConfirmOverwrite);
+                    }
+                    if ui.button(t!("common.cancel")).clicked() {
+                        self.inbox.push(Msg::CancelOverwrite);
+                    }
+                });
+            });
+    }
+
+    /// List the jobs of the batch run with their progress, with buttons to cancel the
+    /// remaining ones and to close the finished run.
+    fn render_batch_window(&mut self, ctx: &egui::Context) {
+        let Some(batch) = &self.model.batch else {
+            return;
+        };
+        let finished = batch.finished();
+        egui::Window::new(t!("batch.title"))
+            .collapsible(false)
+            .resizable(true)
+            .default_width(460.0)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                let done = batch.count(BatchJobState::is_finished);
+                ui.label(t!("batch.progress", done = done, total = batch.jobs.len()));
+                ui.add_space(4.0);
+                egui::ScrollArea::vertical()
+                    .max_height(320.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("batch_jobs")
+                            .num_columns(3)
+                            .spacing([8.0, 4.0])
+                            .show(ui, |ui| {
+                                for (job, state) in batch.jobs.iter().zip(&batch.states) {
+                                    let (icon, text) = batch_state_label(state);
+                                    ui.label(icon);
+                                    ui.label(&job.title);
+                                    match state {
+                                        BatchJobState::Failed(_) => ui.colored_label(
+                                            egui::Color32::from_rgb(200, 80, 80),
+                                            text,
+                                        ),
+                                        _ => ui.label(text),
+                                    };
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    let cancelling = batch.cancel.load(Ordering::Relaxed);
+                    if ui
+                        .add_enabled(
+                            !finished && !cancelling,
+                            egui::Button::new(t!("batch.cancel")),
+                        )
+                        .on_hover_text(t!("batch.cancel.hover"))
+                        .clicked()
+                    {
+                        self.inbox.push(Msg::CancelBatch);
+                    }
+                    if ui
+                        .add_enabled(finished, egui::Button::new(t!("common.close")))
+                        .clicked()
+                    {
+                        self.inbox.push(Msg::CloseBatch);
+                    }
+                });
+            });
+    }
+
+    /// Render the prompt shown when the window is closed with unsaved changes.
+    fn render_close_confirmation(&mut self, ctx: &egui::Context) {
+        if self.model.quit != QuitState::Confirming {
+            return;
         }
+        egui::Window::new(t!("quit.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(t!("quit.text"));
+                ui.label(t!("quit.question"));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(self.save_enabled(), egui::Button::new(t!("quit.save")))
+                        .on_disabled_hover_text(t!("save.disabled_hint"))
+                        .clicked()
+                    {
+                        self.request_save();
+                        // The inbox is processed last-in first-out: queued after the save
+                        // result, the quit intent is applied before it.
+                        self.inbox.push(Msg::SaveAndQuit);
+                    }
+                    if ui
+                        .button(t!("quit.discard"))
+                        .on_hover_text(t!("quit.discard.hover"))
+                        .clicked()
+                    {
+                        self.inbox.push(Msg::DiscardAndQuit);
+                    }
+                    if ui.button(t!("common.cancel")).clicked() {
+                        self.inbox.push(Msg::CancelQuit);
+                    }
+                });
+            });
     }
 
-    /// Render latest status/error message when present.
-    fn render_status(&self, ui: &mut egui::Ui) {
-        if let Some(text) = &self.model.status {
-            let display = if self.model.pending_commands > 0 {
-                format!("{}  ({} working…)", text, self.model.pending_commands)
+    /// Render the latest status message, the history button, and background activity.
+    ///
+    /// Info and success messages fade out after a few seconds; a repaint is scheduled for
+    /// the moment they go. Running saves and uploads show their progress; uploads also get
+    /// a button that cancels them.
+    fn render_status(&mut self, ui: &mut egui::Ui) {
+        let now = time::OffsetDateTime::now_utc();
+        let current = self
+            .model
+            .status
+            .as_ref()
+            .filter(|status| status.is_visible_at(now));
+        if let Some(remaining) = current.and_then(|status| status.remaining(now)) {
+            ui.ctx()
+                .request_repaint_after(Duration::from_secs_f64(remaining.as_seconds_f64()));
+        }
+        ui.horizontal(|ui| {
+            ui.add_enabled_ui(!self.model.status_history.is_empty(), |ui| {
+                ui.menu_button(egui_phosphor::regular::LIST_BULLETS, |ui| {
+                    self.render_status_history(ui);
+                })
+                .response
+                .on_hover_text(t!("status.history"));
+            });
+            if let Some(status) = current {
+                let (icon, color) = severity_style(status.severity, ui.visuals());
+                let mut text = format!("{icon} {}", status.text);
+                if self.model.pending_commands > 0 {
+                    text.push_str(&format!(
+                        "  ({})",
+                        t!("status.working", count = self.model.pending_commands)
+                    ));
+                }
+                ui.label(egui::RichText::new(text).color(color));
+            }
+            if self.model.pending_commands > 0 {
+                ui.add(egui::Spinner::new().size(14.0))
+                    .on_hover_text(t!("status.background", count = self.model.pending_commands));
+            }
+        });
+        if let Some(progress) = &self.model.save_progress {
+            let fraction = if progress.bytes_total > 0 {
+                progress.bytes_done as f32 / progress.bytes_total as f32
             } else {
-                text.to_string()
+                0.0
             };
+            ui.add(
+                egui::ProgressBar::new(fraction.min(1.0))
+                    .desired_width(360.0)
+                    .text(t!(
+                        "status.writing",
+                        file = ellipsize(&progress.current_file, 32),
+                        done = attachments::format_bytes(progress.bytes_done),
+                        total = attachments::format_bytes(progress.bytes_total)
+                    )),
+            );
+        }
+        if self.model.uploading {
             ui.horizontal(|ui| {
-                ui.label(egui::RichText::new(display).color(egui::Color32::from_gray(68)));
-                if self.model.pending_commands > 0 {
-                    ui.add(egui::Spinner::new().size(14.0))
-                        .on_hover_text(format!(
-                            "{} task(s) running in background",
-                            self.model.pending_commands
-                        ));
+                if let Some(progress) = self.model.upload_progress {
+                    let fraction = if progress.bytes_total > 0 {
+                        progress.bytes_sent as f32 / progress.bytes_total as f32
+                    } else {
+                        0.0
+                    };
+                    ui.add(
+                        egui::ProgressBar::new(fraction.min(1.0))
+                            .desired_width(360.0)
+                            .text(t!(
+                                "status.uploading",
+                                done = attachments::format_bytes(progress.bytes_sent),
+                                total = attachments::format_bytes(progress.bytes_total)
+                            )),
+                    );
+                }
+                if ui
+                    .small_button(egui_phosphor::regular::X)
+                    .on_hover_text(t!("upload.cancel.hover"))
+                    .clicked()
+                {
+                    self.inbox.push(Msg::CancelUpload);
                 }
             });
         }
     }
+
+    /// List recent status messages, newest first, with their local time.
+    fn render_status_history(&self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical()
+            .max_height(320.0)
+            .show(ui, |ui| {
+                ui.set_min_width(360.0);
+                for status in self.model.status_history.newest_first() {
+                    render_status_entry(ui, status);
+                }
+            });
+    }
+}
+
+/// Whether the pointer was pressed on `response` while its widget was disabled.
+fn clicked_while_disabled(ui: &egui::Ui, response: &egui::Response) -> bool {
+    !response.enabled()
+        && response.contains_pointer()
+        && ui.input(|input| input.pointer.primary_clicked())
+}
+
+/// One line of the status history: time, severity icon, and text.
+fn render_status_entry(ui: &mut egui::Ui, status: &StatusMessage) {
+    let (icon, color) = severity_style(status.severity, ui.visuals());
+    let at = datetime_picker::from_offset_datetime(status.timestamp)
+        .map(|local| datetime_picker::summary(&local))
+        .unwrap_or_default();
+    ui.horizontal_wrapped(|ui| {
+        ui.label(egui::RichText::new(at).small().weak());
+        ui.label(egui::RichText::new(format!("{icon} {}", status.text)).color(color));
+    });
+}
+
+/// Icon and theme-aware text color of status messages of `severity`.
+fn severity_style(severity: Severity, visuals: &egui::Visuals) -> (&'static str, egui::Color32) {
+    match severity {
+        Severity::Info => (egui_phosphor::regular::INFO, visuals.text_color()),
+        Severity::Success => (
+            egui_phosphor::regular::CHECK_CIRCLE,
+            if visuals.dark_mode {
+                egui::Color32::from_rgb(120, 200, 130)
+            } else {
+                egui::Color32::from_rgb(30, 120, 50)
+            },
+        ),
+        Severity::Warning => (egui_phosphor::regular::WARNING, visuals.warn_fg_color),
+        Severity::Error => (egui_phosphor::regular::X_CIRCLE, visuals.error_fg_color),
+    }
+}
+
+/// Error messages with more lines than this are summarized in the error modal.
+const ERROR_SUMMARY_LINES: usize = 8;
+
+/// Height above which the error text scrolls.
+const ERROR_MAX_HEIGHT: f32 = 320.0;
+
+/// First paragraph of a long error message, shown above its expandable details; `None`
+/// when the message is short enough to show in full.
+fn error_summary(message: &str) -> Option<String> {
+    if message.lines().count() <= ERROR_SUMMARY_LINES && message.chars().count() <= 600 {
+        return None;
+    }
+    let first = message.split("\n\n").next().unwrap_or(message).trim();
+    Some(ellipsize(first, 300))
+}
+
+/// Wrapped, selectable error text in a scroll area of limited height.
+fn render_error_text(ui: &mut egui::Ui, message: &str) {
+    egui::ScrollArea::vertical()
+        .max_height(ERROR_MAX_HEIGHT)
+        .show(ui, |ui| {
+            ui.add(egui::Label::new(message).wrap().selectable(true));
+        });
+}
+
+/// Icon and description of a batch job's state.
+fn batch_state_label(state: &BatchJobState) -> (&'static str, String) {
+    match state {
+        BatchJobState::Pending => (egui_phosphor::regular::CLOCK, t!("batch.waiting").into()),
+        BatchJobState::Running => (
+            egui_phosphor::regular::ARROWS_CLOCKWISE,
+            t!("batch.writing").into(),
+        ),
+        BatchJobState::Done(path) => (
+            egui_phosphor::regular::CHECK_CIRCLE,
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        ),
+        BatchJobState::Failed(err) => (egui_phosphor::regular::X_CIRCLE, err.clone()),
+        BatchJobState::Cancelled => (egui_phosphor::regular::X, t!("batch.cancelled").into()),
+    }
+}
+
+/// Name of `license` in the license picker.
+fn license_label(license: &License) -> &'static str {
+    match license {
+        License::None => t!("license.none"),
+        License::CcBy4 => "CC BY 4.0",
+        License::Cc0 => "CC0 1.0",
+        License::Mit => "MIT",
+        License::Url(_) => t!("license.other"),
+    }
+}
+
+/// egui preference for a stored theme setting.
+fn theme_preference(theme: ThemeSetting) -> egui::ThemePreference {
+    match theme {
+        ThemeSetting::System => egui::ThemePreference::System,
+        ThemeSetting::Light => egui::ThemePreference::Light,
+        ThemeSetting::Dark => egui::ThemePreference::Dark,
+    }
+}
+
+/// Stored theme setting for an egui preference.
+fn theme_setting(preference: egui::ThemePreference) -> ThemeSetting {
+    match preference {
+        egui::ThemePreference::System => ThemeSetting::System,
+        egui::ThemePreference::Light => ThemeSetting::Light,
+        egui::ThemePreference::Dark => ThemeSetting::Dark,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ui::components::attachments::AttachmentsMsg;
+    use crate::ui::components::attachments::{AttachmentsMsg, ThumbnailError};
+    use crate::ui::components::markdown::MarkdownMsg;
     use tempfile::TempDir;
 
     fn sample_image() -> egui::ColorImage {
@@ -529,7 +2425,7 @@ mod tests {
         std::fs::write(&path, b"thumb-bytes").unwrap();
 
         let mut app = ElnPackApp::default();
-        assert!(app.model.attachments.add_path(path.clone()));
+        assert!(app.model.entry.attachments.add_path(path.clone()));
 
         let mut cmds = Vec::new();
         mvu::update(
@@ -537,7 +2433,7 @@ mod tests {
             Msg::Attachments(AttachmentsMsg::LoadThumbnail(path.clone())),
             &mut cmds,
         );
-        assert!(app.model.attachments.is_thumbnail_loading(&path));
+        assert!(app.model.entry.attachments.is_thumbnail_loading(&path));
         app.dispatch_commands(cmds);
 
         let request_id = app.active_thumbnail_requests[&path];
@@ -554,6 +2450,47 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn thumbnail_texture_uploads_are_budgeted_per_frame() {
+        let tmp = TempDir::new().unwrap();
+        let mut app = ElnPackApp::default();
+        for i in 0..10 {
+            let path = tmp.path().join(format!("thumb-{i}.png"));
+            std::fs::write(&path, format!("thumb-bytes-{i}")).unwrap();
+            assert!(app.model.entry.attachments.add_path(path.clone()));
+            // Dispatched directly: the model starts only a few decodes at once, but finished
+            // images may still pile up faster than they are uploaded.
+            app.dispatch_commands(vec![Command::LoadThumbnail {
+                path: path.clone(),
+                _retry: false,
+                request_id: 0,
+            }]);
+            let request_id = app.active_thumbnail_requests[&path];
+            app.pending_thumbnail_images
+                .push((path, request_id, sample_image()));
+        }
+
+        let ctx = egui::Context::default();
+        let mut per_frame = Vec::new();
+        while !app.pending_thumbnail_images.is_empty() {
+            app.realize_pending_thumbnail_textures(&ctx);
+            let ready = app
+                .inbox
+                .drain(..)
+                .filter(|msg| {
+                    matches!(
+                        msg,
+                        Msg::Attachments(AttachmentsMsg::ThumbnailAvailable { .. })
+                    )
+                })
+                .count();
+            per_frame.push(ready);
+        }
+
+        assert_eq!(per_frame, vec![4, 4, 2]);
+        assert_eq!(app.thumbnail_textures.len(), 10);
+    }
+
     #[test]
     fn realizing_pending_thumbnail_textures_skips_removed_attachments() {
         let tmp = TempDir::new().unwrap();
@@ -561,7 +2498,7 @@ mod tests {
         std::fs::write(&path, b"thumb-bytes").unwrap();
 
         let mut app = ElnPackApp::default();
-        assert!(app.model.attachments.add_path(path.clone()));
+        assert!(app.model.entry.attachments.add_path(path.clone()));
         mvu::update(
             &mut app.model,
             Msg::Attachments(AttachmentsMsg::Remove(0)),
@@ -585,7 +2522,7 @@ mod tests {
         std::fs::write(&path, b"thumb-bytes").unwrap();
 
         let mut app = ElnPackApp::default();
-        assert!(app.model.attachments.add_path(path.clone()));
+        assert!(app.model.entry.attachments.add_path(path.clone()));
 
         let mut cmds = Vec::new();
         mvu::update(
@@ -603,7 +2540,7 @@ mod tests {
         );
         app.invalidate_thumbnail_runtime_state(path.as_path());
 
-        assert!(app.model.attachments.add_path(path.clone()));
+        assert!(app.model.entry.attachments.add_path(path.clone()));
         let mut cmds = Vec::new();
         mvu::update(
             &mut app.model,
@@ -624,6 +2561,24 @@ mod tests {
         assert!(app.inbox.is_empty());
     }
 
+    #[test]
+    fn long_error_messages_are_summarized_by_their_first_paragraph() {
+        assert_eq!(error_summary("Title cannot be empty."), None);
+
+        let paths = (0..12)
+            .map(|i| format!("  /data/run-{i}.tif"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let message = format!("Failed to save archive:\n\n{paths}");
+        assert_eq!(
+            error_summary(&message).as_deref(),
+            Some("Failed to save archive:")
+        );
+
+        let summary = error_summary(&"x".repeat(700)).unwrap();
+        assert_eq!(summary.chars().count(), 300);
+    }
+
     #[test]
     fn stale_thumbnail_failures_for_readded_paths_are_ignored() {
         let tmp = TempDir::new().unwrap();
@@ -631,7 +2586,7 @@ mod tests {
         std::fs::write(&path, b"thumb-bytes").unwrap();
 
         let mut app = ElnPackApp::default();
-        assert!(app.model.attachments.add_path(path.clone()));
+        assert!(app.model.entry.attachments.add_path(path.clone()));
 
         let mut cmds = Vec::new();
         mvu::update(
@@ -649,7 +2604,7 @@ mod tests {
         );
         app.invalidate_thumbnail_runtime_state(path.as_path());
 
-        assert!(app.model.attachments.add_path(path.clone()));
+        assert!(app.model.entry.attachments.add_path(path.clone()));
         let mut cmds = Vec::new();
         mvu::update(
             &mut app.model,
@@ -659,21 +2614,92 @@ mod tests {
         app.dispatch_commands(cmds);
         let new_request_id = app.active_thumbnail_requests[&path];
         assert_ne!(old_request_id, new_request_id);
-        assert!(app.model.attachments.is_thumbnail_loading(&path));
+        assert!(app.model.entry.attachments.is_thumbnail_loading(&path));
 
         app.inbox.push(Msg::ThumbnailFailed {
             path: path.clone(),
             request_id: old_request_id,
+            reason: ThumbnailError::Decode("truncated".into()),
         });
         app.process_runtime_messages();
 
-        assert!(app.model.attachments.is_thumbnail_loading(&path));
+        assert!(app.model.entry.attachments.is_thumbnail_loading(&path));
         assert_eq!(
             app.active_thumbnail_requests.get(&path),
             Some(&new_request_id)
         );
     }
 
+    #[test]
+    fn autosave_writes_changed_drafts_only() {
+        let tmp = TempDir::new().unwrap();
+        let draft_path = tmp.path().join("draft.json");
+        let mut app = ElnPackApp::new(
+            AppPaths {
+                draft: Some(draft_path.clone()),
+                settings: None,
+                vocabulary: None,
+                authors: None,
+                templates: None,
+                api_key: None,
+            },
+            false,
+        );
+
+        app.write_draft_if_changed();
+        assert!(!draft_path.exists(), "empty entries are not persisted");
+
+        app.model.entry.title = "Unsaved".into();
+        app.write_draft_if_changed();
+        let restored = crate::logic::draft::load_draft(&draft_path)
+            .unwrap()
+            .expect("draft written");
+        assert_eq!(restored.title, "Unsaved");
+
+        let restarted = ElnPackApp::new(
+            AppPaths {
+                draft: Some(draft_path),
+                settings: None,
+                vocabulary: None,
+                authors: None,
+                templates: None,
+                api_key: None,
+            },
+            false,
+        );
+        assert!(
+            matches!(restarted.inbox.as_slice(), [Msg::DraftRestored(d)] if d.title == "Unsaved")
+        );
+    }
+
+    #[test]
+    fn clearing_the_form_deletes_the_autosaved_draft() {
+        let tmp = TempDir::new().unwrap();
+        let draft_path = tmp.path().join("draft.json");
+        let paths = AppPaths {
+            draft: Some(draft_path.clone()),
+            ..AppPaths::default()
+        };
+        let mut app = ElnPackApp::new(paths.clone(), false);
+        app.model.entry.title = "Unsaved".into();
+        app.write_draft_if_changed();
+        assert!(draft_path.exists());
+
+        let mut restarted = ElnPackApp::new(paths, false);
+        restarted.write_draft_if_changed();
+        assert!(draft_path.exists(), "a draft being restored is kept");
+
+        app.model.entry.title.clear();
+        app.write_draft_if_changed();
+        let msg = app
+            .workers
+            .results
+            .recv_timeout(Duration::from_secs(5))
+            .expect("draft deleted");
+        assert!(matches!(msg, Msg::DraftDeleted(Ok(()))), "{msg:?}");
+        assert!(!draft_path.exists());
+    }
+
     #[test]
     fn removing_attachment_discards_queued_thumbnail_loads() {
         let tmp = TempDir::new().unwrap();
@@ -681,7 +2707,7 @@ mod tests {
         std::fs::write(&path, b"thumb-bytes").unwrap();
 
         let mut app = ElnPackApp::default();
-        assert!(app.model.attachments.add_path(path.clone()));
+        assert!(app.model.entry.attachments.add_path(path.clone()));
 
         app.inbox
             .push(Msg::Attachments(AttachmentsMsg::LoadThumbnail(
@@ -691,8 +2717,111 @@ mod tests {
 
         app.process_runtime_messages();
 
-        assert!(!app.model.attachments.is_thumbnail_loading(&path));
+        assert!(!app.model.entry.attachments.is_thumbnail_loading(&path));
         assert!(!app.active_thumbnail_requests.contains_key(&path));
         assert!(app.pending_thumbnail_images.is_empty());
     }
+
+    /// Press Tab through the entry form up to the authors and collect the label or hint of
+    /// every widget that gains the focus. In the body, Tab is pressed once more to indent
+    /// before Ctrl+Tab leaves it.
+    fn tab_through_entry_form(app: &mut ElnPackApp) -> Vec<String> {
+        let ctx = egui::Context::default();
+        let layout = LayoutParams::default();
+        let mut visited: Vec<String> = Vec::new();
+        let mut tabs_in_body = 0;
+        for frame in 0..80 {
+            if visited.iter().any(|name| name == "Authors") {
+                break;
+            }
+            let mut input = egui::RawInput {
+                screen_rect: Some(egui::Rect::from_min_size(
+                    egui::Pos2::ZERO,
+                    egui::vec2(1200.0, 4000.0),
+                )),
+                ..Default::default()
+            };
+            if frame > 0 {
+                let in_body = visited.last().is_some_and(|name| name == "body");
+                let modifiers = if in_body && tabs_in_body > 0 {
+                    egui::Modifiers::CTRL
+                } else {
+                    egui::Modifiers::NONE
+                };
+                if in_body {
+                    tabs_in_body += 1;
+                }
+                input.modifiers = modifiers;
+                input.events.push(egui::Event::Key {
+                    key: egui::Key::Tab,
+                    physical_key: None,
+                    pressed: true,
+                    repeat: false,
+                    modifiers,
+                });
+            }
+            let output = ctx.run_ui(input, |ui| {
+                app.render_entry_form(ui, &layout);
+            });
+            for event in output.platform_output.events {
+                let egui::output::OutputEvent::FocusGained(info) = event else {
+                    continue;
+                };
+                let name = info
+                    .label
+                    .or(info.hint_text)
+                    .filter(|name| !name.is_empty());
+                let after_toolbar = visited
+                    .iter()
+                    .rev()
+                    .find(|name| !name.is_empty())
+                    .is_some_and(|name| name == "Math");
+                // The body editor is the unnamed text field right after the toolbar.
+                let name = match name {
+                    Some(name) => name,
+                    None if after_toolbar && info.typ == egui::WidgetType::TextEdit => {
+                        "body".to_string()
+                    }
+                    None => String::new(),
+                };
+                visited.push(name);
+            }
+        }
+        visited
+    }
+
+    #[test]
+    fn tab_walks_the_entry_form_in_reading_order() {
+        let mut app = ElnPackApp::default();
+        let visited = tab_through_entry_form(&mut app);
+        let landmarks = [
+            t!("title.hint"),
+            t!("summary.hint"),
+            t!("genre.experiment"),
+            "Heading (Ctrl/Cmd+1…6)",
+            "body",
+            "Keywords",
+            "Related links",
+            "Metadata",
+            "Attachments",
+            "Authors",
+        ];
+        let positions: Vec<usize> = landmarks
+            .iter()
+            .map(|landmark| {
+                visited
+                    .iter()
+                    .position(|name| name == landmark)
+                    .unwrap_or_else(|| panic!("{landmark:?} never focused in {visited:#?}"))
+            })
+            .collect();
+        assert_eq!(positions[0], 0, "the title comes first");
+        assert!(positions.is_sorted(), "{visited:#?}");
+        assert!(
+            app.inbox.iter().any(
+                |msg| matches!(msg, Msg::Markdown(MarkdownMsg::SetText(text)) if text.contains('\t'))
+            ),
+            "Tab indents in the body"
+        );
+    }
 }
@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Bounded reads of attachment contents for the preview dialog.
+//!
+//! Text-like files show their first lines, everything else a hex dump of its first bytes;
+//! no more than [`PREVIEW_MAX_BYTES`] are ever read, however large the file is.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// Most bytes read from a text file for its preview.
+pub const PREVIEW_MAX_BYTES: usize = 256 * 1024;
+
+/// Most lines of a text file shown in the preview.
+pub const PREVIEW_MAX_LINES: usize = 200;
+
+/// Bytes of a binary file shown in its hex dump.
+pub const HEX_DUMP_BYTES: usize = 512;
+
+/// Start of a file as shown in the preview dialog.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FilePreview {
+    Text {
+        /// Up to [`PREVIEW_MAX_LINES`] lines.
+        text: String,
+        /// The file is not valid UTF-8; invalid bytes were replaced with `U+FFFD`.
+        lossy: bool,
+        /// The file continues after `text`.
+        truncated: bool,
+    },
+    Binary {
+        /// Size of the whole file in bytes.
+        size: u64,
+        /// Up to [`HEX_DUMP_BYTES`] leading bytes.
+        head: Vec<u8>,
+    },
+}
+
+/// Whether files of `mime` are shown as text: `text/*`, JSON, and CSV.
+pub fn is_text_mime(mime: &str) -> bool {
+    mime.starts_with("text/")
+        || mime.ends_with("+json")
+        || matches!(mime, "application/json" | "application/csv")
+}
+
+/// Read the start of the file at `path`, shown as text when `mime` is text-like.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be opened or read.
+pub fn read_file_preview(path: &Path, mime: &str) -> Result<FilePreview> {
+    let file = File::open(path).with_context(|| format!("Failed to open {:?}", path))?;
+    let size = file
+        .metadata()
+        .with_context(|| format!("Failed to read metadata of {:?}", path))?
+        .len();
+    let text = is_text_mime(mime);
+    let limit = if text {
+        PREVIEW_MAX_BYTES
+    } else {
+        HEX_DUMP_BYTES
+    };
+    let mut head = Vec::with_capacity(limit.min(size as usize));
+    file.take(limit as u64)
+        .read_to_end(&mut head)
+        .with_context(|| format!("Failed to read {:?}", path))?;
+    if !text {
+        return Ok(FilePreview::Binary { size, head });
+    }
+    let cut = (head.len() as u64) < size;
+    Ok(text_preview(&head, cut))
+}
+
+/// First lines of `bytes`; `cut` tells that the read limit ended them early.
+fn text_preview(bytes: &[u8], cut: bool) -> FilePreview {
+    let (valid, lossy) = match std::str::from_utf8(bytes) {
+        Ok(_) => (bytes, false),
+        // A character split by the read limit is not an encoding error.
+        Err(err) if cut && err.error_len().is_none() => (&bytes[..err.valid_up_to()], false),
+        Err(_) => (bytes, true),
+    };
+    let decoded = String::from_utf8_lossy(valid);
+    let mut lines = decoded.split_inclusive('\n');
+    let text: String = lines.by_ref().take(PREVIEW_MAX_LINES).collect();
+    FilePreview::Text {
+        text,
+        lossy,
+        truncated: cut || lines.next().is_some(),
+    }
+}
+
+/// Format `bytes` like `hexdump -C`: offset, 16 hex bytes, and their printable ASCII.
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for (row, chunk) in bytes.chunks(16).enumerate() {
+        let _ = write!(out, "{:08x} ", row * 16);
+        for column in 0..16 {
+            if column == 8 {
+                out.push(' ');
+            }
+            match chunk.get(column) {
+                Some(byte) => {
+                    let _ = write!(out, " {byte:02x}");
+                }
+                None => out.push_str("   "),
+            }
+        }
+        out.push_str("  |");
+        out.extend(chunk.iter().map(|&byte| {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            }
+        }));
+        out.push_str("|\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn text_previews_stop_at_the_line_limit_and_flag_invalid_utf8() {
+        let tmp = TempDir::new().unwrap();
+        let csv = tmp.path().join("plate.csv");
+        let rows: String = (0..PREVIEW_MAX_LINES + 5)
+            .map(|i| format!("{i},0.5\n"))
+            .collect();
+        fs::write(&csv, rows).unwrap();
+        let FilePreview::Text {
+            text,
+            lossy,
+            truncated,
+        } = read_file_preview(&csv, "text/csv").unwrap()
+        else {
+            panic!("CSV files are text");
+        };
+        assert_eq!(text.lines().count(), PREVIEW_MAX_LINES);
+        assert!(text.ends_with("199,0.5\n"));
+        assert!(truncated && !lossy);
+
+        let latin1 = tmp.path().join("notes.txt");
+        fs::write(&latin1, b"Temp: 37\xb0C\n").unwrap();
+        assert_eq!(
+            read_file_preview(&latin1, "text/plain").unwrap(),
+            FilePreview::Text {
+                text: "Temp: 37\u{fffd}C\n".into(),
+                lossy: true,
+                truncated: false,
+            }
+        );
+    }
+
+    #[test]
+    fn characters_split_by_the_read_limit_are_not_encoding_errors() {
+        let mut bytes = vec![b'a'; 3];
+        bytes.extend_from_slice(&"µ".as_bytes()[..1]);
+        assert_eq!(
+            text_preview(&bytes, true),
+            FilePreview::Text {
+                text: "aaa".into(),
+                lossy: false,
+                truncated: true,
+            }
+        );
+    }
+
+    #[test]
+    fn other_files_are_read_only_up_to_the_hex_dump() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("trace.bin");
+        fs::write(&path, vec![0u8; 4096]).unwrap();
+        let FilePreview::Binary { size, head } =
+            read_file_preview(&path, "application/octet-stream").unwrap()
+        else {
+            panic!("unknown types are binary");
+        };
+        assert_eq!(size, 4096);
+        assert_eq!(head.len(), HEX_DUMP_BYTES);
+
+        assert!(is_text_mime("application/ld+json"));
+        assert!(!is_text_mime("application/pdf"));
+        assert_eq!(
+            hex_dump(b"PK\x03\x04hello"),
+            "00000000  50 4b 03 04 68 65 6c 6c  6f                       |PK..hello|\n"
+        );
+    }
+}
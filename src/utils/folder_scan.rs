@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Recursive listing of files below a picked attachment folder.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// Largest folder that can be attached; bigger folders are rejected after scanning.
+pub const MAX_FOLDER_FILES: usize = 5_000;
+
+/// Regular file found while scanning a folder.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScannedFile {
+    /// Absolute path on disk.
+    pub path: PathBuf,
+    /// Directory of the file relative to the parent of the scanned folder, so it starts
+    /// with the scanned folder's own name (e.g. `run42/csv`).
+    pub relative_dir: PathBuf,
+}
+
+/// Result of [`scan_folder`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FolderScan {
+    /// Folder that was scanned.
+    pub root: PathBuf,
+    /// Files in depth-first, name-sorted order.
+    pub files: Vec<ScannedFile>,
+    /// `true` when scanning stopped at the file limit.
+    pub truncated: bool,
+}
+
+/// List regular files below `root`, stopping after `limit` files.
+///
+/// Symbolic links are skipped so link cycles cannot make the scan loop forever.
+///
+/// # Errors
+///
+/// Returns an error when `root` or one of its subdirectories cannot be read.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let scan = scan_folder(Path::new("/data/run42"), 5_000)?;
+/// for file in &scan.files {
+///     println!("{} in {}", file.path.display(), file.relative_dir.display());
+/// }
+/// ```
+pub fn scan_folder(root: &Path, limit: usize) -> Result<FolderScan> {
+    let root_name = root
+        .file_name()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("folder"));
+    let mut scan = FolderScan {
+        root: root.to_path_buf(),
+        files: Vec::new(),
+        truncated: false,
+    };
+    scan_dir(root, &root_name, limit, &mut scan)?;
+    Ok(scan)
+}
+
+/// Append files of `dir` (recursively) to `scan` until `limit` is reached.
+fn scan_dir(dir: &Path, relative_dir: &Path, limit: usize, scan: &mut FolderScan) -> Result<()> {
+    let mut entries = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read folder {:?}", dir))?
+        .collect::<std::io::Result<Vec<_>>>()
+        .with_context(|| format!("Failed to read folder {:?}", dir))?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        if scan.truncated {
+            break;
+        }
+        let file_type = entry
+            .file_type()
+            .with_context(|| format!("Failed to inspect {:?}", entry.path()))?;
+        if file_type.is_dir() {
+            scan_dir(
+                &entry.path(),
+                &relative_dir.join(entry.file_name()),
+                limit,
+                scan,
+            )?;
+        } else if file_type.is_file() {
+            if scan.files.len() == limit {
+                scan.truncated = true;
+                break;
+            }
+            scan.files.push(ScannedFile {
+                path: entry.path(),
+                relative_dir: relative_dir.to_path_buf(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn scan_keeps_nested_structure_and_honours_limit() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().join("run42");
+        fs::create_dir_all(root.join("csv")).unwrap();
+        fs::write(root.join("notes.txt"), "n").unwrap();
+        fs::write(root.join("csv/a.csv"), "a").unwrap();
+        fs::write(root.join("csv/b.csv"), "b").unwrap();
+
+        let scan = scan_folder(&root, 10).unwrap();
+        let listed: Vec<(String, PathBuf)> = scan
+            .files
+            .iter()
+            .map(|f| {
+                (
+                    f.path.file_name().unwrap().to_string_lossy().into_owned(),
+                    f.relative_dir.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("a.csv".into(), PathBuf::from("run42/csv")),
+                ("b.csv".into(), PathBuf::from("run42/csv")),
+                ("notes.txt".into(), PathBuf::from("run42")),
+            ]
+        );
+        assert!(!scan.truncated);
+
+        let limited = scan_folder(&root, 2).unwrap();
+        assert_eq!(limited.files.len(), 2);
+        assert!(limited.truncated);
+    }
+}
@@ -3,12 +3,96 @@
 
 //! File hashing helper utilities.
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs::File;
-use std::io::Read;
+use std::io::{self, Read, Write};
+use std::ops::ControlFlow;
 use std::path::Path;
 
 use anyhow::{Context, Result};
-use sha2::{Digest, Sha256};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+
+/// Chunk size used when streaming file contents through the hasher.
+const HASH_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Digest algorithm used to fingerprint attachments.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    /// Always computed: ELN consumers verify the `sha256` property of every file.
+    #[default]
+    Sha256,
+    Sha512,
+    Blake3,
+}
+
+impl HashAlgorithm {
+    /// All supported algorithms in display order.
+    pub const ALL: [HashAlgorithm; 3] = [
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Sha512,
+        HashAlgorithm::Blake3,
+    ];
+
+    /// User-facing name, e.g. `SHA-512`.
+    pub fn label(self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "SHA-256",
+            HashAlgorithm::Sha512 => "SHA-512",
+            HashAlgorithm::Blake3 => "BLAKE3",
+        }
+    }
+
+    /// Lowercase identifier used as the RO-Crate `File` property, e.g. `sha512`.
+    pub fn key(self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Sha512 => "sha512",
+            HashAlgorithm::Blake3 => "blake3",
+        }
+    }
+}
+
+/// Lowercase hex digests keyed by algorithm.
+pub type Digests = BTreeMap<HashAlgorithm, String>;
+
+/// Running hasher for one algorithm.
+enum Hasher {
+    Sha256(Sha256),
+    Sha512(Sha512),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            HashAlgorithm::Sha512 => Hasher::Sha512(Sha512::new()),
+            HashAlgorithm::Blake3 => Hasher::Blake3(Box::default()),
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Hasher::Sha256(hasher) => hasher.update(bytes),
+            Hasher::Sha512(hasher) => hasher.update(bytes),
+            Hasher::Blake3(hasher) => {
+                hasher.update(bytes);
+            }
+        }
+    }
+
+    fn finalize(self) -> String {
+        match self {
+            Hasher::Sha256(hasher) => hex::encode(hasher.finalize()),
+            Hasher::Sha512(hasher) => hex::encode(hasher.finalize()),
+            Hasher::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+        }
+    }
+}
 
 /// Compute the SHA-256 hash of a file and return its lowercase hex digest.
 ///
@@ -26,28 +110,171 @@ use sha2::{Digest, Sha256};
 pub fn hash_file(path: &Path) -> Result<String> {
     let mut file =
         File::open(path).with_context(|| format!("Failed to open file for hashing: {:?}", path))?;
-    let mut hasher = Sha256::new();
-    let mut buffer = [0_u8; 8 * 1024];
+    let (digest, _) = copy_and_hash(&mut file, &mut io::sink())
+        .with_context(|| format!("Failed to read file for hashing: {:?}", path))?;
+    Ok(digest)
+}
+
+/// Compute the digests of a file for every algorithm in `algorithms` in one pass, reporting
+/// progress after every chunk.
+///
+/// `on_progress` receives the bytes hashed so far and the file size at open time; returning
+/// [`ControlFlow::Break`] stops reading and yields `Ok(None)`.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be opened or fully read.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use elnpack::utils::hash::HashAlgorithm;
+/// let digests = elnpack::utils::hash_file_with_progress(path, &[HashAlgorithm::Sha256], |done, total| {
+///     println!("{done}/{total}");
+///     std::ops::ControlFlow::Continue(())
+/// })?;
+/// ```
+pub fn hash_file_with_progress<F>(
+    path: &Path,
+    algorithms: &[HashAlgorithm],
+    mut on_progress: F,
+) -> Result<Option<Digests>>
+where
+    F: FnMut(u64, u64) -> ControlFlow<()>,
+{
+    let mut file =
+        File::open(path).with_context(|| format!("Failed to open file for hashing: {:?}", path))?;
+    let total = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let outcome = copy_and_hash_with_progress(&mut file, &mut io::sink(), algorithms, |done| {
+        on_progress(done, total)
+    })
+    .with_context(|| format!("Failed to read file for hashing: {:?}", path))?;
+    Ok(outcome.map(|(digests, _)| digests))
+}
+
+/// Stream `reader` into `writer` while hashing the same bytes in a single pass.
+///
+/// Every chunk read from `reader` is fed to a SHA-256 hasher and written to `writer`,
+/// so callers that need both a copy and a digest avoid reading the source twice.
+///
+/// # Returns
+///
+/// The lowercase hex SHA-256 digest and the number of bytes copied.
+///
+/// # Errors
+///
+/// Returns the first I/O error raised by either the reader or the writer.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let mut out = Vec::new();
+/// let (digest, len) = elnpack::utils::copy_and_hash(&mut &b"abc"[..], &mut out)?;
+/// assert_eq!(len, 3);
+/// assert_eq!(out, b"abc");
+/// ```
+pub fn copy_and_hash<R: Read, W: Write>(
+    reader: &mut R,
+    writer: &mut W,
+) -> io::Result<(String, u64)> {
+    let (mut digests, copied) = copy_and_digest(reader, writer, &[HashAlgorithm::Sha256])?;
+    let digest = digests
+        .remove(&HashAlgorithm::Sha256)
+        .expect("SHA-256 was requested");
+    Ok((digest, copied))
+}
+
+/// [`copy_and_hash`] computing every algorithm in `algorithms` over the same bytes.
+///
+/// # Errors
+///
+/// Returns the first I/O error raised by either the reader or the writer.
+pub fn copy_and_digest<R: Read, W: Write>(
+    reader: &mut R,
+    writer: &mut W,
+    algorithms: &[HashAlgorithm],
+) -> io::Result<(Digests, u64)> {
+    let outcome =
+        copy_and_hash_with_progress(reader, writer, algorithms, |_| ControlFlow::Continue(()))?;
+    Ok(outcome.expect("copy without a progress callback is never cancelled"))
+}
+
+/// [`copy_and_digest`] with a callback invoked with the running byte count after each chunk.
+///
+/// Returns `Ok(None)` when the callback breaks, leaving `writer` with a partial copy.
+///
+/// # Errors
+///
+/// Returns the first I/O error raised by either the reader or the writer.
+pub fn copy_and_hash_with_progress<R, W, F>(
+    reader: &mut R,
+    writer: &mut W,
+    algorithms: &[HashAlgorithm],
+    mut on_chunk: F,
+) -> io::Result<Option<(Digests, u64)>>
+where
+    R: Read,
+    W: Write,
+    F: FnMut(u64) -> ControlFlow<()>,
+{
+    let mut hashers: Vec<(HashAlgorithm, Hasher)> = algorithms
+        .iter()
+        .copied()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|algorithm| (algorithm, Hasher::new(algorithm)))
+        .collect();
+    let mut buffer = vec![0_u8; HASH_CHUNK_SIZE];
+    let mut copied = 0_u64;
 
     loop {
-        let read = file
-            .read(&mut buffer)
-            .with_context(|| format!("Failed to read file for hashing: {:?}", path))?;
-        if read == 0 {
-            break;
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        for (_, hasher) in &mut hashers {
+            hasher.update(&buffer[..read]);
+        }
+        writer.write_all(&buffer[..read])?;
+        copied += read as u64;
+        if on_chunk(copied).is_break() {
+            return Ok(None);
         }
-        hasher.update(&buffer[..read]);
     }
 
-    Ok(hex::encode(hasher.finalize()))
+    let digests = hashers
+        .into_iter()
+        .map(|(algorithm, hasher)| (algorithm, hasher.finalize()))
+        .collect();
+    Ok(Some((digests, copied)))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::hash_file;
+    use super::{
+        HashAlgorithm, copy_and_digest, copy_and_hash, hash_file, hash_file_with_progress,
+    };
     use std::fs;
+    use std::io::{self, Read};
+    use std::ops::ControlFlow;
     use tempfile::tempdir;
 
+    /// Reader wrapper counting how many bytes were pulled from the source.
+    struct CountingReader<R> {
+        inner: R,
+        bytes_read: u64,
+    }
+
+    impl<R: Read> Read for CountingReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            self.bytes_read += n as u64;
+            Ok(n)
+        }
+    }
+
     #[test]
     fn hashes_file_contents_as_lowercase_sha256_hex() {
         let dir = tempdir().unwrap();
@@ -61,4 +288,105 @@ mod tests {
             "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
         );
     }
+
+    #[test]
+    fn copy_and_hash_copies_bytes_and_matches_hash_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("data.bin");
+        let data: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        fs::write(&path, &data).unwrap();
+
+        let mut out = Vec::new();
+        let (digest, copied) =
+            copy_and_hash(&mut fs::File::open(&path).unwrap(), &mut out).unwrap();
+
+        assert_eq!(copied, data.len() as u64);
+        assert_eq!(out, data);
+        assert_eq!(digest, hash_file(&path).unwrap());
+    }
+
+    #[test]
+    fn copy_and_hash_reads_source_exactly_once() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("stack.bin");
+        fs::write(&path, vec![7_u8; 5 * 1024 * 1024 + 123]).unwrap();
+        let len = fs::metadata(&path).unwrap().len();
+        let mut reader = CountingReader {
+            inner: fs::File::open(&path).unwrap(),
+            bytes_read: 0,
+        };
+        let mut copy = Vec::new();
+
+        let (sha256, copied) = copy_and_hash(&mut reader, &mut copy).unwrap();
+
+        assert_eq!(reader.bytes_read, len, "every byte is read once");
+        assert_eq!(copied, len);
+        assert_eq!(copy, fs::read(&path).unwrap());
+        assert_eq!(sha256, hash_file(&path).unwrap());
+    }
+
+    #[test]
+    fn hash_file_with_progress_reports_monotonic_progress() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("big.bin");
+        fs::write(&path, vec![1_u8; 300 * 1024]).unwrap();
+        let mut seen = Vec::new();
+
+        let digests = hash_file_with_progress(&path, &[HashAlgorithm::Sha256], |done, total| {
+            seen.push((done, total));
+            ControlFlow::Continue(())
+        })
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(digests[&HashAlgorithm::Sha256], hash_file(&path).unwrap());
+        assert!(seen.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(seen.last(), Some(&(300 * 1024, 300 * 1024)));
+    }
+
+    #[test]
+    fn hash_file_with_progress_stops_when_cancelled() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("big.bin");
+        fs::write(&path, vec![1_u8; 300 * 1024]).unwrap();
+        let mut calls = 0;
+
+        let digest = hash_file_with_progress(&path, &[HashAlgorithm::Sha256], |_, _| {
+            calls += 1;
+            ControlFlow::Break(())
+        })
+        .unwrap();
+
+        assert_eq!(digest, None);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn copy_and_digest_computes_each_requested_algorithm_once() {
+        let (digests, copied) = copy_and_digest(
+            &mut &b"abc"[..],
+            &mut io::sink(),
+            &[
+                HashAlgorithm::Blake3,
+                HashAlgorithm::Sha512,
+                HashAlgorithm::Blake3,
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(copied, 3);
+        assert_eq!(
+            digests.keys().copied().collect::<Vec<_>>(),
+            vec![HashAlgorithm::Sha512, HashAlgorithm::Blake3]
+        );
+        assert_eq!(
+            digests[&HashAlgorithm::Sha512],
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+             2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        );
+        assert_eq!(
+            digests[&HashAlgorithm::Blake3],
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+    }
 }
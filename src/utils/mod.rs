@@ -4,12 +4,18 @@
 //! Shared helper utilities reused by UI and business logic.
 
 pub mod file_icons;
+pub mod file_preview;
+pub mod folder_scan;
 pub mod hash;
+pub mod pasted_image;
 pub mod sanitize_component;
+pub mod storage;
+pub mod svg;
+pub mod text;
 
 /// Select a Phosphor icon for the given MIME/path.
 pub use file_icons::icon_for;
-/// Compute the SHA-256 hash of a file.
-pub use hash::hash_file;
+/// Compute SHA-256 and other digests of a file or of a stream while copying it.
+pub use hash::{copy_and_hash, copy_and_hash_with_progress, hash_file, hash_file_with_progress};
 /// Sanitize user-provided strings into filesystem-safe path components.
-pub use sanitize_component::sanitize_component;
+pub use sanitize_component::{MAX_COMPONENT_LEN, clamp_component, sanitize_component};
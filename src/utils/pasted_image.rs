@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Clipboard images written to PNG files so they can be attached like any other file.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+
+/// Read the image on the clipboard and write it into `dir` as `pasted-<timestamp>.png`.
+///
+/// Returns the written path and its size in bytes.
+///
+/// # Errors
+///
+/// Returns an error when the clipboard is unavailable or holds no image, or when the PNG
+/// cannot be written.
+pub fn paste_clipboard_image(dir: &Path) -> Result<(PathBuf, u64)> {
+    let mut clipboard = arboard::Clipboard::new().context("Clipboard is not available")?;
+    let image = clipboard.get_image().map_err(|err| match err {
+        arboard::Error::ContentNotAvailable => anyhow!("Clipboard holds no image."),
+        other => anyhow!("Could not read clipboard image: {other}"),
+    })?;
+    let name = format!(
+        "pasted-{}.png",
+        jiff::Zoned::now().strftime("%Y%m%d-%H%M%S")
+    );
+    write_png(dir, &name, image.width, image.height, &image.bytes)
+}
+
+/// Write RGBA pixels as PNG file `name` into `dir`, creating `dir` when missing.
+///
+/// A taken name gets a `-2`, `-3`, … suffix so repeated pastes within one second do not
+/// overwrite each other.
+///
+/// # Errors
+///
+/// Returns an error when `rgba` does not hold `width × height` pixels or writing fails.
+pub fn write_png(
+    dir: &Path,
+    name: &str,
+    width: usize,
+    height: usize,
+    rgba: &[u8],
+) -> Result<(PathBuf, u64)> {
+    let buffer = u32::try_from(width)
+        .ok()
+        .zip(u32::try_from(height).ok())
+        .and_then(|(w, h)| image::RgbaImage::from_raw(w, h, rgba.to_vec()))
+        .context("Clipboard image has an unexpected size")?;
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {:?}", dir))?;
+
+    let path = free_path(dir, name);
+    buffer
+        .save_with_format(&path, image::ImageFormat::Png)
+        .with_context(|| format!("Failed to write pasted image {:?}", path))?;
+    let size = fs::metadata(&path)
+        .with_context(|| format!("Failed to read pasted image {:?}", path))?
+        .len();
+    Ok((path, size))
+}
+
+/// First path in `dir` for `name` that does not exist yet.
+fn free_path(dir: &Path, name: &str) -> PathBuf {
+    let (stem, ext) = name.rsplit_once('.').unwrap_or((name, ""));
+    let mut path = dir.join(name);
+    let mut counter = 2;
+    while path.exists() {
+        path = dir.join(format!("{stem}-{counter}.{ext}"));
+        counter += 1;
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn write_png_keeps_earlier_pastes_and_rejects_bad_sizes() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join("pasted");
+        let pixels = [255u8, 0, 0, 255].repeat(4);
+
+        let (first, size) = write_png(&dir, "pasted-1.png", 2, 2, &pixels).unwrap();
+        let (second, _) = write_png(&dir, "pasted-1.png", 2, 2, &pixels).unwrap();
+
+        assert_eq!(first, dir.join("pasted-1.png"));
+        assert_eq!(second, dir.join("pasted-1-2.png"));
+        assert_eq!(size, fs::metadata(&first).unwrap().len());
+        assert_eq!(image::open(&first).unwrap().width(), 2);
+        assert!(write_png(&dir, "bad.png", 3, 2, &pixels).is_err());
+    }
+}
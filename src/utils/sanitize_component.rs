@@ -3,6 +3,16 @@
 
 //! Produce filesystem-safe path components shared across the app.
 
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+/// Longest archive file stem and root folder name derived from an entry title.
+pub const MAX_COMPONENT_LEN: usize = 64;
+
+/// Length of the hash suffix appended by [`clamp_component`], excluding the dash.
+const CLAMP_HASH_LEN: usize = 8;
+
 /// Produce a filesystem-safe path component.
 ///
 /// # Steps
@@ -78,9 +88,23 @@ pub fn sanitize_component(value: &str) -> String {
         _ => (out.clone(), None),
     };
 
-    let upper = basename.to_ascii_uppercase();
-    let is_reserved = matches!(
-        upper.as_str(),
+    if is_reserved_device_name(&basename) {
+        let mut new_base = basename;
+        new_base.push('_');
+        out = if let Some(ext) = ext {
+            format!("{new_base}.{ext}")
+        } else {
+            new_base
+        };
+    }
+
+    out
+}
+
+/// Whether `basename` names a Windows device, such as `CON` or `lpt1`.
+fn is_reserved_device_name(basename: &str) -> bool {
+    matches!(
+        basename.to_ascii_uppercase().as_str(),
         "CON"
             | "PRN"
             | "AUX"
@@ -103,24 +127,220 @@ pub fn sanitize_component(value: &str) -> String {
             | "LPT7"
             | "LPT8"
             | "LPT9"
-    );
+    )
+}
 
-    if is_reserved {
-        let mut new_base = basename;
-        new_base.push('_');
-        out = if let Some(ext) = ext {
-            format!("{new_base}.{ext}")
+/// Run of characters in a [`SanitizeExplanation`] diff.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiffSegment {
+    /// Kept as typed.
+    Kept(String),
+    /// Typed but changed or dropped by sanitizing.
+    Removed(String),
+    /// Put in by sanitizing.
+    Added(String),
+}
+
+/// How [`sanitize_component`] changed a typed name, for explaining it before it is used.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SanitizeExplanation {
+    /// Typed name turned into the sanitized one, character by character.
+    pub segments: Vec<DiffSegment>,
+    /// One sentence per kind of change, in a fixed order.
+    pub reasons: Vec<String>,
+}
+
+/// Explain how `raw` became `sanitized`, or `None` when they differ in case at most.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use elnpack::utils::sanitize_component::explain_sanitization;
+///
+/// let explanation = explain_sanitization("run 1\\plate.csv ", "run_1_plate.csv").unwrap();
+/// assert_eq!(explanation.reasons.len(), 3);
+/// assert!(explain_sanitization("Data.CSV", "data.csv").is_none());
+/// ```
+pub fn explain_sanitization(raw: &str, sanitized: &str) -> Option<SanitizeExplanation> {
+    if raw.to_lowercase() == sanitized.to_lowercase() {
+        return None;
+    }
+
+    let inner = raw.trim();
+    let mut reasons = Vec::new();
+    let listed = |chars: Vec<char>| {
+        let mut chars = chars;
+        chars.sort_unstable();
+        chars.dedup();
+        chars
+            .iter()
+            .map(|c| match c {
+                ' ' => "space".to_string(),
+                c => c.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let separators: Vec<char> = inner.chars().filter(|c| matches!(c, '/' | '\\')).collect();
+    if !separators.is_empty() {
+        reasons.push(format!(
+            "Path separators ({}) cannot appear in a file name and became _.",
+            listed(separators)
+        ));
+    }
+    if inner.len() != raw.len() {
+        reasons.push("Leading and trailing spaces were removed.".into());
+    }
+    if inner.ends_with('.') {
+        reasons.push("Trailing dots were removed.".into());
+    }
+    let letters: Vec<char> = inner
+        .chars()
+        .filter(|c| !c.is_ascii() && c.is_alphanumeric())
+        .collect();
+    if !letters.is_empty() {
+        reasons.push(format!(
+            "Letters outside ASCII ({}) were spelled with ASCII letters.",
+            listed(letters)
+        ));
+    }
+    let punctuation: Vec<char> = inner
+        .chars()
+        .filter(|c| !c.is_ascii() && !c.is_alphanumeric())
+        .collect();
+    if !punctuation.is_empty() {
+        reasons.push(format!(
+            "Typographic punctuation and symbols ({}) were replaced.",
+            listed(punctuation)
+        ));
+    }
+    let other: Vec<char> = inner
+        .chars()
+        .filter(|c| {
+            c.is_ascii() && !c.is_ascii_alphanumeric() && !matches!(c, '-' | '_' | '.' | '/' | '\\')
+        })
+        .collect();
+    if !other.is_empty() {
+        reasons.push(format!(
+            "Characters that some systems do not allow in file names ({}) became _.",
+            listed(other)
+        ));
+    }
+    let mapped: String = deunicode::deunicode(inner)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if ["__", "..", "_."].iter().any(|run| mapped.contains(run)) {
+        reasons.push("Repeated _ and . were merged.".into());
+    }
+    let basename = sanitized
+        .rsplit_once('.')
+        .map_or(sanitized, |(base, _)| base);
+    if basename
+        .strip_suffix('_')
+        .is_some_and(is_reserved_device_name)
+    {
+        reasons.push(format!(
+            "{} is reserved on Windows, so _ was appended.",
+            basename.trim_end_matches('_')
+        ));
+    }
+    if reasons.is_empty() {
+        reasons.push("The name was replaced because nothing usable was left.".into());
+    }
+
+    Some(SanitizeExplanation {
+        segments: diff_chars(raw, sanitized),
+        reasons,
+    })
+}
+
+/// Character diff turning `from` into `to` along their longest common subsequence.
+fn diff_chars(from: &str, to: &str) -> Vec<DiffSegment> {
+    let from: Vec<char> = from.chars().collect();
+    let to: Vec<char> = to.chars().collect();
+    // common[i][j]: length of the longest common subsequence of from[i..] and to[j..].
+    let mut common = vec![vec![0usize; to.len() + 1]; from.len() + 1];
+    for i in (0..from.len()).rev() {
+        for j in (0..to.len()).rev() {
+            common[i][j] = if from[i] == to[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut segments: Vec<DiffSegment> = Vec::new();
+    let mut push = |segment: DiffSegment| match (segments.last_mut(), segment) {
+        (Some(DiffSegment::Kept(text)), DiffSegment::Kept(next))
+        | (Some(DiffSegment::Removed(text)), DiffSegment::Removed(next))
+        | (Some(DiffSegment::Added(text)), DiffSegment::Added(next)) => text.push_str(&next),
+        (_, segment) => segments.push(segment),
+    };
+    let (mut i, mut j) = (0, 0);
+    while i < from.len() || j < to.len() {
+        if i < from.len() && j < to.len() && from[i] == to[j] {
+            push(DiffSegment::Kept(from[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if j == to.len() || (i < from.len() && common[i + 1][j] >= common[i][j + 1]) {
+            push(DiffSegment::Removed(from[i].to_string()));
+            i += 1;
         } else {
-            new_base
-        };
+            push(DiffSegment::Added(to[j].to_string()));
+            j += 1;
+        }
     }
+    segments
+}
 
-    out
+/// Sanitize each component of a relative directory and join them with `/`.
+pub fn sanitize_folder(dir: &Path) -> String {
+    dir.components()
+        .map(|component| sanitize_component(&component.as_os_str().to_string_lossy()))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Shorten a sanitized component to at most `max_len` bytes while keeping it unique.
+///
+/// Values that fit are returned unchanged. Longer ones keep their start, followed by `-` and
+/// the first 8 hex digits of the SHA-256 of the full value, so two long titles that share a
+/// prefix still produce different names. Clamping an already clamped value is a no-op.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use elnpack::utils::sanitize_component::clamp_component;
+///
+/// assert_eq!(clamp_component("short", 64), "short");
+/// assert_eq!(clamp_component(&"a".repeat(100), 20).len(), 20);
+/// ```
+pub fn clamp_component(value: &str, max_len: usize) -> String {
+    if value.len() <= max_len {
+        return value.to_string();
+    }
+    let digest = hex::encode(Sha256::digest(value.as_bytes()));
+    let mut end = max_len.saturating_sub(CLAMP_HASH_LEN + 1);
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    let head = value[..end].trim_end_matches(['_', '.', '-']);
+    format!("{head}-{}", &digest[..CLAMP_HASH_LEN])
 }
 
 #[cfg(test)]
 mod tests {
-    use super::sanitize_component;
+    use super::{
+        DiffSegment, MAX_COMPONENT_LEN, clamp_component, explain_sanitization, sanitize_component,
+    };
 
     // Sanitization should transliterate accents and preserve dots/extension.
     #[test]
@@ -162,4 +382,107 @@ mod tests {
     fn sanitize_component_falls_back_for_dot_only_names() {
         assert_eq!(sanitize_component("..."), "eln_entry");
     }
+
+    // Long names are cut at the limit with a hash suffix; shorter ones stay untouched.
+    #[test]
+    fn clamp_component_keeps_boundary_lengths_and_stays_unique() {
+        let fits = "a".repeat(MAX_COMPONENT_LEN);
+        assert_eq!(clamp_component(&fits, MAX_COMPONENT_LEN), fits);
+
+        let first = format!("{}1", "a".repeat(MAX_COMPONENT_LEN));
+        let second = format!("{}2", "a".repeat(MAX_COMPONENT_LEN));
+        let clamped = clamp_component(&first, MAX_COMPONENT_LEN);
+        assert_eq!(clamped.len(), MAX_COMPONENT_LEN);
+        assert!(clamped.starts_with("aaaa"));
+        assert_ne!(clamped, clamp_component(&second, MAX_COMPONENT_LEN));
+        assert_eq!(clamp_component(&clamped, MAX_COMPONENT_LEN), clamped);
+    }
+
+    // Separators left dangling at the cut are dropped before the suffix.
+    #[test]
+    fn clamp_component_trims_separators_at_the_cut() {
+        let clamped = clamp_component("abcdefghi_jklmnopqrstuvwxyz", 19);
+        assert!(clamped.starts_with("abcdefghi-"), "{clamped}");
+        assert_eq!(clamped.len(), 18);
+    }
+
+    // Pasted Windows paths explain the separators and mark exactly what was dropped.
+    #[test]
+    fn explanation_marks_replaced_separators_and_trimmed_spaces() {
+        let raw = " C:\\runs\\plate 1.csv ";
+        let sanitized = sanitize_component(raw.trim());
+        assert_eq!(sanitized, "C_runs_plate_1.csv");
+
+        let explanation = explain_sanitization(raw, &sanitized).unwrap();
+        assert_eq!(
+            explanation.reasons,
+            vec![
+                "Path separators (\\) cannot appear in a file name and became _.",
+                "Leading and trailing spaces were removed.",
+                "Characters that some systems do not allow in file names (space :) became _.",
+                "Repeated _ and . were merged.",
+            ]
+        );
+        assert_eq!(
+            explanation.segments,
+            vec![
+                DiffSegment::Removed(" ".into()),
+                DiffSegment::Kept("C".into()),
+                DiffSegment::Removed(":\\".into()),
+                DiffSegment::Added("_".into()),
+                DiffSegment::Kept("runs".into()),
+                DiffSegment::Removed("\\".into()),
+                DiffSegment::Added("_".into()),
+                DiffSegment::Kept("plate".into()),
+                DiffSegment::Removed(" ".into()),
+                DiffSegment::Added("_".into()),
+                DiffSegment::Kept("1.csv".into()),
+                DiffSegment::Removed(" ".into()),
+            ]
+        );
+    }
+
+    // Trailing dots and reserved device names are both named in the explanation.
+    #[test]
+    fn explanation_covers_trailing_dots_and_reserved_names() {
+        let explanation = explain_sanitization("report.", &sanitize_component("report.")).unwrap();
+        assert_eq!(explanation.reasons, vec!["Trailing dots were removed."]);
+        assert_eq!(
+            explanation.segments,
+            vec![
+                DiffSegment::Kept("report".into()),
+                DiffSegment::Removed(".".into()),
+            ]
+        );
+
+        let explanation = explain_sanitization("nul.txt", &sanitize_component("nul.txt")).unwrap();
+        assert_eq!(
+            explanation.reasons,
+            vec!["nul is reserved on Windows, so _ was appended."]
+        );
+    }
+
+    // Accented letters and typographic punctuation get separate reasons; case alone does not
+    // need explaining.
+    #[test]
+    fn explanation_separates_letters_from_unicode_punctuation() {
+        let raw = "Größe – “final”.csv";
+        let explanation = explain_sanitization(raw, &sanitize_component(raw)).unwrap();
+        assert_eq!(
+            explanation.reasons[..2],
+            [
+                "Letters outside ASCII (ß ö) were spelled with ASCII letters.",
+                "Typographic punctuation and symbols (– “ ”) were replaced.",
+            ]
+        );
+        assert!(
+            explanation
+                .segments
+                .iter()
+                .any(|segment| matches!(segment, DiffSegment::Removed(text) if text.contains('ö')))
+        );
+
+        assert_eq!(explain_sanitization("Data.CSV", "data.csv"), None);
+        assert_eq!(explain_sanitization("data.csv", "data.csv"), None);
+    }
 }
@@ -0,0 +1,350 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Platform storage locations, free disk space, and atomic writes for persisted application
+//! state.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// Directory name used below the platform data/config roots.
+const APP_DIR_NAME: &str = "elnpack";
+
+/// Kind of per-user storage location.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StorageKind {
+    /// User configuration (settings, templates).
+    Config,
+    /// Application data (drafts, recent files).
+    Data,
+}
+
+/// Locations of all files ELNPack persists between sessions.
+///
+/// A `None` entry disables persistence for that file (used by tests).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AppPaths {
+    /// Autosaved draft of the current entry.
+    pub draft: Option<PathBuf>,
+    /// User settings.
+    pub settings: Option<PathBuf>,
+    /// Keywords collected from saved archives for autocompletion.
+    pub vocabulary: Option<PathBuf>,
+    /// Authors credited in saved archives.
+    pub authors: Option<PathBuf>,
+    /// Folder holding named entry templates.
+    pub templates: Option<PathBuf>,
+    /// eLabFTW API key used to upload saved archives.
+    pub api_key: Option<PathBuf>,
+}
+
+impl AppPaths {
+    /// Platform-default locations derived from the per-user config and data directories.
+    pub fn platform() -> Self {
+        Self {
+            draft: app_dir(StorageKind::Data).map(|dir| dir.join("draft.json")),
+            settings: app_dir(StorageKind::Config).map(|dir| dir.join("settings.json")),
+            vocabulary: app_dir(StorageKind::Config).map(|dir| dir.join("keywords.json")),
+            authors: app_dir(StorageKind::Config).map(|dir| dir.join("authors.json")),
+            templates: app_dir(StorageKind::Config).map(|dir| dir.join("templates")),
+            api_key: app_dir(StorageKind::Config).map(|dir| dir.join("elabftw-api-key")),
+        }
+    }
+}
+
+/// Resolve the per-user ELNPack directory for `kind` from the process environment.
+///
+/// Returns `None` when no suitable base directory can be derived (e.g. `HOME` unset).
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let dir = elnpack::utils::storage::app_dir(StorageKind::Data);
+/// ```
+pub fn app_dir(kind: StorageKind) -> Option<PathBuf> {
+    resolve_app_dir(kind, std::env::consts::OS, |key| std::env::var_os(key))
+}
+
+/// Resolve the ELNPack directory for `kind` on `os` using `env` for variable lookup.
+///
+/// Follows platform conventions:
+/// - Windows: `%APPDATA%` for config, `%LOCALAPPDATA%` (falling back to `%APPDATA%`) for data.
+/// - macOS: `~/Library/Application Support` for both.
+/// - Others: `$XDG_CONFIG_HOME`/`~/.config` and `$XDG_DATA_HOME`/`~/.local/share`.
+///
+/// Relative XDG values are ignored as mandated by the XDG base directory spec.
+fn resolve_app_dir<F>(kind: StorageKind, os: &str, env: F) -> Option<PathBuf>
+where
+    F: Fn(&str) -> Option<std::ffi::OsString>,
+{
+    let absolute = |key: &str| {
+        env(key)
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+    };
+    let home = || absolute("HOME");
+
+    let base = match os {
+        "windows" => match kind {
+            StorageKind::Config => absolute("APPDATA"),
+            StorageKind::Data => absolute("LOCALAPPDATA").or_else(|| absolute("APPDATA")),
+        },
+        "macos" => home().map(|h| h.join("Library").join("Application Support")),
+        _ => match kind {
+            StorageKind::Config => {
+                absolute("XDG_CONFIG_HOME").or_else(|| home().map(|h| h.join(".config")))
+            }
+            StorageKind::Data => {
+                absolute("XDG_DATA_HOME").or_else(|| home().map(|h| h.join(".local").join("share")))
+            }
+        },
+    }?;
+
+    Some(base.join(APP_DIR_NAME))
+}
+
+/// Write `contents` to `path` atomically by writing a sibling temp file and renaming it.
+///
+/// Parent directories are created when missing. Readers never observe a half-written file:
+/// either the previous content or the new content is visible. The temp file is flushed to
+/// disk before the rename, and on Unix the directory after it, so a power loss cannot leave
+/// an empty file behind either.
+///
+/// # Errors
+///
+/// Returns an error when the directory cannot be created or the temp file cannot be written or renamed.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    write_atomic_with_mode(path, contents, false)
+}
+
+/// Like [`write_atomic`], but on Unix the file is readable and writable only by the user.
+///
+/// The temp file is created with these permissions, so the content is never readable by
+/// others, not even before the rename.
+///
+/// # Errors
+///
+/// Returns an error when the directory cannot be created or the temp file cannot be written or renamed.
+pub fn write_private(path: &Path, contents: &[u8]) -> Result<()> {
+    write_atomic_with_mode(path, contents, true)
+}
+
+/// Shared body of [`write_atomic`] and [`write_private`].
+fn write_atomic_with_mode(path: &Path, contents: &[u8], private: bool) -> Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {:?}", parent))?;
+    }
+
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+
+    // A temp file left behind by a crash would make `create_new` fail.
+    let _ = fs::remove_file(&tmp_path);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    options
+        .open(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .with_context(|| format!("Failed to write temporary file {:?}", tmp_path))
+        .and_then(|()| replace_file(&tmp_path, path))
+        .inspect_err(|_| {
+            let _ = fs::remove_file(&tmp_path);
+        })?;
+    sync_parent_dir(path)
+}
+
+/// Flush the directory entry of `path`, so that a rename into it survives a power loss.
+///
+/// Only Unix can open directories for this; elsewhere this does nothing.
+fn sync_parent_dir(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        let parent = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        fs::File::open(parent)
+            .and_then(|dir| dir.sync_all())
+            .with_context(|| format!("Failed to flush directory {:?}", parent))?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+/// Move the finished file `from` to `path`, replacing the file there, if any.
+///
+/// Windows refuses to rename over a file another program holds open, e.g. a virus scanner.
+/// There the old file is moved aside first, and put back when `from` cannot take its place.
+///
+/// # Errors
+///
+/// Returns an error when `from` cannot be moved; `path` then keeps its previous content.
+pub fn replace_file(from: &Path, path: &Path) -> Result<()> {
+    let Err(err) = fs::rename(from, path) else {
+        return Ok(());
+    };
+    if !cfg!(windows) || !path.exists() {
+        return Err(err).with_context(|| format!("Failed to replace {:?}", path));
+    }
+    let mut aside = path.as_os_str().to_owned();
+    aside.push(".old");
+    let aside = PathBuf::from(aside);
+    fs::rename(path, &aside).with_context(|| format!("Failed to replace {:?}", path))?;
+    if let Err(err) = fs::rename(from, path) {
+        let _ = fs::rename(&aside, path);
+        return Err(err).with_context(|| format!("Failed to replace {:?}", path));
+    }
+    let _ = fs::remove_file(&aside);
+    Ok(())
+}
+
+/// Bytes the current user may still write to the file system holding `dir`, or `None` when
+/// the platform cannot tell.
+pub fn available_space(dir: &Path) -> Option<u64> {
+    #[cfg(unix)]
+    {
+        let stats = rustix::fs::statvfs(dir).ok()?;
+        Some(stats.f_bavail.saturating_mul(stats.f_frsize))
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+        let wide: Vec<u16> = dir.as_os_str().encode_wide().chain(Some(0)).collect();
+        let mut available = 0u64;
+        // SAFETY: `wide` is NUL-terminated and outlives the call; the totals are not wanted.
+        let ok = unsafe {
+            GetDiskFreeSpaceExW(
+                wide.as_ptr(),
+                &mut available,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            )
+        };
+        (ok != 0).then_some(available)
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = dir;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StorageKind, available_space, resolve_app_dir, write_atomic};
+    use std::collections::HashMap;
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+
+    fn env_from(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<OsString> {
+        let map: HashMap<String, OsString> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), OsString::from(v)))
+            .collect();
+        move |key| map.get(key).cloned()
+    }
+
+    #[test]
+    fn linux_prefers_xdg_and_falls_back_to_home() {
+        let env = env_from(&[("HOME", "/home/u"), ("XDG_DATA_HOME", "/data")]);
+        assert_eq!(
+            resolve_app_dir(StorageKind::Data, "linux", &env),
+            Some(PathBuf::from("/data/elnpack"))
+        );
+
+        assert_eq!(
+            resolve_app_dir(StorageKind::Config, "linux", &env),
+            Some(PathBuf::from("/home/u/.config/elnpack"))
+        );
+
+        let env = env_from(&[("HOME", "/home/u")]);
+        assert_eq!(
+            resolve_app_dir(StorageKind::Data, "linux", &env),
+            Some(PathBuf::from("/home/u/.local/share/elnpack"))
+        );
+    }
+
+    #[test]
+    fn relative_xdg_values_are_ignored() {
+        let env = env_from(&[("HOME", "/home/u"), ("XDG_DATA_HOME", "relative")]);
+        assert_eq!(
+            resolve_app_dir(StorageKind::Data, "linux", &env),
+            Some(PathBuf::from("/home/u/.local/share/elnpack"))
+        );
+    }
+
+    #[test]
+    fn macos_uses_application_support() {
+        let env = env_from(&[("HOME", "/Users/u")]);
+        assert_eq!(
+            resolve_app_dir(StorageKind::Data, "macos", &env),
+            Some(PathBuf::from(
+                "/Users/u/Library/Application Support/elnpack"
+            ))
+        );
+    }
+
+    #[test]
+    fn missing_home_yields_none() {
+        let env = env_from(&[]);
+        assert_eq!(resolve_app_dir(StorageKind::Data, "linux", &env), None);
+    }
+
+    #[test]
+    fn write_atomic_replaces_content_and_leaves_no_temp_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("nested").join("state.json");
+
+        write_atomic(&path, b"one").unwrap();
+        write_atomic(&path, b"two").unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), b"two");
+        assert!(!tmp.path().join("nested").join("state.json.tmp").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_private_creates_an_owner_only_file_despite_a_stale_temp_file() {
+        use super::write_private;
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("secret");
+        std::fs::write(tmp.path().join("secret.tmp"), b"stale").unwrap();
+
+        write_private(&path, b"key").unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), b"key");
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(!tmp.path().join("secret.tmp").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn available_space_is_known_for_existing_directories() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(available_space(tmp.path()).is_some_and(|bytes| bytes > 0));
+        assert_eq!(available_space(&tmp.path().join("missing")), None);
+    }
+}
@@ -0,0 +1,337 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! SVG rasterization for thumbnails and previews, guarded against hostile files.
+//!
+//! SVG attachments come from anywhere, so before `usvg` sees one it has to be small enough,
+//! free of nested entity declarations (the "billion laughs" pattern), and below a node limit.
+//! External files and embedded SVG images are never loaded, the output never exceeds the
+//! requested size whatever the document declares, and rendering that takes longer than a
+//! time budget is given up. A rendering cannot be interrupted, so one past its budget keeps
+//! running in the background; at most [`MAX_SVG_RENDERS`] run at once, abandoned ones included.
+
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex, mpsc};
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use resvg::tiny_skia::Pixmap;
+use resvg::usvg::{self, ImageHrefResolver, ImageKind, Options, Transform, Tree, roxmltree};
+
+/// Largest SVG file that is rendered.
+pub const MAX_SVG_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Most XML nodes an SVG may have after entities are expanded.
+const MAX_SVG_NODES: u32 = 200_000;
+
+/// Time a rendering may take before it is given up.
+pub const SVG_RENDER_BUDGET: Duration = Duration::from_secs(3);
+
+/// Most renderings that run at once, including ones given up that are still finishing.
+pub const MAX_SVG_RENDERS: usize = 2;
+
+/// Render threads of the application.
+static RENDER_SLOTS: RenderSlots = RenderSlots::new(MAX_SVG_RENDERS);
+
+/// Why an SVG was not rendered.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SvgError {
+    /// The file exceeds [`MAX_SVG_BYTES`].
+    TooLarge(u64),
+    /// The document declares entities that expand other entities.
+    NestedEntities,
+    /// Rendering did not finish within the time budget.
+    TimedOut,
+    /// Earlier renderings still occupy every render thread.
+    Busy,
+    /// The document is not a valid SVG or has no drawable size.
+    Invalid(String),
+}
+
+impl SvgError {
+    /// Whether the file itself is refused, so rendering it again fails the same way.
+    pub fn is_refused(&self) -> bool {
+        !matches!(self, Self::Invalid(_) | Self::Busy)
+    }
+}
+
+impl fmt::Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLarge(size) => write!(
+                f,
+                "SVG is {:.1} MB; only files up to {} MB are rendered",
+                *size as f64 / (1024.0 * 1024.0),
+                MAX_SVG_BYTES / (1024 * 1024)
+            ),
+            Self::NestedEntities => f.write_str("SVG declares nested XML entities"),
+            Self::TimedOut => write!(
+                f,
+                "SVG took longer than {} s to render",
+                SVG_RENDER_BUDGET.as_secs()
+            ),
+            Self::Busy => f.write_str("other SVG files are still being rendered"),
+            Self::Invalid(reason) => f.write_str(reason),
+        }
+    }
+}
+
+/// Render `bytes` to fit within `max_side` pixels per side, keeping the aspect ratio.
+///
+/// # Errors
+///
+/// Returns an [`SvgError`] when the file is refused by one of the guards, is not a valid
+/// SVG, takes longer than [`SVG_RENDER_BUDGET`], or finds no free render thread within it.
+pub fn render_svg(bytes: Vec<u8>, max_side: u32) -> Result<egui::ColorImage, SvgError> {
+    let size = bytes.len() as u64;
+    if size > MAX_SVG_BYTES {
+        return Err(SvgError::TooLarge(size));
+    }
+    run_within(&RENDER_SLOTS, SVG_RENDER_BUDGET, move || {
+        render_unbounded(&bytes, max_side)
+    })
+}
+
+/// Run `render` on a thread of `slots` and wait at most `budget` for its result, including
+/// the wait for a free thread.
+fn run_within<T: Send + 'static>(
+    slots: &'static RenderSlots,
+    budget: Duration,
+    render: impl FnOnce() -> Result<T, SvgError> + Send + 'static,
+) -> Result<T, SvgError> {
+    let deadline = Instant::now() + budget;
+    let slot = slots.acquire(deadline).ok_or(SvgError::Busy)?;
+    let (tx, rx) = mpsc::channel();
+    // A rendering past its budget cannot be stopped; it keeps its slot until it finishes, and
+    // its result is dropped.
+    std::thread::spawn(move || {
+        let _slot = slot;
+        let _ = tx.send(render());
+    });
+    rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+        .unwrap_or(Err(SvgError::TimedOut))
+}
+
+/// Counting semaphore that bounds the number of render threads.
+struct RenderSlots {
+    free: Mutex<usize>,
+    released: Condvar,
+}
+
+impl RenderSlots {
+    const fn new(count: usize) -> Self {
+        Self {
+            free: Mutex::new(count),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Take a slot, waiting for one until `deadline`; `None` when none became free.
+    fn acquire(&'static self, deadline: Instant) -> Option<RenderSlot> {
+        let mut free = self.free.lock().unwrap();
+        while *free == 0 {
+            let timeout = deadline.checked_duration_since(Instant::now())?;
+            free = self.released.wait_timeout(free, timeout).unwrap().0;
+        }
+        *free -= 1;
+        Some(RenderSlot(self))
+    }
+}
+
+/// Slot held by a render thread; returned to its [`RenderSlots`] on drop.
+struct RenderSlot(&'static RenderSlots);
+
+impl Drop for RenderSlot {
+    fn drop(&mut self) {
+        *self.0.free.lock().unwrap() += 1;
+        self.0.released.notify_one();
+    }
+}
+
+fn render_unbounded(bytes: &[u8], max_side: u32) -> Result<egui::ColorImage, SvgError> {
+    let decompressed;
+    let bytes = if bytes.starts_with(&[0x1f, 0x8b]) {
+        decompressed =
+            usvg::decompress_svgz(bytes).map_err(|e| SvgError::Invalid(e.to_string()))?;
+        if decompressed.len() as u64 > MAX_SVG_BYTES {
+            return Err(SvgError::TooLarge(decompressed.len() as u64));
+        }
+        &decompressed[..]
+    } else {
+        bytes
+    };
+    let text = std::str::from_utf8(bytes)
+        .map_err(|_| SvgError::Invalid("SVG is not valid UTF-8".into()))?;
+    if has_nested_entities(text) {
+        return Err(SvgError::NestedEntities);
+    }
+    let xml = roxmltree::Document::parse_with_options(
+        text,
+        roxmltree::ParsingOptions {
+            allow_dtd: true,
+            nodes_limit: MAX_SVG_NODES,
+        },
+    )
+    .map_err(|e| SvgError::Invalid(e.to_string()))?;
+    let tree = Tree::from_xmltree(&xml, &guarded_options())
+        .map_err(|e| SvgError::Invalid(e.to_string()))?;
+
+    // The output size follows from `max_side` alone, never from the declared canvas.
+    let (source_w, source_h) = (tree.size().width(), tree.size().height());
+    let scale = (max_side as f32 / source_w).min(max_side as f32 / source_h);
+    let width = (source_w * scale).round().clamp(1.0, max_side as f32) as u32;
+    let height = (source_h * scale).round().clamp(1.0, max_side as f32) as u32;
+    let mut pixmap = Pixmap::new(width, height)
+        .ok_or_else(|| SvgError::Invalid(format!("SVG has an invalid size {width}x{height}")))?;
+    resvg::render(
+        &tree,
+        Transform::from_scale(width as f32 / source_w, height as f32 / source_h),
+        &mut pixmap.as_mut(),
+    );
+    Ok(egui::ColorImage::from_rgba_premultiplied(
+        [width as usize, height as usize],
+        pixmap.data(),
+    ))
+}
+
+/// Options that load no files and only embedded raster images.
+fn guarded_options() -> Options<'static> {
+    Options {
+        resources_dir: None,
+        image_href_resolver: ImageHrefResolver {
+            resolve_data: Box::new(|mime: &str, data: Arc<Vec<u8>>, _: &Options| {
+                let format = match mime {
+                    "image/png" => image::ImageFormat::Png,
+                    "image/jpg" | "image/jpeg" => image::ImageFormat::Jpeg,
+                    "image/gif" => image::ImageFormat::Gif,
+                    "image/webp" => image::ImageFormat::WebP,
+                    "text/plain" => image::guess_format(&data).ok()?,
+                    _ => return None,
+                };
+                match format {
+                    image::ImageFormat::Png => Some(ImageKind::PNG(data)),
+                    image::ImageFormat::Jpeg => Some(ImageKind::JPEG(data)),
+                    image::ImageFormat::Gif => Some(ImageKind::GIF(data)),
+                    image::ImageFormat::WebP => Some(ImageKind::WEBP(data)),
+                    _ => None,
+                }
+            }),
+            resolve_string: Box::new(|_: &str, _: &Options| None),
+        },
+        ..Options::default()
+    }
+}
+
+/// Whether an `<!ENTITY …>` declaration refers to another entity, which is how small files
+/// expand into gigabytes. Plain string entities, as some editors write them, are fine.
+fn has_nested_entities(text: &str) -> bool {
+    text.split("<!ENTITY").skip(1).any(|declaration| {
+        let declaration = declaration.split('>').next().unwrap_or_default();
+        declaration.contains('&') || declaration.contains('%')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn huge_declared_canvas_renders_within_the_requested_size() {
+        let svg = "<svg xmlns='http://www.w3.org/2000/svg' width='100000000' \
+                   height='50000000'><rect width='100000000' height='50000000' fill='red'/></svg>";
+        let started = Instant::now();
+        let image = render_svg(svg.as_bytes().to_vec(), 256).unwrap();
+        assert_eq!(image.size, [256, 128]);
+        assert!(started.elapsed() < SVG_RENDER_BUDGET);
+
+        let sliver = "<svg xmlns='http://www.w3.org/2000/svg' width='100000000' height='1'/>";
+        assert_eq!(
+            render_svg(sliver.as_bytes().to_vec(), 256).unwrap().size,
+            [256, 1]
+        );
+    }
+
+    #[test]
+    fn entity_expansion_is_refused_before_parsing() {
+        let mut svg = String::from("<?xml version='1.0'?>\n<!DOCTYPE svg [\n<!ENTITY lol 'lol'>\n");
+        for level in 1..=9 {
+            let previous = if level == 1 {
+                "lol".to_string()
+            } else {
+                format!("lol{}", level - 1)
+            };
+            svg.push_str(&format!(
+                "<!ENTITY lol{level} '{}'>\n",
+                format!("&{previous};").repeat(10)
+            ));
+        }
+        svg.push_str("]>\n<svg xmlns='http://www.w3.org/2000/svg'><text>&lol9;</text></svg>");
+
+        let started = Instant::now();
+        assert_eq!(
+            render_svg(svg.into_bytes(), 256).unwrap_err(),
+            SvgError::NestedEntities
+        );
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        // Flat entities, as written by some editors, still render.
+        let flat = "<!DOCTYPE svg [<!ENTITY ns_svg 'http://www.w3.org/2000/svg'>]>\
+                    <svg xmlns='&ns_svg;' width='4' height='4'><rect width='4' height='4'/></svg>";
+        assert!(render_svg(flat.as_bytes().to_vec(), 16).is_ok());
+    }
+
+    #[test]
+    fn oversized_files_are_refused() {
+        let big = vec![b' '; MAX_SVG_BYTES as usize + 1];
+        assert!(matches!(
+            render_svg(big, 256),
+            Err(SvgError::TooLarge(size)) if size == MAX_SVG_BYTES + 1
+        ));
+    }
+
+    #[test]
+    fn slow_renderings_time_out_but_hold_their_thread_until_done() {
+        static SLOTS: RenderSlots = RenderSlots::new(1);
+        let budget = Duration::from_millis(50);
+        let (release, blocked) = mpsc::channel::<()>();
+        let (finished, done) = mpsc::channel();
+        let slow = move || {
+            blocked.recv().unwrap();
+            finished.send(()).unwrap();
+            Ok(())
+        };
+        assert_eq!(
+            run_within(&SLOTS, budget, slow).unwrap_err(),
+            SvgError::TimedOut
+        );
+
+        // The abandoned rendering still occupies the only thread.
+        assert_eq!(
+            run_within(&SLOTS, budget, || Ok(())).unwrap_err(),
+            SvgError::Busy
+        );
+
+        release.send(()).unwrap();
+        done.recv().unwrap();
+        assert_eq!(run_within(&SLOTS, SVG_RENDER_BUDGET, || Ok(7)), Ok(7));
+    }
+
+    #[test]
+    fn external_images_are_not_loaded() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let png = dir.path().join("secret.png");
+        image::RgbaImage::from_pixel(4, 4, image::Rgba([255, 0, 0, 255]))
+            .save(&png)
+            .unwrap();
+        let svg = format!(
+            "<svg xmlns='http://www.w3.org/2000/svg' xmlns:xlink='http://www.w3.org/1999/xlink' \
+             width='4' height='4'><image width='4' height='4' xlink:href='{}'/></svg>",
+            png.display()
+        );
+
+        let image = render_svg(svg.into_bytes(), 4).unwrap();
+        assert!(image.pixels.iter().all(|p| p.a() == 0));
+    }
+}
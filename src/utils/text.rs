@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Length limits for user text shown in compact widgets.
+
+/// Shorten `text` to at most `max_chars` characters, ending in `…` when cut.
+///
+/// Counts Unicode scalar values, so multi-byte characters are never split.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use elnpack::utils::text::ellipsize;
+///
+/// assert_eq!(ellipsize("short", 10), "short");
+/// assert_eq!(ellipsize("Gel electrophoresis", 8), "Gel ele…");
+/// ```
+pub fn ellipsize(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let head: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    format!("{}…", head.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ellipsize;
+
+    #[test]
+    fn ellipsize_keeps_text_at_the_limit_and_cuts_beyond_it() {
+        assert_eq!(ellipsize("abcde", 5), "abcde");
+        assert_eq!(ellipsize("abcdef", 5), "abcd…");
+        assert_eq!(ellipsize("Ångström", 3), "Ån…");
+        assert_eq!(ellipsize("ab cd", 4), "ab…");
+        assert_eq!(ellipsize("", 0), "");
+    }
+}
@@ -0,0 +1,224 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! End-to-end tests of the headless `elnpack pack` and `elnpack verify-signature` commands.
+
+use std::fs::{self, File};
+use std::io::Read;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use tempfile::TempDir;
+use zip::ZipArchive;
+
+/// Read `ro-crate-metadata.json` from the archive at `path`.
+fn metadata(path: &std::path::Path) -> serde_json::Value {
+    let mut archive = ZipArchive::new(File::open(path).unwrap()).unwrap();
+    let name = (0..archive.len())
+        .map(|i| archive.by_index(i).unwrap().name().to_string())
+        .find(|name| name.ends_with("/ro-crate-metadata.json"))
+        .expect("metadata file present");
+    let mut json = String::new();
+    archive
+        .by_name(&name)
+        .unwrap()
+        .read_to_string(&mut json)
+        .unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn pack_writes_archive_with_attachments_keywords_and_metadata() {
+    let tmp = TempDir::new().unwrap();
+    let body = tmp.path().join("body.md");
+    fs::write(&body, "# Run 7\n\nAll good.").unwrap();
+    let data = tmp.path().join("run7");
+    fs::create_dir_all(data.join("raw")).unwrap();
+    fs::write(data.join("raw/plate1.csv"), "a,b\n1,2\n").unwrap();
+    let notes = tmp.path().join("notes.txt");
+    fs::write(&notes, "notes").unwrap();
+    let extra = tmp.path().join("extra.json");
+    fs::write(
+        &extra,
+        r#"{"extra_fields": {"Detector": {"type": "text", "value": "Pilatus"}}}"#,
+    )
+    .unwrap();
+    let output = tmp.path().join("out");
+
+    let assert = cargo_bin_cmd!("elnpack")
+        .args(["pack", "--title", "Gel run", "--keyword", "SDS-PAGE"])
+        .arg("--body")
+        .arg(&body)
+        .arg("--attach")
+        .arg(&notes)
+        .arg("--attach")
+        .arg(&data)
+        .arg("--metadata")
+        .arg(&extra)
+        .args(["--performed-at", "2025-03-14T09:30:00+01:00"])
+        .arg("--output")
+        .arg(&output)
+        .assert()
+        .success();
+
+    let written = tmp.path().join("out.eln");
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert_eq!(stdout.trim(), written.display().to_string());
+
+    let meta = metadata(&written);
+    let graph = meta["@graph"].as_array().unwrap();
+    let entry = graph
+        .iter()
+        .find(|node| node["@id"] == "./experiment/")
+        .unwrap();
+    assert_eq!(entry["name"], "Gel run");
+    assert_eq!(entry["dateCreated"], "2025-03-14T09:30:00+01:00");
+    assert_eq!(entry["keywords"], serde_json::json!(["SDS-PAGE"]));
+    for id in ["./experiment/notes.txt", "./experiment/run7/raw/plate1.csv"] {
+        assert!(graph.iter().any(|node| node["@id"] == id), "{id} missing");
+    }
+    assert!(
+        graph
+            .iter()
+            .any(|node| node["propertyID"] == "Detector" && node["value"] == "Pilatus")
+    );
+}
+
+#[test]
+fn pack_reports_invalid_input_with_exit_code_2() {
+    let tmp = TempDir::new().unwrap();
+    let first = tmp.path().join("a");
+    let second = tmp.path().join("b");
+    for dir in [&first, &second] {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("data.csv"), dir.display().to_string()).unwrap();
+    }
+    let output = tmp.path().join("dup.eln");
+
+    let assert = cargo_bin_cmd!("elnpack")
+        .args(["pack", "--title", "Duplicates"])
+        .arg("--attach")
+        .arg(first.join("data.csv"))
+        .arg("--attach")
+        .arg(second.join("data.csv"))
+        .arg("--output")
+        .arg(&output)
+        .assert()
+        .code(2);
+    let stderr = String::from_utf8(assert.get_output().stderr.clone()).unwrap();
+    assert!(stderr.starts_with("elnpack: "), "{stderr}");
+    assert!(!output.exists());
+
+    cargo_bin_cmd!("elnpack")
+        .args(["pack", "--title", "   "])
+        .arg("--output")
+        .arg(&output)
+        .assert()
+        .code(2);
+    cargo_bin_cmd!("elnpack")
+        .args(["pack", "--title", "Bad date", "--performed-at", "yesterday"])
+        .arg("--output")
+        .arg(&output)
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn pack_refuses_to_overwrite_without_force_and_fails_on_missing_files() {
+    let tmp = TempDir::new().unwrap();
+    let output = tmp.path().join("existing.eln");
+    fs::write(&output, "keep me").unwrap();
+
+    cargo_bin_cmd!("elnpack")
+        .args(["pack", "--title", "Run"])
+        .arg("--output")
+        .arg(&output)
+        .assert()
+        .code(2);
+    assert_eq!(fs::read_to_string(&output).unwrap(), "keep me");
+
+    cargo_bin_cmd!("elnpack")
+        .args(["pack", "--title", "Run", "--force"])
+        .arg("--output")
+        .arg(&output)
+        .assert()
+        .success();
+
+    cargo_bin_cmd!("elnpack")
+        .args(["pack", "--title", "Run", "--force"])
+        .arg("--attach")
+        .arg(tmp.path().join("missing.csv"))
+        .arg("--output")
+        .arg(&output)
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn verify_signature_accepts_only_the_trusted_key_and_rejects_tampering() {
+    use ssh_key::rand_core::OsRng;
+    use ssh_key::{Algorithm, HashAlg, LineEnding, PrivateKey};
+
+    let tmp = TempDir::new().unwrap();
+    let archive = tmp.path().join("signed.eln");
+    cargo_bin_cmd!("elnpack")
+        .args(["pack", "--title", "Signed"])
+        .arg("--output")
+        .arg(&archive)
+        .assert()
+        .success();
+
+    let key = PrivateKey::random(&mut OsRng, Algorithm::Ed25519).unwrap();
+    let public = tmp.path().join("key.pub");
+    key.public_key().write_openssh_file(&public).unwrap();
+    let signature = key
+        .sign("file", HashAlg::Sha512, &fs::read(&archive).unwrap())
+        .unwrap();
+    let sidecar = tmp.path().join("signed.eln.sig");
+    fs::write(&sidecar, signature.to_pem(LineEnding::LF).unwrap()).unwrap();
+
+    let assert = cargo_bin_cmd!("elnpack")
+        .arg("verify-signature")
+        .arg(&archive)
+        .arg("--key")
+        .arg(&public)
+        .assert()
+        .success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    let fingerprint = key.public_key().fingerprint(HashAlg::Sha256).to_string();
+    assert!(stdout.contains(&fingerprint), "{stdout}");
+
+    let assert = cargo_bin_cmd!("elnpack")
+        .arg("verify-signature")
+        .arg(&archive)
+        .assert()
+        .code(3);
+    let stderr = String::from_utf8(assert.get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("untrusted key"), "{stderr}");
+    assert!(stderr.contains(&fingerprint), "{stderr}");
+    assert!(assert.get_output().stdout.is_empty());
+
+    let other = PrivateKey::random(&mut OsRng, Algorithm::Ed25519).unwrap();
+    let other_public = tmp.path().join("other.pub");
+    other
+        .public_key()
+        .write_openssh_file(&other_public)
+        .unwrap();
+    cargo_bin_cmd!("elnpack")
+        .arg("verify-signature")
+        .arg(&archive)
+        .arg("--key")
+        .arg(&other_public)
+        .assert()
+        .code(1);
+
+    let mut bytes = fs::read(&archive).unwrap();
+    bytes.extend_from_slice(b"tampered");
+    fs::write(&archive, bytes).unwrap();
+    let assert = cargo_bin_cmd!("elnpack")
+        .arg("verify-signature")
+        .arg(&archive)
+        .assert()
+        .code(1);
+    let stderr = String::from_utf8(assert.get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("does not match"), "{stderr}");
+}
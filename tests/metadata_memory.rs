@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: 2025 Alexander Minges
+
+//! Memory needed to write the RO-Crate metadata of large entries.
+//!
+//! Allocations are counted by a global allocator, which replaces the allocator of the whole
+//! test binary; these tests therefore live in a binary of their own.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fs::File;
+use std::io::Read;
+
+use elnpack::logic::eln::{CrateMetadata, write_metadata};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+thread_local! {
+    static TRACKING: Cell<bool> = const { Cell::new(false) };
+    static LIVE: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+}
+
+/// System allocator that records allocations made on threads inside [`peak_during`].
+struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size() as isize);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(-(layout.size() as isize));
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size as isize - layout.size() as isize);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+fn record(delta: isize) {
+    let _ = TRACKING.try_with(|tracking| {
+        if tracking.get() {
+            let live = LIVE.get() + delta;
+            LIVE.set(live);
+            PEAK.set(PEAK.get().max(live));
+        }
+    });
+}
+
+/// Run `f` and return its result with the most bytes it held allocated at once.
+fn peak_during<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    LIVE.set(0);
+    PEAK.set(0);
+    TRACKING.set(true);
+    let result = f();
+    TRACKING.set(false);
+    (result, PEAK.get() as usize)
+}
+
+#[test]
+fn metadata_of_large_entries_is_streamed_without_buffering() {
+    let context = "https://w3id.org/ro/crate/1.2/context";
+    let fields: Vec<Value> = (0..10_000)
+        .map(|i| {
+            json!({
+                "@id": format!("pv://field-{i}"),
+                "@type": "PropertyValue",
+                "propertyID": format!("Channel {i}"),
+                "value": i * 3,
+                "unitText": "mV",
+                "description": format!("Reading of detector channel {i}"),
+            })
+        })
+        .collect();
+    let root = json!({"@id": "./", "@type": "Dataset"});
+    let metadata = CrateMetadata {
+        context,
+        graph: &[std::slice::from_ref(&root), &fields],
+    };
+    let mut graph = vec![root.clone()];
+    graph.extend(fields.iter().cloned());
+    let buffered =
+        serde_json::to_vec_pretty(&json!({"@context": context, "@graph": graph})).unwrap();
+
+    let tmp = TempDir::new().unwrap();
+    let path = tmp.path().join("large.eln");
+    let mut zip = ZipWriter::new(File::create(&path).unwrap());
+    zip.start_file("large/ro-crate-metadata.json", SimpleFileOptions::default())
+        .unwrap();
+    let (written, peak) = peak_during(|| write_metadata(&mut zip, &metadata));
+    written.unwrap();
+    zip.finish().unwrap();
+    assert!(
+        peak < buffered.len() / 10,
+        "{peak} bytes allocated to write {} bytes",
+        buffered.len()
+    );
+
+    let mut archive = zip::ZipArchive::new(File::open(&path).unwrap()).unwrap();
+    let mut streamed = Vec::new();
+    archive
+        .by_name("large/ro-crate-metadata.json")
+        .unwrap()
+        .read_to_end(&mut streamed)
+        .unwrap();
+    assert_eq!(
+        serde_json::from_slice::<Value>(&streamed).unwrap(),
+        serde_json::from_slice::<Value>(&buffered).unwrap()
+    );
+}